@@ -0,0 +1,3 @@
+fn main() {
+    std::process::exit(ai_toolbox_lib::cli::run())
+}
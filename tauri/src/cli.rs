@@ -0,0 +1,497 @@
+//! 命令行入口（`ai-toolbox-cli`）
+//!
+//! 不创建窗口，直接打开桌面应用使用的同一个数据目录与 SurrealDB，供脚本、SSH 远程
+//! 或 CI 中切换 provider、触发同步和备份。`--json` 输出机器可读的结果。
+//!
+//! SurrealKV 不支持多进程同时打开，运行期间会占用与桌面应用相同的单实例锁，桌面应用需先退出。
+
+use std::path::{Path, PathBuf};
+
+use chrono::Local;
+use clap::{Parser, Subcommand, ValueEnum};
+use serde_json::{json, Value};
+use surrealdb::engine::local::SurrealKv;
+use surrealdb::Surreal;
+
+use crate::coding::{self, claude_code, codex, ssh};
+use crate::settings::backup::{crypto, utils as backup_utils};
+use crate::{secret_vault, single_instance, DbState};
+
+/// 与 tauri.conf.json 中的 identifier 一致，决定默认数据目录
+const APP_IDENTIFIER: &str = "com.ai-toolbox";
+
+#[derive(Debug, Parser)]
+#[command(
+    name = "ai-toolbox-cli",
+    version,
+    about = "Switch providers, run syncs and backups without opening the AI Toolbox window"
+)]
+struct Cli {
+    /// App data directory (defaults to the desktop app's data directory)
+    #[arg(long, global = true, env = "AI_TOOLBOX_DATA_DIR")]
+    data_dir: Option<PathBuf>,
+
+    /// Print results as JSON
+    #[arg(long, global = true)]
+    json: bool,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Claude Code / Codex providers
+    Providers {
+        #[command(subcommand)]
+        command: ProvidersCommand,
+    },
+    /// MCP servers
+    Mcp {
+        #[command(subcommand)]
+        command: SyncCommand,
+    },
+    /// Managed skills
+    Skills {
+        #[command(subcommand)]
+        command: SyncCommand,
+    },
+    /// SSH remote sync
+    Ssh {
+        #[command(subcommand)]
+        command: SshCommand,
+    },
+    /// Local backups
+    Backup {
+        #[command(subcommand)]
+        command: BackupCommand,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+enum ProvidersCommand {
+    /// List providers
+    List {
+        /// Only list providers of this tool
+        #[arg(long, value_enum)]
+        tool: Option<Tool>,
+    },
+    /// Apply a provider to the tool's config files
+    Apply {
+        #[arg(value_enum)]
+        tool: Tool,
+        /// Provider id
+        id: String,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+enum SyncCommand {
+    /// Sync to all enabled tools
+    Sync,
+}
+
+#[derive(Debug, Subcommand)]
+enum SshCommand {
+    /// Sync file mappings, MCP and skills to all SSH targets
+    Sync {
+        /// Only sync file mappings of this module
+        #[arg(long)]
+        module: Option<String>,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+enum BackupCommand {
+    /// Create a backup archive in the given directory
+    Create {
+        dir: PathBuf,
+        /// Encrypt the archive with this passphrase
+        #[arg(long, env = "AI_TOOLBOX_BACKUP_PASSPHRASE")]
+        passphrase: Option<String>,
+    },
+    /// Restore a backup archive (the app resyncs skills and MCP on next start)
+    Restore {
+        file: PathBuf,
+        /// Passphrase of an encrypted archive
+        #[arg(long, env = "AI_TOOLBOX_BACKUP_PASSPHRASE")]
+        passphrase: Option<String>,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Tool {
+    Claude,
+    Codex,
+}
+
+impl Tool {
+    fn key(self) -> &'static str {
+        match self {
+            Tool::Claude => "claude",
+            Tool::Codex => "codex",
+        }
+    }
+}
+
+/// 命令执行结果：`data` 用于 `--json`，`lines` 用于终端输出
+struct Report {
+    data: Value,
+    lines: Vec<String>,
+}
+
+/// Parse arguments, run the command and return the process exit code
+pub fn run() -> i32 {
+    let cli = Cli::parse();
+    let json_output = cli.json;
+
+    let runtime = match tokio::runtime::Runtime::new() {
+        Ok(runtime) => runtime,
+        Err(e) => {
+            print_error(json_output, &format!("Failed to start runtime: {}", e));
+            return 1;
+        }
+    };
+
+    match runtime.block_on(execute(cli)) {
+        Ok(report) => {
+            if json_output {
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&report.data).unwrap_or_default()
+                );
+            } else {
+                for line in report.lines {
+                    println!("{}", line);
+                }
+            }
+            0
+        }
+        Err(e) => {
+            print_error(json_output, &e);
+            1
+        }
+    }
+}
+
+fn print_error(json_output: bool, message: &str) {
+    if json_output {
+        println!("{}", json!({ "success": false, "error": message }));
+    } else {
+        eprintln!("Error: {}", message);
+    }
+}
+
+/// 默认数据目录，与 Tauri 的 `app_data_dir()` 相同
+fn default_data_dir() -> Result<PathBuf, String> {
+    dirs::data_dir()
+        .map(|dir| dir.join(APP_IDENTIFIER))
+        .ok_or_else(|| "Failed to resolve app data dir, pass --data-dir".to_string())
+}
+
+async fn execute(cli: Cli) -> Result<Report, String> {
+    let data_dir = match cli.data_dir {
+        Some(dir) => dir,
+        None => default_data_dir()?,
+    };
+
+    // 与桌面应用互斥，避免两个进程同时写数据库
+    let _lock = single_instance::try_acquire_lock()
+        .map_err(|e| format!("{}. Quit the AI Toolbox app first", e))?;
+
+    init_stores(&data_dir);
+
+    // 备份直接读写数据目录，不打开数据库
+    match cli.command {
+        Command::Backup { command } => run_backup(&data_dir, command),
+        Command::Providers { command } => {
+            run_providers(&open_state(&data_dir).await?, command).await
+        }
+        Command::Mcp {
+            command: SyncCommand::Sync,
+        } => sync_mcp(&open_state(&data_dir).await?).await,
+        Command::Skills {
+            command: SyncCommand::Sync,
+        } => sync_skills(&open_state(&data_dir).await?).await,
+        Command::Ssh {
+            command: SshCommand::Sync { module },
+        } => sync_ssh(&open_state(&data_dir).await?, module.as_deref()).await,
+    }
+}
+
+/// 打开数据库并加载与数据库相关的运行期设置
+async fn open_state(data_dir: &Path) -> Result<DbState, String> {
+    let state = open_db(data_dir).await?;
+    coding::config_history::load_retention(&state.db()).await;
+    Ok(state)
+}
+
+/// 初始化基于文件的存储目录（与 `run()` 的 setup 保持一致）
+fn init_stores(data_dir: &Path) {
+    coding::open_code::free_models::set_cache_dir(data_dir.to_path_buf());
+    coding::preset_models::set_cache_dir(data_dir.to_path_buf());
+    ssh::known_hosts::set_store_dir(data_dir.to_path_buf());
+    ssh::sync_state::set_store_dir(data_dir.to_path_buf());
+    secret_vault::set_store_dir(data_dir.to_path_buf());
+    coding::config_history::set_store_dir(data_dir.to_path_buf());
+    coding::skills::central_repo::set_app_data_dir(data_dir.to_path_buf());
+}
+
+async fn open_db(data_dir: &Path) -> Result<DbState, String> {
+    let db_path = data_dir.join("database");
+    if !db_path.exists() {
+        return Err(format!(
+            "Database not found at {}, start the app once or pass --data-dir",
+            db_path.display()
+        ));
+    }
+
+    let db = Surreal::new::<SurrealKv>(db_path)
+        .await
+        .map_err(|e| format!("Failed to open database: {}", e))?;
+    db.use_ns("ai_toolbox")
+        .use_db("main")
+        .await
+        .map_err(|e| format!("Failed to select namespace and database: {}", e))?;
+    Ok(DbState(db))
+}
+
+// ============================================================================
+// Providers
+// ============================================================================
+
+async fn run_providers(state: &DbState, command: ProvidersCommand) -> Result<Report, String> {
+    let db = state.db();
+
+    match command {
+        ProvidersCommand::List { tool } => {
+            let mut rows: Vec<(Tool, Value)> = vec![];
+            if tool != Some(Tool::Codex) {
+                for p in claude_code::list_providers_internal(&db).await? {
+                    rows.push((
+                        Tool::Claude,
+                        provider_summary(&p.id, &p.name, &p.category, p.is_applied, p.is_disabled),
+                    ));
+                }
+            }
+            if tool != Some(Tool::Claude) {
+                for p in codex::list_providers_internal(&db).await? {
+                    rows.push((
+                        Tool::Codex,
+                        provider_summary(&p.id, &p.name, &p.category, p.is_applied, p.is_disabled),
+                    ));
+                }
+            }
+
+            let lines = rows
+                .iter()
+                .map(|(tool, p)| {
+                    let mut flags = vec![];
+                    if p["isApplied"] == true {
+                        flags.push("applied");
+                    }
+                    if p["isDisabled"] == true {
+                        flags.push("disabled");
+                    }
+                    format!(
+                        "{:<7} {:<24} {}{}",
+                        tool.key(),
+                        p["id"].as_str().unwrap_or_default(),
+                        p["name"].as_str().unwrap_or_default(),
+                        if flags.is_empty() {
+                            String::new()
+                        } else {
+                            format!(" [{}]", flags.join(", "))
+                        }
+                    )
+                })
+                .collect();
+            let data = rows
+                .into_iter()
+                .map(|(tool, mut p)| {
+                    p["tool"] = json!(tool.key());
+                    p
+                })
+                .collect();
+
+            Ok(Report {
+                data: Value::Array(data),
+                lines,
+            })
+        }
+        ProvidersCommand::Apply { tool, id } => {
+            match tool {
+                Tool::Claude => claude_code::apply_provider(&db, &id).await?,
+                Tool::Codex => codex::apply_provider(&db, &id).await?,
+            }
+            Ok(Report {
+                data: json!({ "success": true, "tool": tool.key(), "providerId": id }),
+                lines: vec![format!("Applied {} provider {}", tool.key(), id)],
+            })
+        }
+    }
+}
+
+/// 只输出识别信息，不包含 settings_config 中的密钥
+fn provider_summary(
+    id: &str,
+    name: &str,
+    category: &str,
+    is_applied: bool,
+    is_disabled: bool,
+) -> Value {
+    json!({
+        "id": id,
+        "name": name,
+        "category": category,
+        "isApplied": is_applied,
+        "isDisabled": is_disabled,
+    })
+}
+
+// ============================================================================
+// MCP / Skills
+// ============================================================================
+
+async fn sync_mcp(state: &DbState) -> Result<Report, String> {
+    let results = coding::mcp::commands::sync_all_servers(state).await?;
+
+    let lines = if results.is_empty() {
+        vec!["No MCP servers to sync".to_string()]
+    } else {
+        results
+            .iter()
+            .map(|r| match &r.error_message {
+                Some(e) => format!("{}: failed: {}", r.tool, e),
+                None => format!("{}: ok", r.tool),
+            })
+            .collect()
+    };
+    let success = results.iter().all(|r| r.success);
+    Ok(Report {
+        data: json!({ "success": success, "results": results }),
+        lines,
+    })
+}
+
+async fn sync_skills(state: &DbState) -> Result<Report, String> {
+    let central_dir = coding::skills::central_repo::resolve_default_central_repo_path(state)
+        .await
+        .map_err(|e| format!("{}", e))?;
+    let synced = coding::skills::commands::resync_all_skills(state, &central_dir).await?;
+
+    let mut lines = vec![format!("Synced {} skill targets", synced.len())];
+    lines.extend(synced.iter().map(|s| format!("  {}", s)));
+    Ok(Report {
+        data: json!({ "success": true, "synced": synced }),
+        lines,
+    })
+}
+
+// ============================================================================
+// SSH
+// ============================================================================
+
+async fn sync_ssh(state: &DbState, module: Option<&str>) -> Result<Report, String> {
+    let config = ssh::get_ssh_config_internal(&state.db(), true).await?;
+    if !config.enabled || config.active_connection_id.is_empty() {
+        return Err("SSH sync is not enabled".to_string());
+    }
+
+    // 主连接失败时仍同步其它目标，由 do_full_sync 记录主连接错误
+    let mut session = ssh::SshSession::new();
+    if let Some(conn) = config
+        .connections
+        .iter()
+        .find(|c| c.id == config.active_connection_id)
+    {
+        if let Err(e) = session.connect(conn).await {
+            if config.sync_targets().len() <= 1 {
+                return Err(format!("SSH connection failed: {}", e));
+            }
+        }
+    }
+
+    let result = ssh::do_full_sync(state, None, &session, &config, module, None).await;
+    session.disconnect().await;
+    let _ = ssh::update_sync_status(state, &result).await;
+
+    let mut lines = vec![];
+    if result.hosts.is_empty() {
+        lines.push(format!(
+            "Synced {} files, skipped {}",
+            result.synced_files.len(),
+            result.skipped_files.len()
+        ));
+    }
+    for host in &result.hosts {
+        lines.push(format!(
+            "{}: {} synced, {} skipped, {} errors",
+            host.connection_name,
+            host.result.synced_files.len(),
+            host.result.skipped_files.len(),
+            host.result.errors.len()
+        ));
+    }
+    lines.extend(result.errors.iter().map(|e| format!("  error: {}", e)));
+    lines.extend(
+        result
+            .conflicts
+            .iter()
+            .map(|c| format!("  conflict: {}", c.local_path)),
+    );
+
+    Ok(Report {
+        data: serde_json::to_value(&result).map_err(|e| e.to_string())?,
+        lines,
+    })
+}
+
+// ============================================================================
+// Backup
+// ============================================================================
+
+fn run_backup(data_dir: &Path, command: BackupCommand) -> Result<Report, String> {
+    match command {
+        BackupCommand::Create { dir, passphrase } => {
+            let db_path = data_dir.join("database");
+            if !db_path.exists() {
+                return Err(format!("Database not found at {}", db_path.display()));
+            }
+            std::fs::create_dir_all(&dir)
+                .map_err(|e| format!("Failed to create backup dir: {}", e))?;
+
+            let zip_data = backup_utils::create_backup_zip(
+                &db_path,
+                &data_dir.join("skills"),
+                passphrase.as_deref(),
+            )?;
+            let data = crypto::encrypt_if_enabled(zip_data, passphrase.as_deref())?;
+
+            let timestamp = Local::now().format("%Y%m%d-%H%M%S");
+            let path = dir.join(format!("ai-toolbox-backup-{}.zip", timestamp));
+            std::fs::write(&path, data)
+                .map_err(|e| format!("Failed to write backup file: {}", e))?;
+
+            let path = path.to_string_lossy().to_string();
+            Ok(Report {
+                data: json!({ "success": true, "path": path }),
+                lines: vec![format!("Backup created: {}", path)],
+            })
+        }
+        BackupCommand::Restore { file, passphrase } => {
+            let data =
+                std::fs::read(&file).map_err(|e| format!("Failed to read backup file: {}", e))?;
+            let data = crypto::decrypt_if_needed(data, passphrase.as_deref())?;
+            backup_utils::restore_from_zip_data(data_dir, &data, passphrase.as_deref())?;
+
+            Ok(Report {
+                data: json!({ "success": true, "path": file.to_string_lossy() }),
+                lines: vec![format!(
+                    "Restored {}. Skills and MCP will be resynced when the app starts",
+                    file.display()
+                )],
+            })
+        }
+    }
+}
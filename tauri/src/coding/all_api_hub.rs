@@ -14,6 +14,8 @@ use super::open_code::types::{OpenCodeModel, OpenCodeProvider, OpenCodeProviderO
 use crate::db::DbState;
 use crate::http_client;
 
+mod firefox;
+
 const KNOWN_EXTENSION_IDS: &[&str] = &[
     "hnmbbaagobbadojmjkeilcgbnpdfifmk",
     "lapnciffpekdengooeolaienkeoilfeo",
@@ -26,16 +28,54 @@ const QUOTA_TO_USD_CONVERSION_FACTOR: f64 = 500_000.0;
 static EXTENSION_CACHE: Mutex<Option<(bool, Instant)>> = Mutex::new(None);
 const EXTENSION_CACHE_TTL_SECS: u64 = 30;
 
+/// Chromium 系浏览器：(名称, macOS `Application Support` 下的目录, Linux `~/.config` 下的目录,
+/// Windows `%LOCALAPPDATA%` 下的 User Data 目录)
+const CHROMIUM_BROWSERS: &[(&str, &str, &str, &str)] = &[
+    (
+        "Chrome",
+        "Google/Chrome",
+        "google-chrome",
+        "Google/Chrome/User Data",
+    ),
+    (
+        "Edge",
+        "Microsoft Edge",
+        "microsoft-edge",
+        "Microsoft/Edge/User Data",
+    ),
+    (
+        "Brave",
+        "BraveSoftware/Brave-Browser",
+        "BraveSoftware/Brave-Browser",
+        "BraveSoftware/Brave-Browser/User Data",
+    ),
+    ("Chromium", "Chromium", "chromium", "Chromium/User Data"),
+    ("Vivaldi", "Vivaldi", "vivaldi", "Vivaldi/User Data"),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExtensionStorageKind {
+    /// Chromium `Local Extension Settings/<id>` LevelDB
+    LevelDb,
+    /// Firefox `storage.local` IndexedDB (sqlite)
+    FirefoxIndexedDb,
+    /// Legacy Firefox `browser-extension-data/<id>/storage.js`
+    FirefoxStorageJs,
+}
+
 #[derive(Debug, Clone)]
 pub struct ExtensionInfo {
+    pub browser: String,
     pub profile_name: String,
     pub extension_id: String,
     pub path: PathBuf,
+    pub storage: ExtensionStorageKind,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct AllApiHubProfileInfo {
+    pub browser: String,
     pub profile_name: String,
     pub extension_id: String,
     pub path: String,
@@ -50,6 +90,7 @@ pub struct AllApiHubProviderCandidate {
     pub is_disabled: bool,
     pub balance_usd: Option<f64>,
     pub balance_cny: Option<f64>,
+    pub exchange_rate: Option<f64>,
     pub access_token: Option<String>,
     pub user_id: Option<i64>,
     pub auth_type: Option<String>,
@@ -61,6 +102,7 @@ pub struct AllApiHubProviderCandidate {
     pub site_name: Option<String>,
     pub site_type: Option<String>,
     pub account_label: String,
+    pub source_browser: String,
     pub source_profile_name: String,
     pub source_extension_id: String,
 }
@@ -73,6 +115,15 @@ pub struct AllApiHubDiscovery {
     pub message: Option<String>,
 }
 
+/// 账户余额查询结果；`from_site` 为 false 表示站点查询失败，回退为插件缓存中的余额
+#[derive(Debug, Clone)]
+pub struct AllApiHubAccountBalance {
+    pub balance_usd: Option<f64>,
+    pub balance_cny: Option<f64>,
+    pub from_site: bool,
+    pub error: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct AllApiHubProviderModelsRequest {
@@ -134,6 +185,7 @@ pub fn list_provider_candidates() -> Result<AllApiHubDiscovery, String> {
     let profiles = dirs
         .iter()
         .map(|info| AllApiHubProfileInfo {
+            browser: info.browser.clone(),
             profile_name: info.profile_name.clone(),
             extension_id: info.extension_id.clone(),
             path: info.path.display().to_string(),
@@ -146,7 +198,8 @@ pub fn list_provider_candidates() -> Result<AllApiHubDiscovery, String> {
             profiles,
             providers: vec![],
             message: Some(
-                "未找到 All API Hub 浏览器插件，请确认 Chrome 已安装且插件已启用。".to_string(),
+                "未找到 All API Hub 浏览器插件，请确认 Chrome / Edge / Brave / Chromium / Firefox 中已安装并启用该插件。"
+                    .to_string(),
             ),
         });
     }
@@ -157,7 +210,7 @@ pub fn list_provider_candidates() -> Result<AllApiHubDiscovery, String> {
     let mut provider_id_counts: HashMap<String, usize> = HashMap::new();
 
     for info in &dirs {
-        match read_extension_storage(&info.path).and_then(|raw| {
+        match read_storage(info).and_then(|raw| {
             parse_providers_from_storage(&raw, info, &mut seen_signatures, &mut provider_id_counts)
         }) {
             Ok(mut items) => {
@@ -165,8 +218,8 @@ pub fn list_provider_candidates() -> Result<AllApiHubDiscovery, String> {
             }
             Err(err) => {
                 warn!(
-                    "Failed to parse All API Hub storage from profile {}: {}",
-                    info.profile_name, err
+                    "Failed to parse All API Hub storage from {} profile {}: {}",
+                    info.browser, info.profile_name, err
                 );
                 last_error = Some(err);
             }
@@ -343,26 +396,38 @@ pub fn mask_api_key_preview(api_key: &str) -> String {
     format!("{}...{}", prefix, suffix)
 }
 
-fn get_chrome_base_dir() -> Option<PathBuf> {
+/// 已安装的 Chromium 系浏览器的 User Data 目录
+fn chromium_user_data_dirs() -> Vec<(&'static str, PathBuf)> {
     #[cfg(target_os = "macos")]
-    {
-        dirs::home_dir().map(|h| {
-            h.join("Library")
-                .join("Application Support")
-                .join("Google")
-                .join("Chrome")
-        })
-    }
-
+    let base = dirs::home_dir().map(|h| h.join("Library").join("Application Support"));
     #[cfg(target_os = "linux")]
-    {
-        dirs::home_dir().map(|h| h.join(".config").join("google-chrome"))
-    }
-
+    let base = dirs::config_dir();
     #[cfg(target_os = "windows")]
-    {
-        dirs::data_local_dir().map(|d| d.join("Google").join("Chrome").join("User Data"))
-    }
+    let base = dirs::data_local_dir();
+
+    let Some(base) = base else {
+        return Vec::new();
+    };
+
+    CHROMIUM_BROWSERS
+        .iter()
+        .map(|&(browser, macos, linux, windows)| {
+            let relative = if cfg!(target_os = "macos") {
+                macos
+            } else if cfg!(target_os = "windows") {
+                windows
+            } else {
+                linux
+            };
+            (
+                browser,
+                relative
+                    .split('/')
+                    .fold(base.clone(), |dir, part| dir.join(part)),
+            )
+        })
+        .filter(|(_, dir)| dir.is_dir())
+        .collect()
 }
 
 fn list_chrome_profiles(base: &Path) -> Vec<(String, PathBuf)> {
@@ -386,17 +451,23 @@ fn list_chrome_profiles(base: &Path) -> Vec<(String, PathBuf)> {
 }
 
 pub fn discover_extension_dirs() -> Vec<ExtensionInfo> {
-    let base = match get_chrome_base_dir() {
-        Some(path) if path.is_dir() => path,
-        _ => {
-            info!("Chrome base directory not found");
-            return Vec::new();
-        }
-    };
+    let browsers = chromium_user_data_dirs();
+    if browsers.is_empty() {
+        info!("No Chromium-based browser data directory found");
+    }
 
-    let profiles = list_chrome_profiles(&base);
+    let mut results = Vec::new();
+    for (browser, base) in &browsers {
+        results.extend(discover_chromium_extensions(browser, base));
+    }
+    results.extend(firefox::discover_extensions());
+    results
+}
+
+fn discover_chromium_extensions(browser: &str, base: &Path) -> Vec<ExtensionInfo> {
+    let profiles = list_chrome_profiles(base);
     if profiles.is_empty() {
-        info!("No Chrome profiles found in {:?}", base);
+        info!("No {} profiles found in {:?}", browser, base);
         return Vec::new();
     }
 
@@ -407,9 +478,11 @@ pub fn discover_extension_dirs() -> Vec<ExtensionInfo> {
             let candidate = ext_settings_dir.join(ext_id);
             if candidate.is_dir() {
                 results.push(ExtensionInfo {
+                    browser: browser.to_string(),
                     profile_name: profile_name.clone(),
                     extension_id: ext_id.to_string(),
                     path: candidate,
+                    storage: ExtensionStorageKind::LevelDb,
                 });
             }
         }
@@ -419,6 +492,7 @@ pub fn discover_extension_dirs() -> Vec<ExtensionInfo> {
         return results;
     }
 
+    // 其他商店（如 Edge 加载项）发布的扩展 id 不同，逐个检查存储中是否有 site_accounts
     for (profile_name, ext_settings_dir) in &profiles {
         let Ok(entries) = std::fs::read_dir(ext_settings_dir) else {
             continue;
@@ -435,9 +509,11 @@ pub fn discover_extension_dirs() -> Vec<ExtensionInfo> {
             if let Ok(data) = read_extension_storage(&ext_path) {
                 if !data.is_empty() {
                     results.push(ExtensionInfo {
+                        browser: browser.to_string(),
                         profile_name: profile_name.clone(),
                         extension_id: ext_id,
                         path: ext_path,
+                        storage: ExtensionStorageKind::LevelDb,
                     });
                 }
             }
@@ -447,6 +523,14 @@ pub fn discover_extension_dirs() -> Vec<ExtensionInfo> {
     results
 }
 
+fn read_storage(info: &ExtensionInfo) -> Result<String, String> {
+    match info.storage {
+        ExtensionStorageKind::LevelDb => read_extension_storage(&info.path),
+        ExtensionStorageKind::FirefoxIndexedDb => firefox::read_indexed_db_storage(&info.path),
+        ExtensionStorageKind::FirefoxStorageJs => firefox::read_storage_js(&info.path),
+    }
+}
+
 fn read_extension_storage(ext_dir: &Path) -> Result<String, String> {
     let temp_dir = std::env::temp_dir().join(format!(
         "ai-toolbox-all-api-hub-{}",
@@ -562,6 +646,7 @@ fn parse_providers_from_storage(
             is_disabled,
             balance_usd,
             balance_cny,
+            exchange_rate,
             access_token: account_info
                 .and_then(|v| get_string(v, &["access_token", "accessToken"])),
             user_id: account_info.and_then(|v| get_i64(v, &["id"])).or_else(|| {
@@ -587,6 +672,7 @@ fn parse_providers_from_storage(
             site_name,
             site_type,
             account_label,
+            source_browser: info.browser.clone(),
             source_profile_name: info.profile_name.clone(),
             source_extension_id: info.extension_id.clone(),
         });
@@ -706,6 +792,95 @@ async fn fetch_candidate_available_models(
     Ok(normalize_model_list(models))
 }
 
+/// 从站点实时查询账户余额，失败时回退为插件缓存的余额并附带错误信息
+pub async fn refresh_candidate_balance(
+    client: &reqwest::Client,
+    candidate: &AllApiHubProviderCandidate,
+) -> AllApiHubAccountBalance {
+    match fetch_candidate_balance_usd(client, candidate).await {
+        Ok(balance_usd) => AllApiHubAccountBalance {
+            balance_usd: Some(balance_usd),
+            balance_cny: candidate.exchange_rate.map(|rate| balance_usd * rate),
+            from_site: true,
+            error: None,
+        },
+        Err(error) => {
+            let message = match error {
+                ModelsFetchError::Unsupported(message) | ModelsFetchError::Request(message) => {
+                    message
+                }
+            };
+            AllApiHubAccountBalance {
+                balance_usd: candidate.balance_usd,
+                balance_cny: candidate.balance_cny,
+                from_site: false,
+                error: Some(message),
+            }
+        }
+    }
+}
+
+async fn fetch_candidate_balance_usd(
+    client: &reqwest::Client,
+    candidate: &AllApiHubProviderCandidate,
+) -> Result<f64, ModelsFetchError> {
+    if candidate
+        .auth_type
+        .as_deref()
+        .map(|value| value.trim().eq_ignore_ascii_case("cookie"))
+        .unwrap_or(false)
+    {
+        return Err(ModelsFetchError::Unsupported(
+            "Cookie 认证依赖浏览器页面上下文，当前暂不支持直接查询余额".to_string(),
+        ));
+    }
+
+    let site_url = candidate
+        .base_url
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .ok_or_else(|| ModelsFetchError::Unsupported("provider base URL is missing".to_string()))?;
+
+    let normalized_site_type = candidate
+        .site_type
+        .as_deref()
+        .map(normalize_site_type)
+        .unwrap_or_default();
+
+    if normalized_site_type == "sub2api" {
+        let access_token = candidate
+            .access_token
+            .as_deref()
+            .filter(|value| !value.trim().is_empty())
+            .ok_or_else(|| {
+                ModelsFetchError::Unsupported("Sub2API access token is missing".to_string())
+            })?;
+        return fetch_sub2api_balance(
+            client,
+            site_url,
+            access_token,
+            candidate.refresh_token.as_deref(),
+            candidate.token_expires_at,
+        )
+        .await
+        .map_err(ModelsFetchError::Request);
+    }
+
+    if normalized_site_type == "octopus" {
+        return Err(ModelsFetchError::Unsupported(
+            "Octopus does not expose account balances".to_string(),
+        ));
+    }
+
+    let payload = fetch_api_payload(client, site_url, "/api/user/self", candidate).await?;
+    get_f64(&payload, &["quota"])
+        .map(|quota| quota / QUOTA_TO_USD_CONVERSION_FACTOR)
+        .ok_or_else(|| {
+            ModelsFetchError::Request("/api/user/self response missing quota".to_string())
+        })
+}
+
 async fn fetch_api_payload(
     client: &reqwest::Client,
     site_url: &str,
@@ -1141,6 +1316,96 @@ async fn fetch_sub2api_keys_once(
         .ok_or_else(|| format!("No enabled keys found on {}", site_url))
 }
 
+async fn fetch_sub2api_balance(
+    client: &reqwest::Client,
+    site_url: &str,
+    access_token: &str,
+    refresh_token: Option<&str>,
+    token_expires_at: Option<i64>,
+) -> Result<f64, String> {
+    let mut current_access_token = access_token.trim().to_string();
+
+    if should_refresh_sub2api_token(token_expires_at) {
+        if let Some(refresh_token) = refresh_token.filter(|value| !value.trim().is_empty()) {
+            if let Ok(refreshed) =
+                refresh_sub2api_access_token(client, site_url, &current_access_token, refresh_token)
+                    .await
+            {
+                current_access_token = refreshed;
+            }
+        }
+    }
+
+    match fetch_sub2api_balance_once(client, site_url, &current_access_token).await {
+        Ok(balance) => Ok(balance),
+        Err(error) if is_auth_error_message(&error) => {
+            let refresh_token = refresh_token
+                .filter(|value| !value.trim().is_empty())
+                .ok_or(error)?;
+            let refreshed = refresh_sub2api_access_token(
+                client,
+                site_url,
+                &current_access_token,
+                refresh_token,
+            )
+            .await?;
+            fetch_sub2api_balance_once(client, site_url, &refreshed).await
+        }
+        Err(error) => Err(error),
+    }
+}
+
+async fn fetch_sub2api_balance_once(
+    client: &reqwest::Client,
+    site_url: &str,
+    access_token: &str,
+) -> Result<f64, String> {
+    let url = format!("{}/api/v1/auth/me", site_url.trim_end_matches('/'));
+
+    let mut headers = HeaderMap::new();
+    apply_auth_headers(&mut headers, Some("access_token"), access_token, None)?;
+
+    let resp = client
+        .get(&url)
+        .headers(headers)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to fetch Sub2API balance from {}: {}", site_url, e))?;
+
+    let status = resp.status().as_u16();
+    if status == 401 || status == 403 {
+        return Err(format!(
+            "Auth failed fetching Sub2API balance from {} (HTTP {})",
+            site_url, status
+        ));
+    }
+    if !(200..300).contains(&status) {
+        return Err(format!(
+            "Unexpected status {} fetching Sub2API balance from {}",
+            status, site_url
+        ));
+    }
+
+    let body: Value = resp.json().await.map_err(|e| {
+        format!(
+            "Failed to parse Sub2API profile response from {}: {}",
+            site_url, e
+        )
+    })?;
+
+    if body.get("code").and_then(|v| v.as_i64()).unwrap_or(0) != 0 {
+        let message = body
+            .get("message")
+            .and_then(|v| v.as_str())
+            .unwrap_or("unknown error");
+        return Err(format!("{} responded: {}", site_url, message));
+    }
+
+    body.get("data")
+        .and_then(|data| get_f64(data, &["balance"]))
+        .ok_or_else(|| format!("Sub2API profile response missing balance from {}", site_url))
+}
+
 async fn refresh_sub2api_access_token(
     client: &reqwest::Client,
     site_url: &str,
@@ -0,0 +1,514 @@
+//! Firefox 中 All API Hub 扩展存储的读取
+//!
+//! Firefox 的 `storage.local` 默认保存在扩展的 IndexedDB（sqlite）中，值经过 snappy 压缩，
+//! 编码为 SpiderMonkey 的 structured clone 格式；较旧的版本则是
+//! `browser-extension-data/<扩展 id>/storage.js` 中的 JSON。扩展 id 与内部 UUID 的对应关系
+//! 记录在 prefs.js 的 `extensions.webextensions.uuids` 中。
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use log::info;
+use serde_json::{Map, Value};
+
+use super::{ExtensionInfo, ExtensionStorageKind, TempDirCleanup, STORAGE_KEY};
+
+pub(super) const BROWSER_NAME: &str = "Firefox";
+
+const UUIDS_PREF: &str = "extensions.webextensions.uuids";
+
+// SpiderMonkey structured clone tags (js/src/vm/StructuredClone.cpp)
+const SCTAG_FLOAT_MAX: u32 = 0xFFF0_0000;
+const SCTAG_HEADER: u32 = 0xFFF1_0000;
+const SCTAG_NULL: u32 = 0xFFFF_0000;
+const SCTAG_UNDEFINED: u32 = 0xFFFF_0001;
+const SCTAG_BOOLEAN: u32 = 0xFFFF_0002;
+const SCTAG_INT32: u32 = 0xFFFF_0003;
+const SCTAG_STRING: u32 = 0xFFFF_0004;
+const SCTAG_DATE_OBJECT: u32 = 0xFFFF_0005;
+const SCTAG_ARRAY_OBJECT: u32 = 0xFFFF_0007;
+const SCTAG_OBJECT_OBJECT: u32 = 0xFFFF_0008;
+const SCTAG_BOOLEAN_OBJECT: u32 = 0xFFFF_000A;
+const SCTAG_STRING_OBJECT: u32 = 0xFFFF_000B;
+const SCTAG_NUMBER_OBJECT: u32 = 0xFFFF_000C;
+const SCTAG_BACK_REFERENCE_OBJECT: u32 = 0xFFFF_000D;
+const SCTAG_END_OF_KEYS: u32 = 0xFFFF_0013;
+
+/// Firefox 配置目录（包含 profiles.ini）
+fn firefox_base_dirs() -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+
+    #[cfg(target_os = "macos")]
+    if let Some(home) = dirs::home_dir() {
+        dirs.push(
+            home.join("Library")
+                .join("Application Support")
+                .join("Firefox"),
+        );
+    }
+
+    #[cfg(target_os = "linux")]
+    if let Some(home) = dirs::home_dir() {
+        dirs.push(home.join(".mozilla").join("firefox"));
+        // Snap / Flatpak 打包的 Firefox
+        dirs.push(
+            home.join("snap")
+                .join("firefox")
+                .join("common")
+                .join(".mozilla")
+                .join("firefox"),
+        );
+        dirs.push(
+            home.join(".var")
+                .join("app")
+                .join("org.mozilla.firefox")
+                .join(".mozilla")
+                .join("firefox"),
+        );
+    }
+
+    #[cfg(target_os = "windows")]
+    if let Some(appdata) = dirs::config_dir() {
+        dirs.push(appdata.join("Mozilla").join("Firefox"));
+    }
+
+    dirs
+}
+
+/// 解析 profiles.ini，返回 (profile 名称, profile 目录)
+fn parse_profiles_ini(content: &str, base: &Path) -> Vec<(String, PathBuf)> {
+    let mut profiles = Vec::new();
+    let mut in_profile = false;
+    let mut name: Option<String> = None;
+    let mut path: Option<String> = None;
+    let mut is_relative = true;
+
+    let mut flush = |name: &mut Option<String>, path: &mut Option<String>, is_relative: bool| {
+        if let Some(p) = path.take() {
+            let dir = if is_relative {
+                base.join(&p)
+            } else {
+                PathBuf::from(&p)
+            };
+            profiles.push((name.take().unwrap_or(p), dir));
+        }
+        *name = None;
+    };
+
+    for line in content.lines().map(str::trim) {
+        if line.starts_with('[') {
+            if in_profile {
+                flush(&mut name, &mut path, is_relative);
+            }
+            in_profile = line.starts_with("[Profile");
+            is_relative = true;
+            continue;
+        }
+        if !in_profile {
+            continue;
+        }
+        match line.split_once('=') {
+            Some(("Name", value)) => name = Some(value.to_string()),
+            Some(("Path", value)) => path = Some(value.to_string()),
+            Some(("IsRelative", value)) => is_relative = value != "0",
+            _ => {}
+        }
+    }
+    if in_profile {
+        flush(&mut name, &mut path, is_relative);
+    }
+
+    profiles
+}
+
+/// 从 prefs.js 读取 扩展 id → 内部 UUID
+fn parse_extension_uuids(prefs: &str) -> HashMap<String, String> {
+    let prefix = format!("user_pref(\"{}\",", UUIDS_PREF);
+    prefs
+        .lines()
+        .find_map(|line| line.trim().strip_prefix(&prefix))
+        .and_then(|rest| rest.trim().strip_suffix(");"))
+        .and_then(|literal| serde_json::from_str::<String>(literal.trim()).ok())
+        .and_then(|json| serde_json::from_str::<HashMap<String, String>>(&json).ok())
+        .unwrap_or_default()
+}
+
+pub(super) fn discover_extensions() -> Vec<ExtensionInfo> {
+    let mut results = Vec::new();
+
+    for base in firefox_base_dirs() {
+        let Ok(ini) = std::fs::read_to_string(base.join("profiles.ini")) else {
+            continue;
+        };
+
+        for (profile_name, profile_dir) in parse_profiles_ini(&ini, &base) {
+            let Ok(prefs) = std::fs::read_to_string(profile_dir.join("prefs.js")) else {
+                continue;
+            };
+
+            // Firefox 商店中的扩展 id 不固定，逐个检查扩展存储中是否有 site_accounts
+            for (extension_id, uuid) in parse_extension_uuids(&prefs) {
+                if let Some((path, storage)) = find_storage(&profile_dir, &extension_id, &uuid) {
+                    results.push(ExtensionInfo {
+                        browser: BROWSER_NAME.to_string(),
+                        profile_name: profile_name.clone(),
+                        extension_id,
+                        path,
+                        storage,
+                    });
+                }
+            }
+        }
+    }
+
+    if results.is_empty() {
+        info!("No All API Hub storage found in Firefox profiles");
+    }
+    results
+}
+
+fn find_storage(
+    profile_dir: &Path,
+    extension_id: &str,
+    uuid: &str,
+) -> Option<(PathBuf, ExtensionStorageKind)> {
+    let idb_dir = profile_dir
+        .join("storage")
+        .join("default")
+        .join(format!("moz-extension+++{}^userContextId=4294967295", uuid))
+        .join("idb");
+    if let Ok(entries) = std::fs::read_dir(&idb_dir) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().is_some_and(|ext| ext == "sqlite") && has_storage_key(&path) {
+                return Some((path, ExtensionStorageKind::FirefoxIndexedDb));
+            }
+        }
+    }
+
+    let storage_js = profile_dir
+        .join("browser-extension-data")
+        .join(extension_id)
+        .join("storage.js");
+    if storage_js.is_file() && read_storage_js(&storage_js).is_ok() {
+        return Some((storage_js, ExtensionStorageKind::FirefoxStorageJs));
+    }
+
+    None
+}
+
+/// 旧版 storage.js：整个文件是 key → value 的 JSON 对象
+pub(super) fn read_storage_js(path: &Path) -> Result<String, String> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let storage: Value = serde_json::from_str(&content)
+        .map_err(|e| format!("Invalid JSON in {}: {}", path.display(), e))?;
+
+    match storage.get(STORAGE_KEY) {
+        Some(Value::String(s)) => Ok(s.clone()),
+        Some(value) => Ok(value.to_string()),
+        None => Err(format!("Key '{}' not found in storage.js", STORAGE_KEY)),
+    }
+}
+
+/// 复制到临时目录再打开，避免与正在运行的 Firefox 争用数据库锁
+fn open_indexed_db_copy(path: &Path) -> Result<(rusqlite::Connection, TempDirCleanup), String> {
+    let temp_dir = std::env::temp_dir().join(format!(
+        "ai-toolbox-all-api-hub-{}",
+        uuid::Uuid::new_v4().simple()
+    ));
+    let cleanup = TempDirCleanup::new(temp_dir.clone());
+    std::fs::create_dir_all(&temp_dir).map_err(|e| format!("Failed to create temp dir: {}", e))?;
+
+    let db_path = temp_dir.join("storage.sqlite");
+    std::fs::copy(path, &db_path)
+        .map_err(|e| format!("Failed to copy IndexedDB file to temp: {}", e))?;
+    let wal = PathBuf::from(format!("{}-wal", path.display()));
+    if wal.is_file() {
+        let _ = std::fs::copy(&wal, temp_dir.join("storage.sqlite-wal"));
+    }
+
+    let conn = rusqlite::Connection::open(&db_path)
+        .map_err(|e| format!("Failed to open IndexedDB: {}", e))?;
+    Ok((conn, cleanup))
+}
+
+/// 只检查 object_data 中是否有存储 key，不复制、不解码数据；
+/// 只读打开失败（如被 Firefox 锁定）时再退回到临时副本
+fn has_storage_key(path: &Path) -> bool {
+    use rusqlite::OptionalExtension;
+
+    let (key, trimmed_key) = encode_string_key(STORAGE_KEY);
+    let query = |conn: &rusqlite::Connection| {
+        conn.query_row(
+            "SELECT 1 FROM object_data WHERE key IN (?1, ?2)",
+            rusqlite::params![key, trimmed_key],
+            |_| Ok(()),
+        )
+        .optional()
+        .map(|row| row.is_some())
+    };
+
+    let direct = rusqlite::Connection::open_with_flags(
+        path,
+        rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY | rusqlite::OpenFlags::SQLITE_OPEN_NO_MUTEX,
+    )
+    .map_err(|e| e.to_string())
+    .and_then(|conn| query(&conn).map_err(|e| e.to_string()));
+    match direct {
+        Ok(found) => found,
+        Err(_) => open_indexed_db_copy(path)
+            .ok()
+            .is_some_and(|(conn, _cleanup)| query(&conn).unwrap_or(false)),
+    }
+}
+
+pub(super) fn read_indexed_db_storage(path: &Path) -> Result<String, String> {
+    let (conn, _cleanup) = open_indexed_db_copy(path)?;
+    let (key, trimmed_key) = encode_string_key(STORAGE_KEY);
+    let (data, file_ids): (Vec<u8>, Option<String>) = conn
+        .query_row(
+            "SELECT data, file_ids FROM object_data WHERE key IN (?1, ?2)",
+            rusqlite::params![key, trimmed_key],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .map_err(|e| format!("Key '{}' not found in IndexedDB: {}", STORAGE_KEY, e))?;
+    drop(conn);
+
+    if file_ids.is_some_and(|ids| !ids.is_empty()) {
+        return Err(format!(
+            "'{}' is stored outside the IndexedDB file, which is not supported",
+            STORAGE_KEY
+        ));
+    }
+
+    let decompressed = snap::raw::Decoder::new()
+        .decompress_vec(&data)
+        .map_err(|e| format!("Failed to decompress IndexedDB value: {}", e))?;
+
+    match CloneReader::new(&decompressed).read_root()? {
+        Value::String(s) => Ok(s),
+        value => Ok(value.to_string()),
+    }
+}
+
+/// 按 IndexedDB 的 key 编码规则编码 ASCII 字符串 key，返回（带结束符, 去掉结束符）两种形式
+fn encode_string_key(key: &str) -> (Vec<u8>, Vec<u8>) {
+    let mut encoded = vec![0x30];
+    encoded.extend(key.bytes().map(|b| b + 1));
+    let trimmed = encoded.clone();
+    encoded.push(0);
+    (encoded, trimmed)
+}
+
+/// structured clone 数据的最小读取器，只支持 JSON 能表示的类型
+struct CloneReader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> CloneReader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data, pos: 0 }
+    }
+
+    fn read_root(&mut self) -> Result<Value, String> {
+        let (tag, data) = self.read_pair()?;
+        if tag == SCTAG_HEADER {
+            self.read_value()
+        } else {
+            self.read_tagged(tag, data)
+        }
+    }
+
+    fn read_u64(&mut self) -> Result<u64, String> {
+        let bytes = self
+            .data
+            .get(self.pos..self.pos + 8)
+            .ok_or_else(|| "Unexpected end of structured clone data".to_string())?;
+        self.pos += 8;
+        Ok(u64::from_le_bytes(bytes.try_into().unwrap_or_default()))
+    }
+
+    fn read_pair(&mut self) -> Result<(u32, u32), String> {
+        let value = self.read_u64()?;
+        Ok(((value >> 32) as u32, value as u32))
+    }
+
+    fn read_bytes(&mut self, len: usize) -> Result<&'a [u8], String> {
+        let bytes = self
+            .data
+            .get(self.pos..self.pos + len)
+            .ok_or_else(|| "Unexpected end of structured clone data".to_string())?;
+        // 字符数据按 8 字节对齐
+        self.pos += len.div_ceil(8) * 8;
+        Ok(bytes)
+    }
+
+    fn read_string(&mut self, data: u32) -> Result<String, String> {
+        let len = (data & 0x7FFF_FFFF) as usize;
+        if data & 0x8000_0000 != 0 {
+            Ok(self.read_bytes(len)?.iter().map(|&b| b as char).collect())
+        } else {
+            let units: Vec<u16> = self
+                .read_bytes(len * 2)?
+                .chunks_exact(2)
+                .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+                .collect();
+            Ok(String::from_utf16_lossy(&units))
+        }
+    }
+
+    fn read_value(&mut self) -> Result<Value, String> {
+        let (tag, data) = self.read_pair()?;
+        self.read_tagged(tag, data)
+    }
+
+    fn read_tagged(&mut self, tag: u32, data: u32) -> Result<Value, String> {
+        match tag {
+            tag if tag < SCTAG_FLOAT_MAX => Ok(number_value(f64::from_bits(
+                ((tag as u64) << 32) | data as u64,
+            ))),
+            SCTAG_NULL | SCTAG_UNDEFINED | SCTAG_BACK_REFERENCE_OBJECT => Ok(Value::Null),
+            SCTAG_BOOLEAN | SCTAG_BOOLEAN_OBJECT => Ok(Value::Bool(data != 0)),
+            SCTAG_INT32 => Ok(Value::from(data as i32)),
+            SCTAG_STRING | SCTAG_STRING_OBJECT => Ok(Value::String(self.read_string(data)?)),
+            SCTAG_DATE_OBJECT | SCTAG_NUMBER_OBJECT => {
+                Ok(number_value(f64::from_bits(self.read_u64()?)))
+            }
+            SCTAG_ARRAY_OBJECT => {
+                // 长度来自不可信的输入：每个元素至少占 8 字节，超出剩余数据的长度视为损坏
+                let len = data as usize;
+                if len > self.data.len().saturating_sub(self.pos) / 8 {
+                    return Err(format!("Invalid structured clone array length {}", len));
+                }
+                let mut items = vec![Value::Null; len];
+                while let Some(key) = self.read_key()? {
+                    let value = self.read_value()?;
+                    if let Ok(index) = key.parse::<usize>() {
+                        let slot = items.get_mut(index).ok_or_else(|| {
+                            format!("Structured clone array index {} out of bounds", index)
+                        })?;
+                        *slot = value;
+                    }
+                }
+                Ok(Value::Array(items))
+            }
+            SCTAG_OBJECT_OBJECT => {
+                let mut map = Map::new();
+                while let Some(key) = self.read_key()? {
+                    let value = self.read_value()?;
+                    map.insert(key, value);
+                }
+                Ok(Value::Object(map))
+            }
+            other => Err(format!("Unsupported structured clone tag {:#x}", other)),
+        }
+    }
+
+    /// 读取对象/数组的下一个 key，遇到结束标记返回 None
+    fn read_key(&mut self) -> Result<Option<String>, String> {
+        let (tag, data) = self.read_pair()?;
+        match tag {
+            SCTAG_END_OF_KEYS => Ok(None),
+            SCTAG_INT32 => Ok(Some((data as i32).to_string())),
+            SCTAG_STRING => Ok(Some(self.read_string(data)?)),
+            other => Err(format!("Unsupported structured clone key tag {:#x}", other)),
+        }
+    }
+}
+
+/// 整数值的 double 还原为整数，保持与 Chrome 存储中 JSON 数字一致
+fn number_value(value: f64) -> Value {
+    if value.fract() == 0.0 && value.abs() < 9_007_199_254_740_992.0 {
+        Value::from(value as i64)
+    } else {
+        serde_json::Number::from_f64(value)
+            .map(Value::Number)
+            .unwrap_or(Value::Null)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pair(tag: u32, data: u32) -> Vec<u8> {
+        (((tag as u64) << 32) | data as u64).to_le_bytes().to_vec()
+    }
+
+    fn latin1(s: &str) -> Vec<u8> {
+        let mut out = pair(SCTAG_STRING, s.len() as u32 | 0x8000_0000);
+        let mut bytes = s.as_bytes().to_vec();
+        bytes.resize(s.len().div_ceil(8) * 8, 0);
+        out.extend(bytes);
+        out
+    }
+
+    #[test]
+    fn structured_clone_decodes_json_like_values() {
+        // { accounts: [{ quota: 1000000, rate: 7.2 }], name: "hub" }
+        let mut data = pair(SCTAG_HEADER, 0);
+        data.extend(pair(SCTAG_OBJECT_OBJECT, 0));
+        data.extend(latin1("accounts"));
+        data.extend(pair(SCTAG_ARRAY_OBJECT, 1));
+        data.extend(pair(SCTAG_INT32, 0));
+        data.extend(pair(SCTAG_OBJECT_OBJECT, 0));
+        data.extend(latin1("quota"));
+        data.extend(pair(SCTAG_INT32, 1_000_000));
+        data.extend(latin1("rate"));
+        data.extend(7.2f64.to_bits().to_le_bytes());
+        data.extend(pair(SCTAG_END_OF_KEYS, 0));
+        data.extend(pair(SCTAG_END_OF_KEYS, 0));
+        data.extend(latin1("name"));
+        data.extend(latin1("hub"));
+        data.extend(pair(SCTAG_END_OF_KEYS, 0));
+
+        let value = CloneReader::new(&data).read_root().unwrap();
+        assert_eq!(
+            value,
+            serde_json::json!({ "accounts": [{ "quota": 1000000, "rate": 7.2 }], "name": "hub" })
+        );
+    }
+
+    #[test]
+    fn structured_clone_rejects_oversized_arrays() {
+        // 声明的长度远超剩余数据
+        let mut data = pair(SCTAG_HEADER, 0);
+        data.extend(pair(SCTAG_ARRAY_OBJECT, u32::MAX));
+        data.extend(pair(SCTAG_END_OF_KEYS, 0));
+        assert!(CloneReader::new(&data).read_root().is_err());
+
+        // 稀疏下标超出声明的长度
+        let mut data = pair(SCTAG_HEADER, 0);
+        data.extend(pair(SCTAG_ARRAY_OBJECT, 1));
+        data.extend(pair(SCTAG_INT32, 1_000_000));
+        data.extend(pair(SCTAG_INT32, 1));
+        data.extend(pair(SCTAG_END_OF_KEYS, 0));
+        assert!(CloneReader::new(&data).read_root().is_err());
+    }
+
+    #[test]
+    fn profiles_and_extension_uuids_are_parsed() {
+        let ini = "[General]\nStartWithLastProfile=1\n\n[Profile0]\nName=default-release\nIsRelative=1\nPath=Profiles/abc.default-release\n\n[Profile1]\nName=work\nIsRelative=0\nPath=/data/work\n";
+        let profiles = parse_profiles_ini(ini, Path::new("/ff"));
+        assert_eq!(
+            profiles,
+            vec![
+                (
+                    "default-release".to_string(),
+                    PathBuf::from("/ff/Profiles/abc.default-release")
+                ),
+                ("work".to_string(), PathBuf::from("/data/work")),
+            ]
+        );
+
+        let prefs = r#"user_pref("browser.startup.page", 3);
+user_pref("extensions.webextensions.uuids", "{\"hub@example.com\":\"1234-abcd\"}");"#;
+        let uuids = parse_extension_uuids(prefs);
+        assert_eq!(
+            uuids.get("hub@example.com").map(String::as_str),
+            Some("1234-abcd")
+        );
+    }
+}
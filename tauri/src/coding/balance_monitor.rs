@@ -0,0 +1,538 @@
+//! All API Hub 账户余额监控
+//!
+//! 从 All API Hub 导入的 provider（Claude Code / Codex / Gemini CLI 记录了 `source_provider_id`，OpenCode
+//! 以导入时的 provider id 作为配置键）会定期向对应站点查询账户余额，写入
+//! `provider_balance_snapshot` 表形成时间序列，并据此估算每日消耗与剩余天数。
+//!
+//! 当前应用中的 provider 所属账户余额低于阈值时，向前端发送 `provider-balance-alert` 事件
+//! （每次跌破只提醒一次），并在托盘图标上显示余额提示，余额恢复后自动清除。
+//!
+//! 开关、刷新间隔和告警阈值保存在应用设置中，调度循环每分钟检查一次是否到期。
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use serde_json::{json, Value};
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::coding::all_api_hub;
+use crate::coding::claude_code;
+use crate::coding::codex;
+use crate::coding::gemini_cli;
+use crate::coding::health_monitor::{MODULE_CLAUDE_CODE, MODULE_CODEX, MODULE_OPENCODE};
+use crate::coding::open_code;
+use crate::coding::open_code::types::ReadConfigResult;
+use crate::db::DbState;
+use crate::http_client;
+use crate::tray;
+
+/// 单个站点查询超时
+const REQUEST_TIMEOUT_SECS: u64 = 20;
+/// 余额记录保留天数
+const HISTORY_RETENTION_DAYS: i64 = 30;
+/// 估算消耗速度时回看的天数
+const BURN_RATE_WINDOW_DAYS: i64 = 7;
+/// 采样跨度不足该时长时不估算消耗速度
+const MIN_BURN_RATE_SPAN_HOURS: i64 = 1;
+/// Gemini CLI 的模块标识（与前端 provider 模块名一致）
+const MODULE_GEMINI_CLI: &str = "geminicli";
+
+/// 手动刷新与定时刷新互斥
+static REFRESH_LOCK: tokio::sync::Mutex<()> = tokio::sync::Mutex::const_new(());
+/// 已经提醒过的低余额账户，余额恢复后移除
+static ALERTED_ACCOUNTS: Mutex<Option<HashSet<String>>> = Mutex::new(None);
+
+/// 使用某个 All API Hub 账户的 provider
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LinkedProvider {
+    /// "claudecode" | "codex" | "geminicli" | "opencode"
+    pub module: String,
+    pub provider_id: String,
+    pub provider_name: String,
+    pub is_applied: bool,
+}
+
+/// 账户余额概览
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountBalanceSummary {
+    pub account_id: String,
+    pub account_name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub site_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub balance_usd: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub balance_cny: Option<f64>,
+    /// false 表示最近一次站点查询失败，余额来自插件缓存
+    pub from_site: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub checked_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub burn_rate_usd_per_day: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub days_remaining: Option<f64>,
+    pub is_low: bool,
+    pub providers: Vec<LinkedProvider>,
+}
+
+/// 当前应用的 provider 余额不足告警
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BalanceAlert {
+    pub account_id: String,
+    pub account_name: String,
+    pub balance_usd: f64,
+    pub threshold_usd: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub days_remaining: Option<f64>,
+    /// 正在使用该账户的 provider
+    pub providers: Vec<LinkedProvider>,
+}
+
+/// 一条余额记录
+struct BalanceSnapshot {
+    account_id: String,
+    account_name: String,
+    site_url: Option<String>,
+    balance_usd: Option<f64>,
+    balance_cny: Option<f64>,
+    from_site: bool,
+    error: Option<String>,
+    checked_at: String,
+}
+
+/// 启动后台调度循环
+pub fn start_balance_monitor(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        // 启动后稍等，避免与其他初始化任务争抢
+        tokio::time::sleep(Duration::from_secs(90)).await;
+
+        let mut last_run: Option<Instant> = None;
+        loop {
+            let settings = crate::settings::get_settings(app.state())
+                .await
+                .unwrap_or_default();
+
+            if settings.balance_monitor_enabled {
+                let interval = Duration::from_secs(
+                    settings.balance_monitor_interval_minutes.max(1) as u64 * 60,
+                );
+                if last_run.is_none_or(|t| t.elapsed() >= interval) {
+                    last_run = Some(Instant::now());
+                    if let Err(e) = refresh_balances(&app).await {
+                        log::warn!("Provider balance refresh failed: {}", e);
+                    }
+                }
+            } else if last_run.is_some() {
+                last_run = None;
+                update_alerts(&app, &[], 0.0);
+            }
+
+            tokio::time::sleep(Duration::from_secs(60)).await;
+        }
+    });
+}
+
+/// 立即刷新所有已导入账户的余额（不受开关影响）
+#[tauri::command]
+pub async fn refresh_provider_balances(
+    app: AppHandle,
+) -> Result<Vec<AccountBalanceSummary>, String> {
+    refresh_balances(&app).await
+}
+
+/// 读取已导入账户最近一次记录的余额，不访问站点
+#[tauri::command]
+pub async fn list_provider_balances(app: AppHandle) -> Result<Vec<AccountBalanceSummary>, String> {
+    let state = app.state::<DbState>();
+    let settings = crate::settings::get_settings(app.state()).await?;
+    let discovery = all_api_hub::list_provider_candidates()?;
+    let links = collect_links(&app, &discovery.providers).await?;
+    build_summaries(&state, links, settings.balance_alert_threshold_usd).await
+}
+
+async fn refresh_balances(app: &AppHandle) -> Result<Vec<AccountBalanceSummary>, String> {
+    let _guard = REFRESH_LOCK.lock().await;
+
+    let state = app.state::<DbState>();
+    let settings = crate::settings::get_settings(app.state()).await?;
+    let discovery = all_api_hub::list_provider_candidates()?;
+    let links = collect_links(app, &discovery.providers).await?;
+
+    if !links.is_empty() {
+        let client = http_client::client_with_timeout(&state, REQUEST_TIMEOUT_SECS).await?;
+        for candidate in discovery
+            .providers
+            .iter()
+            .filter(|c| links.contains_key(&c.provider_id))
+        {
+            let balance = all_api_hub::refresh_candidate_balance(&client, candidate).await;
+            if let Some(error) = &balance.error {
+                log::info!("Balance of {} not refreshed: {}", candidate.name, error);
+            }
+            let snapshot = BalanceSnapshot {
+                account_id: candidate.provider_id.clone(),
+                account_name: candidate.name.clone(),
+                site_url: candidate.base_url.clone(),
+                balance_usd: balance.balance_usd,
+                balance_cny: balance.balance_cny,
+                from_site: balance.from_site,
+                error: balance.error,
+                checked_at: Utc::now().to_rfc3339(),
+            };
+            save_snapshot(&state, &snapshot).await?;
+        }
+        prune_history(&state).await;
+    }
+
+    let threshold = settings.balance_alert_threshold_usd;
+    let summaries = build_summaries(&state, links, threshold).await?;
+    update_alerts(app, &summaries, threshold);
+    Ok(summaries)
+}
+
+// ============================================================================
+// Linked providers
+// ============================================================================
+
+/// 按 All API Hub 账户 id 汇总各模块中由其导入的 provider
+async fn collect_links(
+    app: &AppHandle,
+    candidates: &[all_api_hub::AllApiHubProviderCandidate],
+) -> Result<BTreeMap<String, Vec<LinkedProvider>>, String> {
+    let db = app.state::<DbState>().db();
+    let mut links: BTreeMap<String, Vec<LinkedProvider>> = BTreeMap::new();
+
+    for provider in claude_code::commands::list_providers_internal(&db).await? {
+        if let Some(account_id) = provider.source_provider_id.filter(|s| !s.is_empty()) {
+            links.entry(account_id).or_default().push(LinkedProvider {
+                module: MODULE_CLAUDE_CODE.to_string(),
+                provider_id: provider.id,
+                provider_name: provider.name,
+                is_applied: provider.is_applied,
+            });
+        }
+    }
+
+    for provider in codex::commands::list_providers_internal(&db).await? {
+        if let Some(account_id) = provider.source_provider_id.filter(|s| !s.is_empty()) {
+            links.entry(account_id).or_default().push(LinkedProvider {
+                module: MODULE_CODEX.to_string(),
+                provider_id: provider.id,
+                provider_name: provider.name,
+                is_applied: provider.is_applied,
+            });
+        }
+    }
+
+    for provider in gemini_cli::commands::list_providers_internal(&db).await? {
+        if let Some(account_id) = provider.source_provider_id.filter(|s| !s.is_empty()) {
+            links.entry(account_id).or_default().push(LinkedProvider {
+                module: MODULE_GEMINI_CLI.to_string(),
+                provider_id: provider.id,
+                provider_name: provider.name,
+                is_applied: provider.is_applied,
+            });
+        }
+    }
+
+    // OpenCode 配置中没有来源信息，只能按导入时使用的 provider id 匹配
+    if let Ok(ReadConfigResult::Success { config }) =
+        open_code::commands::read_opencode_config(app.state()).await
+    {
+        let applied = config
+            .model
+            .as_deref()
+            .and_then(|m| m.split_once('/'))
+            .map(|(provider_id, _)| provider_id);
+        for (provider_id, provider) in config.provider.iter().flatten() {
+            if !candidates.iter().any(|c| &c.provider_id == provider_id) {
+                continue;
+            }
+            links
+                .entry(provider_id.clone())
+                .or_default()
+                .push(LinkedProvider {
+                    module: MODULE_OPENCODE.to_string(),
+                    provider_id: provider_id.clone(),
+                    provider_name: provider.name.clone().unwrap_or_else(|| provider_id.clone()),
+                    is_applied: applied == Some(provider_id.as_str()),
+                });
+        }
+    }
+
+    Ok(links)
+}
+
+// ============================================================================
+// Summaries & alerts
+// ============================================================================
+
+async fn build_summaries(
+    state: &DbState,
+    links: BTreeMap<String, Vec<LinkedProvider>>,
+    threshold_usd: f64,
+) -> Result<Vec<AccountBalanceSummary>, String> {
+    let since = (Utc::now() - chrono::Duration::days(BURN_RATE_WINDOW_DAYS)).to_rfc3339();
+    let mut result = state
+        .db()
+        .query("SELECT account_id, balance_usd, checked_at FROM provider_balance_snapshot WHERE checked_at >= $since AND from_site = true ORDER BY checked_at ASC")
+        .bind(("since", since))
+        .await
+        .map_err(|e| format!("Failed to query balance history: {}", e))?;
+    let records: Vec<Value> = result
+        .take(0)
+        .map_err(|e| format!("Failed to parse balance history: {}", e))?;
+
+    let mut points: HashMap<String, Vec<(DateTime<Utc>, f64)>> = HashMap::new();
+    for record in &records {
+        let account_id = record.get("account_id").and_then(|v| v.as_str());
+        let balance = record.get("balance_usd").and_then(|v| v.as_f64());
+        let checked_at = record
+            .get("checked_at")
+            .and_then(|v| v.as_str())
+            .and_then(|s| DateTime::parse_from_rfc3339(s).ok());
+        if let (Some(account_id), Some(balance), Some(checked_at)) =
+            (account_id, balance, checked_at)
+        {
+            points
+                .entry(account_id.to_string())
+                .or_default()
+                .push((checked_at.with_timezone(&Utc), balance));
+        }
+    }
+
+    let mut summaries = Vec::new();
+    for (account_id, providers) in links {
+        let latest = latest_snapshot(state, &account_id).await?;
+        let burn_rate = points.get(&account_id).and_then(|p| burn_rate_per_day(p));
+        let balance_usd = latest.as_ref().and_then(|s| s.balance_usd);
+
+        summaries.push(AccountBalanceSummary {
+            account_name: latest
+                .as_ref()
+                .map(|s| s.account_name.clone())
+                .unwrap_or_else(|| account_id.clone()),
+            site_url: latest.as_ref().and_then(|s| s.site_url.clone()),
+            balance_usd,
+            balance_cny: latest.as_ref().and_then(|s| s.balance_cny),
+            from_site: latest.as_ref().is_some_and(|s| s.from_site),
+            error: latest.as_ref().and_then(|s| s.error.clone()),
+            checked_at: latest.map(|s| s.checked_at),
+            burn_rate_usd_per_day: burn_rate,
+            days_remaining: balance_usd
+                .zip(burn_rate)
+                .and_then(|(b, r)| days_remaining(b, r)),
+            is_low: is_low_balance(balance_usd, threshold_usd),
+            account_id,
+            providers,
+        });
+    }
+
+    Ok(summaries)
+}
+
+/// 对当前应用且余额不足的账户发送告警并更新托盘提示
+fn update_alerts(app: &AppHandle, summaries: &[AccountBalanceSummary], threshold_usd: f64) {
+    let low: Vec<&AccountBalanceSummary> = summaries
+        .iter()
+        .filter(|s| s.is_low && s.providers.iter().any(|p| p.is_applied))
+        .collect();
+
+    let newly_low: Vec<&AccountBalanceSummary> = {
+        let mut guard = ALERTED_ACCOUNTS.lock().unwrap_or_else(|e| e.into_inner());
+        let alerted = guard.get_or_insert_with(HashSet::new);
+        alerted.retain(|id| low.iter().any(|s| &s.account_id == id));
+        low.iter()
+            .filter(|s| alerted.insert(s.account_id.clone()))
+            .copied()
+            .collect()
+    };
+
+    for summary in newly_low {
+        let alert = BalanceAlert {
+            account_id: summary.account_id.clone(),
+            account_name: summary.account_name.clone(),
+            balance_usd: summary.balance_usd.unwrap_or_default(),
+            threshold_usd,
+            days_remaining: summary.days_remaining,
+            providers: summary
+                .providers
+                .iter()
+                .filter(|p| p.is_applied)
+                .cloned()
+                .collect(),
+        };
+        log::info!(
+            "Balance of {} dropped to ${:.2} (threshold ${:.2})",
+            alert.account_name,
+            alert.balance_usd,
+            threshold_usd
+        );
+        let _ = app.emit("provider-balance-alert", &alert);
+    }
+
+    // 托盘标题显示最低的余额，悬浮提示列出所有余额不足的账户
+    match low
+        .iter()
+        .filter_map(|s| s.balance_usd)
+        .min_by(|a, b| a.total_cmp(b))
+    {
+        Some(lowest) => {
+            let tooltip = low
+                .iter()
+                .map(|s| {
+                    format!(
+                        "余额不足: {} ${:.2}",
+                        s.account_name,
+                        s.balance_usd.unwrap_or_default()
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join("\n");
+            tray::set_tray_alert(app, Some(&format!("${:.2}", lowest)), Some(&tooltip));
+        }
+        None => tray::set_tray_alert(app, None, None),
+    }
+}
+
+fn is_low_balance(balance_usd: Option<f64>, threshold_usd: f64) -> bool {
+    threshold_usd > 0.0 && balance_usd.is_some_and(|b| b < threshold_usd)
+}
+
+/// 按时间升序的 (时间, 余额) 估算每日消耗（美元）；余额上升视为充值，不计入消耗
+fn burn_rate_per_day(points: &[(DateTime<Utc>, f64)]) -> Option<f64> {
+    let (first, last) = (points.first()?, points.last()?);
+    let span = last.0 - first.0;
+    if span < chrono::Duration::hours(MIN_BURN_RATE_SPAN_HOURS) {
+        return None;
+    }
+
+    let spent: f64 = points
+        .windows(2)
+        .map(|pair| (pair[0].1 - pair[1].1).max(0.0))
+        .sum();
+    Some(spent / (span.num_seconds() as f64 / 86_400.0))
+}
+
+fn days_remaining(balance_usd: f64, burn_rate_usd_per_day: f64) -> Option<f64> {
+    (burn_rate_usd_per_day > 0.0).then(|| balance_usd.max(0.0) / burn_rate_usd_per_day)
+}
+
+// ============================================================================
+// Storage
+// ============================================================================
+
+fn from_db_value(value: Value) -> BalanceSnapshot {
+    let get_str = |key: &str| {
+        value
+            .get(key)
+            .and_then(|v| v.as_str())
+            .unwrap_or_default()
+            .to_string()
+    };
+    let get_opt_str = |key: &str| value.get(key).and_then(|v| v.as_str()).map(String::from);
+    BalanceSnapshot {
+        account_id: get_str("account_id"),
+        account_name: get_str("account_name"),
+        site_url: get_opt_str("site_url"),
+        balance_usd: value.get("balance_usd").and_then(|v| v.as_f64()),
+        balance_cny: value.get("balance_cny").and_then(|v| v.as_f64()),
+        from_site: value
+            .get("from_site")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false),
+        error: get_opt_str("error"),
+        checked_at: get_str("checked_at"),
+    }
+}
+
+async fn save_snapshot(state: &DbState, snapshot: &BalanceSnapshot) -> Result<(), String> {
+    let data = json!({
+        "account_id": snapshot.account_id,
+        "account_name": snapshot.account_name,
+        "site_url": snapshot.site_url,
+        "balance_usd": snapshot.balance_usd,
+        "balance_cny": snapshot.balance_cny,
+        "from_site": snapshot.from_site,
+        "error": snapshot.error,
+        "checked_at": snapshot.checked_at,
+    });
+
+    state
+        .db()
+        .query(format!(
+            "CREATE provider_balance_snapshot:`{}` CONTENT $data",
+            crate::coding::db_new_id()
+        ))
+        .bind(("data", data))
+        .await
+        .map_err(|e| format!("Failed to save balance snapshot: {}", e))?;
+    Ok(())
+}
+
+async fn latest_snapshot(
+    state: &DbState,
+    account_id: &str,
+) -> Result<Option<BalanceSnapshot>, String> {
+    let mut result = state
+        .db()
+        .query("SELECT * FROM provider_balance_snapshot WHERE account_id = $account_id ORDER BY checked_at DESC LIMIT 1")
+        .bind(("account_id", account_id.to_string()))
+        .await
+        .map_err(|e| format!("Failed to query balance snapshot: {}", e))?;
+    let records: Vec<Value> = result
+        .take(0)
+        .map_err(|e| format!("Failed to parse balance snapshot: {}", e))?;
+    Ok(records.into_iter().next().map(from_db_value))
+}
+
+async fn prune_history(state: &DbState) {
+    let cutoff = (Utc::now() - chrono::Duration::days(HISTORY_RETENTION_DAYS)).to_rfc3339();
+    if let Err(e) = state
+        .db()
+        .query("DELETE provider_balance_snapshot WHERE checked_at < $cutoff")
+        .bind(("cutoff", cutoff))
+        .await
+    {
+        log::warn!("Failed to prune provider balance history: {}", e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(hours: i64, balance: f64) -> (DateTime<Utc>, f64) {
+        let start = DateTime::parse_from_rfc3339("2026-01-01T00:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        (start + chrono::Duration::hours(hours), balance)
+    }
+
+    #[test]
+    fn burn_rate_ignores_top_ups() {
+        // 两天内消耗 4 + 2，中途充值 10 不计入
+        let points = [at(0, 20.0), at(24, 16.0), at(30, 26.0), at(48, 24.0)];
+        let rate = burn_rate_per_day(&points).unwrap();
+        assert!((rate - 3.0).abs() < 1e-9);
+        assert!((days_remaining(24.0, rate).unwrap() - 8.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn burn_rate_needs_enough_span() {
+        assert_eq!(burn_rate_per_day(&[]), None);
+        assert_eq!(burn_rate_per_day(&[at(0, 5.0)]), None);
+        assert_eq!(days_remaining(5.0, 0.0), None);
+        assert!(is_low_balance(Some(1.0), 5.0));
+        assert!(!is_low_balance(Some(1.0), 0.0));
+        assert!(!is_low_balance(None, 5.0));
+    }
+}
@@ -3,6 +3,7 @@ use super::types::{
     ClaudePromptConfigContent,
 };
 use crate::coding::db_id::db_extract_id;
+use crate::secret_vault;
 use chrono::Local;
 use serde_json::{json, Value};
 
@@ -72,11 +73,20 @@ pub fn from_db_value_provider(value: Value) -> ClaudeCodeProvider {
 }
 
 /// Convert ClaudeCodeProviderContent to database Value
+/// API Key 存入密钥保险库，settings_config 中只保存引用
 pub fn to_db_value_provider(content: &ClaudeCodeProviderContent) -> Value {
-    serde_json::to_value(content).unwrap_or_else(|e| {
+    let mut value = serde_json::to_value(content).unwrap_or_else(|e| {
         eprintln!("Failed to serialize provider content: {}", e);
         json!({})
-    })
+    });
+    if let Some(Value::String(config)) = value.get_mut("settings_config") {
+        *config = secret_vault::seal_json_fields(
+            &format!("claude_provider/{}", content.name),
+            config,
+            secret_vault::CLAUDE_SECRET_FIELDS,
+        );
+    }
+    value
 }
 
 // ============================================================================
@@ -6,9 +6,13 @@ use std::path::Path;
 use super::adapter;
 use super::types::*;
 use crate::coding::all_api_hub;
+use crate::coding::config_drift::{diff_values, ConfigDrift};
+use crate::coding::config_history;
+use crate::coding::config_validation::{self, ConfigKind, ValidationReport};
 use crate::coding::db_id::{db_new_id, db_record_id};
 use crate::coding::prompt_file::{read_prompt_content_file, write_prompt_content_file};
 use crate::db::DbState;
+use crate::secret_vault;
 use tauri::Emitter;
 
 const KNOWN_ENV_FIELDS: [&str; 8] = [
@@ -22,10 +26,37 @@ const KNOWN_ENV_FIELDS: [&str; 8] = [
     "ANTHROPIC_REASONING_MODEL",
 ];
 
-fn get_claude_prompt_file_path() -> Result<std::path::PathBuf, String> {
-    let home_dir = std::env::var("USERPROFILE")
+/// provider settings_config 中的模型字段与写入 env 的变量名
+const MODEL_ENV_FIELDS: [(&str, &str); 5] = [
+    ("model", "ANTHROPIC_MODEL"),
+    ("haikuModel", "ANTHROPIC_DEFAULT_HAIKU_MODEL"),
+    ("sonnetModel", "ANTHROPIC_DEFAULT_SONNET_MODEL"),
+    ("opusModel", "ANTHROPIC_DEFAULT_OPUS_MODEL"),
+    ("reasoningModel", "ANTHROPIC_REASONING_MODEL"),
+];
+
+/// 测试中替换用户目录，不修改进程的 HOME 环境变量（同一测试进程中的其他用例会并行读取）
+#[cfg(test)]
+pub(crate) static TEST_HOME_DIR: std::sync::Mutex<Option<std::path::PathBuf>> =
+    std::sync::Mutex::new(None);
+
+fn get_home_dir() -> Result<std::path::PathBuf, String> {
+    #[cfg(test)]
+    if let Some(home) = TEST_HOME_DIR
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone()
+    {
+        return Ok(home);
+    }
+    std::env::var("USERPROFILE")
         .or_else(|_| std::env::var("HOME"))
-        .map_err(|_| "Failed to get home directory".to_string())?;
+        .map(std::path::PathBuf::from)
+        .map_err(|_| "Failed to get home directory".to_string())
+}
+
+fn get_claude_prompt_file_path() -> Result<std::path::PathBuf, String> {
+    let home_dir = get_home_dir()?;
 
     Ok(Path::new(&home_dir).join(".claude").join("CLAUDE.md"))
 }
@@ -50,7 +81,7 @@ async fn get_local_prompt_config() -> Result<Option<ClaudePromptConfig>, String>
 
 fn write_prompt_content_to_file(prompt_content: Option<&str>) -> Result<(), String> {
     let prompt_path = get_claude_prompt_file_path()?;
-    write_prompt_content_file(&prompt_path, prompt_content, "Claude Code")
+    write_prompt_content_file(&prompt_path, prompt_content, "Claude Code", "claudecode")
 }
 
 fn emit_prompt_sync_requests<R: tauri::Runtime>(app: &tauri::AppHandle<R>) {
@@ -70,7 +101,13 @@ pub async fn list_claude_providers(
     state: tauri::State<'_, DbState>,
 ) -> Result<Vec<ClaudeCodeProvider>, String> {
     let db = state.db();
+    list_providers_internal(&db).await
+}
 
+/// 内部共享函数：读取所有 provider（命令行工具也会调用）
+pub async fn list_providers_internal(
+    db: &surrealdb::Surreal<surrealdb::engine::local::Db>,
+) -> Result<Vec<ClaudeCodeProvider>, String> {
     let records_result: Result<Vec<Value>, _> = db
         .query("SELECT *, type::string(id) as id FROM claude_provider")
         .await
@@ -429,9 +466,7 @@ pub async fn select_claude_provider(
 /// Get Claude config file path (~/.claude/settings.json)
 #[tauri::command]
 pub fn get_claude_config_path() -> Result<String, String> {
-    let home_dir = std::env::var("USERPROFILE")
-        .or_else(|_| std::env::var("HOME"))
-        .map_err(|_| "Failed to get home directory".to_string())?;
+    let home_dir = get_home_dir()?;
 
     let config_path = Path::new(&home_dir).join(".claude").join("settings.json");
     Ok(config_path.to_string_lossy().to_string())
@@ -440,9 +475,7 @@ pub fn get_claude_config_path() -> Result<String, String> {
 /// Reveal Claude config folder in file explorer
 #[tauri::command]
 pub fn reveal_claude_config_folder() -> Result<(), String> {
-    let home_dir = std::env::var("USERPROFILE")
-        .or_else(|_| std::env::var("HOME"))
-        .map_err(|_| "Failed to get home directory".to_string())?;
+    let home_dir = get_home_dir()?;
 
     let config_dir = Path::new(&home_dir).join(".claude");
 
@@ -516,7 +549,43 @@ pub async fn apply_config_to_file_public(
     db: &surrealdb::Surreal<surrealdb::engine::local::Db>,
     provider_id: &str,
 ) -> Result<(), String> {
-    // Get the provider
+    let provider = load_provider(db, provider_id).await?;
+
+    // Check if provider is disabled
+    if provider.is_disabled {
+        return Err(format!(
+            "Provider '{}' is disabled and cannot be applied",
+            provider_id
+        ));
+    }
+
+    let final_settings = build_expected_settings(db, &provider).await?;
+
+    // Write to settings.json
+    let config_path_str = get_claude_config_path()?;
+    let config_path = Path::new(&config_path_str);
+
+    // Ensure directory exists
+    if let Some(parent) = config_path.parent() {
+        if !parent.exists() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create .claude directory: {}", e))?;
+        }
+    }
+
+    let json_content = serde_json::to_string_pretty(&final_settings)
+        .map_err(|e| format!("Failed to serialize settings: {}", e))?;
+
+    config_history::write(config_path, json_content, "claudecode", "apply_provider")
+        .map_err(|e| format!("Failed to write settings file: {}", e))?;
+
+    Ok(())
+}
+
+async fn load_provider(
+    db: &surrealdb::Surreal<surrealdb::engine::local::Db>,
+    provider_id: &str,
+) -> Result<ClaudeCodeProvider, String> {
     let record_id = db_record_id("claude_provider", provider_id);
     let provider_result: Result<Vec<Value>, _> = db
         .query(&format!(
@@ -527,30 +596,41 @@ pub async fn apply_config_to_file_public(
         .map_err(|e| format!("Failed to query provider: {}", e))?
         .take(0);
 
-    let provider = match provider_result {
-        Ok(records) => {
-            if let Some(record) = records.first() {
-                adapter::from_db_value_provider(record.clone())
-            } else {
-                return Err("Provider not found".to_string());
-            }
-        }
-        Err(e) => {
-            return Err(format!("Failed to deserialize provider: {}", e));
-        }
-    };
-
-    // Check if provider is disabled
-    if provider.is_disabled {
-        return Err(format!(
-            "Provider '{}' is disabled and cannot be applied",
-            provider_id
-        ));
+    match provider_result {
+        Ok(records) => match records.first() {
+            Some(record) => Ok(adapter::from_db_value_provider(record.clone())),
+            None => Err("Provider not found".to_string()),
+        },
+        Err(e) => Err(format!("Failed to deserialize provider: {}", e)),
     }
+}
+
+pub async fn load_applied_provider(
+    db: &surrealdb::Surreal<surrealdb::engine::local::Db>,
+) -> Result<Option<ClaudeCodeProvider>, String> {
+    let applied_result: Result<Vec<Value>, _> = db
+        .query(
+            "SELECT *, type::string(id) as id FROM claude_provider WHERE is_applied = true LIMIT 1",
+        )
+        .await
+        .map_err(|e| format!("Failed to query applied provider: {}", e))?
+        .take(0);
 
-    // Parse provider settings_config
-    let provider_config: serde_json::Value = serde_json::from_str(&provider.settings_config)
-        .map_err(|e| format!("Failed to parse provider config: {}", e))?;
+    Ok(applied_result
+        .ok()
+        .and_then(|records| records.first().cloned())
+        .map(adapter::from_db_value_provider))
+}
+
+/// 按 provider 与通用配置生成 settings.json 应有的完整内容
+async fn build_expected_settings(
+    db: &surrealdb::Surreal<surrealdb::engine::local::Db>,
+    provider: &ClaudeCodeProvider,
+) -> Result<serde_json::Map<String, Value>, String> {
+    // Parse provider settings_config (保险库引用在此解出明文)
+    let provider_config: serde_json::Value =
+        serde_json::from_str(&secret_vault::resolve_json_text(&provider.settings_config)?)
+            .map_err(|e| format!("Failed to parse provider config: {}", e))?;
 
     // Get common config
     let common_config_result: Result<Vec<Value>, _> = db
@@ -597,36 +677,28 @@ pub async fn apply_config_to_file_public(
         }
     }
 
-    if let Some(model) = provider_config.get("model").and_then(|v| v.as_str()) {
-        env.insert("ANTHROPIC_MODEL".to_string(), serde_json::json!(model));
-    }
-
-    if let Some(haiku) = provider_config.get("haikuModel").and_then(|v| v.as_str()) {
-        env.insert(
-            "ANTHROPIC_DEFAULT_HAIKU_MODEL".to_string(),
-            serde_json::json!(haiku),
-        );
-    }
-
-    if let Some(sonnet) = provider_config.get("sonnetModel").and_then(|v| v.as_str()) {
-        env.insert(
-            "ANTHROPIC_DEFAULT_SONNET_MODEL".to_string(),
-            serde_json::json!(sonnet),
-        );
+    for (field, env_key) in MODEL_ENV_FIELDS {
+        if let Some(model) = provider_config.get(field).and_then(|v| v.as_str()) {
+            env.insert(env_key.to_string(), serde_json::json!(model));
+        }
     }
 
-    if let Some(opus) = provider_config.get("opusModel").and_then(|v| v.as_str()) {
+    // 开启本地网关时，地址与密钥改为网关，由网关转发到当前应用的 provider
+    if let Some(gateway) = crate::llm_gateway::claude_endpoint(db).await {
         env.insert(
-            "ANTHROPIC_DEFAULT_OPUS_MODEL".to_string(),
-            serde_json::json!(opus),
+            "ANTHROPIC_BASE_URL".to_string(),
+            serde_json::json!(gateway.base_url),
         );
-    }
-
-    if let Some(reasoning) = provider_config.get("reasoningModel").and_then(|v| v.as_str()) {
         env.insert(
-            "ANTHROPIC_REASONING_MODEL".to_string(),
-            serde_json::json!(reasoning),
+            "ANTHROPIC_AUTH_TOKEN".to_string(),
+            serde_json::json!(gateway.token),
         );
+    } else if uses_openai_chat_format(&provider_config) {
+        // OpenAI 格式的上游 Claude Code 无法直接访问，只能经网关转换协议
+        return Err(format!(
+            "Provider '{}' uses the OpenAI Chat Completions format and requires the local LLM gateway. Enable it in Settings first.",
+            provider.name
+        ));
     }
 
     // Merge common config and provider env
@@ -652,26 +724,151 @@ pub async fn apply_config_to_file_public(
     final_settings.remove("env");
     final_settings.insert("env".to_string(), serde_json::json!(merged_env));
 
-    // Write to settings.json
-    let config_path_str = get_claude_config_path()?;
-    let config_path = Path::new(&config_path_str);
+    Ok(final_settings)
+}
 
-    // Ensure directory exists
-    if let Some(parent) = config_path.parent() {
-        if !parent.exists() {
-            fs::create_dir_all(parent)
-                .map_err(|e| format!("Failed to create .claude directory: {}", e))?;
-        }
+/// 读取 settings.json，文件不存在时视为空对象
+fn read_settings_value(config_path: &str) -> Result<Value, String> {
+    let config_path = Path::new(config_path);
+    if !config_path.exists() {
+        return Ok(serde_json::json!({}));
     }
 
-    let json_content = serde_json::to_string_pretty(&final_settings)
-        .map_err(|e| format!("Failed to serialize settings: {}", e))?;
+    let content = fs::read_to_string(config_path)
+        .map_err(|e| format!("Failed to read settings file: {}", e))?;
+    serde_json::from_str(&content).map_err(|e| format!("Failed to parse settings file: {}", e))
+}
 
-    fs::write(config_path, json_content)
-        .map_err(|e| format!("Failed to write settings file: {}", e))?;
+/// 检测 settings.json 是否被外部修改（与当前应用的 provider + 通用配置对比）
+/// 没有已应用的 provider 时返回 None
+#[tauri::command]
+pub async fn detect_claude_config_drift(
+    state: tauri::State<'_, DbState>,
+) -> Result<Option<ConfigDrift>, String> {
+    let db = state.db();
+
+    let provider = match load_applied_provider(&db).await? {
+        Some(provider) if !provider.is_disabled => provider,
+        _ => return Ok(None),
+    };
+
+    let expected = build_expected_settings(&db, &provider).await?;
+    let config_path = get_claude_config_path()?;
+    let actual = read_settings_value(&config_path)?;
+    let entries = diff_values(&Value::Object(expected), &actual);
+
+    Ok(Some(ConfigDrift::new(
+        "claudecode",
+        Some(provider.id),
+        Some(provider.name),
+        config_path,
+        entries,
+    )))
+}
+
+/// 将 settings.json 的 env 写回 provider 配置：
+/// 保留 provider 中与文件无关的字段，只覆盖会写入 settings.json 的字段。
+/// 开启网关时文件中的地址与密钥是网关的，不写回，保留 provider 原有的上游地址与密钥
+fn provider_config_from_settings(
+    mut provider_config: serde_json::Map<String, Value>,
+    env_obj: &serde_json::Map<String, Value>,
+    gateway_active: bool,
+) -> serde_json::Map<String, Value> {
+    if !gateway_active {
+        let mut provider_env = provider_config
+            .get("env")
+            .and_then(|v| v.as_object())
+            .cloned()
+            .unwrap_or_default();
+
+        provider_env.remove("ANTHROPIC_API_KEY");
+        match env_obj
+            .get("ANTHROPIC_AUTH_TOKEN")
+            .or_else(|| env_obj.get("ANTHROPIC_API_KEY"))
+        {
+            Some(key) => provider_env.insert("ANTHROPIC_AUTH_TOKEN".to_string(), key.clone()),
+            None => provider_env.remove("ANTHROPIC_AUTH_TOKEN"),
+        };
+        match env_obj.get("ANTHROPIC_BASE_URL") {
+            Some(base_url) => {
+                provider_env.insert("ANTHROPIC_BASE_URL".to_string(), base_url.clone())
+            }
+            None => provider_env.remove("ANTHROPIC_BASE_URL"),
+        };
+        provider_config.insert("env".to_string(), serde_json::json!(provider_env));
+    }
+
+    for (field, env_key) in MODEL_ENV_FIELDS {
+        match env_obj.get(env_key) {
+            Some(model) => provider_config.insert(field.to_string(), model.clone()),
+            None => provider_config.remove(field),
+        };
+    }
+
+    provider_config
+}
+
+/// 将 settings.json 中的外部修改写回数据库：
+/// 已知 env 字段写回当前应用的 provider，其余字段写入通用配置
+#[tauri::command]
+pub async fn import_claude_config_drift(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+) -> Result<(), String> {
+    let db = state.db();
+
+    let provider = load_applied_provider(&db)
+        .await?
+        .ok_or_else(|| "No applied Claude Code provider".to_string())?;
+
+    let settings = read_settings_value(&get_claude_config_path()?)?;
+    let settings_obj = settings
+        .as_object()
+        .ok_or_else(|| "Invalid settings format".to_string())?;
+    let env_obj = settings_obj
+        .get("env")
+        .and_then(|v| v.as_object())
+        .cloned()
+        .unwrap_or_default();
+
+    let gateway_active = crate::llm_gateway::claude_endpoint(&db).await.is_some();
+    let provider_config = provider_config_from_settings(
+        serde_json::from_str(&provider.settings_config).unwrap_or_default(),
+        &env_obj,
+        gateway_active,
+    );
+
+    let settings_config = serde_json::to_string(&provider_config)
+        .map_err(|e| format!("Failed to serialize provider settings: {}", e))?;
+    let settings_config = secret_vault::seal_json_fields(
+        &format!("claude_provider/{}", provider.name),
+        &settings_config,
+        secret_vault::CLAUDE_SECRET_FIELDS,
+    );
+    let common_config = serde_json::to_string(&extract_common_settings(settings_obj))
+        .map_err(|e| format!("Failed to serialize common config: {}", e))?;
+
+    let now = Local::now().to_rfc3339();
+    let record_id = db_record_id("claude_provider", &provider.id);
+    db.query(format!(
+        "UPDATE {} SET settings_config = $settings_config, updated_at = $now",
+        record_id
+    ))
+    .bind(("settings_config", settings_config))
+    .bind(("now", now))
+    .await
+    .map_err(|e| format!("Failed to update provider: {}", e))?;
+
+    db.query("UPSERT claude_common_config:`common` CONTENT $data")
+        .bind(("data", adapter::to_db_value_common(&common_config)))
+        .await
+        .map_err(|e| format!("Failed to save common config: {}", e))?;
+
+    let _ = app.emit("config-changed", "window");
 
     Ok(())
 }
+
 /// Toggle is_disabled status for a provider
 #[tauri::command]
 pub async fn toggle_claude_code_provider_disabled(
@@ -714,7 +911,7 @@ pub async fn toggle_claude_code_provider_disabled(
 
         if is_applied {
             // Re-apply config to update files (will check is_disabled internally)
-            apply_config_internal(&db, &app, &provider_id, false).await?;
+            apply_config_internal(&db, &app, &provider_id, false, false).await?;
         }
     }
 
@@ -722,14 +919,16 @@ pub async fn toggle_claude_code_provider_disabled(
 }
 
 /// Apply Claude Code provider configuration to settings.json
+/// `force` 为 true 时即使校验出错误也写入
 #[tauri::command]
 pub async fn apply_claude_config(
     state: tauri::State<'_, DbState>,
     app: tauri::AppHandle,
     provider_id: String,
+    force: Option<bool>,
 ) -> Result<(), String> {
     let db = state.db();
-    apply_config_internal(&db, &app, &provider_id, false).await
+    apply_config_internal(&db, &app, &provider_id, false, force.unwrap_or(false)).await
 }
 
 /// Internal function to apply config: writes to file and updates database
@@ -739,6 +938,40 @@ pub async fn apply_config_internal<R: tauri::Runtime>(
     app: &tauri::AppHandle<R>,
     provider_id: &str,
     from_tray: bool,
+    force: bool,
+) -> Result<(), String> {
+    let report = config_validation::check(validate_provider_config(db, provider_id).await?, force)?;
+    apply_provider(db, provider_id).await?;
+    config_validation::emit_warnings(app, &report);
+
+    // Notify based on source
+    let payload = if from_tray { "tray" } else { "window" };
+    let _ = app.emit("config-changed", payload);
+
+    // Trigger WSL sync via event (Windows only)
+    #[cfg(target_os = "windows")]
+    let _ = app.emit("wsl-sync-request-claude", ());
+
+    Ok(())
+}
+
+/// 校验 provider 应用后 settings.json 的完整内容
+async fn validate_provider_config(
+    db: &surrealdb::Surreal<surrealdb::engine::local::Db>,
+    provider_id: &str,
+) -> Result<ValidationReport, String> {
+    let provider = load_provider(db, provider_id).await?;
+    let settings = build_expected_settings(db, &provider).await?;
+    Ok(config_validation::validate_json(
+        ConfigKind::ClaudeSettings,
+        &Value::Object(settings),
+    ))
+}
+
+/// 应用 provider 到配置文件并更新 is_applied，不发送事件（供命令行工具使用）
+pub async fn apply_provider(
+    db: &surrealdb::Surreal<surrealdb::engine::local::Db>,
+    provider_id: &str,
 ) -> Result<(), String> {
     // 应用配置到文件
     apply_config_to_file(db, provider_id).await?;
@@ -764,13 +997,16 @@ pub async fn apply_config_internal<R: tauri::Runtime>(
     .await
     .map_err(|e| format!("Failed to set applied status: {}", e))?;
 
-    // Notify based on source
-    let payload = if from_tray { "tray" } else { "window" };
-    let _ = app.emit("config-changed", payload);
-
-    // Trigger WSL sync via event (Windows only)
-    #[cfg(target_os = "windows")]
-    let _ = app.emit("wsl-sync-request-claude", ());
+    // 记录切换时间，用量统计据此把会话日志归属到 provider
+    if let Ok(provider) = load_provider(db, provider_id).await {
+        crate::coding::usage::record_provider_applied(
+            db,
+            crate::coding::usage::SOURCE_CLAUDE_CODE,
+            provider_id,
+            &provider.name,
+        )
+        .await;
+    }
 
     Ok(())
 }
@@ -1213,6 +1449,20 @@ async fn load_temp_common_config_from_file() -> Result<ClaudeCommonConfig, Strin
         None => return Err("Invalid settings format".to_string()),
     };
 
+    let common_config = extract_common_settings(settings_obj);
+
+    let now = Local::now().to_rfc3339();
+    Ok(ClaudeCommonConfig {
+        config: serde_json::to_string(&common_config)
+            .map_err(|e| format!("Failed to serialize: {}", e))?,
+        updated_at: now,
+    })
+}
+
+/// Extract non-env fields and unknown env fields from settings.json as common config
+fn extract_common_settings(
+    settings_obj: &serde_json::Map<String, Value>,
+) -> serde_json::Map<String, Value> {
     let mut common_config = serde_json::Map::new();
 
     // Add non-env fields to common config
@@ -1235,12 +1485,7 @@ async fn load_temp_common_config_from_file() -> Result<ClaudeCommonConfig, Strin
         }
     }
 
-    let now = Local::now().to_rfc3339();
-    Ok(ClaudeCommonConfig {
-        config: serde_json::to_string(&common_config)
-            .map_err(|e| format!("Failed to serialize: {}", e))?,
-        updated_at: now,
-    })
+    common_config
 }
 
 /// Save Claude common config
@@ -1249,12 +1494,17 @@ pub async fn save_claude_common_config(
     state: tauri::State<'_, DbState>,
     app: tauri::AppHandle,
     config: String,
+    force: Option<bool>,
 ) -> Result<(), String> {
     let db = state.db();
 
     // Validate JSON
-    let _: serde_json::Value =
+    let parsed: serde_json::Value =
         serde_json::from_str(&config).map_err(|e| format!("Invalid JSON: {}", e))?;
+    let report = config_validation::check(
+        config_validation::validate_fragment_json(ConfigKind::ClaudeSettings, &parsed),
+        force.unwrap_or(false),
+    )?;
 
     let json_data = adapter::to_db_value_common(&config);
 
@@ -1287,6 +1537,8 @@ pub async fn save_claude_common_config(
         }
     }
 
+    config_validation::emit_warnings(&app, &report);
+
     // Notify frontend to refresh
     let _ = app.emit("config-changed", "window");
 
@@ -1397,9 +1649,7 @@ pub async fn save_claude_local_config(
 
 /// Get Claude plugin config path (~/.claude/config.json)
 fn get_claude_plugin_config_path() -> Result<std::path::PathBuf, String> {
-    let home_dir = std::env::var("USERPROFILE")
-        .or_else(|_| std::env::var("HOME"))
-        .map_err(|_| "Failed to get home directory".to_string())?;
+    let home_dir = get_home_dir()?;
 
     Ok(std::path::Path::new(&home_dir)
         .join(".claude")
@@ -1490,8 +1740,13 @@ pub async fn apply_claude_plugin_config(enabled: bool) -> Result<bool, String> {
     let serialized = serde_json::to_string_pretty(&serde_json::Value::Object(obj))
         .map_err(|e| format!("Failed to serialize config: {}", e))?;
 
-    fs::write(&config_path, format!("{serialized}\n"))
-        .map_err(|e| format!("Failed to write config file: {}", e))?;
+    config_history::write(
+        &config_path,
+        format!("{serialized}\n"),
+        "claudecode",
+        "plugin_config",
+    )
+    .map_err(|e| format!("Failed to write config file: {}", e))?;
 
     Ok(true)
 }
@@ -1675,9 +1930,7 @@ pub async fn init_claude_provider_from_settings(
 
 /// Get the Claude MCP config path (~/.claude.json)
 fn get_claude_mcp_config_path() -> Result<std::path::PathBuf, String> {
-    let home_dir = std::env::var("USERPROFILE")
-        .or_else(|_| std::env::var("HOME"))
-        .map_err(|_| "Failed to get home directory".to_string())?;
+    let home_dir = get_home_dir()?;
 
     Ok(std::path::Path::new(&home_dir).join(".claude.json"))
 }
@@ -1752,8 +2005,13 @@ pub async fn apply_claude_onboarding_skip() -> Result<bool, String> {
     let serialized = serde_json::to_string_pretty(&serde_json::Value::Object(obj))
         .map_err(|e| format!("Failed to serialize config: {}", e))?;
 
-    fs::write(&config_path, format!("{serialized}\n"))
-        .map_err(|e| format!("Failed to write config file: {}", e))?;
+    config_history::write(
+        &config_path,
+        format!("{serialized}\n"),
+        "claudecode",
+        "onboarding_skip",
+    )
+    .map_err(|e| format!("Failed to write config file: {}", e))?;
 
     Ok(true)
 }
@@ -1788,8 +2046,13 @@ pub async fn clear_claude_onboarding_skip() -> Result<bool, String> {
     let serialized = serde_json::to_string_pretty(&serde_json::Value::Object(obj))
         .map_err(|e| format!("Failed to serialize config: {}", e))?;
 
-    fs::write(&config_path, format!("{serialized}\n"))
-        .map_err(|e| format!("Failed to write config file: {}", e))?;
+    config_history::write(
+        &config_path,
+        format!("{serialized}\n"),
+        "claudecode",
+        "onboarding_restore",
+    )
+    .map_err(|e| format!("Failed to write config file: {}", e))?;
 
     Ok(true)
 }
@@ -1829,6 +2092,7 @@ pub async fn list_claude_all_api_hub_providers(
             site_name: candidate.site_name.clone(),
             site_type: candidate.site_type.clone(),
             account_label: candidate.account_label.clone(),
+            source_browser: candidate.source_browser.clone(),
             source_profile_name: candidate.source_profile_name.clone(),
             source_extension_id: candidate.source_extension_id.clone(),
             provider_config: serde_json::to_value(all_api_hub::candidate_to_opencode_provider(
@@ -1881,6 +2145,7 @@ pub async fn resolve_claude_all_api_hub_providers(
             site_name: candidate.site_name.clone(),
             site_type: candidate.site_type.clone(),
             account_label: candidate.account_label.clone(),
+            source_browser: candidate.source_browser.clone(),
             source_profile_name: candidate.source_profile_name.clone(),
             source_extension_id: candidate.source_extension_id.clone(),
             provider_config: serde_json::to_value(all_api_hub::candidate_to_opencode_provider(
@@ -1890,3 +2155,72 @@ pub async fn resolve_claude_all_api_hub_providers(
         })
         .collect())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn provider_config_from_settings_writes_back_known_env_fields() {
+        let provider_config = serde_json::json!({
+            "env": {
+                "ANTHROPIC_API_KEY": "old-key",
+                "ANTHROPIC_BASE_URL": "https://old.example.com",
+            },
+            "model": "old-model",
+            "opusModel": "old-opus",
+            "notes": "kept",
+        });
+        let env_obj = serde_json::json!({
+            "ANTHROPIC_AUTH_TOKEN": "new-key",
+            "ANTHROPIC_BASE_URL": "https://new.example.com",
+            "ANTHROPIC_MODEL": "new-model",
+            "DISABLE_TELEMETRY": "1",
+        });
+
+        let config = provider_config_from_settings(
+            provider_config.as_object().unwrap().clone(),
+            env_obj.as_object().unwrap(),
+            false,
+        );
+
+        let env = config["env"].as_object().unwrap();
+        assert_eq!(env["ANTHROPIC_AUTH_TOKEN"], "new-key");
+        assert_eq!(env["ANTHROPIC_BASE_URL"], "https://new.example.com");
+        assert!(!env.contains_key("ANTHROPIC_API_KEY"));
+        // 未知 env 字段属于通用配置，不写回 provider
+        assert!(!env.contains_key("DISABLE_TELEMETRY"));
+        assert_eq!(config["model"], "new-model");
+        // 文件中已删除的模型字段同步删除
+        assert!(!config.contains_key("opusModel"));
+        assert_eq!(config["notes"], "kept");
+    }
+
+    #[test]
+    fn provider_config_from_settings_keeps_upstream_when_gateway_active() {
+        let provider_config = serde_json::json!({
+            "env": {
+                "ANTHROPIC_AUTH_TOKEN": "sk-upstream",
+                "ANTHROPIC_BASE_URL": "https://relay.example.com",
+            },
+        });
+        let env_obj = serde_json::json!({
+            "ANTHROPIC_AUTH_TOKEN": "gateway-token",
+            "ANTHROPIC_BASE_URL": "http://127.0.0.1:15721/claude",
+            "ANTHROPIC_MODEL": "claude-opus",
+        });
+
+        let config = provider_config_from_settings(
+            provider_config.as_object().unwrap().clone(),
+            env_obj.as_object().unwrap(),
+            true,
+        );
+
+        assert_eq!(config["env"]["ANTHROPIC_AUTH_TOKEN"], "sk-upstream");
+        assert_eq!(
+            config["env"]["ANTHROPIC_BASE_URL"],
+            "https://relay.example.com"
+        );
+        assert_eq!(config["model"], "claude-opus");
+    }
+}
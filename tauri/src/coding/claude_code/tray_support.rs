@@ -5,11 +5,13 @@
 use crate::coding::claude_code::apply_config_internal;
 use crate::coding::db_id::db_clean_id;
 use crate::db::DbState;
+use serde::Serialize;
 use serde_json::Value;
 use tauri::{AppHandle, Manager, Runtime};
 
 /// Item for provider selection in tray menu
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct TrayProviderItem {
     /// Provider ID (used in event handling)
     pub id: String,
@@ -20,11 +22,13 @@ pub struct TrayProviderItem {
     /// Whether this provider is disabled
     pub is_disabled: bool,
     /// Sort index for ordering
+    #[serde(skip)]
     pub sort_index: i64,
 }
 
 /// Data for provider submenu
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct TrayProviderData {
     /// Title of the section
     pub title: String,
@@ -113,7 +117,7 @@ pub async fn apply_claude_code_provider<R: Runtime>(
     let state = app.state::<DbState>();
     let db = state.db();
 
-    apply_config_internal(&db, app, provider_id, true).await?;
+    apply_config_internal(&db, app, provider_id, true, false).await?;
 
     Ok(())
 }
@@ -244,6 +244,7 @@ pub struct ClaudeAllApiHubProvider {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub site_type: Option<String>,
     pub account_label: String,
+    pub source_browser: String,
     pub source_profile_name: String,
     pub source_extension_id: String,
     pub provider_config: serde_json::Value,
@@ -264,3 +265,12 @@ pub struct ClaudeAllApiHubProvidersResult {
 pub struct ResolveClaudeAllApiHubProvidersRequest {
     pub provider_ids: Vec<String>,
 }
+
+/// provider settings_config 中 `apiFormat` 的取值：上游只提供 OpenAI Chat Completions 接口，
+/// 由本地网关把 Anthropic Messages 请求转换后转发，ANTHROPIC_BASE_URL 保存 OpenAI base URL（含 /v1）
+pub const API_FORMAT_OPENAI_CHAT: &str = "openai_chat";
+
+/// provider 是否需要经网关做 Anthropic → OpenAI 协议转换
+pub fn uses_openai_chat_format(settings_config: &serde_json::Value) -> bool {
+    settings_config.get("apiFormat").and_then(|v| v.as_str()) == Some(API_FORMAT_OPENAI_CHAT)
+}
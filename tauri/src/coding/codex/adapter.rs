@@ -6,6 +6,7 @@ use super::types::{
     CodexProviderContent,
 };
 use crate::coding::db_id::db_extract_id;
+use crate::secret_vault;
 
 // ============================================================================
 // Provider Adapter Functions
@@ -80,6 +81,7 @@ pub fn from_db_value_provider(value: Value) -> CodexProvider {
 }
 
 /// Convert CodexProviderContent to database value
+/// auth.json 中的密钥存入密钥保险库，settings_config 中只保存引用
 pub fn to_db_value_provider(content: &CodexProviderContent) -> Value {
     let mut map = serde_json::Map::new();
     map.insert("name".to_string(), Value::String(content.name.clone()));
@@ -89,7 +91,11 @@ pub fn to_db_value_provider(content: &CodexProviderContent) -> Value {
     );
     map.insert(
         "settings_config".to_string(),
-        Value::String(content.settings_config.clone()),
+        Value::String(secret_vault::seal_json_fields(
+            &format!("codex_provider/{}", content.name),
+            &content.settings_config,
+            secret_vault::CODEX_SECRET_FIELDS,
+        )),
     );
 
     if let Some(ref source_id) = content.source_provider_id {
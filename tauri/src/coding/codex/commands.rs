@@ -5,9 +5,13 @@ use std::path::Path;
 use super::adapter;
 use super::types::*;
 use crate::coding::all_api_hub;
+use crate::coding::config_drift::{diff_values_at, ConfigDrift};
+use crate::coding::config_history;
+use crate::coding::config_validation::{self, ConfigKind, ValidationReport};
 use crate::coding::db_id::{db_new_id, db_record_id};
 use crate::coding::prompt_file::{read_prompt_content_file, write_prompt_content_file};
 use crate::db::DbState;
+use crate::secret_vault;
 use chrono::Local;
 use tauri::Emitter;
 
@@ -30,7 +34,7 @@ fn get_codex_auth_path() -> Result<std::path::PathBuf, String> {
 }
 
 /// Get Codex config.toml path
-fn get_codex_config_path() -> Result<std::path::PathBuf, String> {
+pub(super) fn get_codex_config_path() -> Result<std::path::PathBuf, String> {
     Ok(get_codex_config_dir()?.join("config.toml"))
 }
 
@@ -58,7 +62,7 @@ async fn get_local_prompt_config() -> Result<Option<CodexPromptConfig>, String>
 
 fn write_prompt_content_to_file(prompt_content: Option<&str>) -> Result<(), String> {
     let prompt_path = get_codex_prompt_file_path()?;
-    write_prompt_content_file(&prompt_path, prompt_content, "Codex")
+    write_prompt_content_file(&prompt_path, prompt_content, "Codex", "codex")
 }
 
 fn emit_prompt_sync_requests<R: tauri::Runtime>(app: &tauri::AppHandle<R>) {
@@ -132,7 +136,13 @@ pub async fn list_codex_providers(
     state: tauri::State<'_, DbState>,
 ) -> Result<Vec<CodexProvider>, String> {
     let db = state.db();
+    list_providers_internal(&db).await
+}
 
+/// 内部共享函数：读取所有 provider（命令行工具也会调用）
+pub async fn list_providers_internal(
+    db: &surrealdb::Surreal<surrealdb::engine::local::Db>,
+) -> Result<Vec<CodexProvider>, String> {
     let records_result: Result<Vec<Value>, _> = db
         .query("SELECT *, type::string(id) as id FROM codex_provider")
         .await
@@ -568,7 +578,26 @@ pub async fn apply_config_to_file_public(
     db: &surrealdb::Surreal<surrealdb::engine::local::Db>,
     provider_id: &str,
 ) -> Result<(), String> {
-    // Get the provider
+    let provider = load_provider(db, provider_id).await?;
+
+    // Check if provider is disabled
+    if provider.is_disabled {
+        return Err(format!(
+            "Provider '{}' is disabled and cannot be applied",
+            provider_id
+        ));
+    }
+
+    let (auth, final_config) = build_expected_config(db, &provider).await?;
+
+    write_codex_config_files(&auth, &final_config)?;
+    Ok(())
+}
+
+async fn load_provider(
+    db: &surrealdb::Surreal<surrealdb::engine::local::Db>,
+    provider_id: &str,
+) -> Result<CodexProvider, String> {
     let record_id = db_record_id("codex_provider", provider_id);
     let provider_result: Result<Vec<Value>, _> = db
         .query(&format!(
@@ -579,47 +608,65 @@ pub async fn apply_config_to_file_public(
         .map_err(|e| format!("Failed to query provider: {}", e))?
         .take(0);
 
-    let provider = match provider_result {
-        Ok(records) => {
-            if let Some(record) = records.first() {
-                adapter::from_db_value_provider(record.clone())
-            } else {
-                return Err("Provider not found".to_string());
-            }
-        }
-        Err(e) => return Err(format!("Failed to deserialize provider: {}", e)),
-    };
-
-    // Check if provider is disabled
-    if provider.is_disabled {
-        return Err(format!(
-            "Provider '{}' is disabled and cannot be applied",
-            provider_id
-        ));
+    match provider_result {
+        Ok(records) => match records.first() {
+            Some(record) => Ok(adapter::from_db_value_provider(record.clone())),
+            None => Err("Provider not found".to_string()),
+        },
+        Err(e) => Err(format!("Failed to deserialize provider: {}", e)),
     }
+}
 
-    // Parse provider settings_config
-    let provider_config: serde_json::Value = serde_json::from_str(&provider.settings_config)
-        .map_err(|e| format!("Failed to parse provider config: {}", e))?;
+pub async fn load_applied_provider(
+    db: &surrealdb::Surreal<surrealdb::engine::local::Db>,
+) -> Result<Option<CodexProvider>, String> {
+    let applied_result: Result<Vec<Value>, _> = db
+        .query(
+            "SELECT *, type::string(id) as id FROM codex_provider WHERE is_applied = true LIMIT 1",
+        )
+        .await
+        .map_err(|e| format!("Failed to query applied provider: {}", e))?
+        .take(0);
 
-    // Get common config
+    Ok(applied_result
+        .ok()
+        .and_then(|records| records.first().cloned())
+        .map(adapter::from_db_value_provider))
+}
+
+async fn load_common_toml(
+    db: &surrealdb::Surreal<surrealdb::engine::local::Db>,
+) -> Result<Option<String>, String> {
     let common_config_result: Result<Vec<Value>, _> = db
         .query("SELECT * OMIT id FROM codex_common_config:`common` LIMIT 1")
         .await
         .map_err(|e| format!("Failed to query common config: {}", e))?
         .take(0);
 
-    let common_toml: Option<String> = match common_config_result {
+    Ok(match common_config_result {
         Ok(records) => records.first().and_then(|r| {
             r.get("config")
                 .and_then(|v| v.as_str())
                 .map(|s| s.to_string())
         }),
         Err(_) => None,
-    };
+    })
+}
+
+/// 按 provider 与通用配置生成 auth.json 内容与 config.toml 中由应用管理的部分
+async fn build_expected_config(
+    db: &surrealdb::Surreal<surrealdb::engine::local::Db>,
+    provider: &CodexProvider,
+) -> Result<(serde_json::Value, String), String> {
+    // Parse provider settings_config (保险库引用在此解出明文)
+    let provider_config: serde_json::Value =
+        serde_json::from_str(&secret_vault::resolve_json_text(&provider.settings_config)?)
+            .map_err(|e| format!("Failed to parse provider config: {}", e))?;
+
+    let common_toml = load_common_toml(db).await?;
 
     // Extract auth and config
-    let auth = provider_config
+    let mut auth = provider_config
         .get("auth")
         .cloned()
         .unwrap_or(serde_json::json!({}));
@@ -640,7 +687,214 @@ pub async fn apply_config_to_file_public(
         config_toml
     };
 
-    write_codex_config_files(&auth, &final_config)?;
+    // 开启本地网关时，config.toml 指向网关，auth.json 中的密钥改为网关 token
+    if let Some(gateway) = crate::llm_gateway::codex_endpoint(db).await {
+        let final_config =
+            crate::llm_gateway::route_codex_config(&final_config, &gateway.base_url)?;
+        if !auth.is_object() {
+            auth = serde_json::json!({});
+        }
+        auth["OPENAI_API_KEY"] = serde_json::json!(gateway.token);
+        return Ok((auth, final_config));
+    }
+
+    Ok((auth, final_config))
+}
+
+fn read_auth_value(auth_path: &Path) -> Result<serde_json::Value, String> {
+    if !auth_path.exists() {
+        return Ok(serde_json::json!({}));
+    }
+    let content =
+        fs::read_to_string(auth_path).map_err(|e| format!("Failed to read auth.json: {}", e))?;
+    serde_json::from_str(&content).map_err(|e| format!("Failed to parse auth.json: {}", e))
+}
+
+pub(super) fn read_config_doc(config_path: &Path) -> Result<toml_edit::DocumentMut, String> {
+    if !config_path.exists() {
+        return Ok(toml_edit::DocumentMut::new());
+    }
+    let content = fs::read_to_string(config_path)
+        .map_err(|e| format!("Failed to read config.toml: {}", e))?;
+    if content.trim().is_empty() {
+        return Ok(toml_edit::DocumentMut::new());
+    }
+    content
+        .parse()
+        .map_err(|e| format!("Failed to parse existing config.toml: {}", e))
+}
+
+fn toml_doc_to_json(doc: &toml_edit::DocumentMut) -> Result<serde_json::Value, String> {
+    let table: toml::Table = toml::from_str(&doc.to_string())
+        .map_err(|e| format!("Failed to parse config.toml: {}", e))?;
+    serde_json::to_value(table).map_err(|e| format!("Failed to convert config.toml: {}", e))
+}
+
+/// 检测 auth.json / config.toml 是否被外部修改（与当前应用的 provider + 通用配置对比）
+/// config.toml 中不由应用管理的键（mcp_servers、Codex 自己写入的字段等）不参与对比
+#[tauri::command]
+pub async fn detect_codex_config_drift(
+    state: tauri::State<'_, DbState>,
+) -> Result<Option<ConfigDrift>, String> {
+    let db = state.db();
+
+    let provider = match load_applied_provider(&db).await? {
+        Some(provider) if !provider.is_disabled => provider,
+        _ => return Ok(None),
+    };
+
+    let (expected_auth, final_config) = build_expected_config(&db, &provider).await?;
+    let auth_path = get_codex_auth_path()?;
+    let config_path = get_codex_config_path()?;
+
+    let actual_auth = read_auth_value(&auth_path)?;
+    let actual_doc = read_config_doc(&config_path)?;
+    // 重新应用时得到的 config.toml：在现有文件上覆盖由应用管理的键
+    let expected_doc = merge_codex_config_toml(actual_doc.clone(), &final_config)?;
+
+    let mut entries = diff_values_at("auth", Some(&expected_auth), Some(&actual_auth));
+    entries.extend(diff_values_at(
+        "config",
+        Some(&toml_doc_to_json(&expected_doc)?),
+        Some(&toml_doc_to_json(&actual_doc)?),
+    ));
+
+    Ok(Some(ConfigDrift::new(
+        "codex",
+        Some(provider.id),
+        Some(provider.name),
+        get_codex_config_dir()?.to_string_lossy().to_string(),
+        entries,
+    )))
+}
+
+/// 将 config.toml 中由应用写入的顶层键写回 provider 或通用配置；
+/// 通用配置中的键优先写回通用配置（合并时通用配置覆盖 provider）
+fn import_drifted_keys(
+    provider_doc: &mut toml_edit::DocumentMut,
+    common_doc: &mut toml_edit::DocumentMut,
+    actual_doc: &toml_edit::DocumentMut,
+) {
+    let managed_keys: Vec<String> = provider_doc
+        .iter()
+        .chain(common_doc.iter())
+        .map(|(key, _)| key.to_string())
+        .collect();
+    for key in managed_keys {
+        let actual = actual_doc.get(&key).cloned();
+        let (target, other) = if common_doc.contains_key(&key) {
+            (&mut *common_doc, &mut *provider_doc)
+        } else {
+            (&mut *provider_doc, &mut *common_doc)
+        };
+        other.remove(&key);
+        match actual {
+            Some(item) => target[key.as_str()] = item,
+            None => {
+                target.remove(&key);
+            }
+        }
+    }
+}
+
+/// 开启网关时 auth.json 中的 OPENAI_API_KEY 是网关 token，恢复为 provider 原有的密钥
+fn restore_gateway_auth(actual_auth: &mut Value, provider_auth: Option<&Value>) {
+    let Some(auth) = actual_auth.as_object_mut() else {
+        return;
+    };
+    match provider_auth.and_then(|a| a.get("OPENAI_API_KEY")) {
+        Some(key) => auth.insert("OPENAI_API_KEY".to_string(), key.clone()),
+        None => auth.remove("OPENAI_API_KEY"),
+    };
+}
+
+/// 将 auth.json / config.toml 中的外部修改写回数据库：
+/// auth.json 整体写回 provider；config.toml 中由应用管理的键按来源写回通用配置或 provider
+#[tauri::command]
+pub async fn import_codex_config_drift(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+) -> Result<(), String> {
+    use toml_edit::DocumentMut;
+
+    let db = state.db();
+
+    let provider = load_applied_provider(&db)
+        .await?
+        .ok_or_else(|| "No applied Codex provider".to_string())?;
+
+    let mut provider_config: serde_json::Map<String, Value> =
+        serde_json::from_str(&provider.settings_config).unwrap_or_default();
+    let parse_doc = |content: &str| -> Result<DocumentMut, String> {
+        if content.trim().is_empty() {
+            Ok(DocumentMut::new())
+        } else {
+            content
+                .parse()
+                .map_err(|e| format!("Failed to parse config: {}", e))
+        }
+    };
+    let mut provider_doc = parse_doc(
+        provider_config
+            .get("config")
+            .and_then(|v| v.as_str())
+            .unwrap_or(""),
+    )?;
+    let common_toml = load_common_toml(&db).await?.unwrap_or_default();
+    let mut common_doc = parse_doc(&common_toml)?;
+
+    let mut actual_auth = read_auth_value(&get_codex_auth_path()?)?;
+    let mut actual_doc = read_config_doc(&get_codex_config_path()?)?;
+
+    // 开启网关时文件中的地址与密钥是网关的，保留 provider 原有的上游地址与密钥
+    if crate::llm_gateway::codex_endpoint(&db).await.is_some() {
+        let provider_toml = provider_config
+            .get("config")
+            .and_then(|v| v.as_str())
+            .unwrap_or("");
+        let original_doc = parse_doc(&append_toml_configs(provider_toml, &common_toml)?)?;
+        crate::llm_gateway::unroute_codex_config(&mut actual_doc, &original_doc);
+        restore_gateway_auth(&mut actual_auth, provider_config.get("auth"));
+    }
+
+    import_drifted_keys(&mut provider_doc, &mut common_doc, &actual_doc);
+
+    provider_config.insert("auth".to_string(), actual_auth);
+    provider_config.insert(
+        "config".to_string(),
+        serde_json::json!(provider_doc.to_string().trim()),
+    );
+    let settings_config = serde_json::to_string(&provider_config)
+        .map_err(|e| format!("Failed to serialize provider settings: {}", e))?;
+    let settings_config = secret_vault::seal_json_fields(
+        &format!("codex_provider/{}", provider.name),
+        &settings_config,
+        secret_vault::CODEX_SECRET_FIELDS,
+    );
+
+    let now = Local::now().to_rfc3339();
+    let record_id = db_record_id("codex_provider", &provider.id);
+    db.query(format!(
+        "UPDATE {} SET settings_config = $settings_config, updated_at = $now",
+        record_id
+    ))
+    .bind(("settings_config", settings_config))
+    .bind(("now", now))
+    .await
+    .map_err(|e| format!("Failed to update provider: {}", e))?;
+
+    if !common_toml.trim().is_empty() || !common_doc.is_empty() {
+        db.query("UPSERT codex_common_config:`common` CONTENT $data")
+            .bind((
+                "data",
+                adapter::to_db_value_common(common_doc.to_string().trim()),
+            ))
+            .await
+            .map_err(|e| format!("Failed to save common config: {}", e))?;
+    }
+
+    let _ = app.emit("config-changed", "window");
+
     Ok(())
 }
 
@@ -704,7 +958,8 @@ fn write_codex_config_files(auth: &serde_json::Value, config_toml: &str) -> Resu
     let auth_path = config_dir.join("auth.json");
     let auth_content = serde_json::to_string_pretty(auth)
         .map_err(|e| format!("Failed to serialize auth: {}", e))?;
-    fs::write(&auth_path, auth_content).map_err(|e| format!("Failed to write auth.json: {}", e))?;
+    config_history::write(&auth_path, auth_content, "codex", "apply_provider")
+        .map_err(|e| format!("Failed to write auth.json: {}", e))?;
 
     // Write config.toml with partial update (preserve mcp_servers)
     let config_path = config_dir.join("config.toml");
@@ -713,11 +968,12 @@ fn write_codex_config_files(auth: &serde_json::Value, config_toml: &str) -> Resu
     Ok(())
 }
 
-/// Write config.toml while preserving mcp_servers and other unrelated fields
-fn write_codex_config_toml_preserve_mcp(
-    config_path: &std::path::Path,
+/// Overlay the managed config onto an existing config.toml document,
+/// preserving mcp_servers and other unrelated fields
+fn merge_codex_config_toml(
+    mut existing_doc: toml_edit::DocumentMut,
     new_config: &str,
-) -> Result<(), String> {
+) -> Result<toml_edit::DocumentMut, String> {
     use toml_edit::DocumentMut;
 
     // Parse new config
@@ -729,27 +985,26 @@ fn write_codex_config_toml_preserve_mcp(
             .map_err(|e| format!("Failed to parse new config: {}", e))?
     };
 
-    // Read existing config (if exists)
-    let mut existing_doc: DocumentMut = if config_path.exists() {
-        let content = fs::read_to_string(config_path)
-            .map_err(|e| format!("Failed to read config.toml: {}", e))?;
-        if content.trim().is_empty() {
-            DocumentMut::new()
-        } else {
-            content
-                .parse()
-                .map_err(|e| format!("Failed to parse existing config.toml: {}", e))?
-        }
-    } else {
-        DocumentMut::new()
-    };
-
     // Preserve mcp_servers from existing config
     let preserved_mcp = existing_doc.get("mcp_servers").cloned();
 
     // Replace all fields from new config
+    // model_providers / profiles 按条目覆盖（标准表与内联表均可），保留结构化编辑写入的其他条目
     for (key, value) in new_doc.iter() {
-        existing_doc[key] = value.clone();
+        match (
+            key,
+            existing_doc
+                .get_mut(key)
+                .and_then(toml_edit::Item::as_table_like_mut),
+            value.as_table_like(),
+        ) {
+            ("model_providers" | "profiles", Some(existing_table), Some(new_table)) => {
+                for (entry_key, entry) in new_table.iter() {
+                    existing_table.insert(entry_key, entry.clone());
+                }
+            }
+            _ => existing_doc[key] = value.clone(),
+        }
     }
 
     // Restore preserved mcp_servers (if it was present and not in new config)
@@ -759,6 +1014,38 @@ fn write_codex_config_toml_preserve_mcp(
         }
     }
 
+    // 默认 profile 中的 model_provider / model 优先级高于顶层键，保留它会让刚应用的 provider 不生效；
+    // 只调整审批、沙箱等其他选项的默认 profile 保留
+    if !new_doc.contains_key("profile") && default_profile_overrides_provider(&existing_doc) {
+        existing_doc.remove("profile");
+    }
+
+    Ok(existing_doc)
+}
+
+/// 顶层 `profile` 指向的条目是否设置了 model_provider 或 model
+fn default_profile_overrides_provider(doc: &toml_edit::DocumentMut) -> bool {
+    let Some(name) = doc.get("profile").and_then(|v| v.as_str()) else {
+        return false;
+    };
+    doc.get("profiles")
+        .and_then(toml_edit::Item::as_table_like)
+        .and_then(|profiles| profiles.get(name))
+        .and_then(toml_edit::Item::as_table_like)
+        .is_some_and(|profile| {
+            profile.contains_key("model_provider") || profile.contains_key("model")
+        })
+}
+
+/// Write config.toml while preserving mcp_servers and other unrelated fields
+fn write_codex_config_toml_preserve_mcp(
+    config_path: &std::path::Path,
+    new_config: &str,
+) -> Result<(), String> {
+    // Read existing config (if exists)
+    let existing_doc = read_config_doc(config_path)?;
+    let existing_doc = merge_codex_config_toml(existing_doc, new_config)?;
+
     // Write back with #:schema none header
     let doc_content = existing_doc.to_string();
     let final_content = if doc_content.trim_start().starts_with("#:schema") {
@@ -766,21 +1053,23 @@ fn write_codex_config_toml_preserve_mcp(
     } else {
         format!("#:schema none\n{}", doc_content)
     };
-    fs::write(config_path, final_content)
+    config_history::write(config_path, final_content, "codex", "apply_provider")
         .map_err(|e| format!("Failed to write config.toml: {}", e))?;
 
     Ok(())
 }
 
 /// Apply Codex config to files
+/// `force` 为 true 时即使校验出错误也写入
 #[tauri::command]
 pub async fn apply_codex_config(
     state: tauri::State<'_, DbState>,
     app: tauri::AppHandle,
     provider_id: String,
+    force: Option<bool>,
 ) -> Result<(), String> {
     let db = state.db();
-    apply_config_internal(&db, &app, &provider_id, false).await
+    apply_config_internal(&db, &app, &provider_id, false, force.unwrap_or(false)).await
 }
 
 /// Toggle is_disabled status for a provider
@@ -825,7 +1114,7 @@ pub async fn toggle_codex_provider_disabled(
 
         if is_applied {
             // Re-apply config to update files (will check is_disabled internally)
-            apply_config_internal(&db, &app, &provider_id, false).await?;
+            apply_config_internal(&db, &app, &provider_id, false, false).await?;
         }
     }
 
@@ -839,12 +1128,11 @@ pub async fn apply_config_internal<R: tauri::Runtime>(
     app: &tauri::AppHandle<R>,
     provider_id: &str,
     from_tray: bool,
+    force: bool,
 ) -> Result<(), String> {
-    // Apply config to files
-    apply_config_to_file(db, provider_id).await?;
-
-    // Update is_applied status using DELETE + CREATE pattern
-    update_is_applied_status(db, provider_id).await?;
+    let report = config_validation::check(validate_provider_config(db, provider_id).await?, force)?;
+    apply_provider(db, provider_id).await?;
+    config_validation::emit_warnings(app, &report);
 
     let payload = if from_tray { "tray" } else { "window" };
     let _ = app.emit("config-changed", payload);
@@ -856,6 +1144,46 @@ pub async fn apply_config_internal<R: tauri::Runtime>(
     Ok(())
 }
 
+/// 校验 provider 应用后 config.toml 的完整内容（与写入时一样合并到现有文件上）
+async fn validate_provider_config(
+    db: &surrealdb::Surreal<surrealdb::engine::local::Db>,
+    provider_id: &str,
+) -> Result<ValidationReport, String> {
+    let provider = load_provider(db, provider_id).await?;
+    let (_, final_config) = build_expected_config(db, &provider).await?;
+    let existing_doc = read_config_doc(&get_codex_config_path()?)?;
+    let merged = merge_codex_config_toml(existing_doc, &final_config)?;
+    Ok(config_validation::validate_toml(
+        ConfigKind::CodexConfig,
+        &merged.to_string(),
+    ))
+}
+
+/// 应用 provider 到配置文件并更新 is_applied，不发送事件（供命令行工具使用）
+pub async fn apply_provider(
+    db: &surrealdb::Surreal<surrealdb::engine::local::Db>,
+    provider_id: &str,
+) -> Result<(), String> {
+    // Apply config to files
+    apply_config_to_file(db, provider_id).await?;
+
+    // Update is_applied status using DELETE + CREATE pattern
+    update_is_applied_status(db, provider_id).await?;
+
+    // 记录切换时间，用量统计据此把会话日志归属到 provider
+    if let Ok(provider) = load_provider(db, provider_id).await {
+        crate::coding::usage::record_provider_applied(
+            db,
+            crate::coding::usage::SOURCE_CODEX,
+            provider_id,
+            &provider.name,
+        )
+        .await;
+    }
+
+    Ok(())
+}
+
 // ============================================================================
 // Codex Prompt Config Commands
 // ============================================================================
@@ -1261,6 +1589,7 @@ pub async fn list_codex_all_api_hub_providers(
             site_name: candidate.site_name.clone(),
             site_type: candidate.site_type.clone(),
             account_label: candidate.account_label.clone(),
+            source_browser: candidate.source_browser.clone(),
             source_profile_name: candidate.source_profile_name.clone(),
             source_extension_id: candidate.source_extension_id.clone(),
             provider_config: serde_json::to_value(all_api_hub::candidate_to_opencode_provider(
@@ -1313,6 +1642,7 @@ pub async fn resolve_codex_all_api_hub_providers(
             site_name: candidate.site_name.clone(),
             site_type: candidate.site_type.clone(),
             account_label: candidate.account_label.clone(),
+            source_browser: candidate.source_browser.clone(),
             source_profile_name: candidate.source_profile_name.clone(),
             source_extension_id: candidate.source_extension_id.clone(),
             provider_config: serde_json::to_value(all_api_hub::candidate_to_opencode_provider(
@@ -1351,9 +1681,92 @@ pub async fn read_codex_settings() -> Result<CodexSettings, String> {
 
 #[cfg(test)]
 mod tests {
-    use super::append_toml_configs;
+    use super::{
+        append_toml_configs, import_drifted_keys, merge_codex_config_toml, restore_gateway_auth,
+    };
     use toml_edit::DocumentMut;
 
+    #[test]
+    fn merge_codex_config_toml_merges_inline_entry_tables() {
+        let existing: DocumentMut = r#"
+profiles = { work = { model_provider = "saved" } }
+
+[model_providers]
+saved = { base_url = "https://saved.example.com/v1" }
+"#
+        .parse()
+        .unwrap();
+        let new_config = r#"
+model_provider = "custom"
+
+[model_providers.custom]
+base_url = "https://custom.example.com/v1"
+
+[profiles.fast]
+model_reasoning_effort = "low"
+"#;
+
+        let merged = merge_codex_config_toml(existing, new_config).unwrap();
+        let reparsed: DocumentMut = merged.to_string().parse().unwrap();
+
+        assert_eq!(
+            reparsed["model_providers"]["saved"]["base_url"].as_str(),
+            Some("https://saved.example.com/v1")
+        );
+        assert_eq!(
+            reparsed["model_providers"]["custom"]["base_url"].as_str(),
+            Some("https://custom.example.com/v1")
+        );
+        assert_eq!(
+            reparsed["profiles"]["work"]["model_provider"].as_str(),
+            Some("saved")
+        );
+        assert_eq!(
+            reparsed["profiles"]["fast"]["model_reasoning_effort"].as_str(),
+            Some("low")
+        );
+    }
+
+    #[test]
+    fn merge_codex_config_toml_keeps_default_profile_without_provider() {
+        let existing: DocumentMut = r#"
+profile = "safe"
+
+[profiles.safe]
+approval_policy = "on-request"
+sandbox_mode = "read-only"
+"#
+        .parse()
+        .unwrap();
+
+        let merged = merge_codex_config_toml(existing, "model_provider = \"new\"\n").unwrap();
+
+        assert_eq!(merged["profile"].as_str(), Some("safe"));
+        assert_eq!(merged["model_provider"].as_str(), Some("new"));
+    }
+
+    #[test]
+    fn merge_codex_config_toml_clears_default_profile() {
+        let existing: DocumentMut = r#"
+profile = "work"
+model_provider = "old"
+
+[profiles.work]
+model_provider = "old"
+"#
+        .parse()
+        .unwrap();
+
+        let merged = merge_codex_config_toml(existing, "model_provider = \"new\"\n").unwrap();
+
+        assert!(merged.get("profile").is_none());
+        assert_eq!(merged["model_provider"].as_str(), Some("new"));
+        assert_eq!(
+            merged["profiles"]["work"]["model_provider"].as_str(),
+            Some("old")
+        );
+    }
+
     #[test]
     fn append_toml_configs_keeps_common_root_keys_at_root() {
         let provider = r#"
@@ -1405,6 +1818,53 @@ wire_api = "responses"
             Some("responses")
         );
     }
+
+    #[test]
+    fn import_drifted_keys_writes_back_only_managed_keys() {
+        let mut provider_doc: DocumentMut = r#"
+model = "gpt-5"
+model_provider = "custom"
+"#
+        .parse()
+        .unwrap();
+        let mut common_doc: DocumentMut = r#"approval_policy = "never""#.parse().unwrap();
+        let actual_doc: DocumentMut = r#"
+model = "gpt-5-codex"
+approval_policy = "on-request"
+notify = ["say"]
+"#
+        .parse()
+        .unwrap();
+
+        import_drifted_keys(&mut provider_doc, &mut common_doc, &actual_doc);
+
+        // provider 的键写回 provider，被删除的键同步删除
+        assert_eq!(provider_doc["model"].as_str(), Some("gpt-5-codex"));
+        assert!(!provider_doc.contains_key("model_provider"));
+        // 通用配置的键写回通用配置
+        assert_eq!(common_doc["approval_policy"].as_str(), Some("on-request"));
+        assert!(!provider_doc.contains_key("approval_policy"));
+        // 应用未管理的键不导入
+        assert!(!provider_doc.contains_key("notify"));
+        assert!(!common_doc.contains_key("notify"));
+    }
+
+    #[test]
+    fn restore_gateway_auth_keeps_upstream_key() {
+        let mut actual = serde_json::json!({
+            "OPENAI_API_KEY": "gateway-token",
+            "tokens": { "id_token": "x" },
+        });
+        let provider_auth = serde_json::json!({ "OPENAI_API_KEY": "sk-upstream" });
+        restore_gateway_auth(&mut actual, Some(&provider_auth));
+        assert_eq!(actual["OPENAI_API_KEY"], "sk-upstream");
+        assert_eq!(actual["tokens"]["id_token"], "x");
+
+        // provider 原本没有密钥（使用 ChatGPT 登录）时不写入网关 token
+        let mut actual = serde_json::json!({ "OPENAI_API_KEY": "gateway-token" });
+        restore_gateway_auth(&mut actual, Some(&serde_json::json!({})));
+        assert!(actual.get("OPENAI_API_KEY").is_none());
+    }
 }
 
 // ============================================================================
@@ -1452,12 +1912,18 @@ pub async fn save_codex_common_config(
     state: tauri::State<'_, DbState>,
     app: tauri::AppHandle,
     config: String,
+    force: Option<bool>,
 ) -> Result<(), String> {
     let db = state.db();
 
     // Validate TOML if not empty
+    let mut report = None;
     if !config.trim().is_empty() {
         let _: toml::Table = toml::from_str(&config).map_err(|e| format!("Invalid TOML: {}", e))?;
+        report = Some(config_validation::check(
+            config_validation::validate_fragment_toml(ConfigKind::CodexConfig, &config),
+            force.unwrap_or(false),
+        )?);
     }
 
     let json_data = adapter::to_db_value_common(&config);
@@ -1489,6 +1955,10 @@ pub async fn save_codex_common_config(
         }
     }
 
+    if let Some(report) = &report {
+        config_validation::emit_warnings(&app, report);
+    }
+
     // Emit config-changed event to notify frontend
     let _ = app.emit("config-changed", "window");
 
@@ -1,7 +1,9 @@
 pub mod adapter;
 pub mod commands;
+pub mod profiles;
 pub mod tray_support;
 pub mod types;
 
 pub use commands::*;
+pub use profiles::*;
 pub use types::*;
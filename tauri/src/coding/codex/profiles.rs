@@ -0,0 +1,635 @@
+use std::collections::{BTreeMap, HashSet};
+
+use tauri::Emitter;
+use toml_edit::{DocumentMut, InlineTable, Item, Table, TableLike};
+
+use super::commands::{get_codex_config_path, list_providers_internal, read_config_doc};
+use super::types::*;
+use crate::coding::config_history;
+use crate::coding::config_validation::{self, ConfigKind};
+use crate::db::DbState;
+use crate::secret_vault;
+
+/// 内置 openai provider 的默认地址
+const DEFAULT_OPENAI_BASE_URL: &str = "https://api.openai.com/v1";
+
+// ============================================================================
+// Codex Profiles Commands
+// ============================================================================
+
+/// 读取 config.toml 中的 model_providers / profiles 与默认 profile
+#[tauri::command]
+pub async fn read_codex_profiles() -> Result<CodexProfilesConfig, String> {
+    let doc = read_config_doc(&get_codex_config_path()?)?;
+    let mut config = profiles_from_doc(&doc);
+    seal_bearer_tokens(&mut config);
+    Ok(config)
+}
+
+/// 由已保存的 provider 生成 model_providers / profiles（与 config.toml 现有条目合并，不写入文件）
+/// 同名条目以生成结果为准，默认 profile 保持不变
+#[tauri::command]
+pub async fn generate_codex_profiles(
+    state: tauri::State<'_, DbState>,
+) -> Result<CodexProfilesConfig, String> {
+    let db = state.db();
+    let providers = list_providers_internal(&db).await?;
+    let doc = read_config_doc(&get_codex_config_path()?)?;
+
+    let mut config = profiles_from_doc(&doc);
+    let (model_providers, profiles) = generate_entries(&providers)?;
+    for entry in model_providers {
+        match config.model_providers.iter_mut().find(|p| p.id == entry.id) {
+            Some(existing) => *existing = entry,
+            None => config.model_providers.push(entry),
+        }
+    }
+    for entry in profiles {
+        match config.profiles.iter_mut().find(|p| p.name == entry.name) {
+            Some(existing) => *existing = entry,
+            None => config.profiles.push(entry),
+        }
+    }
+    seal_bearer_tokens(&mut config);
+    Ok(config)
+}
+
+/// 一次性写入全部 model_providers / profiles 与默认 profile
+/// 列表中没有的条目会从 config.toml 删除，条目内未识别的键与其他顶层配置原样保留
+/// `force` 为 true 时即使校验出错误也写入
+#[tauri::command]
+pub async fn save_codex_profiles(
+    app: tauri::AppHandle,
+    config: CodexProfilesConfig,
+    force: Option<bool>,
+) -> Result<(), String> {
+    let config_path = get_codex_config_path()?;
+    let mut doc = read_config_doc(&config_path)?;
+    let mut config = config;
+    resolve_bearer_tokens(&mut config)?;
+    apply_profiles_to_doc(&mut doc, &config)?;
+    write_profiles_doc(&app, &config_path, doc, force.unwrap_or(false))
+}
+
+/// 设置默认 profile（顶层 `profile` 键），传 None 时移除
+#[tauri::command]
+pub async fn set_codex_default_profile(
+    app: tauri::AppHandle,
+    profile: Option<String>,
+) -> Result<(), String> {
+    let config_path = get_codex_config_path()?;
+    let mut doc = read_config_doc(&config_path)?;
+    let mut config = profiles_from_doc(&doc);
+    config.default_profile = profile.filter(|name| !name.trim().is_empty());
+    apply_profiles_to_doc(&mut doc, &config)?;
+    write_profiles_doc(&app, &config_path, doc, false)
+}
+
+/// 返回前端的密钥改为保险库引用，避免明文离开后端
+fn seal_bearer_tokens(config: &mut CodexProfilesConfig) {
+    for provider in &mut config.model_providers {
+        if let Some(token) = &mut provider.experimental_bearer_token {
+            *token = secret_vault::seal(
+                &format!(
+                    "codex/model_providers/{}/experimental_bearer_token",
+                    provider.id
+                ),
+                token,
+            );
+        }
+    }
+}
+
+/// 写入 config.toml 前把保险库引用解析为明文（Codex 只认明文密钥）
+fn resolve_bearer_tokens(config: &mut CodexProfilesConfig) -> Result<(), String> {
+    for provider in &mut config.model_providers {
+        if let Some(token) = &mut provider.experimental_bearer_token {
+            *token = secret_vault::resolve(token)?;
+        }
+    }
+    Ok(())
+}
+
+fn write_profiles_doc<R: tauri::Runtime>(
+    app: &tauri::AppHandle<R>,
+    config_path: &std::path::Path,
+    doc: DocumentMut,
+    force: bool,
+) -> Result<(), String> {
+    let content = doc.to_string();
+    let report = config_validation::check(
+        config_validation::validate_toml(ConfigKind::CodexConfig, &content),
+        force,
+    )?;
+
+    if let Some(parent) = config_path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create .codex directory: {}", e))?;
+    }
+    let final_content = if content.trim_start().starts_with("#:schema") {
+        content
+    } else {
+        format!("#:schema none\n{}", content)
+    };
+    config_history::write(config_path, final_content, "codex", "save_profiles")
+        .map_err(|e| format!("Failed to write config.toml: {}", e))?;
+
+    config_validation::emit_warnings(app, &report);
+    let _ = app.emit("config-changed", "window");
+
+    // Trigger WSL sync via event (Windows only)
+    #[cfg(target_os = "windows")]
+    let _ = app.emit("wsl-sync-request-codex", ());
+
+    Ok(())
+}
+
+// ============================================================================
+// TOML <-> Structured Conversion
+// ============================================================================
+
+fn get_str(table: &dyn TableLike, key: &str) -> Option<String> {
+    table
+        .get(key)
+        .and_then(|item| item.as_str())
+        .map(|s| s.to_string())
+}
+
+fn get_string_map(table: &dyn TableLike, key: &str) -> Option<BTreeMap<String, String>> {
+    let entries = table.get(key)?.as_table_like()?;
+    Some(
+        entries
+            .iter()
+            .filter_map(|(k, v)| v.as_str().map(|s| (k.to_string(), s.to_string())))
+            .collect(),
+    )
+}
+
+/// `[key]` 下的全部条目，标准表与内联表（`foo = { ... }`）都支持
+fn entry_tables<'a>(doc: &'a DocumentMut, key: &str) -> Vec<(&'a str, &'a dyn TableLike)> {
+    doc.get(key)
+        .and_then(Item::as_table_like)
+        .map(|table| {
+            table
+                .iter()
+                .filter_map(|(k, v)| v.as_table_like().map(|t| (k, t)))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn profiles_from_doc(doc: &DocumentMut) -> CodexProfilesConfig {
+    let model_providers = entry_tables(doc, "model_providers")
+        .into_iter()
+        .map(|(id, table)| CodexModelProviderEntry {
+            id: id.to_string(),
+            name: get_str(table, "name"),
+            base_url: get_str(table, "base_url"),
+            wire_api: get_str(table, "wire_api"),
+            env_key: get_str(table, "env_key"),
+            experimental_bearer_token: get_str(table, "experimental_bearer_token"),
+            requires_openai_auth: table.get("requires_openai_auth").and_then(|v| v.as_bool()),
+            query_params: get_string_map(table, "query_params"),
+            http_headers: get_string_map(table, "http_headers"),
+        })
+        .collect();
+
+    let profiles = entry_tables(doc, "profiles")
+        .into_iter()
+        .map(|(name, table)| CodexProfileEntry {
+            name: name.to_string(),
+            model_provider: get_str(table, "model_provider"),
+            model: get_str(table, "model"),
+            model_reasoning_effort: get_str(table, "model_reasoning_effort"),
+            approval_policy: get_str(table, "approval_policy"),
+            sandbox_mode: get_str(table, "sandbox_mode"),
+        })
+        .collect();
+
+    CodexProfilesConfig {
+        default_profile: doc
+            .get("profile")
+            .and_then(|v| v.as_str())
+            .map(String::from),
+        model_providers,
+        profiles,
+    }
+}
+
+fn set_str(table: &mut dyn TableLike, key: &str, value: &Option<String>) {
+    match value.as_deref().map(str::trim) {
+        Some(v) if !v.is_empty() => {
+            table.insert(key, toml_edit::value(v));
+        }
+        _ => {
+            table.remove(key);
+        }
+    }
+}
+
+fn set_string_map(table: &mut dyn TableLike, key: &str, value: &Option<BTreeMap<String, String>>) {
+    match value {
+        Some(map) if !map.is_empty() => {
+            let inline: InlineTable = map.iter().map(|(k, v)| (k.as_str(), v.as_str())).collect();
+            table.insert(key, toml_edit::value(inline));
+        }
+        _ => {
+            table.remove(key);
+        }
+    }
+}
+
+/// 取出（或新建）`[key]` 表，删除不在 `keep` 中的条目；`keep` 为空时移除整张表
+/// 已有的内联表（`key = { ... }`）保持内联写法
+fn sync_entry_table<'a>(
+    doc: &'a mut DocumentMut,
+    key: &str,
+    keep: &HashSet<&str>,
+) -> Option<&'a mut dyn TableLike> {
+    if keep.is_empty() {
+        doc.remove(key);
+        return None;
+    }
+    if !doc.get(key).is_some_and(Item::is_table_like) {
+        let mut table = Table::new();
+        table.set_implicit(true);
+        doc[key] = Item::Table(table);
+    }
+    let table = doc[key].as_table_like_mut()?;
+    let stale: Vec<String> = table
+        .iter()
+        .map(|(k, _)| k.to_string())
+        .filter(|k| !keep.contains(k.as_str()))
+        .collect();
+    for k in stale {
+        table.remove(&k);
+    }
+    Some(table)
+}
+
+fn entry_table<'a>(parent: &'a mut dyn TableLike, key: &str) -> &'a mut dyn TableLike {
+    if !parent.get(key).is_some_and(Item::is_table_like) {
+        // 父表为内联表时 insert 会自动转成内联表
+        parent.insert(key, Item::Table(Table::new()));
+    }
+    parent
+        .get_mut(key)
+        .and_then(Item::as_table_like_mut)
+        .expect("entry was just inserted as a table")
+}
+
+fn check_unique<'a>(kind: &str, names: impl Iterator<Item = &'a str>) -> Result<(), String> {
+    let mut seen = HashSet::new();
+    for name in names {
+        if name.trim().is_empty() {
+            return Err(format!("{} name cannot be empty", kind));
+        }
+        if !seen.insert(name) {
+            return Err(format!("Duplicate {} name: {}", kind, name));
+        }
+    }
+    Ok(())
+}
+
+/// 将结构化配置写回文档：按条目更新已知字段，保留条目内其他键与文档中的其他配置
+fn apply_profiles_to_doc(
+    doc: &mut DocumentMut,
+    config: &CodexProfilesConfig,
+) -> Result<(), String> {
+    check_unique(
+        "model provider",
+        config.model_providers.iter().map(|p| p.id.as_str()),
+    )?;
+    check_unique("profile", config.profiles.iter().map(|p| p.name.as_str()))?;
+    if let Some(default) = &config.default_profile {
+        if !config.profiles.iter().any(|p| &p.name == default) {
+            return Err(format!("Default profile '{}' does not exist", default));
+        }
+    }
+
+    let keep: HashSet<&str> = config
+        .model_providers
+        .iter()
+        .map(|p| p.id.as_str())
+        .collect();
+    if let Some(table) = sync_entry_table(doc, "model_providers", &keep) {
+        for provider in &config.model_providers {
+            let entry = entry_table(table, &provider.id);
+            set_str(entry, "name", &provider.name);
+            set_str(entry, "base_url", &provider.base_url);
+            set_str(entry, "wire_api", &provider.wire_api);
+            set_str(entry, "env_key", &provider.env_key);
+            set_str(
+                entry,
+                "experimental_bearer_token",
+                &provider.experimental_bearer_token,
+            );
+            match provider.requires_openai_auth {
+                Some(value) => {
+                    entry.insert("requires_openai_auth", toml_edit::value(value));
+                }
+                None => {
+                    entry.remove("requires_openai_auth");
+                }
+            }
+            set_string_map(entry, "query_params", &provider.query_params);
+            set_string_map(entry, "http_headers", &provider.http_headers);
+        }
+    }
+
+    let keep: HashSet<&str> = config.profiles.iter().map(|p| p.name.as_str()).collect();
+    if let Some(table) = sync_entry_table(doc, "profiles", &keep) {
+        for profile in &config.profiles {
+            let entry = entry_table(table, &profile.name);
+            set_str(entry, "model_provider", &profile.model_provider);
+            set_str(entry, "model", &profile.model);
+            set_str(
+                entry,
+                "model_reasoning_effort",
+                &profile.model_reasoning_effort,
+            );
+            set_str(entry, "approval_policy", &profile.approval_policy);
+            set_str(entry, "sandbox_mode", &profile.sandbox_mode);
+        }
+    }
+
+    match &config.default_profile {
+        Some(default) => doc["profile"] = toml_edit::value(default.as_str()),
+        None => {
+            doc.remove("profile");
+        }
+    }
+
+    Ok(())
+}
+
+// ============================================================================
+// Generate From Stored Providers
+// ============================================================================
+
+/// 将 provider 名称转换为 TOML 键（小写字母、数字、`-`、`_`），重名时追加序号
+fn profile_key(name: &str, used: &mut HashSet<String>) -> String {
+    let mut slug = String::new();
+    for c in name.trim().chars().flat_map(char::to_lowercase) {
+        if c.is_ascii_alphanumeric() || c == '_' {
+            slug.push(c);
+        } else if !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    let slug = slug.trim_matches('-');
+    let base = if slug.is_empty() { "provider" } else { slug }.to_string();
+
+    let mut key = base.clone();
+    let mut index = 2;
+    while used.contains(&key) {
+        key = format!("{}-{}", base, index);
+        index += 1;
+    }
+    used.insert(key.clone());
+    key
+}
+
+/// 每个启用的 provider 生成一个同名的 model provider 与 profile
+/// auth.json 只能保存一个密钥，因此生成的 model provider 改用 experimental_bearer_token 携带各自的密钥；
+/// 使用内置 openai 且配置了密钥的 provider 也生成独立条目，否则切换 profile 时仍会读取 auth.json 中当前应用的密钥。
+/// 密钥保持 settings_config 中的保险库引用，保存时才解析为明文
+fn generate_entries(
+    providers: &[CodexProvider],
+) -> Result<(Vec<CodexModelProviderEntry>, Vec<CodexProfileEntry>), String> {
+    let mut used = HashSet::new();
+    let mut model_providers = Vec::new();
+    let mut profiles = Vec::new();
+
+    for provider in providers {
+        if provider.is_disabled || provider.id == "__local__" {
+            continue;
+        }
+
+        let settings: serde_json::Value = serde_json::from_str(&provider.settings_config)
+            .map_err(|e| format!("Failed to parse provider '{}': {}", provider.name, e))?;
+        let api_key = settings
+            .pointer("/auth/OPENAI_API_KEY")
+            .and_then(|v| v.as_str())
+            .filter(|s| !s.is_empty())
+            .map(String::from);
+        let config_toml = settings
+            .get("config")
+            .and_then(|v| v.as_str())
+            .unwrap_or("");
+        let doc: DocumentMut = if config_toml.trim().is_empty() {
+            DocumentMut::new()
+        } else {
+            config_toml
+                .parse()
+                .map_err(|e| format!("Failed to parse config of '{}': {}", provider.name, e))?
+        };
+
+        let key = profile_key(&provider.name, &mut used);
+        let source = profiles_from_doc(&doc);
+        let provider_ref = doc
+            .get("model_provider")
+            .and_then(|v| v.as_str())
+            .map(String::from);
+
+        // provider 自定义的 model_provider 改名为生成的键；带密钥的内置 openai 生成独立条目，其他内置 provider 直接引用
+        let model_provider = match provider_ref
+            .as_ref()
+            .and_then(|name| source.model_providers.iter().find(|p| &p.id == name))
+        {
+            Some(defined) => {
+                let mut entry = defined.clone();
+                entry.id = key.clone();
+                if entry.name.is_none() {
+                    entry.name = Some(provider.name.clone());
+                }
+                if entry.env_key.is_none() && entry.experimental_bearer_token.is_none() {
+                    if let Some(api_key) = &api_key {
+                        entry.experimental_bearer_token = Some(api_key.clone());
+                        entry.requires_openai_auth = None;
+                    }
+                }
+                model_providers.push(entry);
+                Some(key.clone())
+            }
+            None if provider_ref.as_deref().unwrap_or("openai") == "openai"
+                && api_key.is_some() =>
+            {
+                model_providers.push(CodexModelProviderEntry {
+                    id: key.clone(),
+                    name: Some(provider.name.clone()),
+                    base_url: Some(DEFAULT_OPENAI_BASE_URL.to_string()),
+                    wire_api: Some("responses".to_string()),
+                    env_key: None,
+                    experimental_bearer_token: api_key.clone(),
+                    requires_openai_auth: None,
+                    query_params: None,
+                    http_headers: None,
+                });
+                Some(key.clone())
+            }
+            None => provider_ref,
+        };
+
+        let get = |field: &str| doc.get(field).and_then(|v| v.as_str()).map(String::from);
+        profiles.push(CodexProfileEntry {
+            name: key,
+            model_provider,
+            model: get("model"),
+            model_reasoning_effort: get("model_reasoning_effort"),
+            approval_policy: get("approval_policy"),
+            sandbox_mode: get("sandbox_mode"),
+        });
+    }
+
+    Ok((model_providers, profiles))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn apply_profiles_keeps_unknown_keys_and_removes_stale_entries() {
+        let mut doc: DocumentMut = r#"
+model = "gpt-5"
+
+[model_providers.keep]
+name = "Keep"
+stream_max_retries = 3
+
+[model_providers.stale]
+name = "Stale"
+
+[mcp_servers.docs]
+command = "docs-mcp"
+"#
+        .parse()
+        .unwrap();
+
+        let mut config = profiles_from_doc(&doc);
+        config.model_providers.retain(|p| p.id == "keep");
+        config.model_providers[0].base_url = Some("https://api.example.com/v1".to_string());
+        config.profiles.push(CodexProfileEntry {
+            name: "work".to_string(),
+            model_provider: Some("keep".to_string()),
+            model: Some("gpt-5-codex".to_string()),
+            model_reasoning_effort: None,
+            approval_policy: None,
+            sandbox_mode: None,
+        });
+        config.default_profile = Some("work".to_string());
+        apply_profiles_to_doc(&mut doc, &config).unwrap();
+
+        assert_eq!(doc["profile"].as_str(), Some("work"));
+        assert_eq!(doc["model"].as_str(), Some("gpt-5"));
+        assert_eq!(
+            doc["mcp_servers"]["docs"]["command"].as_str(),
+            Some("docs-mcp")
+        );
+        assert_eq!(
+            doc["model_providers"]["keep"]["stream_max_retries"].as_integer(),
+            Some(3)
+        );
+        assert_eq!(
+            doc["model_providers"]["keep"]["base_url"].as_str(),
+            Some("https://api.example.com/v1")
+        );
+        assert!(doc["model_providers"].get("stale").is_none());
+        assert_eq!(profiles_from_doc(&doc), config);
+
+        config.default_profile = Some("missing".to_string());
+        assert!(apply_profiles_to_doc(&mut doc, &config).is_err());
+    }
+
+    #[test]
+    fn profile_key_slugifies_and_dedupes() {
+        let mut used = HashSet::new();
+        assert_eq!(profile_key("My Proxy", &mut used), "my-proxy");
+        assert_eq!(profile_key("my proxy!", &mut used), "my-proxy-2");
+        assert_eq!(profile_key("中转", &mut used), "provider");
+    }
+
+    #[test]
+    fn inline_entry_tables_are_read_and_updated_in_place() {
+        let mut doc: DocumentMut = r#"
+profiles = { work = { model_provider = "foo", model = "gpt-5" } }
+
+[model_providers]
+foo = { base_url = "https://foo.example.com/v1", stream_max_retries = 3 }
+stale = { base_url = "https://stale.example.com/v1" }
+"#
+        .parse()
+        .unwrap();
+
+        let mut config = profiles_from_doc(&doc);
+        assert_eq!(config.profiles[0].name, "work");
+        assert_eq!(config.profiles[0].model_provider.as_deref(), Some("foo"));
+        assert_eq!(
+            config.model_providers[0].base_url.as_deref(),
+            Some("https://foo.example.com/v1")
+        );
+
+        config.model_providers.retain(|p| p.id == "foo");
+        config.model_providers.push(CodexModelProviderEntry {
+            id: "bar".to_string(),
+            name: None,
+            base_url: Some("https://bar.example.com/v1".to_string()),
+            wire_api: None,
+            env_key: None,
+            experimental_bearer_token: None,
+            requires_openai_auth: None,
+            query_params: None,
+            http_headers: None,
+        });
+        config.profiles[0].model = Some("gpt-5-codex".to_string());
+        apply_profiles_to_doc(&mut doc, &config).unwrap();
+
+        assert_eq!(
+            doc["model_providers"]["foo"]["stream_max_retries"].as_integer(),
+            Some(3)
+        );
+        assert!(doc["model_providers"]["foo"].is_inline_table());
+        assert!(doc["model_providers"].get("stale").is_none());
+        assert!(doc["profiles"].is_inline_table());
+        assert_eq!(
+            doc["profiles"]["work"]["model"].as_str(),
+            Some("gpt-5-codex")
+        );
+        let reparsed: DocumentMut = doc.to_string().parse().unwrap();
+        assert_eq!(profiles_from_doc(&reparsed), config);
+    }
+
+    #[test]
+    fn generate_entries_gives_builtin_openai_providers_their_own_key() {
+        let provider: CodexProvider = serde_json::from_value(serde_json::json!({
+            "id": "p1",
+            "name": "Official",
+            "category": "official",
+            "settingsConfig": serde_json::json!({
+                "auth": { "OPENAI_API_KEY": "aitb-vault://0123456789abcdef0123456789abcdef" },
+                "config": "model = \"gpt-5\"\n"
+            })
+            .to_string(),
+            "isApplied": false,
+            "isDisabled": false,
+            "createdAt": "",
+            "updatedAt": ""
+        }))
+        .unwrap();
+
+        let (model_providers, profiles) = generate_entries(&[provider]).unwrap();
+
+        assert_eq!(model_providers[0].id, "official");
+        assert_eq!(
+            model_providers[0].base_url.as_deref(),
+            Some(DEFAULT_OPENAI_BASE_URL)
+        );
+        assert_eq!(
+            model_providers[0].experimental_bearer_token.as_deref(),
+            Some("aitb-vault://0123456789abcdef0123456789abcdef")
+        );
+        assert_eq!(profiles[0].model_provider.as_deref(), Some("official"));
+        assert_eq!(profiles[0].model.as_deref(), Some("gpt-5"));
+    }
+}
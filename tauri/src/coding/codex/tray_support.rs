@@ -5,21 +5,25 @@
 use crate::coding::codex::apply_config_internal;
 use crate::coding::db_id::db_clean_id;
 use crate::db::DbState;
+use serde::Serialize;
 use serde_json::Value;
 use tauri::{AppHandle, Manager, Runtime};
 
 /// Item for provider selection in tray menu
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct TrayProviderItem {
     pub id: String,
     pub display_name: String,
     pub is_selected: bool,
     pub is_disabled: bool,
+    #[serde(skip)]
     pub sort_index: i64,
 }
 
 /// Data for provider submenu
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct TrayProviderData {
     pub title: String,
     pub items: Vec<TrayProviderItem>,
@@ -84,7 +88,7 @@ pub async fn apply_codex_provider<R: Runtime>(
     let state = app.state::<DbState>();
     let db = state.db();
 
-    apply_config_internal(&db, app, provider_id, true).await
+    apply_config_internal(&db, app, provider_id, true, false).await
 }
 
 /// Check if Codex should be shown in tray menu
@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 use serde::{Deserialize, Serialize};
 use surrealdb::sql::Thing;
 
@@ -233,6 +235,7 @@ pub struct CodexAllApiHubProvider {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub site_type: Option<String>,
     pub account_label: String,
+    pub source_browser: String,
     pub source_profile_name: String,
     pub source_extension_id: String,
     pub provider_config: serde_json::Value,
@@ -253,3 +256,59 @@ pub struct CodexAllApiHubProvidersResult {
 pub struct ResolveCodexAllApiHubProvidersRequest {
     pub provider_ids: Vec<String>,
 }
+
+// ============================================================================
+// Codex Profiles Types
+// ============================================================================
+
+/// config.toml 中的 `[model_providers.<id>]`
+/// 只描述应用关心的字段，其余键（如 stream_max_retries）写入时原样保留
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CodexModelProviderEntry {
+    pub id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wire_api: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub env_key: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub experimental_bearer_token: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub requires_openai_auth: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub query_params: Option<BTreeMap<String, String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub http_headers: Option<BTreeMap<String, String>>,
+}
+
+/// config.toml 中的 `[profiles.<name>]`，通过 `codex --profile <name>` 选用
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CodexProfileEntry {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model_provider: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model_reasoning_effort: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub approval_policy: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sandbox_mode: Option<String>,
+}
+
+/// config.toml 中 model_providers / profiles 的结构化视图
+/// `default_profile` 对应顶层 `profile` 键
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CodexProfilesConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default_profile: Option<String>,
+    pub model_providers: Vec<CodexModelProviderEntry>,
+    pub profiles: Vec<CodexProfileEntry>,
+}
@@ -0,0 +1,158 @@
+//! 托管配置文件的漂移检测
+//!
+//! 应用 provider 后，用户或工具本身可能直接修改 settings.json / config.toml，
+//! 导致数据库中 `is_applied` 的 provider 与真实文件不一致。这里提供通用的
+//! 键路径对比，各模块负责构造"应用后应得到的配置"并决定如何回写。
+//!
+//! - Claude Code / Codex：与已应用 provider + 通用配置生成的结果对比
+//! - OpenCode：与收藏 provider（每次保存时从配置文件同步）对比 `provider` 段
+//! - OpenClaw：配置只存在于文件本身，数据库中没有副本，不存在漂移
+
+use serde::Serialize;
+use serde_json::Value;
+
+/// 一个不一致的键
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DriftEntry {
+    /// 点分隔的键路径，如 `env.ANTHROPIC_MODEL`
+    pub key: String,
+    /// 按数据库记录应得到的值（None 表示文件中多出的键）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expected: Option<Value>,
+    /// 文件中的实际值（None 表示文件中缺少该键）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub actual: Option<Value>,
+}
+
+/// 漂移检测结果
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigDrift {
+    /// "claudecode" | "codex" | "opencode"
+    pub module: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub provider_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub provider_name: Option<String>,
+    pub config_path: String,
+    pub drifted: bool,
+    pub entries: Vec<DriftEntry>,
+}
+
+impl ConfigDrift {
+    pub fn new(
+        module: &str,
+        provider_id: Option<String>,
+        provider_name: Option<String>,
+        config_path: String,
+        entries: Vec<DriftEntry>,
+    ) -> Self {
+        Self {
+            module: module.to_string(),
+            provider_id,
+            provider_name,
+            config_path,
+            drifted: !entries.is_empty(),
+            entries,
+        }
+    }
+}
+
+/// 递归对比两个 JSON 值，对象逐键展开，数组与标量整体比较
+pub fn diff_values(expected: &Value, actual: &Value) -> Vec<DriftEntry> {
+    let mut entries = vec![];
+    diff_at("", Some(expected), Some(actual), &mut entries);
+    entries
+}
+
+/// 在 `prefix` 下对比，键路径以 `prefix.` 开头
+pub fn diff_values_at(
+    prefix: &str,
+    expected: Option<&Value>,
+    actual: Option<&Value>,
+) -> Vec<DriftEntry> {
+    let mut entries = vec![];
+    diff_at(prefix, expected, actual, &mut entries);
+    entries
+}
+
+fn diff_at(
+    path: &str,
+    expected: Option<&Value>,
+    actual: Option<&Value>,
+    out: &mut Vec<DriftEntry>,
+) {
+    match (expected, actual) {
+        (Some(Value::Object(exp)), Some(Value::Object(act))) => {
+            for (key, exp_value) in exp {
+                diff_at(&join_key(path, key), Some(exp_value), act.get(key), out);
+            }
+            for (key, act_value) in act {
+                if !exp.contains_key(key) {
+                    diff_at(&join_key(path, key), None, Some(act_value), out);
+                }
+            }
+        }
+        (None, None) => {}
+        (exp, act) if exp == act => {}
+        (exp, act) => out.push(DriftEntry {
+            key: path.to_string(),
+            expected: exp.cloned(),
+            actual: act.cloned(),
+        }),
+    }
+}
+
+fn join_key(prefix: &str, key: &str) -> String {
+    if prefix.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}.{}", prefix, key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_diff_values_reports_changed_missing_and_extra_keys() {
+        let expected = json!({
+            "env": { "ANTHROPIC_MODEL": "a", "ANTHROPIC_BASE_URL": "https://x" },
+            "permissions": { "allow": ["Bash"] },
+        });
+        let actual = json!({
+            "env": { "ANTHROPIC_MODEL": "b", "DEBUG": "1" },
+            "permissions": { "allow": ["Bash"] },
+        });
+
+        let entries = diff_values(&expected, &actual);
+        assert_eq!(
+            entries,
+            vec![
+                DriftEntry {
+                    key: "env.ANTHROPIC_MODEL".to_string(),
+                    expected: Some(json!("a")),
+                    actual: Some(json!("b")),
+                },
+                DriftEntry {
+                    key: "env.ANTHROPIC_BASE_URL".to_string(),
+                    expected: Some(json!("https://x")),
+                    actual: None,
+                },
+                DriftEntry {
+                    key: "env.DEBUG".to_string(),
+                    expected: None,
+                    actual: Some(json!("1")),
+                },
+            ]
+        );
+        assert!(diff_values(&expected, &expected).is_empty());
+        assert_eq!(
+            diff_values_at("auth", None, Some(&json!({"k": 1})))[0].key,
+            "auth"
+        );
+    }
+}
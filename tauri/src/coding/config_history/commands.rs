@@ -0,0 +1,74 @@
+use tauri::Emitter;
+
+use super::types::{ConfigHistoryFile, ConfigHistoryStatus, ConfigSnapshot};
+
+/// 列出所有有写入历史的托管文件
+#[tauri::command]
+pub fn list_config_history_files() -> Result<Vec<ConfigHistoryFile>, String> {
+    Ok(super::list_files())
+}
+
+/// 快照记录状态（保险库不可用时跳过的快照数）
+#[tauri::command]
+pub fn get_config_history_status() -> Result<ConfigHistoryStatus, String> {
+    Ok(super::status())
+}
+
+/// 列出某个文件的快照（不含内容），新的在前
+#[tauri::command]
+pub fn list_config_history(path: String) -> Result<Vec<ConfigSnapshot>, String> {
+    Ok(super::list_snapshots(&path))
+}
+
+/// 读取单个快照的内容
+#[tauri::command]
+pub fn get_config_snapshot(path: String, snapshot_id: String) -> Result<ConfigSnapshot, String> {
+    super::get_snapshot(&path, &snapshot_id)
+}
+
+/// 对比两个快照（unified diff）；`to_id` 为空时与文件当前内容对比
+#[tauri::command]
+pub fn diff_config_snapshots(
+    path: String,
+    from_id: String,
+    to_id: Option<String>,
+) -> Result<String, String> {
+    super::diff_snapshots(&path, &from_id, to_id.as_deref())
+}
+
+/// 把文件恢复为指定快照的内容
+#[tauri::command]
+pub fn restore_config_snapshot(
+    app: tauri::AppHandle,
+    path: String,
+    snapshot_id: String,
+) -> Result<(), String> {
+    let snapshot = super::restore_snapshot(&path, &snapshot_id)?;
+    log::info!(
+        "已将 {} 恢复到 {} 的版本",
+        snapshot.path,
+        snapshot.created_at
+    );
+
+    let _ = app.emit("config-changed", "window");
+
+    // 恢复的文件同样需要同步到 WSL / SSH
+    let target = match snapshot.module.as_str() {
+        "claudecode" => Some("claude"),
+        "codex" => Some("codex"),
+        "opencode" => Some("opencode"),
+        "openclaw" => Some("openclaw"),
+        _ => None,
+    };
+    if let Some(target) = target {
+        #[cfg(target_os = "windows")]
+        let _ = app.emit(&format!("wsl-sync-request-{}", target), ());
+
+        // OpenClaw 目前只支持同步到 WSL
+        if target != "openclaw" {
+            let _ = app.emit(&format!("ssh-sync-request-{}", target), ());
+        }
+    }
+
+    Ok(())
+}
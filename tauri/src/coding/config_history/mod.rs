@@ -0,0 +1,589 @@
+//! 托管配置文件的写入历史
+//!
+//! 各模块写入 settings.json / auth.json / config.toml / opencode.json、MCP 配置和提示词文件时
+//! 使用 [`write`] 代替 `fs::write`：写入前把文件原内容保存为快照，设置页可以按文件查看历史、
+//! 对比任意两个版本并恢复到指定版本。
+//!
+//! 快照保存在应用数据目录下的 `config_history/<路径 hash>/<快照 id>.json`，
+//! 按设置中的每文件保留数量和保留天数清理。托管文件中含有 API Key，快照内容用密钥保险库的主密钥加密；
+//! 保险库不可用（如口令模式未解锁）时不记录快照，跳过的次数通过 [`get_config_history_status`] 在历史页提示。
+
+mod commands;
+mod types;
+
+pub use commands::*;
+pub use types::{ConfigHistoryFile, ConfigHistoryStatus, ConfigSnapshot};
+
+use std::cell::RefCell;
+use std::fs;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+
+use chrono::{DateTime, Duration, Local, Utc};
+use sha2::{Digest, Sha256};
+
+use super::db_new_id;
+use super::sync_preview::unified_diff;
+use crate::secret_vault;
+
+const STORE_DIR_NAME: &str = "config_history";
+
+static STORE_DIR: OnceLock<PathBuf> = OnceLock::new();
+/// 保护快照目录的读-改-写
+static STORE_LOCK: Mutex<()> = Mutex::new(());
+/// 保留策略；从设置加载之前不清理任何快照
+static RETENTION: Mutex<Option<Retention>> = Mutex::new(None);
+/// 因保险库不可用而跳过快照的情况，成功记录一次后清零
+static STATUS: Mutex<ConfigHistoryStatus> = Mutex::new(ConfigHistoryStatus {
+    skipped_snapshots: 0,
+    last_error: None,
+});
+tokio::task_local! {
+    /// 当前任务中进行的写入事务；其他任务（文件监听、健康检查切换、控制 API 等）的写入不会被记录
+    static TRANSACTION: RefCell<Vec<TrackedFile>>;
+}
+
+/// 事务中写过的文件，内容为 `None` 表示文件不存在
+#[derive(Debug)]
+struct TrackedFile {
+    path: PathBuf,
+    /// 事务中首次写入前的内容
+    original: Option<Vec<u8>>,
+    /// 事务最后一次写入的内容
+    written: Option<Vec<u8>>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Retention {
+    /// 每个文件最多保留的快照数，0 表示不限
+    max_per_file: u32,
+    /// 快照最长保留天数，0 表示不限
+    max_age_days: u32,
+}
+
+/// Set the app data directory for the history store (call once at startup)
+pub fn set_store_dir(app_data_dir: PathBuf) {
+    let _ = STORE_DIR.set(app_data_dir.join(STORE_DIR_NAME));
+}
+
+/// 更新保留策略并立即按新策略清理
+pub fn set_retention(max_per_file: u32, max_age_days: u32) {
+    *RETENTION.lock().unwrap_or_else(|e| e.into_inner()) = Some(Retention {
+        max_per_file,
+        max_age_days,
+    });
+
+    let _guard = STORE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    if let Err(e) = prune_all() {
+        log::warn!("清理配置历史失败: {}", e);
+    }
+}
+
+/// 快照记录状态
+fn status() -> ConfigHistoryStatus {
+    STATUS.lock().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+fn set_status(error: Option<&str>) {
+    let mut status = STATUS.lock().unwrap_or_else(|e| e.into_inner());
+    match error {
+        Some(error) => {
+            status.skipped_snapshots += 1;
+            status.last_error = Some(error.to_string());
+        }
+        None => *status = ConfigHistoryStatus::default(),
+    }
+}
+
+/// 从设置中读取保留策略
+pub async fn load_retention(db: &surrealdb::Surreal<surrealdb::engine::local::Db>) {
+    match crate::settings::commands::load_settings(db).await {
+        Ok(settings) => set_retention(
+            settings.config_history_max_per_file,
+            settings.config_history_max_age_days,
+        ),
+        Err(e) => log::warn!("读取配置历史保留策略失败: {}", e),
+    }
+}
+
+// ============================================================================
+// Write API
+// ============================================================================
+
+/// 写入托管配置文件，写入前记录文件原内容。
+///
+/// `module` / `action` 记录触发写入的来源，在历史列表中展示。
+/// 记录快照失败只输出警告，不影响写入本身。
+pub fn write(
+    path: impl AsRef<Path>,
+    contents: impl AsRef<[u8]>,
+    module: &str,
+    action: &str,
+) -> std::io::Result<()> {
+    let path = path.as_ref();
+    let contents = contents.as_ref();
+    track_transaction(path, Some(contents));
+    if let Err(e) = record_snapshot(path, Some(contents), module, action) {
+        log::warn!("记录配置历史失败 {}: {}", path.display(), e);
+    }
+    fs::write(path, contents)
+}
+
+/// 删除托管配置文件，删除前记录文件原内容
+fn remove(path: &Path, module: &str, action: &str) -> std::io::Result<()> {
+    track_transaction(path, None);
+    if let Err(e) = record_snapshot(path, None, module, action) {
+        log::warn!("记录配置历史失败 {}: {}", path.display(), e);
+    }
+    match fs::remove_file(path) {
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        result => result,
+    }
+}
+
+// ============================================================================
+// Transaction
+// ============================================================================
+
+/// 已结束的写入事务，按首次写入顺序记录 [`transaction`] 中写过的文件
+#[derive(Debug, Default)]
+pub struct Transaction {
+    files: Vec<TrackedFile>,
+}
+
+/// 在写入事务中执行 `future`：其中（同一任务内）经由 [`write`] 写入的文件都会记住原内容，
+/// 结束后可用 [`Transaction::rollback`] 还原；直接丢弃返回的事务即保留所有写入。
+///
+/// 事务保存在 task-local 中，`tokio::spawn` / `spawn_blocking` 启动的任务不会继承：
+/// 需要随事务回滚的写入必须在 `future` 内直接 await 完成，后台任务的写入只记入历史、不会被回滚。
+pub async fn transaction<F: Future>(future: F) -> (F::Output, Transaction) {
+    TRANSACTION
+        .scope(RefCell::new(Vec::new()), async {
+            let output = future.await;
+            let files = TRANSACTION.with(|files| files.take());
+            (output, Transaction { files })
+        })
+        .await
+}
+
+impl Transaction {
+    /// 把事务中写过的文件按相反顺序还原为写入前的内容。
+    ///
+    /// 事务结束后被其他写入改动过的文件不会被覆盖；还原本身也会记入历史。
+    /// 返回未能还原的文件及原因。
+    pub fn rollback(self, module: &str) -> Vec<String> {
+        let mut failures = Vec::new();
+        for file in self.files.into_iter().rev() {
+            match read_existing(&file.path) {
+                Ok(current) if current == file.written => {}
+                Ok(_) => {
+                    failures.push(format!("{}: 已被其他写入修改，未还原", file.path.display()));
+                    continue;
+                }
+                Err(e) => {
+                    failures.push(format!("{}: {}", file.path.display(), e));
+                    continue;
+                }
+            }
+            let result = match file.original {
+                Some(data) => write(&file.path, data, module, "rollback"),
+                None => remove(&file.path, module, "rollback"),
+            };
+            if let Err(e) = result {
+                failures.push(format!("{}: {}", file.path.display(), e));
+            }
+        }
+        failures
+    }
+}
+
+/// 读取文件内容，不存在时返回 `None`
+fn read_existing(path: &Path) -> std::io::Result<Option<Vec<u8>>> {
+    match fs::read(path) {
+        Ok(data) => Ok(Some(data)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+fn track_transaction(path: &Path, contents: Option<&[u8]>) {
+    let _ = TRANSACTION.try_with(|files| {
+        let mut files = files.borrow_mut();
+        if let Some(file) = files.iter_mut().find(|file| file.path == path) {
+            file.written = contents.map(<[u8]>::to_vec);
+            return;
+        }
+        match read_existing(path) {
+            Ok(original) => files.push(TrackedFile {
+                path: path.to_path_buf(),
+                original,
+                written: contents.map(<[u8]>::to_vec),
+            }),
+            Err(e) => log::warn!("事务无法读取原文件 {}: {}", path.display(), e),
+        }
+    });
+}
+
+// ============================================================================
+// Snapshot Store
+// ============================================================================
+
+fn file_key(path: &Path) -> String {
+    let digest = hex::encode(Sha256::digest(path.to_string_lossy().as_bytes()));
+    digest[..16].to_string()
+}
+
+fn file_dir(path: &Path) -> Option<PathBuf> {
+    STORE_DIR.get().map(|dir| dir.join(file_key(path)))
+}
+
+/// 快照 id 只包含时间戳、数字和 `-`，防止拼接出目录外的路径
+fn is_valid_snapshot_id(id: &str) -> bool {
+    !id.is_empty() && id.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'-')
+}
+
+/// 目录下的快照 id，新的在前
+fn snapshot_ids(dir: &Path) -> Vec<String> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut ids: Vec<String> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            name.strip_suffix(".json").map(String::from)
+        })
+        .filter(|id| is_valid_snapshot_id(id))
+        .collect();
+    ids.sort_unstable_by(|a, b| b.cmp(a));
+    ids
+}
+
+/// 读取快照文件，`content` 保持存储时的形式（可能是密文）
+fn read_snapshot(dir: &Path, id: &str) -> Result<ConfigSnapshot, String> {
+    let content = fs::read_to_string(dir.join(format!("{}.json", id)))
+        .map_err(|e| format!("读取配置快照 {} 失败: {}", id, e))?;
+    serde_json::from_str(&content).map_err(|e| format!("解析配置快照 {} 失败: {}", id, e))
+}
+
+fn write_snapshot(dir: &Path, snapshot: &ConfigSnapshot) -> Result<(), String> {
+    let json =
+        serde_json::to_string_pretty(snapshot).map_err(|e| format!("序列化配置快照失败: {}", e))?;
+    fs::write(dir.join(format!("{}.json", snapshot.id)), json)
+        .map_err(|e| format!("写入配置快照失败: {}", e))
+}
+
+/// 快照 id 作为附加数据，防止密文在快照间被调换
+fn encrypt_content(snapshot: &mut ConfigSnapshot) -> Result<(), String> {
+    if snapshot.encrypted {
+        return Ok(());
+    }
+    if let Some(content) = &snapshot.content {
+        snapshot.content = Some(secret_vault::encrypt_text(&snapshot.id, content)?);
+    }
+    snapshot.encrypted = true;
+    Ok(())
+}
+
+fn decrypt_content(mut snapshot: ConfigSnapshot) -> Result<ConfigSnapshot, String> {
+    if snapshot.encrypted {
+        if let Some(content) = &snapshot.content {
+            snapshot.content = Some(secret_vault::decrypt_text(&snapshot.id, content)?);
+        }
+        snapshot.encrypted = false;
+    }
+    Ok(snapshot)
+}
+
+fn record_snapshot(
+    path: &Path,
+    new_contents: Option<&[u8]>,
+    module: &str,
+    action: &str,
+) -> Result<(), String> {
+    let Some(dir) = file_dir(path) else {
+        return Ok(());
+    };
+
+    let previous = read_existing(path).map_err(|e| format!("读取原文件失败: {}", e))?;
+    // 内容没有变化，不产生历史
+    if previous.as_deref() == new_contents {
+        return Ok(());
+    }
+
+    let size = previous.as_ref().map(|data| data.len() as u64).unwrap_or(0);
+    let content = previous
+        .map(String::from_utf8)
+        .transpose()
+        .map_err(|_| "文件不是 UTF-8 文本，跳过快照".to_string())?;
+
+    let _guard = STORE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+
+    // 与最近一次快照相同（例如两次写入之间文件没有被改动过），无需重复保存
+    if let Some(latest_id) = snapshot_ids(&dir).first() {
+        if let Ok(latest) = read_snapshot(&dir, latest_id).and_then(decrypt_content) {
+            if latest.existed == content.is_some() && latest.content == content {
+                return Ok(());
+            }
+        }
+    }
+
+    fs::create_dir_all(&dir).map_err(|e| format!("创建配置历史目录失败: {}", e))?;
+
+    let id = format!(
+        "{}-{}",
+        Utc::now().format("%Y%m%d%H%M%S%3f"),
+        &db_new_id()[..6]
+    );
+    let mut snapshot = ConfigSnapshot {
+        id,
+        path: path.to_string_lossy().to_string(),
+        module: module.to_string(),
+        action: action.to_string(),
+        created_at: Local::now().to_rfc3339(),
+        existed: content.is_some(),
+        size,
+        content,
+        encrypted: false,
+    };
+    if let Err(e) = encrypt_content(&mut snapshot) {
+        set_status(Some(&e));
+        return Err(e);
+    }
+    write_snapshot(&dir, &snapshot)?;
+    set_status(None);
+
+    prune_dir(&dir)
+}
+
+/// 按保留策略清理单个文件的快照
+fn prune_dir(dir: &Path) -> Result<(), String> {
+    let Some(retention) = *RETENTION.lock().unwrap_or_else(|e| e.into_inner()) else {
+        return Ok(());
+    };
+
+    let cutoff = (retention.max_age_days > 0)
+        .then(|| Local::now() - Duration::days(i64::from(retention.max_age_days)));
+
+    for (index, id) in snapshot_ids(dir).iter().enumerate() {
+        let over_count = retention.max_per_file > 0 && index >= retention.max_per_file as usize;
+        let expired = cutoff.is_some_and(|cutoff| {
+            read_snapshot(dir, id)
+                .ok()
+                .and_then(|snapshot| DateTime::parse_from_rfc3339(&snapshot.created_at).ok())
+                .is_some_and(|created| created < cutoff)
+        });
+        if over_count || expired {
+            fs::remove_file(dir.join(format!("{}.json", id)))
+                .map_err(|e| format!("删除配置快照 {} 失败: {}", id, e))?;
+        }
+    }
+
+    if snapshot_ids(dir).is_empty() {
+        let _ = fs::remove_dir_all(dir);
+    }
+    Ok(())
+}
+
+fn prune_all() -> Result<(), String> {
+    let Some(root) = STORE_DIR.get() else {
+        return Ok(());
+    };
+    let Ok(entries) = fs::read_dir(root) else {
+        return Ok(());
+    };
+    for entry in entries.flatten() {
+        if entry.path().is_dir() {
+            prune_dir(&entry.path())?;
+        }
+    }
+    Ok(())
+}
+
+// ============================================================================
+// Queries
+// ============================================================================
+
+/// 所有有历史记录的文件，最近写入的在前
+fn list_files() -> Vec<ConfigHistoryFile> {
+    let Some(root) = STORE_DIR.get() else {
+        return Vec::new();
+    };
+    let Ok(entries) = fs::read_dir(root) else {
+        return Vec::new();
+    };
+
+    let _guard = STORE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut files: Vec<ConfigHistoryFile> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let dir = entry.path();
+            let ids = snapshot_ids(&dir);
+            let latest = read_snapshot(&dir, ids.first()?).ok()?;
+            Some(ConfigHistoryFile {
+                exists: Path::new(&latest.path).exists(),
+                path: latest.path,
+                module: latest.module,
+                snapshot_count: ids.len(),
+                latest_at: latest.created_at,
+            })
+        })
+        .collect();
+    files.sort_by(|a, b| b.latest_at.cmp(&a.latest_at));
+    files
+}
+
+/// 某个文件的快照列表（不含内容），新的在前
+fn list_snapshots(path: &str) -> Vec<ConfigSnapshot> {
+    let Some(dir) = file_dir(Path::new(path)) else {
+        return Vec::new();
+    };
+
+    let _guard = STORE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    snapshot_ids(&dir)
+        .iter()
+        .filter_map(|id| read_snapshot(&dir, id).ok())
+        .map(|snapshot| ConfigSnapshot {
+            content: None,
+            encrypted: false,
+            ..snapshot
+        })
+        .collect()
+}
+
+/// 读取单个快照（含内容）
+fn get_snapshot(path: &str, id: &str) -> Result<ConfigSnapshot, String> {
+    if !is_valid_snapshot_id(id) {
+        return Err(format!("无效的快照 id: {}", id));
+    }
+    let dir = file_dir(Path::new(path)).ok_or("配置历史目录未初始化")?;
+
+    let _guard = STORE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let snapshot = read_snapshot(&dir, id)?;
+    if snapshot.path != path {
+        return Err(format!("快照 {} 不属于 {}", id, path));
+    }
+    decrypt_content(snapshot)
+}
+
+/// 对比两个快照；`to_id` 为空时与文件当前内容对比
+fn diff_snapshots(path: &str, from_id: &str, to_id: Option<&str>) -> Result<String, String> {
+    let from = get_snapshot(path, from_id)?;
+    let (to_content, to_label) = match to_id {
+        Some(to_id) => {
+            let to = get_snapshot(path, to_id)?;
+            (to.content.unwrap_or_default(), to.created_at)
+        }
+        None => {
+            let current = match fs::read_to_string(path) {
+                Ok(content) => content,
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+                Err(e) => return Err(format!("读取 {} 失败: {}", path, e)),
+            };
+            (current, "current".to_string())
+        }
+    };
+
+    Ok(unified_diff(
+        from.content.as_deref().unwrap_or_default(),
+        &to_content,
+        &from.created_at,
+        &to_label,
+    ))
+}
+
+/// 把文件恢复为快照中的内容，恢复前的内容同样会记录为快照
+fn restore_snapshot(path: &str, id: &str) -> Result<ConfigSnapshot, String> {
+    let snapshot = get_snapshot(path, id)?;
+    let target = Path::new(path);
+
+    match snapshot.content.as_deref() {
+        Some(content) if snapshot.existed => {
+            if let Some(parent) = target.parent() {
+                fs::create_dir_all(parent).map_err(|e| format!("创建目录失败: {}", e))?;
+            }
+            write(target, content, &snapshot.module, "restore")
+                .map_err(|e| format!("恢复 {} 失败: {}", path, e))?;
+        }
+        _ => remove(target, &snapshot.module, "restore")
+            .map_err(|e| format!("删除 {} 失败: {}", path, e))?,
+    }
+
+    Ok(snapshot)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snapshot_id_validation() {
+        assert!(is_valid_snapshot_id("20260101120000123-a1b2c3"));
+        assert!(!is_valid_snapshot_id(""));
+        assert!(!is_valid_snapshot_id("../settings"));
+        assert!(!is_valid_snapshot_id("a/b"));
+    }
+
+    #[test]
+    fn test_file_key_is_stable_per_path() {
+        let a = file_key(Path::new("/home/u/.claude/settings.json"));
+        assert_eq!(a, file_key(Path::new("/home/u/.claude/settings.json")));
+        assert_ne!(a, file_key(Path::new("/home/u/.codex/config.toml")));
+        assert_eq!(a.len(), 16);
+    }
+
+    #[tokio::test]
+    async fn test_rollback_restores_files_written_in_transaction() {
+        let dir = std::env::temp_dir().join(format!("config_history_tx_{}", db_new_id()));
+        fs::create_dir_all(&dir).unwrap();
+        let existing = dir.join("settings.json");
+        let created = dir.join("new.json");
+        fs::write(&existing, "original").unwrap();
+
+        let (_, transaction) = transaction(async {
+            write(&existing, "first", "test", "apply").unwrap();
+            write(&existing, "second", "test", "apply").unwrap();
+            write(&created, "created", "test", "apply").unwrap();
+        })
+        .await;
+        assert!(transaction.rollback("test").is_empty());
+
+        assert_eq!(fs::read_to_string(&existing).unwrap(), "original");
+        assert!(!created.exists());
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn test_rollback_keeps_writes_from_other_tasks() {
+        let dir = std::env::temp_dir().join(format!("config_history_tx_{}", db_new_id()));
+        fs::create_dir_all(&dir).unwrap();
+        let own = dir.join("settings.json");
+        let shared = dir.join("auth.json");
+        let other = dir.join("config.toml");
+        fs::write(&own, "original").unwrap();
+        fs::write(&shared, "original").unwrap();
+
+        let (_, transaction) = transaction(async {
+            write(&own, "applied", "test", "apply").unwrap();
+            write(&shared, "applied", "test", "apply").unwrap();
+            // 其他任务（如文件监听、健康检查切换）在事务期间写入
+            let (shared, other) = (shared.clone(), other.clone());
+            tokio::spawn(async move {
+                write(&shared, "failover", "test", "failover").unwrap();
+                write(&other, "watcher", "test", "watcher").unwrap();
+            })
+            .await
+            .unwrap();
+        })
+        .await;
+
+        let failures = transaction.rollback("test");
+        assert_eq!(failures.len(), 1);
+        assert!(failures[0].contains("auth.json"));
+        assert_eq!(fs::read_to_string(&own).unwrap(), "original");
+        assert_eq!(fs::read_to_string(&shared).unwrap(), "failover");
+        assert_eq!(fs::read_to_string(&other).unwrap(), "watcher");
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
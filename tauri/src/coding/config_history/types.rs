@@ -0,0 +1,51 @@
+use serde::{Deserialize, Serialize};
+
+/// 托管文件被写入前的一次快照
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigSnapshot {
+    /// 按时间排序的快照 id
+    pub id: String,
+    /// 被写入文件的绝对路径
+    pub path: String,
+    /// 触发写入的模块："claudecode" | "codex" | "opencode" | "openclaw" | "mcp" ...
+    pub module: String,
+    /// 触发写入的操作，如 `apply_provider`、`mcp_sync`、`restore`
+    pub action: String,
+    /// 快照时间（RFC 3339）
+    pub created_at: String,
+    /// 写入前文件是否存在；为 false 时恢复该快照会删除文件
+    pub existed: bool,
+    /// 写入前的文件大小（字节）
+    pub size: u64,
+    /// 写入前的文件内容，列表接口中省略
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
+    /// 快照文件中的 `content` 是否以保险库主密钥加密；读取快照时解密，返回给前端的始终是明文
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub encrypted: bool,
+}
+
+/// 快照记录状态：保险库不可用时写入照常进行，但不记录快照
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigHistoryStatus {
+    /// 最近一次成功记录之后跳过的快照数
+    pub skipped_snapshots: u32,
+    /// 最近一次跳过的原因
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+}
+
+/// 有历史记录的文件
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigHistoryFile {
+    pub path: String,
+    /// 最近一次写入来自的模块
+    pub module: String,
+    pub snapshot_count: usize,
+    pub latest_at: String,
+    /// 文件当前是否存在
+    pub exists: bool,
+}
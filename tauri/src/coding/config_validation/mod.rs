@@ -0,0 +1,397 @@
+//! 写入前校验生成的工具配置
+//!
+//! 配置由 `settings_config`、通用配置、oh-my-opencode 的 `other_fields` 等自由 JSON/TOML 拼出，
+//! 写入前按内置 schema（`schemas/*.json`）逐路径检查，再叠加几条 schema 表达不了的跨字段规则。
+//!
+//! - 错误（类型不符、非法枚举值、缺少必填项等）默认阻止写入，命令返回
+//!   `CONFIG_VALIDATION_FAILED:<report json>`，前端确认后带 `force` 重试
+//! - 警告（未知键、已废弃的键）不阻止写入，写入后通过 `config-validation-warning` 事件通知前端
+
+mod schema;
+
+use std::sync::OnceLock;
+
+use serde::Serialize;
+use serde_json::Value;
+use tauri::Emitter;
+
+/// 校验失败错误前缀，前端据此识别并弹出确认框
+pub const ERR_CONFIG_INVALID: &str = "CONFIG_VALIDATION_FAILED";
+
+/// 写入后仍有问题时发给前端的事件
+const WARNING_EVENT: &str = "config-validation-warning";
+
+/// Codex 内置的 model_provider，无需在 model_providers 中定义
+const CODEX_BUILTIN_PROVIDERS: &[&str] = &["openai", "oss", "ollama", "lmstudio"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+/// 单条校验问题，`path` 为点分路径，如 `model_providers.foo.wire_api`、`plugin[0]`
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidationIssue {
+    pub path: String,
+    pub message: String,
+    pub severity: Severity,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidationReport {
+    /// 被校验的目标文件，如 `settings.json`、`config.toml`
+    pub target: String,
+    /// 是否由用户强制写入
+    pub forced: bool,
+    pub issues: Vec<ValidationIssue>,
+}
+
+impl ValidationReport {
+    pub fn has_errors(&self) -> bool {
+        self.issues
+            .iter()
+            .any(|issue| issue.severity == Severity::Error)
+    }
+}
+
+/// 可校验的配置文件
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigKind {
+    ClaudeSettings,
+    CodexConfig,
+    OpenCode,
+    OhMyOpenCode,
+    OhMyOpenCodeSlim,
+    OpenClaw,
+}
+
+impl ConfigKind {
+    const ALL: [ConfigKind; 6] = [
+        ConfigKind::ClaudeSettings,
+        ConfigKind::CodexConfig,
+        ConfigKind::OpenCode,
+        ConfigKind::OhMyOpenCode,
+        ConfigKind::OhMyOpenCodeSlim,
+        ConfigKind::OpenClaw,
+    ];
+
+    fn target(self) -> &'static str {
+        match self {
+            ConfigKind::ClaudeSettings => "settings.json",
+            ConfigKind::CodexConfig => "config.toml",
+            ConfigKind::OpenCode => "opencode.json",
+            ConfigKind::OhMyOpenCode => "oh-my-opencode.json",
+            ConfigKind::OhMyOpenCodeSlim => "oh-my-opencode-slim.json",
+            ConfigKind::OpenClaw => "openclaw.json",
+        }
+    }
+
+    fn schema_source(self) -> &'static str {
+        match self {
+            ConfigKind::ClaudeSettings => include_str!("schemas/claude_settings.json"),
+            ConfigKind::CodexConfig => include_str!("schemas/codex_config.json"),
+            ConfigKind::OpenCode => include_str!("schemas/opencode.json"),
+            ConfigKind::OhMyOpenCode => include_str!("schemas/oh_my_opencode.json"),
+            ConfigKind::OhMyOpenCodeSlim => include_str!("schemas/oh_my_opencode_slim.json"),
+            ConfigKind::OpenClaw => include_str!("schemas/openclaw.json"),
+        }
+    }
+
+    fn schema(self) -> &'static Value {
+        static SCHEMAS: OnceLock<Vec<Value>> = OnceLock::new();
+        let schemas = SCHEMAS.get_or_init(|| {
+            ConfigKind::ALL
+                .iter()
+                .map(|kind| {
+                    serde_json::from_str(kind.schema_source())
+                        .expect("bundled config schema must be valid JSON")
+                })
+                .collect()
+        });
+        let index = ConfigKind::ALL
+            .iter()
+            .position(|kind| *kind == self)
+            .unwrap_or_default();
+        &schemas[index]
+    }
+}
+
+/// 校验即将写入的完整配置
+pub fn validate_json(kind: ConfigKind, value: &Value) -> ValidationReport {
+    let mut report = validate_fragment_json(kind, value);
+    check_rules(kind, value, &mut report.issues);
+    report
+}
+
+/// 校验即将写入的完整 TOML 配置（Codex config.toml）
+pub fn validate_toml(kind: ConfigKind, content: &str) -> ValidationReport {
+    match parse_toml(kind, content) {
+        Ok(value) => validate_json(kind, &value),
+        Err(report) => report,
+    }
+}
+
+/// 校验配置片段（通用配置、全局配置）：只做 schema 检查，跨字段规则需要完整配置才有意义
+pub fn validate_fragment_json(kind: ConfigKind, value: &Value) -> ValidationReport {
+    let root = kind.schema();
+    let mut issues = Vec::new();
+    schema::validate(root, root, value, "", &mut issues);
+    ValidationReport {
+        target: kind.target().to_string(),
+        forced: false,
+        issues,
+    }
+}
+
+/// 校验 TOML 配置片段
+pub fn validate_fragment_toml(kind: ConfigKind, content: &str) -> ValidationReport {
+    match parse_toml(kind, content) {
+        Ok(value) => validate_fragment_json(kind, &value),
+        Err(report) => report,
+    }
+}
+
+fn parse_toml(kind: ConfigKind, content: &str) -> Result<Value, ValidationReport> {
+    toml::from_str::<toml::Value>(content)
+        .map_err(|e| e.to_string())
+        .and_then(|value| serde_json::to_value(value).map_err(|e| e.to_string()))
+        .map_err(|message| ValidationReport {
+            target: kind.target().to_string(),
+            forced: false,
+            issues: vec![ValidationIssue {
+                path: String::new(),
+                message: format!("invalid TOML: {}", message.trim()),
+                severity: Severity::Error,
+            }],
+        })
+}
+
+/// 有错误且未强制时阻止写入；强制写入时只记录日志
+pub fn check(mut report: ValidationReport, force: bool) -> Result<ValidationReport, String> {
+    if report.has_errors() {
+        if !force {
+            let json = serde_json::to_string(&report)
+                .map_err(|e| format!("Failed to serialize validation report: {}", e))?;
+            return Err(format!("{}:{}", ERR_CONFIG_INVALID, json));
+        }
+        log::warn!(
+            "{} 未通过校验，用户选择强制写入: {:?}",
+            report.target,
+            report.issues
+        );
+        report.forced = true;
+    }
+    Ok(report)
+}
+
+/// 写入成功后把剩余问题（警告，或强制写入时的错误）通知前端
+pub fn emit_warnings<R: tauri::Runtime>(app: &tauri::AppHandle<R>, report: &ValidationReport) {
+    if !report.issues.is_empty() {
+        let _ = app.emit(WARNING_EVENT, report);
+    }
+}
+
+/// schema 表达不了的跨字段规则
+fn check_rules(kind: ConfigKind, value: &Value, issues: &mut Vec<ValidationIssue>) {
+    match kind {
+        ConfigKind::CodexConfig => {
+            check_codex_model_provider(value, value, "model_provider", issues);
+            for (name, profile) in object_entries(value, "profiles") {
+                let path = schema::join(&schema::join("profiles", name), "model_provider");
+                check_codex_model_provider(value, profile, &path, issues);
+            }
+            if let Some(profile) = value.get("profile").and_then(|v| v.as_str()) {
+                if value.get("profiles").and_then(|v| v.get(profile)).is_none() {
+                    schema::push(
+                        issues,
+                        "profile",
+                        Severity::Error,
+                        format!("\"{}\" is not defined in profiles", profile),
+                    );
+                }
+            }
+            for (name, server) in object_entries(value, "mcp_servers") {
+                if server.get("command").is_none() && server.get("url").is_none() {
+                    schema::push(
+                        issues,
+                        &schema::join("mcp_servers", name),
+                        Severity::Error,
+                        "either command or url is required".to_string(),
+                    );
+                }
+            }
+        }
+        ConfigKind::OpenCode => {
+            for (name, server) in object_entries(value, "mcp") {
+                let required = match server.get("type").and_then(|v| v.as_str()) {
+                    Some("local") => "command",
+                    Some("remote") => "url",
+                    _ => continue,
+                };
+                if server.get(required).is_none() {
+                    schema::push(
+                        issues,
+                        &schema::join(&schema::join("mcp", name), required),
+                        Severity::Error,
+                        format!(
+                            "required key is missing for a {} server",
+                            server["type"].as_str().unwrap_or_default()
+                        ),
+                    );
+                }
+            }
+        }
+        ConfigKind::OpenClaw => {
+            // 默认模型引用的 provider 必须存在
+            let primary = value
+                .pointer("/agents/defaults/model/primary")
+                .and_then(|v| v.as_str());
+            if let Some((provider, _)) = primary.and_then(|p| p.split_once('/')) {
+                let providers = value.pointer("/models/providers");
+                if providers.is_some_and(|p| p.get(provider).is_none()) {
+                    schema::push(
+                        issues,
+                        "agents.defaults.model.primary",
+                        Severity::Warning,
+                        format!(
+                            "provider \"{}\" is not defined in models.providers",
+                            provider
+                        ),
+                    );
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// `model_provider` 必须是内置 provider 或在 model_providers 中定义（顶层与每个 profile 都适用）
+fn check_codex_model_provider(
+    root: &Value,
+    scope: &Value,
+    path: &str,
+    issues: &mut Vec<ValidationIssue>,
+) {
+    let Some(provider) = scope.get("model_provider").and_then(|v| v.as_str()) else {
+        return;
+    };
+    let defined = root
+        .get("model_providers")
+        .and_then(|v| v.get(provider))
+        .is_some();
+    if !defined && !CODEX_BUILTIN_PROVIDERS.contains(&provider) {
+        schema::push(
+            issues,
+            path,
+            Severity::Error,
+            format!(
+                "\"{}\" is neither a built-in provider nor defined in model_providers",
+                provider
+            ),
+        );
+    }
+}
+
+fn object_entries<'a>(value: &'a Value, key: &str) -> Vec<(&'a String, &'a Value)> {
+    value
+        .get(key)
+        .and_then(|v| v.as_object())
+        .map(|map| map.iter().collect())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn reports_path_level_errors_and_warnings() {
+        let report = validate_json(
+            ConfigKind::ClaudeSettings,
+            &json!({
+                "env": { "ANTHROPIC_BASE_URL": "https://api.example.com", "API_TIMEOUT_MS": 3000 },
+                "permissions": { "defaultMode": "yolo", "alow": [] }
+            }),
+        );
+
+        let find = |path: &str| report.issues.iter().find(|i| i.path == path);
+        assert_eq!(
+            find("env.API_TIMEOUT_MS").map(|i| i.severity),
+            Some(Severity::Error)
+        );
+        assert_eq!(
+            find("permissions.defaultMode").map(|i| i.severity),
+            Some(Severity::Error)
+        );
+        assert_eq!(
+            find("permissions.alow").map(|i| i.severity),
+            Some(Severity::Warning)
+        );
+        assert_eq!(report.issues.len(), 3);
+    }
+
+    #[test]
+    fn codex_model_provider_must_be_defined() {
+        let config = r#"
+model_provider = "custom"
+model_reasoning_effort = "high"
+
+[model_providers.other]
+base_url = "https://api.example.com/v1"
+wire_api = "responses"
+"#;
+        let report = validate_toml(ConfigKind::CodexConfig, config);
+        assert!(report.has_errors());
+        assert!(report.issues.iter().any(|i| i.path == "model_provider"));
+
+        let fixed = config.replace("\"custom\"", "\"other\"");
+        assert!(validate_toml(ConfigKind::CodexConfig, &fixed)
+            .issues
+            .is_empty());
+
+        // 片段不做跨字段检查
+        assert!(validate_fragment_toml(ConfigKind::CodexConfig, config)
+            .issues
+            .is_empty());
+    }
+
+    #[test]
+    fn codex_profiles_must_reference_defined_entries() {
+        let config = r#"
+profile = "missing"
+
+[model_providers.proxy]
+base_url = "https://api.example.com/v1"
+
+[profiles.work]
+model_provider = "proxy"
+
+[profiles.home]
+model_provider = "unknown"
+"#;
+        let report = validate_toml(ConfigKind::CodexConfig, config);
+        let paths: Vec<&str> = report.issues.iter().map(|i| i.path.as_str()).collect();
+        assert_eq!(paths.len(), 2);
+        assert!(paths.contains(&"profile"));
+        assert!(paths.contains(&"profiles.home.model_provider"));
+    }
+
+    #[test]
+    fn check_blocks_errors_unless_forced() {
+        let report = validate_json(
+            ConfigKind::OpenCode,
+            &json!({ "mcp": { "fs": { "type": "local" } } }),
+        );
+        let err = check(report.clone(), false).unwrap_err();
+        assert!(err.starts_with(ERR_CONFIG_INVALID));
+        assert!(err.contains("mcp.fs.command"));
+
+        let forced = check(report, true).unwrap();
+        assert!(forced.forced);
+    }
+}
@@ -0,0 +1,296 @@
+//! 精简版 JSON Schema 校验器
+//!
+//! 只实现内置 schema 用到的关键字：`$ref`（仅 `#/$defs/<name>`）、`type`、`enum`、`format: uri`、
+//! `minimum` / `maximum`、`minItems`、`properties`、`additionalProperties`、`required`、`items`、
+//! `anyOf` 与 `deprecated`。
+//!
+//! 类型不符、枚举值不合法、缺少必填键记为错误；`additionalProperties: false` 下的未知键和
+//! 已废弃的键只记为警告——工具新增的字段不应被我们的 schema 挡住。
+
+use serde_json::Value;
+
+use super::{Severity, ValidationIssue};
+
+/// 用 `schema` 校验 `value`，问题追加到 `issues`；`root` 用于解析 `$ref`
+pub(super) fn validate(
+    root: &Value,
+    schema: &Value,
+    value: &Value,
+    path: &str,
+    issues: &mut Vec<ValidationIssue>,
+) {
+    let Some(schema) = schema.as_object() else {
+        return;
+    };
+
+    if let Some(reference) = schema.get("$ref").and_then(|v| v.as_str()) {
+        match resolve_ref(root, reference) {
+            Some(target) => validate(root, target, value, path, issues),
+            None => log::warn!("配置 schema 中的引用无法解析: {}", reference),
+        }
+        return;
+    }
+
+    if schema.get("deprecated").and_then(|v| v.as_bool()) == Some(true) {
+        let hint = schema
+            .get("description")
+            .and_then(|v| v.as_str())
+            .map(|d| format!(": {}", d))
+            .unwrap_or_default();
+        push(
+            issues,
+            path,
+            Severity::Warning,
+            format!("deprecated key{}", hint),
+        );
+    }
+
+    if let Some(expected) = schema.get("type") {
+        if !matches_type(expected, value) {
+            push(
+                issues,
+                path,
+                Severity::Error,
+                format!(
+                    "expected {}, found {}",
+                    describe_type(expected),
+                    type_name(value)
+                ),
+            );
+            return;
+        }
+    }
+
+    if let Some(allowed) = schema.get("enum").and_then(|v| v.as_array()) {
+        if !allowed.contains(value) {
+            let options = allowed
+                .iter()
+                .map(|v| v.to_string())
+                .collect::<Vec<_>>()
+                .join(", ");
+            push(
+                issues,
+                path,
+                Severity::Error,
+                format!("{} is not one of: {}", value, options),
+            );
+            return;
+        }
+    }
+
+    if let Some(branches) = schema.get("anyOf").and_then(|v| v.as_array()) {
+        validate_any_of(root, branches, value, path, issues);
+    }
+
+    match value {
+        Value::String(s)
+            if schema.get("format").and_then(|v| v.as_str()) == Some("uri") && !is_http_url(s) =>
+        {
+            push(
+                issues,
+                path,
+                Severity::Error,
+                format!("\"{}\" is not a valid http(s) URL", s),
+            );
+        }
+        Value::Number(n) => {
+            let n = n.as_f64().unwrap_or_default();
+            if let Some(min) = schema.get("minimum").and_then(|v| v.as_f64()) {
+                if n < min {
+                    push(
+                        issues,
+                        path,
+                        Severity::Error,
+                        format!("{} is less than the minimum {}", n, min),
+                    );
+                }
+            }
+            if let Some(max) = schema.get("maximum").and_then(|v| v.as_f64()) {
+                if n > max {
+                    push(
+                        issues,
+                        path,
+                        Severity::Error,
+                        format!("{} is greater than the maximum {}", n, max),
+                    );
+                }
+            }
+        }
+        Value::Array(items) => {
+            if let Some(min) = schema.get("minItems").and_then(|v| v.as_u64()) {
+                if (items.len() as u64) < min {
+                    push(
+                        issues,
+                        path,
+                        Severity::Error,
+                        format!("expected at least {} item(s)", min),
+                    );
+                }
+            }
+            if let Some(item_schema) = schema.get("items") {
+                for (idx, item) in items.iter().enumerate() {
+                    validate(
+                        root,
+                        item_schema,
+                        item,
+                        &format!("{}[{}]", path, idx),
+                        issues,
+                    );
+                }
+            }
+        }
+        Value::Object(map) => {
+            if let Some(required) = schema.get("required").and_then(|v| v.as_array()) {
+                for key in required.iter().filter_map(|k| k.as_str()) {
+                    if !map.contains_key(key) {
+                        push(
+                            issues,
+                            &join(path, key),
+                            Severity::Error,
+                            "required key is missing".to_string(),
+                        );
+                    }
+                }
+            }
+
+            let properties = schema.get("properties").and_then(|v| v.as_object());
+            let additional = schema.get("additionalProperties");
+            for (key, child) in map {
+                let child_path = join(path, key);
+                if let Some(child_schema) = properties.and_then(|p| p.get(key)) {
+                    validate(root, child_schema, child, &child_path, issues);
+                    continue;
+                }
+                match additional {
+                    Some(Value::Bool(false)) => push(
+                        issues,
+                        &child_path,
+                        Severity::Warning,
+                        "unknown key".to_string(),
+                    ),
+                    Some(extra @ Value::Object(_)) => {
+                        validate(root, extra, child, &child_path, issues)
+                    }
+                    _ => {}
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// 任一分支通过即可；都不通过时报告错误最少的那个分支
+fn validate_any_of(
+    root: &Value,
+    branches: &[Value],
+    value: &Value,
+    path: &str,
+    issues: &mut Vec<ValidationIssue>,
+) {
+    let mut best: Option<Vec<ValidationIssue>> = None;
+    for branch in branches {
+        let mut branch_issues = Vec::new();
+        validate(root, branch, value, path, &mut branch_issues);
+        let error_count = count_errors(&branch_issues);
+        if error_count == 0 {
+            issues.extend(branch_issues);
+            return;
+        }
+        if best
+            .as_ref()
+            .is_none_or(|current| error_count < count_errors(current))
+        {
+            best = Some(branch_issues);
+        }
+    }
+    if let Some(best) = best {
+        issues.extend(best);
+    }
+}
+
+fn count_errors(issues: &[ValidationIssue]) -> usize {
+    issues
+        .iter()
+        .filter(|issue| issue.severity == Severity::Error)
+        .count()
+}
+
+fn resolve_ref<'a>(root: &'a Value, reference: &str) -> Option<&'a Value> {
+    let name = reference.strip_prefix("#/$defs/")?;
+    root.get("$defs")?.get(name)
+}
+
+fn matches_type(expected: &Value, value: &Value) -> bool {
+    match expected {
+        Value::String(name) => matches_type_name(name, value),
+        Value::Array(names) => names
+            .iter()
+            .filter_map(|n| n.as_str())
+            .any(|name| matches_type_name(name, value)),
+        _ => true,
+    }
+}
+
+fn matches_type_name(name: &str, value: &Value) -> bool {
+    match name {
+        "object" => value.is_object(),
+        "array" => value.is_array(),
+        "string" => value.is_string(),
+        "boolean" => value.is_boolean(),
+        "null" => value.is_null(),
+        "number" => value.is_number(),
+        "integer" => value.is_i64() || value.is_u64(),
+        _ => true,
+    }
+}
+
+fn describe_type(expected: &Value) -> String {
+    match expected {
+        Value::Array(names) => names
+            .iter()
+            .filter_map(|n| n.as_str())
+            .collect::<Vec<_>>()
+            .join(" or "),
+        other => other.as_str().unwrap_or("value").to_string(),
+    }
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(n) if n.is_f64() => "number",
+        Value::Number(_) => "integer",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+fn is_http_url(value: &str) -> bool {
+    let rest = value
+        .strip_prefix("https://")
+        .or_else(|| value.strip_prefix("http://"));
+    rest.is_some_and(|host| !host.is_empty() && !host.starts_with('/'))
+}
+
+pub(super) fn join(path: &str, key: &str) -> String {
+    if path.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}.{}", path, key)
+    }
+}
+
+pub(super) fn push(
+    issues: &mut Vec<ValidationIssue>,
+    path: &str,
+    severity: Severity,
+    message: String,
+) {
+    issues.push(ValidationIssue {
+        path: path.to_string(),
+        message,
+        severity,
+    });
+}
@@ -0,0 +1,1611 @@
+use serde_json::Value;
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::path::Path;
+
+use super::adapter;
+use super::types::*;
+use crate::coding::all_api_hub;
+use crate::coding::config_history;
+use crate::coding::db_id::{db_new_id, db_record_id};
+use crate::coding::prompt_file::{read_prompt_content_file, write_prompt_content_file};
+use crate::db::DbState;
+use crate::secret_vault;
+use chrono::Local;
+use tauri::Emitter;
+
+/// Gemini API Key 认证（settings.json 中 `security.auth.selectedType` 的取值）
+pub const AUTH_TYPE_GEMINI_API_KEY: &str = "gemini-api-key";
+/// Vertex AI 认证
+pub const AUTH_TYPE_VERTEX_AI: &str = "vertex-ai";
+
+/// provider `env` 中可保存的变量，从本地 .env 导入时只读取这些
+const PROVIDER_ENV_FIELDS: [&str; 5] = [
+    "GEMINI_API_KEY",
+    "GOOGLE_API_KEY",
+    "GOOGLE_GEMINI_BASE_URL",
+    "GOOGLE_CLOUD_PROJECT",
+    "GOOGLE_CLOUD_LOCATION",
+];
+
+/// 由应用管理的 .env 变量：应用 provider 时先移除旧值，避免切换后残留上一个 provider 的配置
+const MANAGED_ENV_FIELDS: [&str; 7] = [
+    "GEMINI_API_KEY",
+    "GOOGLE_API_KEY",
+    "GOOGLE_GEMINI_BASE_URL",
+    "GOOGLE_CLOUD_PROJECT",
+    "GOOGLE_CLOUD_LOCATION",
+    "GOOGLE_GENAI_USE_VERTEXAI",
+    "GEMINI_MODEL",
+];
+
+/// 由应用管理的 settings.json 字段路径：合并前先移除，provider 未设置时不残留上一个 provider 的值
+const MANAGED_SETTINGS_FIELDS: [&[&str]; 2] =
+    [&["model", "name"], &["security", "auth", "selectedType"]];
+
+// ============================================================================
+// Gemini CLI Config Path Commands
+// ============================================================================
+
+/// Get Gemini CLI config directory path (~/.gemini/)
+fn get_gemini_cli_config_dir() -> Result<std::path::PathBuf, String> {
+    let home_dir = std::env::var("USERPROFILE")
+        .or_else(|_| std::env::var("HOME"))
+        .map_err(|_| "Failed to get home directory".to_string())?;
+
+    Ok(Path::new(&home_dir).join(".gemini"))
+}
+
+/// Get Gemini CLI settings.json path
+fn get_gemini_cli_settings_path() -> Result<std::path::PathBuf, String> {
+    Ok(get_gemini_cli_config_dir()?.join("settings.json"))
+}
+
+/// Get Gemini CLI .env path
+fn get_gemini_cli_env_path() -> Result<std::path::PathBuf, String> {
+    Ok(get_gemini_cli_config_dir()?.join(".env"))
+}
+
+fn get_gemini_cli_prompt_file_path() -> Result<std::path::PathBuf, String> {
+    Ok(get_gemini_cli_config_dir()?.join("GEMINI.md"))
+}
+
+async fn get_local_prompt_config() -> Result<Option<GeminiCliPromptConfig>, String> {
+    let prompt_path = get_gemini_cli_prompt_file_path()?;
+    let Some(prompt_content) = read_prompt_content_file(&prompt_path, "Gemini CLI")? else {
+        return Ok(None);
+    };
+
+    let now = Local::now().to_rfc3339();
+    Ok(Some(GeminiCliPromptConfig {
+        id: "__local__".to_string(),
+        name: "default".to_string(),
+        content: prompt_content,
+        is_applied: true,
+        sort_index: None,
+        created_at: Some(now.clone()),
+        updated_at: Some(now),
+    }))
+}
+
+fn write_prompt_content_to_file(prompt_content: Option<&str>) -> Result<(), String> {
+    let prompt_path = get_gemini_cli_prompt_file_path()?;
+    write_prompt_content_file(&prompt_path, prompt_content, "Gemini CLI", "geminicli")
+}
+
+/// Get Gemini CLI config directory path
+#[tauri::command]
+pub fn get_gemini_cli_config_dir_path() -> Result<String, String> {
+    let config_dir = get_gemini_cli_config_dir()?;
+    Ok(config_dir.to_string_lossy().to_string())
+}
+
+/// Get Gemini CLI settings.json file path
+#[tauri::command]
+pub fn get_gemini_cli_config_file_path() -> Result<String, String> {
+    let config_path = get_gemini_cli_settings_path()?;
+    Ok(config_path.to_string_lossy().to_string())
+}
+
+/// Reveal Gemini CLI config folder in file explorer
+#[tauri::command]
+pub fn reveal_gemini_cli_config_folder() -> Result<(), String> {
+    let config_dir = get_gemini_cli_config_dir()?;
+
+    // Ensure directory exists
+    if !config_dir.exists() {
+        fs::create_dir_all(&config_dir)
+            .map_err(|e| format!("Failed to create .gemini directory: {}", e))?;
+    }
+
+    // Open in file explorer (platform-specific)
+    #[cfg(target_os = "windows")]
+    {
+        std::process::Command::new("explorer")
+            .arg(&config_dir)
+            .spawn()
+            .map_err(|e| format!("Failed to open folder: {}", e))?;
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        std::process::Command::new("open")
+            .arg(&config_dir)
+            .spawn()
+            .map_err(|e| format!("Failed to open folder: {}", e))?;
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        std::process::Command::new("xdg-open")
+            .arg(&config_dir)
+            .spawn()
+            .map_err(|e| format!("Failed to open folder: {}", e))?;
+    }
+
+    Ok(())
+}
+
+// ============================================================================
+// Gemini CLI Provider Commands
+// ============================================================================
+
+/// List all Gemini CLI providers ordered by sort_index
+/// If database is empty, returns a temporary provider loaded from local config files
+#[tauri::command]
+pub async fn list_gemini_cli_providers(
+    state: tauri::State<'_, DbState>,
+) -> Result<Vec<GeminiCliProvider>, String> {
+    let db = state.db();
+    list_providers_internal(&db).await
+}
+
+/// 内部共享函数：读取所有 provider
+pub async fn list_providers_internal(
+    db: &surrealdb::Surreal<surrealdb::engine::local::Db>,
+) -> Result<Vec<GeminiCliProvider>, String> {
+    let records_result: Result<Vec<Value>, _> = db
+        .query("SELECT *, type::string(id) as id FROM gemini_cli_provider")
+        .await
+        .map_err(|e| format!("Failed to query providers: {}", e))?
+        .take(0);
+
+    match records_result {
+        Ok(records) => {
+            if records.is_empty() {
+                // Database is empty, try to load from local files as temporary provider
+                if let Ok(temp_provider) = load_temp_provider_from_files().await {
+                    return Ok(vec![temp_provider]);
+                }
+                Ok(Vec::new())
+            } else {
+                let mut result: Vec<GeminiCliProvider> = records
+                    .into_iter()
+                    .map(adapter::from_db_value_provider)
+                    .collect();
+                result.sort_by_key(|p| p.sort_index.unwrap_or(0));
+                Ok(result)
+            }
+        }
+        Err(e) => {
+            eprintln!("Failed to deserialize providers: {}", e);
+            // Try to load from local files as fallback
+            if let Ok(temp_provider) = load_temp_provider_from_files().await {
+                return Ok(vec![temp_provider]);
+            }
+            Ok(Vec::new())
+        }
+    }
+}
+
+/// Load a temporary provider from settings.json / .env without writing to database
+/// This is used when the database is empty and we want to show the local config
+async fn load_temp_provider_from_files() -> Result<GeminiCliProvider, String> {
+    let settings_path = get_gemini_cli_settings_path()?;
+    let env_path = get_gemini_cli_env_path()?;
+
+    if !settings_path.exists() && !env_path.exists() {
+        return Err("No config files found".to_string());
+    }
+
+    let settings = read_settings_value(&settings_path)?;
+    let env_vars = read_env_file(&env_path)?;
+
+    let mut provider_settings = serde_json::Map::new();
+    let auth_type = settings
+        .pointer("/security/auth/selectedType")
+        .and_then(|v| v.as_str())
+        .unwrap_or(AUTH_TYPE_GEMINI_API_KEY);
+    provider_settings.insert("authType".to_string(), serde_json::json!(auth_type));
+
+    let provider_env: serde_json::Map<String, Value> = PROVIDER_ENV_FIELDS
+        .iter()
+        .filter_map(|key| {
+            env_vars
+                .get(*key)
+                .map(|value| (key.to_string(), serde_json::json!(value)))
+        })
+        .collect();
+    provider_settings.insert("env".to_string(), serde_json::json!(provider_env));
+
+    let model = env_vars.get("GEMINI_MODEL").cloned().or_else(|| {
+        settings
+            .pointer("/model/name")
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string())
+    });
+    if let Some(model) = model {
+        provider_settings.insert("model".to_string(), serde_json::json!(model));
+    }
+
+    let now = Local::now().to_rfc3339();
+    Ok(GeminiCliProvider {
+        id: "__local__".to_string(), // Special ID to indicate this is from local files
+        name: "default".to_string(),
+        category: "custom".to_string(),
+        settings_config: serde_json::to_string(&provider_settings)
+            .map_err(|e| format!("Failed to serialize: {}", e))?,
+        source_provider_id: None,
+        website_url: None,
+        notes: None,
+        icon: None,
+        icon_color: None,
+        sort_index: Some(0),
+        is_applied: true,
+        is_disabled: false,
+        created_at: now.clone(),
+        updated_at: now,
+    })
+}
+
+/// Create a new Gemini CLI provider
+#[tauri::command]
+pub async fn create_gemini_cli_provider(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+    provider: GeminiCliProviderInput,
+) -> Result<GeminiCliProvider, String> {
+    let db = state.db();
+
+    let now = Local::now().to_rfc3339();
+    let content = GeminiCliProviderContent {
+        name: provider.name,
+        category: provider.category,
+        settings_config: provider.settings_config,
+        source_provider_id: provider.source_provider_id,
+        website_url: provider.website_url,
+        notes: provider.notes,
+        icon: provider.icon,
+        icon_color: provider.icon_color,
+        sort_index: provider.sort_index,
+        is_applied: false,
+        is_disabled: provider.is_disabled.unwrap_or(false),
+        created_at: now.clone(),
+        updated_at: now,
+    };
+
+    let json_data = adapter::to_db_value_provider(&content);
+
+    // Create new provider - SurrealDB auto-generates record ID
+    db.query("CREATE gemini_cli_provider CONTENT $data")
+        .bind(("data", json_data))
+        .await
+        .map_err(|e| format!("Failed to create provider: {}", e))?;
+
+    // Fetch the created record to get the auto-generated ID
+    let result: Result<Vec<Value>, _> = db
+        .query(
+            "SELECT *, type::string(id) as id FROM gemini_cli_provider ORDER BY created_at DESC LIMIT 1",
+        )
+        .await
+        .map_err(|e| format!("Failed to fetch created provider: {}", e))?
+        .take(0);
+
+    // Notify to refresh tray menu
+    let _ = app.emit("config-changed", "window");
+
+    match result {
+        Ok(records) => {
+            if let Some(record) = records.first() {
+                Ok(adapter::from_db_value_provider(record.clone()))
+            } else {
+                Err("Failed to retrieve created provider".to_string())
+            }
+        }
+        Err(e) => Err(format!("Failed to retrieve created provider: {}", e)),
+    }
+}
+
+/// Update an existing Gemini CLI provider
+#[tauri::command]
+pub async fn update_gemini_cli_provider(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+    provider: GeminiCliProvider,
+) -> Result<GeminiCliProvider, String> {
+    let db = state.db();
+
+    let id = provider.id.clone();
+    let now = Local::now().to_rfc3339();
+
+    // Get existing record to preserve created_at / is_disabled
+    let record_id = db_record_id("gemini_cli_provider", &id);
+    let existing_result: Result<Vec<Value>, _> = db
+        .query(format!("SELECT * OMIT id FROM {} LIMIT 1", record_id))
+        .await
+        .map_err(|e| format!("Failed to query existing provider: {}", e))?
+        .take(0);
+
+    let existing = match existing_result {
+        Ok(records) => records.into_iter().next(),
+        Err(_) => None,
+    }
+    .ok_or_else(|| format!("Gemini CLI provider with ID '{}' not found", id))?;
+
+    let created_at = if provider.created_at.is_empty() {
+        existing
+            .get("created_at")
+            .and_then(|v| v.as_str())
+            .unwrap_or(&now)
+            .to_string()
+    } else {
+        provider.created_at
+    };
+    let is_disabled = existing
+        .get("is_disabled")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+
+    let content = GeminiCliProviderContent {
+        name: provider.name,
+        category: provider.category,
+        settings_config: provider.settings_config,
+        source_provider_id: provider.source_provider_id,
+        website_url: provider.website_url,
+        notes: provider.notes,
+        icon: provider.icon,
+        icon_color: provider.icon_color,
+        sort_index: provider.sort_index,
+        is_applied: provider.is_applied,
+        is_disabled,
+        created_at,
+        updated_at: now,
+    };
+
+    let json_data = adapter::to_db_value_provider(&content);
+
+    db.query(format!("UPDATE {} CONTENT $data", record_id))
+        .bind(("data", json_data))
+        .await
+        .map_err(|e| format!("Failed to update provider: {}", e))?;
+
+    // If this provider is applied, re-apply to config files
+    if content.is_applied {
+        if let Err(e) = apply_config_to_file(&db, &id).await {
+            eprintln!("Failed to auto-apply updated config: {}", e);
+        }
+    }
+
+    // Notify frontend and tray to refresh
+    let _ = app.emit("config-changed", "window");
+
+    Ok(GeminiCliProvider {
+        id,
+        name: content.name,
+        category: content.category,
+        settings_config: content.settings_config,
+        source_provider_id: content.source_provider_id,
+        website_url: content.website_url,
+        notes: content.notes,
+        icon: content.icon,
+        icon_color: content.icon_color,
+        sort_index: content.sort_index,
+        is_applied: content.is_applied,
+        is_disabled: content.is_disabled,
+        created_at: content.created_at,
+        updated_at: content.updated_at,
+    })
+}
+
+/// Delete a Gemini CLI provider
+#[tauri::command]
+pub async fn delete_gemini_cli_provider(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+    id: String,
+) -> Result<(), String> {
+    let db = state.db();
+
+    db.query(format!(
+        "DELETE {}",
+        db_record_id("gemini_cli_provider", &id)
+    ))
+    .await
+    .map_err(|e| format!("Failed to delete Gemini CLI provider: {}", e))?;
+
+    let _ = app.emit("config-changed", "window");
+    Ok(())
+}
+
+/// Reorder Gemini CLI providers
+#[tauri::command]
+pub async fn reorder_gemini_cli_providers(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+    ids: Vec<String>,
+) -> Result<(), String> {
+    let db = state.db();
+
+    for (index, id) in ids.iter().enumerate() {
+        let record_id = db_record_id("gemini_cli_provider", id);
+        db.query(format!("UPDATE {} SET sort_index = $index", record_id))
+            .bind(("index", index as i32))
+            .await
+            .map_err(|e| format!("Failed to update provider {}: {}", id, e))?;
+    }
+
+    drop(db);
+    let _ = app.emit("config-changed", "window");
+
+    Ok(())
+}
+
+/// Select a Gemini CLI provider (mark as applied in database)
+#[tauri::command]
+pub async fn select_gemini_cli_provider(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+    id: String,
+) -> Result<(), String> {
+    let db = state.db();
+    update_is_applied_status(&db, &id).await?;
+
+    let _ = app.emit("config-changed", "window");
+    Ok(())
+}
+
+/// Internal function: update is_applied status
+/// Use UPDATE with WHERE to avoid SurrealDB MVCC version control issues
+async fn update_is_applied_status(
+    db: &surrealdb::Surreal<surrealdb::engine::local::Db>,
+    target_id: &str,
+) -> Result<(), String> {
+    let now = Local::now().to_rfc3339();
+
+    // Clear current applied status (only update the currently applied one)
+    db.query(
+        "UPDATE gemini_cli_provider SET is_applied = false, updated_at = $now WHERE is_applied = true",
+    )
+    .bind(("now", now.clone()))
+    .await
+    .map_err(|e| format!("Failed to clear applied status: {}", e))?;
+
+    // Set target provider as applied
+    let record_id = db_record_id("gemini_cli_provider", target_id);
+    db.query(format!(
+        "UPDATE {} SET is_applied = true, updated_at = $now",
+        record_id
+    ))
+    .bind(("now", now))
+    .await
+    .map_err(|e| format!("Failed to set applied status: {}", e))?;
+
+    Ok(())
+}
+
+/// Toggle is_disabled status for a provider
+#[tauri::command]
+pub async fn toggle_gemini_cli_provider_disabled(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+    provider_id: String,
+    is_disabled: bool,
+) -> Result<(), String> {
+    let db = state.db();
+
+    let now = Local::now().to_rfc3339();
+    let record_id = db_record_id("gemini_cli_provider", &provider_id);
+    db.query(format!(
+        "UPDATE {} SET is_disabled = $is_disabled, updated_at = $now",
+        record_id
+    ))
+    .bind(("is_disabled", is_disabled))
+    .bind(("now", now))
+    .await
+    .map_err(|e| format!("Failed to toggle provider disabled status: {}", e))?;
+
+    let provider = load_provider(&db, &provider_id).await?;
+    if provider.is_applied {
+        // Re-apply config to update files (will check is_disabled internally)
+        apply_config_internal(&db, &app, &provider_id, false).await?;
+    } else {
+        let _ = app.emit("config-changed", "window");
+    }
+
+    Ok(())
+}
+
+// ============================================================================
+// Gemini CLI Config File Commands
+// ============================================================================
+
+/// Internal function: apply provider config to files
+async fn apply_config_to_file(
+    db: &surrealdb::Surreal<surrealdb::engine::local::Db>,
+    provider_id: &str,
+) -> Result<(), String> {
+    apply_config_to_file_replacing(db, provider_id, &serde_json::Map::new()).await
+}
+
+/// 应用 provider 配置，并从现有文件中移除旧通用配置写入过的字段（通用配置修改后删掉的键不会残留）
+async fn apply_config_to_file_replacing(
+    db: &surrealdb::Surreal<surrealdb::engine::local::Db>,
+    provider_id: &str,
+    previous_common: &serde_json::Map<String, Value>,
+) -> Result<(), String> {
+    let provider = load_provider(db, provider_id).await?;
+
+    // Check if provider is disabled
+    if provider.is_disabled {
+        return Err(format!(
+            "Provider '{}' is disabled and cannot be applied",
+            provider_id
+        ));
+    }
+
+    let (settings, env_vars) = build_expected_config(db, &provider).await?;
+    let mut previous_common = Value::Object(previous_common.clone());
+    let previous_env = previous_common
+        .as_object_mut()
+        .and_then(|common| common.remove("env"))
+        .and_then(|env| env.as_object().map(|env| env.keys().cloned().collect()))
+        .unwrap_or_default();
+
+    write_gemini_cli_config_files(&settings, &env_vars, &previous_common, &previous_env)
+}
+
+async fn load_provider(
+    db: &surrealdb::Surreal<surrealdb::engine::local::Db>,
+    provider_id: &str,
+) -> Result<GeminiCliProvider, String> {
+    let record_id = db_record_id("gemini_cli_provider", provider_id);
+    let provider_result: Result<Vec<Value>, _> = db
+        .query(format!(
+            "SELECT *, type::string(id) as id FROM {} LIMIT 1",
+            record_id
+        ))
+        .await
+        .map_err(|e| format!("Failed to query provider: {}", e))?
+        .take(0);
+
+    match provider_result {
+        Ok(records) => match records.first() {
+            Some(record) => Ok(adapter::from_db_value_provider(record.clone())),
+            None => Err("Provider not found".to_string()),
+        },
+        Err(e) => Err(format!("Failed to deserialize provider: {}", e)),
+    }
+}
+
+pub async fn load_applied_provider(
+    db: &surrealdb::Surreal<surrealdb::engine::local::Db>,
+) -> Result<Option<GeminiCliProvider>, String> {
+    let applied_result: Result<Vec<Value>, _> = db
+        .query(
+            "SELECT *, type::string(id) as id FROM gemini_cli_provider WHERE is_applied = true LIMIT 1",
+        )
+        .await
+        .map_err(|e| format!("Failed to query applied provider: {}", e))?
+        .take(0);
+
+    Ok(applied_result
+        .ok()
+        .and_then(|records| records.first().cloned())
+        .map(adapter::from_db_value_provider))
+}
+
+/// 读取通用配置（settings.json 片段），未保存时为空对象
+async fn load_common_config(
+    db: &surrealdb::Surreal<surrealdb::engine::local::Db>,
+) -> Result<serde_json::Map<String, Value>, String> {
+    let common_config_result: Result<Vec<Value>, _> = db
+        .query("SELECT * OMIT id FROM gemini_cli_common_config:`common` LIMIT 1")
+        .await
+        .map_err(|e| format!("Failed to query common config: {}", e))?
+        .take(0);
+
+    let config = match common_config_result {
+        Ok(records) => records
+            .first()
+            .map(|record| adapter::from_db_value_common(record.clone()).config)
+            .unwrap_or_default(),
+        Err(_) => String::new(),
+    };
+
+    if config.trim().is_empty() {
+        return Ok(serde_json::Map::new());
+    }
+    match serde_json::from_str(&config) {
+        Ok(Value::Object(map)) => Ok(map),
+        Ok(_) => Err("Common config must be a JSON object".to_string()),
+        Err(e) => Err(format!("Failed to parse common config: {}", e)),
+    }
+}
+
+/// 按 provider 与通用配置生成 settings.json 中由应用管理的部分与 .env 变量
+async fn build_expected_config(
+    db: &surrealdb::Surreal<surrealdb::engine::local::Db>,
+    provider: &GeminiCliProvider,
+) -> Result<(Value, BTreeMap<String, String>), String> {
+    // Parse provider settings_config (保险库引用在此解出明文)
+    let provider_config: Value =
+        serde_json::from_str(&secret_vault::resolve_json_text(&provider.settings_config)?)
+            .map_err(|e| format!("Failed to parse provider config: {}", e))?;
+
+    let mut settings = Value::Object(load_common_config(db).await?);
+
+    // 通用配置中的 env 写入 .env，其余字段写入 settings.json
+    let mut env_vars = BTreeMap::new();
+    if let Some(Value::Object(common_env)) = settings.as_object_mut().and_then(|s| s.remove("env"))
+    {
+        for (key, value) in common_env {
+            env_vars.insert(key, env_value_to_string(&value));
+        }
+    }
+
+    // provider 的 env 覆盖通用配置，空值视为未设置
+    if let Some(provider_env) = provider_config.get("env").and_then(|v| v.as_object()) {
+        for (key, value) in provider_env {
+            let value = env_value_to_string(value);
+            if !value.trim().is_empty() {
+                env_vars.insert(key.clone(), value);
+            }
+        }
+    }
+
+    let auth_type = provider_config
+        .get("authType")
+        .and_then(|v| v.as_str())
+        .filter(|value| !value.trim().is_empty())
+        .unwrap_or(AUTH_TYPE_GEMINI_API_KEY);
+    if auth_type == AUTH_TYPE_VERTEX_AI {
+        env_vars.insert("GOOGLE_GENAI_USE_VERTEXAI".to_string(), "true".to_string());
+    }
+    merge_json_values(
+        &mut settings,
+        &serde_json::json!({ "security": { "auth": { "selectedType": auth_type } } }),
+    );
+
+    // GEMINI_MODEL 的优先级高于 settings.json，两处都写入避免旧值生效
+    if let Some(model) = provider_config
+        .get("model")
+        .and_then(|v| v.as_str())
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+    {
+        env_vars.insert("GEMINI_MODEL".to_string(), model.to_string());
+        merge_json_values(
+            &mut settings,
+            &serde_json::json!({ "model": { "name": model } }),
+        );
+    }
+
+    Ok((settings, env_vars))
+}
+
+fn env_value_to_string(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// 将 overlay 深度合并到 base：对象逐键合并，其他值直接覆盖
+fn merge_json_values(base: &mut Value, overlay: &Value) {
+    match (base, overlay) {
+        (Value::Object(base_map), Value::Object(overlay_map)) => {
+            for (key, overlay_value) in overlay_map {
+                match base_map.get_mut(key) {
+                    Some(base_value) => merge_json_values(base_value, overlay_value),
+                    None => {
+                        base_map.insert(key.clone(), overlay_value.clone());
+                    }
+                }
+            }
+        }
+        (base, overlay) => *base = overlay.clone(),
+    }
+}
+
+/// 由字段路径构造嵌套对象（叶子为 null），用于标记需要移除的字段
+fn path_to_value(path: &[&str]) -> Value {
+    path.iter()
+        .rev()
+        .fold(Value::Null, |value, key| serde_json::json!({ *key: value }))
+}
+
+/// 从 settings 中移除 managed 里出现的叶子字段，并清理因此变空的对象
+fn remove_managed_settings(settings: &mut Value, managed: &Value) {
+    let (Value::Object(settings_map), Value::Object(managed_map)) = (settings, managed) else {
+        return;
+    };
+    for (key, managed_value) in managed_map {
+        let Some(value) = settings_map.get_mut(key) else {
+            continue;
+        };
+        if !managed_value.is_object() || !value.is_object() {
+            settings_map.remove(key);
+            continue;
+        }
+        remove_managed_settings(value, managed_value);
+        if value.as_object().is_some_and(|map| map.is_empty()) {
+            settings_map.remove(key);
+        }
+    }
+}
+
+/// 在现有 settings.json 上写入应用管理的配置：先移除应用管理的字段、本次与上次通用配置的字段，
+/// 再深度合并，保留 mcpServers 等用户自行维护的字段
+fn merge_settings(existing: Value, settings: &Value, previous_common: &Value) -> Value {
+    let mut merged = if existing.is_object() {
+        existing
+    } else {
+        serde_json::json!({})
+    };
+    for path in MANAGED_SETTINGS_FIELDS {
+        remove_managed_settings(&mut merged, &path_to_value(path));
+    }
+    remove_managed_settings(&mut merged, previous_common);
+    remove_managed_settings(&mut merged, settings);
+    merge_json_values(&mut merged, settings);
+    merged
+}
+
+/// 读取 settings.json，文件不存在或为空时视为空对象
+fn read_settings_value(settings_path: &Path) -> Result<Value, String> {
+    if !settings_path.exists() {
+        return Ok(serde_json::json!({}));
+    }
+
+    let content = fs::read_to_string(settings_path)
+        .map_err(|e| format!("Failed to read settings.json: {}", e))?;
+    if content.trim().is_empty() {
+        return Ok(serde_json::json!({}));
+    }
+    serde_json::from_str(&content).map_err(|e| format!("Failed to parse settings.json: {}", e))
+}
+
+fn read_env_file(env_path: &Path) -> Result<BTreeMap<String, String>, String> {
+    if !env_path.exists() {
+        return Ok(BTreeMap::new());
+    }
+    let content =
+        fs::read_to_string(env_path).map_err(|e| format!("Failed to read .env: {}", e))?;
+    Ok(content.lines().filter_map(parse_env_line).collect())
+}
+
+/// 解析 .env 中的一行 `KEY=value`（支持 `export` 前缀、引号与行尾注释）
+fn parse_env_line(line: &str) -> Option<(String, String)> {
+    let line = line.trim();
+    if line.is_empty() || line.starts_with('#') {
+        return None;
+    }
+    let line = line.strip_prefix("export ").unwrap_or(line);
+    let (key, value) = line.split_once('=')?;
+    let key = key.trim();
+    if key.is_empty() {
+        return None;
+    }
+
+    let value = value.trim();
+    let value = if let Some(inner) = value
+        .strip_prefix('"')
+        .and_then(|rest| rest.rfind('"').map(|end| &rest[..end]))
+    {
+        inner.replace("\\\"", "\"").replace("\\\\", "\\")
+    } else if let Some(inner) = value
+        .strip_prefix('\'')
+        .and_then(|rest| rest.rfind('\'').map(|end| &rest[..end]))
+    {
+        inner.to_string()
+    } else {
+        value
+            .split(" #")
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .to_string()
+    };
+
+    Some((key.to_string(), value))
+}
+
+fn format_env_value(value: &str) -> String {
+    let needs_quotes = value.is_empty()
+        || value
+            .chars()
+            .any(|c| c.is_whitespace() || matches!(c, '#' | '"' | '\'' | '\\'));
+    if needs_quotes {
+        format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// 在现有 .env 内容上写入新变量：移除由应用管理的旧变量（含上次通用配置写入的变量），保留其他行（含注释）
+fn merge_env_content(
+    existing: &str,
+    env_vars: &BTreeMap<String, String>,
+    previous_env: &HashSet<String>,
+) -> String {
+    let managed: HashSet<&str> = MANAGED_ENV_FIELDS
+        .iter()
+        .copied()
+        .chain(env_vars.keys().map(String::as_str))
+        .chain(previous_env.iter().map(String::as_str))
+        .collect();
+
+    let mut lines: Vec<String> = existing
+        .lines()
+        .filter(|line| {
+            parse_env_line(line)
+                .map(|(key, _)| !managed.contains(key.as_str()))
+                .unwrap_or(true)
+        })
+        .map(|line| line.to_string())
+        .collect();
+    while lines.last().is_some_and(|line| line.trim().is_empty()) {
+        lines.pop();
+    }
+
+    for (key, value) in env_vars {
+        lines.push(format!("{}={}", key, format_env_value(value)));
+    }
+
+    let mut content = lines.join("\n");
+    content.push('\n');
+    content
+}
+
+/// Write settings.json and .env files
+fn write_gemini_cli_config_files(
+    settings: &Value,
+    env_vars: &BTreeMap<String, String>,
+    previous_common: &Value,
+    previous_env: &HashSet<String>,
+) -> Result<(), String> {
+    let config_dir = get_gemini_cli_config_dir()?;
+
+    // Ensure directory exists
+    if !config_dir.exists() {
+        fs::create_dir_all(&config_dir)
+            .map_err(|e| format!("Failed to create .gemini directory: {}", e))?;
+    }
+
+    // settings.json 在现有文件上合并，保留 mcpServers 等不由应用管理的字段
+    let settings_path = config_dir.join("settings.json");
+    let final_settings = merge_settings(
+        read_settings_value(&settings_path)?,
+        settings,
+        previous_common,
+    );
+    let settings_content = serde_json::to_string_pretty(&final_settings)
+        .map_err(|e| format!("Failed to serialize settings: {}", e))?;
+    config_history::write(
+        &settings_path,
+        settings_content,
+        "geminicli",
+        "apply_provider",
+    )
+    .map_err(|e| format!("Failed to write settings.json: {}", e))?;
+
+    let env_path = config_dir.join(".env");
+    let existing_env = if env_path.exists() {
+        fs::read_to_string(&env_path).map_err(|e| format!("Failed to read .env: {}", e))?
+    } else {
+        String::new()
+    };
+    config_history::write(
+        &env_path,
+        merge_env_content(&existing_env, env_vars, previous_env),
+        "geminicli",
+        "apply_provider",
+    )
+    .map_err(|e| format!("Failed to write .env: {}", e))?;
+
+    Ok(())
+}
+
+/// Apply Gemini CLI config to files
+#[tauri::command]
+pub async fn apply_gemini_cli_config(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+    provider_id: String,
+) -> Result<(), String> {
+    let db = state.db();
+    apply_config_internal(&db, &app, &provider_id, false).await
+}
+
+/// Internal function to apply config
+pub async fn apply_config_internal<R: tauri::Runtime>(
+    db: &surrealdb::Surreal<surrealdb::engine::local::Db>,
+    app: &tauri::AppHandle<R>,
+    provider_id: &str,
+    from_tray: bool,
+) -> Result<(), String> {
+    apply_provider(db, provider_id).await?;
+
+    let payload = if from_tray { "tray" } else { "window" };
+    let _ = app.emit("config-changed", payload);
+
+    Ok(())
+}
+
+/// 应用 provider 到配置文件并更新 is_applied，不发送事件
+pub async fn apply_provider(
+    db: &surrealdb::Surreal<surrealdb::engine::local::Db>,
+    provider_id: &str,
+) -> Result<(), String> {
+    apply_config_to_file(db, provider_id).await?;
+    update_is_applied_status(db, provider_id).await
+}
+
+/// Read current Gemini CLI settings from files
+#[tauri::command]
+pub async fn read_gemini_cli_settings() -> Result<GeminiCliSettings, String> {
+    let settings_path = get_gemini_cli_settings_path()?;
+    let env_path = get_gemini_cli_env_path()?;
+
+    let settings = if settings_path.exists() {
+        Some(read_settings_value(&settings_path)?)
+    } else {
+        None
+    };
+    let env = if env_path.exists() {
+        Some(read_env_file(&env_path)?)
+    } else {
+        None
+    };
+
+    Ok(GeminiCliSettings { settings, env })
+}
+
+// ============================================================================
+// Gemini CLI Common Config Commands
+// ============================================================================
+
+/// Get Gemini CLI common config
+/// If database is empty, returns None (settings.json is shared with MCP and not imported as common config)
+#[tauri::command]
+pub async fn get_gemini_cli_common_config(
+    state: tauri::State<'_, DbState>,
+) -> Result<Option<GeminiCliCommonConfig>, String> {
+    let db = state.db();
+
+    let records_result: Result<Vec<Value>, _> = db
+        .query("SELECT *, type::string(id) as id FROM gemini_cli_common_config:`common` LIMIT 1")
+        .await
+        .map_err(|e| format!("Failed to query common config: {}", e))?
+        .take(0);
+
+    match records_result {
+        Ok(records) => Ok(records
+            .first()
+            .map(|record| adapter::from_db_value_common(record.clone()))),
+        Err(e) => {
+            // 反序列化失败，删除旧数据以修复版本冲突
+            eprintln!(
+                "⚠️ Gemini CLI common config has incompatible format, cleaning up: {}",
+                e
+            );
+            let _ = db.query("DELETE gemini_cli_common_config:`common`").await;
+            Ok(None)
+        }
+    }
+}
+
+fn validate_common_config(config: &str) -> Result<(), String> {
+    if config.trim().is_empty() {
+        return Ok(());
+    }
+    match serde_json::from_str::<Value>(config) {
+        Ok(Value::Object(_)) => Ok(()),
+        Ok(_) => Err("Common config must be a JSON object".to_string()),
+        Err(e) => Err(format!("Invalid JSON: {}", e)),
+    }
+}
+
+/// Save Gemini CLI common config
+#[tauri::command]
+pub async fn save_gemini_cli_common_config(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+    config: String,
+) -> Result<(), String> {
+    validate_common_config(&config)?;
+
+    let db = state.db();
+    let previous_common = load_common_config(&db).await.unwrap_or_default();
+    let json_data = adapter::to_db_value_common(&config);
+
+    // Use UPSERT to handle both update and create
+    db.query("UPSERT gemini_cli_common_config:`common` CONTENT $data")
+        .bind(("data", json_data))
+        .await
+        .map_err(|e| format!("Failed to save config: {}", e))?;
+
+    // Re-apply current provider config to write merged config to files
+    if let Some(provider) = load_applied_provider(&db).await? {
+        if let Err(e) = apply_config_to_file_replacing(&db, &provider.id, &previous_common).await {
+            eprintln!("Failed to re-apply config: {}", e);
+        }
+    }
+
+    let _ = app.emit("config-changed", "window");
+
+    Ok(())
+}
+
+/// Save local config (provider and/or common) into database
+/// Input can include provider and/or commonConfig; missing parts will be loaded from local files
+#[tauri::command]
+pub async fn save_gemini_cli_local_config(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+    input: GeminiCliLocalConfigInput,
+) -> Result<(), String> {
+    let common_config = input.common_config.unwrap_or_default();
+    validate_common_config(&common_config)?;
+
+    let db = state.db();
+
+    // Load base provider from local files
+    let base_provider = load_temp_provider_from_files().await?;
+
+    let provider_input = input.provider;
+    let provider_name = provider_input
+        .as_ref()
+        .map(|p| p.name.clone())
+        .unwrap_or(base_provider.name);
+    let provider_category = provider_input
+        .as_ref()
+        .map(|p| p.category.clone())
+        .unwrap_or(base_provider.category);
+    let provider_settings_config = provider_input
+        .as_ref()
+        .map(|p| p.settings_config.clone())
+        .unwrap_or(base_provider.settings_config);
+    let provider_source_id = provider_input
+        .as_ref()
+        .and_then(|p| p.source_provider_id.clone());
+    let provider_notes = provider_input
+        .as_ref()
+        .and_then(|p| p.notes.clone())
+        .or(base_provider.notes);
+    let provider_sort_index = provider_input
+        .as_ref()
+        .and_then(|p| p.sort_index)
+        .or(base_provider.sort_index);
+
+    let now = Local::now().to_rfc3339();
+    let provider_content = GeminiCliProviderContent {
+        name: provider_name,
+        category: provider_category,
+        settings_config: provider_settings_config,
+        source_provider_id: provider_source_id,
+        website_url: None,
+        notes: provider_notes,
+        icon: None,
+        icon_color: None,
+        sort_index: provider_sort_index,
+        is_applied: true,
+        is_disabled: false,
+        created_at: now.clone(),
+        updated_at: now,
+    };
+
+    let provider_json = adapter::to_db_value_provider(&provider_content);
+    db.query("CREATE gemini_cli_provider CONTENT $data")
+        .bind(("data", provider_json))
+        .await
+        .map_err(|e| format!("Failed to create provider: {}", e))?;
+
+    let previous_common = load_common_config(&db).await.unwrap_or_default();
+    let common_json = adapter::to_db_value_common(&common_config);
+    db.query("UPSERT gemini_cli_common_config:`common` CONTENT $data")
+        .bind(("data", common_json))
+        .await
+        .map_err(|e| format!("Failed to save common config: {}", e))?;
+
+    // Re-apply config to files using the newly created provider
+    if let Some(created_provider) = load_applied_provider(&db).await? {
+        if let Err(e) =
+            apply_config_to_file_replacing(&db, &created_provider.id, &previous_common).await
+        {
+            eprintln!("Failed to apply config after local save: {}", e);
+        }
+    }
+
+    let _ = app.emit("config-changed", "window");
+    Ok(())
+}
+
+// ============================================================================
+// Gemini CLI Prompt Config Commands
+// ============================================================================
+
+#[tauri::command]
+pub async fn list_gemini_cli_prompt_configs(
+    state: tauri::State<'_, DbState>,
+) -> Result<Vec<GeminiCliPromptConfig>, String> {
+    let db = state.db();
+
+    let records_result: Result<Vec<Value>, _> = db
+        .query("SELECT *, type::string(id) as id FROM gemini_cli_prompt_config")
+        .await
+        .map_err(|e| format!("Failed to query prompt configs: {}", e))?
+        .take(0);
+
+    match records_result {
+        Ok(records) => {
+            if records.is_empty() {
+                drop(db);
+                if let Some(local_config) = get_local_prompt_config().await? {
+                    return Ok(vec![local_config]);
+                }
+                return Ok(Vec::new());
+            }
+
+            let mut result: Vec<GeminiCliPromptConfig> = records
+                .into_iter()
+                .map(adapter::from_db_value_prompt)
+                .collect();
+
+            result.sort_by(|a, b| match (a.sort_index, b.sort_index) {
+                (Some(ai), Some(bi)) => ai.cmp(&bi),
+                (Some(_), None) => std::cmp::Ordering::Less,
+                (None, Some(_)) => std::cmp::Ordering::Greater,
+                (None, None) => a.name.cmp(&b.name),
+            });
+
+            Ok(result)
+        }
+        Err(e) => {
+            eprintln!("Failed to deserialize Gemini CLI prompt configs: {}", e);
+            drop(db);
+            if let Some(local_config) = get_local_prompt_config().await? {
+                return Ok(vec![local_config]);
+            }
+            Ok(Vec::new())
+        }
+    }
+}
+
+#[tauri::command]
+pub async fn create_gemini_cli_prompt_config(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+    input: GeminiCliPromptConfigInput,
+) -> Result<GeminiCliPromptConfig, String> {
+    let db = state.db();
+    let now = Local::now().to_rfc3339();
+
+    let sort_index_result: Result<Vec<Value>, _> = db
+        .query("SELECT sort_index FROM gemini_cli_prompt_config ORDER BY sort_index DESC LIMIT 1")
+        .await
+        .map_err(|e| format!("Failed to query prompt sort index: {}", e))?
+        .take(0);
+
+    let next_sort_index = sort_index_result
+        .ok()
+        .and_then(|records| records.first().cloned())
+        .and_then(|record| record.get("sort_index").and_then(|value| value.as_i64()))
+        .map(|value| value as i32 + 1)
+        .unwrap_or(0);
+
+    let content = GeminiCliPromptConfigContent {
+        name: input.name,
+        content: input.content,
+        is_applied: false,
+        sort_index: Some(next_sort_index),
+        created_at: now.clone(),
+        updated_at: now,
+    };
+
+    let json_data = adapter::to_db_value_prompt(&content);
+    let prompt_id = db_new_id();
+    let record_id = db_record_id("gemini_cli_prompt_config", &prompt_id);
+
+    db.query(format!("CREATE {} CONTENT $data", record_id))
+        .bind(("data", json_data))
+        .await
+        .map_err(|e| format!("Failed to create prompt config: {}", e))?;
+
+    let records_result: Result<Vec<Value>, _> = db
+        .query(format!(
+            "SELECT *, type::string(id) as id FROM {} LIMIT 1",
+            record_id
+        ))
+        .await
+        .map_err(|e| format!("Failed to query created prompt config: {}", e))?
+        .take(0);
+    let created_config = match records_result {
+        Ok(records) => match records.first() {
+            Some(record) => adapter::from_db_value_prompt(record.clone()),
+            None => return Err("Failed to retrieve created prompt config".to_string()),
+        },
+        Err(e) => {
+            return Err(format!(
+                "Failed to deserialize created prompt config: {}",
+                e
+            ))
+        }
+    };
+
+    let _ = app.emit("config-changed", "window");
+
+    Ok(created_config)
+}
+
+#[tauri::command]
+pub async fn update_gemini_cli_prompt_config(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+    input: GeminiCliPromptConfigInput,
+) -> Result<GeminiCliPromptConfig, String> {
+    let config_id = input
+        .id
+        .ok_or_else(|| "ID is required for update".to_string())?;
+    let db = state.db();
+    let record_id = db_record_id("gemini_cli_prompt_config", &config_id);
+
+    let existing_result: Result<Vec<Value>, _> = db
+        .query(format!(
+            "SELECT *, type::string(id) as id FROM {} LIMIT 1",
+            record_id
+        ))
+        .await
+        .map_err(|e| format!("Failed to query prompt config: {}", e))?
+        .take(0);
+
+    let existing = match existing_result {
+        Ok(records) => match records.first() {
+            Some(record) => adapter::from_db_value_prompt(record.clone()),
+            None => return Err(format!("Prompt config '{}' not found", config_id)),
+        },
+        Err(e) => return Err(format!("Failed to deserialize prompt config: {}", e)),
+    };
+
+    let now = Local::now().to_rfc3339();
+    let content = GeminiCliPromptConfigContent {
+        name: input.name,
+        content: input.content,
+        is_applied: existing.is_applied,
+        sort_index: existing.sort_index,
+        created_at: existing.created_at.unwrap_or_else(|| now.clone()),
+        updated_at: now.clone(),
+    };
+    let json_data = adapter::to_db_value_prompt(&content);
+
+    db.query(format!("UPDATE {} CONTENT $data", record_id))
+        .bind(("data", json_data))
+        .await
+        .map_err(|e| format!("Failed to update prompt config: {}", e))?;
+
+    drop(db);
+
+    if content.is_applied {
+        write_prompt_content_to_file(Some(content.content.as_str()))?;
+    }
+
+    let _ = app.emit("config-changed", "window");
+
+    Ok(GeminiCliPromptConfig {
+        id: config_id,
+        name: content.name,
+        content: content.content,
+        is_applied: content.is_applied,
+        sort_index: content.sort_index,
+        created_at: Some(content.created_at),
+        updated_at: Some(now),
+    })
+}
+
+#[tauri::command]
+pub async fn delete_gemini_cli_prompt_config(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+    id: String,
+) -> Result<(), String> {
+    let db = state.db();
+    let record_id = db_record_id("gemini_cli_prompt_config", &id);
+
+    db.query(format!("DELETE {}", record_id))
+        .await
+        .map_err(|e| format!("Failed to delete prompt config: {}", e))?;
+
+    drop(db);
+    let _ = app.emit("config-changed", "window");
+    Ok(())
+}
+
+pub async fn apply_prompt_config_internal<R: tauri::Runtime>(
+    state: tauri::State<'_, DbState>,
+    app: &tauri::AppHandle<R>,
+    config_id: &str,
+    from_tray: bool,
+) -> Result<(), String> {
+    if config_id == "__local__" {
+        let local_prompt = get_local_prompt_config()
+            .await?
+            .ok_or_else(|| "Local default prompt not found".to_string())?;
+        write_prompt_content_to_file(Some(local_prompt.content.as_str()))?;
+
+        let payload = if from_tray { "tray" } else { "window" };
+        let _ = app.emit("config-changed", payload);
+
+        return Ok(());
+    }
+
+    let db = state.db();
+    let record_id = db_record_id("gemini_cli_prompt_config", config_id);
+    let records_result: Result<Vec<Value>, _> = db
+        .query(format!(
+            "SELECT *, type::string(id) as id FROM {} LIMIT 1",
+            record_id
+        ))
+        .await
+        .map_err(|e| format!("Failed to query prompt config: {}", e))?
+        .take(0);
+
+    let prompt_config = match records_result {
+        Ok(records) => match records.first() {
+            Some(record) => adapter::from_db_value_prompt(record.clone()),
+            None => return Err(format!("Prompt config '{}' not found", config_id)),
+        },
+        Err(e) => return Err(format!("Failed to deserialize prompt config: {}", e)),
+    };
+
+    let now = Local::now().to_rfc3339();
+
+    db.query("UPDATE gemini_cli_prompt_config SET is_applied = false, updated_at = $now WHERE is_applied = true")
+        .bind(("now", now.clone()))
+        .await
+        .map_err(|e| format!("Failed to clear prompt applied flags: {}", e))?;
+
+    db.query(format!(
+        "UPDATE {} SET is_applied = true, updated_at = $now",
+        record_id
+    ))
+    .bind(("now", now))
+    .await
+    .map_err(|e| format!("Failed to set prompt applied flag: {}", e))?;
+
+    drop(db);
+
+    write_prompt_content_to_file(Some(prompt_config.content.as_str()))?;
+
+    let payload = if from_tray { "tray" } else { "window" };
+    let _ = app.emit("config-changed", payload);
+
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn apply_gemini_cli_prompt_config(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+    config_id: String,
+) -> Result<(), String> {
+    apply_prompt_config_internal(state, &app, &config_id, false).await
+}
+
+#[tauri::command]
+pub async fn reorder_gemini_cli_prompt_configs(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+    ids: Vec<String>,
+) -> Result<(), String> {
+    let db = state.db();
+
+    for (index, id) in ids.iter().enumerate() {
+        let record_id = db_record_id("gemini_cli_prompt_config", id);
+        db.query(format!("UPDATE {} SET sort_index = $index", record_id))
+            .bind(("index", index as i32))
+            .await
+            .map_err(|e| format!("Failed to update prompt sort index: {}", e))?;
+    }
+
+    drop(db);
+    let _ = app.emit("config-changed", "window");
+
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn save_gemini_cli_local_prompt_config(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+    input: GeminiCliPromptConfigInput,
+) -> Result<GeminiCliPromptConfig, String> {
+    let prompt_content = if input.content.trim().is_empty() {
+        get_local_prompt_config()
+            .await?
+            .map(|config| config.content)
+            .unwrap_or_default()
+    } else {
+        input.content
+    };
+
+    let created = create_gemini_cli_prompt_config(
+        state.clone(),
+        app.clone(),
+        GeminiCliPromptConfigInput {
+            id: None,
+            name: input.name,
+            content: prompt_content,
+        },
+    )
+    .await?;
+
+    apply_prompt_config_internal(state.clone(), &app, &created.id, false).await?;
+
+    Ok(GeminiCliPromptConfig {
+        is_applied: true,
+        ..created
+    })
+}
+
+// ============================================================================
+// Gemini CLI All API Hub Import Commands
+// ============================================================================
+
+fn to_all_api_hub_provider(
+    candidate: &all_api_hub::AllApiHubProviderCandidate,
+) -> GeminiCliAllApiHubProvider {
+    GeminiCliAllApiHubProvider {
+        provider_id: candidate.provider_id.clone(),
+        name: candidate.name.clone(),
+        npm: Some(candidate.npm.clone()),
+        base_url: candidate.base_url.clone(),
+        requires_browser_open: candidate
+            .auth_type
+            .as_deref()
+            .map(|value| value.trim().eq_ignore_ascii_case("cookie"))
+            .unwrap_or(false),
+        is_disabled: candidate.is_disabled,
+        has_api_key: candidate
+            .api_key
+            .as_ref()
+            .map(|v| !v.is_empty())
+            .unwrap_or(false),
+        api_key_preview: candidate
+            .api_key
+            .as_ref()
+            .map(|value| all_api_hub::mask_api_key_preview(value)),
+        balance_usd: candidate.balance_usd,
+        balance_cny: candidate.balance_cny,
+        site_name: candidate.site_name.clone(),
+        site_type: candidate.site_type.clone(),
+        account_label: candidate.account_label.clone(),
+        source_browser: candidate.source_browser.clone(),
+        source_profile_name: candidate.source_profile_name.clone(),
+        source_extension_id: candidate.source_extension_id.clone(),
+        provider_config: serde_json::to_value(all_api_hub::candidate_to_opencode_provider(
+            candidate,
+        ))
+        .unwrap_or_else(|_| serde_json::json!({})),
+    }
+}
+
+#[tauri::command]
+pub async fn list_gemini_cli_all_api_hub_providers(
+    state: tauri::State<'_, DbState>,
+) -> Result<GeminiCliAllApiHubProvidersResult, String> {
+    let _ = state;
+    let discovery = all_api_hub::list_provider_candidates()?;
+
+    Ok(GeminiCliAllApiHubProvidersResult {
+        found: discovery.found,
+        profiles: discovery.profiles,
+        providers: discovery
+            .providers
+            .iter()
+            .map(to_all_api_hub_provider)
+            .collect(),
+        message: discovery.message,
+    })
+}
+
+#[tauri::command]
+pub async fn resolve_gemini_cli_all_api_hub_providers(
+    state: tauri::State<'_, DbState>,
+    request: ResolveGeminiCliAllApiHubProvidersRequest,
+) -> Result<Vec<GeminiCliAllApiHubProvider>, String> {
+    let providers =
+        all_api_hub::resolve_provider_candidates_with_keys(&state, &request.provider_ids).await?;
+
+    Ok(providers.iter().map(to_all_api_hub_provider).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{merge_env_content, merge_json_values, merge_settings, parse_env_line};
+    use std::collections::{BTreeMap, HashSet};
+
+    #[test]
+    fn parse_env_line_handles_quotes_export_and_comments() {
+        assert_eq!(
+            parse_env_line("export GEMINI_API_KEY=abc # key"),
+            Some(("GEMINI_API_KEY".to_string(), "abc".to_string()))
+        );
+        assert_eq!(
+            parse_env_line(r#"GOOGLE_CLOUD_PROJECT="my \"project\"""#),
+            Some((
+                "GOOGLE_CLOUD_PROJECT".to_string(),
+                r#"my "project""#.to_string()
+            ))
+        );
+        assert_eq!(parse_env_line("# comment"), None);
+        assert_eq!(parse_env_line("   "), None);
+    }
+
+    #[test]
+    fn merge_env_content_replaces_managed_keys_and_keeps_others() {
+        let existing = "# my env\nGEMINI_API_KEY=old\nGOOGLE_GENAI_USE_VERTEXAI=true\nHTTPS_PROXY=http://127.0.0.1:7890\nOLD_COMMON=1\n";
+        let mut env_vars = BTreeMap::new();
+        env_vars.insert("GEMINI_API_KEY".to_string(), "new key".to_string());
+        let previous_env = HashSet::from(["OLD_COMMON".to_string()]);
+
+        let merged = merge_env_content(existing, &env_vars, &previous_env);
+
+        assert_eq!(
+            merged,
+            "# my env\nHTTPS_PROXY=http://127.0.0.1:7890\nGEMINI_API_KEY=\"new key\"\n"
+        );
+        let parsed: Vec<_> = merged.lines().filter_map(parse_env_line).collect();
+        assert!(parsed.contains(&("GEMINI_API_KEY".to_string(), "new key".to_string())));
+    }
+
+    #[test]
+    fn merge_json_values_keeps_unrelated_settings() {
+        let mut base = serde_json::json!({
+            "mcpServers": { "fs": { "command": "npx" } },
+            "security": { "auth": { "selectedType": "oauth-personal" }, "folderTrust": true }
+        });
+        merge_json_values(
+            &mut base,
+            &serde_json::json!({ "security": { "auth": { "selectedType": "gemini-api-key" } } }),
+        );
+
+        assert_eq!(base["mcpServers"]["fs"]["command"], "npx");
+        assert_eq!(base["security"]["folderTrust"], true);
+        assert_eq!(base["security"]["auth"]["selectedType"], "gemini-api-key");
+    }
+
+    #[test]
+    fn merge_settings_drops_stale_managed_and_common_fields() {
+        let existing = serde_json::json!({
+            "mcpServers": { "fs": { "command": "npx" } },
+            "model": { "name": "gemini-2.5-pro" },
+            "security": { "auth": { "selectedType": "vertex-ai" } },
+            "ui": { "theme": "Dracula", "hideTips": true }
+        });
+        let settings = serde_json::json!({
+            "security": { "auth": { "selectedType": "gemini-api-key" } }
+        });
+        let previous_common = serde_json::json!({ "ui": { "hideTips": true } });
+
+        let merged = merge_settings(existing, &settings, &previous_common);
+
+        assert_eq!(
+            merged,
+            serde_json::json!({
+                "mcpServers": { "fs": { "command": "npx" } },
+                "security": { "auth": { "selectedType": "gemini-api-key" } },
+                "ui": { "theme": "Dracula" }
+            })
+        );
+    }
+}
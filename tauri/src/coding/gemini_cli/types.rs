@@ -0,0 +1,206 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+// ============================================================================
+// Gemini CLI Provider Types
+// ============================================================================
+
+/// GeminiCliProvider - API response
+///
+/// `settings_config` 为 JSON 字符串：
+/// `{ "authType": "gemini-api-key" | "vertex-ai", "env": { ... }, "model": "..." }`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GeminiCliProvider {
+    pub id: String,
+    pub name: String,
+    pub category: String,
+    pub settings_config: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source_provider_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub website_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub icon: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub icon_color: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sort_index: Option<i32>,
+    pub is_applied: bool,
+    pub is_disabled: bool,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+/// GeminiCliProvider - Content for create/update (Database storage)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GeminiCliProviderContent {
+    pub name: String,
+    pub category: String,
+    pub settings_config: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source_provider_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub website_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub icon: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub icon_color: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sort_index: Option<i32>,
+    pub is_applied: bool,
+    pub is_disabled: bool,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+/// GeminiCliProvider - Input from frontend (for create operation)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GeminiCliProviderInput {
+    #[serde(default)]
+    pub id: Option<String>,
+    pub name: String,
+    pub category: String,
+    pub settings_config: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source_provider_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub website_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub icon: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub icon_color: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sort_index: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_disabled: Option<bool>,
+}
+
+// ============================================================================
+// Gemini CLI Common Config Types
+// ============================================================================
+
+/// GeminiCliCommonConfig - API response
+/// `config` 为 settings.json 片段（JSON 字符串），其中的 `env` 写入 .env
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GeminiCliCommonConfig {
+    pub config: String,
+    pub updated_at: String,
+}
+
+/// Input for saving local config (provider and/or common)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GeminiCliLocalConfigInput {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub provider: Option<GeminiCliProviderInput>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub common_config: Option<String>,
+}
+
+/// Gemini CLI settings structure (for reading config files)
+/// settings.json + .env combined
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GeminiCliSettings {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub settings: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub env: Option<BTreeMap<String, String>>,
+}
+
+// ============================================================================
+// Gemini CLI Prompt Config Types
+// ============================================================================
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GeminiCliPromptConfigInput {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    pub name: String,
+    pub content: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GeminiCliPromptConfig {
+    pub id: String,
+    pub name: String,
+    pub content: String,
+    pub is_applied: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sort_index: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GeminiCliPromptConfigContent {
+    pub name: String,
+    pub content: String,
+    pub is_applied: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sort_index: Option<i32>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+// ============================================================================
+// Gemini CLI All API Hub Import Types
+// ============================================================================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GeminiCliAllApiHubProvider {
+    pub provider_id: String,
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub npm: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base_url: Option<String>,
+    pub requires_browser_open: bool,
+    pub is_disabled: bool,
+    pub has_api_key: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub api_key_preview: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub balance_usd: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub balance_cny: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub site_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub site_type: Option<String>,
+    pub account_label: String,
+    pub source_browser: String,
+    pub source_profile_name: String,
+    pub source_extension_id: String,
+    pub provider_config: serde_json::Value,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GeminiCliAllApiHubProvidersResult {
+    pub found: bool,
+    pub profiles: Vec<crate::coding::all_api_hub::AllApiHubProfileInfo>,
+    pub providers: Vec<GeminiCliAllApiHubProvider>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResolveGeminiCliAllApiHubProvidersRequest {
+    pub provider_ids: Vec<String>,
+}
@@ -0,0 +1,704 @@
+//! Provider 健康监控
+//!
+//! 后台定期探测 Claude Code / Codex / OpenCode 当前应用的 provider（复用 OpenCode 的
+//! 连通性测试，发送一条极短的请求），把延迟与错误写入 `provider_health_check` 表。
+//!
+//! 当前 provider 连续失败达到阈值时向前端发送 `provider-health-alert` 事件；开启自动
+//! 切换后，Claude Code / Codex 按 `sort_index` 切换到下一个未禁用的 provider，OpenCode
+//! 按配置文件顺序切换到下一个有模型的 provider（主模型取其第一个模型）。
+//!
+//! 开关、检查间隔、失败阈值和自动切换保存在应用设置中，调度循环每分钟检查一次是否到期。
+
+use std::time::{Duration, Instant};
+
+use chrono::Utc;
+use serde::Serialize;
+use serde_json::{json, Value};
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::coding::claude_code;
+use crate::coding::codex;
+use crate::coding::open_code;
+use crate::coding::open_code::models_api::{
+    run_connectivity_test_for_model, ConnectivityTestRequest,
+};
+use crate::coding::open_code::types::ReadConfigResult;
+use crate::db::DbState;
+use crate::http_client;
+use crate::secret_vault;
+use crate::tray;
+
+pub const MODULE_CLAUDE_CODE: &str = "claudecode";
+pub const MODULE_CODEX: &str = "codex";
+pub const MODULE_OPENCODE: &str = "opencode";
+
+const STATUS_SUCCESS: &str = "success";
+const STATUS_ERROR: &str = "error";
+
+/// 单次探测超时
+const PROBE_TIMEOUT_SECS: u64 = 30;
+const PROBE_PROMPT: &str = "hi";
+const PROBE_MAX_TOKENS: i64 = 16;
+/// 历史记录保留天数
+const HISTORY_RETENTION_DAYS: i64 = 7;
+/// 计算连续失败次数时最多回看的记录数
+const MAX_FAILURE_LOOKBACK: usize = 50;
+
+const DEFAULT_ANTHROPIC_BASE_URL: &str = "https://api.anthropic.com";
+const DEFAULT_CLAUDE_MODEL: &str = "claude-sonnet-4-5";
+const DEFAULT_OPENAI_BASE_URL: &str = "https://api.openai.com/v1";
+const DEFAULT_CODEX_MODEL: &str = "gpt-5";
+
+/// 手动检查与定时检查互斥，避免同一次故障触发两次切换
+static CHECK_LOCK: tokio::sync::Mutex<()> = tokio::sync::Mutex::const_new(());
+
+/// 一条健康检查记录
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HealthCheckRecord {
+    pub id: String,
+    /// "claudecode" | "codex" | "opencode"
+    pub module: String,
+    pub provider_id: String,
+    pub provider_name: String,
+    /// "success" | "error"
+    pub status: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub latency_ms: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error_message: Option<String>,
+    pub checked_at: String,
+}
+
+/// 连续失败告警，`failover_*` 为空表示没有切换
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HealthAlert {
+    pub module: String,
+    pub provider_id: String,
+    pub provider_name: String,
+    pub consecutive_failures: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error_message: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub failover_provider_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub failover_provider_name: Option<String>,
+}
+
+/// 待探测的 provider
+struct ProbeTarget {
+    provider_id: String,
+    provider_name: String,
+    model_id: String,
+    request: ConnectivityTestRequest,
+}
+
+/// 启动后台调度循环
+pub fn start_health_monitor(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        // 启动后稍等，避免与其他初始化任务争抢
+        tokio::time::sleep(Duration::from_secs(60)).await;
+
+        let mut last_run: Option<Instant> = None;
+        loop {
+            let settings = crate::settings::get_settings(app.state())
+                .await
+                .unwrap_or_default();
+
+            if settings.health_monitor_enabled {
+                let interval = Duration::from_secs(
+                    settings.health_monitor_interval_minutes.max(1) as u64 * 60,
+                );
+                if last_run.is_none_or(|t| t.elapsed() >= interval) {
+                    last_run = Some(Instant::now());
+                    if let Err(e) = run_checks(&app).await {
+                        log::warn!("Provider health check failed: {}", e);
+                    }
+                }
+            } else {
+                last_run = None;
+            }
+
+            tokio::time::sleep(Duration::from_secs(60)).await;
+        }
+    });
+}
+
+/// 立即检查一次所有模块（不受开关影响）
+#[tauri::command]
+pub async fn run_provider_health_check(app: AppHandle) -> Result<Vec<HealthCheckRecord>, String> {
+    run_checks(&app).await
+}
+
+/// 查询最近的健康检查记录（新→旧）
+#[tauri::command]
+pub async fn list_provider_health_history(
+    state: tauri::State<'_, DbState>,
+    module: Option<String>,
+    limit: Option<u32>,
+) -> Result<Vec<HealthCheckRecord>, String> {
+    let db = state.db();
+    let limit = limit.unwrap_or(100).clamp(1, 1000);
+
+    let mut result = match module {
+        Some(module) => db
+            .query(format!(
+                "SELECT *, type::string(id) as id FROM provider_health_check WHERE module = $module ORDER BY checked_at DESC LIMIT {}",
+                limit
+            ))
+            .bind(("module", module))
+            .await,
+        None => db
+            .query(format!(
+                "SELECT *, type::string(id) as id FROM provider_health_check ORDER BY checked_at DESC LIMIT {}",
+                limit
+            ))
+            .await,
+    }
+    .map_err(|e| format!("Failed to query health history: {}", e))?;
+
+    let records: Vec<Value> = result
+        .take(0)
+        .map_err(|e| format!("Failed to parse health history: {}", e))?;
+
+    Ok(records.into_iter().map(from_db_value).collect())
+}
+
+async fn run_checks(app: &AppHandle) -> Result<Vec<HealthCheckRecord>, String> {
+    let _guard = CHECK_LOCK.lock().await;
+
+    let state = app.state::<DbState>();
+    let settings = crate::settings::get_settings(app.state()).await?;
+    let client = http_client::client_with_timeout(&state, PROBE_TIMEOUT_SECS).await?;
+    let threshold = settings.health_monitor_failure_threshold.max(1) as usize;
+
+    let mut records = Vec::new();
+    for module in [MODULE_CLAUDE_CODE, MODULE_CODEX, MODULE_OPENCODE] {
+        let target = match build_target(app, module).await {
+            Ok(Some(target)) => target,
+            Ok(None) => continue,
+            Err(e) => {
+                log::warn!("Skip health check for {}: {}", module, e);
+                continue;
+            }
+        };
+
+        let result =
+            run_connectivity_test_for_model(&client, &target.request, &target.model_id).await;
+        let success = result.status == STATUS_SUCCESS;
+        let record = HealthCheckRecord {
+            id: String::new(),
+            module: module.to_string(),
+            provider_id: target.provider_id.clone(),
+            provider_name: target.provider_name.clone(),
+            status: if success {
+                STATUS_SUCCESS
+            } else {
+                STATUS_ERROR
+            }
+            .to_string(),
+            latency_ms: result.first_byte_ms.or(result.total_ms),
+            error_message: result.error_message,
+            checked_at: Utc::now().to_rfc3339(),
+        };
+        let record = save_record(&state, record).await?;
+
+        if !success {
+            let failures = count_consecutive_failures(&state, module, &target.provider_id).await?;
+            if failures >= threshold {
+                handle_failures(
+                    app,
+                    &record,
+                    failures,
+                    threshold,
+                    settings.health_monitor_auto_failover,
+                )
+                .await;
+            }
+        }
+
+        records.push(record);
+    }
+
+    prune_history(&state).await;
+    Ok(records)
+}
+
+/// 连续失败达到阈值：尝试切换，失败或未开启切换时只在刚达到阈值时告警一次
+async fn handle_failures(
+    app: &AppHandle,
+    record: &HealthCheckRecord,
+    failures: usize,
+    threshold: usize,
+    auto_failover: bool,
+) {
+    let mut failover = None;
+    if auto_failover {
+        match failover_module(app, &record.module, &record.provider_id).await {
+            Ok(next) => failover = next,
+            Err(e) => log::warn!("Provider failover for {} failed: {}", record.module, e),
+        }
+    }
+
+    if failover.is_none() && failures != threshold {
+        return;
+    }
+
+    if failover.is_some() {
+        log::info!(
+            "Provider {} for {} failed {} checks, switched to {:?}",
+            record.provider_name,
+            record.module,
+            failures,
+            failover.as_ref().map(|(_, name)| name)
+        );
+        if let Err(e) = tray::refresh_tray_menus(app).await {
+            log::warn!("Failed to refresh tray after failover: {}", e);
+        }
+    }
+
+    let (failover_provider_id, failover_provider_name) = failover.unzip();
+    let alert = HealthAlert {
+        module: record.module.clone(),
+        provider_id: record.provider_id.clone(),
+        provider_name: record.provider_name.clone(),
+        consecutive_failures: failures,
+        error_message: record.error_message.clone(),
+        failover_provider_id,
+        failover_provider_name,
+    };
+    let _ = app.emit("provider-health-alert", &alert);
+}
+
+// ============================================================================
+// Probe targets
+// ============================================================================
+
+async fn build_target(app: &AppHandle, module: &str) -> Result<Option<ProbeTarget>, String> {
+    match module {
+        MODULE_CLAUDE_CODE => build_claude_target(app).await,
+        MODULE_CODEX => build_codex_target(app).await,
+        MODULE_OPENCODE => build_opencode_target(app).await,
+        _ => Ok(None),
+    }
+}
+
+fn base_request(npm: &str, base_url: String, api_key: Option<String>) -> ConnectivityTestRequest {
+    ConnectivityTestRequest {
+        npm: npm.to_string(),
+        base_url,
+        api_key,
+        headers: None,
+        prompt: PROBE_PROMPT.to_string(),
+        temperature: None,
+        max_tokens: Some(PROBE_MAX_TOKENS),
+        max_output_tokens: Some(PROBE_MAX_TOKENS),
+        stream: None,
+        body: None,
+        model_ids: vec![],
+        timeout_secs: Some(PROBE_TIMEOUT_SECS),
+    }
+}
+
+fn non_empty_str(value: Option<&Value>) -> Option<String> {
+    value
+        .and_then(|v| v.as_str())
+        .map(|s| s.trim())
+        .filter(|s| !s.is_empty())
+        .map(String::from)
+}
+
+async fn build_claude_target(app: &AppHandle) -> Result<Option<ProbeTarget>, String> {
+    let db = app.state::<DbState>().db();
+    let providers = claude_code::commands::list_providers_internal(&db).await?;
+    let Some(provider) = providers.into_iter().find(|p| p.is_applied) else {
+        return Ok(None);
+    };
+
+    let config: Value =
+        serde_json::from_str(&secret_vault::resolve_json_text(&provider.settings_config)?)
+            .map_err(|e| format!("Failed to parse provider config: {}", e))?;
+    let env = config.get("env");
+
+    // Claude Code 的 ANTHROPIC_BASE_URL 不含 /v1，连通性测试直接拼接 /messages
+    let base_url = non_empty_str(env.and_then(|e| e.get("ANTHROPIC_BASE_URL")))
+        .unwrap_or_else(|| DEFAULT_ANTHROPIC_BASE_URL.to_string());
+    let base_url = {
+        let trimmed = base_url.trim_end_matches('/');
+        if trimmed.ends_with("/v1") {
+            trimmed.to_string()
+        } else {
+            format!("{}/v1", trimmed)
+        }
+    };
+
+    let auth_token = non_empty_str(env.and_then(|e| e.get("ANTHROPIC_AUTH_TOKEN")));
+    let api_key = non_empty_str(env.and_then(|e| e.get("ANTHROPIC_API_KEY")));
+    let model_id = non_empty_str(config.get("model"))
+        .or_else(|| non_empty_str(env.and_then(|e| e.get("ANTHROPIC_MODEL"))))
+        .or_else(|| non_empty_str(config.get("sonnetModel")))
+        .unwrap_or_else(|| DEFAULT_CLAUDE_MODEL.to_string());
+
+    // OpenAI 格式的 provider 直接按 Chat Completions 探测上游，base URL 原样使用
+    if claude_code::uses_openai_chat_format(&config) {
+        let base_url = non_empty_str(env.and_then(|e| e.get("ANTHROPIC_BASE_URL")))
+            .unwrap_or_else(|| DEFAULT_OPENAI_BASE_URL.to_string());
+        return Ok(Some(ProbeTarget {
+            provider_id: provider.id,
+            provider_name: provider.name,
+            model_id,
+            request: base_request(
+                "@ai-sdk/openai-compatible",
+                base_url,
+                auth_token.or(api_key),
+            ),
+        }));
+    }
+
+    let mut request = base_request(
+        "@ai-sdk/anthropic",
+        base_url,
+        auth_token.or_else(|| api_key.clone()),
+    );
+    if let Some(key) = api_key {
+        request.headers = Some(json!({ "x-api-key": key }));
+    }
+
+    Ok(Some(ProbeTarget {
+        provider_id: provider.id,
+        provider_name: provider.name,
+        model_id,
+        request,
+    }))
+}
+
+async fn build_codex_target(app: &AppHandle) -> Result<Option<ProbeTarget>, String> {
+    let db = app.state::<DbState>().db();
+    let providers = codex::commands::list_providers_internal(&db).await?;
+    let Some(provider) = providers.into_iter().find(|p| p.is_applied) else {
+        return Ok(None);
+    };
+
+    let config: Value =
+        serde_json::from_str(&secret_vault::resolve_json_text(&provider.settings_config)?)
+            .map_err(|e| format!("Failed to parse provider config: {}", e))?;
+    let config_toml = config.get("config").and_then(|v| v.as_str()).unwrap_or("");
+    let table: toml::Table = toml::from_str(config_toml)
+        .map_err(|e| format!("Failed to parse provider config.toml: {}", e))?;
+
+    let model_id = table
+        .get("model")
+        .and_then(|v| v.as_str())
+        .unwrap_or(DEFAULT_CODEX_MODEL)
+        .to_string();
+    let model_provider = table
+        .get("model_provider")
+        .and_then(|v| v.as_str())
+        .and_then(|key| table.get("model_providers")?.get(key));
+
+    let base_url = model_provider
+        .and_then(|p| p.get("base_url"))
+        .and_then(|v| v.as_str())
+        .unwrap_or(DEFAULT_OPENAI_BASE_URL)
+        .to_string();
+    // wire_api 缺省为 chat（Codex 的默认值），responses 走 OpenAI Responses 接口
+    let npm = match model_provider
+        .and_then(|p| p.get("wire_api"))
+        .and_then(|v| v.as_str())
+    {
+        Some("responses") => "@ai-sdk/openai",
+        Some(_) => "@ai-sdk/openai-compatible",
+        None if model_provider.is_none() => "@ai-sdk/openai",
+        None => "@ai-sdk/openai-compatible",
+    };
+    let api_key =
+        non_empty_str(config.get("auth").and_then(|a| a.get("OPENAI_API_KEY"))).or_else(|| {
+            model_provider
+                .and_then(|p| p.get("experimental_bearer_token"))
+                .and_then(|v| v.as_str())
+                .map(String::from)
+        });
+
+    Ok(Some(ProbeTarget {
+        provider_id: provider.id,
+        provider_name: provider.name,
+        model_id,
+        request: base_request(npm, base_url, api_key),
+    }))
+}
+
+async fn build_opencode_target(app: &AppHandle) -> Result<Option<ProbeTarget>, String> {
+    let ReadConfigResult::Success { config } =
+        open_code::commands::read_opencode_config(app.state()).await?
+    else {
+        return Ok(None);
+    };
+    let Some((provider_id, model_id)) = config.model.as_deref().and_then(|m| m.split_once('/'))
+    else {
+        return Ok(None);
+    };
+    // auth.json 中的官方 provider 不在配置文件里，没有可探测的地址
+    let Some(provider) = config.provider.as_ref().and_then(|p| p.get(provider_id)) else {
+        return Ok(None);
+    };
+    let options = provider.options.as_ref();
+    let Some(base_url) = options.and_then(|o| o.base_url.clone()) else {
+        return Ok(None);
+    };
+
+    let npm = provider
+        .npm
+        .as_deref()
+        .unwrap_or("@ai-sdk/openai-compatible");
+    let mut request = base_request(npm, base_url, options.and_then(|o| o.api_key.clone()));
+    request.headers = options.and_then(|o| o.headers.clone());
+
+    Ok(Some(ProbeTarget {
+        provider_id: provider_id.to_string(),
+        provider_name: provider
+            .name
+            .clone()
+            .unwrap_or_else(|| provider_id.to_string()),
+        model_id: model_id.to_string(),
+        request,
+    }))
+}
+
+// ============================================================================
+// Failover
+// ============================================================================
+
+/// 切换到下一个可用 provider，返回 (id, name)；没有可切换的 provider 时返回 None
+async fn failover_module(
+    app: &AppHandle,
+    module: &str,
+    current_id: &str,
+) -> Result<Option<(String, String)>, String> {
+    let db = app.state::<DbState>().db();
+    match module {
+        MODULE_CLAUDE_CODE => {
+            let providers = claude_code::commands::list_providers_internal(&db).await?;
+            let candidates: Vec<(&str, bool)> = providers
+                .iter()
+                .map(|p| (p.id.as_str(), p.is_disabled))
+                .collect();
+            let Some(next) = next_provider(&candidates, current_id) else {
+                return Ok(None);
+            };
+            let name = providers
+                .iter()
+                .find(|p| p.id == next)
+                .map(|p| p.name.clone())
+                .unwrap_or_default();
+            claude_code::commands::apply_config_internal(&db, app, &next, false, false).await?;
+            Ok(Some((next, name)))
+        }
+        MODULE_CODEX => {
+            let providers = codex::commands::list_providers_internal(&db).await?;
+            let candidates: Vec<(&str, bool)> = providers
+                .iter()
+                .map(|p| (p.id.as_str(), p.is_disabled))
+                .collect();
+            let Some(next) = next_provider(&candidates, current_id) else {
+                return Ok(None);
+            };
+            let name = providers
+                .iter()
+                .find(|p| p.id == next)
+                .map(|p| p.name.clone())
+                .unwrap_or_default();
+            codex::commands::apply_config_internal(&db, app, &next, false, false).await?;
+            Ok(Some((next, name)))
+        }
+        MODULE_OPENCODE => failover_opencode(app, current_id).await,
+        _ => Ok(None),
+    }
+}
+
+async fn failover_opencode(
+    app: &AppHandle,
+    current_id: &str,
+) -> Result<Option<(String, String)>, String> {
+    let ReadConfigResult::Success { mut config } =
+        open_code::commands::read_opencode_config(app.state()).await?
+    else {
+        return Ok(None);
+    };
+    let Some(providers) = config.provider.as_ref() else {
+        return Ok(None);
+    };
+
+    let disabled: Vec<&str> = config
+        .other
+        .get("disabled_providers")
+        .and_then(|v| v.as_array())
+        .map(|arr| arr.iter().filter_map(|v| v.as_str()).collect())
+        .unwrap_or_default();
+    let candidates: Vec<(&str, bool)> = providers
+        .iter()
+        .map(|(id, p)| {
+            let unusable = p.models.is_empty() || disabled.contains(&id.as_str());
+            (id.as_str(), unusable)
+        })
+        .collect();
+    let Some(next) = next_provider(&candidates, current_id) else {
+        return Ok(None);
+    };
+    let Some((model_id, provider)) = providers
+        .get(&next)
+        .and_then(|p| p.models.keys().next().map(|m| (m.clone(), p)))
+    else {
+        return Ok(None);
+    };
+    let name = provider.name.clone().unwrap_or_else(|| next.clone());
+
+    config.model = Some(format!("{}/{}", next, model_id));
+    open_code::commands::apply_config_internal(app.state(), app, config, false, false).await?;
+    Ok(Some((next, name)))
+}
+
+/// 在 `current` 之后按顺序查找下一个未禁用的 provider，到末尾后从头开始
+fn next_provider(candidates: &[(&str, bool)], current: &str) -> Option<String> {
+    let start = candidates
+        .iter()
+        .position(|(id, _)| *id == current)
+        .map(|i| i + 1)
+        .unwrap_or(0);
+    (0..candidates.len())
+        .map(|offset| candidates[(start + offset) % candidates.len()])
+        .find(|(id, disabled)| !disabled && *id != current)
+        .map(|(id, _)| id.to_string())
+}
+
+/// 新→旧排列的状态中，从最新一条开始连续失败的次数
+fn consecutive_failures(statuses: &[String]) -> usize {
+    statuses
+        .iter()
+        .take_while(|s| s.as_str() == STATUS_ERROR)
+        .count()
+}
+
+// ============================================================================
+// Storage
+// ============================================================================
+
+fn from_db_value(value: Value) -> HealthCheckRecord {
+    let get_str = |key: &str| {
+        value
+            .get(key)
+            .and_then(|v| v.as_str())
+            .unwrap_or_default()
+            .to_string()
+    };
+    HealthCheckRecord {
+        id: crate::coding::db_clean_id(&get_str("id")),
+        module: get_str("module"),
+        provider_id: get_str("provider_id"),
+        provider_name: get_str("provider_name"),
+        status: get_str("status"),
+        latency_ms: value.get("latency_ms").and_then(|v| v.as_u64()),
+        error_message: value
+            .get("error_message")
+            .and_then(|v| v.as_str())
+            .map(String::from),
+        checked_at: get_str("checked_at"),
+    }
+}
+
+async fn save_record(
+    state: &DbState,
+    mut record: HealthCheckRecord,
+) -> Result<HealthCheckRecord, String> {
+    let id = crate::coding::db_new_id();
+    let data = json!({
+        "module": record.module,
+        "provider_id": record.provider_id,
+        "provider_name": record.provider_name,
+        "status": record.status,
+        "latency_ms": record.latency_ms,
+        "error_message": record.error_message,
+        "checked_at": record.checked_at,
+    });
+
+    state
+        .db()
+        .query(format!(
+            "CREATE provider_health_check:`{}` CONTENT $data",
+            id
+        ))
+        .bind(("data", data))
+        .await
+        .map_err(|e| format!("Failed to save health check: {}", e))?;
+
+    record.id = id;
+    Ok(record)
+}
+
+async fn count_consecutive_failures(
+    state: &DbState,
+    module: &str,
+    provider_id: &str,
+) -> Result<usize, String> {
+    let mut result = state
+        .db()
+        .query(format!(
+            "SELECT status, checked_at FROM provider_health_check WHERE module = $module AND provider_id = $provider_id ORDER BY checked_at DESC LIMIT {}",
+            MAX_FAILURE_LOOKBACK
+        ))
+        .bind(("module", module.to_string()))
+        .bind(("provider_id", provider_id.to_string()))
+        .await
+        .map_err(|e| format!("Failed to query health history: {}", e))?;
+
+    let records: Vec<Value> = result
+        .take(0)
+        .map_err(|e| format!("Failed to parse health history: {}", e))?;
+    let statuses: Vec<String> = records
+        .iter()
+        .map(|r| {
+            r.get("status")
+                .and_then(|v| v.as_str())
+                .unwrap_or_default()
+                .to_string()
+        })
+        .collect();
+
+    Ok(consecutive_failures(&statuses))
+}
+
+async fn prune_history(state: &DbState) {
+    let cutoff = (Utc::now() - chrono::Duration::days(HISTORY_RETENTION_DAYS)).to_rfc3339();
+    if let Err(e) = state
+        .db()
+        .query("DELETE provider_health_check WHERE checked_at < $cutoff")
+        .bind(("cutoff", cutoff))
+        .await
+    {
+        log::warn!("Failed to prune provider health history: {}", e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn next_provider_skips_disabled_and_wraps() {
+        let candidates = [("a", false), ("b", true), ("c", false)];
+        assert_eq!(next_provider(&candidates, "a").as_deref(), Some("c"));
+        assert_eq!(next_provider(&candidates, "c").as_deref(), Some("a"));
+        assert_eq!(next_provider(&candidates, "missing").as_deref(), Some("a"));
+        assert_eq!(next_provider(&[("a", false), ("b", true)], "a"), None);
+    }
+
+    #[test]
+    fn consecutive_failures_stops_at_first_success() {
+        let statuses: Vec<String> = ["error", "error", "success", "error"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        assert_eq!(consecutive_failures(&statuses), 2);
+        assert_eq!(consecutive_failures(&[]), 0);
+    }
+}
@@ -393,10 +393,21 @@ pub async fn mcp_sync_all<R: Runtime>(
     app: AppHandle<R>,
     state: State<'_, DbState>,
 ) -> Result<Vec<McpSyncResultDto>, String> {
-    let custom_tools = custom_store::get_custom_tools(&state)
+    let results = sync_all_servers(&state).await?;
+
+    // Emit config-changed and mcp-changed events
+    let _ = app.emit("config-changed", "window");
+    let _ = app.emit("mcp-changed", "window");
+
+    Ok(results)
+}
+
+/// 将所有服务器同步到已启用的工具，不发送事件（供命令行工具使用）
+pub async fn sync_all_servers(state: &DbState) -> Result<Vec<McpSyncResultDto>, String> {
+    let custom_tools = custom_store::get_custom_tools(state)
         .await
         .unwrap_or_default();
-    let servers = mcp_store::get_mcp_servers(&state).await?;
+    let servers = mcp_store::get_mcp_servers(state).await?;
     let mut results = Vec::new();
 
     for server in servers {
@@ -411,7 +422,7 @@ pub async fn mcp_sync_all<R: Runtime>(
 
             match sync_server_to_tool(&server, &tool) {
                 Ok(detail) => {
-                    mcp_store::update_sync_detail(&state, &server.id, &detail).await?;
+                    mcp_store::update_sync_detail(state, &server.id, &detail).await?;
                     results.push(McpSyncResultDto {
                         tool: tool_key.clone(),
                         success: true,
@@ -425,7 +436,7 @@ pub async fn mcp_sync_all<R: Runtime>(
                         synced_at: Some(now_ms()),
                         error_message: Some(e.clone()),
                     };
-                    mcp_store::update_sync_detail(&state, &server.id, &detail).await?;
+                    mcp_store::update_sync_detail(state, &server.id, &detail).await?;
                     results.push(McpSyncResultDto {
                         tool: tool_key.clone(),
                         success: false,
@@ -437,18 +448,14 @@ pub async fn mcp_sync_all<R: Runtime>(
     }
 
     // Also sync disabled servers to opencode if switch is ON
-    let prefs = mcp_store::get_mcp_preferences(&state)
+    let prefs = mcp_store::get_mcp_preferences(state)
         .await
         .unwrap_or_default();
     if prefs.sync_disabled_to_opencode {
-        let all_servers = mcp_store::get_mcp_servers(&state).await.unwrap_or_default();
+        let all_servers = mcp_store::get_mcp_servers(state).await.unwrap_or_default();
         sync_opencode_disabled(&all_servers, &custom_tools);
     }
 
-    // Emit config-changed and mcp-changed events
-    let _ = app.emit("config-changed", "window");
-    let _ = app.emit("mcp-changed", "window");
-
     Ok(results)
 }
 
@@ -11,6 +11,7 @@ use serde_json::Value;
 use super::command_normalize;
 use super::format_configs::get_format_config;
 use super::types::{now_ms, McpServer, McpSyncDetail};
+use crate::coding::config_history;
 use crate::coding::tools::{resolve_mcp_config_path, McpFormatConfig, RuntimeTool};
 
 /// Sync an MCP server to a specific tool's config file
@@ -117,7 +118,7 @@ fn sync_server_to_json(
     // which is valid JSON5 (JSON is a subset of JSON5)
     let content = serde_json::to_string_pretty(&config)
         .map_err(|e| format!("Failed to serialize config: {}", e))?;
-    std::fs::write(config_path, content)
+    config_history::write(config_path, content, "mcp", "mcp_sync")
         .map_err(|e| format!("Failed to write config file: {}", e))?;
 
     Ok(())
@@ -152,7 +153,7 @@ fn remove_server_from_json(
     // Write back to file
     let content = serde_json::to_string_pretty(&config)
         .map_err(|e| format!("Failed to serialize config: {}", e))?;
-    std::fs::write(config_path, content)
+    config_history::write(config_path, content, "mcp", "mcp_remove")
         .map_err(|e| format!("Failed to write config file: {}", e))?;
 
     Ok(())
@@ -200,7 +201,7 @@ fn sync_server_to_toml(
 
     // Write back to file
     let content = doc.to_string();
-    std::fs::write(config_path, content)
+    config_history::write(config_path, content, "mcp", "mcp_sync")
         .map_err(|e| format!("Failed to write config file: {}", e))?;
 
     Ok(())
@@ -231,7 +232,7 @@ fn remove_server_from_toml(
 
     // Write back to file
     let content = doc.to_string();
-    std::fs::write(config_path, content)
+    config_history::write(config_path, content, "mcp", "mcp_remove")
         .map_err(|e| format!("Failed to write config file: {}", e))?;
 
     Ok(())
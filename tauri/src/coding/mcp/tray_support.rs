@@ -2,6 +2,7 @@
 //!
 //! Provides data structures and functions for system tray menu integration.
 
+use serde::Serialize;
 use tauri::{AppHandle, Emitter, Manager, Runtime};
 
 use super::mcp_store;
@@ -9,14 +10,16 @@ use crate::coding::tools::{custom_store, get_mcp_runtime_tools, is_tool_installe
 use crate::DbState;
 
 /// Tray data for MCP servers section
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct TrayMcpData {
     pub title: String,
     pub items: Vec<TrayMcpServerItem>,
 }
 
 /// Single MCP server item in tray menu
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct TrayMcpServerItem {
     pub id: String,
     pub display_name: String,
@@ -24,7 +27,8 @@ pub struct TrayMcpServerItem {
 }
 
 /// Tool toggle item within MCP server submenu
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct TrayMcpToolItem {
     pub tool_key: String,
     pub display_name: String,
@@ -94,13 +98,28 @@ pub async fn apply_mcp_tool_toggle<R: Runtime>(
 
     // Toggle the tool
     let is_enabled = mcp_store::toggle_tool_enabled(&state, server_id, tool_key).await?;
+    sync_tool_state(&state, server_id, tool_key, is_enabled).await?;
 
+    // Emit config-changed event (from tray)
+    let _ = app.emit("config-changed", "tray");
+    let _ = app.emit("mcp-changed", "tray");
+
+    Ok(())
+}
+
+/// Write or remove an MCP server in a tool's config after its enabled state changed
+pub async fn sync_tool_state(
+    state: &DbState,
+    server_id: &str,
+    tool_key: &str,
+    is_enabled: bool,
+) -> Result<(), String> {
     // Get the server and tool
-    let server = mcp_store::get_mcp_server_by_id(&state, server_id)
+    let server = mcp_store::get_mcp_server_by_id(state, server_id)
         .await?
         .ok_or_else(|| format!("MCP server not found: {}", server_id))?;
 
-    let custom_tools = custom_store::get_custom_tools(&state)
+    let custom_tools = custom_store::get_custom_tools(state)
         .await
         .unwrap_or_default();
     let tool = crate::coding::tools::runtime_tool_by_key(tool_key, &custom_tools)
@@ -110,7 +129,7 @@ pub async fn apply_mcp_tool_toggle<R: Runtime>(
     if is_enabled {
         match super::config_sync::sync_server_to_tool(&server, &tool) {
             Ok(detail) => {
-                mcp_store::update_sync_detail(&state, server_id, &detail).await?;
+                mcp_store::update_sync_detail(state, server_id, &detail).await?;
             }
             Err(e) => {
                 let detail = super::types::McpSyncDetail {
@@ -119,18 +138,14 @@ pub async fn apply_mcp_tool_toggle<R: Runtime>(
                     synced_at: Some(super::types::now_ms()),
                     error_message: Some(e.clone()),
                 };
-                mcp_store::update_sync_detail(&state, server_id, &detail).await?;
+                mcp_store::update_sync_detail(state, server_id, &detail).await?;
                 return Err(e);
             }
         }
     } else {
         let _ = super::config_sync::remove_server_from_tool(&server.name, &tool);
-        mcp_store::delete_sync_detail(&state, server_id, tool_key).await?;
+        mcp_store::delete_sync_detail(state, server_id, tool_key).await?;
     }
 
-    // Emit config-changed event (from tray)
-    let _ = app.emit("config-changed", "tray");
-    let _ = app.emit("mcp-changed", "tray");
-
     Ok(())
 }
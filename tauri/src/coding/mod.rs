@@ -1,15 +1,25 @@
 pub mod all_api_hub;
+pub mod balance_monitor;
 pub mod claude_code;
 pub mod codex;
+pub mod config_drift;
+pub mod config_history;
+pub mod config_validation;
+pub mod gemini_cli;
+pub mod health_monitor;
 pub mod mcp;
 pub mod oh_my_opencode;
 pub mod oh_my_opencode_slim;
 pub mod open_claw;
 pub mod open_code;
 pub mod preset_models;
+pub mod session_history;
 pub mod skills;
 pub mod ssh;
+pub mod sync_watcher;
 pub mod tools;
+pub mod usage;
+pub mod workspace_profile;
 pub mod wsl;
 
 mod db_id;
@@ -20,3 +30,5 @@ pub use db_id::{
 
 mod path_expand;
 pub use path_expand::expand_local_path;
+
+mod sync_preview;
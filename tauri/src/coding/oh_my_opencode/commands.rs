@@ -4,6 +4,8 @@ use std::fs;
 
 use super::adapter;
 use super::types::*;
+use crate::coding::config_history;
+use crate::coding::config_validation::{self, ConfigKind};
 use crate::coding::db_id::db_record_id;
 use crate::db::DbState;
 use tauri::Emitter;
@@ -516,6 +518,34 @@ pub async fn apply_config_to_file_public(
     db: &surrealdb::Surreal<surrealdb::engine::local::Db>,
     config_id: &str,
 ) -> Result<(), String> {
+    let final_json = build_final_config(db, config_id).await?;
+
+    // Get config path using unified function
+    let config_path = get_oh_my_opencode_config_path()?;
+
+    // Ensure parent directory exists
+    if let Some(parent) = config_path.parent() {
+        if !parent.exists() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create opencode config directory: {}", e))?;
+        }
+    }
+
+    // Write to file with pretty formatting
+    let json_content = serde_json::to_string_pretty(&final_json)
+        .map_err(|e| format!("Failed to serialize final config: {}", e))?;
+
+    config_history::write(&config_path, json_content, "opencode", "oh_my_opencode")
+        .map_err(|e| format!("Failed to write config file: {}", e))?;
+
+    Ok(())
+}
+
+/// 按配置方案与全局配置生成最终写入的 JSON
+async fn build_final_config(
+    db: &surrealdb::Surreal<surrealdb::engine::local::Db>,
+    config_id: &str,
+) -> Result<Value, String> {
     // Get the config from database using direct ID format (like ClaudeCode)
     let records_result: Result<Vec<Value>, _> = db
         .query(format!(
@@ -544,17 +574,6 @@ pub async fn apply_config_to_file_public(
         ));
     }
 
-    // Get config path using unified function
-    let config_path = get_oh_my_opencode_config_path()?;
-
-    // Ensure parent directory exists
-    if let Some(parent) = config_path.parent() {
-        if !parent.exists() {
-            fs::create_dir_all(parent)
-                .map_err(|e| format!("Failed to create opencode config directory: {}", e))?;
-        }
-    }
-
     // 获取 Global Config
     let global_records_result: Result<Vec<Value>, _> = db
         .query(
@@ -704,14 +723,7 @@ pub async fn apply_config_to_file_public(
     // 清理空值：删除空对象和空数组
     adapter::clean_empty_values(&mut final_json);
 
-    // Write to file with pretty formatting
-    let json_content = serde_json::to_string_pretty(&final_json)
-        .map_err(|e| format!("Failed to serialize final config: {}", e))?;
-
-    fs::write(&config_path, json_content)
-        .map_err(|e| format!("Failed to write config file: {}", e))?;
-
-    Ok(())
+    Ok(final_json)
 }
 /// Apply an oh-my-opencode config to the JSON file
 #[tauri::command]
@@ -719,9 +731,10 @@ pub async fn apply_oh_my_opencode_config(
     state: tauri::State<'_, DbState>,
     app: tauri::AppHandle,
     config_id: String,
+    force: Option<bool>,
 ) -> Result<(), String> {
     let db = state.db();
-    apply_config_internal(&db, &app, &config_id, false).await?;
+    apply_config_internal(&db, &app, &config_id, false, force.unwrap_or(false)).await?;
     Ok(())
 }
 
@@ -732,9 +745,19 @@ pub async fn apply_config_internal<R: tauri::Runtime>(
     app: &tauri::AppHandle<R>,
     config_id: &str,
     from_tray: bool,
+    force: bool,
 ) -> Result<(), String> {
+    let report = config_validation::check(
+        config_validation::validate_json(
+            ConfigKind::OhMyOpenCode,
+            &build_final_config(db, config_id).await?,
+        ),
+        force,
+    )?;
+
     // 应用配置到文件
     apply_config_to_file(db, config_id).await?;
+    config_validation::emit_warnings(app, &report);
 
     // Update database - set all configs to not applied, then set this one to applied
     let now = Local::now().to_rfc3339();
@@ -824,7 +847,7 @@ pub async fn toggle_oh_my_opencode_config_disabled(
                 adapter::get_bool_compat(config_value, "is_applied", "isApplied", false);
             if is_applied {
                 // Re-apply config to update files (will check is_disabled internally)
-                apply_config_internal(&db, &app, &config_id, false).await?;
+                apply_config_internal(&db, &app, &config_id, false, false).await?;
             }
         }
     }
@@ -932,6 +955,22 @@ pub async fn get_oh_my_opencode_global_config(
     }
 }
 
+/// 按写入配置文件时的方式展开全局配置（other_fields 平铺到顶层），用于校验
+fn global_config_fragment(content: &OhMyOpenCodeGlobalConfigContent) -> Value {
+    let mut fragment = match serde_json::to_value(content) {
+        Ok(Value::Object(map)) => map,
+        _ => serde_json::Map::new(),
+    };
+    fragment.remove("updated_at");
+    if let Some(schema) = fragment.remove("schema") {
+        fragment.insert("$schema".to_string(), schema);
+    }
+    if let Some(Value::Object(others)) = fragment.remove("other_fields") {
+        fragment.extend(others);
+    }
+    Value::Object(fragment)
+}
+
 /// Save oh-my-opencode global config
 #[tauri::command]
 #[allow(unused_variables)] // app 在 Windows 平台上用于 WSL 同步
@@ -939,6 +978,7 @@ pub async fn save_oh_my_opencode_global_config(
     state: tauri::State<'_, DbState>,
     app: tauri::AppHandle,
     input: OhMyOpenCodeGlobalConfigInput,
+    force: Option<bool>,
 ) -> Result<OhMyOpenCodeGlobalConfig, String> {
     let db = state.db();
 
@@ -959,6 +999,14 @@ pub async fn save_oh_my_opencode_global_config(
         updated_at: now.clone(),
     };
 
+    let report = config_validation::check(
+        config_validation::validate_fragment_json(
+            ConfigKind::OhMyOpenCode,
+            &global_config_fragment(&content),
+        ),
+        force.unwrap_or(false),
+    )?;
+
     let json_data = adapter::global_config_to_db_value(&content);
 
     // Use UPSERT to handle both update and create
@@ -986,6 +1034,8 @@ pub async fn save_oh_my_opencode_global_config(
         }
     }
 
+    config_validation::emit_warnings(&app, &report);
+
     // 从数据库读取刚保存的配置
     let records_result: Result<Vec<Value>, _> = db
         .query(
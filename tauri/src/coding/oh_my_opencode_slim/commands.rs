@@ -4,6 +4,8 @@ use std::fs;
 
 use super::adapter;
 use super::types::*;
+use crate::coding::config_history;
+use crate::coding::config_validation::{self, ConfigKind};
 use crate::coding::db_id::db_record_id;
 use crate::db::DbState;
 use tauri::Emitter;
@@ -410,6 +412,36 @@ pub async fn apply_config_to_file_public(
     db: &surrealdb::Surreal<surrealdb::engine::local::Db>,
     config_id: &str,
 ) -> Result<(), String> {
+    let final_json = build_final_config(db, config_id).await?;
+
+    let config_path = get_oh_my_opencode_slim_config_path()?;
+
+    if let Some(parent) = config_path.parent() {
+        if !parent.exists() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create opencode config directory: {}", e))?;
+        }
+    }
+
+    let json_content = serde_json::to_string_pretty(&final_json)
+        .map_err(|e| format!("Failed to serialize final config: {}", e))?;
+
+    config_history::write(
+        &config_path,
+        json_content,
+        "opencode",
+        "oh_my_opencode_slim",
+    )
+    .map_err(|e| format!("Failed to write config file: {}", e))?;
+
+    Ok(())
+}
+
+/// 按配置方案与全局配置生成最终写入的 JSON
+async fn build_final_config(
+    db: &surrealdb::Surreal<surrealdb::engine::local::Db>,
+    config_id: &str,
+) -> Result<Value, String> {
     let records_result: Result<Vec<Value>, _> = db
         .query(format!(
             "SELECT *, type::string(id) as id FROM oh_my_opencode_slim_config:`{}` LIMIT 1",
@@ -437,15 +469,6 @@ pub async fn apply_config_to_file_public(
         ));
     }
 
-    let config_path = get_oh_my_opencode_slim_config_path()?;
-
-    if let Some(parent) = config_path.parent() {
-        if !parent.exists() {
-            fs::create_dir_all(parent)
-                .map_err(|e| format!("Failed to create opencode config directory: {}", e))?;
-        }
-    }
-
     // 获取 Global Config
     let global_records_result: Result<Vec<Value>, _> = db
         .query("SELECT *, type::string(id) as id FROM oh_my_opencode_slim_global_config:`global` LIMIT 1")
@@ -540,13 +563,7 @@ pub async fn apply_config_to_file_public(
 
     adapter::clean_empty_values(&mut final_json);
 
-    let json_content = serde_json::to_string_pretty(&final_json)
-        .map_err(|e| format!("Failed to serialize final config: {}", e))?;
-
-    fs::write(&config_path, json_content)
-        .map_err(|e| format!("Failed to write config file: {}", e))?;
-
-    Ok(())
+    Ok(final_json)
 }
 
 /// Apply an oh-my-opencode-slim config to the JSON file
@@ -555,9 +572,10 @@ pub async fn apply_oh_my_opencode_slim_config(
     state: tauri::State<'_, DbState>,
     app: tauri::AppHandle,
     config_id: String,
+    force: Option<bool>,
 ) -> Result<(), String> {
     let db = state.db();
-    apply_config_internal(&db, &app, &config_id, false).await?;
+    apply_config_internal(&db, &app, &config_id, false, force.unwrap_or(false)).await?;
     Ok(())
 }
 
@@ -567,8 +585,18 @@ pub async fn apply_config_internal<R: tauri::Runtime>(
     app: &tauri::AppHandle<R>,
     config_id: &str,
     from_tray: bool,
+    force: bool,
 ) -> Result<(), String> {
+    let report = config_validation::check(
+        config_validation::validate_json(
+            ConfigKind::OhMyOpenCodeSlim,
+            &build_final_config(db, config_id).await?,
+        ),
+        force,
+    )?;
+
     apply_config_to_file(db, config_id).await?;
+    config_validation::emit_warnings(app, &report);
 
     let now = Local::now().to_rfc3339();
 
@@ -703,6 +731,19 @@ pub async fn get_oh_my_opencode_slim_global_config(
     }
 }
 
+/// 按写入配置文件时的方式展开全局配置（other_fields 平铺到顶层），用于校验
+fn global_config_fragment(content: &OhMyOpenCodeSlimGlobalConfigContent) -> Value {
+    let mut fragment = match serde_json::to_value(content) {
+        Ok(Value::Object(map)) => map,
+        _ => serde_json::Map::new(),
+    };
+    fragment.remove("updated_at");
+    if let Some(Value::Object(others)) = fragment.remove("other_fields") {
+        fragment.extend(others);
+    }
+    Value::Object(fragment)
+}
+
 /// Save oh-my-opencode-slim global config
 #[tauri::command]
 #[allow(unused_variables)]
@@ -710,6 +751,7 @@ pub async fn save_oh_my_opencode_slim_global_config(
     state: tauri::State<'_, DbState>,
     app: tauri::AppHandle,
     input: OhMyOpenCodeSlimGlobalConfigInput,
+    force: Option<bool>,
 ) -> Result<OhMyOpenCodeSlimGlobalConfig, String> {
     let db = state.db();
 
@@ -725,6 +767,14 @@ pub async fn save_oh_my_opencode_slim_global_config(
         updated_at: now.clone(),
     };
 
+    let report = config_validation::check(
+        config_validation::validate_fragment_json(
+            ConfigKind::OhMyOpenCodeSlim,
+            &global_config_fragment(&content),
+        ),
+        force.unwrap_or(false),
+    )?;
+
     let json_data = adapter::global_config_to_db_value(&content);
 
     db.query("UPSERT oh_my_opencode_slim_global_config:`global` CONTENT $data")
@@ -748,6 +798,8 @@ pub async fn save_oh_my_opencode_slim_global_config(
         }
     }
 
+    config_validation::emit_warnings(&app, &report);
+
     let records_result: Result<Vec<Value>, _> = db
         .query("SELECT *, type::string(id) as id FROM oh_my_opencode_slim_global_config:`global` LIMIT 1")
         .await
@@ -803,7 +855,7 @@ pub async fn toggle_oh_my_opencode_slim_config_disabled(
                 adapter::get_bool_compat(config_value, "is_applied", "isApplied", false);
             if is_applied {
                 // Re-apply config to update files (will check is_disabled internally)
-                apply_config_internal(&db, &app, &config_id, false).await?;
+                apply_config_internal(&db, &app, &config_id, false, false).await?;
             }
         }
     }
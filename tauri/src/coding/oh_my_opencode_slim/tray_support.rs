@@ -104,7 +104,7 @@ pub async fn apply_oh_my_opencode_slim_config<R: Runtime>(
     let state = app.state::<DbState>();
     let db = state.db();
 
-    super::commands::apply_config_internal(&db, app, config_id, true).await?;
+    super::commands::apply_config_internal(&db, app, config_id, true, false).await?;
 
     Ok(())
 }
@@ -6,6 +6,8 @@ use tauri::Emitter;
 use super::adapter;
 use super::types::*;
 use crate::coding::all_api_hub;
+use crate::coding::config_history;
+use crate::coding::config_validation::{self, ConfigKind};
 use crate::db::DbState;
 
 // ============================================================================
@@ -29,7 +31,15 @@ pub async fn apply_config_internal<R: tauri::Runtime>(
     app: &tauri::AppHandle<R>,
     config: OpenClawConfig,
     from_tray: bool,
+    force: bool,
 ) -> Result<(), String> {
+    let config_value =
+        serde_json::to_value(&config).map_err(|e| format!("Failed to serialize config: {}", e))?;
+    let report = config_validation::check(
+        config_validation::validate_json(ConfigKind::OpenClaw, &config_value),
+        force,
+    )?;
+
     let config_path_str = get_openclaw_config_path(state).await?;
     let config_path = Path::new(&config_path_str);
 
@@ -45,8 +55,9 @@ pub async fn apply_config_internal<R: tauri::Runtime>(
     let json_content = serde_json::to_string_pretty(&config)
         .map_err(|e| format!("Failed to serialize config: {}", e))?;
 
-    fs::write(config_path, json_content)
+    config_history::write(config_path, json_content, "openclaw", "save_config")
         .map_err(|e| format!("Failed to write config file: {}", e))?;
+    config_validation::emit_warnings(app, &report);
 
     let payload = if from_tray { "tray" } else { "window" };
     let _ = app.emit("openclaw-config-changed", payload);
@@ -176,8 +187,9 @@ pub async fn save_openclaw_config<R: tauri::Runtime>(
     state: tauri::State<'_, DbState>,
     app: tauri::AppHandle<R>,
     config: OpenClawConfig,
+    force: Option<bool>,
 ) -> Result<(), String> {
-    apply_config_internal(state, &app, config, false).await
+    apply_config_internal(state, &app, config, false, force.unwrap_or(false)).await
 }
 
 /// Backup OpenClaw configuration file
@@ -272,6 +284,7 @@ pub async fn set_openclaw_agents_defaults<R: tauri::Runtime>(
     state: tauri::State<'_, DbState>,
     app: tauri::AppHandle<R>,
     defaults: OpenClawAgentsDefaults,
+    force: Option<bool>,
 ) -> Result<(), String> {
     let mut config = read_and_parse_config(state.clone()).await?;
 
@@ -283,7 +296,7 @@ pub async fn set_openclaw_agents_defaults<R: tauri::Runtime>(
     agents.defaults = Some(defaults);
     config.agents = Some(agents);
 
-    apply_config_internal(state, &app, config, false).await
+    apply_config_internal(state, &app, config, false, force.unwrap_or(false)).await
 }
 
 // ============================================================================
@@ -305,10 +318,11 @@ pub async fn set_openclaw_env<R: tauri::Runtime>(
     state: tauri::State<'_, DbState>,
     app: tauri::AppHandle<R>,
     env: OpenClawEnvConfig,
+    force: Option<bool>,
 ) -> Result<(), String> {
     let mut config = read_and_parse_config(state.clone()).await?;
     config.env = Some(env);
-    apply_config_internal(state, &app, config, false).await
+    apply_config_internal(state, &app, config, false, force.unwrap_or(false)).await
 }
 
 // ============================================================================
@@ -330,10 +344,11 @@ pub async fn set_openclaw_tools<R: tauri::Runtime>(
     state: tauri::State<'_, DbState>,
     app: tauri::AppHandle<R>,
     tools: OpenClawToolsConfig,
+    force: Option<bool>,
 ) -> Result<(), String> {
     let mut config = read_and_parse_config(state.clone()).await?;
     config.tools = Some(tools);
-    apply_config_internal(state, &app, config, false).await
+    apply_config_internal(state, &app, config, false, force.unwrap_or(false)).await
 }
 
 #[tauri::command]
@@ -371,6 +386,7 @@ pub async fn list_openclaw_all_api_hub_providers(
             site_name: candidate.site_name.clone(),
             site_type: candidate.site_type.clone(),
             account_label: candidate.account_label.clone(),
+            source_browser: candidate.source_browser.clone(),
             source_profile_name: candidate.source_profile_name.clone(),
             source_extension_id: candidate.source_extension_id.clone(),
             config: all_api_hub::candidate_to_openclaw_provider(candidate),
@@ -420,6 +436,7 @@ pub async fn resolve_openclaw_all_api_hub_providers(
             site_name: candidate.site_name.clone(),
             site_type: candidate.site_type.clone(),
             account_label: candidate.account_label.clone(),
+            source_browser: candidate.source_browser.clone(),
             source_profile_name: candidate.source_profile_name.clone(),
             source_extension_id: candidate.source_extension_id.clone(),
             config: all_api_hub::candidate_to_openclaw_provider(candidate),
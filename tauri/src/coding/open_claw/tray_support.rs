@@ -109,10 +109,17 @@ pub async fn apply_openclaw_model<R: Runtime>(
 ) -> Result<(), String> {
     let result = read_openclaw_config(app.state()).await?;
     let mut config = extract_config_or_default(result);
+    set_primary_model(&mut config, item_id);
 
+    super::commands::apply_config_internal(app.state(), app, config, true, false).await
+}
+
+/// Set `agents.defaults.model.primary`, creating the missing sections
+pub fn set_primary_model(config: &mut OpenClawConfig, model_id: &str) {
     // Ensure agents.defaults.model exists
     let mut agents = config
         .agents
+        .take()
         .unwrap_or(super::types::OpenClawAgentsSection {
             defaults: None,
             extra: std::collections::HashMap::new(),
@@ -126,10 +133,10 @@ pub async fn apply_openclaw_model<R: Runtime>(
         });
 
     if let Some(ref mut model) = defaults.model {
-        model.primary = item_id.to_string();
+        model.primary = model_id.to_string();
     } else {
         defaults.model = Some(super::types::OpenClawDefaultModel {
-            primary: item_id.to_string(),
+            primary: model_id.to_string(),
             fallbacks: Vec::new(),
             extra: std::collections::HashMap::new(),
         });
@@ -137,8 +144,6 @@ pub async fn apply_openclaw_model<R: Runtime>(
 
     agents.defaults = Some(defaults);
     config.agents = Some(agents);
-
-    super::commands::apply_config_internal(app.state(), app, config, true).await
 }
 
 /// Check if OpenClaw should be shown in tray menu
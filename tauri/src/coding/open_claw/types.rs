@@ -213,6 +213,7 @@ pub struct OpenClawAllApiHubProvider {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub site_type: Option<String>,
     pub account_label: String,
+    pub source_browser: String,
     pub source_profile_name: String,
     pub source_extension_id: String,
     pub config: OpenClawProviderConfig,
@@ -7,6 +7,9 @@ use tauri::Emitter;
 use super::adapter;
 use super::types::*;
 use crate::coding::all_api_hub;
+use crate::coding::config_drift::{diff_values_at, ConfigDrift, DriftEntry};
+use crate::coding::config_history;
+use crate::coding::config_validation::{self, ConfigKind};
 use crate::coding::db_id::{db_new_id, db_record_id};
 use crate::coding::prompt_file::{read_prompt_content_file, write_prompt_content_file};
 use crate::db::DbState;
@@ -32,7 +35,7 @@ async fn write_opencode_config_file(
     let json_content = serde_json::to_string_pretty(config)
         .map_err(|e| format!("Failed to serialize config: {}", e))?;
 
-    fs::write(config_path, json_content)
+    config_history::write(config_path, json_content, "opencode", "save_config")
         .map_err(|e| format!("Failed to write config file: {}", e))?;
 
     Ok(())
@@ -76,7 +79,7 @@ async fn write_prompt_content_to_file(
     prompt_content: Option<&str>,
 ) -> Result<(), String> {
     let prompt_path = get_opencode_prompt_file_path(state).await?;
-    write_prompt_content_file(&prompt_path, prompt_content, "OpenCode")
+    write_prompt_content_file(&prompt_path, prompt_content, "OpenCode", "opencode")
 }
 
 fn emit_prompt_sync_requests<R: tauri::Runtime>(app: &tauri::AppHandle<R>) {
@@ -297,13 +300,15 @@ pub async fn backup_opencode_config(state: tauri::State<'_, DbState>) -> Result<
 }
 
 /// Save OpenCode configuration file
+/// `force` 为 true 时即使校验出错误也写入
 #[tauri::command]
 pub async fn save_opencode_config<R: tauri::Runtime>(
     state: tauri::State<'_, DbState>,
     app: tauri::AppHandle<R>,
     config: OpenCodeConfig,
+    force: Option<bool>,
 ) -> Result<(), String> {
-    apply_config_internal(state, &app, config, false).await
+    apply_config_internal(state, &app, config, false, force.unwrap_or(false)).await
 }
 
 /// Internal function to save config and emit events
@@ -312,8 +317,16 @@ pub async fn apply_config_internal<R: tauri::Runtime>(
     app: &tauri::AppHandle<R>,
     config: OpenCodeConfig,
     from_tray: bool,
+    force: bool,
 ) -> Result<(), String> {
+    let config_value =
+        serde_json::to_value(&config).map_err(|e| format!("Failed to serialize config: {}", e))?;
+    let report = config_validation::check(
+        config_validation::validate_json(ConfigKind::OpenCode, &config_value),
+        force,
+    )?;
     write_opencode_config_file(state.clone(), &config).await?;
+    config_validation::emit_warnings(app, &report);
 
     // Notify based on source
     let payload = if from_tray { "tray" } else { "window" };
@@ -1230,6 +1243,106 @@ pub async fn delete_opencode_favorite_provider(
     Ok(())
 }
 
+// ============================================================================
+// Config Drift Commands
+// ============================================================================
+
+/// 读取配置文件并与收藏 provider 对比，返回 (配置, 配置路径, 对比结果)
+/// 收藏 provider 在每次通过应用保存配置时同步，代表应用最后一次写入的 provider
+async fn compare_providers_with_favorites(
+    state: tauri::State<'_, DbState>,
+) -> Result<Option<(OpenCodeConfig, String, Vec<DriftEntry>)>, String> {
+    let config = match read_opencode_config(state.clone()).await? {
+        ReadConfigResult::Success { config } => config,
+        _ => return Ok(None),
+    };
+    let favorites = list_opencode_favorite_providers(state.clone()).await?;
+    // 从未通过应用保存过配置，没有可对比的记录
+    if favorites.is_empty() {
+        return Ok(None);
+    }
+    let config_path = get_opencode_config_path(state).await?;
+
+    let favorites: std::collections::HashMap<String, OpenCodeProvider> = favorites
+        .into_iter()
+        .map(|f| (f.provider_id, f.provider_config))
+        .collect();
+
+    let mut entries = vec![];
+    for (provider_id, provider) in config.provider.iter().flatten() {
+        let actual = serde_json::to_value(provider)
+            .map_err(|e| format!("Failed to serialize provider config: {}", e))?;
+        let expected = favorites
+            .get(provider_id)
+            .map(serde_json::to_value)
+            .transpose()
+            .map_err(|e| format!("Failed to serialize favorite provider: {}", e))?;
+        entries.extend(diff_values_at(
+            &format!("provider.{}", provider_id),
+            expected.as_ref(),
+            Some(&actual),
+        ));
+    }
+
+    Ok(Some((config, config_path, entries)))
+}
+
+/// 检测配置文件中的 provider 是否被外部修改
+#[tauri::command]
+pub async fn detect_opencode_config_drift(
+    state: tauri::State<'_, DbState>,
+) -> Result<Option<ConfigDrift>, String> {
+    Ok(compare_providers_with_favorites(state)
+        .await?
+        .map(|(_, config_path, entries)| {
+            ConfigDrift::new("opencode", None, None, config_path, entries)
+        }))
+}
+
+/// 将配置文件中被外部修改的 provider 写回收藏 provider
+#[tauri::command]
+pub async fn import_opencode_config_drift(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+) -> Result<(), String> {
+    let config = match read_opencode_config(state.clone()).await? {
+        ReadConfigResult::Success { config } => config,
+        _ => return Err("OpenCode config file is not readable".to_string()),
+    };
+
+    let db = state.db();
+    sync_providers_from_config(&db, &config).await?;
+
+    let _ = app.emit("config-changed", "window");
+    Ok(())
+}
+
+/// 用收藏 provider 覆盖配置文件中被外部修改的 provider（文件中新增的 provider 保持不变）
+#[tauri::command]
+pub async fn restore_opencode_config_drift(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+) -> Result<(), String> {
+    let favorites = list_opencode_favorite_providers(state.clone()).await?;
+    let Some((mut config, _, entries)) = compare_providers_with_favorites(state.clone()).await?
+    else {
+        return Ok(());
+    };
+    if entries.is_empty() {
+        return Ok(());
+    }
+
+    if let Some(ref mut providers) = config.provider {
+        for favorite in favorites {
+            if let Some(provider) = providers.get_mut(&favorite.provider_id) {
+                *provider = favorite.provider_config;
+            }
+        }
+    }
+
+    apply_config_internal(state, &app, config, false, false).await
+}
+
 #[tauri::command]
 pub async fn list_opencode_all_api_hub_providers(
     state: tauri::State<'_, DbState>,
@@ -1265,6 +1378,7 @@ pub async fn list_opencode_all_api_hub_providers(
             site_name: candidate.site_name.clone(),
             site_type: candidate.site_type.clone(),
             account_label: candidate.account_label.clone(),
+            source_browser: candidate.source_browser.clone(),
             source_profile_name: candidate.source_profile_name.clone(),
             source_extension_id: candidate.source_extension_id.clone(),
             provider_config: all_api_hub::candidate_to_opencode_provider(candidate),
@@ -1314,6 +1428,7 @@ pub async fn resolve_opencode_all_api_hub_providers(
             site_name: candidate.site_name.clone(),
             site_type: candidate.site_type.clone(),
             account_label: candidate.account_label.clone(),
+            source_browser: candidate.source_browser.clone(),
             source_profile_name: candidate.source_profile_name.clone(),
             source_extension_id: candidate.source_extension_id.clone(),
             provider_config: all_api_hub::candidate_to_opencode_provider(candidate),
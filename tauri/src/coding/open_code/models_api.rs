@@ -2,6 +2,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::db::DbState;
 use crate::http_client;
+use crate::secret_vault;
 use futures_util::StreamExt;
 use serde_json::{json, Value};
 use std::collections::BTreeMap;
@@ -196,12 +197,28 @@ fn build_models_url(
     }
 }
 
+/// 表单传入的 API Key / headers 可能是密钥保险库引用，发请求前解出明文
+fn resolve_vault_refs(
+    api_key: &mut Option<String>,
+    headers: &mut Option<Value>,
+) -> Result<(), String> {
+    if let Some(key) = api_key {
+        *key = secret_vault::resolve(key)?;
+    }
+    if let Some(headers) = headers {
+        secret_vault::resolve_json(headers)?;
+    }
+    Ok(())
+}
+
 /// Fetch models list from provider API
 #[tauri::command]
 pub async fn fetch_provider_models(
     state: tauri::State<'_, DbState>,
-    request: FetchModelsRequest,
+    mut request: FetchModelsRequest,
 ) -> Result<FetchModelsResponse, String> {
+    resolve_vault_refs(&mut request.api_key, &mut request.headers)?;
+
     // Create HTTP client with timeout and proxy support
     let client = http_client::client_with_timeout(&state, 30).await?;
 
@@ -704,7 +721,7 @@ fn enforce_prompt_and_model(npm: &str, body: &mut Value, model_id: &str, prompt:
     }
 }
 
-async fn run_connectivity_test_for_model(
+pub(crate) async fn run_connectivity_test_for_model(
     client: &reqwest::Client,
     request: &ConnectivityTestRequest,
     model_id: &str,
@@ -901,8 +918,10 @@ async fn run_connectivity_test_for_model(
 #[tauri::command]
 pub async fn test_provider_model_connectivity(
     state: tauri::State<'_, DbState>,
-    request: ConnectivityTestRequest,
+    mut request: ConnectivityTestRequest,
 ) -> Result<ConnectivityTestResponse, String> {
+    resolve_vault_refs(&mut request.api_key, &mut request.headers)?;
+
     let timeout_secs = request.timeout_secs.unwrap_or(30);
     let client = http_client::client_with_timeout(&state, timeout_secs).await?;
 
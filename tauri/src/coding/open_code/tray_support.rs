@@ -7,6 +7,7 @@ use crate::coding::open_code::free_models;
 use crate::coding::open_code::types::{OpenCodeProvider, ReadConfigResult, UnifiedModelOption};
 use crate::coding::open_code::{read_opencode_config, OpenCodeConfig};
 use indexmap::IndexMap;
+use serde::Serialize;
 use tauri::{AppHandle, Manager, Runtime};
 
 /// Helper to extract OpenCodeConfig from ReadConfigResult, returning default config for non-success cases
@@ -26,7 +27,8 @@ fn extract_config_or_default(result: ReadConfigResult) -> OpenCodeConfig {
 }
 
 /// Item for model selection in tray menu
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct TrayModelItem {
     /// Unique identifier for the model (used in event handling)
     pub id: String,
@@ -37,7 +39,8 @@ pub struct TrayModelItem {
 }
 
 /// Data for a model submenu
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct TrayModelData {
     /// Title of the submenu (e.g., "主模型")
     pub title: String,
@@ -181,7 +184,7 @@ pub async fn apply_opencode_model<R: Runtime>(
     }
 
     // Save config from tray (will emit "tray" event)
-    super::commands::apply_config_internal(app.state(), app, config, true).await?;
+    super::commands::apply_config_internal(app.state(), app, config, true, false).await?;
 
     Ok(())
 }
@@ -388,7 +391,7 @@ pub async fn apply_opencode_plugin<R: Runtime>(
     config.plugin = Some(plugins);
 
     // Save config from tray (will emit "tray" event)
-    super::commands::apply_config_internal(app.state(), app, config, true).await?;
+    super::commands::apply_config_internal(app.state(), app, config, true, false).await?;
 
     Ok(())
 }
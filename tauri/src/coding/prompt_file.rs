@@ -1,6 +1,8 @@
 use std::fs;
 use std::path::Path;
 
+use super::config_history;
+
 pub fn read_prompt_content_file(path: &Path, product_name: &str) -> Result<Option<String>, String> {
     if !path.exists() {
         return Ok(None);
@@ -21,6 +23,7 @@ pub fn write_prompt_content_file(
     path: &Path,
     prompt_content: Option<&str>,
     product_name: &str,
+    module: &str,
 ) -> Result<(), String> {
     if let Some(parent) = path.parent() {
         if !parent.exists() {
@@ -35,7 +38,7 @@ pub fn write_prompt_content_file(
         .filter(|value| !value.is_empty())
         .unwrap_or("");
 
-    fs::write(path, content)
+    config_history::write(path, content, module, "prompt")
         .map_err(|e| format!("Failed to write {} prompt file: {}", product_name, e))?;
 
     Ok(())
@@ -0,0 +1,506 @@
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+
+use chrono::{DateTime, Local};
+use serde_json::{json, Value};
+
+use super::markdown;
+use super::transcript::{self, ParsedSession};
+use super::types::*;
+use crate::coding::db_id::{db_clean_id, db_new_id, db_record_id};
+use crate::coding::usage::{collect_units, SourceUnit, SOURCE_CLAUDE_CODE, SOURCE_OPENCODE};
+use crate::db::DbState;
+
+/// 单条消息写入索引的最大长度
+const MESSAGE_TEXT_MAX_CHARS: usize = 8000;
+const DEFAULT_SEARCH_LIMIT: usize = 200;
+/// 全文搜索最多取回的消息命中数
+const SEARCH_HITS_LIMIT: usize = 2000;
+const SNIPPET_BEFORE_CHARS: usize = 60;
+const SNIPPET_AFTER_CHARS: usize = 140;
+
+/// 索引更新互斥，避免并发搜索重复写入同一会话
+static INDEX_LOCK: tokio::sync::Mutex<()> = tokio::sync::Mutex::const_new(());
+
+type Db = surrealdb::Surreal<surrealdb::engine::local::Db>;
+
+fn fallback_id(path: &Path) -> String {
+    path.file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+fn read_json(path: &Path) -> Option<Value> {
+    serde_json::from_str(&fs::read_to_string(path).ok()?).ok()
+}
+
+fn read_json_dir(dir: &Path) -> Vec<Value> {
+    let mut files: Vec<_> = fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| p.extension().and_then(|e| e.to_str()) == Some("json"))
+        .collect();
+    // OpenCode 的 ID 按创建时间递增
+    files.sort();
+    files.iter().filter_map(|p| read_json(p)).collect()
+}
+
+/// `dir` 为 `storage/message/<session>`，会话信息与内容块在同一 storage 下的 session/ 与 part/ 目录
+fn read_opencode_session(dir: &Path) -> ParsedSession {
+    let session_id = fallback_id(dir);
+    let Some(storage) = dir.parent().and_then(|p| p.parent()) else {
+        return ParsedSession::default();
+    };
+    let info = fs::read_dir(storage.join("session"))
+        .into_iter()
+        .flatten()
+        .filter_map(|e| e.ok())
+        .find_map(|project| read_json(&project.path().join(format!("{}.json", session_id))));
+    let messages = read_json_dir(dir)
+        .into_iter()
+        .map(|message| {
+            let parts = message
+                .get("id")
+                .and_then(|v| v.as_str())
+                .map(|id| read_json_dir(&storage.join("part").join(id)))
+                .unwrap_or_default();
+            (message, parts)
+        })
+        .collect();
+    transcript::parse_opencode_session(info.as_ref(), messages, &session_id)
+}
+
+fn parse_unit(unit: &SourceUnit) -> ParsedSession {
+    if unit.source == SOURCE_OPENCODE {
+        return read_opencode_session(&unit.path);
+    }
+    let content = fs::read_to_string(&unit.path).unwrap_or_default();
+    let fallback = fallback_id(&unit.path);
+    if unit.source == SOURCE_CLAUDE_CODE {
+        transcript::parse_claude_session(&content, &fallback)
+    } else {
+        transcript::parse_codex_session(&content, &fallback)
+    }
+}
+
+/// 全文索引：按空白、字符类别和标点切词，英文词干化
+async fn ensure_search_index(db: &Db) -> Result<(), String> {
+    db.query(
+        "DEFINE ANALYZER IF NOT EXISTS session_history_analyzer TOKENIZERS blank,class,punct FILTERS lowercase,ascii,snowball(english);
+         DEFINE INDEX IF NOT EXISTS session_history_message_text ON session_history_message FIELDS text SEARCH ANALYZER session_history_analyzer BM25;",
+    )
+    .await
+    .map_err(|e| format!("Failed to define session search index: {}", e))?
+    .check()
+    .map_err(|e| format!("Failed to define session search index: {}", e))?;
+    Ok(())
+}
+
+async fn remove_session(db: &Db, id: &str) -> Result<(), String> {
+    db.query(format!(
+        "DELETE session_history_message WHERE session = $id; DELETE {}",
+        db_record_id("session_history", id)
+    ))
+    .bind(("id", id.to_string()))
+    .await
+    .map_err(|e| format!("Failed to remove session from index: {}", e))?;
+    Ok(())
+}
+
+/// 增量索引：只解析新增或变化的会话，日志已删除的会话从索引移除
+pub async fn index_sessions(db: &Db) -> Result<SessionIndexResult, String> {
+    let _guard = INDEX_LOCK.lock().await;
+    ensure_search_index(db).await?;
+
+    let records: Vec<Value> = db
+        .query("SELECT type::string(id) as id, path, fingerprint FROM session_history")
+        .await
+        .map_err(|e| format!("Failed to query session index: {}", e))?
+        .take(0)
+        .map_err(|e| format!("Failed to parse session index: {}", e))?;
+    let known: HashMap<String, (String, String)> = records
+        .iter()
+        .filter_map(|r| {
+            Some((
+                r.get("path")?.as_str()?.to_string(),
+                (
+                    db_clean_id(r.get("id")?.as_str()?),
+                    r.get("fingerprint")?.as_str()?.to_string(),
+                ),
+            ))
+        })
+        .collect();
+
+    let units = collect_units();
+    let mut seen = HashSet::new();
+    let mut sessions_updated = 0;
+    for unit in &units {
+        let path = unit.path.to_string_lossy().to_string();
+        seen.insert(path.clone());
+        let existing = known.get(&path);
+        if existing.map(|(_, fp)| fp) == Some(&unit.fingerprint) {
+            continue;
+        }
+        // 重建时沿用原 ID，前端打开中的会话不会失效
+        let id = match existing {
+            Some((id, _)) => {
+                remove_session(db, id).await?;
+                id.clone()
+            }
+            None => db_new_id(),
+        };
+
+        let session = parse_unit(unit);
+        let rows: Vec<Value> = session
+            .messages
+            .iter()
+            .enumerate()
+            .filter(|(_, m)| m.role != "tool")
+            .map(|(idx, m)| {
+                json!({
+                    "session": id,
+                    "idx": idx,
+                    "role": m.role,
+                    "text": transcript::searchable_text(m, MESSAGE_TEXT_MAX_CHARS),
+                })
+            })
+            .filter(|row| row["text"].as_str().is_some_and(|t| !t.is_empty()))
+            .collect();
+        let data = json!({
+            "path": path,
+            "source": unit.source,
+            "fingerprint": unit.fingerprint,
+            "session_id": session.session_id,
+            "title": session.title,
+            "project": session.project,
+            "models": session.models,
+            "started_at": session.started_at,
+            "updated_at": session.updated_at,
+            "message_count": session.messages.len(),
+        });
+        db.query(format!(
+            "CREATE {} CONTENT $data",
+            db_record_id("session_history", &id)
+        ))
+        .bind(("data", data))
+        .await
+        .map_err(|e| format!("Failed to save session index: {}", e))?;
+        if !rows.is_empty() {
+            db.query("INSERT INTO session_history_message $rows")
+                .bind(("rows", rows))
+                .await
+                .map_err(|e| format!("Failed to save session messages: {}", e))?;
+        }
+        sessions_updated += 1;
+    }
+
+    let mut sessions_removed = 0;
+    for (path, (id, _)) in &known {
+        if !seen.contains(path) {
+            remove_session(db, id).await?;
+            sessions_removed += 1;
+        }
+    }
+
+    Ok(SessionIndexResult {
+        sessions_total: units.len(),
+        sessions_updated,
+        sessions_removed,
+    })
+}
+
+fn summary_from_record(record: &Value) -> SessionSummary {
+    let get = |key: &str| {
+        record
+            .get(key)
+            .and_then(|v| v.as_str())
+            .unwrap_or_default()
+            .to_string()
+    };
+    SessionSummary {
+        id: db_clean_id(&get("id")),
+        source: get("source"),
+        session_id: get("session_id"),
+        path: get("path"),
+        title: get("title"),
+        project: get("project"),
+        models: record
+            .get("models")
+            .and_then(|v| v.as_array())
+            .into_iter()
+            .flatten()
+            .filter_map(|m| m.as_str().map(String::from))
+            .collect(),
+        started_at: get("started_at"),
+        updated_at: get("updated_at"),
+        message_count: record
+            .get("message_count")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(0) as usize,
+        snippet: None,
+    }
+}
+
+fn local_date(timestamp: &str) -> Option<String> {
+    DateTime::parse_from_rfc3339(timestamp)
+        .ok()
+        .map(|t| t.with_timezone(&Local).format("%Y-%m-%d").to_string())
+}
+
+/// 截取首个关键词附近的文本，关键词以 `**` 包裹；找不到时取开头
+fn make_snippet(text: &str, terms: &[String]) -> String {
+    let chars: Vec<char> = text
+        .chars()
+        .map(|c| if c == '\n' { ' ' } else { c })
+        .collect();
+    let lower: Vec<char> = chars
+        .iter()
+        .map(|c| c.to_lowercase().next().unwrap_or(*c))
+        .collect();
+    let found = terms.iter().find_map(|term| {
+        let needle: Vec<char> = term.chars().collect();
+        if needle.is_empty() {
+            return None;
+        }
+        lower
+            .windows(needle.len())
+            .position(|w| w == needle.as_slice())
+            .map(|pos| (pos, needle.len()))
+    });
+    let collect = |range: std::ops::Range<usize>| chars[range].iter().collect::<String>();
+
+    match found {
+        Some((pos, len)) => {
+            let start = pos.saturating_sub(SNIPPET_BEFORE_CHARS);
+            let end = (pos + len + SNIPPET_AFTER_CHARS).min(chars.len());
+            format!(
+                "{}{}**{}**{}{}",
+                if start > 0 { "…" } else { "" },
+                collect(start..pos),
+                collect(pos..pos + len),
+                collect(pos + len..end),
+                if end < chars.len() { "…" } else { "" }
+            )
+        }
+        None => {
+            let end = (SNIPPET_BEFORE_CHARS + SNIPPET_AFTER_CHARS).min(chars.len());
+            let suffix = if end < chars.len() { "…" } else { "" };
+            format!("{}{}", collect(0..end), suffix)
+        }
+    }
+}
+
+/// 全文检索消息，返回 会话 ID → (得分, 片段)
+///
+/// 英文等按词检索（BM25）；含中日韩等非 ASCII 字符时分词不可靠，改为逐条包含匹配
+async fn search_messages(db: &Db, text: &str) -> Result<HashMap<String, (f64, String)>, String> {
+    let terms: Vec<String> = text.split_whitespace().map(|t| t.to_lowercase()).collect();
+    let hits: Vec<Value> = if text.is_ascii() {
+        db.query(format!(
+            "SELECT session, text, search::score(1) AS score FROM session_history_message WHERE text @1@ $q ORDER BY score DESC LIMIT {}",
+            SEARCH_HITS_LIMIT
+        ))
+        .bind(("q", text.to_string()))
+        .await
+    } else {
+        let conditions: Vec<String> = (0..terms.len())
+            .map(|i| format!("string::contains(string::lowercase(text), $t{})", i))
+            .collect();
+        let mut query = db.query(format!(
+            "SELECT session, text, 1 AS score FROM session_history_message WHERE {} LIMIT {}",
+            conditions.join(" AND "),
+            SEARCH_HITS_LIMIT
+        ));
+        for (i, term) in terms.iter().enumerate() {
+            query = query.bind((format!("t{}", i), term.clone()));
+        }
+        query.await
+    }
+    .map_err(|e| format!("Failed to search session messages: {}", e))?
+    .take(0)
+    .map_err(|e| format!("Failed to parse session search results: {}", e))?;
+
+    let mut sessions: HashMap<String, (f64, String)> = HashMap::new();
+    for hit in &hits {
+        let Some(session) = hit.get("session").and_then(|v| v.as_str()) else {
+            continue;
+        };
+        let score = hit.get("score").and_then(|v| v.as_f64()).unwrap_or(0.0);
+        let entry = sessions
+            .entry(session.to_string())
+            .or_insert((f64::MIN, String::new()));
+        if score > entry.0 {
+            let text = hit.get("text").and_then(|v| v.as_str()).unwrap_or_default();
+            *entry = (score, make_snippet(text, &terms));
+        }
+    }
+    Ok(sessions)
+}
+
+pub async fn search_sessions(
+    db: &Db,
+    query: &SessionSearchQuery,
+) -> Result<Vec<SessionSummary>, String> {
+    let records: Vec<Value> = db
+        .query("SELECT *, type::string(id) as id FROM session_history WHERE message_count > 0")
+        .await
+        .map_err(|e| format!("Failed to query session index: {}", e))?
+        .take(0)
+        .map_err(|e| format!("Failed to parse session index: {}", e))?;
+
+    let text = query
+        .text
+        .as_deref()
+        .map(str::trim)
+        .filter(|t| !t.is_empty());
+    let hits = match text {
+        Some(text) => Some(search_messages(db, text).await?),
+        None => None,
+    };
+    let contains = |value: &str, needle: &Option<String>| match needle.as_deref().map(str::trim) {
+        Some(needle) if !needle.is_empty() => value.to_lowercase().contains(&needle.to_lowercase()),
+        _ => true,
+    };
+
+    let mut results: Vec<(f64, SessionSummary)> = Vec::new();
+    for record in &records {
+        let mut summary = summary_from_record(record);
+        if !query.sources.is_empty() && !query.sources.contains(&summary.source) {
+            continue;
+        }
+        if !contains(&summary.project, &query.project)
+            || !contains(&summary.models.join("\n"), &query.model)
+        {
+            continue;
+        }
+        if query.start_date.is_some() || query.end_date.is_some() {
+            let Some(date) = local_date(&summary.updated_at) else {
+                continue;
+            };
+            if query.start_date.as_ref().is_some_and(|start| &date < start)
+                || query.end_date.as_ref().is_some_and(|end| &date > end)
+            {
+                continue;
+            }
+        }
+        let score = match &hits {
+            Some(hits) => {
+                let Some((score, snippet)) = hits.get(&summary.id) else {
+                    continue;
+                };
+                summary.snippet = Some(snippet.clone());
+                *score
+            }
+            None => 0.0,
+        };
+        results.push((score, summary));
+    }
+
+    results.sort_by(|(a_score, a), (b_score, b)| {
+        b_score
+            .total_cmp(a_score)
+            .then_with(|| b.updated_at.cmp(&a.updated_at))
+    });
+    Ok(results
+        .into_iter()
+        .take(query.limit.unwrap_or(DEFAULT_SEARCH_LIMIT))
+        .map(|(_, summary)| summary)
+        .collect())
+}
+
+/// 从原始日志重新解析完整会话（索引只保存元数据和可检索文本）
+pub async fn load_transcript(db: &Db, id: &str) -> Result<SessionTranscript, String> {
+    let records: Vec<Value> = db
+        .query(format!(
+            "SELECT *, type::string(id) as id FROM {} LIMIT 1",
+            db_record_id("session_history", id)
+        ))
+        .await
+        .map_err(|e| format!("Failed to query session: {}", e))?
+        .take(0)
+        .map_err(|e| format!("Failed to parse session: {}", e))?;
+    let record = records
+        .first()
+        .ok_or_else(|| format!("Session not found: {}", id))?;
+    let summary = summary_from_record(record);
+
+    let path = Path::new(&summary.path);
+    if !path.exists() {
+        return Err(format!("Session log no longer exists: {}", summary.path));
+    }
+    let source = match summary.source.as_str() {
+        SOURCE_CLAUDE_CODE => SOURCE_CLAUDE_CODE,
+        SOURCE_OPENCODE => SOURCE_OPENCODE,
+        _ => crate::coding::usage::SOURCE_CODEX,
+    };
+    let session = parse_unit(&SourceUnit {
+        source,
+        path: path.to_path_buf(),
+        fingerprint: String::new(),
+    });
+    Ok(SessionTranscript {
+        summary,
+        messages: session.messages,
+    })
+}
+
+// ============================================================================
+// Tauri Commands
+// ============================================================================
+
+/// 搜索会话历史（先增量更新索引）
+#[tauri::command]
+pub async fn search_session_history(
+    state: tauri::State<'_, DbState>,
+    query: SessionSearchQuery,
+) -> Result<Vec<SessionSummary>, String> {
+    let db = state.db();
+    index_sessions(&db).await?;
+    search_sessions(&db, &query).await
+}
+
+#[tauri::command]
+pub async fn reindex_session_history(
+    state: tauri::State<'_, DbState>,
+) -> Result<SessionIndexResult, String> {
+    index_sessions(&state.db()).await
+}
+
+#[tauri::command]
+pub async fn get_session_transcript(
+    state: tauri::State<'_, DbState>,
+    id: String,
+) -> Result<SessionTranscript, String> {
+    load_transcript(&state.db(), &id).await
+}
+
+/// 导出会话为 Markdown 文件，返回写入的路径
+#[tauri::command]
+pub async fn export_session_markdown(
+    state: tauri::State<'_, DbState>,
+    id: String,
+    output_path: String,
+) -> Result<String, String> {
+    let transcript = load_transcript(&state.db(), &id).await?;
+    fs::write(&output_path, markdown::render_markdown(&transcript))
+        .map_err(|e| format!("Failed to write {}: {}", output_path, e))?;
+    Ok(output_path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn snippet_highlights_first_matching_term() {
+        let text = format!("{}Fixed the Migration\nfor users", "x".repeat(100));
+        let snippet = make_snippet(&text, &["migration".to_string()]);
+        assert!(snippet.starts_with('…'));
+        assert!(snippet.contains("the **Migration** for users"));
+
+        assert_eq!(make_snippet("短文本", &["无".to_string()]), "短文本");
+    }
+}
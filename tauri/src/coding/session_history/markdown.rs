@@ -0,0 +1,184 @@
+//! 会话导出为 Markdown
+
+use serde_json::Value;
+
+use super::types::{SessionTranscript, TranscriptPart};
+
+/// 工具输出在导出中的最大长度
+const TOOL_OUTPUT_MAX_CHARS: usize = 4000;
+
+fn source_label(source: &str) -> &str {
+    match source {
+        "claudecode" => "Claude Code",
+        "codex" => "Codex",
+        "opencode" => "OpenCode",
+        other => other,
+    }
+}
+
+/// 生成比内容中最长反引号序列更长的代码围栏，避免内容提前闭合代码块
+fn fenced(content: &str, lang: &str) -> String {
+    let longest = content
+        .split(|c| c != '`')
+        .map(|run| run.len())
+        .max()
+        .unwrap_or(0);
+    let fence = "`".repeat(longest.max(2) + 1);
+    format!("{}{}\n{}\n{}\n\n", fence, lang, content.trim_end(), fence)
+}
+
+fn truncate(text: &str) -> String {
+    match text.char_indices().nth(TOOL_OUTPUT_MAX_CHARS) {
+        Some((idx, _)) => format!("{}\n… (truncated)", &text[..idx]),
+        None => text.to_string(),
+    }
+}
+
+fn render_part(out: &mut String, part: &TranscriptPart) {
+    match part {
+        TranscriptPart::Text { text } => {
+            out.push_str(text.trim_end());
+            out.push_str("\n\n");
+        }
+        TranscriptPart::Thinking { text } => {
+            out.push_str("<details>\n<summary>Thinking</summary>\n\n");
+            out.push_str(text.trim_end());
+            out.push_str("\n\n</details>\n\n");
+        }
+        TranscriptPart::ToolCall { name, input, .. } => {
+            out.push_str(&format!("**Tool call:** `{}`\n\n", name));
+            let input = match input {
+                Value::Null => return,
+                Value::String(s) => s.clone(),
+                other => serde_json::to_string_pretty(other).unwrap_or_default(),
+            };
+            out.push_str(&fenced(&input, "json"));
+        }
+        TranscriptPart::ToolResult {
+            output, is_error, ..
+        } => {
+            let label = if *is_error {
+                "Tool error"
+            } else {
+                "Tool result"
+            };
+            out.push_str(&format!("<details>\n<summary>{}</summary>\n\n", label));
+            out.push_str(&fenced(&truncate(output), ""));
+            out.push_str("</details>\n\n");
+        }
+        TranscriptPart::FileEdit {
+            path,
+            tool,
+            old_text,
+            new_text,
+            diff,
+        } => {
+            out.push_str(&format!("**Edited file:** `{}` ({})\n\n", path, tool));
+            let diff = diff.clone().unwrap_or_else(|| {
+                let lines = |text: &Option<String>, prefix: char| {
+                    text.as_deref()
+                        .unwrap_or_default()
+                        .lines()
+                        .map(|l| format!("{}{}", prefix, l))
+                        .collect::<Vec<_>>()
+                };
+                [lines(old_text, '-'), lines(new_text, '+')]
+                    .concat()
+                    .join("\n")
+            });
+            if !diff.is_empty() {
+                out.push_str(&fenced(&diff, "diff"));
+            }
+        }
+    }
+}
+
+pub fn render_markdown(transcript: &SessionTranscript) -> String {
+    let summary = &transcript.summary;
+    let title = if summary.title.is_empty() {
+        summary.session_id.as_str()
+    } else {
+        summary.title.as_str()
+    };
+
+    let mut out = format!("# {}\n\n", title.replace('\n', " "));
+    out.push_str(&format!(
+        "- **Source:** {}\n",
+        source_label(&summary.source)
+    ));
+    out.push_str(&format!("- **Session:** `{}`\n", summary.session_id));
+    if !summary.project.is_empty() {
+        out.push_str(&format!("- **Project:** `{}`\n", summary.project));
+    }
+    if !summary.models.is_empty() {
+        out.push_str(&format!("- **Models:** {}\n", summary.models.join(", ")));
+    }
+    out.push_str(&format!("- **Started:** {}\n", summary.started_at));
+    out.push_str(&format!("- **Updated:** {}\n\n---\n\n", summary.updated_at));
+
+    for message in &transcript.messages {
+        let role = match message.role.as_str() {
+            "user" => "User",
+            "assistant" => "Assistant",
+            _ => "Tool",
+        };
+        let mut heading = format!("## {}", role);
+        if let Some(model) = &message.model {
+            heading.push_str(&format!(" · {}", model));
+        }
+        if let Some(timestamp) = &message.timestamp {
+            heading.push_str(&format!(" · {}", timestamp));
+        }
+        out.push_str(&heading);
+        out.push_str("\n\n");
+        for part in &message.parts {
+            render_part(&mut out, part);
+        }
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coding::session_history::types::{SessionSummary, TranscriptMessage};
+
+    #[test]
+    fn markdown_uses_longer_fences_and_renders_edits() {
+        let transcript = SessionTranscript {
+            summary: SessionSummary {
+                source: "codex".to_string(),
+                session_id: "c1".to_string(),
+                title: "Rename".to_string(),
+                ..Default::default()
+            },
+            messages: vec![TranscriptMessage {
+                role: "assistant".to_string(),
+                timestamp: None,
+                model: Some("gpt-5".to_string()),
+                parts: vec![
+                    TranscriptPart::ToolResult {
+                        call_id: None,
+                        output: "```\ncode\n```".to_string(),
+                        is_error: false,
+                    },
+                    TranscriptPart::FileEdit {
+                        path: "a.rs".to_string(),
+                        tool: "Edit".to_string(),
+                        old_text: Some("x".to_string()),
+                        new_text: Some("y".to_string()),
+                        diff: None,
+                    },
+                ],
+            }],
+        };
+
+        let markdown = render_markdown(&transcript);
+        assert!(markdown.starts_with("# Rename\n"));
+        assert!(markdown.contains("- **Source:** Codex"));
+        assert!(markdown.contains("## Assistant · gpt-5"));
+        assert!(markdown.contains("````\n```\ncode\n```\n````"));
+        assert!(markdown.contains("```diff\n-x\n+y\n```"));
+    }
+}
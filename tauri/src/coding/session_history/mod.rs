@@ -0,0 +1,15 @@
+//! 会话历史
+//!
+//! 增量索引 Claude Code、Codex、OpenCode 的本地会话日志，支持按关键词、项目、模型、日期检索，
+//! 查看归一化后的完整对话并导出为 Markdown。
+//!
+//! 索引只保存会话元数据（`session_history`）和每条消息的可检索文本（`session_history_message`，
+//! BM25 全文索引），查看和导出时从原始日志重新解析。日志文件清单与用量统计共用。
+
+mod commands;
+mod markdown;
+mod transcript;
+mod types;
+
+pub use commands::*;
+pub use types::*;
@@ -0,0 +1,670 @@
+//! 把各工具的会话日志归一化为统一的消息/工具调用/文件编辑结构
+
+use std::collections::BTreeSet;
+
+use chrono::{Local, TimeZone};
+use serde_json::Value;
+
+use super::types::{TranscriptMessage, TranscriptPart};
+
+/// 会话标题的最大长度（取首条用户消息时截断）
+const TITLE_MAX_CHARS: usize = 80;
+
+/// 解析后的会话
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ParsedSession {
+    pub session_id: String,
+    pub title: String,
+    pub project: String,
+    pub models: Vec<String>,
+    pub started_at: String,
+    pub updated_at: String,
+    pub messages: Vec<TranscriptMessage>,
+}
+
+fn str_at<'a>(value: &'a Value, path: &str) -> Option<&'a str> {
+    value
+        .pointer(path)
+        .and_then(|v| v.as_str())
+        .filter(|s| !s.is_empty())
+}
+
+fn truncate_chars(text: &str, max: usize) -> String {
+    match text.char_indices().nth(max) {
+        Some((idx, _)) => format!("{}…", &text[..idx]),
+        None => text.to_string(),
+    }
+}
+
+fn millis_to_rfc3339(millis: i64) -> Option<String> {
+    Local
+        .timestamp_millis_opt(millis)
+        .single()
+        .map(|t| t.to_rfc3339())
+}
+
+/// 追加到上一条同角色消息（Claude Code 把一条回复拆成多行，Codex 把一轮拆成多个 item）
+fn push_parts(
+    messages: &mut Vec<TranscriptMessage>,
+    role: &str,
+    timestamp: Option<&str>,
+    model: Option<&str>,
+    parts: Vec<TranscriptPart>,
+    merge: bool,
+) {
+    if parts.is_empty() {
+        return;
+    }
+    if merge {
+        if let Some(last) = messages.last_mut().filter(|m| m.role == role) {
+            last.parts.extend(parts);
+            return;
+        }
+    }
+    messages.push(TranscriptMessage {
+        role: role.to_string(),
+        timestamp: timestamp.map(String::from),
+        model: model.map(String::from),
+        parts,
+    });
+}
+
+/// 由消息推导标题与时间范围等公共字段
+fn finish(mut session: ParsedSession, models: BTreeSet<String>) -> ParsedSession {
+    if session.title.is_empty() {
+        session.title = session
+            .messages
+            .iter()
+            .filter(|m| m.role == "user")
+            .flat_map(|m| m.parts.iter())
+            .find_map(|part| match part {
+                TranscriptPart::Text { text } if !text.trim().is_empty() => Some(text.trim()),
+                _ => None,
+            })
+            .map(|text| truncate_chars(&text.replace('\n', " "), TITLE_MAX_CHARS))
+            .unwrap_or_default();
+    }
+    let mut timestamps = session
+        .messages
+        .iter()
+        .filter_map(|m| m.timestamp.as_deref());
+    if session.started_at.is_empty() {
+        session.started_at = timestamps.next().unwrap_or_default().to_string();
+    }
+    if let Some(last) = session
+        .messages
+        .iter()
+        .rev()
+        .find_map(|m| m.timestamp.as_deref())
+    {
+        session.updated_at = last.to_string();
+    }
+    if session.updated_at.is_empty() {
+        session.updated_at = session.started_at.clone();
+    }
+    session.models = models.into_iter().collect();
+    session
+}
+
+/// Claude Code 的文件编辑工具
+fn claude_file_edits(name: &str, input: &Value) -> Option<Vec<TranscriptPart>> {
+    let path = str_at(input, "/file_path")
+        .or_else(|| str_at(input, "/notebook_path"))?
+        .to_string();
+    let edit = |old: Option<&str>, new: Option<&str>| TranscriptPart::FileEdit {
+        path: path.clone(),
+        tool: name.to_string(),
+        old_text: old.map(String::from),
+        new_text: new.map(String::from),
+        diff: None,
+    };
+    match name {
+        "Edit" => Some(vec![edit(
+            str_at(input, "/old_string"),
+            str_at(input, "/new_string"),
+        )]),
+        "Write" => Some(vec![edit(None, str_at(input, "/content"))]),
+        "NotebookEdit" => Some(vec![edit(None, str_at(input, "/new_source"))]),
+        "MultiEdit" => Some(
+            input
+                .get("edits")
+                .and_then(|v| v.as_array())
+                .into_iter()
+                .flatten()
+                .map(|e| edit(str_at(e, "/old_string"), str_at(e, "/new_string")))
+                .collect(),
+        ),
+        _ => None,
+    }
+}
+
+fn claude_tool_output(content: &Value) -> String {
+    match content {
+        Value::String(text) => text.clone(),
+        Value::Array(blocks) => blocks
+            .iter()
+            .filter_map(|b| str_at(b, "/text"))
+            .collect::<Vec<_>>()
+            .join("\n"),
+        _ => String::new(),
+    }
+}
+
+/// Claude Code 会话（`~/.claude/projects/<project>/<session>.jsonl`）
+///
+/// 同一条 assistant 回复按内容块拆成多行，message.id 相同的连续行合并为一条消息；
+/// 只包含 tool_result 的 user 行归为 "tool" 消息。子代理（sidechain）和元信息行不计入。
+pub fn parse_claude_session(content: &str, fallback_id: &str) -> ParsedSession {
+    let mut session = ParsedSession {
+        session_id: fallback_id.to_string(),
+        ..Default::default()
+    };
+    let mut models = BTreeSet::new();
+    let mut last_message_id: Option<String> = None;
+
+    for line in content.lines() {
+        let Ok(entry) = serde_json::from_str::<Value>(line) else {
+            continue;
+        };
+        let kind = entry
+            .get("type")
+            .and_then(|v| v.as_str())
+            .unwrap_or_default();
+        if kind == "summary" {
+            if let Some(summary) = str_at(&entry, "/summary") {
+                session.title = summary.to_string();
+            }
+            continue;
+        }
+        if (kind != "user" && kind != "assistant")
+            || entry.get("isSidechain").and_then(|v| v.as_bool()) == Some(true)
+            || entry.get("isMeta").and_then(|v| v.as_bool()) == Some(true)
+        {
+            continue;
+        }
+        if let Some(id) = str_at(&entry, "/sessionId") {
+            session.session_id = id.to_string();
+        }
+        if session.project.is_empty() {
+            session.project = str_at(&entry, "/cwd").unwrap_or_default().to_string();
+        }
+        let timestamp = str_at(&entry, "/timestamp");
+        let message = entry.get("message").unwrap_or(&Value::Null);
+
+        let mut parts = Vec::new();
+        let mut only_tool_results = true;
+        match message.get("content") {
+            Some(Value::String(text)) => {
+                only_tool_results = false;
+                parts.push(TranscriptPart::Text { text: text.clone() });
+            }
+            Some(Value::Array(blocks)) => {
+                for block in blocks {
+                    let block_type = str_at(block, "/type").unwrap_or_default();
+                    if block_type != "tool_result" {
+                        only_tool_results = false;
+                    }
+                    match block_type {
+                        "text" => {
+                            if let Some(text) = str_at(block, "/text") {
+                                parts.push(TranscriptPart::Text {
+                                    text: text.to_string(),
+                                });
+                            }
+                        }
+                        "thinking" => {
+                            if let Some(text) = str_at(block, "/thinking") {
+                                parts.push(TranscriptPart::Thinking {
+                                    text: text.to_string(),
+                                });
+                            }
+                        }
+                        "tool_use" => {
+                            let name = str_at(block, "/name").unwrap_or("tool");
+                            let input = block.get("input").cloned().unwrap_or(Value::Null);
+                            match claude_file_edits(name, &input) {
+                                Some(edits) => parts.extend(edits),
+                                None => parts.push(TranscriptPart::ToolCall {
+                                    call_id: str_at(block, "/id").map(String::from),
+                                    name: name.to_string(),
+                                    input,
+                                }),
+                            }
+                        }
+                        "tool_result" => parts.push(TranscriptPart::ToolResult {
+                            call_id: str_at(block, "/tool_use_id").map(String::from),
+                            output: claude_tool_output(
+                                block.get("content").unwrap_or(&Value::Null),
+                            ),
+                            is_error: block.get("is_error").and_then(|v| v.as_bool()) == Some(true),
+                        }),
+                        _ => {}
+                    }
+                }
+            }
+            _ => continue,
+        }
+
+        if kind == "assistant" {
+            let model = str_at(message, "/model").filter(|m| *m != "<synthetic>");
+            if let Some(model) = model {
+                models.insert(model.to_string());
+            }
+            let message_id = str_at(message, "/id").map(String::from);
+            let merge = message_id.is_some() && message_id == last_message_id;
+            last_message_id = message_id;
+            push_parts(
+                &mut session.messages,
+                "assistant",
+                timestamp,
+                model,
+                parts,
+                merge,
+            );
+        } else {
+            last_message_id = None;
+            let role = if only_tool_results { "tool" } else { "user" };
+            push_parts(
+                &mut session.messages,
+                role,
+                timestamp,
+                None,
+                parts,
+                role == "tool",
+            );
+        }
+    }
+
+    finish(session, models)
+}
+
+/// 拆分 Codex apply_patch 补丁，每个文件一个编辑块
+fn codex_patch_edits(patch: &str) -> Vec<TranscriptPart> {
+    let mut edits = Vec::new();
+    let mut current: Option<(String, Vec<&str>)> = None;
+    for line in patch.lines() {
+        let header = ["*** Add File: ", "*** Update File: ", "*** Delete File: "]
+            .iter()
+            .find_map(|prefix| line.strip_prefix(prefix));
+        if header.is_some() || line == "*** End Patch" {
+            if let Some((path, lines)) = current.take() {
+                edits.push(TranscriptPart::FileEdit {
+                    path,
+                    tool: "apply_patch".to_string(),
+                    old_text: None,
+                    new_text: None,
+                    diff: Some(lines.join("\n")),
+                });
+            }
+        }
+        match (header, current.as_mut()) {
+            (Some(path), _) => current = Some((path.trim().to_string(), vec![line])),
+            (None, Some((_, lines))) => lines.push(line),
+            (None, None) => {}
+        }
+    }
+    if let Some((path, lines)) = current {
+        edits.push(TranscriptPart::FileEdit {
+            path,
+            tool: "apply_patch".to_string(),
+            old_text: None,
+            new_text: None,
+            diff: Some(lines.join("\n")),
+        });
+    }
+    edits
+}
+
+/// Codex 注入的环境/指令消息，不是用户输入
+fn is_codex_context_message(text: &str) -> bool {
+    let text = text.trim_start();
+    text.starts_with("<environment_context>")
+        || text.starts_with("<user_instructions>")
+        || text.starts_with("# AGENTS.md instructions")
+}
+
+/// Codex 工具输出可能是纯文本，也可能是 `{"output": ..., "metadata": ...}` 的 JSON 字符串
+fn codex_tool_output(output: &Value) -> String {
+    let text = match output {
+        Value::String(text) => text.clone(),
+        Value::Null => return String::new(),
+        other => other.to_string(),
+    };
+    serde_json::from_str::<Value>(&text)
+        .ok()
+        .and_then(|v| v.get("output").and_then(|o| o.as_str()).map(String::from))
+        .unwrap_or(text)
+}
+
+/// Codex 会话（`~/.codex/sessions/YYYY/MM/DD/rollout-*.jsonl`），只读取 response_item，
+/// event_msg 中的消息与其重复
+pub fn parse_codex_session(content: &str, fallback_id: &str) -> ParsedSession {
+    let mut session = ParsedSession {
+        session_id: fallback_id.to_string(),
+        ..Default::default()
+    };
+    let mut models = BTreeSet::new();
+    let mut model: Option<String> = None;
+
+    for line in content.lines() {
+        let Ok(entry) = serde_json::from_str::<Value>(line) else {
+            continue;
+        };
+        let timestamp = str_at(&entry, "/timestamp");
+        let payload = entry.get("payload").unwrap_or(&Value::Null);
+        match entry.get("type").and_then(|v| v.as_str()) {
+            Some("session_meta") => {
+                if let Some(id) = str_at(payload, "/id") {
+                    session.session_id = id.to_string();
+                }
+                if let Some(cwd) = str_at(payload, "/cwd") {
+                    session.project = cwd.to_string();
+                }
+                if let Some(started) = str_at(payload, "/timestamp") {
+                    session.started_at = started.to_string();
+                }
+                continue;
+            }
+            Some("turn_context") => {
+                if let Some(m) = str_at(payload, "/model") {
+                    models.insert(m.to_string());
+                    model = Some(m.to_string());
+                }
+                continue;
+            }
+            Some("response_item") => {}
+            _ => continue,
+        }
+
+        let model = model.as_deref();
+        match str_at(payload, "/type").unwrap_or_default() {
+            "message" => {
+                let role = str_at(payload, "/role").unwrap_or_default();
+                if role != "user" && role != "assistant" {
+                    continue;
+                }
+                let parts: Vec<_> = payload
+                    .get("content")
+                    .and_then(|v| v.as_array())
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|c| str_at(c, "/text"))
+                    .filter(|text| role != "user" || !is_codex_context_message(text))
+                    .map(|text| TranscriptPart::Text {
+                        text: text.to_string(),
+                    })
+                    .collect();
+                let model = if role == "assistant" { model } else { None };
+                push_parts(
+                    &mut session.messages,
+                    role,
+                    timestamp,
+                    model,
+                    parts,
+                    role == "assistant",
+                );
+            }
+            "reasoning" => {
+                let parts = payload
+                    .get("summary")
+                    .and_then(|v| v.as_array())
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|s| str_at(s, "/text"))
+                    .map(|text| TranscriptPart::Thinking {
+                        text: text.to_string(),
+                    })
+                    .collect();
+                push_parts(
+                    &mut session.messages,
+                    "assistant",
+                    timestamp,
+                    model,
+                    parts,
+                    true,
+                );
+            }
+            "function_call" | "custom_tool_call" | "local_shell_call" => {
+                let name = str_at(payload, "/name").unwrap_or("shell");
+                let input = match payload.get("arguments").or_else(|| payload.get("input")) {
+                    Some(Value::String(raw)) => {
+                        serde_json::from_str(raw).unwrap_or_else(|_| Value::String(raw.clone()))
+                    }
+                    Some(other) => other.clone(),
+                    None => payload.pointer("/action").cloned().unwrap_or(Value::Null),
+                };
+                // apply_patch 既可能是独立工具，也可能是 shell 命令 ["apply_patch", patch]
+                let patch = match (name, &input) {
+                    ("apply_patch", Value::String(patch)) => Some(patch.as_str()),
+                    _ => input
+                        .pointer("/command")
+                        .and_then(|c| c.as_array())
+                        .filter(|c| c.first().and_then(|v| v.as_str()) == Some("apply_patch"))
+                        .and_then(|c| c.get(1))
+                        .and_then(|v| v.as_str()),
+                };
+                let parts = match patch {
+                    Some(patch) => codex_patch_edits(patch),
+                    None => vec![TranscriptPart::ToolCall {
+                        call_id: str_at(payload, "/call_id").map(String::from),
+                        name: name.to_string(),
+                        input,
+                    }],
+                };
+                push_parts(
+                    &mut session.messages,
+                    "assistant",
+                    timestamp,
+                    model,
+                    parts,
+                    true,
+                );
+            }
+            "function_call_output" | "custom_tool_call_output" => {
+                let parts = vec![TranscriptPart::ToolResult {
+                    call_id: str_at(payload, "/call_id").map(String::from),
+                    output: codex_tool_output(payload.get("output").unwrap_or(&Value::Null)),
+                    is_error: false,
+                }];
+                push_parts(&mut session.messages, "tool", timestamp, None, parts, true);
+            }
+            _ => {}
+        }
+    }
+
+    finish(session, models)
+}
+
+/// OpenCode 的文件编辑工具
+fn opencode_file_edit(tool: &str, input: &Value) -> Option<TranscriptPart> {
+    if !matches!(tool, "edit" | "write" | "multiedit") {
+        return None;
+    }
+    Some(TranscriptPart::FileEdit {
+        path: str_at(input, "/filePath")?.to_string(),
+        tool: tool.to_string(),
+        old_text: str_at(input, "/oldString").map(String::from),
+        new_text: str_at(input, "/newString")
+            .or_else(|| str_at(input, "/content"))
+            .map(String::from),
+        diff: None,
+    })
+}
+
+/// OpenCode 会话
+///
+/// `info` 为 `storage/session/<project>/<session>.json`，`messages` 为
+/// `storage/message/<session>/*.json` 及其在 `storage/part/<message>/` 下的内容块（按 ID 排序）。
+pub fn parse_opencode_session(
+    info: Option<&Value>,
+    mut messages: Vec<(Value, Vec<Value>)>,
+    fallback_id: &str,
+) -> ParsedSession {
+    let mut session = ParsedSession {
+        session_id: fallback_id.to_string(),
+        ..Default::default()
+    };
+    if let Some(info) = info {
+        session.title = str_at(info, "/title").unwrap_or_default().to_string();
+        session.project = str_at(info, "/directory").unwrap_or_default().to_string();
+        if let Some(created) = info.pointer("/time/created").and_then(|v| v.as_i64()) {
+            session.started_at = millis_to_rfc3339(created).unwrap_or_default();
+        }
+    }
+    let mut models = BTreeSet::new();
+    messages.sort_by_key(|(m, _)| m.pointer("/time/created").and_then(|v| v.as_i64()));
+
+    for (message, parts) in messages {
+        let role = str_at(&message, "/role").unwrap_or("user");
+        let model = str_at(&message, "/modelID");
+        if let (Some(model), "assistant") = (model, role) {
+            models.insert(model.to_string());
+        }
+        if session.project.is_empty() {
+            session.project = str_at(&message, "/path/cwd")
+                .unwrap_or_default()
+                .to_string();
+        }
+        let timestamp = message
+            .pointer("/time/created")
+            .and_then(|v| v.as_i64())
+            .and_then(millis_to_rfc3339);
+
+        let mut normalized = Vec::new();
+        for part in &parts {
+            match str_at(part, "/type").unwrap_or_default() {
+                "text" if part.get("synthetic").and_then(|v| v.as_bool()) != Some(true) => {
+                    if let Some(text) = str_at(part, "/text") {
+                        normalized.push(TranscriptPart::Text {
+                            text: text.to_string(),
+                        });
+                    }
+                }
+                "reasoning" => {
+                    if let Some(text) = str_at(part, "/text") {
+                        normalized.push(TranscriptPart::Thinking {
+                            text: text.to_string(),
+                        });
+                    }
+                }
+                "tool" => {
+                    let tool = str_at(part, "/tool").unwrap_or("tool");
+                    let input = part.pointer("/state/input").cloned().unwrap_or(Value::Null);
+                    if let Some(edit) = opencode_file_edit(tool, &input) {
+                        normalized.push(edit);
+                        continue;
+                    }
+                    let call_id = str_at(part, "/callID").map(String::from);
+                    normalized.push(TranscriptPart::ToolCall {
+                        call_id: call_id.clone(),
+                        name: tool.to_string(),
+                        input,
+                    });
+                    let error = str_at(part, "/state/error");
+                    if let Some(output) = str_at(part, "/state/output").or(error) {
+                        normalized.push(TranscriptPart::ToolResult {
+                            call_id,
+                            output: output.to_string(),
+                            is_error: error.is_some(),
+                        });
+                    }
+                }
+                _ => {}
+            }
+        }
+        let model = if role == "assistant" { model } else { None };
+        push_parts(
+            &mut session.messages,
+            role,
+            timestamp.as_deref(),
+            model,
+            normalized,
+            false,
+        );
+    }
+
+    finish(session, models)
+}
+
+/// 用于全文索引的消息文本：正文、编辑过的文件路径和工具调用参数，不含工具输出
+pub fn searchable_text(message: &TranscriptMessage, max_chars: usize) -> String {
+    let mut pieces = Vec::new();
+    for part in &message.parts {
+        match part {
+            TranscriptPart::Text { text } => pieces.push(text.clone()),
+            TranscriptPart::FileEdit { path, .. } => pieces.push(path.clone()),
+            TranscriptPart::ToolCall { name, input, .. } => {
+                let input = match input {
+                    Value::String(s) => s.clone(),
+                    other => other.to_string(),
+                };
+                pieces.push(format!("{} {}", name, truncate_chars(&input, 500)));
+            }
+            TranscriptPart::Thinking { .. } | TranscriptPart::ToolResult { .. } => {}
+        }
+    }
+    truncate_chars(&pieces.join("\n"), max_chars)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn claude_session_merges_split_replies_and_extracts_edits() {
+        let content = [
+            r#"{"type":"summary","summary":"Fix migration"}"#,
+            r#"{"type":"user","sessionId":"s1","cwd":"/repo","timestamp":"2025-06-01T10:00:00Z","message":{"role":"user","content":"fix the migration"}}"#,
+            r#"{"type":"assistant","sessionId":"s1","timestamp":"2025-06-01T10:00:05Z","message":{"id":"m1","model":"claude-sonnet-4","content":[{"type":"text","text":"Looking"}]}}"#,
+            r#"{"type":"assistant","sessionId":"s1","timestamp":"2025-06-01T10:00:06Z","message":{"id":"m1","model":"claude-sonnet-4","content":[{"type":"tool_use","id":"t1","name":"Edit","input":{"file_path":"/repo/a.sql","old_string":"a","new_string":"b"}}]}}"#,
+            r#"{"type":"user","sessionId":"s1","timestamp":"2025-06-01T10:00:07Z","message":{"role":"user","content":[{"type":"tool_result","tool_use_id":"t1","content":"ok"}]}}"#,
+            r#"{"type":"user","isSidechain":true,"sessionId":"s1","message":{"role":"user","content":"ignored"}}"#,
+        ]
+        .join("\n");
+
+        let session = parse_claude_session(&content, "fallback");
+        assert_eq!(session.session_id, "s1");
+        assert_eq!(session.title, "Fix migration");
+        assert_eq!(session.project, "/repo");
+        assert_eq!(session.models, vec!["claude-sonnet-4".to_string()]);
+        assert_eq!(session.updated_at, "2025-06-01T10:00:07Z");
+        let roles: Vec<_> = session.messages.iter().map(|m| m.role.as_str()).collect();
+        assert_eq!(roles, vec!["user", "assistant", "tool"]);
+        assert!(matches!(
+            &session.messages[1].parts[1],
+            TranscriptPart::FileEdit { path, new_text: Some(new), .. } if path == "/repo/a.sql" && new == "b"
+        ));
+    }
+
+    #[test]
+    fn codex_session_skips_context_and_splits_patches() {
+        let patch = "*** Begin Patch\n*** Update File: src/a.rs\n@@\n-a\n+b\n*** Add File: src/b.rs\n+new\n*** End Patch";
+        let content = [
+            r#"{"timestamp":"2025-06-01T10:00:00Z","type":"session_meta","payload":{"id":"c1","cwd":"/repo","timestamp":"2025-06-01T10:00:00Z"}}"#.to_string(),
+            r#"{"timestamp":"2025-06-01T10:00:00Z","type":"turn_context","payload":{"model":"gpt-5"}}"#.to_string(),
+            r#"{"timestamp":"2025-06-01T10:00:01Z","type":"response_item","payload":{"type":"message","role":"user","content":[{"type":"input_text","text":"<environment_context>x</environment_context>"}]}}"#.to_string(),
+            r#"{"timestamp":"2025-06-01T10:00:02Z","type":"response_item","payload":{"type":"message","role":"user","content":[{"type":"input_text","text":"rename things"}]}}"#.to_string(),
+            serde_json::json!({"timestamp":"2025-06-01T10:00:03Z","type":"response_item","payload":{"type":"custom_tool_call","name":"apply_patch","call_id":"p1","input":patch}}).to_string(),
+            r#"{"timestamp":"2025-06-01T10:00:04Z","type":"response_item","payload":{"type":"function_call_output","call_id":"p1","output":"{\"output\":\"Done\"}"}}"#.to_string(),
+        ]
+        .join("\n");
+
+        let session = parse_codex_session(&content, "fallback");
+        assert_eq!(session.session_id, "c1");
+        assert_eq!(session.title, "rename things");
+        assert_eq!(session.messages.len(), 3);
+        let paths: Vec<_> = session.messages[1]
+            .parts
+            .iter()
+            .filter_map(|p| match p {
+                TranscriptPart::FileEdit { path, .. } => Some(path.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(paths, vec!["src/a.rs", "src/b.rs"]);
+        assert!(matches!(
+            &session.messages[2].parts[0],
+            TranscriptPart::ToolResult { output, .. } if output == "Done"
+        ));
+    }
+}
@@ -0,0 +1,115 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// 会话列表项（索引中的元数据）
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionSummary {
+    /// 索引记录 ID
+    pub id: String,
+    /// "claudecode" | "codex" | "opencode"
+    pub source: String,
+    /// 工具自身的会话 ID
+    pub session_id: String,
+    /// 日志文件（OpenCode 为消息目录）
+    pub path: String,
+    pub title: String,
+    /// 会话工作目录
+    pub project: String,
+    #[serde(default)]
+    pub models: Vec<String>,
+    /// RFC 3339
+    pub started_at: String,
+    /// RFC 3339
+    pub updated_at: String,
+    pub message_count: usize,
+    /// 全文搜索命中的片段，匹配词以 `**` 包裹
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub snippet: Option<String>,
+}
+
+/// 会话搜索条件，字段均可为空
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionSearchQuery {
+    /// 全文关键词
+    pub text: Option<String>,
+    /// 项目路径（包含匹配，不区分大小写）
+    pub project: Option<String>,
+    /// 模型（包含匹配，不区分大小写）
+    pub model: Option<String>,
+    /// 为空表示全部来源
+    #[serde(default)]
+    pub sources: Vec<String>,
+    /// 起始日期（含），YYYY-MM-DD，按会话最后更新时间
+    pub start_date: Option<String>,
+    /// 结束日期（含），YYYY-MM-DD
+    pub end_date: Option<String>,
+    pub limit: Option<usize>,
+}
+
+/// 归一化后的消息内容块
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum TranscriptPart {
+    Text {
+        text: String,
+    },
+    Thinking {
+        text: String,
+    },
+    #[serde(rename_all = "camelCase")]
+    ToolCall {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        call_id: Option<String>,
+        name: String,
+        input: Value,
+    },
+    #[serde(rename_all = "camelCase")]
+    ToolResult {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        call_id: Option<String>,
+        output: String,
+        #[serde(default)]
+        is_error: bool,
+    },
+    /// 编辑/写入文件的工具调用；`diff` 为 Codex 补丁片段，`oldText`/`newText` 为替换前后内容
+    #[serde(rename_all = "camelCase")]
+    FileEdit {
+        path: String,
+        tool: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        old_text: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        new_text: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        diff: Option<String>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TranscriptMessage {
+    /// "user" | "assistant"
+    pub role: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    pub parts: Vec<TranscriptPart>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionTranscript {
+    pub summary: SessionSummary,
+    pub messages: Vec<TranscriptMessage>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionIndexResult {
+    pub sessions_total: usize,
+    pub sessions_updated: usize,
+    pub sessions_removed: usize,
+}
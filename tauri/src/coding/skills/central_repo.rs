@@ -1,16 +1,49 @@
 use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
 
 use anyhow::{Context, Result};
 use tauri::Manager;
 
 const CENTRAL_DIR_NAME: &str = "skills";
 
+/// 无 AppHandle 时（命令行工具、后台同步）使用的应用数据目录
+static APP_DATA_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+/// Set the app data directory for headless path resolution (call once at startup)
+pub fn set_app_data_dir(dir: PathBuf) {
+    let _ = APP_DATA_DIR.set(dir);
+}
+
 /// Resolve the central repo path from settings or default to app_data_dir/skills
 pub async fn resolve_central_repo_path(
     app: &tauri::AppHandle,
     state: &crate::DbState,
 ) -> Result<PathBuf> {
-    // Try to get from settings first
+    if let Some(path) = central_repo_path_from_settings(state).await {
+        return Ok(path);
+    }
+
+    // Default to app data directory / skills
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .context("failed to resolve app data directory")?;
+    Ok(app_data_dir.join(CENTRAL_DIR_NAME))
+}
+
+/// Same as `resolve_central_repo_path`, using the directory set by `set_app_data_dir`
+pub async fn resolve_default_central_repo_path(state: &crate::DbState) -> Result<PathBuf> {
+    if let Some(path) = central_repo_path_from_settings(state).await {
+        return Ok(path);
+    }
+
+    let app_data_dir = APP_DATA_DIR
+        .get()
+        .context("app data directory is not initialized")?;
+    Ok(app_data_dir.join(CENTRAL_DIR_NAME))
+}
+
+async fn central_repo_path_from_settings(state: &crate::DbState) -> Option<PathBuf> {
     let settings_result: std::result::Result<Option<PathBuf>, String> = async {
         let db = state.db();
         let mut result = db
@@ -31,16 +64,7 @@ pub async fn resolve_central_repo_path(
     }
     .await;
 
-    if let Ok(Some(path)) = settings_result {
-        return Ok(path);
-    }
-
-    // Default to app data directory / skills
-    let app_data_dir = app
-        .path()
-        .app_data_dir()
-        .context("failed to resolve app data directory")?;
-    Ok(app_data_dir.join(CENTRAL_DIR_NAME))
+    settings_result.ok().flatten()
 }
 
 /// Ensure the central repo directory exists
@@ -806,19 +806,27 @@ pub async fn skills_resync_all(
     app: tauri::AppHandle,
     state: State<'_, DbState>,
 ) -> Result<Vec<String>, String> {
-    let custom_tools = skill_store::get_custom_tools(&state)
-        .await
-        .unwrap_or_default();
-    let skills = skill_store::get_managed_skills(&state).await?;
     let central_dir = resolve_central_repo_path(&app, &state)
         .await
         .map_err(|e| format_error(e))?;
+    resync_all_skills(&state, &central_dir).await
+}
+
+/// Re-sync all managed skills from the central repo, without an app handle (used by the CLI)
+pub async fn resync_all_skills(
+    state: &DbState,
+    central_dir: &std::path::Path,
+) -> Result<Vec<String>, String> {
+    let custom_tools = skill_store::get_custom_tools(state)
+        .await
+        .unwrap_or_default();
+    let skills = skill_store::get_managed_skills(state).await?;
 
     let mut synced: Vec<String> = Vec::new();
 
     for skill in skills {
         // Resolve central_path (handles cross-platform legacy paths)
-        let central_path = resolve_skill_central_path(&skill.central_path, &central_dir);
+        let central_path = resolve_skill_central_path(&skill.central_path, central_dir);
         if !central_path.exists() {
             continue;
         }
@@ -858,7 +866,7 @@ pub async fn skills_resync_all(
                     error_message: None,
                     synced_at: Some(now_ms()),
                 };
-                let _ = skill_store::upsert_skill_target(&state, &skill.id, &record).await;
+                let _ = skill_store::upsert_skill_target(state, &skill.id, &record).await;
                 synced.push(format!("{}:{}", skill.name, tool_key));
             }
         }
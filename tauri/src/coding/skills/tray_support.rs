@@ -151,14 +151,27 @@ pub async fn apply_skills_tool_toggle<R: Runtime>(
     tool_key: &str,
 ) -> Result<(), String> {
     let state = app.state::<DbState>();
+    toggle_skill_tool(&state, skill_id, tool_key).await?;
 
+    // Notify frontend to refresh skills data
+    let _ = app.emit("skills-changed", "tray");
+
+    Ok(())
+}
+
+/// Sync a skill to a tool if it is not synced yet, otherwise remove it from the tool
+pub async fn toggle_skill_tool(
+    state: &DbState,
+    skill_id: &str,
+    tool_key: &str,
+) -> Result<(), String> {
     // Get custom tools for adapter lookup
-    let custom_tools = skill_store::get_custom_tools(&state)
+    let custom_tools = skill_store::get_custom_tools(state)
         .await
         .unwrap_or_default();
 
     // Get skill by ID
-    let skill = skill_store::get_skill_by_id(&state, skill_id)
+    let skill = skill_store::get_skill_by_id(state, skill_id)
         .await?
         .ok_or_else(|| format!("Skill not found: {}", skill_id))?;
 
@@ -172,14 +185,14 @@ pub async fn apply_skills_tool_toggle<R: Runtime>(
     }
 
     // Check current sync state
-    let existing_target = skill_store::get_skill_target(&state, skill_id, tool_key).await?;
+    let existing_target = skill_store::get_skill_target(state, skill_id, tool_key).await?;
 
     if existing_target.is_some() {
         // Currently synced -> unsync
         if let Some(target) = existing_target {
             // Remove the link/copy in tool directory
             remove_path(&target.target_path)?;
-            skill_store::delete_skill_target(&state, skill_id, tool_key).await?;
+            skill_store::delete_skill_target(state, skill_id, tool_key).await?;
         }
     } else {
         // Currently not synced -> sync
@@ -206,11 +219,8 @@ pub async fn apply_skills_tool_toggle<R: Runtime>(
             error_message: None,
             synced_at: Some(now_ms()),
         };
-        skill_store::upsert_skill_target(&state, skill_id, &record).await?;
+        skill_store::upsert_skill_target(state, skill_id, &record).await?;
     }
 
-    // Notify frontend to refresh skills data
-    let _ = app.emit("skills-changed", "tray");
-
     Ok(())
 }
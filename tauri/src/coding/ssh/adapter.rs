@@ -1,8 +1,10 @@
 use super::super::db_id;
-use super::types::{SSHConnection, SSHFileMapping, SSHSyncConfig};
+use super::types::{SSHConnection, SSHFileMapping, SSHHostSyncStatus, SSHJumpHost, SSHSyncConfig};
 use chrono::Local;
 use serde_json::{json, Value};
 
+use crate::secret_vault;
+
 // ============================================================================
 // SSH Sync Config Adapter Functions
 // ============================================================================
@@ -24,9 +26,24 @@ pub fn config_from_db_value(
             .and_then(|v| v.as_str())
             .unwrap_or("")
             .to_string(),
+        target_connection_ids: value
+            .get("target_connection_ids")
+            .or_else(|| value.get("targetConnectionIds"))
+            .and_then(|v| v.as_array())
+            .map(|ids| {
+                ids.iter()
+                    .filter_map(|id| id.as_str().map(String::from))
+                    .collect()
+            })
+            .unwrap_or_default(),
         // sync_mcp and sync_skills are always true (no UI to toggle them)
         sync_mcp: true,
         sync_skills: true,
+        auto_sync_on_change: value
+            .get("auto_sync_on_change")
+            .or_else(|| value.get("autoSyncOnChange"))
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false),
         file_mappings,
         connections,
         last_sync_time: value
@@ -45,17 +62,61 @@ pub fn config_from_db_value(
             .or_else(|| value.get("lastSyncError"))
             .and_then(|v| v.as_str())
             .map(String::from),
+        host_status: value
+            .get("host_status")
+            .or_else(|| value.get("hostStatus"))
+            .and_then(|v| v.as_array())
+            .map(|items| items.iter().map(host_status_from_db_value).collect())
+            .unwrap_or_default(),
+    }
+}
+
+/// Convert a per-host status entry in the config record to SSHHostSyncStatus
+fn host_status_from_db_value(value: &Value) -> SSHHostSyncStatus {
+    let get_opt = |snake: &str, camel: &str| {
+        value
+            .get(snake)
+            .or_else(|| value.get(camel))
+            .and_then(|v| v.as_str())
+            .map(String::from)
+    };
+
+    SSHHostSyncStatus {
+        connection_id: get_opt("connection_id", "connectionId").unwrap_or_default(),
+        connection_name: get_opt("connection_name", "connectionName").unwrap_or_default(),
+        last_sync_time: get_opt("last_sync_time", "lastSyncTime"),
+        last_sync_status: get_opt("last_sync_status", "lastSyncStatus")
+            .unwrap_or_else(|| "never".to_string()),
+        last_sync_error: get_opt("last_sync_error", "lastSyncError"),
     }
 }
 
+/// Convert SSHHostSyncStatus to database Value
+pub fn host_status_to_db_value(status: &SSHHostSyncStatus) -> Value {
+    json!({
+        "connection_id": status.connection_id,
+        "connection_name": status.connection_name,
+        "last_sync_time": status.last_sync_time,
+        "last_sync_status": status.last_sync_status,
+        "last_sync_error": status.last_sync_error,
+    })
+}
+
 /// Convert SSHSyncConfig to database Value
 pub fn config_to_db_value(config: &SSHSyncConfig) -> Value {
     json!({
         "enabled": config.enabled,
         "active_connection_id": config.active_connection_id,
+        "target_connection_ids": config.target_connection_ids,
+        "auto_sync_on_change": config.auto_sync_on_change,
         "last_sync_time": config.last_sync_time,
         "last_sync_status": config.last_sync_status,
         "last_sync_error": config.last_sync_error,
+        "host_status": config
+            .host_status
+            .iter()
+            .map(host_status_to_db_value)
+            .collect::<Vec<_>>(),
     })
 }
 
@@ -118,22 +179,84 @@ pub fn connection_from_db_value(value: Value) -> SSHConnection {
             .or_else(|| value.get("sortOrder"))
             .and_then(|v| v.as_u64())
             .unwrap_or(0) as u32,
+        jump_hosts: value
+            .get("jump_hosts")
+            .or_else(|| value.get("jumpHosts"))
+            .and_then(|v| v.as_array())
+            .map(|items| items.iter().map(jump_host_from_db_value).collect())
+            .unwrap_or_default(),
+    }
+}
+
+/// Convert a jump host entry in the connection record to SSHJumpHost
+fn jump_host_from_db_value(value: &Value) -> SSHJumpHost {
+    let get_str = |snake: &str, camel: &str| {
+        value
+            .get(snake)
+            .or_else(|| value.get(camel))
+            .and_then(|v| v.as_str())
+            .unwrap_or("")
+            .to_string()
+    };
+
+    SSHJumpHost {
+        host: get_str("host", "host"),
+        port: value.get("port").and_then(|v| v.as_u64()).unwrap_or(22) as u16,
+        username: get_str("username", "username"),
+        auth_method: value
+            .get("auth_method")
+            .or_else(|| value.get("authMethod"))
+            .and_then(|v| v.as_str())
+            .unwrap_or("agent")
+            .to_string(),
+        password: get_str("password", "password"),
+        private_key_path: get_str("private_key_path", "privateKeyPath"),
+        private_key_content: get_str("private_key_content", "privateKeyContent"),
+        passphrase: get_str("passphrase", "passphrase"),
     }
 }
 
+/// Convert SSHJumpHost to database Value
+fn jump_host_to_db_value(conn_name: &str, jump: &SSHJumpHost) -> Value {
+    let label = format!("ssh_connection/{}/jump/{}", conn_name, jump.host);
+    json!({
+        "host": jump.host,
+        "port": jump.port,
+        "username": jump.username,
+        "auth_method": jump.auth_method,
+        "password": secret_vault::seal(&format!("{}/password", label), &jump.password),
+        "private_key_path": jump.private_key_path,
+        "private_key_content": secret_vault::seal(
+            &format!("{}/private_key_content", label),
+            &jump.private_key_content,
+        ),
+        "passphrase": secret_vault::seal(&format!("{}/passphrase", label), &jump.passphrase),
+    })
+}
+
 /// Convert SSHConnection to database Value
+/// 密码、私钥内容与私钥口令存入密钥保险库，只保存引用
 pub fn connection_to_db_value(conn: &SSHConnection) -> Value {
+    let label = format!("ssh_connection/{}", conn.name);
     json!({
         "name": conn.name,
         "host": conn.host,
         "port": conn.port,
         "username": conn.username,
         "auth_method": conn.auth_method,
-        "password": conn.password,
+        "password": secret_vault::seal(&format!("{}/password", label), &conn.password),
         "private_key_path": conn.private_key_path,
-        "private_key_content": conn.private_key_content,
-        "passphrase": conn.passphrase,
+        "private_key_content": secret_vault::seal(
+            &format!("{}/private_key_content", label),
+            &conn.private_key_content,
+        ),
+        "passphrase": secret_vault::seal(&format!("{}/passphrase", label), &conn.passphrase),
         "sort_order": conn.sort_order,
+        "jump_hosts": conn
+            .jump_hosts
+            .iter()
+            .map(|jump| jump_host_to_db_value(&conn.name, jump))
+            .collect::<Vec<_>>(),
         "updated_at": Local::now().to_rfc3339(),
     })
 }
@@ -184,6 +307,12 @@ pub fn mapping_from_db_value(value: Value) -> SSHFileMapping {
             .or_else(|| value.get("isDirectory"))
             .and_then(|v| v.as_bool())
             .unwrap_or(false),
+        direction: value
+            .get("direction")
+            .and_then(|v| v.as_str())
+            .filter(|v| matches!(*v, "push" | "pull" | "both"))
+            .unwrap_or("push")
+            .to_string(),
     }
 }
 
@@ -197,6 +326,7 @@ pub fn mapping_to_db_value(mapping: &SSHFileMapping) -> Value {
         "enabled": mapping.enabled,
         "is_pattern": mapping.is_pattern,
         "is_directory": mapping.is_directory,
+        "direction": mapping.direction,
         "updated_at": Local::now().to_rfc3339(),
     })
 }
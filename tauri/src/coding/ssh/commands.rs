@@ -1,14 +1,16 @@
-use super::key_file;
 use super::types::{
-    SSHConnection, SSHConnectionResult, SSHFileMapping, SSHStatusResult, SSHSyncConfig,
+    HostSyncResult, SSHConfigHost, SSHConnection, SSHConnectionResult, SSHFileMapping,
+    SSHHostSyncStatus, SSHKnownHost, SSHStatusResult, SSHSyncConfig, SyncConflict, SyncPreview,
     SyncProgress, SyncResult,
 };
 use super::{adapter, session::SshSession, session::SshSessionState, sync};
+use super::{key_file, known_hosts, ssh_config};
 use crate::coding::db_id::db_record_id;
 use crate::coding::{oh_my_opencode, oh_my_opencode_slim, open_code};
 use crate::db::DbState;
 use chrono::Local;
-use tauri::Emitter;
+use futures_util::future::join_all;
+use tauri::{Emitter, Manager};
 
 // ============================================================================
 // 内部共享函数
@@ -169,7 +171,7 @@ pub async fn ssh_save_config(
 
         if session.try_acquire_sync_lock() {
             let _ = session.ensure_connected().await;
-            let result = do_full_sync(&state, &app, &session, &config, None, None).await;
+            let result = do_full_sync(&state, Some(&app), &session, &config, None, None).await;
             session.release_sync_lock();
 
             if !result.errors.is_empty() {
@@ -270,10 +272,21 @@ pub async fn ssh_delete_connection(
 
     // 如果删除的是当前活跃连接，清除 active_connection_id
     db.query("UPDATE ssh_sync_config SET active_connection_id = '' WHERE id = ssh_sync_config:`config` AND active_connection_id = $id")
-        .bind(("id", id))
+        .bind(("id", id.clone()))
         .await
         .map_err(|e| format!("Failed to clear active connection: {}", e))?;
 
+    // 从同步目标中移除
+    db.query("UPDATE ssh_sync_config SET target_connection_ids -= $id WHERE id = ssh_sync_config:`config`")
+        .bind(("id", id.clone()))
+        .await
+        .map_err(|e| format!("Failed to remove sync target: {}", e))?;
+
+    // 清除该连接及其跳板记录的主机密钥
+    if let Err(e) = known_hosts::forget_connection_known_hosts(&id) {
+        log::warn!("Failed to forget SSH host key: {}", e);
+    }
+
     let _ = app.emit("ssh-config-changed", ());
     Ok(())
 }
@@ -300,7 +313,7 @@ pub async fn ssh_set_active_connection(
         if let Some(conn) = config.connections.iter().find(|c| c.id == connection_id) {
             let mut session = session_state.0.lock().await;
             if session.connect(conn).await.is_ok() && session.try_acquire_sync_lock() {
-                let result = do_full_sync(&state, &app, &session, &config, None, None).await;
+                let result = do_full_sync(&state, Some(&app), &session, &config, None, None).await;
                 session.release_sync_lock();
                 let _ = update_sync_status(state.inner(), &result).await;
                 let _ = app.emit("ssh-sync-completed", result);
@@ -320,6 +333,77 @@ pub async fn ssh_test_connection(mut connection: SSHConnection) -> SSHConnection
     sync::test_connection(&connection).await
 }
 
+/// Get the host key recorded for a connection (first-seen fingerprint)
+#[tauri::command]
+pub fn ssh_get_host_key(connection_id: String) -> Option<SSHKnownHost> {
+    known_hosts::get_known_host(&connection_id)
+}
+
+/// Forget the recorded host key of a connection, the next connect records it again
+#[tauri::command]
+pub fn ssh_forget_host_key(connection_id: String) -> Result<(), String> {
+    known_hosts::forget_known_host(&connection_id)?;
+    log::info!("Forgot SSH host key for connection {}", connection_id);
+    Ok(())
+}
+
+/// List host aliases from ~/.ssh/config that can be imported as connection presets
+#[tauri::command]
+pub async fn ssh_list_config_hosts(
+    state: tauri::State<'_, DbState>,
+) -> Result<Vec<SSHConfigHost>, String> {
+    let existing = ssh_list_connections(state).await?;
+    let config = ssh_config::SshConfig::load_user_config();
+
+    Ok(config
+        .host_aliases()
+        .iter()
+        .map(|alias| {
+            let imported = existing.iter().any(|c| &c.name == alias);
+            config.resolve(alias).to_config_host(imported)
+        })
+        .collect())
+}
+
+/// Create connection presets from ~/.ssh/config host aliases (existing names are skipped)
+#[tauri::command]
+pub async fn ssh_import_config_hosts(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+    aliases: Vec<String>,
+) -> Result<Vec<SSHConnection>, String> {
+    let existing = ssh_list_connections(state.clone()).await?;
+    let config = ssh_config::SshConfig::load_user_config();
+    let db = state.db();
+
+    let base_id = Local::now().timestamp_millis();
+    let mut created = Vec::new();
+    for alias in aliases {
+        if existing.iter().any(|c| c.name == alias)
+            || created.iter().any(|c: &SSHConnection| c.name == alias)
+        {
+            continue;
+        }
+
+        let id = format!("ssh-{}", base_id + created.len() as i64);
+        let sort_order = (existing.len() + created.len()) as u32;
+        let connection = ssh_config::connection_from_alias(&config, &alias, id, sort_order);
+
+        let conn_data = adapter::connection_to_db_value(&connection);
+        let record_id = db_record_id("ssh_connection", &connection.id);
+        db.query(format!("UPSERT {} CONTENT $data", record_id))
+            .bind(("data", conn_data))
+            .await
+            .map_err(|e| format!("Failed to import SSH connection {}: {}", alias, e))?;
+        created.push(connection);
+    }
+
+    if !created.is_empty() {
+        let _ = app.emit("ssh-config-changed", ());
+    }
+    Ok(created)
+}
+
 // ============================================================================
 // File Mapping Commands
 // ============================================================================
@@ -403,9 +487,13 @@ pub async fn ssh_reset_file_mappings(
 // ============================================================================
 
 /// Internal full sync implementation
+///
+/// 活动连接使用主连接 `session`，其它目标连接各自建立临时连接，与活动连接并发同步。
+/// 其它目标只接收推送：pull/both 映射只与活动连接双向同步，对其它目标按 push 处理，
+/// 因此冲突只会出现在活动连接上。
 pub async fn do_full_sync(
     state: &DbState,
-    app: &tauri::AppHandle,
+    app: Option<&tauri::AppHandle>,
     session: &SshSession,
     config: &SSHSyncConfig,
     module: Option<&str>,
@@ -414,34 +502,124 @@ pub async fn do_full_sync(
     // Emit initial progress
     let enabled_mappings: Vec<_> = config.file_mappings.iter().filter(|m| m.enabled).collect();
     let total_files = enabled_mappings.len() as u32;
-    let _ = app.emit(
-        "ssh-sync-progress",
-        SyncProgress {
-            phase: "files".to_string(),
-            current_item: "准备中...".to_string(),
-            current: 0,
-            total: total_files,
-            message: format!("文件同步: 0/{}", total_files),
-        },
-    );
+    if let Some(app) = app {
+        let _ = app.emit(
+            "ssh-sync-progress",
+            SyncProgress {
+                phase: "files".to_string(),
+                current_item: "准备中...".to_string(),
+                current: 0,
+                total: total_files,
+                message: format!("文件同步: 0/{}", total_files),
+            },
+        );
+    }
 
     // Resolve dynamic config paths
     let file_mappings = resolve_dynamic_paths(config.file_mappings.clone());
+    let push_mappings: Vec<_> = file_mappings
+        .iter()
+        .cloned()
+        .map(|mut m| {
+            m.direction = "push".to_string();
+            m
+        })
+        .collect();
+
+    let primary_id = session.conn().map(|c| c.id.clone()).unwrap_or_default();
+    let primary_name = session.conn().map(|c| c.name.clone()).unwrap_or_default();
+
+    let primary = async {
+        let result = if session.is_alive() {
+            sync_host(
+                state,
+                app,
+                session,
+                config,
+                &file_mappings,
+                module,
+                skip_modules,
+                true,
+            )
+            .await
+        } else {
+            host_error_result("SSH 连接不可用".to_string())
+        };
+        HostSyncResult {
+            connection_id: primary_id.clone(),
+            connection_name: primary_name,
+            result,
+        }
+    };
+
+    let push_mappings = &push_mappings;
+    let others = config
+        .sync_targets()
+        .into_iter()
+        .filter(|conn| conn.id != primary_id)
+        .map(|conn| async move {
+            let mut host_session = SshSession::new();
+            let result = match host_session.connect(conn).await {
+                Ok(()) => {
+                    sync_host(
+                        state,
+                        app,
+                        &host_session,
+                        config,
+                        push_mappings,
+                        module,
+                        skip_modules,
+                        false,
+                    )
+                    .await
+                }
+                Err(e) => host_error_result(e),
+            };
+            host_session.disconnect().await;
+            HostSyncResult {
+                connection_id: conn.id.clone(),
+                connection_name: conn.name.clone(),
+                result,
+            }
+        });
+
+    let (primary, others) = tokio::join!(primary, join_all(others));
+
+    let mut hosts = vec![primary];
+    hosts.extend(others);
+    aggregate_host_results(hosts)
+}
+
+/// Sync file mappings, MCP and Skills to a single connected host
+#[allow(clippy::too_many_arguments)]
+async fn sync_host(
+    state: &DbState,
+    app: Option<&tauri::AppHandle>,
+    session: &SshSession,
+    config: &SSHSyncConfig,
+    file_mappings: &[SSHFileMapping],
+    module: Option<&str>,
+    skip_modules: Option<&[String]>,
+    emit_progress: bool,
+) -> SyncResult {
+    // 只有活动连接上报文件进度，避免多个目标的进度互相覆盖
+    let progress_app = if emit_progress { app } else { None };
 
     // Sync file mappings with progress
     let mut result =
-        sync_mappings_with_progress(&file_mappings, session, module, skip_modules, app).await;
+        sync_mappings_with_progress(file_mappings, session, module, skip_modules, progress_app)
+            .await;
 
     // Also sync MCP and Skills
     if config.sync_mcp {
-        if let Err(e) = super::mcp_sync::sync_mcp_to_ssh(state, session, app.clone()).await {
+        if let Err(e) = super::mcp_sync::sync_mcp_to_ssh(state, session, app).await {
             log::warn!("MCP SSH sync failed: {}", e);
             result.errors.push(format!("MCP sync: {}", e));
             result.success = false;
         }
     }
     if config.sync_skills {
-        if let Err(e) = super::skills_sync::sync_skills_to_ssh(state, session, app.clone()).await {
+        if let Err(e) = super::skills_sync::sync_skills_to_ssh(state, session, app).await {
             log::warn!("Skills SSH sync failed: {}", e);
             result.errors.push(format!("Skills sync: {}", e));
             result.success = false;
@@ -449,7 +627,7 @@ pub async fn do_full_sync(
     }
 
     // Ensure OpenClaw config exists on remote (create empty {} if missing)
-    let skip_openclaw = skip_modules.map_or(false, |s| s.iter().any(|m| m == "openclaw"));
+    let skip_openclaw = skip_modules.is_some_and(|s| s.iter().any(|m| m == "openclaw"));
     if !skip_openclaw && (module.is_none() || module == Some("openclaw")) {
         if let Err(e) = ensure_openclaw_config_on_remote(session).await {
             log::warn!("OpenClaw SSH config init failed: {}", e);
@@ -459,17 +637,105 @@ pub async fn do_full_sync(
     result
 }
 
+fn host_error_result(error: String) -> SyncResult {
+    SyncResult {
+        success: false,
+        synced_files: vec![],
+        skipped_files: vec![],
+        errors: vec![error],
+        conflicts: vec![],
+        hosts: vec![],
+    }
+}
+
+/// 汇总各目标连接的结果；多个目标时文件和错误前加上连接名
+fn aggregate_host_results(hosts: Vec<HostSyncResult>) -> SyncResult {
+    let multi_host = hosts.len() > 1;
+    let label = |host: &HostSyncResult, item: &str| {
+        if multi_host {
+            format!("[{}] {}", host.connection_name, item)
+        } else {
+            item.to_string()
+        }
+    };
+
+    let mut aggregated = SyncResult {
+        success: hosts.iter().all(|h| h.result.success),
+        synced_files: vec![],
+        skipped_files: vec![],
+        errors: vec![],
+        conflicts: vec![],
+        hosts: vec![],
+    };
+    for host in &hosts {
+        let result = &host.result;
+        aggregated
+            .synced_files
+            .extend(result.synced_files.iter().map(|f| label(host, f)));
+        aggregated
+            .skipped_files
+            .extend(result.skipped_files.iter().map(|f| label(host, f)));
+        aggregated
+            .errors
+            .extend(result.errors.iter().map(|e| label(host, e)));
+        aggregated
+            .conflicts
+            .extend(result.conflicts.iter().cloned());
+    }
+    aggregated.hosts = hosts;
+    aggregated
+}
+
+/// 对活动连接以外的目标连接执行 MCP 或 Skills 同步（scope: "mcp" | "skills"）
+///
+/// 活动连接由调用方通过主连接同步；其它目标各自建立临时连接并发执行
+pub async fn sync_other_targets(state: &DbState, app: &tauri::AppHandle, scope: &str) {
+    let config = {
+        let db = state.db();
+        match get_ssh_config_internal(&db, false).await {
+            Ok(c) => c,
+            Err(_) => return,
+        }
+    };
+    if !config.enabled {
+        return;
+    }
+
+    let tasks = config
+        .sync_targets()
+        .into_iter()
+        .filter(|conn| conn.id != config.active_connection_id)
+        .map(|conn| async move {
+            let mut session = SshSession::new();
+            if let Err(e) = session.connect(conn).await {
+                log::warn!("SSH {} sync to {} skipped: {}", scope, conn.name, e);
+                return;
+            }
+            let result = if scope == "mcp" {
+                super::mcp_sync::sync_mcp_to_ssh(state, &session, Some(app)).await
+            } else {
+                super::skills_sync::sync_skills_to_ssh(state, &session, Some(app)).await
+            };
+            if let Err(e) = result {
+                log::warn!("SSH {} sync to {} failed: {}", scope, conn.name, e);
+            }
+            session.disconnect().await;
+        });
+    join_all(tasks).await;
+}
+
 /// Sync file mappings with progress events
 async fn sync_mappings_with_progress(
     mappings: &[SSHFileMapping],
     session: &SshSession,
     module_filter: Option<&str>,
     skip_modules: Option<&[String]>,
-    app: &tauri::AppHandle,
+    app: Option<&tauri::AppHandle>,
 ) -> SyncResult {
     let mut synced_files = vec![];
     let mut skipped_files = vec![];
     let mut errors = vec![];
+    let mut conflicts = vec![];
 
     let filtered_mappings: Vec<_> = mappings
         .iter()
@@ -483,23 +749,26 @@ async fn sync_mappings_with_progress(
     for (idx, mapping) in filtered_mappings.iter().enumerate() {
         let current = (idx + 1) as u32;
 
-        let _ = app.emit(
-            "ssh-sync-progress",
-            SyncProgress {
-                phase: "files".to_string(),
-                current_item: mapping.name.clone(),
-                current,
-                total,
-                message: format!("文件同步: {}/{} - {}", current, total, mapping.name),
-            },
-        );
+        if let Some(app) = app {
+            let _ = app.emit(
+                "ssh-sync-progress",
+                SyncProgress {
+                    phase: "files".to_string(),
+                    current_item: mapping.name.clone(),
+                    current,
+                    total,
+                    message: format!("文件同步: {}/{} - {}", current, total, mapping.name),
+                },
+            );
+        }
 
         match sync::sync_file_mapping(mapping, session).await {
-            Ok(files) if files.is_empty() => {
+            Ok(outcome) if outcome.synced.is_empty() && outcome.conflicts.is_empty() => {
                 skipped_files.push(mapping.name.clone());
             }
-            Ok(files) => {
-                synced_files.extend(files);
+            Ok(outcome) => {
+                synced_files.extend(outcome.synced);
+                conflicts.extend(outcome.conflicts);
             }
             Err(e) => {
                 errors.push(format!("{}: {}", mapping.name, e));
@@ -512,6 +781,8 @@ async fn sync_mappings_with_progress(
         synced_files,
         skipped_files,
         errors,
+        conflicts,
+        hosts: vec![],
     }
 }
 
@@ -532,9 +803,70 @@ pub async fn ssh_sync(
             synced_files: vec![],
             skipped_files: vec![],
             errors: vec!["SSH 同步未启用".to_string()],
+            conflicts: vec![],
+            hosts: vec![],
         });
     }
 
+    run_sync(
+        state.inner(),
+        session_state.inner(),
+        &app,
+        &config,
+        module.as_deref(),
+        skip_modules.as_deref(),
+    )
+    .await
+}
+
+/// Incrementally sync the given file mappings (triggered by the local file watcher)
+///
+/// MCP and Skills are not included; they have their own change events.
+pub async fn sync_changed_mappings(
+    app: &tauri::AppHandle,
+    mapping_ids: &[String],
+) -> Result<SyncResult, String> {
+    let state = app.state::<DbState>();
+    let config = ssh_get_config(state.clone()).await?;
+
+    if !config.enabled || config.active_connection_id.is_empty() {
+        return Err("SSH 同步未启用".to_string());
+    }
+
+    let file_mappings = config
+        .file_mappings
+        .iter()
+        .filter(|m| mapping_ids.contains(&m.id))
+        .cloned()
+        .collect();
+    let config = SSHSyncConfig {
+        file_mappings,
+        sync_mcp: false,
+        sync_skills: false,
+        ..config
+    };
+
+    let session_state = app.state::<SshSessionState>();
+    run_sync(
+        state.inner(),
+        session_state.inner(),
+        app,
+        &config,
+        None,
+        None,
+    )
+    .await
+}
+
+/// 通过主连接执行同步并记录状态（ssh_sync 与文件监听共用）
+async fn run_sync(
+    state: &DbState,
+    session_state: &SshSessionState,
+    app: &tauri::AppHandle,
+    config: &SSHSyncConfig,
+    module: Option<&str>,
+    skip_modules: Option<&[String]>,
+) -> Result<SyncResult, String> {
     let mut session = session_state.0.lock().await;
 
     // 并发控制：如果正在同步，直接返回
@@ -544,38 +876,104 @@ pub async fn ssh_sync(
             synced_files: vec![],
             skipped_files: vec![],
             errors: vec!["另一个同步操作正在进行中".to_string()],
+            conflicts: vec![],
+            hosts: vec![],
         });
     }
 
-    // 确保连接可用（自动重连）
+    // 确保连接可用（自动重连）；有其它目标时主连接失败不影响其它目标
     if let Err(e) = session.ensure_connected().await {
-        session.release_sync_lock();
-        return Ok(SyncResult {
-            success: false,
-            synced_files: vec![],
-            skipped_files: vec![],
-            errors: vec![format!("SSH 连接失败: {}", e)],
-        });
+        if config.sync_targets().len() <= 1 {
+            session.release_sync_lock();
+            return Ok(SyncResult {
+                success: false,
+                synced_files: vec![],
+                skipped_files: vec![],
+                errors: vec![format!("SSH 连接失败: {}", e)],
+                conflicts: vec![],
+                hosts: vec![],
+            });
+        }
+        log::warn!("SSH 主连接失败，继续同步其它目标: {}", e);
     }
 
-    let result = do_full_sync(
-        &state,
-        &app,
-        &session,
-        &config,
-        module.as_deref(),
-        skip_modules.as_deref(),
-    )
-    .await;
+    let result = do_full_sync(state, Some(app), &session, config, module, skip_modules).await;
 
     session.release_sync_lock();
 
-    update_sync_status(state.inner(), &result).await?;
+    update_sync_status(state, &result).await?;
     let _ = app.emit("ssh-sync-completed", result.clone());
 
     Ok(result)
 }
 
+/// Preview what `ssh_sync` would change without writing anything (dry-run)
+#[tauri::command]
+pub async fn ssh_sync_preview(
+    state: tauri::State<'_, DbState>,
+    session_state: tauri::State<'_, SshSessionState>,
+    app: tauri::AppHandle,
+    module: Option<String>,
+    skip_modules: Option<Vec<String>>,
+) -> Result<SyncPreview, String> {
+    let config = ssh_get_config(state.clone()).await?;
+
+    if !config.enabled || config.active_connection_id.is_empty() {
+        return Err("SSH 同步未启用".to_string());
+    }
+
+    let mut session = session_state.0.lock().await;
+    session
+        .ensure_connected()
+        .await
+        .map_err(|e| format!("SSH 连接失败: {}", e))?;
+
+    let file_mappings: Vec<_> = resolve_dynamic_paths(config.file_mappings.clone())
+        .into_iter()
+        .filter(|m| {
+            !skip_modules
+                .as_ref()
+                .is_some_and(|skip| skip.contains(&m.module))
+        })
+        .collect();
+    let mut preview = sync::preview_mappings(&file_mappings, &session, module.as_deref()).await;
+
+    if config.sync_mcp {
+        match super::mcp_sync::preview_mcp_to_ssh(&state, &session).await {
+            Ok(mcp) => preview.mcp = Some(mcp),
+            Err(e) => preview.errors.push(format!("MCP sync: {}", e)),
+        }
+    }
+    if config.sync_skills {
+        match super::skills_sync::preview_skills_to_ssh(&state, &session, &app).await {
+            Ok(skills) => preview.skills = Some(skills),
+            Err(e) => preview.errors.push(format!("Skills sync: {}", e)),
+        }
+    }
+
+    Ok(preview)
+}
+
+/// Resolve a two-way sync conflict by keeping one side ("local" | "remote")
+#[tauri::command]
+pub async fn ssh_resolve_conflict(
+    session_state: tauri::State<'_, SshSessionState>,
+    conflict: SyncConflict,
+    keep: String,
+) -> Result<(), String> {
+    let mut session = session_state.0.lock().await;
+    session.ensure_connected().await?;
+    sync::resolve_conflict(&session, &conflict, &keep).await?;
+
+    log::info!(
+        "Resolved SSH sync conflict {} <-> {} (kept {})",
+        conflict.local_path,
+        conflict.remote_path,
+        keep
+    );
+    Ok(())
+}
+
 /// Get SSH sync status
 #[tauri::command]
 pub async fn ssh_get_status(state: tauri::State<'_, DbState>) -> Result<SSHStatusResult, String> {
@@ -591,12 +989,24 @@ pub async fn ssh_get_status(state: tauri::State<'_, DbState>) -> Result<SSHStatu
         None
     };
 
+    // 只返回当前仍是同步目标的连接状态
+    let targets = config.sync_targets();
+    let target_count = if config.enabled { targets.len() } else { 0 };
+    let host_status = config
+        .host_status
+        .iter()
+        .filter(|h| targets.iter().any(|c| c.id == h.connection_id))
+        .cloned()
+        .collect();
+
     Ok(SSHStatusResult {
         ssh_available: config.enabled && active_connection_name.is_some(),
         active_connection_name,
         last_sync_time: config.last_sync_time,
         last_sync_status: config.last_sync_status,
         last_sync_error: config.last_sync_error,
+        target_count,
+        host_status,
     })
 }
 
@@ -746,16 +1156,54 @@ pub async fn update_sync_status(state: &DbState, result: &SyncResult) -> Result<
 
     let now = Local::now().to_rfc3339();
 
-    db.query("UPDATE ssh_sync_config SET last_sync_time = $time, last_sync_status = $status, last_sync_error = $error WHERE id = ssh_sync_config:`config`")
+    let existing = get_ssh_config_internal(&db, false).await?.host_status;
+    let host_status = merge_host_status(existing, &result.hosts, &now);
+    let host_status: Vec<_> = host_status
+        .iter()
+        .map(adapter::host_status_to_db_value)
+        .collect();
+
+    db.query("UPDATE ssh_sync_config SET last_sync_time = $time, last_sync_status = $status, last_sync_error = $error, host_status = $hosts WHERE id = ssh_sync_config:`config`")
         .bind(("time", now))
         .bind(("status", status))
         .bind(("error", error))
+        .bind(("hosts", host_status))
         .await
         .map_err(|e| format!("Failed to update SSH sync status: {}", e))?;
 
     Ok(())
 }
 
+/// 合并各目标连接的状态，未参与本次同步的连接保留原状态
+fn merge_host_status(
+    mut host_status: Vec<SSHHostSyncStatus>,
+    hosts: &[HostSyncResult],
+    now: &str,
+) -> Vec<SSHHostSyncStatus> {
+    for host in hosts {
+        let entry = SSHHostSyncStatus {
+            connection_id: host.connection_id.clone(),
+            connection_name: host.connection_name.clone(),
+            last_sync_time: Some(now.to_string()),
+            last_sync_status: if host.result.success {
+                "success"
+            } else {
+                "error"
+            }
+            .to_string(),
+            last_sync_error: (!host.result.success).then(|| host.result.errors.join("; ")),
+        };
+        match host_status
+            .iter_mut()
+            .find(|h| h.connection_id == entry.connection_id)
+        {
+            Some(existing) => *existing = entry,
+            None => host_status.push(entry),
+        }
+    }
+    host_status
+}
+
 /// Get default file mappings for SSH sync
 pub fn default_file_mappings() -> Vec<SSHFileMapping> {
     vec![
@@ -769,6 +1217,7 @@ pub fn default_file_mappings() -> Vec<SSHFileMapping> {
             enabled: true,
             is_pattern: false,
             is_directory: false,
+            direction: "push".to_string(),
         },
         SSHFileMapping {
             id: "opencode-oh-my".to_string(),
@@ -779,6 +1228,7 @@ pub fn default_file_mappings() -> Vec<SSHFileMapping> {
             enabled: true,
             is_pattern: false,
             is_directory: false,
+            direction: "push".to_string(),
         },
         SSHFileMapping {
             id: "opencode-oh-my-slim".to_string(),
@@ -789,6 +1239,7 @@ pub fn default_file_mappings() -> Vec<SSHFileMapping> {
             enabled: false,
             is_pattern: false,
             is_directory: false,
+            direction: "push".to_string(),
         },
         SSHFileMapping {
             id: "opencode-auth".to_string(),
@@ -799,6 +1250,7 @@ pub fn default_file_mappings() -> Vec<SSHFileMapping> {
             enabled: true,
             is_pattern: false,
             is_directory: false,
+            direction: "push".to_string(),
         },
         SSHFileMapping {
             id: "opencode-plugins".to_string(),
@@ -809,6 +1261,7 @@ pub fn default_file_mappings() -> Vec<SSHFileMapping> {
             enabled: true,
             is_pattern: true,
             is_directory: false,
+            direction: "push".to_string(),
         },
         SSHFileMapping {
             id: "opencode-prompt".to_string(),
@@ -819,6 +1272,7 @@ pub fn default_file_mappings() -> Vec<SSHFileMapping> {
             enabled: true,
             is_pattern: false,
             is_directory: false,
+            direction: "push".to_string(),
         },
         // Claude Code
         SSHFileMapping {
@@ -830,6 +1284,7 @@ pub fn default_file_mappings() -> Vec<SSHFileMapping> {
             enabled: true,
             is_pattern: false,
             is_directory: false,
+            direction: "push".to_string(),
         },
         SSHFileMapping {
             id: "claude-config".to_string(),
@@ -840,6 +1295,7 @@ pub fn default_file_mappings() -> Vec<SSHFileMapping> {
             enabled: true,
             is_pattern: false,
             is_directory: false,
+            direction: "push".to_string(),
         },
         SSHFileMapping {
             id: "claude-prompt".to_string(),
@@ -850,6 +1306,7 @@ pub fn default_file_mappings() -> Vec<SSHFileMapping> {
             enabled: true,
             is_pattern: false,
             is_directory: false,
+            direction: "push".to_string(),
         },
         // Codex
         SSHFileMapping {
@@ -861,6 +1318,7 @@ pub fn default_file_mappings() -> Vec<SSHFileMapping> {
             enabled: true,
             is_pattern: false,
             is_directory: false,
+            direction: "push".to_string(),
         },
         SSHFileMapping {
             id: "codex-config".to_string(),
@@ -871,6 +1329,7 @@ pub fn default_file_mappings() -> Vec<SSHFileMapping> {
             enabled: true,
             is_pattern: false,
             is_directory: false,
+            direction: "push".to_string(),
         },
         SSHFileMapping {
             id: "codex-prompt".to_string(),
@@ -881,6 +1340,7 @@ pub fn default_file_mappings() -> Vec<SSHFileMapping> {
             enabled: true,
             is_pattern: false,
             is_directory: false,
+            direction: "push".to_string(),
         },
         // OpenClaw
         SSHFileMapping {
@@ -892,6 +1352,7 @@ pub fn default_file_mappings() -> Vec<SSHFileMapping> {
             enabled: true,
             is_pattern: false,
             is_directory: false,
+            direction: "push".to_string(),
         },
     ]
 }
@@ -915,3 +1376,128 @@ async fn ensure_openclaw_config_on_remote(session: &SshSession) -> Result<(), St
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn connection(id: &str) -> SSHConnection {
+        SSHConnection {
+            id: id.to_string(),
+            name: format!("host-{}", id),
+            host: format!("{}.example.com", id),
+            port: 22,
+            username: "root".to_string(),
+            auth_method: "agent".to_string(),
+            password: String::new(),
+            private_key_path: String::new(),
+            private_key_content: String::new(),
+            passphrase: String::new(),
+            sort_order: 0,
+            jump_hosts: vec![],
+        }
+    }
+
+    fn host_result(id: &str, synced: &[&str], errors: &[&str]) -> HostSyncResult {
+        HostSyncResult {
+            connection_id: id.to_string(),
+            connection_name: format!("host-{}", id),
+            result: SyncResult {
+                success: errors.is_empty(),
+                synced_files: synced.iter().map(|s| s.to_string()).collect(),
+                skipped_files: vec![],
+                errors: errors.iter().map(|s| s.to_string()).collect(),
+                conflicts: vec![],
+                hosts: vec![],
+            },
+        }
+    }
+
+    #[test]
+    fn test_sync_targets_dedupes_and_puts_active_first() {
+        let config = SSHSyncConfig {
+            active_connection_id: "b".to_string(),
+            target_connection_ids: vec![
+                "a".to_string(),
+                "b".to_string(),
+                "a".to_string(),
+                "missing".to_string(),
+            ],
+            connections: vec![connection("a"), connection("b")],
+            ..Default::default()
+        };
+        let ids: Vec<_> = config
+            .sync_targets()
+            .iter()
+            .map(|c| c.id.as_str())
+            .collect();
+        assert_eq!(ids, vec!["b", "a"]);
+    }
+
+    #[test]
+    fn test_aggregate_some_hosts_failing() {
+        let result = aggregate_host_results(vec![
+            host_result("a", &["Claude settings"], &[]),
+            host_result("b", &[], &["connection refused"]),
+        ]);
+        assert!(!result.success);
+        assert_eq!(result.synced_files, vec!["[host-a] Claude settings"]);
+        assert_eq!(result.errors, vec!["[host-b] connection refused"]);
+        assert_eq!(result.hosts.len(), 2);
+    }
+
+    #[test]
+    fn test_aggregate_all_hosts_failing_and_single_host() {
+        let result = aggregate_host_results(vec![
+            host_result("a", &[], &["timeout"]),
+            host_result("b", &[], &["auth failed"]),
+        ]);
+        assert!(!result.success);
+        assert_eq!(result.errors.len(), 2);
+        assert!(result.hosts.iter().all(|h| !h.result.success));
+
+        // 单个目标时不加连接名前缀
+        let result = aggregate_host_results(vec![host_result("a", &["Codex config"], &[])]);
+        assert!(result.success);
+        assert_eq!(result.synced_files, vec!["Codex config"]);
+    }
+
+    #[test]
+    fn test_merge_host_status_keeps_untouched_hosts() {
+        let existing = vec![
+            SSHHostSyncStatus {
+                connection_id: "a".to_string(),
+                connection_name: "host-a".to_string(),
+                last_sync_time: Some("old".to_string()),
+                last_sync_status: "error".to_string(),
+                last_sync_error: Some("timeout".to_string()),
+            },
+            SSHHostSyncStatus {
+                connection_id: "c".to_string(),
+                connection_name: "host-c".to_string(),
+                last_sync_time: Some("old".to_string()),
+                last_sync_status: "success".to_string(),
+                last_sync_error: None,
+            },
+        ];
+        let hosts = vec![
+            host_result("a", &["file"], &[]),
+            host_result("b", &[], &["refused", "mcp failed"]),
+        ];
+        let merged = merge_host_status(existing, &hosts, "now");
+
+        assert_eq!(merged.len(), 3);
+        assert_eq!(merged[0].connection_id, "a");
+        assert_eq!(merged[0].last_sync_status, "success");
+        assert_eq!(merged[0].last_sync_error, None);
+        assert_eq!(merged[0].last_sync_time.as_deref(), Some("now"));
+        assert_eq!(merged[1].connection_id, "c");
+        assert_eq!(merged[1].last_sync_time.as_deref(), Some("old"));
+        assert_eq!(merged[2].connection_id, "b");
+        assert_eq!(merged[2].last_sync_status, "error");
+        assert_eq!(
+            merged[2].last_sync_error.as_deref(),
+            Some("refused; mcp failed")
+        );
+    }
+}
@@ -0,0 +1,503 @@
+//! SSH 主机密钥校验
+//!
+//! 行为等同于 OpenSSH 的 `StrictHostKeyChecking=accept-new`：
+//! - 读取 `~/.ssh/known_hosts`（支持明文、通配符、`|1|` 哈希条目及 `@revoked` 标记）
+//! - 每个 SSHConnection 首次连接时记录服务器密钥（存储于应用数据目录的 JSON 文件）
+//! - 已记录或 known_hosts 中的密钥发生变化时拒绝连接
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{Mutex, OnceLock};
+
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use hmac::{Hmac, Mac};
+use russh::keys::ssh_key::{HashAlg, PublicKey};
+use sha1::Sha1;
+
+use super::types::{SSHHostKeyError, SSHKnownHost};
+
+const STORE_FILE_NAME: &str = "ssh_known_hosts.json";
+
+static STORE_DIR: OnceLock<PathBuf> = OnceLock::new();
+/// 保护存储文件的读-改-写
+static STORE_LOCK: Mutex<()> = Mutex::new(());
+
+/// Set the directory for the recorded host key store (call once at startup)
+pub fn set_store_dir(dir: PathBuf) {
+    let _ = STORE_DIR.set(dir);
+}
+
+fn get_store_file_path() -> Option<PathBuf> {
+    STORE_DIR.get().map(|dir| dir.join(STORE_FILE_NAME))
+}
+
+// ============================================================================
+// Recorded host keys (per SSHConnection)
+// ============================================================================
+
+fn read_store() -> BTreeMap<String, SSHKnownHost> {
+    get_store_file_path()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+/// Atomic write: write to .tmp then rename
+fn write_store(store: &BTreeMap<String, SSHKnownHost>) -> Result<(), String> {
+    let path = get_store_file_path().ok_or("主机密钥存储目录未初始化")?;
+    let content =
+        serde_json::to_string_pretty(store).map_err(|e| format!("序列化主机密钥失败: {}", e))?;
+    let tmp_path = path.with_extension("json.tmp");
+    fs::write(&tmp_path, content).map_err(|e| format!("写入主机密钥失败: {}", e))?;
+    fs::rename(&tmp_path, &path).map_err(|e| format!("写入主机密钥失败: {}", e))
+}
+
+/// 获取某个连接已记录的主机密钥
+pub fn get_known_host(connection_id: &str) -> Option<SSHKnownHost> {
+    let _guard = STORE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    read_store().remove(connection_id)
+}
+
+/// 清除某个连接已记录的主机密钥，下次连接时重新记录
+/// 返回是否存在记录
+pub fn forget_known_host(connection_id: &str) -> Result<bool, String> {
+    let _guard = STORE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut store = read_store();
+    if store.remove(connection_id).is_none() {
+        return Ok(false);
+    }
+    write_store(&store)?;
+    Ok(true)
+}
+
+/// 清除连接自身及其跳板（`<id>#jumpN`）记录的主机密钥，用于删除连接
+pub fn forget_connection_known_hosts(connection_id: &str) -> Result<(), String> {
+    let _guard = STORE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut store = read_store();
+    let before = store.len();
+    store.retain(|key, _| !belongs_to_connection(key, connection_id));
+    if store.len() != before {
+        write_store(&store)?;
+    }
+    Ok(())
+}
+
+/// 记录键是否属于该连接：连接本身或 `<id>#jump<N>`
+fn belongs_to_connection(key: &str, connection_id: &str) -> bool {
+    match key.strip_prefix(connection_id) {
+        Some("") => true,
+        Some(rest) => rest
+            .strip_prefix("#jump")
+            .is_some_and(|n| !n.is_empty() && n.bytes().all(|b| b.is_ascii_digit())),
+        None => false,
+    }
+}
+
+fn record_known_host(entry: SSHKnownHost) -> Result<(), String> {
+    let _guard = STORE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut store = read_store();
+    store.insert(entry.connection_id.clone(), entry);
+    write_store(&store)
+}
+
+// ============================================================================
+// known_hosts parsing
+// ============================================================================
+
+/// known_hosts 检查结果
+#[derive(Debug, PartialEq)]
+enum KnownHostsCheck {
+    /// 存在匹配的密钥
+    Match,
+    /// 未找到该主机（或仅有其他算法的密钥）
+    NotFound,
+    /// 该主机同算法的密钥与服务器提供的不一致
+    Changed { line: usize, expected: String },
+    /// 服务器密钥被标记为 @revoked
+    Revoked { line: usize },
+}
+
+/// known_hosts 中使用的主机名格式：非 22 端口为 `[host]:port`
+fn host_port_str(host: &str, port: u16) -> String {
+    let host = host.to_lowercase();
+    if port == 22 {
+        host
+    } else {
+        format!("[{}]:{}", host, port)
+    }
+}
+
+/// 通配符匹配（支持 `*` 与 `?`）
+pub(super) fn wildcard_match(pattern: &str, text: &str) -> bool {
+    let p: Vec<char> = pattern.chars().collect();
+    let t: Vec<char> = text.chars().collect();
+    let (mut pi, mut ti) = (0, 0);
+    let mut star: Option<(usize, usize)> = None;
+
+    while ti < t.len() {
+        if pi < p.len() && (p[pi] == '?' || p[pi] == t[ti]) {
+            pi += 1;
+            ti += 1;
+        } else if pi < p.len() && p[pi] == '*' {
+            star = Some((pi, ti));
+            pi += 1;
+        } else if let Some((sp, st)) = star {
+            pi = sp + 1;
+            ti = st + 1;
+            star = Some((sp, st + 1));
+        } else {
+            return false;
+        }
+    }
+    while pi < p.len() && p[pi] == '*' {
+        pi += 1;
+    }
+    pi == p.len()
+}
+
+/// 匹配 `|1|salt|hash` 哈希主机名（HMAC-SHA1）
+fn hashed_host_match(entry: &str, host: &str) -> bool {
+    let mut parts = entry.trim_start_matches("|1|").split('|');
+    let (Some(salt), Some(hash)) = (parts.next(), parts.next()) else {
+        return false;
+    };
+    let (Ok(salt), Ok(hash)) = (BASE64.decode(salt), BASE64.decode(hash)) else {
+        return false;
+    };
+    let Ok(mut mac) = Hmac::<Sha1>::new_from_slice(&salt) else {
+        return false;
+    };
+    mac.update(host.as_bytes());
+    mac.verify_slice(&hash).is_ok()
+}
+
+/// 主机名字段匹配：逗号分隔，任一正向模式匹配且无否定模式 (`!pattern`) 匹配
+fn host_field_matches(field: &str, host: &str) -> bool {
+    let mut matched = false;
+    for entry in field.split(',') {
+        if entry.starts_with("|1|") {
+            if hashed_host_match(entry, host) {
+                matched = true;
+            }
+        } else if let Some(negated) = entry.strip_prefix('!') {
+            if wildcard_match(&negated.to_lowercase(), host) {
+                return false;
+            }
+        } else if wildcard_match(&entry.to_lowercase(), host) {
+            matched = true;
+        }
+    }
+    matched
+}
+
+fn fingerprint(key: &PublicKey) -> String {
+    key.fingerprint(HashAlg::Sha256).to_string()
+}
+
+/// 在 known_hosts 内容中检查服务器密钥
+fn check_known_hosts_content(
+    content: &str,
+    host: &str,
+    port: u16,
+    key: &PublicKey,
+) -> KnownHostsCheck {
+    let host = host_port_str(host, port);
+    let mut found = false;
+    let mut changed: Option<(usize, String)> = None;
+
+    for (idx, raw_line) in content.lines().enumerate() {
+        let line = raw_line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let mut fields = line.split_whitespace();
+        let Some(mut first) = fields.next() else {
+            continue;
+        };
+        let marker = if first.starts_with('@') {
+            let marker = first;
+            match fields.next() {
+                Some(next) => first = next,
+                None => continue,
+            }
+            Some(marker)
+        } else {
+            None
+        };
+
+        // 不支持证书颁发机构条目
+        if marker == Some("@cert-authority") {
+            continue;
+        }
+
+        let (Some(key_type), Some(key_b64)) = (fields.next(), fields.next()) else {
+            continue;
+        };
+        if !host_field_matches(first, &host) {
+            continue;
+        }
+        let Ok(recorded) = PublicKey::from_openssh(&format!("{} {}", key_type, key_b64)) else {
+            continue;
+        };
+
+        if marker == Some("@revoked") {
+            if recorded.key_data() == key.key_data() {
+                return KnownHostsCheck::Revoked { line: idx + 1 };
+            }
+            continue;
+        }
+
+        if recorded.algorithm() != key.algorithm() {
+            continue;
+        }
+        if recorded.key_data() == key.key_data() {
+            found = true;
+        } else if changed.is_none() {
+            changed = Some((idx + 1, fingerprint(&recorded)));
+        }
+    }
+
+    match (found, changed) {
+        (true, _) => KnownHostsCheck::Match,
+        (false, Some((line, expected))) => KnownHostsCheck::Changed { line, expected },
+        (false, None) => KnownHostsCheck::NotFound,
+    }
+}
+
+fn user_known_hosts_path() -> Option<PathBuf> {
+    std::env::var("USERPROFILE")
+        .or_else(|_| std::env::var("HOME"))
+        .ok()
+        .map(|home| PathBuf::from(home).join(".ssh").join("known_hosts"))
+}
+
+// ============================================================================
+// Verification
+// ============================================================================
+
+impl SSHHostKeyError {
+    /// 面向用户的错误描述
+    pub fn message(&self) -> String {
+        let source = if self.source == "app" {
+            "已记录的指纹".to_string()
+        } else {
+            format!("known_hosts 第 {} 行", self.line.unwrap_or_default())
+        };
+        match self.kind.as_str() {
+            "revoked" => format!(
+                "主机密钥已被吊销: {}:{} ({} {})，见 {}",
+                self.host, self.port, self.key_type, self.fingerprint, source
+            ),
+            _ => format!(
+                "主机密钥已变更: {}:{} 当前 {} 指纹 {}，与{}不一致 ({})。可能存在中间人攻击；如确认服务器密钥已更换，请清除该连接已记录的指纹后重试",
+                self.host,
+                self.port,
+                self.key_type,
+                self.fingerprint,
+                source,
+                self.expected_fingerprint.clone().unwrap_or_default()
+            ),
+        }
+    }
+}
+
+/// 校验服务器主机密钥（accept-new 语义）
+///
+/// 1. 连接已记录密钥：必须一致（host/port 被修改过则重新记录）
+/// 2. known_hosts 中存在该主机：同算法密钥必须一致，@revoked 直接拒绝
+/// 3. 均未找到：接受并记录为该连接的首次密钥
+///
+/// `connection_id` 为空（未保存的连接）时不记录。
+pub fn verify_host_key(
+    connection_id: &str,
+    host: &str,
+    port: u16,
+    key: &PublicKey,
+) -> Result<(), Box<SSHHostKeyError>> {
+    let presented_fingerprint = fingerprint(key);
+    let key_type = key.algorithm().as_str().to_string();
+    let make_error = |kind: &str, source: &str, expected: Option<String>, line: Option<usize>| {
+        Box::new(SSHHostKeyError {
+            kind: kind.to_string(),
+            host: host.to_string(),
+            port,
+            key_type: key_type.clone(),
+            fingerprint: presented_fingerprint.clone(),
+            expected_fingerprint: expected,
+            source: source.to_string(),
+            line,
+        })
+    };
+
+    let recorded = if connection_id.is_empty() {
+        None
+    } else {
+        get_known_host(connection_id)
+    };
+    if let Some(recorded) = &recorded {
+        if recorded.host.eq_ignore_ascii_case(host) && recorded.port == port {
+            if recorded.fingerprint == presented_fingerprint {
+                return Ok(());
+            }
+            return Err(make_error(
+                "changed",
+                "app",
+                Some(recorded.fingerprint.clone()),
+                None,
+            ));
+        }
+    }
+
+    if let Some(content) = user_known_hosts_path().and_then(|p| fs::read_to_string(p).ok()) {
+        match check_known_hosts_content(&content, host, port, key) {
+            KnownHostsCheck::Revoked { line } => {
+                return Err(make_error("revoked", "known_hosts", None, Some(line)));
+            }
+            KnownHostsCheck::Changed { line, expected } => {
+                return Err(make_error(
+                    "changed",
+                    "known_hosts",
+                    Some(expected),
+                    Some(line),
+                ));
+            }
+            KnownHostsCheck::Match | KnownHostsCheck::NotFound => {}
+        }
+    }
+
+    if !connection_id.is_empty() {
+        let entry = SSHKnownHost {
+            connection_id: connection_id.to_string(),
+            host: host.to_string(),
+            port,
+            key_type,
+            fingerprint: presented_fingerprint,
+            public_key: key.to_openssh().unwrap_or_default(),
+            first_seen: chrono::Local::now().to_rfc3339(),
+        };
+        if recorded.is_none() {
+            log::info!(
+                "记录 SSH 主机密钥: {}:{} {} {}",
+                host,
+                port,
+                entry.key_type,
+                entry.fingerprint
+            );
+        }
+        if let Err(e) = record_known_host(entry) {
+            log::warn!("{}", e);
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEY_A: &str =
+        "ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAIBERERERERERERERERERERERERERERERERERERERERER";
+    const KEY_B: &str =
+        "ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAICIiIiIiIiIiIiIiIiIiIiIiIiIiIiIiIiIiIiIiIiIi";
+
+    fn key(s: &str) -> PublicKey {
+        PublicKey::from_openssh(s).unwrap()
+    }
+
+    #[test]
+    fn test_plain_and_port_entries() {
+        let content = format!(
+            "example.com,10.0.0.1 {}\n[example.com]:2222 {}\n",
+            KEY_A, KEY_B
+        );
+        assert_eq!(
+            check_known_hosts_content(&content, "example.com", 22, &key(KEY_A)),
+            KnownHostsCheck::Match
+        );
+        assert_eq!(
+            check_known_hosts_content(&content, "EXAMPLE.com", 2222, &key(KEY_B)),
+            KnownHostsCheck::Match
+        );
+        assert_eq!(
+            check_known_hosts_content(&content, "other.com", 22, &key(KEY_A)),
+            KnownHostsCheck::NotFound
+        );
+    }
+
+    #[test]
+    fn test_changed_key() {
+        let content = format!("# comment\n\nexample.com {}\n", KEY_A);
+        match check_known_hosts_content(&content, "example.com", 22, &key(KEY_B)) {
+            KnownHostsCheck::Changed { line, expected } => {
+                assert_eq!(line, 3);
+                assert_eq!(expected, fingerprint(&key(KEY_A)));
+            }
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_hashed_entry() {
+        // Same scheme as `ssh-keygen -H`: HMAC-SHA1 keyed by the salt over the host name
+        let salt = [7u8; 20];
+        let mut mac = Hmac::<Sha1>::new_from_slice(&salt).unwrap();
+        mac.update(b"example.com");
+        let hash = mac.finalize().into_bytes();
+        let content = format!(
+            "|1|{}|{} {}\n",
+            BASE64.encode(salt),
+            BASE64.encode(hash),
+            KEY_A
+        );
+        assert_eq!(
+            check_known_hosts_content(&content, "example.com", 22, &key(KEY_A)),
+            KnownHostsCheck::Match
+        );
+        assert_eq!(
+            check_known_hosts_content(&content, "example.org", 22, &key(KEY_A)),
+            KnownHostsCheck::NotFound
+        );
+    }
+
+    #[test]
+    fn test_wildcards_negation_and_revoked() {
+        let content = format!("*.example.com,!bad.example.com {}\n", KEY_A);
+        assert_eq!(
+            check_known_hosts_content(&content, "web.example.com", 22, &key(KEY_A)),
+            KnownHostsCheck::Match
+        );
+        assert_eq!(
+            check_known_hosts_content(&content, "bad.example.com", 22, &key(KEY_A)),
+            KnownHostsCheck::NotFound
+        );
+
+        let revoked = format!("@revoked * {}\nweb.example.com {}\n", KEY_A, KEY_A);
+        assert_eq!(
+            check_known_hosts_content(&revoked, "web.example.com", 22, &key(KEY_A)),
+            KnownHostsCheck::Revoked { line: 1 }
+        );
+    }
+
+    #[test]
+    fn test_belongs_to_connection() {
+        assert!(belongs_to_connection("abc", "abc"));
+        assert!(belongs_to_connection("abc#jump0", "abc"));
+        assert!(belongs_to_connection("abc#jump12", "abc"));
+        assert!(!belongs_to_connection("abcd", "abc"));
+        assert!(!belongs_to_connection("abcd#jump0", "abc"));
+        assert!(!belongs_to_connection("abc#jump", "abc"));
+        assert!(!belongs_to_connection("abc#jumpx", "abc"));
+    }
+
+    #[test]
+    fn test_wildcard_match() {
+        assert!(wildcard_match("*", "anything"));
+        assert!(wildcard_match("host-?", "host-1"));
+        assert!(wildcard_match("*.example.*", "a.example.org"));
+        assert!(!wildcard_match("host-?", "host-12"));
+    }
+}
@@ -11,9 +11,10 @@ use tauri::{AppHandle, Emitter};
 use super::commands::resolve_dynamic_paths;
 use super::session::SshSession;
 use super::sync::{read_remote_file, sync_mappings, write_remote_file};
-use super::types::{SSHFileMapping, SyncProgress};
+use super::types::{McpChangePreview, SSHFileMapping, SyncProgress};
 use crate::coding::mcp::command_normalize;
 use crate::coding::mcp::mcp_store;
+use crate::coding::sync_preview::{is_mcp_config_file, preview_claude_mcp, strip_cmd_c};
 use crate::DbState;
 
 /// Get file mappings from database
@@ -39,7 +40,7 @@ async fn get_file_mappings(state: &DbState) -> Result<Vec<SSHFileMapping>, Strin
 pub async fn sync_mcp_to_ssh(
     state: &DbState,
     session: &SshSession,
-    app: AppHandle,
+    app: Option<&AppHandle>,
 ) -> Result<(), String> {
     let db = state.db();
     let config = super::commands::get_ssh_config_internal(&db, false).await?;
@@ -53,16 +54,18 @@ pub async fn sync_mcp_to_ssh(
     let mut all_errors: Vec<String> = vec![];
 
     // Emit progress
-    let _ = app.emit(
-        "ssh-sync-progress",
-        SyncProgress {
-            phase: "mcp".to_string(),
-            current_item: "Claude Code MCP".to_string(),
-            current: 1,
-            total: 2,
-            message: "MCP 同步: Claude Code...".to_string(),
-        },
-    );
+    if let Some(app) = app {
+        let _ = app.emit(
+            "ssh-sync-progress",
+            SyncProgress {
+                phase: "mcp".to_string(),
+                current_item: "Claude Code MCP".to_string(),
+                current: 1,
+                total: 2,
+                message: "MCP 同步: Claude Code...".to_string(),
+            },
+        );
+    }
 
     // 1. Claude Code: directly modify remote ~/.claude.json
     let servers = mcp_store::get_mcp_servers(state).await?;
@@ -74,26 +77,30 @@ pub async fn sync_mcp_to_ssh(
     if let Err(e) = sync_mcp_to_ssh_claude(session, &claude_servers).await {
         log::warn!("Skipped claude.json MCP sync: {}", e);
         all_errors.push(format!("Claude Code: {}", e));
-        let _ = app.emit(
-            "ssh-sync-warning",
-            format!(
-                "SSH ~/.claude.json 同步已跳过：文件解析失败，请检查该文件格式是否正确。({})",
-                e
-            ),
-        );
+        if let Some(app) = app {
+            let _ = app.emit(
+                "ssh-sync-warning",
+                format!(
+                    "SSH ~/.claude.json 同步已跳过：文件解析失败，请检查该文件格式是否正确。({})",
+                    e
+                ),
+            );
+        }
     }
 
     // Emit progress for OpenCode/Codex
-    let _ = app.emit(
-        "ssh-sync-progress",
-        SyncProgress {
-            phase: "mcp".to_string(),
-            current_item: "OpenCode/Codex MCP".to_string(),
-            current: 2,
-            total: 2,
-            message: "MCP 同步: OpenCode/Codex...".to_string(),
-        },
-    );
+    if let Some(app) = app {
+        let _ = app.emit(
+            "ssh-sync-progress",
+            SyncProgress {
+                phase: "mcp".to_string(),
+                current_item: "OpenCode/Codex MCP".to_string(),
+                current: 2,
+                total: 2,
+                message: "MCP 同步: OpenCode/Codex...".to_string(),
+            },
+        );
+    }
 
     // 2. OpenCode/Codex: sync config files via file mappings
     match get_file_mappings(state).await {
@@ -111,10 +118,12 @@ pub async fn sync_mcp_to_ssh(
                     let msg = result.errors.join("; ");
                     log::warn!("MCP file mapping sync errors: {}", msg);
                     all_errors.push(format!("OpenCode/Codex: {}", msg));
-                    let _ = app.emit(
-                        "ssh-sync-warning",
-                        format!("OpenCode/Codex 配置同步部分失败：{}", msg),
-                    );
+                    if let Some(app) = app {
+                        let _ = app.emit(
+                            "ssh-sync-warning",
+                            format!("OpenCode/Codex 配置同步部分失败：{}", msg),
+                        );
+                    }
                 }
 
                 // Post-process: strip cmd /c from synced MCP config files
@@ -148,10 +157,12 @@ pub async fn sync_mcp_to_ssh(
         Err(e) => {
             log::warn!("Skipped OpenCode/Codex MCP sync: {}", e);
             all_errors.push(format!("OpenCode/Codex: {}", e));
-            let _ = app.emit(
-                "ssh-sync-warning",
-                format!("OpenCode/Codex MCP 同步已跳过：{}", e),
-            );
+            if let Some(app) = app {
+                let _ = app.emit(
+                    "ssh-sync-warning",
+                    format!("OpenCode/Codex MCP 同步已跳过：{}", e),
+                );
+            }
         }
     }
 
@@ -164,7 +175,9 @@ pub async fn sync_mcp_to_ssh(
         return Err(all_errors.join("; "));
     }
 
-    let _ = app.emit("ssh-mcp-sync-completed", ());
+    if let Some(app) = app {
+        let _ = app.emit("ssh-mcp-sync-completed", ());
+    }
 
     Ok(())
 }
@@ -208,6 +221,22 @@ async fn sync_mcp_to_ssh_claude(
     Ok(())
 }
 
+/// Preview changes to remote ~/.claude.json mcpServers without writing (dry-run)
+pub(super) async fn preview_mcp_to_ssh(
+    state: &DbState,
+    session: &SshSession,
+) -> Result<McpChangePreview, String> {
+    let servers = mcp_store::get_mcp_servers(state).await?;
+    let mcp_servers: serde_json::Map<String, Value> = servers
+        .iter()
+        .filter(|s| s.enabled_tools.contains(&"claude_code".to_string()))
+        .map(|s| (s.name.clone(), build_standard_server_config(s)))
+        .collect();
+
+    let existing_content = read_remote_file(session, "~/.claude.json").await?;
+    preview_claude_mcp(&existing_content, &mcp_servers, "remote")
+}
+
 /// Build standard JSON server config for Claude Code format
 fn build_standard_server_config(server: &crate::coding::mcp::types::McpServer) -> Value {
     match server.server_type.as_str() {
@@ -271,14 +300,6 @@ fn build_standard_server_config(server: &crate::coding::mcp::types::McpServer) -
     }
 }
 
-/// Check if a file mapping ID corresponds to an MCP config file
-fn is_mcp_config_file(mapping_id: &str) -> bool {
-    matches!(
-        mapping_id,
-        "opencode-main" | "opencode-oh-my" | "codex-config"
-    )
-}
-
 /// Strip cmd /c from remote MCP config file after sync
 async fn strip_cmd_c_from_remote_mcp_file(
     session: &SshSession,
@@ -286,20 +307,8 @@ async fn strip_cmd_c_from_remote_mcp_file(
     module: &str,
 ) -> Result<(), String> {
     let content = read_remote_file(session, remote_path).await?;
-    if content.trim().is_empty() {
+    let Some(processed) = strip_cmd_c(remote_path, module, &content)? else {
         return Ok(());
-    }
-
-    let processed = match module {
-        "opencode" => command_normalize::process_opencode_json(&content, false)?,
-        "codex" => {
-            if remote_path.ends_with(".toml") {
-                command_normalize::process_codex_toml(&content, false)?
-            } else {
-                return Ok(());
-            }
-        }
-        _ => return Ok(()),
     };
 
     if processed != content {
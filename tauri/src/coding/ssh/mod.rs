@@ -1,10 +1,13 @@
-mod adapter;
+pub(crate) mod adapter;
 mod commands;
 pub mod key_file;
+pub mod known_hosts;
 mod mcp_sync;
 mod session;
 mod skills_sync;
+mod ssh_config;
 mod sync;
+pub mod sync_state;
 mod types;
 
 pub use commands::*;
@@ -3,18 +3,23 @@
 //! 维护一个进程内持久 SSH 连接，所有操作复用该连接。
 //! 网络断开后自动重连。跨平台兼容（Windows/macOS/Linux）。
 
+use std::future::Future;
+use std::pin::Pin;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
 
 use log::{info, warn};
+use russh::keys::agent::client::AgentClient;
 use russh::keys::ssh_key;
 use russh::{client, ChannelMsg, Disconnect};
 use tokio::io::AsyncWriteExt;
 use tokio::sync::Mutex;
 
 use super::key_file;
-use super::types::SSHConnection;
+use super::known_hosts;
+use super::types::{SSHConnection, SSHHostKeyError};
+use crate::secret_vault;
 
 /// 加载私钥：优先从内容直接解析，否则从文件路径加载
 fn load_private_key(conn: &SSHConnection) -> Result<russh::keys::PrivateKey, String> {
@@ -51,67 +56,410 @@ pub enum SessionStatus {
     Failed(String),
 }
 
+/// SSH 连接错误（区分主机密钥校验失败与其他错误）
+#[derive(Debug, Clone)]
+pub enum ConnectError {
+    /// 主机密钥校验失败（密钥变更 / 被吊销）
+    HostKey(SSHHostKeyError),
+    /// 其他错误（网络、认证等）
+    Other(String),
+}
+
+impl std::fmt::Display for ConnectError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConnectError::HostKey(e) => write!(f, "{}", e.message()),
+            ConnectError::Other(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl From<String> for ConnectError {
+    fn from(e: String) -> Self {
+        ConnectError::Other(e)
+    }
+}
+
 /// russh 客户端 Handler 实现
-struct SshHandler;
+///
+/// 主机密钥校验采用 StrictHostKeyChecking=accept-new 语义（见 known_hosts 模块），
+/// 校验失败的详情写入 `rejection`，供连接失败后取回
+struct SshHandler {
+    connection_id: String,
+    host: String,
+    port: u16,
+    rejection: Arc<std::sync::Mutex<Option<SSHHostKeyError>>>,
+}
+
+impl SshHandler {
+    fn new(conn: &SSHConnection) -> Self {
+        Self {
+            connection_id: conn.id.clone(),
+            host: conn.host.clone(),
+            port: conn.port,
+            rejection: Arc::new(std::sync::Mutex::new(None)),
+        }
+    }
+}
 
 impl client::Handler for SshHandler {
     type Error = russh::Error;
 
     async fn check_server_key(
         &mut self,
-        _server_public_key: &ssh_key::PublicKey,
+        server_public_key: &ssh_key::PublicKey,
     ) -> Result<bool, Self::Error> {
-        // TODO: 实现 known_hosts 验证以达到真正的 StrictHostKeyChecking=accept-new 行为
-        // 当前行为等同于 StrictHostKeyChecking=no，无条件接受所有服务器密钥
-        Ok(true)
+        match known_hosts::verify_host_key(
+            &self.connection_id,
+            &self.host,
+            self.port,
+            server_public_key,
+        ) {
+            Ok(()) => Ok(true),
+            Err(e) => {
+                warn!("{}", e.message());
+                if let Ok(mut slot) = self.rejection.lock() {
+                    *slot = Some(*e);
+                }
+                Ok(false)
+            }
+        }
     }
 }
 
-/// 对已建立的 SSH 连接进行用户认证（密码或公钥）
-async fn authenticate(
+/// 已建立的 SSH 连接：目标会话及途经的跳板会话
+///
+/// 跳板会话承载着到下一跳的 direct-tcpip 隧道，必须与目标会话同生命周期
+struct ConnectedSession {
+    handle: client::Handle<SshHandler>,
+    jumps: Vec<client::Handle<SshHandler>>,
+}
+
+/// 展开跳板链（等同于 OpenSSH ProxyJump），按连接顺序返回各跳板主机
+///
+/// 跳板复用 SSHConnection 的认证逻辑；主机密钥以 `<id>#jump<n>` 单独记录
+fn jump_connections(conn: &SSHConnection) -> Vec<SSHConnection> {
+    conn.jump_hosts
+        .iter()
+        .enumerate()
+        .map(|(idx, jump)| SSHConnection {
+            id: if conn.id.is_empty() {
+                String::new()
+            } else {
+                format!("{}#jump{}", conn.id, idx)
+            },
+            name: format!("{} (jump {})", conn.name, idx + 1),
+            host: jump.host.clone(),
+            port: if jump.port == 0 { 22 } else { jump.port },
+            username: if jump.username.is_empty() {
+                conn.username.clone()
+            } else {
+                jump.username.clone()
+            },
+            auth_method: jump.auth_method.clone(),
+            password: jump.password.clone(),
+            private_key_path: jump.private_key_path.clone(),
+            private_key_content: jump.private_key_content.clone(),
+            passphrase: jump.passphrase.clone(),
+            sort_order: 0,
+            jump_hosts: vec![],
+        })
+        .collect()
+}
+
+/// 连接单跳并完成认证：`via` 为空时直连 TCP，否则通过上一跳的 direct-tcpip 隧道
+async fn connect_hop(
+    hop: &SSHConnection,
+    config: &Arc<client::Config>,
+    via: Option<&client::Handle<SshHandler>>,
+) -> Result<client::Handle<SshHandler>, ConnectError> {
+    let handler = SshHandler::new(hop);
+    let rejection = handler.rejection.clone();
+
+    let connected = match via {
+        None => client::connect(config.clone(), (hop.host.as_str(), hop.port), handler).await,
+        Some(prev) => {
+            let channel = prev
+                .channel_open_direct_tcpip(
+                    hop.host.clone(),
+                    hop.port as u32,
+                    "127.0.0.1".to_string(),
+                    0,
+                )
+                .await
+                .map_err(|e| {
+                    format!("通过跳板建立到 {}:{} 的隧道失败: {}", hop.host, hop.port, e)
+                })?;
+            client::connect_stream(config.clone(), channel.into_stream(), handler).await
+        }
+    };
+
+    let mut session = match connected {
+        Ok(session) => session,
+        Err(e) => {
+            if let Some(host_key_error) = rejection.lock().ok().and_then(|mut slot| slot.take()) {
+                return Err(ConnectError::HostKey(host_key_error));
+            }
+            return Err(format!("连接到 {}:{} 失败: {}", hop.host, hop.port, e).into());
+        }
+    };
+
+    authenticate(&mut session, hop).await?;
+
+    Ok(session)
+}
+
+/// 依次连接跳板链与目标主机
+async fn connect_chain(
+    conn: &SSHConnection,
+    config: &Arc<client::Config>,
+) -> Result<ConnectedSession, ConnectError> {
+    let mut jumps: Vec<client::Handle<SshHandler>> = Vec::new();
+    for jump in jump_connections(conn) {
+        let session = connect_hop(&jump, config, jumps.last())
+            .await
+            .map_err(|e| match e {
+                ConnectError::Other(msg) => {
+                    ConnectError::Other(format!("跳板 {}: {}", jump.host, msg))
+                }
+                host_key => host_key,
+            })?;
+        jumps.push(session);
+    }
+
+    let handle = connect_hop(conn, config, jumps.last()).await?;
+    Ok(ConnectedSession { handle, jumps })
+}
+
+/// 解出连接（含跳板机）中以密钥保险库引用保存的密码、私钥内容与私钥口令
+fn resolve_vault_secrets(conn: &SSHConnection) -> Result<SSHConnection, String> {
+    let mut conn = conn.clone();
+    conn.password = secret_vault::resolve(&conn.password)?;
+    conn.private_key_content = secret_vault::resolve(&conn.private_key_content)?;
+    conn.passphrase = secret_vault::resolve(&conn.passphrase)?;
+    for jump in &mut conn.jump_hosts {
+        jump.password = secret_vault::resolve(&jump.password)?;
+        jump.private_key_content = secret_vault::resolve(&jump.private_key_content)?;
+        jump.passphrase = secret_vault::resolve(&jump.passphrase)?;
+    }
+    Ok(conn)
+}
+
+/// 建立 SSH 连接（依次经过跳板链）并完成认证
+async fn open_session(
+    conn: &SSHConnection,
+    config: client::Config,
+    timeout: Duration,
+) -> Result<ConnectedSession, ConnectError> {
+    let conn = &resolve_vault_secrets(conn).map_err(ConnectError::Other)?;
+    let config = Arc::new(config);
+
+    tokio::time::timeout(timeout, connect_chain(conn, &config))
+        .await
+        .map_err(|_| ConnectError::Other(format!("连接超时: {}:{}", conn.host, conn.port)))?
+}
+
+/// 通过 ssh-agent 认证：依次尝试 agent 中的每个密钥
+async fn authenticate_with_agent(
     session: &mut client::Handle<SshHandler>,
     conn: &SSHConnection,
 ) -> Result<(), String> {
-    if conn.auth_method == "password" && !conn.password.is_empty() {
-        let auth_result = session
-            .authenticate_password(&conn.username, &conn.password)
+    let mut agent = connect_agent().await?;
+    let identities = agent
+        .request_identities()
+        .await
+        .map_err(|e| format!("读取 ssh-agent 密钥失败: {}", e))?;
+    if identities.is_empty() {
+        return Err("ssh-agent 中没有可用的密钥".to_string());
+    }
+
+    let rsa_hash = session
+        .best_supported_rsa_hash()
+        .await
+        .map_err(|e| format!("获取 RSA hash 算法失败: {}", e))?
+        .flatten();
+
+    let mut signer = AgentSigner(agent);
+    for key in identities {
+        let hash_alg = if key.algorithm().is_rsa() {
+            rsa_hash
+        } else {
+            None
+        };
+        match session
+            .authenticate_publickey_with(conn.username.clone(), key, hash_alg, &mut signer)
             .await
-            .map_err(|e| format!("密码认证失败: {}", e))?;
-        if !auth_result.success() {
-            return Err("密码认证失败: 用户名或密码错误".to_string());
+        {
+            Ok(result) if result.success() => return Ok(()),
+            Ok(_) => {}
+            Err(e) => warn!("ssh-agent 密钥认证出错: {}", e),
         }
-    } else if conn.auth_method == "key" {
-        let key_pair = load_private_key(conn)?;
-
-        let auth_result = session
-            .authenticate_publickey(
-                &conn.username,
-                russh::keys::PrivateKeyWithHashAlg::new(
-                    Arc::new(key_pair),
-                    session
-                        .best_supported_rsa_hash()
-                        .await
-                        .map_err(|e| format!("获取 RSA hash 算法失败: {}", e))?
-                        .flatten(),
-                ),
-            )
+    }
+
+    Err("ssh-agent 认证失败: 服务器未接受 agent 中的任何密钥".to_string())
+}
+
+type DynAgentClient =
+    AgentClient<Box<dyn russh::keys::agent::client::AgentStream + Send + Unpin + 'static>>;
+
+/// 包装 AgentClient 的签名器：将签名 future 装箱，
+/// 避免 russh 自带实现在 `tokio::spawn` 中触发 "Send is not general enough" 编译错误
+struct AgentSigner(DynAgentClient);
+
+impl russh::Signer for AgentSigner {
+    type Error = russh::AgentAuthError;
+
+    fn auth_publickey_sign(
+        &mut self,
+        key: &ssh_key::PublicKey,
+        hash_alg: Option<russh::keys::HashAlg>,
+        to_sign: russh::CryptoVec,
+    ) -> impl Future<Output = Result<russh::CryptoVec, Self::Error>> + Send {
+        let key = key.clone();
+        let agent = &mut self.0;
+        let signing: Pin<
+            Box<dyn Future<Output = Result<russh::CryptoVec, Self::Error>> + Send + '_>,
+        > = Box::pin(async move {
+            agent
+                .sign_request(&key, hash_alg, to_sign)
+                .await
+                .map_err(Into::into)
+        });
+        signing
+    }
+}
+
+/// 连接 ssh-agent（SSH_AUTH_SOCK）
+#[cfg(unix)]
+async fn connect_agent() -> Result<DynAgentClient, String> {
+    AgentClient::connect_env()
+        .await
+        .map(AgentClient::dynamic)
+        .map_err(|e| format!("连接 ssh-agent 失败 (SSH_AUTH_SOCK): {}", e))
+}
+
+/// 连接 ssh-agent：优先 SSH_AUTH_SOCK / OpenSSH for Windows 命名管道，其次 Pageant
+#[cfg(windows)]
+async fn connect_agent() -> Result<DynAgentClient, String> {
+    let pipe = std::env::var("SSH_AUTH_SOCK")
+        .unwrap_or_else(|_| r"\\.\pipe\openssh-ssh-agent".to_string());
+    match AgentClient::connect_named_pipe(&pipe).await {
+        Ok(agent) => Ok(agent.dynamic()),
+        Err(pipe_err) => AgentClient::connect_pageant()
             .await
-            .map_err(|e| format!("公钥认证失败: {}", e))?;
-        if !auth_result.success() {
-            return Err("公钥认证失败: 密钥不被服务器接受".to_string());
+            .map(AgentClient::dynamic)
+            .map_err(|e| {
+                format!(
+                    "连接 ssh-agent 失败 (命名管道 {}: {}; Pageant: {})",
+                    pipe, pipe_err, e
+                )
+            }),
+    }
+}
+
+/// 单次认证尝试所用的方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum AuthStep {
+    Password,
+    PublicKey,
+    Agent,
+}
+
+/// 根据认证方式决定尝试顺序：ssh-agent 失败（无 agent、无密钥或均被拒绝）时，
+/// 依次回退到已配置的私钥和密码
+fn auth_steps(conn: &SSHConnection) -> Result<Vec<AuthStep>, String> {
+    let has_key =
+        !conn.private_key_content.trim().is_empty() || !conn.private_key_path.trim().is_empty();
+    let has_password = !conn.password.is_empty();
+    match conn.auth_method.as_str() {
+        "password" if has_password => Ok(vec![AuthStep::Password]),
+        "password" => Err("密码认证失败: 未填写密码".to_string()),
+        "key" => Ok(vec![AuthStep::PublicKey]),
+        "agent" => {
+            let mut steps = vec![AuthStep::Agent];
+            if has_key {
+                steps.push(AuthStep::PublicKey);
+            }
+            if has_password {
+                steps.push(AuthStep::Password);
+            }
+            Ok(steps)
         }
-    } else {
-        return Err(format!("不支持的认证方式: {}", conn.auth_method));
+        other => Err(format!("不支持的认证方式: {}", other)),
+    }
+}
+
+async fn authenticate_with_password(
+    session: &mut client::Handle<SshHandler>,
+    conn: &SSHConnection,
+) -> Result<(), String> {
+    let auth_result = session
+        .authenticate_password(&conn.username, &conn.password)
+        .await
+        .map_err(|e| format!("密码认证失败: {}", e))?;
+    if !auth_result.success() {
+        return Err("密码认证失败: 用户名或密码错误".to_string());
     }
     Ok(())
 }
 
+async fn authenticate_with_key(
+    session: &mut client::Handle<SshHandler>,
+    conn: &SSHConnection,
+) -> Result<(), String> {
+    let key_pair = load_private_key(conn)?;
+
+    let auth_result = session
+        .authenticate_publickey(
+            &conn.username,
+            russh::keys::PrivateKeyWithHashAlg::new(
+                Arc::new(key_pair),
+                session
+                    .best_supported_rsa_hash()
+                    .await
+                    .map_err(|e| format!("获取 RSA hash 算法失败: {}", e))?
+                    .flatten(),
+            ),
+        )
+        .await
+        .map_err(|e| format!("公钥认证失败: {}", e))?;
+    if !auth_result.success() {
+        return Err("公钥认证失败: 密钥不被服务器接受".to_string());
+    }
+    Ok(())
+}
+
+/// 对已建立的 SSH 连接进行用户认证（密码、公钥或 ssh-agent）
+async fn authenticate(
+    session: &mut client::Handle<SshHandler>,
+    conn: &SSHConnection,
+) -> Result<(), String> {
+    let mut errors = Vec::new();
+    for step in auth_steps(conn)? {
+        let result = match step {
+            AuthStep::Password => authenticate_with_password(session, conn).await,
+            AuthStep::PublicKey => authenticate_with_key(session, conn).await,
+            AuthStep::Agent => authenticate_with_agent(session, conn).await,
+        };
+        match result {
+            Ok(()) => return Ok(()),
+            Err(e) => {
+                warn!("SSH 认证方式 {:?} 失败: {}", step, e);
+                errors.push(e);
+            }
+        }
+    }
+    Err(errors.join("; "))
+}
+
 /// SSH 持久连接会话管理器
 pub struct SshSession {
     /// 当前使用的连接信息
     conn: Option<SSHConnection>,
     /// russh 持久连接句柄
     handle: Option<client::Handle<SshHandler>>,
+    /// 跳板连接句柄（承载到目标主机的隧道）
+    jump_handles: Vec<client::Handle<SshHandler>>,
     /// 当前会话状态
     status: SessionStatus,
     /// 是否正在进行同步操作（防止并发）
@@ -127,6 +475,7 @@ impl SshSession {
         Self {
             conn: None,
             handle: None,
+            jump_handles: Vec::new(),
             status: SessionStatus::Disconnected,
             syncing: AtomicBool::new(false),
         }
@@ -157,8 +506,9 @@ impl SshSession {
         self.conn = Some(conn.clone());
 
         match self.do_connect(conn).await {
-            Ok(handle) => {
-                self.handle = Some(handle);
+            Ok(connected) => {
+                self.handle = Some(connected.handle);
+                self.jump_handles = connected.jumps;
                 self.status = SessionStatus::Connected;
                 info!(
                     "SSH 连接已建立: {}@{}:{}",
@@ -175,7 +525,7 @@ impl SshSession {
     }
 
     /// 内部连接逻辑
-    async fn do_connect(&self, conn: &SSHConnection) -> Result<client::Handle<SshHandler>, String> {
+    async fn do_connect(&self, conn: &SSHConnection) -> Result<ConnectedSession, String> {
         let config = client::Config {
             inactivity_timeout: Some(Duration::from_secs(90)),
             keepalive_interval: Some(Duration::from_secs(30)),
@@ -183,18 +533,9 @@ impl SshSession {
             ..Default::default()
         };
 
-        let handler = SshHandler;
-        let mut session = tokio::time::timeout(
-            Duration::from_secs(30),
-            client::connect(Arc::new(config), (conn.host.as_str(), conn.port), handler),
-        )
-        .await
-        .map_err(|_| format!("连接超时: {}:{}", conn.host, conn.port))?
-        .map_err(|e| format!("连接到 {}:{} 失败: {}", conn.host, conn.port, e))?;
-
-        authenticate(&mut session, conn).await?;
-
-        Ok(session)
+        open_session(conn, config, Duration::from_secs(30))
+            .await
+            .map_err(|e| e.to_string())
     }
 
     /// 检查连接是否存活
@@ -230,6 +571,10 @@ impl SshSession {
                 );
             }
         }
+        // 由近及远关闭跳板连接
+        while let Some(jump) = self.jump_handles.pop() {
+            let _ = jump.disconnect(Disconnect::ByApplication, "", "").await;
+        }
         self.conn = None;
         self.status = SessionStatus::Disconnected;
     }
@@ -375,6 +720,12 @@ impl SshSession {
         upload_file_via_sftp(&sftp, local_path, remote_path).await
     }
 
+    /// 通过 SFTP 下载单个文件到本地（原子写入）
+    pub async fn download_file(&self, remote_path: &str, local_path: &str) -> Result<(), String> {
+        let sftp = self.create_sftp_session().await?;
+        download_file_via_sftp(&sftp, remote_path, local_path).await
+    }
+
     /// 通过 SFTP 递归上传目录
     pub async fn upload_dir(&self, local_path: &str, remote_path: &str) -> Result<(), String> {
         let sftp = self.create_sftp_session().await?;
@@ -410,22 +761,16 @@ impl SshSession {
 pub async fn test_connection_with_command(
     conn: &SSHConnection,
     cmd: &str,
-) -> Result<String, String> {
+) -> Result<String, ConnectError> {
     let config = client::Config {
         inactivity_timeout: Some(Duration::from_secs(10)),
         ..Default::default()
     };
 
-    let handler = SshHandler;
-    let mut session = tokio::time::timeout(
-        Duration::from_secs(15),
-        client::connect(Arc::new(config), (conn.host.as_str(), conn.port), handler),
-    )
-    .await
-    .map_err(|_| format!("连接超时: {}:{}", conn.host, conn.port))?
-    .map_err(|e| format!("连接到 {}:{} 失败: {}", conn.host, conn.port, e))?;
-
-    authenticate(&mut session, conn).await?;
+    let ConnectedSession {
+        handle: session,
+        mut jumps,
+    } = open_session(conn, config, Duration::from_secs(15)).await?;
 
     // 执行命令
     let mut channel = session
@@ -458,6 +803,9 @@ pub async fn test_connection_with_command(
     }
 
     let _ = session.disconnect(Disconnect::ByApplication, "", "").await;
+    while let Some(jump) = jumps.pop() {
+        let _ = jump.disconnect(Disconnect::ByApplication, "", "").await;
+    }
 
     Ok(String::from_utf8_lossy(&stdout_buf).to_string())
 }
@@ -510,6 +858,39 @@ pub async fn upload_file_via_sftp(
     Ok(())
 }
 
+/// 通过已有 SFTP 会话下载单个文件
+/// 先写入本地临时文件再 rename，避免下载中断导致本地配置损坏
+pub async fn download_file_via_sftp(
+    sftp: &russh_sftp::client::SftpSession,
+    remote_path: &str,
+    local_path: &str,
+) -> Result<(), String> {
+    let abs_remote_path = resolve_remote_path(sftp, remote_path).await?;
+
+    let data = sftp
+        .read(abs_remote_path.as_str())
+        .await
+        .map_err(|e| format!("读取远程文件失败 {}: {}", abs_remote_path, e))?;
+
+    let local = std::path::Path::new(local_path);
+    if let Some(parent) = local.parent() {
+        tokio::fs::create_dir_all(parent)
+            .await
+            .map_err(|e| format!("创建本地目录失败 {}: {}", parent.display(), e))?;
+    }
+
+    let tmp_path = format!("{}.tmp_download", local_path);
+    tokio::fs::write(&tmp_path, &data)
+        .await
+        .map_err(|e| format!("写入本地文件失败 {}: {}", local_path, e))?;
+    if let Err(e) = tokio::fs::rename(&tmp_path, local).await {
+        let _ = tokio::fs::remove_file(&tmp_path).await;
+        return Err(format!("写入本地文件失败 {}: {}", local_path, e));
+    }
+
+    Ok(())
+}
+
 /// 递归上传目录内容到远程
 /// 使用 tokio::fs::metadata 跟随符号链接，等同于 cp -rL 行为
 async fn upload_dir_recursive(
@@ -622,3 +1003,83 @@ impl Drop for SshSession {
         self.handle.take();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coding::ssh::types::SSHJumpHost;
+
+    fn connection(auth_method: &str) -> SSHConnection {
+        SSHConnection {
+            id: "conn".to_string(),
+            name: "Server".to_string(),
+            host: "target.example.com".to_string(),
+            port: 22,
+            username: "deploy".to_string(),
+            auth_method: auth_method.to_string(),
+            password: String::new(),
+            private_key_path: String::new(),
+            private_key_content: String::new(),
+            passphrase: String::new(),
+            sort_order: 0,
+            jump_hosts: vec![],
+        }
+    }
+
+    fn jump(host: &str, port: u16, username: &str) -> SSHJumpHost {
+        SSHJumpHost {
+            host: host.to_string(),
+            port,
+            username: username.to_string(),
+            auth_method: "agent".to_string(),
+            password: String::new(),
+            private_key_path: String::new(),
+            private_key_content: String::new(),
+            passphrase: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_jump_connections_chain() {
+        let mut conn = connection("key");
+        conn.jump_hosts = vec![jump("bastion1", 0, ""), jump("bastion2", 2222, "ops")];
+
+        let hops = jump_connections(&conn);
+        assert_eq!(hops.len(), 2);
+        assert_eq!(hops[0].id, "conn#jump0");
+        assert_eq!(hops[0].host, "bastion1");
+        assert_eq!(hops[0].port, 22);
+        assert_eq!(hops[0].username, "deploy");
+        assert_eq!(hops[1].id, "conn#jump1");
+        assert_eq!(hops[1].port, 2222);
+        assert_eq!(hops[1].username, "ops");
+        assert!(hops.iter().all(|hop| hop.jump_hosts.is_empty()));
+
+        // 未保存的连接（测试连接）不记录跳板主机密钥
+        conn.id = String::new();
+        assert!(jump_connections(&conn).iter().all(|hop| hop.id.is_empty()));
+    }
+
+    #[test]
+    fn test_auth_steps_agent_fallback() {
+        let conn = connection("agent");
+        assert_eq!(auth_steps(&conn).unwrap(), vec![AuthStep::Agent]);
+
+        let mut conn = connection("agent");
+        conn.private_key_path = "~/.ssh/id_ed25519".to_string();
+        conn.password = "secret".to_string();
+        assert_eq!(
+            auth_steps(&conn).unwrap(),
+            vec![AuthStep::Agent, AuthStep::PublicKey, AuthStep::Password]
+        );
+
+        // 显式选择密钥或密码时不尝试 agent
+        conn.auth_method = "key".to_string();
+        assert_eq!(auth_steps(&conn).unwrap(), vec![AuthStep::PublicKey]);
+        conn.auth_method = "password".to_string();
+        assert_eq!(auth_steps(&conn).unwrap(), vec![AuthStep::Password]);
+
+        assert!(auth_steps(&connection("password")).is_err());
+        assert!(auth_steps(&connection("kerberos")).is_err());
+    }
+}
@@ -13,9 +13,12 @@ use super::sync::{
     check_remote_symlink_exists, create_remote_symlink, list_remote_dir, read_remote_file_raw,
     remove_remote_path, sync_directory, write_remote_file,
 };
-use super::types::SyncProgress;
-use crate::coding::skills::central_repo::{resolve_central_repo_path, resolve_skill_central_path};
+use super::types::{SkillsChangePreview, SyncProgress};
+use crate::coding::skills::central_repo::{
+    resolve_central_repo_path, resolve_default_central_repo_path, resolve_skill_central_path,
+};
 use crate::coding::skills::skill_store;
+use crate::coding::sync_preview::{parse_skills_state, plan_skills, skills_state_command};
 use crate::coding::tools::builtin::BUILTIN_TOOLS;
 use crate::DbState;
 
@@ -49,7 +52,7 @@ fn get_all_skill_tool_keys() -> Vec<&'static str> {
 pub async fn sync_skills_to_ssh(
     state: &DbState,
     session: &SshSession,
-    app: AppHandle,
+    app: Option<&AppHandle>,
 ) -> Result<(), String> {
     let db = state.db();
     let config = get_ssh_config_internal(&db, false).await?;
@@ -62,9 +65,11 @@ pub async fn sync_skills_to_ssh(
 
     // Get all managed skills
     let skills = skill_store::get_managed_skills(state).await?;
-    let central_dir = resolve_central_repo_path(&app, state)
-        .await
-        .map_err(|e| format!("{}", e))?;
+    let central_dir = match app {
+        Some(app) => resolve_central_repo_path(app, state).await,
+        None => resolve_default_central_repo_path(state).await,
+    }
+    .map_err(|e| format!("{}", e))?;
 
     let total_skills = skills.len() as u32;
     info!(
@@ -74,16 +79,18 @@ pub async fn sync_skills_to_ssh(
     );
 
     // Emit initial progress
-    let _ = app.emit(
-        "ssh-sync-progress",
-        SyncProgress {
-            phase: "skills".to_string(),
-            current_item: "准备中...".to_string(),
-            current: 0,
-            total: total_skills,
-            message: format!("Skills 同步: 0/{}", total_skills),
-        },
-    );
+    if let Some(app) = app {
+        let _ = app.emit(
+            "ssh-sync-progress",
+            SyncProgress {
+                phase: "skills".to_string(),
+                current_item: "准备中...".to_string(),
+                current: 0,
+                total: total_skills,
+                message: format!("Skills 同步: 0/{}", total_skills),
+            },
+        );
+    }
 
     // 1. Get existing skills in remote central repo
     let existing_remote_skills = list_remote_dir(session, SSH_CENTRAL_DIR)
@@ -113,19 +120,21 @@ pub async fn sync_skills_to_ssh(
     for (idx, skill) in skills.iter().enumerate() {
         let current_idx = (idx + 1) as u32;
 
-        let _ = app.emit(
-            "ssh-sync-progress",
-            SyncProgress {
-                phase: "skills".to_string(),
-                current_item: skill.name.clone(),
-                current: current_idx,
-                total: total_skills,
-                message: format!(
-                    "Skills 同步: {}/{} - {}",
-                    current_idx, total_skills, skill.name
-                ),
-            },
-        );
+        if let Some(app) = app {
+            let _ = app.emit(
+                "ssh-sync-progress",
+                SyncProgress {
+                    phase: "skills".to_string(),
+                    current_item: skill.name.clone(),
+                    current: current_idx,
+                    total: total_skills,
+                    message: format!(
+                        "Skills 同步: {}/{} - {}",
+                        current_idx, total_skills, skill.name
+                    ),
+                },
+            );
+        }
 
         let source = resolve_skill_central_path(&skill.central_path, &central_dir);
         if !source.exists() {
@@ -208,7 +217,43 @@ pub async fn sync_skills_to_ssh(
         return Err(all_errors.join("; "));
     }
 
-    let _ = app.emit("ssh-skills-sync-completed", ());
+    if let Some(app) = app {
+        let _ = app.emit("ssh-skills-sync-completed", ());
+    }
 
     Ok(())
 }
+
+/// Preview skills sync changes on SSH remote without writing (dry-run)
+pub(super) async fn preview_skills_to_ssh(
+    state: &DbState,
+    session: &SshSession,
+    app: &AppHandle,
+) -> Result<SkillsChangePreview, String> {
+    let skills = skill_store::get_managed_skills(state).await?;
+    let central_dir = resolve_central_repo_path(app, state)
+        .await
+        .map_err(|e| format!("{}", e))?;
+    let unavailable: HashSet<String> = skills
+        .iter()
+        .filter(|s| !resolve_skill_central_path(&s.central_path, &central_dir).exists())
+        .map(|s| s.name.clone())
+        .collect();
+
+    let tool_dirs: Vec<(&str, String)> = get_all_skill_tool_keys()
+        .into_iter()
+        .filter_map(|key| get_remote_tool_skills_dir(key).map(|dir| (key, dir)))
+        .collect();
+    let dirs: Vec<String> = tool_dirs.iter().map(|(_, dir)| dir.clone()).collect();
+
+    let output = session
+        .exec_command(&skills_state_command(SSH_CENTRAL_DIR, &dirs))
+        .await?;
+    Ok(plan_skills(
+        &skills,
+        &unavailable,
+        &tool_dirs,
+        SSH_CENTRAL_DIR,
+        &parse_skills_state(&output),
+    ))
+}
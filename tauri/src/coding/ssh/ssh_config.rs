@@ -0,0 +1,589 @@
+//! OpenSSH 客户端配置（`~/.ssh/config`）解析
+//!
+//! 按 `ssh -G <alias>` 的规则解析主机别名：
+//! - 逐行读取，每个关键字取第一次出现的值（`IdentityFile` 可累积多个）
+//! - `Host` 模式支持 `*` / `?` 通配符与 `!` 否定
+//! - `Match` 仅支持 `all` / `host` / `originalhost` / `user`，其余条件（如 `exec`）视为不匹配
+//! - `Include` 支持通配符，相对路径相对于 `~/.ssh`
+//!
+//! 用于从已有的 SSH 配置导入 SSHConnection 预设。
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::known_hosts::wildcard_match;
+use super::types::{SSHConfigHost, SSHConnection, SSHJumpHost};
+
+/// 与 OpenSSH 一致的 Include 最大嵌套深度
+const MAX_INCLUDE_DEPTH: usize = 16;
+/// ProxyJump 递归解析的最大深度（防止配置中的循环引用）
+const MAX_JUMP_DEPTH: usize = 8;
+
+#[derive(Debug, Clone)]
+enum Condition {
+    /// `Host` 模式列表，匹配命令行上的别名
+    Host(Vec<String>),
+    /// `Match host`，匹配（已解析的）HostName
+    MatchHost(Vec<String>),
+    /// `Match originalhost`，匹配别名
+    MatchOriginalHost(Vec<String>),
+    /// `Match user`，匹配远程用户名
+    MatchUser(Vec<String>),
+    /// 不支持的 Match 条件
+    Never,
+}
+
+#[derive(Debug, Clone)]
+struct Directive {
+    conditions: Vec<Condition>,
+    keyword: String,
+    args: Vec<String>,
+}
+
+/// 解析后的配置文件（按出现顺序展开 Include）
+#[derive(Debug, Default)]
+pub struct SshConfig {
+    directives: Vec<Directive>,
+}
+
+/// 按 `ssh -G` 规则解析得到的主机设置
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResolvedHost {
+    pub alias: String,
+    pub host_name: String,
+    pub port: u16,
+    pub user: String,
+    pub identity_files: Vec<String>,
+    pub proxy_jump: Vec<String>,
+}
+
+// ============================================================================
+// Parsing
+// ============================================================================
+
+/// 拆分一行配置：关键字与参数之间可用空白或 `=` 分隔，参数支持双引号
+fn split_line(line: &str) -> Option<(String, Vec<String>)> {
+    let line = line.trim();
+    if line.is_empty() || line.starts_with('#') {
+        return None;
+    }
+
+    let key_end = line
+        .find(|c: char| c.is_whitespace() || c == '=')
+        .unwrap_or(line.len());
+    let keyword = line[..key_end].to_lowercase();
+    let rest = line[key_end..].trim_start();
+    let rest = rest.strip_prefix('=').unwrap_or(rest);
+
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+    let mut has_token = false;
+    for c in rest.chars() {
+        match c {
+            '"' => {
+                in_quotes = !in_quotes;
+                has_token = true;
+            }
+            c if c.is_whitespace() && !in_quotes => {
+                if has_token {
+                    args.push(std::mem::take(&mut current));
+                    has_token = false;
+                }
+            }
+            // 行内注释
+            '#' if !in_quotes && !has_token => break,
+            c => {
+                current.push(c);
+                has_token = true;
+            }
+        }
+    }
+    if has_token {
+        args.push(current);
+    }
+
+    Some((keyword, args))
+}
+
+fn parse_match(args: &[String]) -> Vec<Condition> {
+    let mut conditions = Vec::new();
+    let mut iter = args.iter();
+    while let Some(criterion) = iter.next() {
+        let criterion = criterion.to_lowercase();
+        if criterion == "all" {
+            continue;
+        }
+        let Some(value) = iter.next() else {
+            conditions.push(Condition::Never);
+            break;
+        };
+        let list: Vec<String> = value.split(',').map(|p| p.to_string()).collect();
+        conditions.push(match criterion.as_str() {
+            "host" => Condition::MatchHost(list),
+            "originalhost" => Condition::MatchOriginalHost(list),
+            "user" => Condition::MatchUser(list),
+            _ => Condition::Never,
+        });
+    }
+    conditions
+}
+
+fn expand_include_path(path: &str, base_dir: &Path) -> PathBuf {
+    if let Some(rest) = path.strip_prefix("~/") {
+        home_dir().unwrap_or_default().join(rest)
+    } else if Path::new(path).is_absolute() {
+        PathBuf::from(path)
+    } else {
+        base_dir.join(path)
+    }
+}
+
+impl SshConfig {
+    /// 解析配置内容；`base_dir` 为 Include 相对路径的基准目录
+    pub fn parse(content: &str, base_dir: &Path) -> Self {
+        let mut config = SshConfig::default();
+        config.parse_into(content, base_dir, &[], 0);
+        config
+    }
+
+    /// 读取用户配置 `~/.ssh/config`（不存在时返回空配置）
+    pub fn load_user_config() -> Self {
+        let Some(ssh_dir) = home_dir().map(|home| home.join(".ssh")) else {
+            return SshConfig::default();
+        };
+        let content = fs::read_to_string(ssh_dir.join("config")).unwrap_or_default();
+        Self::parse(&content, &ssh_dir)
+    }
+
+    fn parse_into(&mut self, content: &str, base_dir: &Path, outer: &[Condition], depth: usize) {
+        let mut current: Vec<Condition> = outer.to_vec();
+
+        for line in content.lines() {
+            let Some((keyword, args)) = split_line(line) else {
+                continue;
+            };
+
+            match keyword.as_str() {
+                "host" => {
+                    current = outer.to_vec();
+                    current.push(Condition::Host(args));
+                }
+                "match" => {
+                    current = outer.to_vec();
+                    current.extend(parse_match(&args));
+                }
+                "include" => {
+                    if depth >= MAX_INCLUDE_DEPTH {
+                        log::warn!("ssh config Include 嵌套过深，已忽略");
+                        continue;
+                    }
+                    for arg in &args {
+                        let pattern = expand_include_path(arg, base_dir);
+                        let Ok(paths) = glob::glob(&pattern.to_string_lossy()) else {
+                            continue;
+                        };
+                        for path in paths.filter_map(|p| p.ok()) {
+                            if let Ok(included) = fs::read_to_string(&path) {
+                                self.parse_into(&included, base_dir, &current, depth + 1);
+                            }
+                        }
+                    }
+                }
+                _ => self.directives.push(Directive {
+                    conditions: current.clone(),
+                    keyword,
+                    args,
+                }),
+            }
+        }
+    }
+
+    /// 配置中所有可直接导入的主机别名（不含通配符与否定模式），按出现顺序去重
+    pub fn host_aliases(&self) -> Vec<String> {
+        let mut aliases: Vec<String> = Vec::new();
+        for directive in &self.directives {
+            for condition in &directive.conditions {
+                let Condition::Host(patterns) = condition else {
+                    continue;
+                };
+                for pattern in patterns {
+                    let concrete = !pattern.starts_with('!')
+                        && !pattern.contains('*')
+                        && !pattern.contains('?');
+                    if concrete && !aliases.contains(pattern) {
+                        aliases.push(pattern.clone());
+                    }
+                }
+            }
+        }
+        aliases
+    }
+
+    /// 按 `ssh -G` 规则解析别名
+    pub fn resolve(&self, alias: &str) -> ResolvedHost {
+        let local_user = local_username();
+        let mut host_name: Option<String> = None;
+        let mut port: Option<u16> = None;
+        let mut user: Option<String> = None;
+        let mut proxy_jump: Option<Vec<String>> = None;
+        let mut identity_files: Vec<String> = Vec::new();
+
+        for directive in &self.directives {
+            let current_host = host_name.as_deref().unwrap_or(alias);
+            let current_user = user.as_deref().unwrap_or(&local_user);
+            let active = directive
+                .conditions
+                .iter()
+                .all(|c| condition_matches(c, alias, current_host, current_user));
+            if !active {
+                continue;
+            }
+
+            let Some(first) = directive.args.first() else {
+                continue;
+            };
+            match directive.keyword.as_str() {
+                "hostname" if host_name.is_none() => host_name = Some(first.clone()),
+                "port" if port.is_none() => port = first.parse().ok(),
+                "user" if user.is_none() => user = Some(first.clone()),
+                "proxyjump" if proxy_jump.is_none() => {
+                    proxy_jump = Some(if first.eq_ignore_ascii_case("none") {
+                        Vec::new()
+                    } else {
+                        first
+                            .split(',')
+                            .filter(|s| !s.is_empty())
+                            .map(|s| s.to_string())
+                            .collect()
+                    });
+                }
+                "identityfile"
+                    if !first.eq_ignore_ascii_case("none") && !identity_files.contains(first) =>
+                {
+                    identity_files.push(first.clone());
+                }
+                _ => {}
+            }
+        }
+
+        let host_name = expand_tokens(host_name.as_deref().unwrap_or("%h"), alias, alias, 22, "");
+        let port = port.unwrap_or(22);
+        let user = user.unwrap_or(local_user);
+        let identity_files = identity_files
+            .iter()
+            .map(|file| expand_tilde(&expand_tokens(file, alias, &host_name, port, &user)))
+            .collect();
+
+        ResolvedHost {
+            alias: alias.to_string(),
+            host_name,
+            port,
+            user,
+            identity_files,
+            proxy_jump: proxy_jump.unwrap_or_default(),
+        }
+    }
+
+    /// 将 ProxyJump 链解析为跳板机列表（跳板机自身的别名与 ProxyJump 也会被解析）
+    pub fn resolve_jump_hosts(&self, resolved: &ResolvedHost) -> Vec<SSHJumpHost> {
+        let mut jumps = Vec::new();
+        self.collect_jump_hosts(&resolved.proxy_jump, 0, &mut jumps);
+        jumps
+    }
+
+    fn collect_jump_hosts(&self, specs: &[String], depth: usize, out: &mut Vec<SSHJumpHost>) {
+        if depth >= MAX_JUMP_DEPTH {
+            log::warn!("ssh config ProxyJump 嵌套过深，已忽略");
+            return;
+        }
+        for spec in specs {
+            let (spec_user, spec_host, spec_port) = parse_jump_spec(spec);
+            let jump = self.resolve(&spec_host);
+            // 跳板机自身还需要经过其它跳板机时，先连接那些跳板机
+            self.collect_jump_hosts(&jump.proxy_jump, depth + 1, out);
+
+            let identity = first_existing_identity(&jump.identity_files);
+            out.push(SSHJumpHost {
+                host: jump.host_name.clone(),
+                port: spec_port.unwrap_or(jump.port),
+                username: spec_user.unwrap_or(jump.user.clone()),
+                auth_method: if identity.is_some() { "key" } else { "agent" }.to_string(),
+                password: String::new(),
+                private_key_path: identity.unwrap_or_default(),
+                private_key_content: String::new(),
+                passphrase: String::new(),
+            });
+        }
+    }
+}
+
+fn patterns_match(patterns: &[String], value: &str) -> bool {
+    let value = value.to_lowercase();
+    let mut matched = false;
+    for pattern in patterns {
+        if let Some(negated) = pattern.strip_prefix('!') {
+            if wildcard_match(&negated.to_lowercase(), &value) {
+                return false;
+            }
+        } else if wildcard_match(&pattern.to_lowercase(), &value) {
+            matched = true;
+        }
+    }
+    matched
+}
+
+fn condition_matches(condition: &Condition, alias: &str, host: &str, user: &str) -> bool {
+    match condition {
+        Condition::Host(patterns) | Condition::MatchOriginalHost(patterns) => {
+            patterns_match(patterns, alias)
+        }
+        Condition::MatchHost(patterns) => patterns_match(patterns, host),
+        Condition::MatchUser(patterns) => patterns_match(patterns, user),
+        Condition::Never => false,
+    }
+}
+
+/// 解析 ProxyJump 单项：`[user@]host[:port]` 或 `ssh://[user@]host[:port]`
+fn parse_jump_spec(spec: &str) -> (Option<String>, String, Option<u16>) {
+    let spec = spec.trim();
+    let spec = spec.strip_prefix("ssh://").unwrap_or(spec);
+    let (user, host_port) = match spec.rsplit_once('@') {
+        Some((user, rest)) => (Some(user.to_string()), rest),
+        None => (None, spec),
+    };
+
+    // [IPv6]:port
+    if let Some(rest) = host_port.strip_prefix('[') {
+        if let Some((host, tail)) = rest.split_once(']') {
+            let port = tail.strip_prefix(':').and_then(|p| p.parse().ok());
+            return (user, host.to_string(), port);
+        }
+    }
+
+    match host_port.rsplit_once(':') {
+        Some((host, port)) if !host.contains(':') => (user, host.to_string(), port.parse().ok()),
+        _ => (user, host_port.to_string(), None),
+    }
+}
+
+/// 展开 `%%` `%d` `%h` `%n` `%p` `%r` `%u` token
+fn expand_tokens(value: &str, alias: &str, host: &str, port: u16, user: &str) -> String {
+    if !value.contains('%') {
+        return value.to_string();
+    }
+
+    let mut result = String::new();
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            result.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('%') => result.push('%'),
+            Some('d') => result.push_str(&home_dir().unwrap_or_default().to_string_lossy()),
+            Some('h') => result.push_str(host),
+            Some('n') => result.push_str(alias),
+            Some('p') => result.push_str(&port.to_string()),
+            Some('r') => result.push_str(user),
+            Some('u') => result.push_str(&local_username()),
+            Some(other) => {
+                result.push('%');
+                result.push(other);
+            }
+            None => result.push('%'),
+        }
+    }
+    result
+}
+
+fn expand_tilde(path: &str) -> String {
+    match (path.strip_prefix("~/"), home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest).to_string_lossy().to_string(),
+        _ => path.to_string(),
+    }
+}
+
+fn first_existing_identity(files: &[String]) -> Option<String> {
+    files.iter().find(|file| Path::new(file).exists()).cloned()
+}
+
+fn home_dir() -> Option<PathBuf> {
+    std::env::var("USERPROFILE")
+        .or_else(|_| std::env::var("HOME"))
+        .ok()
+        .map(PathBuf::from)
+}
+
+fn local_username() -> String {
+    std::env::var("USER")
+        .or_else(|_| std::env::var("USERNAME"))
+        .unwrap_or_default()
+}
+
+// ============================================================================
+// Import
+// ============================================================================
+
+impl ResolvedHost {
+    /// 第一个存在的 IdentityFile（导入时作为私钥路径）
+    pub fn identity_file(&self) -> Option<String> {
+        first_existing_identity(&self.identity_files)
+    }
+
+    pub fn to_config_host(&self, imported: bool) -> SSHConfigHost {
+        SSHConfigHost {
+            alias: self.alias.clone(),
+            host_name: self.host_name.clone(),
+            port: self.port,
+            user: self.user.clone(),
+            identity_file: self.identity_file().unwrap_or_default(),
+            proxy_jump: self.proxy_jump.clone(),
+            imported,
+        }
+    }
+}
+
+/// 由 ssh config 中的别名生成 SSHConnection 预设
+///
+/// 有可用的 IdentityFile 时使用密钥认证，否则使用 ssh-agent（与 ssh 默认行为一致）
+pub fn connection_from_alias(
+    config: &SshConfig,
+    alias: &str,
+    id: String,
+    sort_order: u32,
+) -> SSHConnection {
+    let resolved = config.resolve(alias);
+    let identity = resolved.identity_file();
+
+    SSHConnection {
+        id,
+        name: alias.to_string(),
+        host: resolved.host_name.clone(),
+        port: resolved.port,
+        username: resolved.user.clone(),
+        auth_method: if identity.is_some() { "key" } else { "agent" }.to_string(),
+        password: String::new(),
+        private_key_path: identity.unwrap_or_default(),
+        private_key_content: String::new(),
+        passphrase: String::new(),
+        sort_order,
+        jump_hosts: config.resolve_jump_hosts(&resolved),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(content: &str) -> SshConfig {
+        SshConfig::parse(content, Path::new("/nonexistent"))
+    }
+
+    #[test]
+    fn test_first_value_wins() {
+        let config = parse(
+            r#"
+# comment
+Host web
+    HostName 10.0.0.5
+    Port 2222
+    User deploy
+
+Host web staging
+    HostName ignored.example.com
+    User other
+    IdentityFile ~/.ssh/web_key
+
+Host *
+    Port 22
+    User fallback
+    IdentityFile=~/.ssh/id_%h
+"#,
+        );
+
+        let web = config.resolve("web");
+        assert_eq!(web.host_name, "10.0.0.5");
+        assert_eq!(web.port, 2222);
+        assert_eq!(web.user, "deploy");
+        assert_eq!(web.identity_files.len(), 2);
+        assert!(web.identity_files[1].ends_with("id_10.0.0.5"));
+
+        let staging = config.resolve("staging");
+        assert_eq!(staging.host_name, "ignored.example.com");
+        assert_eq!(staging.user, "other");
+        assert_eq!(staging.port, 22);
+
+        let other = config.resolve("unknown.example.com");
+        assert_eq!(other.host_name, "unknown.example.com");
+        assert_eq!(other.user, "fallback");
+    }
+
+    #[test]
+    fn test_patterns_negation_and_match() {
+        let config = parse(
+            r#"
+Host *.internal !db.internal
+    User internal
+Match host 10.*
+    Port 2200
+Match exec "true"
+    User never
+Host db.internal
+    HostName 10.1.2.3
+"#,
+        );
+
+        assert_eq!(config.resolve("app.internal").user, "internal");
+        let db = config.resolve("db.internal");
+        assert_ne!(db.user, "internal");
+        assert_ne!(db.user, "never");
+        assert_eq!(db.host_name, "10.1.2.3");
+        // Match host 在 HostName 解析之前出现，匹配的是别名
+        assert_eq!(db.port, 22);
+
+        assert_eq!(config.host_aliases(), vec!["db.internal".to_string()]);
+    }
+
+    #[test]
+    fn test_proxy_jump_chain() {
+        let config = parse(
+            r#"
+Host target
+    HostName 192.168.0.10
+    ProxyJump bastion,ops@10.0.0.2:2022
+Host bastion
+    HostName bastion.example.com
+    User jump
+    ProxyJump edge
+Host edge
+    HostName edge.example.com
+    Port 443
+"#,
+        );
+
+        let target = config.resolve("target");
+        assert_eq!(target.proxy_jump, vec!["bastion", "ops@10.0.0.2:2022"]);
+
+        let jumps = config.resolve_jump_hosts(&target);
+        let hops: Vec<(String, u16, String)> = jumps
+            .iter()
+            .map(|j| (j.host.clone(), j.port, j.username.clone()))
+            .collect();
+        assert_eq!(hops[0].0, "edge.example.com");
+        assert_eq!(hops[0].1, 443);
+        assert_eq!(hops[1], ("bastion.example.com".into(), 22, "jump".into()));
+        assert_eq!(hops[2], ("10.0.0.2".into(), 2022, "ops".into()));
+        assert!(jumps.iter().all(|j| j.auth_method == "agent"));
+    }
+
+    #[test]
+    fn test_parse_jump_spec() {
+        assert_eq!(
+            parse_jump_spec("ssh://me@[::1]:2200"),
+            (Some("me".into()), "::1".into(), Some(2200))
+        );
+        assert_eq!(parse_jump_spec("host"), (None, "host".into(), None));
+    }
+}
@@ -1,5 +1,16 @@
-use super::session::{self, upload_file_via_sftp, SshSession};
-use super::types::{SSHConnection, SSHConnectionResult, SSHFileMapping, SyncResult};
+use super::known_hosts::wildcard_match;
+use super::session::{
+    self, download_file_via_sftp, upload_file_via_sftp, ConnectError, SshSession,
+};
+use super::sync_state::{content_hash, decide, BaseHashes, SyncAction};
+use super::types::{
+    FileChangePreview, SSHConnection, SSHConnectionResult, SSHFileMapping, SyncConflict,
+    SyncPreview, SyncResult,
+};
+use crate::coding::sync_preview::{
+    decode_text, parse_hash_output, pushed_text, remote_text, text_change, HASH_CMD, MAX_DIFF_BYTES,
+};
+use std::collections::BTreeMap;
 use std::path::Path;
 
 // ============================================================================
@@ -20,12 +31,17 @@ pub async fn test_connection(conn: &SSHConnection) -> SSHConnectionResult {
                 } else {
                     Some(server_info)
                 },
+                host_key_error: None,
             }
         }
         Err(e) => SSHConnectionResult {
             connected: false,
-            error: Some(e),
+            error: Some(e.to_string()),
             server_info: None,
+            host_key_error: match e {
+                ConnectError::HostKey(host_key_error) => Some(host_key_error),
+                ConnectError::Other(_) => None,
+            },
         },
     }
 }
@@ -174,18 +190,43 @@ pub async fn sync_pattern_files(
     Ok(synced)
 }
 
+/// 单个文件映射的同步结果
+#[derive(Debug, Default)]
+pub struct MappingSyncOutcome {
+    pub synced: Vec<String>,
+    pub conflicts: Vec<SyncConflict>,
+}
+
 /// 同步单个文件映射
+///
+/// `push` 方向沿用整体上传；`pull` / `both` 方向逐文件比较 hash，
+/// 两端都发生变化的文件记为冲突，不覆盖任何一端。
 pub async fn sync_file_mapping(
     mapping: &SSHFileMapping,
     session: &SshSession,
-) -> Result<Vec<String>, String> {
-    if mapping.is_directory {
-        sync_directory(&mapping.local_path, &mapping.remote_path, session).await
-    } else if mapping.is_pattern {
-        sync_pattern_files(&mapping.local_path, &mapping.remote_path, session).await
-    } else {
-        sync_single_file(&mapping.local_path, &mapping.remote_path, session).await
+) -> Result<MappingSyncOutcome, String> {
+    if mapping.direction != "pull" && mapping.direction != "both" {
+        let synced = if mapping.is_directory {
+            sync_directory(&mapping.local_path, &mapping.remote_path, session).await?
+        } else if mapping.is_pattern {
+            sync_pattern_files(&mapping.local_path, &mapping.remote_path, session).await?
+        } else {
+            sync_single_file(&mapping.local_path, &mapping.remote_path, session).await?
+        };
+        return Ok(MappingSyncOutcome {
+            synced,
+            conflicts: vec![],
+        });
+    }
+
+    let connection_id = session.conn().map(|c| c.id.clone()).unwrap_or_default();
+    let mut base = BaseHashes::load(&connection_id);
+    let result = sync_file_mapping_by_hash(mapping, session, &mut base).await;
+    // 即使中途出错，也保存已成功同步文件的基准 hash
+    if let Err(e) = base.save() {
+        log::warn!("保存同步状态失败: {}", e);
     }
+    result
 }
 
 /// 同步所有启用的文件映射
@@ -197,6 +238,7 @@ pub async fn sync_mappings(
     let mut synced_files = vec![];
     let mut skipped_files = vec![];
     let mut errors = vec![];
+    let mut conflicts = vec![];
 
     let filtered_mappings: Vec<_> = mappings
         .iter()
@@ -206,11 +248,12 @@ pub async fn sync_mappings(
 
     for mapping in filtered_mappings {
         match sync_file_mapping(mapping, session).await {
-            Ok(files) if files.is_empty() => {
+            Ok(outcome) if outcome.synced.is_empty() && outcome.conflicts.is_empty() => {
                 skipped_files.push(mapping.name.clone());
             }
-            Ok(files) => {
-                synced_files.extend(files);
+            Ok(outcome) => {
+                synced_files.extend(outcome.synced);
+                conflicts.extend(outcome.conflicts);
             }
             Err(e) => {
                 errors.push(format!("{}: {}", mapping.name, e));
@@ -223,7 +266,444 @@ pub async fn sync_mappings(
         synced_files,
         skipped_files,
         errors,
+        conflicts,
+        hosts: vec![],
+    }
+}
+
+// ============================================================================
+// Pull / Two-way Sync (逐文件比较 hash)
+// ============================================================================
+
+/// 参与比较的单个文件
+struct FileEntry {
+    /// 展开后的本地绝对路径
+    local_path: String,
+    /// 远程路径（可能包含 ~）
+    remote_path: String,
+    local_hash: Option<String>,
+    remote_hash: Option<String>,
+}
+
+async fn local_file_hash(path: &Path) -> Option<String> {
+    tokio::fs::read(path)
+        .await
+        .ok()
+        .map(|data| content_hash(&data))
+}
+
+async fn remote_file_hash(
+    session: &SshSession,
+    remote_path: &str,
+) -> Result<Option<String>, String> {
+    let target = remote_path.replace("~", "$HOME");
+    let command = format!(
+        "{} if [ -f \"{}\" ]; then $H \"{}\"; fi",
+        HASH_CMD, target, target
+    );
+    let output = session.exec_command(&command).await?;
+    Ok(parse_hash_output(&output)
+        .into_iter()
+        .next()
+        .map(|(_, hash)| hash))
+}
+
+/// 远程目录下文件的 hash（key 为相对路径）
+async fn remote_dir_hashes(
+    session: &SshSession,
+    remote_dir: &str,
+    recursive: bool,
+) -> Result<BTreeMap<String, String>, String> {
+    let target = remote_dir.replace("~", "$HOME");
+    let depth = if recursive { "" } else { " -maxdepth 1" };
+    let command = format!(
+        "{} if [ -d \"{}\" ]; then cd \"{}\" && find .{} -type f -exec $H {{}} +; fi",
+        HASH_CMD, target, target, depth
+    );
+    let output = session.exec_command(&command).await?;
+
+    Ok(parse_hash_output(&output)
+        .into_iter()
+        .map(|(path, hash)| (path.trim_start_matches("./").to_string(), hash))
+        .collect())
+}
+
+/// 收集映射涉及的所有文件及两端 hash
+async fn collect_entries(
+    mapping: &SSHFileMapping,
+    session: &SshSession,
+) -> Result<Vec<FileEntry>, String> {
+    let expanded = expand_local_path(&mapping.local_path)?;
+
+    if !mapping.is_directory && !mapping.is_pattern {
+        return Ok(vec![FileEntry {
+            local_hash: local_file_hash(Path::new(&expanded)).await,
+            remote_hash: remote_file_hash(session, &mapping.remote_path).await?,
+            local_path: expanded,
+            remote_path: mapping.remote_path.clone(),
+        }]);
+    }
+
+    let remote_dir = mapping.remote_path.trim_end_matches('/').to_string();
+    // 相对路径 -> (本地 hash, 远程 hash)
+    let mut files: BTreeMap<String, (Option<String>, Option<String>)> = BTreeMap::new();
+
+    let local_dir = if mapping.is_directory {
+        for entry in walkdir::WalkDir::new(&expanded)
+            .follow_links(true)
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_file())
+        {
+            let Ok(relative) = entry.path().strip_prefix(&expanded) else {
+                continue;
+            };
+            let relative = relative.to_string_lossy().replace('\\', "/");
+            files.entry(relative).or_default().0 = local_file_hash(entry.path()).await;
+        }
+        for (relative, hash) in remote_dir_hashes(session, &remote_dir, true).await? {
+            files.entry(relative).or_default().1 = Some(hash);
+        }
+        expanded.clone()
+    } else {
+        let pattern_path = Path::new(&expanded);
+        let name_pattern = pattern_path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let matches = glob::glob(&expanded).map_err(|e| format!("无效的 glob 模式: {}", e))?;
+        for path in matches.filter_map(|p| p.ok()).filter(|p| p.is_file()) {
+            let name = path
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default();
+            files.entry(name).or_default().0 = local_file_hash(&path).await;
+        }
+        for (name, hash) in remote_dir_hashes(session, &remote_dir, false).await? {
+            if wildcard_match(&name_pattern, &name) {
+                files.entry(name).or_default().1 = Some(hash);
+            }
+        }
+        pattern_path
+            .parent()
+            .map(|p| p.to_string_lossy().to_string())
+            .unwrap_or_default()
+    };
+
+    Ok(files
+        .into_iter()
+        .map(|(relative, (local_hash, remote_hash))| FileEntry {
+            local_path: Path::new(&local_dir)
+                .join(&relative)
+                .to_string_lossy()
+                .to_string(),
+            remote_path: format!("{}/{}", remote_dir, relative),
+            local_hash,
+            remote_hash,
+        })
+        .collect())
+}
+
+/// 按 hash 比较结果逐文件拉取 / 推送，记录冲突
+async fn sync_file_mapping_by_hash(
+    mapping: &SSHFileMapping,
+    session: &SshSession,
+    base: &mut BaseHashes,
+) -> Result<MappingSyncOutcome, String> {
+    let mut outcome = MappingSyncOutcome::default();
+
+    let entries = collect_entries(mapping, session).await?;
+    if entries.is_empty() {
+        return Ok(outcome);
+    }
+
+    // 复用同一个 SFTP session 传输所有文件
+    let sftp = session.create_sftp_session().await?;
+
+    for entry in entries {
+        let base_hash = base
+            .get(&entry.local_path, &entry.remote_path)
+            .map(|h| h.to_string());
+        let action = decide(
+            &mapping.direction,
+            base_hash.as_deref(),
+            entry.local_hash.as_deref(),
+            entry.remote_hash.as_deref(),
+        );
+
+        match action {
+            SyncAction::None => {
+                if let (Some(hash), true) =
+                    (&entry.local_hash, entry.local_hash == entry.remote_hash)
+                {
+                    base.set(&entry.local_path, &entry.remote_path, hash.clone());
+                }
+            }
+            SyncAction::Push => {
+                upload_file_via_sftp(&sftp, &entry.local_path, &entry.remote_path).await?;
+                if let Some(hash) = entry.local_hash {
+                    base.set(&entry.local_path, &entry.remote_path, hash);
+                }
+                outcome
+                    .synced
+                    .push(format!("{} -> {}", entry.local_path, entry.remote_path));
+            }
+            SyncAction::Pull => {
+                download_file_via_sftp(&sftp, &entry.remote_path, &entry.local_path).await?;
+                if let Some(hash) = entry.remote_hash {
+                    base.set(&entry.local_path, &entry.remote_path, hash);
+                }
+                outcome
+                    .synced
+                    .push(format!("{} <- {}", entry.local_path, entry.remote_path));
+            }
+            SyncAction::Conflict => {
+                outcome.conflicts.push(SyncConflict {
+                    mapping_id: mapping.id.clone(),
+                    mapping_name: mapping.name.clone(),
+                    local_path: entry.local_path,
+                    remote_path: entry.remote_path,
+                    local_exists: entry.local_hash.is_some(),
+                    remote_exists: entry.remote_hash.is_some(),
+                });
+            }
+        }
+    }
+
+    Ok(outcome)
+}
+
+/// 解决冲突：保留一端（"local" | "remote"）覆盖另一端，并更新基准 hash
+pub async fn resolve_conflict(
+    session: &SshSession,
+    conflict: &SyncConflict,
+    keep: &str,
+) -> Result<(), String> {
+    match keep {
+        "local" => {
+            session
+                .upload_file(&conflict.local_path, &conflict.remote_path)
+                .await?
+        }
+        "remote" => {
+            session
+                .download_file(&conflict.remote_path, &conflict.local_path)
+                .await?
+        }
+        _ => return Err(format!("无效的冲突处理方式: {}", keep)),
+    }
+
+    let hash = local_file_hash(Path::new(&conflict.local_path))
+        .await
+        .ok_or_else(|| format!("读取本地文件失败: {}", conflict.local_path))?;
+
+    let connection_id = session.conn().map(|c| c.id.clone()).unwrap_or_default();
+    let mut base = BaseHashes::load(&connection_id);
+    base.set(&conflict.local_path, &conflict.remote_path, hash);
+    base.save()
+}
+
+// ============================================================================
+// Dry-run Preview (只读，不写入任何文件)
+// ============================================================================
+
+/// 读取远程文本文件用于 diff；文件过大或不是文本时返回 None
+async fn read_remote_text(session: &SshSession, remote_path: &str) -> Option<String> {
+    let target = remote_path.replace("~", "$HOME");
+    // 输出以 T 开头表示文件存在且未超过大小上限
+    let command = format!(
+        "if [ -f \"{}\" ] && [ \"$(wc -c < \"{}\")\" -le {} ]; then printf T; cat \"{}\"; fi",
+        target, target, MAX_DIFF_BYTES, target
+    );
+    let output = session.exec_command(&command).await.ok()?;
+    remote_text(output.strip_prefix('T')?.to_string())
+}
+
+fn file_change(
+    mapping: &SSHFileMapping,
+    action: &str,
+    direction: &str,
+    source_path: &str,
+    target_path: &str,
+    diff: Option<String>,
+) -> FileChangePreview {
+    FileChangePreview {
+        mapping_id: mapping.id.clone(),
+        mapping_name: mapping.name.clone(),
+        action: action.to_string(),
+        direction: direction.to_string(),
+        source_path: source_path.to_string(),
+        target_path: target_path.to_string(),
+        diff,
+    }
+}
+
+/// 预览单个文件映射：逐文件比较两端 hash，给出将要执行的动作及文本 diff
+///
+/// 返回空列表表示源不存在，同步时会被跳过。
+pub async fn preview_file_mapping(
+    mapping: &SSHFileMapping,
+    session: &SshSession,
+) -> Result<Vec<FileChangePreview>, String> {
+    let two_way = mapping.direction == "pull" || mapping.direction == "both";
+    let entries = collect_entries(mapping, session).await?;
+    if entries
+        .iter()
+        .all(|e| e.local_hash.is_none() && (!two_way || e.remote_hash.is_none()))
+    {
+        return Ok(vec![]);
     }
+
+    let connection_id = session.conn().map(|c| c.id.clone()).unwrap_or_default();
+    let base = BaseHashes::load(&connection_id);
+    let mut changes = vec![];
+
+    for entry in &entries {
+        let action = if two_way {
+            decide(
+                &mapping.direction,
+                base.get(&entry.local_path, &entry.remote_path),
+                entry.local_hash.as_deref(),
+                entry.remote_hash.as_deref(),
+            )
+        } else if entry.local_hash.is_none() {
+            // 目录推送会整体替换远程目录，本地不存在的文件将被删除
+            if mapping.is_directory && entry.remote_hash.is_some() {
+                changes.push(file_change(
+                    mapping,
+                    "delete",
+                    "push",
+                    &entry.local_path,
+                    &entry.remote_path,
+                    None,
+                ));
+            }
+            continue;
+        } else {
+            decide(
+                "push",
+                None,
+                entry.local_hash.as_deref(),
+                entry.remote_hash.as_deref(),
+            )
+        };
+
+        match action {
+            SyncAction::None => {}
+            SyncAction::Push => {
+                let local = tokio::fs::read(&entry.local_path).await.ok();
+                let source = local.as_deref().and_then(|data| {
+                    pushed_text(&mapping.id, &mapping.module, &entry.remote_path, data)
+                });
+                let target = match entry.remote_hash {
+                    Some(_) => read_remote_text(session, &entry.remote_path).await,
+                    None => Some(String::new()),
+                };
+                let action = if entry.remote_hash.is_some() {
+                    "update"
+                } else {
+                    "create"
+                };
+                if let Some(diff) = text_change(
+                    target.as_deref(),
+                    source.as_deref(),
+                    &entry.remote_path,
+                    &entry.local_path,
+                ) {
+                    changes.push(file_change(
+                        mapping,
+                        action,
+                        "push",
+                        &entry.local_path,
+                        &entry.remote_path,
+                        diff,
+                    ));
+                }
+            }
+            SyncAction::Pull => {
+                let source = read_remote_text(session, &entry.remote_path).await;
+                let target = match entry.local_hash {
+                    Some(_) => tokio::fs::read(&entry.local_path)
+                        .await
+                        .ok()
+                        .and_then(|data| decode_text(&data)),
+                    None => Some(String::new()),
+                };
+                let action = if entry.local_hash.is_some() {
+                    "update"
+                } else {
+                    "create"
+                };
+                if let Some(diff) = text_change(
+                    target.as_deref(),
+                    source.as_deref(),
+                    &entry.local_path,
+                    &entry.remote_path,
+                ) {
+                    changes.push(file_change(
+                        mapping,
+                        action,
+                        "pull",
+                        &entry.remote_path,
+                        &entry.local_path,
+                        diff,
+                    ));
+                }
+            }
+            SyncAction::Conflict => {
+                changes.push(file_change(
+                    mapping,
+                    "conflict",
+                    &mapping.direction,
+                    &entry.local_path,
+                    &entry.remote_path,
+                    None,
+                ));
+            }
+        }
+    }
+
+    if changes.is_empty() {
+        changes.push(file_change(
+            mapping,
+            "unchanged",
+            if two_way { &mapping.direction } else { "push" },
+            &mapping.local_path,
+            &mapping.remote_path,
+            None,
+        ));
+    }
+
+    Ok(changes)
+}
+
+/// 预览所有启用的文件映射（dry-run，不写入任何文件）
+pub async fn preview_mappings(
+    mappings: &[SSHFileMapping],
+    session: &SshSession,
+    module_filter: Option<&str>,
+) -> SyncPreview {
+    let mut preview = SyncPreview::default();
+
+    let filtered_mappings: Vec<_> = mappings
+        .iter()
+        .filter(|m| m.enabled)
+        .filter(|m| module_filter.is_none() || Some(m.module.as_str()) == module_filter)
+        .collect();
+
+    for mapping in filtered_mappings {
+        match preview_file_mapping(mapping, session).await {
+            Ok(changes) if changes.is_empty() => {
+                preview.skipped_files.push(mapping.name.clone());
+            }
+            Ok(changes) => preview.files.extend(changes),
+            Err(e) => {
+                preview.errors.push(format!("{}: {}", mapping.name, e));
+            }
+        }
+    }
+
+    preview
 }
 
 // ============================================================================
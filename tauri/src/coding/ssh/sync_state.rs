@@ -0,0 +1,186 @@
+//! 双向同步基准状态
+//!
+//! 记录每个连接下每个文件最近一次同步后两端一致的内容 hash（SHA-256），
+//! 用于判断自上次同步以来是哪一端发生了变化：
+//! - 只有一端变化：将该端同步到另一端
+//! - 两端都变化且内容不同：记为冲突，不覆盖任何一端
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{Mutex, OnceLock};
+
+use sha2::{Digest, Sha256};
+
+const STORE_FILE_NAME: &str = "ssh_sync_state.json";
+
+static STORE_DIR: OnceLock<PathBuf> = OnceLock::new();
+/// 保护存储文件的读-改-写
+static STORE_LOCK: Mutex<()> = Mutex::new(());
+
+/// connection_id -> (entry key -> sha256)
+type Store = BTreeMap<String, BTreeMap<String, String>>;
+
+/// Set the directory for the sync state store (call once at startup)
+pub fn set_store_dir(dir: PathBuf) {
+    let _ = STORE_DIR.set(dir);
+}
+
+fn get_store_file_path() -> Option<PathBuf> {
+    STORE_DIR.get().map(|dir| dir.join(STORE_FILE_NAME))
+}
+
+fn read_store() -> Store {
+    get_store_file_path()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+/// Atomic write: write to .tmp then rename
+fn write_store(store: &Store) -> Result<(), String> {
+    let path = get_store_file_path().ok_or("同步状态存储目录未初始化")?;
+    let content =
+        serde_json::to_string_pretty(store).map_err(|e| format!("序列化同步状态失败: {}", e))?;
+    let tmp_path = path.with_extension("json.tmp");
+    fs::write(&tmp_path, content).map_err(|e| format!("写入同步状态失败: {}", e))?;
+    fs::rename(&tmp_path, &path).map_err(|e| format!("写入同步状态失败: {}", e))
+}
+
+fn entry_key(local_path: &str, remote_path: &str) -> String {
+    format!("{} -> {}", local_path, remote_path)
+}
+
+/// 计算内容的 SHA-256（小写 hex）
+pub fn content_hash(data: &[u8]) -> String {
+    hex::encode(Sha256::digest(data))
+}
+
+/// 某个连接的基准 hash 集合，修改后调用 `save` 写回
+pub struct BaseHashes {
+    connection_id: String,
+    entries: BTreeMap<String, String>,
+    changes: BTreeMap<String, String>,
+}
+
+impl BaseHashes {
+    pub fn load(connection_id: &str) -> Self {
+        let _guard = STORE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        Self {
+            connection_id: connection_id.to_string(),
+            entries: read_store().remove(connection_id).unwrap_or_default(),
+            changes: BTreeMap::new(),
+        }
+    }
+
+    pub fn get(&self, local_path: &str, remote_path: &str) -> Option<&str> {
+        self.entries
+            .get(&entry_key(local_path, remote_path))
+            .map(|s| s.as_str())
+    }
+
+    pub fn set(&mut self, local_path: &str, remote_path: &str, hash: String) {
+        let key = entry_key(local_path, remote_path);
+        if self.entries.get(&key) != Some(&hash) {
+            self.entries.insert(key.clone(), hash.clone());
+            self.changes.insert(key, hash);
+        }
+    }
+
+    /// 只写回本次修改的条目，避免覆盖其它写入者的更新
+    pub fn save(self) -> Result<(), String> {
+        if self.changes.is_empty() || self.connection_id.is_empty() {
+            return Ok(());
+        }
+        let _guard = STORE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let mut store = read_store();
+        store
+            .entry(self.connection_id)
+            .or_default()
+            .extend(self.changes);
+        write_store(&store)
+    }
+}
+
+/// 单个文件的同步动作
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyncAction {
+    /// 两端一致（或两端都不存在）
+    None,
+    /// 本地 → 远程
+    Push,
+    /// 远程 → 本地
+    Pull,
+    /// 两端都在上次同步后发生了变化
+    Conflict,
+}
+
+/// 根据同步方向、基准 hash 与两端当前 hash 决定动作
+///
+/// 删除不会被同步：只存在于一端的文件总是复制到另一端。
+pub fn decide(
+    direction: &str,
+    base: Option<&str>,
+    local: Option<&str>,
+    remote: Option<&str>,
+) -> SyncAction {
+    if local == remote {
+        return SyncAction::None;
+    }
+
+    match direction {
+        "pull" if remote.is_some() => SyncAction::Pull,
+        "pull" => SyncAction::None,
+        "both" => match (local, remote) {
+            (None, Some(_)) => SyncAction::Pull,
+            (Some(_), None) => SyncAction::Push,
+            _ => {
+                let local_changed = local != base;
+                let remote_changed = remote != base;
+                match (local_changed, remote_changed) {
+                    (true, false) => SyncAction::Push,
+                    (false, true) => SyncAction::Pull,
+                    _ => SyncAction::Conflict,
+                }
+            }
+        },
+        _ if local.is_some() => SyncAction::Push,
+        _ => SyncAction::None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decide_two_way() {
+        let d = |b, l, r| decide("both", b, l, r);
+
+        assert_eq!(d(Some("a"), Some("a"), Some("a")), SyncAction::None);
+        assert_eq!(d(Some("a"), Some("b"), Some("a")), SyncAction::Push);
+        assert_eq!(d(Some("a"), Some("a"), Some("b")), SyncAction::Pull);
+        assert_eq!(d(Some("a"), Some("b"), Some("c")), SyncAction::Conflict);
+        // 两端改成了相同内容，不算冲突
+        assert_eq!(d(Some("a"), Some("b"), Some("b")), SyncAction::None);
+        // 从未同步过且两端内容不同
+        assert_eq!(d(None, Some("b"), Some("c")), SyncAction::Conflict);
+        // 只存在于一端
+        assert_eq!(d(None, Some("b"), None), SyncAction::Push);
+        assert_eq!(d(Some("a"), None, Some("a")), SyncAction::Pull);
+    }
+
+    #[test]
+    fn test_decide_one_way() {
+        assert_eq!(
+            decide("pull", Some("a"), Some("b"), Some("c")),
+            SyncAction::Pull
+        );
+        assert_eq!(decide("pull", None, Some("b"), None), SyncAction::None);
+        assert_eq!(
+            decide("push", Some("a"), Some("a"), Some("c")),
+            SyncAction::Push
+        );
+        assert_eq!(decide("push", None, None, Some("c")), SyncAction::None);
+    }
+}
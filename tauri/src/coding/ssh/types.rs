@@ -1,7 +1,10 @@
 use serde::{Deserialize, Serialize};
 
 // Re-use SyncResult and SyncProgress from wsl module
-pub use super::super::wsl::{SyncProgress, SyncResult};
+pub use super::super::wsl::{
+    FileChangePreview, HostSyncResult, McpChangePreview, SkillsChangePreview, SyncConflict,
+    SyncPreview, SyncProgress, SyncResult,
+};
 
 // ============================================================================
 // SSH Connection Types
@@ -16,12 +19,30 @@ pub struct SSHConnection {
     pub host: String,
     pub port: u16,
     pub username: String,
-    pub auth_method: String, // "key" | "password"
+    pub auth_method: String, // "key" | "password" | "agent"
     pub password: String,
     pub private_key_path: String,
     pub private_key_content: String,
     pub passphrase: String,
     pub sort_order: u32,
+    /// Jump hosts (bastions) traversed in order before the target, like OpenSSH ProxyJump
+    #[serde(default)]
+    pub jump_hosts: Vec<SSHJumpHost>,
+}
+
+/// Jump host (bastion) in a connection's ProxyJump chain
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SSHJumpHost {
+    pub host: String,
+    pub port: u16,
+    /// Empty = same username as the target connection
+    pub username: String,
+    pub auth_method: String, // "key" | "password" | "agent"
+    pub password: String,
+    pub private_key_path: String,
+    pub private_key_content: String,
+    pub passphrase: String,
 }
 
 // ============================================================================
@@ -40,6 +61,13 @@ pub struct SSHFileMapping {
     pub enabled: bool,
     pub is_pattern: bool,
     pub is_directory: bool,
+    /// "push" (local → remote) | "pull" (remote → local) | "both" (two-way, conflicts are reported)
+    #[serde(default = "default_sync_direction")]
+    pub direction: String,
+}
+
+fn default_sync_direction() -> String {
+    "push".to_string()
 }
 
 // ============================================================================
@@ -52,14 +80,23 @@ pub struct SSHFileMapping {
 pub struct SSHSyncConfig {
     pub enabled: bool,
     pub active_connection_id: String,
+    /// Other connections that receive the same sync as the active connection
+    #[serde(default)]
+    pub target_connection_ids: Vec<String>,
     // sync_mcp and sync_skills are always true (no UI to toggle them)
     pub sync_mcp: bool,
     pub sync_skills: bool,
+    /// Sync a mapping automatically when its local file changes (default: false)
+    #[serde(default)]
+    pub auto_sync_on_change: bool,
     pub file_mappings: Vec<SSHFileMapping>,
     pub connections: Vec<SSHConnection>,
     pub last_sync_time: Option<String>,
     pub last_sync_status: String, // "success" | "error" | "never"
     pub last_sync_error: Option<String>,
+    /// Last sync status of every target connection
+    #[serde(default)]
+    pub host_status: Vec<SSHHostSyncStatus>,
 }
 
 impl Default for SSHSyncConfig {
@@ -67,17 +104,46 @@ impl Default for SSHSyncConfig {
         Self {
             enabled: false,
             active_connection_id: String::new(),
+            target_connection_ids: vec![],
             sync_mcp: true,
             sync_skills: true,
+            auto_sync_on_change: false,
             file_mappings: vec![],
             connections: vec![],
             last_sync_time: None,
             last_sync_status: "never".to_string(),
             last_sync_error: None,
+            host_status: vec![],
+        }
+    }
+}
+
+impl SSHSyncConfig {
+    /// Connections to sync: the active connection first, then the other selected targets
+    pub fn sync_targets(&self) -> Vec<&SSHConnection> {
+        let mut ids = vec![&self.active_connection_id];
+        for id in &self.target_connection_ids {
+            if !ids.contains(&id) {
+                ids.push(id);
+            }
         }
+        ids.into_iter()
+            .filter_map(|id| self.connections.iter().find(|c| &c.id == id))
+            .collect()
     }
 }
 
+/// Last sync status of a single target connection
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SSHHostSyncStatus {
+    pub connection_id: String,
+    pub connection_name: String,
+    pub last_sync_time: Option<String>,
+    pub last_sync_status: String, // "success" | "error"
+    pub last_sync_error: Option<String>,
+}
+
 // ============================================================================
 // SSH Result Types
 // ============================================================================
@@ -89,6 +155,62 @@ pub struct SSHConnectionResult {
     pub connected: bool,
     pub error: Option<String>,
     pub server_info: Option<String>,
+    /// Set when the connection was rejected by host key verification
+    pub host_key_error: Option<SSHHostKeyError>,
+}
+
+// ============================================================================
+// SSH Host Key Types
+// ============================================================================
+
+/// Host key recorded for a connection on first connect (accept-new)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SSHKnownHost {
+    pub connection_id: String,
+    pub host: String,
+    pub port: u16,
+    pub key_type: String,
+    pub fingerprint: String, // "SHA256:..."
+    pub public_key: String,  // OpenSSH format
+    pub first_seen: String,
+}
+
+/// Host key verification failure
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SSHHostKeyError {
+    pub kind: String, // "changed" | "revoked"
+    pub host: String,
+    pub port: u16,
+    pub key_type: String,
+    /// Fingerprint presented by the server
+    pub fingerprint: String,
+    /// Fingerprint we expected (for "changed")
+    pub expected_fingerprint: Option<String>,
+    pub source: String, // "app" | "known_hosts"
+    /// Line number in known_hosts (source = "known_hosts")
+    pub line: Option<usize>,
+}
+
+// ============================================================================
+// SSH Config Import Types
+// ============================================================================
+
+/// Host alias from ~/.ssh/config, resolved like `ssh -G`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SSHConfigHost {
+    pub alias: String,
+    pub host_name: String,
+    pub port: u16,
+    pub user: String,
+    /// First IdentityFile that exists locally (empty = use ssh-agent)
+    pub identity_file: String,
+    /// ProxyJump entries as written in the config
+    pub proxy_jump: Vec<String>,
+    /// A connection with the same name already exists
+    pub imported: bool,
 }
 
 /// SSH status result
@@ -100,4 +222,7 @@ pub struct SSHStatusResult {
     pub last_sync_time: Option<String>,
     pub last_sync_status: String,
     pub last_sync_error: Option<String>,
+    /// Number of connections a sync goes to (active + other targets)
+    pub target_count: usize,
+    pub host_status: Vec<SSHHostSyncStatus>,
 }
@@ -0,0 +1,528 @@
+//! 同步预览（dry-run）共用逻辑
+//!
+//! SSH / WSL 同步在真正写入远端之前，可以先计算将要发生的变更：
+//! - 文件映射：逐文件比较 SHA-256，给出 create / update / delete 等动作，文本文件附带 unified diff
+//! - MCP：~/.claude.json 中将新增 / 删除 / 修改的 server
+//! - Skills：中心仓库中将新增 / 更新 / 删除的 skill 目录，以及各工具目录下的符号链接变化
+//!
+//! 这里只包含与传输方式无关的纯函数，远端读取由 ssh / wsl 各自完成。
+
+use std::collections::{BTreeMap, HashSet};
+
+use serde_json::{Map, Value};
+
+use super::mcp::command_normalize;
+use super::skills::types::Skill;
+use super::wsl::{FileChangePreview, McpChangePreview, SkillsChangePreview};
+
+/// 超过该大小的文件不生成 diff
+pub const MAX_DIFF_BYTES: usize = 256 * 1024;
+
+/// diff 上下文行数
+const DIFF_CONTEXT: usize = 3;
+
+/// LCS 表的最大单元数，超过后中间部分按整体替换输出
+const MAX_LCS_CELLS: usize = 4_000_000;
+
+// ============================================================================
+// Remote Hashes
+// ============================================================================
+
+/// 远端计算 SHA-256：Linux 使用 sha256sum，macOS 回退到 shasum
+pub const HASH_CMD: &str =
+    "H=$(command -v sha256sum >/dev/null 2>&1 && echo sha256sum || echo 'shasum -a 256');";
+
+/// 解析 sha256sum / shasum 输出：`<hash>  <path>`
+/// 含特殊字符的文件名会被转义（行首为 `\`），直接跳过
+pub fn parse_hash_output(output: &str) -> Vec<(String, String)> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let hash = line.get(..64)?;
+            if !hash.chars().all(|c| c.is_ascii_hexdigit()) {
+                return None;
+            }
+            let rest = &line[64..];
+            let path = rest
+                .strip_prefix("  ")
+                .or_else(|| rest.strip_prefix(" *"))?;
+            Some((path.to_string(), hash.to_lowercase()))
+        })
+        .collect()
+}
+
+// ============================================================================
+// Text Diff
+// ============================================================================
+
+/// 将文件内容视为文本：过大、含 NUL 或不是合法 UTF-8 时返回 None
+pub fn decode_text(data: &[u8]) -> Option<String> {
+    if data.len() > MAX_DIFF_BYTES || data.contains(&0) {
+        return None;
+    }
+    String::from_utf8(data.to_vec()).ok()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LineOp {
+    Equal,
+    Delete,
+    Insert,
+}
+
+/// 按行计算编辑序列（先去掉公共前后缀，中间部分用 LCS）
+fn diff_lines<'a>(old: &[&'a str], new: &[&'a str]) -> Vec<(LineOp, &'a str)> {
+    let prefix = old
+        .iter()
+        .zip(new.iter())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let a = &old[prefix..old.len() - suffix];
+    let b = &new[prefix..new.len() - suffix];
+
+    let mut ops: Vec<(LineOp, &str)> = old[..prefix].iter().map(|l| (LineOp::Equal, *l)).collect();
+
+    if a.len().saturating_mul(b.len()) <= MAX_LCS_CELLS {
+        // lcs[i * w + j] = a[i..] 与 b[j..] 的最长公共子序列长度
+        let w = b.len() + 1;
+        let mut lcs = vec![0u32; (a.len() + 1) * w];
+        for i in (0..a.len()).rev() {
+            for j in (0..b.len()).rev() {
+                lcs[i * w + j] = if a[i] == b[j] {
+                    lcs[(i + 1) * w + j + 1] + 1
+                } else {
+                    lcs[(i + 1) * w + j].max(lcs[i * w + j + 1])
+                };
+            }
+        }
+
+        let (mut i, mut j) = (0, 0);
+        while i < a.len() && j < b.len() {
+            if a[i] == b[j] {
+                ops.push((LineOp::Equal, a[i]));
+                i += 1;
+                j += 1;
+            } else if lcs[(i + 1) * w + j] >= lcs[i * w + j + 1] {
+                ops.push((LineOp::Delete, a[i]));
+                i += 1;
+            } else {
+                ops.push((LineOp::Insert, b[j]));
+                j += 1;
+            }
+        }
+        ops.extend(a[i..].iter().map(|l| (LineOp::Delete, *l)));
+        ops.extend(b[j..].iter().map(|l| (LineOp::Insert, *l)));
+    } else {
+        ops.extend(a.iter().map(|l| (LineOp::Delete, *l)));
+        ops.extend(b.iter().map(|l| (LineOp::Insert, *l)));
+    }
+
+    ops.extend(
+        old[old.len() - suffix..]
+            .iter()
+            .map(|l| (LineOp::Equal, *l)),
+    );
+    ops
+}
+
+/// 生成 unified diff（3 行上下文），内容相同时返回空字符串
+pub fn unified_diff(old: &str, new: &str, old_label: &str, new_label: &str) -> String {
+    let old_lines: Vec<&str> = old.lines().collect();
+    let new_lines: Vec<&str> = new.lines().collect();
+    let ops = diff_lines(&old_lines, &new_lines);
+
+    let changes: Vec<usize> = ops
+        .iter()
+        .enumerate()
+        .filter(|(_, (op, _))| *op != LineOp::Equal)
+        .map(|(idx, _)| idx)
+        .collect();
+    if changes.is_empty() {
+        return String::new();
+    }
+
+    // 每个操作之前已经过的旧 / 新行数
+    let mut old_before = Vec::with_capacity(ops.len() + 1);
+    let mut new_before = Vec::with_capacity(ops.len() + 1);
+    let (mut old_pos, mut new_pos) = (0, 0);
+    for (op, _) in &ops {
+        old_before.push(old_pos);
+        new_before.push(new_pos);
+        if *op != LineOp::Insert {
+            old_pos += 1;
+        }
+        if *op != LineOp::Delete {
+            new_pos += 1;
+        }
+    }
+
+    let mut out = format!("--- {}\n+++ {}\n", old_label, new_label);
+    let mut k = 0;
+    while k < changes.len() {
+        let start = changes[k].saturating_sub(DIFF_CONTEXT);
+        let mut last = changes[k];
+        // 两处变更之间的相同行不超过 2 倍上下文时合并为同一个 hunk
+        while k + 1 < changes.len() && changes[k + 1] - last <= 2 * DIFF_CONTEXT + 1 {
+            k += 1;
+            last = changes[k];
+        }
+        let end = (last + DIFF_CONTEXT + 1).min(ops.len());
+        let hunk = &ops[start..end];
+
+        let old_count = hunk.iter().filter(|(op, _)| *op != LineOp::Insert).count();
+        let new_count = hunk.iter().filter(|(op, _)| *op != LineOp::Delete).count();
+        let old_start = old_before[start] + usize::from(old_count > 0);
+        let new_start = new_before[start] + usize::from(new_count > 0);
+
+        out.push_str(&format!(
+            "@@ -{},{} +{},{} @@\n",
+            old_start, old_count, new_start, new_count
+        ));
+        for (op, line) in hunk {
+            let marker = match op {
+                LineOp::Equal => ' ',
+                LineOp::Delete => '-',
+                LineOp::Insert => '+',
+            };
+            out.push(marker);
+            out.push_str(line);
+            out.push('\n');
+        }
+        k += 1;
+    }
+
+    out
+}
+
+/// 读取远端文件得到的文本（`cat` 输出经过 lossy 解码）：过大或包含替换字符时视为非文本
+pub fn remote_text(content: String) -> Option<String> {
+    if content.len() > MAX_DIFF_BYTES || content.contains('\u{FFFD}') || content.contains('\0') {
+        return None;
+    }
+    Some(content)
+}
+
+/// 比较目标现有文本与即将写入的文本（目标不存在时传入空字符串）
+///
+/// 两端内容相同时返回 None；否则返回 Some(diff)，任一端不是文本时 diff 为 None。
+pub fn text_change(
+    target: Option<&str>,
+    source: Option<&str>,
+    target_label: &str,
+    source_label: &str,
+) -> Option<Option<String>> {
+    match (target, source) {
+        (Some(target), Some(source)) if target == source => None,
+        (Some(target), Some(source)) => Some(Some(unified_diff(
+            target,
+            source,
+            target_label,
+            source_label,
+        ))),
+        _ => Some(None),
+    }
+}
+
+// ============================================================================
+// MCP
+// ============================================================================
+
+/// 内容包含 MCP server 配置、同步到 Linux 后需要去掉 `cmd /c` 的文件映射
+pub fn is_mcp_config_file(mapping_id: &str) -> bool {
+    matches!(
+        mapping_id,
+        "opencode-main" | "opencode-oh-my" | "codex-config"
+    )
+}
+
+/// 去掉 MCP 配置中的 `cmd /c` 包装
+///
+/// 按文件扩展名选择解析器，避免把 JSON 当作 TOML 解析；不需要处理的文件返回 None。
+pub fn strip_cmd_c(path: &str, module: &str, content: &str) -> Result<Option<String>, String> {
+    if content.trim().is_empty() {
+        return Ok(None);
+    }
+
+    match module {
+        "opencode" => command_normalize::process_opencode_json(content, false).map(Some),
+        "codex" if path.ends_with(".toml") => {
+            command_normalize::process_codex_toml(content, false).map(Some)
+        }
+        _ => Ok(None),
+    }
+}
+
+/// 推送到 Linux 端后的最终文本（MCP 配置会在同步后去掉 cmd /c），非文本返回 None
+pub fn pushed_text(
+    mapping_id: &str,
+    module: &str,
+    target_path: &str,
+    data: &[u8],
+) -> Option<String> {
+    let text = decode_text(data)?;
+    if !is_mcp_config_file(mapping_id) {
+        return Some(text);
+    }
+    match strip_cmd_c(target_path, module, &text) {
+        Ok(Some(stripped)) => Some(stripped),
+        _ => Some(text),
+    }
+}
+
+/// 比较 ~/.claude.json 现有的 mcpServers 与即将写入的 servers
+///
+/// `location` 用于错误信息（"remote" / "WSL"）。diff 只覆盖 mcpServers 字段，
+/// 避免 claude.json 中的其它大字段淹没变更。
+pub fn preview_claude_mcp(
+    existing_content: &str,
+    servers: &Map<String, Value>,
+    location: &str,
+) -> Result<McpChangePreview, String> {
+    let config: Value = if existing_content.trim().is_empty() {
+        serde_json::json!({})
+    } else {
+        json5::from_str(existing_content)
+            .map_err(|e| format!("Failed to parse {} claude.json: {}", location, e))?
+    };
+    let current = config
+        .as_object()
+        .ok_or_else(|| format!("{} claude.json is not a JSON object", location))?
+        .get("mcpServers")
+        .and_then(|v| v.as_object())
+        .cloned()
+        .unwrap_or_default();
+
+    let mut preview = McpChangePreview::default();
+    for (name, server) in servers {
+        match current.get(name) {
+            None => preview.added.push(name.clone()),
+            Some(existing) if existing != server => preview.updated.push(name.clone()),
+            _ => {}
+        }
+    }
+    preview.removed = current
+        .keys()
+        .filter(|name| !servers.contains_key(*name))
+        .cloned()
+        .collect();
+
+    let old = serde_json::to_string_pretty(&current)
+        .map_err(|e| format!("Failed to serialize config: {}", e))?;
+    let new = serde_json::to_string_pretty(servers)
+        .map_err(|e| format!("Failed to serialize config: {}", e))?;
+    let diff = unified_diff(
+        &old,
+        &new,
+        "~/.claude.json mcpServers",
+        "~/.claude.json mcpServers",
+    );
+    preview.diff = (!diff.is_empty()).then_some(diff);
+
+    Ok(preview)
+}
+
+// ============================================================================
+// Skills
+// ============================================================================
+
+/// 远端中心仓库与各工具 skills 目录的现状
+#[derive(Debug, Default)]
+pub struct RemoteSkillsState {
+    home: String,
+    /// skill 目录名 -> .synced_hash 内容
+    skills: BTreeMap<String, String>,
+    /// 工具 skills 目录下的条目（~ 形式路径）-> 符号链接目标，非符号链接为 None
+    entries: BTreeMap<String, Option<String>>,
+}
+
+/// 一次性读取中心仓库与所有工具 skills 目录状态的 shell 命令
+pub fn skills_state_command(central_dir: &str, tool_dirs: &[String]) -> String {
+    let mut command = format!(
+        "echo \"$HOME\"; for d in \"{}\"/*; do [ -e \"$d\" ] || continue; \
+         printf 'S\\t%s\\t%s\\n' \"${{d##*/}}\" \"$(cat \"$d/.synced_hash\" 2>/dev/null)\"; done;",
+        central_dir.replace("~", "$HOME")
+    );
+    for dir in tool_dirs {
+        command.push_str(&format!(
+            " for l in \"{}\"/*; do if [ -L \"$l\" ]; then \
+             printf 'L\\t%s\\t%s\\n' \"{}/${{l##*/}}\" \"$(readlink \"$l\")\"; \
+             elif [ -e \"$l\" ]; then printf 'E\\t%s\\t\\n' \"{}/${{l##*/}}\"; fi; done;",
+            dir.replace("~", "$HOME"),
+            dir,
+            dir
+        ));
+    }
+    command
+}
+
+/// 解析 `skills_state_command` 的输出
+pub fn parse_skills_state(output: &str) -> RemoteSkillsState {
+    let mut lines = output.lines();
+    let mut state = RemoteSkillsState {
+        home: lines.next().unwrap_or_default().trim().to_string(),
+        ..Default::default()
+    };
+
+    for line in lines {
+        let mut parts = line.splitn(3, '\t');
+        match (parts.next(), parts.next(), parts.next()) {
+            (Some("S"), Some(name), hash) => {
+                state.skills.insert(
+                    name.to_string(),
+                    hash.unwrap_or_default().trim().to_string(),
+                );
+            }
+            (Some("L"), Some(path), target) => {
+                state.entries.insert(
+                    path.to_string(),
+                    Some(target.unwrap_or_default().to_string()),
+                );
+            }
+            (Some("E"), Some(path), _) => {
+                state.entries.insert(path.to_string(), None);
+            }
+            _ => {}
+        }
+    }
+
+    state
+}
+
+fn link_change(
+    skill: &str,
+    action: &str,
+    central_path: &str,
+    link_path: String,
+) -> FileChangePreview {
+    FileChangePreview {
+        mapping_id: "skills".to_string(),
+        mapping_name: skill.to_string(),
+        action: action.to_string(),
+        direction: "push".to_string(),
+        source_path: central_path.to_string(),
+        target_path: link_path,
+        diff: None,
+    }
+}
+
+/// 计算 skills 同步将产生的变更（与 `sync_skills_to_ssh` / `sync_skills_to_wsl` 的逻辑一致）
+///
+/// - `unavailable`: 本地源目录不存在、同步时会被跳过的 skill
+/// - `tool_dirs`: (tool key, ~ 形式的工具 skills 目录)
+pub fn plan_skills(
+    skills: &[Skill],
+    unavailable: &HashSet<String>,
+    tool_dirs: &[(&str, String)],
+    central_dir: &str,
+    state: &RemoteSkillsState,
+) -> SkillsChangePreview {
+    let mut preview = SkillsChangePreview::default();
+    let local_names: HashSet<&str> = skills.iter().map(|s| s.name.as_str()).collect();
+
+    // 远端存在但本地已删除的 skill：删除目录及所有工具目录下的链接
+    for name in state.skills.keys() {
+        if local_names.contains(name.as_str()) {
+            continue;
+        }
+        preview.removed.push(name.clone());
+        let central_path = format!("{}/{}", central_dir, name);
+        for (_, dir) in tool_dirs {
+            let link_path = format!("{}/{}", dir, name);
+            let action = match state.entries.get(&link_path) {
+                Some(Some(_)) => "delete_symlink",
+                Some(None) => "delete",
+                None => continue,
+            };
+            preview
+                .links
+                .push(link_change(name, action, &central_path, link_path));
+        }
+    }
+
+    for skill in skills {
+        if unavailable.contains(&skill.name) {
+            continue;
+        }
+
+        let central_path = format!("{}/{}", central_dir, skill.name);
+        match state.skills.get(&skill.name) {
+            None => preview.added.push(skill.name.clone()),
+            Some(hash) if hash != skill.content_hash.as_deref().unwrap_or("") => {
+                preview.updated.push(skill.name.clone())
+            }
+            _ => {}
+        }
+
+        let expected_target = central_path.replace("~", &state.home);
+        for (tool_key, dir) in tool_dirs {
+            let link_path = format!("{}/{}", dir, skill.name);
+            let existing = state.entries.get(&link_path);
+            let enabled = skill.enabled_tools.iter().any(|t| t == tool_key);
+            let action = match (enabled, existing) {
+                (true, None) => "create",
+                (true, Some(Some(target))) if *target == expected_target => continue,
+                (true, Some(_)) => "update",
+                (false, Some(Some(_))) => "delete_symlink",
+                (false, Some(None)) => "delete",
+                (false, None) => continue,
+            };
+            // 多个工具可能共用同一个 skills 目录
+            if preview.links.iter().any(|l| l.target_path == link_path) {
+                continue;
+            }
+            preview
+                .links
+                .push(link_change(&skill.name, action, &central_path, link_path));
+        }
+    }
+
+    preview
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unified_diff() {
+        let old = "a\nb\nc\nd\ne\nf\ng\nh\ni\nj\n";
+        let new = "a\nB\nc\nd\ne\nf\ng\nh\ni\nj\nk\n";
+        assert_eq!(
+            unified_diff(old, new, "old", "new"),
+            "--- old\n+++ new\n\
+             @@ -1,5 +1,5 @@\n a\n-b\n+B\n c\n d\n e\n\
+             @@ -8,3 +8,4 @@\n h\n i\n j\n+k\n"
+        );
+
+        assert_eq!(
+            unified_diff("", "x\n", "old", "new"),
+            "--- old\n+++ new\n@@ -0,0 +1,1 @@\n+x\n"
+        );
+        assert!(unified_diff("same\n", "same\n", "old", "new").is_empty());
+    }
+
+    #[test]
+    fn test_parse_skills_state() {
+        let output = "/home/me\n\
+                      S\tfoo\tabc\n\
+                      S\tbar\t\n\
+                      L\t~/.claude/skills/foo\t/home/me/.ai-toolbox/skills/foo\n\
+                      E\t~/.claude/skills/local\t\n";
+        let state = parse_skills_state(output);
+
+        assert_eq!(state.home, "/home/me");
+        assert_eq!(state.skills.get("foo").map(String::as_str), Some("abc"));
+        assert_eq!(state.skills.get("bar").map(String::as_str), Some(""));
+        assert_eq!(
+            state.entries.get("~/.claude/skills/foo"),
+            Some(&Some("/home/me/.ai-toolbox/skills/foo".to_string()))
+        );
+        assert_eq!(state.entries.get("~/.claude/skills/local"), Some(&None));
+    }
+}
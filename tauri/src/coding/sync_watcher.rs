@@ -0,0 +1,345 @@
+//! 本地配置文件监听
+//!
+//! 监听 SSH / WSL 同步中所有已启用映射的本地路径（开关为各自配置的 `auto_sync_on_change`），
+//! 一段时间内的连续变化合并（防抖）后，只对发生变化的映射执行增量同步。
+//! 同步配置或映射变化（`ssh-config-changed` / `wsl-config-changed`）时重建监听。
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Listener, Manager};
+use tokio::sync::{mpsc, Mutex};
+
+use super::expand_local_path;
+use super::{ssh, wsl};
+use crate::db::DbState;
+
+/// 最后一次变化后等待多久再同步
+const DEBOUNCE: Duration = Duration::from_millis(1500);
+
+/// 当前生效的监听器，注册到 Tauri State
+pub struct SyncWatcherState(pub Mutex<Option<RecommendedWatcher>>);
+
+/// 文件监听触发同步时发给前端的事件（"sync-watcher-triggered"）
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncWatcherEvent {
+    /// "ssh" | "wsl"
+    pub target: String,
+    /// 发生变化的映射名称
+    pub mappings: Vec<String>,
+}
+
+/// 一个被监听的映射
+#[derive(Debug, Clone)]
+struct WatchEntry {
+    /// "ssh" | "wsl"
+    target: &'static str,
+    mapping_id: String,
+    mapping_name: String,
+    /// 展开后的本地路径（文件、目录或 glob 模式）
+    path: PathBuf,
+    is_pattern: bool,
+    is_directory: bool,
+}
+
+impl WatchEntry {
+    fn new(
+        target: &'static str,
+        mapping_id: &str,
+        mapping_name: &str,
+        local_path: &str,
+        is_pattern: bool,
+        is_directory: bool,
+    ) -> Option<Self> {
+        let path = expand_local_path(local_path).ok()?;
+        Some(Self {
+            target,
+            mapping_id: mapping_id.to_string(),
+            mapping_name: mapping_name.to_string(),
+            path: PathBuf::from(path),
+            is_pattern,
+            is_directory,
+        })
+    }
+
+    /// 需要注册到 notify 的目录与递归方式
+    ///
+    /// 单个文件监听其所在目录：编辑器常以"写临时文件再重命名"的方式保存，
+    /// 直接监听文件会在第一次保存后失效。
+    fn watch_dir(&self) -> Option<(PathBuf, RecursiveMode)> {
+        if self.is_directory && !self.is_pattern {
+            return Some((self.path.clone(), RecursiveMode::Recursive));
+        }
+        let dir = if self.is_pattern {
+            pattern_base_dir(&self.path)
+        } else {
+            self.path.parent()?.to_path_buf()
+        };
+        Some((dir, RecursiveMode::NonRecursive))
+    }
+
+    fn matches(&self, changed: &Path) -> bool {
+        if self.is_pattern {
+            glob::Pattern::new(&self.path.to_string_lossy())
+                .is_ok_and(|pattern| pattern.matches_path(changed))
+        } else if self.is_directory {
+            changed.starts_with(&self.path)
+        } else {
+            changed == self.path
+        }
+    }
+}
+
+/// glob 模式中第一个通配符之前的目录
+fn pattern_base_dir(pattern: &Path) -> PathBuf {
+    let mut base = PathBuf::new();
+    for component in pattern.components() {
+        let part = component.as_os_str().to_string_lossy();
+        if part.contains(['*', '?', '[']) {
+            break;
+        }
+        base.push(component);
+    }
+    base
+}
+
+/// 收集所有需要监听的映射
+async fn collect_entries(app: &AppHandle) -> Vec<WatchEntry> {
+    let state = app.state::<DbState>();
+    let mut entries = vec![];
+
+    if let Ok(config) = ssh::ssh_get_config(state.clone()).await {
+        if config.enabled && config.auto_sync_on_change {
+            // pull 映射以远程为准，本地修改会在同步时被覆盖，不监听
+            let mappings = ssh::resolve_dynamic_paths(config.file_mappings)
+                .into_iter()
+                .filter(|m| m.enabled && m.direction != "pull");
+            entries.extend(mappings.filter_map(|m| {
+                WatchEntry::new(
+                    "ssh",
+                    &m.id,
+                    &m.name,
+                    &m.local_path,
+                    m.is_pattern,
+                    m.is_directory,
+                )
+            }));
+        }
+    }
+
+    if let Ok(config) = wsl::wsl_get_config(state).await {
+        if config.enabled && config.auto_sync_on_change {
+            let mappings = wsl::resolve_dynamic_paths(config.file_mappings)
+                .into_iter()
+                .filter(|m| m.enabled);
+            entries.extend(mappings.filter_map(|m| {
+                WatchEntry::new(
+                    "wsl",
+                    &m.id,
+                    &m.name,
+                    &m.windows_path,
+                    m.is_pattern,
+                    m.is_directory,
+                )
+            }));
+        }
+    }
+
+    entries
+}
+
+/// 按当前配置重建监听（关闭时停止监听）
+pub async fn refresh(app: &AppHandle) {
+    let watcher_state = app.state::<SyncWatcherState>();
+    // 持有锁直到新监听器就位，避免并发刷新装上过期的监听器
+    let mut current = watcher_state.0.lock().await;
+    // 先停止旧监听器，其防抖任务会在通道关闭后退出
+    *current = None;
+
+    let entries = collect_entries(app).await;
+    if entries.is_empty() {
+        return;
+    }
+
+    let (tx, rx) = mpsc::unbounded_channel::<Vec<PathBuf>>();
+    let mut watcher =
+        match notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+            if let Ok(event) = res {
+                if !matches!(event.kind, EventKind::Access(_)) {
+                    let _ = tx.send(event.paths);
+                }
+            }
+        }) {
+            Ok(w) => w,
+            Err(e) => {
+                log::warn!("Failed to create sync file watcher: {}", e);
+                return;
+            }
+        };
+
+    // 同一目录只注册一次，有递归需求时按递归注册
+    let mut dirs: BTreeMap<PathBuf, RecursiveMode> = BTreeMap::new();
+    for (dir, mode) in entries.iter().filter_map(WatchEntry::watch_dir) {
+        let existing = dirs.entry(dir).or_insert(mode);
+        if mode == RecursiveMode::Recursive {
+            *existing = mode;
+        }
+    }
+    for (dir, mode) in &dirs {
+        // 目录不存在时跳过（如未安装对应工具），配置变化后会重新尝试
+        if !dir.is_dir() {
+            continue;
+        }
+        if let Err(e) = watcher.watch(dir, *mode) {
+            log::warn!("Failed to watch {}: {}", dir.display(), e);
+        }
+    }
+
+    log::info!(
+        "Sync file watcher: {} mappings, {} directories",
+        entries.len(),
+        dirs.len()
+    );
+
+    tauri::async_runtime::spawn(debounce_loop(app.clone(), rx, entries));
+    *current = Some(watcher);
+}
+
+/// 合并连续变化，静默 `DEBOUNCE` 后对变化的映射执行同步
+async fn debounce_loop(
+    app: AppHandle,
+    mut rx: mpsc::UnboundedReceiver<Vec<PathBuf>>,
+    entries: Vec<WatchEntry>,
+) {
+    // target -> 发生变化的映射下标
+    let mut pending: BTreeMap<&'static str, BTreeSet<usize>> = BTreeMap::new();
+
+    loop {
+        let next = if pending.is_empty() {
+            rx.recv().await
+        } else {
+            match tokio::time::timeout(DEBOUNCE, rx.recv()).await {
+                Ok(next) => next,
+                Err(_) => {
+                    flush(&app, &entries, std::mem::take(&mut pending)).await;
+                    continue;
+                }
+            }
+        };
+
+        // 监听器已被替换或关闭
+        let Some(paths) = next else {
+            break;
+        };
+
+        for path in &paths {
+            for (idx, entry) in entries.iter().enumerate() {
+                if entry.matches(path) {
+                    pending.entry(entry.target).or_default().insert(idx);
+                }
+            }
+        }
+    }
+}
+
+async fn flush(
+    app: &AppHandle,
+    entries: &[WatchEntry],
+    pending: BTreeMap<&'static str, BTreeSet<usize>>,
+) {
+    for (target, indexes) in pending {
+        let changed: Vec<&WatchEntry> = indexes.into_iter().map(|idx| &entries[idx]).collect();
+        let mapping_ids: Vec<String> = changed.iter().map(|e| e.mapping_id.clone()).collect();
+        let mapping_names: Vec<String> = changed.iter().map(|e| e.mapping_name.clone()).collect();
+
+        log::info!(
+            "Local config changed, syncing to {}: {:?}",
+            target,
+            mapping_names
+        );
+        let _ = app.emit(
+            "sync-watcher-triggered",
+            SyncWatcherEvent {
+                target: target.to_string(),
+                mappings: mapping_names,
+            },
+        );
+
+        let result = if target == "ssh" {
+            ssh::sync_changed_mappings(app, &mapping_ids).await
+        } else {
+            wsl::sync_changed_mappings(app, &mapping_ids).await
+        };
+        match result {
+            Ok(result) if !result.errors.is_empty() => {
+                log::warn!("Auto {} sync errors: {:?}", target, result.errors);
+            }
+            Ok(_) => {}
+            Err(e) => log::warn!("Auto {} sync skipped: {}", target, e),
+        }
+    }
+}
+
+/// 启动时建立监听，并在同步配置变化时重建
+pub fn init(app: &AppHandle) {
+    for event in ["ssh-config-changed", "wsl-config-changed"] {
+        let app_clone = app.clone();
+        app.listen(event, move |_event| {
+            let app = app_clone.clone();
+            tauri::async_runtime::spawn(async move {
+                refresh(&app).await;
+            });
+        });
+    }
+
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        refresh(&app).await;
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(path: &str, is_pattern: bool, is_directory: bool) -> WatchEntry {
+        WatchEntry {
+            target: "ssh",
+            mapping_id: "id".to_string(),
+            mapping_name: "name".to_string(),
+            path: PathBuf::from(path),
+            is_pattern,
+            is_directory,
+        }
+    }
+
+    #[test]
+    fn test_watch_entry_matches() {
+        let file = entry("/home/u/.codex/config.toml", false, false);
+        assert!(file.matches(Path::new("/home/u/.codex/config.toml")));
+        assert!(!file.matches(Path::new("/home/u/.codex/auth.json")));
+        assert_eq!(
+            file.watch_dir(),
+            Some((PathBuf::from("/home/u/.codex"), RecursiveMode::NonRecursive))
+        );
+
+        let dir = entry("/home/u/.claude/agents", false, true);
+        assert!(dir.matches(Path::new("/home/u/.claude/agents/a/b.md")));
+        assert!(!dir.matches(Path::new("/home/u/.claude/settings.json")));
+
+        let pattern = entry("/home/u/.config/opencode/*.json", true, false);
+        assert!(pattern.matches(Path::new("/home/u/.config/opencode/a.json")));
+        assert!(!pattern.matches(Path::new("/home/u/.config/opencode/a.toml")));
+        assert_eq!(
+            pattern.watch_dir(),
+            Some((
+                PathBuf::from("/home/u/.config/opencode"),
+                RecursiveMode::NonRecursive
+            ))
+        );
+    }
+}
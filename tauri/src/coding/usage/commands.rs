@@ -0,0 +1,443 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use chrono::Local;
+use serde_json::{json, Value};
+use walkdir::WalkDir;
+
+use super::parser::{self, ProviderTimeline};
+use super::pricing::{self, PriceTable};
+use super::types::*;
+use crate::coding::{db_new_id, preset_models};
+use crate::db::DbState;
+
+pub const SOURCE_CLAUDE_CODE: &str = "claudecode";
+pub const SOURCE_CODEX: &str = "codex";
+pub const SOURCE_OPENCODE: &str = "opencode";
+
+/// 报表与重新扫描互斥，避免同一文件被并发写入两次
+static SCAN_LOCK: tokio::sync::Mutex<()> = tokio::sync::Mutex::const_new(());
+
+/// 一个扫描单元：Claude Code / Codex 的 jsonl 文件，或 OpenCode 的一个会话目录
+pub(crate) struct SourceUnit {
+    pub source: &'static str,
+    pub path: PathBuf,
+    /// 大小与修改时间，未变化时跳过解析
+    pub fingerprint: String,
+}
+
+fn modified_millis(meta: &fs::Metadata) -> u128 {
+    meta.modified()
+        .ok()
+        .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+        .map(|d| d.as_millis())
+        .unwrap_or(0)
+}
+
+fn collect_jsonl_files(root: &Path, source: &'static str, units: &mut Vec<SourceUnit>) {
+    if !root.exists() {
+        return;
+    }
+    for entry in WalkDir::new(root).into_iter().filter_map(|e| e.ok()) {
+        let path = entry.path();
+        if !entry.file_type().is_file()
+            || path.extension().and_then(|e| e.to_str()) != Some("jsonl")
+        {
+            continue;
+        }
+        let Ok(meta) = entry.metadata() else {
+            continue;
+        };
+        units.push(SourceUnit {
+            source,
+            path: path.to_path_buf(),
+            fingerprint: format!("{}:{}", meta.len(), modified_millis(&meta)),
+        });
+    }
+}
+
+/// OpenCode 每条消息一个文件，按会话目录整体判断是否变化
+fn collect_opencode_sessions(root: &Path, units: &mut Vec<SourceUnit>) {
+    let Ok(sessions) = fs::read_dir(root) else {
+        return;
+    };
+    for session in sessions.filter_map(|e| e.ok()) {
+        let path = session.path();
+        if !path.is_dir() {
+            continue;
+        }
+        let (mut count, mut size, mut latest) = (0u64, 0u64, 0u128);
+        for file in fs::read_dir(&path)
+            .into_iter()
+            .flatten()
+            .filter_map(|e| e.ok())
+        {
+            if let Ok(meta) = file.metadata() {
+                count += 1;
+                size += meta.len();
+                latest = latest.max(modified_millis(&meta));
+            }
+        }
+        units.push(SourceUnit {
+            source: SOURCE_OPENCODE,
+            path,
+            fingerprint: format!("{}:{}:{}", count, size, latest),
+        });
+    }
+}
+
+/// 列出所有会话日志，会话历史索引也复用这份清单
+pub(crate) fn collect_units() -> Vec<SourceUnit> {
+    let Some(home) = dirs::home_dir() else {
+        return Vec::new();
+    };
+    let mut units = Vec::new();
+    collect_jsonl_files(
+        &home.join(".claude").join("projects"),
+        SOURCE_CLAUDE_CODE,
+        &mut units,
+    );
+    for dir in ["sessions", "archived_sessions"] {
+        collect_jsonl_files(&home.join(".codex").join(dir), SOURCE_CODEX, &mut units);
+    }
+    collect_opencode_sessions(
+        &home.join(".local/share/opencode/storage/message"),
+        &mut units,
+    );
+    units
+}
+
+fn parse_unit(unit: &SourceUnit, timeline: &ProviderTimeline) -> Vec<UsageBucket> {
+    let events = match unit.source {
+        SOURCE_OPENCODE => fs::read_dir(&unit.path)
+            .into_iter()
+            .flatten()
+            .filter_map(|e| e.ok())
+            .filter_map(|e| fs::read_to_string(e.path()).ok())
+            .filter_map(|content| parser::parse_opencode_message(&content))
+            .collect(),
+        source => {
+            let Ok(content) = fs::read_to_string(&unit.path) else {
+                return Vec::new();
+            };
+            if source == SOURCE_CLAUDE_CODE {
+                parser::parse_claude_transcript(&content)
+            } else {
+                parser::parse_codex_session(&content)
+            }
+        }
+    };
+    parser::bucketize(unit.source, events, timeline)
+}
+
+async fn load_fingerprints(
+    db: &surrealdb::Surreal<surrealdb::engine::local::Db>,
+) -> Result<HashMap<String, String>, String> {
+    let records: Vec<Value> = db
+        .query("SELECT path, fingerprint FROM usage_file")
+        .await
+        .map_err(|e| format!("Failed to query usage cache: {}", e))?
+        .take(0)
+        .map_err(|e| format!("Failed to parse usage cache: {}", e))?;
+    Ok(records
+        .iter()
+        .filter_map(|r| {
+            Some((
+                r.get("path")?.as_str()?.to_string(),
+                r.get("fingerprint")?.as_str()?.to_string(),
+            ))
+        })
+        .collect())
+}
+
+async fn load_timelines(
+    db: &surrealdb::Surreal<surrealdb::engine::local::Db>,
+) -> Result<HashMap<String, ProviderTimeline>, String> {
+    let records: Vec<Value> = db
+        .query("SELECT module, provider_id, provider_name, applied_at FROM usage_provider_timeline")
+        .await
+        .map_err(|e| format!("Failed to query provider timeline: {}", e))?
+        .take(0)
+        .map_err(|e| format!("Failed to parse provider timeline: {}", e))?;
+
+    let mut entries: HashMap<String, Vec<_>> = HashMap::new();
+    for record in &records {
+        let get = |key: &str| record.get(key).and_then(|v| v.as_str()).unwrap_or_default();
+        let Some(applied_at) = parser::parse_timestamp(get("applied_at")) else {
+            continue;
+        };
+        entries.entry(get("module").to_string()).or_default().push((
+            applied_at,
+            get("provider_id").to_string(),
+            get("provider_name").to_string(),
+        ));
+    }
+    Ok(entries
+        .into_iter()
+        .map(|(module, entries)| (module, ProviderTimeline::new(entries)))
+        .collect())
+}
+
+/// 增量扫描：只重新解析新增或变化的日志，已删除的日志保留其历史用量
+pub async fn scan_usage_logs(
+    db: &surrealdb::Surreal<surrealdb::engine::local::Db>,
+) -> Result<UsageScanResult, String> {
+    let _guard = SCAN_LOCK.lock().await;
+
+    let known = load_fingerprints(db).await?;
+    let timelines = load_timelines(db).await?;
+    let no_timeline = ProviderTimeline::default();
+    let units = collect_units();
+
+    let mut files_updated = 0;
+    for unit in &units {
+        let path = unit.path.to_string_lossy().to_string();
+        if known.get(&path) == Some(&unit.fingerprint) {
+            continue;
+        }
+        let timeline = timelines.get(unit.source).unwrap_or(&no_timeline);
+        let buckets = parse_unit(unit, timeline);
+
+        let data = json!({
+            "path": path,
+            "source": unit.source,
+            "fingerprint": unit.fingerprint,
+            "buckets": buckets,
+            "scanned_at": Local::now().to_rfc3339(),
+        });
+        db.query(format!(
+            "DELETE usage_file WHERE path = $path; CREATE usage_file:`{}` CONTENT $data",
+            db_new_id()
+        ))
+        .bind(("path", path))
+        .bind(("data", data))
+        .await
+        .map_err(|e| format!("Failed to save usage cache: {}", e))?;
+        files_updated += 1;
+    }
+
+    Ok(UsageScanResult {
+        files_total: units.len(),
+        files_updated,
+    })
+}
+
+/// 记录 Claude Code / Codex 应用了哪个 provider，用于把之后的会话用量归属到该 provider
+pub async fn record_provider_applied(
+    db: &surrealdb::Surreal<surrealdb::engine::local::Db>,
+    module: &str,
+    provider_id: &str,
+    provider_name: &str,
+) {
+    let last: Vec<Value> = match db
+        .query("SELECT provider_id, applied_at FROM usage_provider_timeline WHERE module = $module ORDER BY applied_at DESC LIMIT 1")
+        .bind(("module", module.to_string()))
+        .await
+    {
+        Ok(mut result) => result.take(0).unwrap_or_default(),
+        Err(e) => {
+            log::warn!("Failed to query provider timeline: {}", e);
+            return;
+        }
+    };
+    let unchanged = last
+        .first()
+        .and_then(|r| r.get("provider_id"))
+        .and_then(|v| v.as_str())
+        == Some(provider_id);
+    if unchanged {
+        return;
+    }
+
+    let data = json!({
+        "module": module,
+        "provider_id": provider_id,
+        "provider_name": provider_name,
+        "applied_at": Local::now().to_rfc3339(),
+    });
+    if let Err(e) = db
+        .query(format!(
+            "CREATE usage_provider_timeline:`{}` CONTENT $data",
+            db_new_id()
+        ))
+        .bind(("data", data))
+        .await
+    {
+        log::warn!("Failed to record provider switch: {}", e);
+    }
+}
+
+async fn load_buckets(
+    db: &surrealdb::Surreal<surrealdb::engine::local::Db>,
+    sources: &[String],
+) -> Result<Vec<UsageBucket>, String> {
+    let mut result = if sources.is_empty() {
+        db.query("SELECT buckets FROM usage_file").await
+    } else {
+        db.query("SELECT buckets FROM usage_file WHERE source IN $sources")
+            .bind(("sources", sources.to_vec()))
+            .await
+    }
+    .map_err(|e| format!("Failed to query usage cache: {}", e))?;
+
+    let records: Vec<Value> = result
+        .take(0)
+        .map_err(|e| format!("Failed to parse usage cache: {}", e))?;
+    Ok(records
+        .into_iter()
+        .filter_map(|r| r.get("buckets").and_then(|b| b.as_array()).cloned())
+        .flatten()
+        .filter_map(|b| serde_json::from_value(b).ok())
+        .collect())
+}
+
+fn accumulate(row: &mut UsageReportRow, tokens: &TokenCounts, cost: Option<f64>) {
+    row.tokens.add(tokens);
+    match cost {
+        Some(cost) => row.cost_usd += cost,
+        None => row.unpriced_tokens += tokens.total(),
+    }
+}
+
+fn build_report(buckets: Vec<UsageBucket>, query: &UsageQuery, prices: &PriceTable) -> UsageReport {
+    let mut rows: BTreeMap<String, UsageReportRow> = BTreeMap::new();
+    let mut total = UsageReportRow {
+        key: "total".to_string(),
+        ..Default::default()
+    };
+    let mut unpriced_models = BTreeSet::new();
+
+    for bucket in buckets {
+        if query
+            .start_date
+            .as_deref()
+            .is_some_and(|start| bucket.date.as_str() < start)
+            || query
+                .end_date
+                .as_deref()
+                .is_some_and(|end| bucket.date.as_str() > end)
+            || (!query.sources.is_empty() && !query.sources.contains(&bucket.source))
+        {
+            continue;
+        }
+
+        let cost = prices
+            .lookup(&bucket.model)
+            .map(|price| pricing::cost_usd(price, &bucket.tokens));
+        if cost.is_none() && bucket.tokens.total() > 0 {
+            unpriced_models.insert(bucket.model.clone());
+        }
+
+        let (key, label) = match query.group_by.as_str() {
+            "model" => (bucket.model, None),
+            "project" => (bucket.project, None),
+            "provider" => (bucket.provider_id.unwrap_or_default(), bucket.provider_name),
+            "source" => (bucket.source, None),
+            _ => (bucket.date, None),
+        };
+        let row = rows.entry(key.clone()).or_insert_with(|| UsageReportRow {
+            key,
+            ..Default::default()
+        });
+        if row.label.is_none() {
+            row.label = label;
+        }
+        accumulate(row, &bucket.tokens, cost);
+        accumulate(&mut total, &bucket.tokens, cost);
+    }
+
+    let mut rows: Vec<UsageReportRow> = rows.into_values().collect();
+    // 按天时保持日期升序，其余维度按费用、token 数降序
+    if query.group_by != "day" {
+        rows.sort_by(|a, b| {
+            b.cost_usd
+                .total_cmp(&a.cost_usd)
+                .then_with(|| b.tokens.total().cmp(&a.tokens.total()))
+        });
+    }
+
+    UsageReport {
+        rows,
+        total,
+        unpriced_models: unpriced_models.into_iter().collect(),
+    }
+}
+
+/// 扫描最新日志后按条件汇总用量与费用
+#[tauri::command]
+pub async fn get_usage_report(
+    state: tauri::State<'_, DbState>,
+    query: UsageQuery,
+) -> Result<UsageReport, String> {
+    let db = state.db();
+    scan_usage_logs(&db).await?;
+    let buckets = load_buckets(&db, &query.sources).await?;
+    let catalog = preset_models::load_cached_preset_models()?.unwrap_or(Value::Null);
+    Ok(build_report(
+        buckets,
+        &query,
+        &PriceTable::from_catalog(&catalog),
+    ))
+}
+
+/// 清空缓存后重新解析全部日志（provider 切换记录不受影响）
+#[tauri::command]
+pub async fn rescan_usage_logs(
+    state: tauri::State<'_, DbState>,
+) -> Result<UsageScanResult, String> {
+    let db = state.db();
+    db.query("DELETE usage_file")
+        .await
+        .map_err(|e| format!("Failed to clear usage cache: {}", e))?;
+    scan_usage_logs(&db).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bucket(date: &str, source: &str, model: &str, input: u64) -> UsageBucket {
+        UsageBucket {
+            date: date.to_string(),
+            source: source.to_string(),
+            model: model.to_string(),
+            project: "/p".to_string(),
+            provider_id: None,
+            provider_name: None,
+            tokens: TokenCounts {
+                input_tokens: input,
+                requests: 1,
+                ..Default::default()
+            },
+        }
+    }
+
+    #[test]
+    fn build_report_filters_dates_and_prices_models() {
+        let prices = PriceTable::from_catalog(&json!({
+            "@ai-sdk/openai": [{ "id": "gpt-5", "cost": { "input": 1, "output": 10 } }],
+        }));
+        let buckets = vec![
+            bucket("2025-05-31", SOURCE_CODEX, "gpt-5", 1_000_000),
+            bucket("2025-06-01", SOURCE_CODEX, "gpt-5", 2_000_000),
+            bucket("2025-06-01", SOURCE_OPENCODE, "local-llama", 500),
+            bucket("2025-06-02", SOURCE_CODEX, "gpt-5", 1_000_000),
+        ];
+        let query = UsageQuery {
+            start_date: Some("2025-06-01".to_string()),
+            end_date: Some("2025-06-01".to_string()),
+            sources: Vec::new(),
+            group_by: "model".to_string(),
+        };
+        let report = build_report(buckets, &query, &prices);
+
+        assert_eq!(report.rows.len(), 2);
+        assert_eq!(report.rows[0].key, "gpt-5");
+        assert!((report.rows[0].cost_usd - 2.0).abs() < 1e-9);
+        assert_eq!(report.rows[1].unpriced_tokens, 500);
+        assert_eq!(report.total.tokens.requests, 2);
+        assert_eq!(report.unpriced_models, vec!["local-llama".to_string()]);
+    }
+}
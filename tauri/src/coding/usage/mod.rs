@@ -0,0 +1,19 @@
+//! Token 用量与费用统计
+//!
+//! 解析本地会话日志：Claude Code（`~/.claude/projects/**/*.jsonl`）、Codex
+//! （`~/.codex/sessions`）与 OpenCode（`~/.local/share/opencode/storage/message`），
+//! 按日期、模型、项目和 provider 聚合 input / output / cache token。
+//!
+//! 每个日志文件（OpenCode 为会话目录）解析后的结果缓存在 `usage_file` 表，按文件大小和
+//! 修改时间判断是否需要重新解析。Claude Code / Codex 的日志不记录 provider，应用 provider 时
+//! 写入 `usage_provider_timeline`，按调用时间归属到当时应用的 provider。
+//!
+//! 费用按预设模型目录中的 `cost`（与 OpenClaw 相同，美元 / 百万 token）计算。
+
+mod commands;
+mod parser;
+mod pricing;
+mod types;
+
+pub use commands::*;
+pub use types::*;
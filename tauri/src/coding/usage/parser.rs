@@ -0,0 +1,349 @@
+//! 解析各工具的本地会话日志，提取每次模型调用的 token 用量
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Local, TimeZone};
+use serde_json::Value;
+
+use super::types::{TokenCounts, UsageBucket};
+
+/// 一次模型调用
+#[derive(Debug, Clone, PartialEq)]
+pub struct UsageEvent {
+    pub timestamp: DateTime<Local>,
+    pub model: String,
+    pub project: String,
+    /// 日志本身记录了 provider 时使用（OpenCode），否则按切换记录推断
+    pub provider_id: Option<String>,
+    pub tokens: TokenCounts,
+}
+
+fn u64_at(value: &Value, path: &str) -> u64 {
+    value.pointer(path).and_then(|v| v.as_u64()).unwrap_or(0)
+}
+
+fn str_at<'a>(value: &'a Value, path: &str) -> Option<&'a str> {
+    value
+        .pointer(path)
+        .and_then(|v| v.as_str())
+        .filter(|s| !s.is_empty())
+}
+
+pub fn parse_timestamp(value: &str) -> Option<DateTime<Local>> {
+    DateTime::parse_from_rfc3339(value)
+        .ok()
+        .map(|t| t.with_timezone(&Local))
+}
+
+/// Claude Code 会话（`~/.claude/projects/<project>/<session>.jsonl`）
+///
+/// 同一条 assistant 消息按内容块拆成多行写入，usage 相同，按 message.id + requestId 去重并保留最后一行。
+pub fn parse_claude_transcript(content: &str) -> Vec<UsageEvent> {
+    let mut order = Vec::new();
+    let mut events: HashMap<String, UsageEvent> = HashMap::new();
+
+    for (line_no, line) in content.lines().enumerate() {
+        let Ok(entry) = serde_json::from_str::<Value>(line) else {
+            continue;
+        };
+        if entry.get("type").and_then(|v| v.as_str()) != Some("assistant") {
+            continue;
+        }
+        let Some(usage) = entry.pointer("/message/usage") else {
+            continue;
+        };
+        let model = str_at(&entry, "/message/model").unwrap_or("unknown");
+        // 本地生成的提示消息（如中断、错误）不是模型调用
+        if model == "<synthetic>" {
+            continue;
+        }
+        let Some(timestamp) = str_at(&entry, "/timestamp").and_then(parse_timestamp) else {
+            continue;
+        };
+
+        let key = match (str_at(&entry, "/message/id"), str_at(&entry, "/requestId")) {
+            (Some(id), request_id) => format!("{}:{}", id, request_id.unwrap_or_default()),
+            (None, _) => format!("line:{}", line_no),
+        };
+        let event = UsageEvent {
+            timestamp,
+            model: model.to_string(),
+            project: str_at(&entry, "/cwd").unwrap_or_default().to_string(),
+            provider_id: None,
+            tokens: TokenCounts {
+                input_tokens: u64_at(usage, "/input_tokens"),
+                output_tokens: u64_at(usage, "/output_tokens"),
+                cache_read_tokens: u64_at(usage, "/cache_read_input_tokens"),
+                cache_write_tokens: u64_at(usage, "/cache_creation_input_tokens"),
+                requests: 1,
+            },
+        };
+        if events.insert(key.clone(), event).is_none() {
+            order.push(key);
+        }
+    }
+
+    order
+        .into_iter()
+        .filter_map(|key| events.remove(&key))
+        .collect()
+}
+
+/// Codex 会话（`~/.codex/sessions/YYYY/MM/DD/rollout-*.jsonl`）
+///
+/// 每轮结束时写入 `token_count` 事件，`last_token_usage` 为本轮用量；同一轮的事件可能重复写入，
+/// 累计用量（`total_token_usage`）未变化时跳过。Codex 的 input_tokens 包含缓存命中部分。
+pub fn parse_codex_session(content: &str) -> Vec<UsageEvent> {
+    let mut events = Vec::new();
+    let mut model = String::from("unknown");
+    let mut project = String::new();
+    let mut last_total: Option<Value> = None;
+
+    for line in content.lines() {
+        let Ok(entry) = serde_json::from_str::<Value>(line) else {
+            continue;
+        };
+        let payload = entry.get("payload").unwrap_or(&Value::Null);
+        match entry.get("type").and_then(|v| v.as_str()) {
+            Some("session_meta") => {
+                if let Some(cwd) = str_at(payload, "/cwd") {
+                    project = cwd.to_string();
+                }
+            }
+            Some("turn_context") => {
+                if let Some(m) = str_at(payload, "/model") {
+                    model = m.to_string();
+                }
+                if let Some(cwd) = str_at(payload, "/cwd") {
+                    project = cwd.to_string();
+                }
+            }
+            Some("event_msg") if str_at(payload, "/type") == Some("token_count") => {
+                let Some(info) = payload.get("info").filter(|i| i.is_object()) else {
+                    continue;
+                };
+                let total = info.get("total_token_usage").cloned();
+                if total.is_some() && total == last_total {
+                    continue;
+                }
+                last_total = total;
+
+                let Some(last) = info.get("last_token_usage") else {
+                    continue;
+                };
+                let Some(timestamp) = str_at(&entry, "/timestamp").and_then(parse_timestamp) else {
+                    continue;
+                };
+                let cached = u64_at(last, "/cached_input_tokens");
+                events.push(UsageEvent {
+                    timestamp,
+                    model: model.clone(),
+                    project: project.clone(),
+                    provider_id: None,
+                    tokens: TokenCounts {
+                        input_tokens: u64_at(last, "/input_tokens").saturating_sub(cached),
+                        output_tokens: u64_at(last, "/output_tokens"),
+                        cache_read_tokens: cached,
+                        cache_write_tokens: 0,
+                        requests: 1,
+                    },
+                });
+            }
+            _ => {}
+        }
+    }
+
+    events
+}
+
+/// OpenCode 消息（`~/.local/share/opencode/storage/message/<session>/<message>.json`）
+pub fn parse_opencode_message(content: &str) -> Option<UsageEvent> {
+    let message: Value = serde_json::from_str(content).ok()?;
+    if message.get("role").and_then(|v| v.as_str()) != Some("assistant") {
+        return None;
+    }
+    let tokens = message.get("tokens")?;
+    let created = message.pointer("/time/created").and_then(|v| v.as_i64())?;
+    let timestamp = Local.timestamp_millis_opt(created).single()?;
+
+    let tokens = TokenCounts {
+        input_tokens: u64_at(tokens, "/input"),
+        // reasoning 单独统计，按输出计费
+        output_tokens: u64_at(tokens, "/output") + u64_at(tokens, "/reasoning"),
+        cache_read_tokens: u64_at(tokens, "/cache/read"),
+        cache_write_tokens: u64_at(tokens, "/cache/write"),
+        requests: 1,
+    };
+    if tokens.total() == 0 {
+        return None;
+    }
+
+    Some(UsageEvent {
+        timestamp,
+        model: str_at(&message, "/modelID")
+            .unwrap_or("unknown")
+            .to_string(),
+        project: str_at(&message, "/path/cwd")
+            .or_else(|| str_at(&message, "/path/root"))
+            .unwrap_or_default()
+            .to_string(),
+        provider_id: str_at(&message, "/providerID").map(String::from),
+        tokens,
+    })
+}
+
+/// Claude Code / Codex 的 provider 切换记录，按时间升序
+#[derive(Debug, Clone, Default)]
+pub struct ProviderTimeline {
+    entries: Vec<(DateTime<Local>, String, String)>,
+}
+
+impl ProviderTimeline {
+    /// `entries`: (应用时间, provider id, provider 名称)
+    pub fn new(mut entries: Vec<(DateTime<Local>, String, String)>) -> Self {
+        entries.sort_by_key(|(time, _, _)| *time);
+        Self { entries }
+    }
+
+    /// 事件发生时已应用的 provider；早于第一条记录时无法判断
+    pub fn provider_at(&self, timestamp: DateTime<Local>) -> Option<(&str, &str)> {
+        self.entries
+            .iter()
+            .rev()
+            .find(|(time, _, _)| *time <= timestamp)
+            .map(|(_, id, name)| (id.as_str(), name.as_str()))
+    }
+}
+
+/// 把事件聚合为按天的 bucket
+pub fn bucketize(
+    source: &str,
+    events: Vec<UsageEvent>,
+    timeline: &ProviderTimeline,
+) -> Vec<UsageBucket> {
+    let mut buckets: Vec<UsageBucket> = Vec::new();
+    for event in events {
+        // OpenCode 的 providerID 即配置中的 provider 键名，直接作为名称显示
+        let (provider_id, provider_name) = match event.provider_id {
+            Some(id) => (Some(id), None),
+            None => match timeline.provider_at(event.timestamp) {
+                Some((id, name)) => (Some(id.to_string()), Some(name.to_string())),
+                None => (None, None),
+            },
+        };
+        let date = event.timestamp.format("%Y-%m-%d").to_string();
+
+        match buckets.iter_mut().find(|b| {
+            b.date == date
+                && b.model == event.model
+                && b.project == event.project
+                && b.provider_id == provider_id
+        }) {
+            Some(bucket) => bucket.tokens.add(&event.tokens),
+            None => buckets.push(UsageBucket {
+                date,
+                source: source.to_string(),
+                model: event.model,
+                project: event.project,
+                provider_id,
+                provider_name,
+                tokens: event.tokens,
+            }),
+        }
+    }
+    buckets
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn claude_transcript_dedupes_split_messages() {
+        let content = [
+            r#"{"type":"user","timestamp":"2025-06-01T10:00:00Z","message":{"role":"user","content":"hi"}}"#,
+            r#"{"type":"assistant","timestamp":"2025-06-01T10:00:01Z","cwd":"/work/app","requestId":"req_1","message":{"id":"msg_1","model":"claude-sonnet-4-5-20250929","usage":{"input_tokens":10,"output_tokens":1,"cache_read_input_tokens":100,"cache_creation_input_tokens":5}}}"#,
+            r#"{"type":"assistant","timestamp":"2025-06-01T10:00:02Z","cwd":"/work/app","requestId":"req_1","message":{"id":"msg_1","model":"claude-sonnet-4-5-20250929","usage":{"input_tokens":10,"output_tokens":40,"cache_read_input_tokens":100,"cache_creation_input_tokens":5}}}"#,
+            r#"{"type":"assistant","timestamp":"2025-06-01T10:00:03Z","message":{"id":"msg_2","model":"<synthetic>","usage":{"input_tokens":0,"output_tokens":0}}}"#,
+        ]
+        .join("\n");
+        let events = parse_claude_transcript(&content);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].project, "/work/app");
+        assert_eq!(
+            events[0].tokens,
+            TokenCounts {
+                input_tokens: 10,
+                output_tokens: 40,
+                cache_read_tokens: 100,
+                cache_write_tokens: 5,
+                requests: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn codex_session_skips_repeated_token_counts() {
+        let content = [
+            r#"{"timestamp":"2025-06-01T10:00:00Z","type":"session_meta","payload":{"cwd":"/work/api"}}"#,
+            r#"{"timestamp":"2025-06-01T10:00:00Z","type":"turn_context","payload":{"model":"gpt-5-codex","cwd":"/work/api"}}"#,
+            r#"{"timestamp":"2025-06-01T10:00:05Z","type":"event_msg","payload":{"type":"token_count","info":{"total_token_usage":{"input_tokens":1000},"last_token_usage":{"input_tokens":1000,"cached_input_tokens":800,"output_tokens":50}}}}"#,
+            r#"{"timestamp":"2025-06-01T10:00:06Z","type":"event_msg","payload":{"type":"token_count","info":{"total_token_usage":{"input_tokens":1000},"last_token_usage":{"input_tokens":1000,"cached_input_tokens":800,"output_tokens":50}}}}"#,
+            r#"{"timestamp":"2025-06-01T10:00:07Z","type":"event_msg","payload":{"type":"token_count","info":null}}"#,
+        ]
+        .join("\n");
+        let events = parse_codex_session(&content);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].model, "gpt-5-codex");
+        assert_eq!(events[0].tokens.input_tokens, 200);
+        assert_eq!(events[0].tokens.cache_read_tokens, 800);
+    }
+
+    #[test]
+    fn bucketize_attributes_provider_by_timeline() {
+        let at = |s: &str| parse_timestamp(s).unwrap();
+        let timeline = ProviderTimeline::new(vec![
+            (at("2025-06-01T12:00:00Z"), "p2".into(), "Relay".into()),
+            (at("2025-06-01T08:00:00Z"), "p1".into(), "Official".into()),
+        ]);
+        let event = |time: &str| UsageEvent {
+            timestamp: at(time),
+            model: "m".into(),
+            project: "/p".into(),
+            provider_id: None,
+            tokens: TokenCounts {
+                input_tokens: 1,
+                requests: 1,
+                ..Default::default()
+            },
+        };
+        let buckets = bucketize(
+            "claudecode",
+            vec![
+                event("2025-06-01T07:00:00Z"),
+                event("2025-06-01T09:00:00Z"),
+                event("2025-06-01T10:00:00Z"),
+                event("2025-06-01T13:00:00Z"),
+            ],
+            &timeline,
+        );
+        // 按本地日期分桶，跨时区时可能拆成多天，这里只按 provider 汇总
+        let requests = |id: Option<&str>| {
+            buckets
+                .iter()
+                .filter(|b| b.provider_id.as_deref() == id)
+                .map(|b| b.tokens.requests)
+                .sum::<u64>()
+        };
+        assert_eq!(requests(None), 1);
+        assert_eq!(requests(Some("p1")), 2);
+        assert_eq!(requests(Some("p2")), 1);
+        assert_eq!(
+            buckets
+                .iter()
+                .find(|b| b.provider_id.as_deref() == Some("p2"))
+                .and_then(|b| b.provider_name.as_deref()),
+            Some("Relay")
+        );
+    }
+}
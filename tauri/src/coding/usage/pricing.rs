@@ -0,0 +1,133 @@
+//! 按预设模型目录中的价格（OpenClaw `cost` 格式，单位：美元 / 百万 token）计算费用
+
+use serde_json::Value;
+
+use super::types::TokenCounts;
+use crate::coding::open_claw::types::OpenClawModelCost;
+
+/// 预设模型目录中带价格的模型，按规范化后的 id 查找
+#[derive(Debug, Default)]
+pub struct PriceTable {
+    entries: Vec<(String, OpenClawModelCost)>,
+}
+
+/// 统一大小写，去掉 `provider/` 前缀、`@版本`（Vertex）和 `-YYYYMMDD` 日期后缀
+pub fn normalize_model_id(model: &str) -> String {
+    let model = model.trim().to_ascii_lowercase();
+    let model = model.rsplit('/').next().unwrap_or_default();
+    let model = model.split('@').next().unwrap_or_default();
+    match model.rsplit_once('-') {
+        Some((base, suffix)) if suffix.len() == 8 && suffix.bytes().all(|b| b.is_ascii_digit()) => {
+            base.to_string()
+        }
+        _ => model.to_string(),
+    }
+}
+
+impl PriceTable {
+    /// `catalog` 为 `{ npm: [model, ...] }`，同一模型出现多次时取第一个价格
+    pub fn from_catalog(catalog: &Value) -> Self {
+        let mut entries: Vec<(String, OpenClawModelCost)> = Vec::new();
+        let models = catalog
+            .as_object()
+            .into_iter()
+            .flat_map(|groups| groups.values())
+            .filter_map(|group| group.as_array())
+            .flatten();
+        for model in models {
+            let (Some(id), Some(cost)) =
+                (model.get("id").and_then(|v| v.as_str()), model.get("cost"))
+            else {
+                continue;
+            };
+            let Ok(cost) = serde_json::from_value::<OpenClawModelCost>(cost.clone()) else {
+                continue;
+            };
+            let id = normalize_model_id(id);
+            if !entries.iter().any(|(existing, _)| *existing == id) {
+                entries.push((id, cost));
+            }
+        }
+        Self { entries }
+    }
+
+    /// 先精确匹配，再匹配最长的前缀（如 `gpt-5-codex` 使用 `gpt-5` 的价格）
+    pub fn lookup(&self, model: &str) -> Option<&OpenClawModelCost> {
+        let model = normalize_model_id(model);
+        if let Some((_, cost)) = self.entries.iter().find(|(id, _)| *id == model) {
+            return Some(cost);
+        }
+        self.entries
+            .iter()
+            .filter(|(id, _)| {
+                model
+                    .strip_prefix(id.as_str())
+                    .is_some_and(|rest| rest.starts_with('-'))
+            })
+            .max_by_key(|(id, _)| id.len())
+            .map(|(_, cost)| cost)
+    }
+}
+
+/// 缓存读写未单独定价时按输入价格计算
+pub fn cost_usd(cost: &OpenClawModelCost, tokens: &TokenCounts) -> f64 {
+    let per_token = |price: f64, count: u64| price * count as f64 / 1_000_000.0;
+    per_token(cost.input, tokens.input_tokens)
+        + per_token(cost.output, tokens.output_tokens)
+        + per_token(
+            cost.cache_read.unwrap_or(cost.input),
+            tokens.cache_read_tokens,
+        )
+        + per_token(
+            cost.cache_write.unwrap_or(cost.input),
+            tokens.cache_write_tokens,
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn lookup_normalizes_dates_and_prefixes() {
+        let table = PriceTable::from_catalog(&json!({
+            "@ai-sdk/anthropic": [
+                { "id": "claude-sonnet-4-5-20250929", "cost": { "input": 3, "output": 15, "cacheRead": 0.3, "cacheWrite": 3.75 } },
+                { "id": "claude-no-price" },
+            ],
+            "@ai-sdk/openai": [
+                { "id": "gpt-5", "cost": { "input": 1.25, "output": 10 } },
+                { "id": "gpt-5-mini", "cost": { "input": 0.25, "output": 2 } },
+            ],
+        }));
+
+        assert_eq!(
+            table.lookup("claude-sonnet-4-5").map(|c| c.output),
+            Some(15.0)
+        );
+        assert_eq!(
+            table
+                .lookup("anthropic/Claude-Sonnet-4-5@20250929")
+                .map(|c| c.input),
+            Some(3.0)
+        );
+        assert_eq!(table.lookup("gpt-5-codex").map(|c| c.input), Some(1.25));
+        assert_eq!(
+            table.lookup("gpt-5-mini-2025-08-07").map(|c| c.input),
+            Some(0.25)
+        );
+        assert!(table.lookup("claude-no-price").is_none());
+        assert!(table.lookup("gpt-50").is_none());
+
+        let cost = table.lookup("claude-sonnet-4-5").unwrap();
+        let tokens = TokenCounts {
+            input_tokens: 1_000_000,
+            output_tokens: 100_000,
+            cache_read_tokens: 1_000_000,
+            cache_write_tokens: 0,
+            requests: 1,
+        };
+        assert!((cost_usd(cost, &tokens) - 4.8).abs() < 1e-9);
+    }
+}
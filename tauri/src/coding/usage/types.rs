@@ -0,0 +1,98 @@
+use serde::{Deserialize, Serialize};
+
+/// 累计的 token 数
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenCounts {
+    #[serde(default)]
+    pub input_tokens: u64,
+    #[serde(default)]
+    pub output_tokens: u64,
+    #[serde(default)]
+    pub cache_read_tokens: u64,
+    #[serde(default)]
+    pub cache_write_tokens: u64,
+    /// 模型请求（assistant 消息）次数
+    #[serde(default)]
+    pub requests: u64,
+}
+
+impl TokenCounts {
+    pub fn add(&mut self, other: &TokenCounts) {
+        self.input_tokens += other.input_tokens;
+        self.output_tokens += other.output_tokens;
+        self.cache_read_tokens += other.cache_read_tokens;
+        self.cache_write_tokens += other.cache_write_tokens;
+        self.requests += other.requests;
+    }
+
+    pub fn total(&self) -> u64 {
+        self.input_tokens + self.output_tokens + self.cache_read_tokens + self.cache_write_tokens
+    }
+}
+
+/// 单个日志文件按 (日期, 模型, 项目, provider) 聚合后的用量，随 `usage_file` 记录缓存在数据库中
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UsageBucket {
+    /// 本地日期 YYYY-MM-DD
+    pub date: String,
+    /// "claudecode" | "codex" | "opencode"
+    pub source: String,
+    pub model: String,
+    /// 会话工作目录
+    pub project: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provider_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provider_name: Option<String>,
+    #[serde(flatten)]
+    pub tokens: TokenCounts,
+}
+
+/// 用量查询条件
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UsageQuery {
+    /// 起始日期（含），YYYY-MM-DD
+    pub start_date: Option<String>,
+    /// 结束日期（含），YYYY-MM-DD
+    pub end_date: Option<String>,
+    /// 只统计这些来源，为空表示全部
+    #[serde(default)]
+    pub sources: Vec<String>,
+    /// "day" | "model" | "project" | "provider" | "source"
+    pub group_by: String,
+}
+
+/// 报表中的一行
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UsageReportRow {
+    /// 分组键；provider 未知时为空字符串
+    pub key: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    #[serde(flatten)]
+    pub tokens: TokenCounts,
+    /// 按预设模型价格计算的费用（美元），不含未定价模型
+    pub cost_usd: f64,
+    /// 没有价格数据的 token 数
+    pub unpriced_tokens: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UsageReport {
+    pub rows: Vec<UsageReportRow>,
+    pub total: UsageReportRow,
+    /// 有用量但预设模型中没有价格的模型
+    pub unpriced_models: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UsageScanResult {
+    pub files_total: usize,
+    pub files_updated: usize,
+}
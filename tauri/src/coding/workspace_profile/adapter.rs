@@ -0,0 +1,41 @@
+use super::types::{WorkspaceProfile, WorkspaceProfileContent};
+use crate::coding::db_id::db_extract_id;
+use serde_json::{json, Value};
+
+fn get_opt_str(value: &Value, key: &str) -> Option<String> {
+    value.get(key).and_then(|v| v.as_str()).map(String::from)
+}
+
+/// Convert database Value to WorkspaceProfile with fault tolerance
+pub fn from_db_value(value: Value) -> WorkspaceProfile {
+    let selection = value
+        .get("selection")
+        .cloned()
+        .and_then(|v| serde_json::from_value(v).ok())
+        .unwrap_or_default();
+
+    WorkspaceProfile {
+        id: db_extract_id(&value),
+        name: get_opt_str(&value, "name").unwrap_or_else(|| "Unnamed Profile".to_string()),
+        description: get_opt_str(&value, "description"),
+        selection,
+        is_applied: value
+            .get("is_applied")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false),
+        sort_index: value
+            .get("sort_index")
+            .and_then(|v| v.as_i64())
+            .map(|v| v as i32),
+        created_at: get_opt_str(&value, "created_at"),
+        updated_at: get_opt_str(&value, "updated_at"),
+    }
+}
+
+/// Convert WorkspaceProfileContent to database Value
+pub fn to_db_value(content: &WorkspaceProfileContent) -> Value {
+    serde_json::to_value(content).unwrap_or_else(|e| {
+        eprintln!("Failed to serialize workspace profile content: {}", e);
+        json!({})
+    })
+}
@@ -0,0 +1,924 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use chrono::Local;
+use serde_json::Value;
+use tauri::{Emitter, Manager};
+
+use super::adapter;
+use super::types::*;
+use crate::coding::db_id::{db_extract_id_opt, db_record_id};
+use crate::coding::open_claw::types::ReadOpenClawConfigResult;
+use crate::coding::open_code::types::ReadConfigResult;
+use crate::coding::{
+    claude_code, codex, config_history, gemini_cli, mcp, oh_my_opencode, oh_my_opencode_slim,
+    open_claw, open_code, skills,
+};
+use crate::db::DbState;
+
+/// 同一时间只允许应用一个工作区配置，避免两次应用交错写入同一批文件和应用标记
+static APPLY_LOCK: tokio::sync::Mutex<()> = tokio::sync::Mutex::const_new(());
+
+/// 配置历史中记录的模块名
+const HISTORY_MODULE: &str = "workspace_profile";
+
+/// 应用过程中切换过的 MCP / Skill 工具，失败时再切换一次即可还原
+enum Toggled {
+    Mcp { server_id: String, tool_key: String },
+    Skill { skill_id: String, tool_key: String },
+}
+
+// ============================================================================
+// Workspace Profile Commands
+// ============================================================================
+
+/// List all workspace profiles ordered by sort_index, then name
+#[tauri::command]
+pub async fn list_workspace_profiles(
+    state: tauri::State<'_, DbState>,
+) -> Result<Vec<WorkspaceProfile>, String> {
+    let db = state.db();
+
+    let records: Vec<Value> = db
+        .query("SELECT *, type::string(id) as id FROM workspace_profile")
+        .await
+        .map_err(|e| format!("Failed to query workspace profiles: {}", e))?
+        .take(0)
+        .map_err(|e| format!("Failed to read workspace profiles: {}", e))?;
+
+    let mut result: Vec<WorkspaceProfile> =
+        records.into_iter().map(adapter::from_db_value).collect();
+    result.sort_by(|a, b| match (a.sort_index, b.sort_index) {
+        (Some(ai), Some(bi)) => ai.cmp(&bi),
+        (Some(_), None) => std::cmp::Ordering::Less,
+        (None, Some(_)) => std::cmp::Ordering::Greater,
+        (None, None) => a.name.cmp(&b.name),
+    });
+    Ok(result)
+}
+
+/// Create a new workspace profile
+#[tauri::command]
+pub async fn create_workspace_profile(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+    input: WorkspaceProfileInput,
+) -> Result<WorkspaceProfile, String> {
+    let db = state.db();
+
+    let now = Local::now().to_rfc3339();
+    let content = WorkspaceProfileContent {
+        name: input.name,
+        description: input.description,
+        selection: input.selection,
+        is_applied: false,
+        sort_index: None,
+        created_at: now.clone(),
+        updated_at: now,
+    };
+
+    db.query("CREATE workspace_profile CONTENT $data")
+        .bind(("data", adapter::to_db_value(&content)))
+        .await
+        .map_err(|e| format!("Failed to create workspace profile: {}", e))?;
+
+    // Fetch the created record to get the auto-generated ID
+    let records: Vec<Value> = db
+        .query("SELECT *, type::string(id) as id FROM workspace_profile ORDER BY created_at DESC LIMIT 1")
+        .await
+        .map_err(|e| format!("Failed to query new workspace profile: {}", e))?
+        .take(0)
+        .map_err(|e| format!("Failed to query new workspace profile: {}", e))?;
+
+    // Notify to refresh tray menu
+    let _ = app.emit("config-changed", "window");
+
+    records
+        .into_iter()
+        .next()
+        .map(adapter::from_db_value)
+        .ok_or_else(|| "Failed to retrieve created workspace profile".to_string())
+}
+
+/// Update an existing workspace profile (does not re-apply it)
+#[tauri::command]
+pub async fn update_workspace_profile(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+    id: String,
+    input: WorkspaceProfileInput,
+) -> Result<WorkspaceProfile, String> {
+    let db = state.db();
+    let existing = get_profile(&db, &id).await?;
+
+    let content = WorkspaceProfileContent {
+        name: input.name,
+        description: input.description,
+        selection: input.selection,
+        is_applied: existing.is_applied,
+        sort_index: existing.sort_index,
+        created_at: existing
+            .created_at
+            .unwrap_or_else(|| Local::now().to_rfc3339()),
+        updated_at: Local::now().to_rfc3339(),
+    };
+
+    db.query(format!(
+        "UPDATE {} CONTENT $data",
+        db_record_id("workspace_profile", &id)
+    ))
+    .bind(("data", adapter::to_db_value(&content)))
+    .await
+    .map_err(|e| format!("Failed to update workspace profile: {}", e))?;
+
+    // Notify to refresh tray menu
+    let _ = app.emit("config-changed", "window");
+
+    Ok(WorkspaceProfile {
+        id,
+        name: content.name,
+        description: content.description,
+        selection: content.selection,
+        is_applied: content.is_applied,
+        sort_index: content.sort_index,
+        created_at: Some(content.created_at),
+        updated_at: Some(content.updated_at),
+    })
+}
+
+/// Delete a workspace profile
+#[tauri::command]
+pub async fn delete_workspace_profile(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+    id: String,
+) -> Result<(), String> {
+    let db = state.db();
+
+    db.query(format!("DELETE {}", db_record_id("workspace_profile", &id)))
+        .await
+        .map_err(|e| format!("Failed to delete workspace profile: {}", e))?;
+
+    // Notify to refresh tray menu
+    let _ = app.emit("config-changed", "window");
+
+    Ok(())
+}
+
+/// Reorder workspace profiles
+#[tauri::command]
+pub async fn reorder_workspace_profiles(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+    ids: Vec<String>,
+) -> Result<(), String> {
+    let db = state.db();
+
+    for (index, id) in ids.iter().enumerate() {
+        db.query(format!(
+            "UPDATE {} SET sort_index = $index",
+            db_record_id("workspace_profile", id)
+        ))
+        .bind(("index", index as i32))
+        .await
+        .map_err(|e| format!("Failed to update sort index: {}", e))?;
+    }
+
+    let _ = app.emit("config-changed", "window");
+
+    Ok(())
+}
+
+/// 读取各工具当前的选择，用于从现状创建工作区配置
+#[tauri::command]
+pub async fn capture_workspace_selection(
+    state: tauri::State<'_, DbState>,
+) -> Result<WorkspaceProfileSelection, String> {
+    let db = state.db();
+
+    let (opencode_model, opencode_small_model) =
+        match open_code::read_opencode_config(state.clone()).await {
+            Ok(ReadConfigResult::Success { config }) => (config.model, config.small_model),
+            _ => (None, None),
+        };
+    let openclaw_model = match open_claw::read_openclaw_config(state.clone()).await {
+        Ok(ReadOpenClawConfigResult::Success { config }) => config
+            .agents
+            .and_then(|agents| agents.defaults)
+            .and_then(|defaults| defaults.model)
+            .map(|model| model.primary),
+        _ => None,
+    };
+
+    let mcp_servers = mcp::mcp_store::get_mcp_servers(&state)
+        .await?
+        .into_iter()
+        .map(|server| (server.id, server.enabled_tools))
+        .collect();
+    let skills = skills::skill_store::get_managed_skills(&state)
+        .await?
+        .into_iter()
+        .map(|skill| {
+            let tools = skills::adapter::parse_sync_details(&skill)
+                .into_iter()
+                .map(|target| target.tool)
+                .collect();
+            (skill.id, tools)
+        })
+        .collect();
+
+    Ok(WorkspaceProfileSelection {
+        claude_provider_id: applied_id(&db, "claude_provider").await,
+        codex_provider_id: applied_id(&db, "codex_provider").await,
+        gemini_cli_provider_id: applied_id(&db, "gemini_cli_provider").await,
+        claude_prompt_id: applied_id(&db, "claude_prompt_config").await,
+        codex_prompt_id: applied_id(&db, "codex_prompt_config").await,
+        gemini_cli_prompt_id: applied_id(&db, "gemini_cli_prompt_config").await,
+        opencode_prompt_id: applied_id(&db, "opencode_prompt_config").await,
+        opencode_model,
+        opencode_small_model,
+        openclaw_model,
+        oh_my_opencode_config_id: applied_id(&db, "oh_my_opencode_config").await,
+        oh_my_opencode_slim_config_id: applied_id(&db, "oh_my_opencode_slim_config").await,
+        mcp_servers: Some(mcp_servers),
+        skills: Some(skills),
+    })
+}
+
+/// Apply a workspace profile to all tools
+#[tauri::command]
+pub async fn apply_workspace_profile(
+    app: tauri::AppHandle,
+    profile_id: String,
+    force: Option<bool>,
+) -> Result<(), String> {
+    apply_profile_internal(&app, &profile_id, false, force.unwrap_or(false)).await
+}
+
+/// 依次应用配置中的每一项；任一步失败时还原已写入的文件、应用标记和 MCP / Skill 开关
+pub async fn apply_profile_internal<R: tauri::Runtime>(
+    app: &tauri::AppHandle<R>,
+    profile_id: &str,
+    from_tray: bool,
+    force: bool,
+) -> Result<(), String> {
+    let _guard = APPLY_LOCK.lock().await;
+    let db = app.state::<DbState>().db();
+    let profile = get_profile(&db, profile_id).await?;
+    let selection = &profile.selection;
+
+    let previous_flags = capture_applied_flags(&db, selection).await;
+    let mut toggled = Vec::new();
+
+    let (result, transaction) = config_history::transaction(apply_selection(
+        app,
+        &db,
+        selection,
+        from_tray,
+        force,
+        &mut toggled,
+    ))
+    .await;
+    if let Err(e) = result {
+        log::warn!("应用工作区配置 {} 失败，开始回滚: {}", profile.name, e);
+        rollback(app, &db, previous_flags, toggled, transaction).await;
+        let payload = if from_tray { "tray" } else { "window" };
+        let _ = app.emit("config-changed", payload);
+        return Err(e);
+    }
+
+    let now = Local::now().to_rfc3339();
+    db.query("UPDATE workspace_profile SET is_applied = false WHERE is_applied = true")
+        .await
+        .map_err(|e| format!("Failed to clear applied flags: {}", e))?;
+    db.query(format!(
+        "UPDATE {} SET is_applied = true, updated_at = $now",
+        db_record_id("workspace_profile", profile_id)
+    ))
+    .bind(("now", now))
+    .await
+    .map_err(|e| format!("Failed to update applied flag: {}", e))?;
+
+    let payload = if from_tray { "tray" } else { "window" };
+    let _ = app.emit("config-changed", payload);
+    if selection.mcp_servers.is_some() {
+        let _ = app.emit("mcp-changed", "tray");
+    }
+    if selection.skills.is_some() {
+        let _ = app.emit("skills-changed", "tray");
+    }
+
+    Ok(())
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+async fn get_profile(
+    db: &surrealdb::Surreal<surrealdb::engine::local::Db>,
+    profile_id: &str,
+) -> Result<WorkspaceProfile, String> {
+    let records: Vec<Value> = db
+        .query(format!(
+            "SELECT *, type::string(id) as id FROM {} LIMIT 1",
+            db_record_id("workspace_profile", profile_id)
+        ))
+        .await
+        .map_err(|e| format!("Failed to query workspace profile: {}", e))?
+        .take(0)
+        .map_err(|e| format!("Failed to read workspace profile: {}", e))?;
+
+    records
+        .into_iter()
+        .next()
+        .map(adapter::from_db_value)
+        .ok_or_else(|| format!("Workspace profile '{}' not found", profile_id))
+}
+
+/// 表中当前 is_applied = true 的记录 id
+async fn applied_id(
+    db: &surrealdb::Surreal<surrealdb::engine::local::Db>,
+    table: &str,
+) -> Option<String> {
+    let records: Vec<Value> = db
+        .query(format!(
+            "SELECT type::string(id) as id FROM {} WHERE is_applied = true LIMIT 1",
+            table
+        ))
+        .await
+        .ok()?
+        .take(0)
+        .ok()?;
+    records.first().and_then(db_extract_id_opt)
+}
+
+/// 记录配置会改动的表中当前应用的记录
+async fn capture_applied_flags(
+    db: &surrealdb::Surreal<surrealdb::engine::local::Db>,
+    selection: &WorkspaceProfileSelection,
+) -> Vec<(&'static str, Option<String>)> {
+    let tables = [
+        ("claude_provider", &selection.claude_provider_id),
+        ("codex_provider", &selection.codex_provider_id),
+        ("gemini_cli_provider", &selection.gemini_cli_provider_id),
+        ("claude_prompt_config", &selection.claude_prompt_id),
+        ("codex_prompt_config", &selection.codex_prompt_id),
+        ("gemini_cli_prompt_config", &selection.gemini_cli_prompt_id),
+        ("opencode_prompt_config", &selection.opencode_prompt_id),
+        ("oh_my_opencode_config", &selection.oh_my_opencode_config_id),
+        (
+            "oh_my_opencode_slim_config",
+            &selection.oh_my_opencode_slim_config_id,
+        ),
+    ];
+
+    let mut flags = Vec::new();
+    for (table, target) in tables {
+        if target.is_some() {
+            flags.push((table, applied_id(db, table).await));
+        }
+    }
+    flags
+}
+
+async fn apply_selection<R: tauri::Runtime>(
+    app: &tauri::AppHandle<R>,
+    db: &surrealdb::Surreal<surrealdb::engine::local::Db>,
+    selection: &WorkspaceProfileSelection,
+    from_tray: bool,
+    force: bool,
+    toggled: &mut Vec<Toggled>,
+) -> Result<(), String> {
+    if let Some(id) = &selection.claude_provider_id {
+        claude_code::commands::apply_config_internal(db, app, id, from_tray, force)
+            .await
+            .map_err(|e| format!("Claude Code: {}", e))?;
+    }
+    if let Some(id) = &selection.codex_provider_id {
+        codex::commands::apply_config_internal(db, app, id, from_tray, force)
+            .await
+            .map_err(|e| format!("Codex: {}", e))?;
+    }
+    if let Some(id) = &selection.gemini_cli_provider_id {
+        gemini_cli::commands::apply_config_internal(db, app, id, from_tray)
+            .await
+            .map_err(|e| format!("Gemini CLI: {}", e))?;
+    }
+    if let Some(id) = &selection.claude_prompt_id {
+        claude_code::commands::apply_prompt_config_internal(app.state(), app, id, from_tray)
+            .await
+            .map_err(|e| format!("Claude Code prompt: {}", e))?;
+    }
+    if let Some(id) = &selection.codex_prompt_id {
+        codex::commands::apply_prompt_config_internal(app.state(), app, id, from_tray)
+            .await
+            .map_err(|e| format!("Codex prompt: {}", e))?;
+    }
+    if let Some(id) = &selection.gemini_cli_prompt_id {
+        gemini_cli::commands::apply_prompt_config_internal(app.state(), app, id, from_tray)
+            .await
+            .map_err(|e| format!("Gemini CLI prompt: {}", e))?;
+    }
+    if let Some(id) = &selection.opencode_prompt_id {
+        open_code::commands::apply_prompt_config_internal(app.state(), app, id, from_tray)
+            .await
+            .map_err(|e| format!("OpenCode prompt: {}", e))?;
+    }
+    if selection.opencode_model.is_some() || selection.opencode_small_model.is_some() {
+        apply_opencode_models(app, selection, from_tray, force)
+            .await
+            .map_err(|e| format!("OpenCode: {}", e))?;
+    }
+    if let Some(model) = &selection.openclaw_model {
+        apply_openclaw_model(app, model, from_tray, force)
+            .await
+            .map_err(|e| format!("OpenClaw: {}", e))?;
+    }
+    if let Some(id) = &selection.oh_my_opencode_config_id {
+        oh_my_opencode::commands::apply_config_internal(db, app, id, from_tray, force)
+            .await
+            .map_err(|e| format!("Oh My OpenCode: {}", e))?;
+    }
+    if let Some(id) = &selection.oh_my_opencode_slim_config_id {
+        oh_my_opencode_slim::commands::apply_config_internal(db, app, id, from_tray, force)
+            .await
+            .map_err(|e| format!("Oh My OpenCode Slim: {}", e))?;
+    }
+    if let Some(servers) = &selection.mcp_servers {
+        apply_mcp_servers(app, servers, toggled)
+            .await
+            .map_err(|e| format!("MCP: {}", e))?;
+    }
+    if let Some(skill_tools) = &selection.skills {
+        apply_skills(app, skill_tools, toggled)
+            .await
+            .map_err(|e| format!("Skills: {}", e))?;
+    }
+    Ok(())
+}
+
+async fn apply_opencode_models<R: tauri::Runtime>(
+    app: &tauri::AppHandle<R>,
+    selection: &WorkspaceProfileSelection,
+    from_tray: bool,
+    force: bool,
+) -> Result<(), String> {
+    let mut config = match open_code::read_opencode_config(app.state()).await? {
+        ReadConfigResult::Success { config } => config,
+        _ => return Err("opencode.json is missing or cannot be parsed".to_string()),
+    };
+
+    let mut changed = false;
+    for (target, model) in [
+        (&mut config.model, &selection.opencode_model),
+        (&mut config.small_model, &selection.opencode_small_model),
+    ] {
+        if model.is_some() && target != model {
+            target.clone_from(model);
+            changed = true;
+        }
+    }
+    if !changed {
+        return Ok(());
+    }
+
+    open_code::commands::apply_config_internal(app.state(), app, config, from_tray, force).await
+}
+
+async fn apply_openclaw_model<R: tauri::Runtime>(
+    app: &tauri::AppHandle<R>,
+    model: &str,
+    from_tray: bool,
+    force: bool,
+) -> Result<(), String> {
+    let mut config = match open_claw::read_openclaw_config(app.state()).await? {
+        ReadOpenClawConfigResult::Success { config } => config,
+        _ => return Err("openclaw.json is missing or cannot be parsed".to_string()),
+    };
+
+    let current = config
+        .agents
+        .as_ref()
+        .and_then(|agents| agents.defaults.as_ref())
+        .and_then(|defaults| defaults.model.as_ref())
+        .map(|m| m.primary.as_str());
+    if current == Some(model) {
+        return Ok(());
+    }
+
+    open_claw::tray_support::set_primary_model(&mut config, model);
+    open_claw::commands::apply_config_internal(app.state(), app, config, from_tray, force).await
+}
+
+/// 让列出的 MCP 服务器恰好在指定工具中启用
+async fn apply_mcp_servers<R: tauri::Runtime>(
+    app: &tauri::AppHandle<R>,
+    servers: &BTreeMap<String, Vec<String>>,
+    toggled: &mut Vec<Toggled>,
+) -> Result<(), String> {
+    let state = app.state::<DbState>();
+    let existing = mcp::mcp_store::get_mcp_servers(&state).await?;
+
+    for (server_id, tools) in servers {
+        let Some(server) = existing.iter().find(|s| &s.id == server_id) else {
+            log::warn!("工作区配置中的 MCP 服务器 {} 已不存在，跳过", server_id);
+            continue;
+        };
+        let current: BTreeSet<&String> = server.enabled_tools.iter().collect();
+        let target: BTreeSet<&String> = tools.iter().collect();
+
+        for tool_key in current.symmetric_difference(&target) {
+            let is_enabled =
+                mcp::mcp_store::toggle_tool_enabled(&state, server_id, tool_key).await?;
+            toggled.push(Toggled::Mcp {
+                server_id: server_id.clone(),
+                tool_key: tool_key.to_string(),
+            });
+            mcp::tray_support::sync_tool_state(&state, server_id, tool_key, is_enabled).await?;
+        }
+    }
+    Ok(())
+}
+
+/// 让列出的 Skill 恰好同步到指定工具
+async fn apply_skills<R: tauri::Runtime>(
+    app: &tauri::AppHandle<R>,
+    skill_tools: &BTreeMap<String, Vec<String>>,
+    toggled: &mut Vec<Toggled>,
+) -> Result<(), String> {
+    let state = app.state::<DbState>();
+    let existing = skills::skill_store::get_managed_skills(&state).await?;
+
+    for (skill_id, tools) in skill_tools {
+        let Some(skill) = existing.iter().find(|s| &s.id == skill_id) else {
+            log::warn!("工作区配置中的 Skill {} 已不存在，跳过", skill_id);
+            continue;
+        };
+        let current: BTreeSet<String> = skills::adapter::parse_sync_details(skill)
+            .into_iter()
+            .map(|target| target.tool)
+            .collect();
+        let target: BTreeSet<String> = tools.iter().cloned().collect();
+
+        for tool_key in current.symmetric_difference(&target) {
+            skills::tray_support::toggle_skill_tool(&state, skill_id, tool_key).await?;
+            toggled.push(Toggled::Skill {
+                skill_id: skill_id.clone(),
+                tool_key: tool_key.clone(),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// 尽力还原：先还原事务中写过的文件，再反向切换 MCP / Skill，最后恢复各表的应用标记。
+///
+/// 文件必须先还原：反向切换会在事务之外再次写入 MCP 配置，之后的还原会把它当作其他写入而跳过；
+/// 反向切换写入的内容与还原后的文件一致。
+async fn rollback<R: tauri::Runtime>(
+    app: &tauri::AppHandle<R>,
+    db: &surrealdb::Surreal<surrealdb::engine::local::Db>,
+    previous_flags: Vec<(&'static str, Option<String>)>,
+    toggled: Vec<Toggled>,
+    transaction: config_history::Transaction,
+) {
+    for failure in transaction.rollback(HISTORY_MODULE) {
+        log::warn!("回滚配置文件失败: {}", failure);
+    }
+
+    let state = app.state::<DbState>();
+    for item in toggled.into_iter().rev() {
+        let result = match &item {
+            Toggled::Mcp {
+                server_id,
+                tool_key,
+            } => match mcp::mcp_store::toggle_tool_enabled(&state, server_id, tool_key).await {
+                Ok(is_enabled) => {
+                    mcp::tray_support::sync_tool_state(&state, server_id, tool_key, is_enabled)
+                        .await
+                }
+                Err(e) => Err(e),
+            },
+            Toggled::Skill { skill_id, tool_key } => {
+                skills::tray_support::toggle_skill_tool(&state, skill_id, tool_key).await
+            }
+        };
+        if let Err(e) = result {
+            log::warn!("回滚 MCP / Skill 开关失败: {}", e);
+        }
+    }
+
+    let now = Local::now().to_rfc3339();
+    for (table, previous) in previous_flags {
+        let cleared = db
+            .query(format!(
+                "UPDATE {} SET is_applied = false, updated_at = $now WHERE is_applied = true",
+                table
+            ))
+            .bind(("now", now.clone()))
+            .await;
+        if let Err(e) = cleared {
+            log::warn!("回滚 {} 应用标记失败: {}", table, e);
+            continue;
+        }
+        if let Some(id) = previous {
+            let restored = db
+                .query(format!(
+                    "UPDATE {} SET is_applied = true, updated_at = $now",
+                    db_record_id(table, &id)
+                ))
+                .bind(("now", now.clone()))
+                .await;
+            if let Err(e) = restored {
+                log::warn!("回滚 {} 应用标记失败: {}", table, e);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures_util::FutureExt;
+    use serde_json::json;
+    use std::fs;
+    use std::path::{Path, PathBuf};
+    use surrealdb::engine::local::SurrealKv;
+    use surrealdb::Surreal;
+    use tauri::test::{mock_builder, mock_context, noop_assets, MockRuntime};
+
+    use super::*;
+    use crate::coding::db_new_id;
+    use crate::coding::mcp::types::McpServer;
+    use crate::coding::skills::types::Skill;
+    use crate::coding::tools::{custom_store, CustomTool};
+
+    /// MCP 配置与 Skill 目录都指向临时目录的自定义工具
+    const TOOL_KEY: &str = "aitb_test_tool";
+
+    fn setup() -> (tauri::App<MockRuntime>, PathBuf) {
+        let root = std::env::temp_dir().join(format!("workspace_profile_{}", db_new_id()));
+        fs::create_dir_all(&root).unwrap();
+        let db = tauri::async_runtime::block_on(async {
+            let db = Surreal::new::<SurrealKv>(root.join("db")).await.unwrap();
+            db.use_ns("ai_toolbox").use_db("main").await.unwrap();
+            db
+        });
+        let app = mock_builder()
+            .manage(DbState(db))
+            .build(mock_context(noop_assets()))
+            .unwrap();
+        (app, root)
+    }
+
+    async fn save_test_tool(state: &DbState, root: &Path) {
+        let tool = CustomTool {
+            key: TOOL_KEY.to_string(),
+            display_name: "Test Tool".to_string(),
+            relative_skills_dir: Some(root.join("skills").to_string_lossy().to_string()),
+            relative_detect_dir: Some(root.to_string_lossy().to_string()),
+            force_copy: true,
+            mcp_config_path: Some(root.join("mcp.json").to_string_lossy().to_string()),
+            mcp_config_format: Some("json".to_string()),
+            mcp_field: Some("mcpServers".to_string()),
+            created_at: 0,
+        };
+        custom_store::save_custom_tool(state, &tool).await.unwrap();
+    }
+
+    async fn create_mcp_server(state: &DbState, name: &str) -> String {
+        let server = McpServer {
+            id: String::new(),
+            name: name.to_string(),
+            server_type: "stdio".to_string(),
+            server_config: json!({ "command": "echo" }),
+            enabled_tools: Vec::new(),
+            sync_details: None,
+            description: None,
+            tags: Vec::new(),
+            timeout: None,
+            sort_index: 0,
+            created_at: 0,
+            updated_at: 0,
+        };
+        mcp::mcp_store::upsert_mcp_server(state, &server)
+            .await
+            .unwrap()
+    }
+
+    async fn create_skill(state: &DbState, root: &Path, name: &str) -> String {
+        let central = root.join("central").join(name);
+        fs::create_dir_all(&central).unwrap();
+        fs::write(central.join("SKILL.md"), "# test skill").unwrap();
+        let skill = Skill {
+            id: String::new(),
+            name: name.to_string(),
+            source_type: "local".to_string(),
+            source_ref: None,
+            source_revision: None,
+            central_path: central.to_string_lossy().to_string(),
+            content_hash: None,
+            created_at: 0,
+            updated_at: 0,
+            last_sync_at: None,
+            status: "ok".to_string(),
+            sort_index: 0,
+            enabled_tools: Vec::new(),
+            sync_details: None,
+        };
+        skills::skill_store::upsert_skill(state, &skill)
+            .await
+            .unwrap()
+    }
+
+    async fn create_profile(
+        db: &surrealdb::Surreal<surrealdb::engine::local::Db>,
+        id: &str,
+        selection: WorkspaceProfileSelection,
+    ) {
+        let now = Local::now().to_rfc3339();
+        let content = WorkspaceProfileContent {
+            name: id.to_string(),
+            description: None,
+            selection,
+            is_applied: false,
+            sort_index: None,
+            created_at: now.clone(),
+            updated_at: now,
+        };
+        db.query(format!(
+            "CREATE {} CONTENT $data",
+            db_record_id("workspace_profile", id)
+        ))
+        .bind(("data", adapter::to_db_value(&content)))
+        .await
+        .unwrap();
+    }
+
+    async fn applied_profiles(
+        db: &surrealdb::Surreal<surrealdb::engine::local::Db>,
+    ) -> Vec<String> {
+        let records: Vec<Value> = db
+            .query("SELECT type::string(id) as id FROM workspace_profile WHERE is_applied = true")
+            .await
+            .unwrap()
+            .take(0)
+            .unwrap();
+        records.iter().filter_map(db_extract_id_opt).collect()
+    }
+
+    fn read_json(path: &Path) -> Value {
+        serde_json::from_str(&fs::read_to_string(path).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_failed_apply_restores_files_toggles_and_flags() {
+        let (app, root) = setup();
+        // Claude Code 的 settings.json 写到临时目录下
+        let home = root.join("home");
+        *claude_code::commands::TEST_HOME_DIR
+            .lock()
+            .unwrap_or_else(|e| e.into_inner()) = Some(home.clone());
+        let handle = app.handle().clone();
+        let settings_path = home.join(".claude").join("settings.json");
+        let mcp_path = root.join("mcp.json");
+
+        tauri::async_runtime::block_on(async {
+            let state = handle.state::<DbState>();
+            let db = state.db();
+            save_test_tool(&state, &root).await;
+
+            for (id, applied) in [("old", true), ("new", false)] {
+                let settings_config = json!({ "env": { "ANTHROPIC_BASE_URL": format!("https://{}.example.com", id) } });
+                db.query(format!(
+                    "CREATE {} CONTENT $data",
+                    db_record_id("claude_provider", id)
+                ))
+                .bind((
+                    "data",
+                    json!({
+                        "name": id,
+                        "category": "custom",
+                        "settings_config": settings_config.to_string(),
+                        "is_applied": applied,
+                        "is_disabled": false,
+                        "created_at": "",
+                        "updated_at": "",
+                    }),
+                ))
+                .await
+                .unwrap();
+            }
+            fs::create_dir_all(settings_path.parent().unwrap()).unwrap();
+            fs::write(&settings_path, r#"{"original": true}"#).unwrap();
+            fs::write(&mcp_path, r#"{"mcpServers": {}}"#).unwrap();
+
+            let server_id = create_mcp_server(&state, "rollback-test").await;
+            let skill_id = create_skill(&state, &root, "rollback-skill").await;
+            create_profile(
+                &db,
+                "broken",
+                WorkspaceProfileSelection {
+                    claude_provider_id: Some("new".to_string()),
+                    mcp_servers: Some(BTreeMap::from([(
+                        server_id.clone(),
+                        vec![TOOL_KEY.to_string()],
+                    )])),
+                    // 按工具 key 排序依次切换：第一个工具同步成功，第二个工具不存在，应用在此失败
+                    skills: Some(BTreeMap::from([(
+                        skill_id.clone(),
+                        vec![TOOL_KEY.to_string(), "zz_missing_tool".to_string()],
+                    )])),
+                    ..Default::default()
+                },
+            )
+            .await;
+
+            let err = apply_profile_internal(&handle, "broken", false, true)
+                .await
+                .unwrap_err();
+            assert!(err.starts_with("Skills:"), "{}", err);
+
+            // 文件
+            assert_eq!(
+                fs::read_to_string(&settings_path).unwrap(),
+                r#"{"original": true}"#
+            );
+            assert_eq!(read_json(&mcp_path), json!({ "mcpServers": {} }));
+            assert!(!root.join("skills").join("rollback-skill").exists());
+
+            // MCP / Skill 开关
+            let server = mcp::mcp_store::get_mcp_server_by_id(&state, &server_id)
+                .await
+                .unwrap()
+                .unwrap();
+            assert!(server.enabled_tools.is_empty());
+            let skill = skills::skill_store::get_skill_by_id(&state, &skill_id)
+                .await
+                .unwrap()
+                .unwrap();
+            assert!(skills::adapter::parse_sync_details(&skill).is_empty());
+
+            // 应用标记
+            assert_eq!(
+                applied_id(&db, "claude_provider").await.as_deref(),
+                Some("old")
+            );
+            assert!(applied_profiles(&db).await.is_empty());
+        });
+
+        *claude_code::commands::TEST_HOME_DIR
+            .lock()
+            .unwrap_or_else(|e| e.into_inner()) = None;
+        drop(app);
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_apply_waits_for_lock_holder() {
+        let (app, root) = setup();
+        let handle = app.handle().clone();
+        let mcp_path = root.join("mcp.json");
+
+        tauri::async_runtime::block_on(async {
+            let state = handle.state::<DbState>();
+            let db = state.db();
+            save_test_tool(&state, &root).await;
+            let server_id = create_mcp_server(&state, "lock-test").await;
+            create_profile(
+                &db,
+                "enable",
+                WorkspaceProfileSelection {
+                    mcp_servers: Some(BTreeMap::from([(
+                        server_id.clone(),
+                        vec![TOOL_KEY.to_string()],
+                    )])),
+                    ..Default::default()
+                },
+            )
+            .await;
+
+            let guard = APPLY_LOCK.lock().await;
+            let mut apply = Box::pin(apply_profile_internal(&handle, "enable", false, false));
+
+            // 加锁是应用的第一步：锁被占用时首次轮询即挂起，不会改动任何状态
+            assert!((&mut apply).now_or_never().is_none());
+            assert!(applied_profiles(&db).await.is_empty());
+            assert!(!mcp_path.exists());
+
+            drop(guard);
+            apply.await.unwrap();
+            assert_eq!(applied_profiles(&db).await, vec!["enable".to_string()]);
+            let server = mcp::mcp_store::get_mcp_server_by_id(&state, &server_id)
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(server.enabled_tools, vec![TOOL_KEY.to_string()]);
+            assert!(read_json(&mcp_path)["mcpServers"]
+                .get("lock-test")
+                .is_some());
+        });
+
+        drop(app);
+        let _ = fs::remove_dir_all(&root);
+    }
+}
@@ -0,0 +1,7 @@
+pub mod adapter;
+pub mod commands;
+pub mod tray_support;
+pub mod types;
+
+pub use commands::*;
+pub use types::*;
@@ -0,0 +1,53 @@
+//! Workspace Profile Tray Support Module
+//!
+//! Provides standardized API for tray menu integration.
+
+use crate::db::DbState;
+use tauri::{AppHandle, Manager, Runtime};
+
+/// Item for profile selection in tray menu
+#[derive(Debug, Clone)]
+pub struct TrayProfileItem {
+    /// Profile ID (used in event handling)
+    pub id: String,
+    /// Display name in menu
+    pub display_name: String,
+    /// Whether this profile was the last one applied
+    pub is_selected: bool,
+}
+
+/// Data for workspace profile section in tray
+#[derive(Debug, Clone)]
+pub struct TrayProfileData {
+    /// Title of the section
+    pub title: String,
+    /// Items for selection
+    pub items: Vec<TrayProfileItem>,
+}
+
+/// Get tray data for workspace profiles
+pub async fn get_workspace_profile_tray_data<R: Runtime>(
+    app: &AppHandle<R>,
+) -> Result<TrayProfileData, String> {
+    let profiles = super::commands::list_workspace_profiles(app.state::<DbState>()).await?;
+
+    Ok(TrayProfileData {
+        title: "──── Workspace Profiles ────".to_string(),
+        items: profiles
+            .into_iter()
+            .map(|profile| TrayProfileItem {
+                id: profile.id,
+                display_name: profile.name,
+                is_selected: profile.is_applied,
+            })
+            .collect(),
+    })
+}
+
+/// Apply workspace profile from tray menu
+pub async fn apply_workspace_profile<R: Runtime>(
+    app: &AppHandle<R>,
+    profile_id: &str,
+) -> Result<(), String> {
+    super::commands::apply_profile_internal(app, profile_id, true, false).await
+}
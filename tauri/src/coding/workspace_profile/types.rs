@@ -0,0 +1,86 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+/// 工作区配置：一次切换所有工具的选择
+///
+/// 每个字段为 `None` 时表示应用该配置时不改动对应的工具。
+/// `mcp_servers` / `skills` 为 `服务器或 Skill id -> 启用的工具 key`，
+/// 只改动其中列出的条目，列表为空表示在所有工具中停用。
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceProfileSelection {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub claude_provider_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub codex_provider_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gemini_cli_provider_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub claude_prompt_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub codex_prompt_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gemini_cli_prompt_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub opencode_prompt_id: Option<String>,
+    /// OpenCode 主模型，格式 `provider/model`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub opencode_model: Option<String>,
+    /// OpenCode 小模型，格式 `provider/model`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub opencode_small_model: Option<String>,
+    /// OpenClaw 默认模型（agents.defaults.model.primary）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub openclaw_model: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub oh_my_opencode_config_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub oh_my_opencode_slim_config_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mcp_servers: Option<BTreeMap<String, Vec<String>>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub skills: Option<BTreeMap<String, Vec<String>>>,
+}
+
+/// Input type for creating/updating a workspace profile
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceProfileInput {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub selection: WorkspaceProfileSelection,
+}
+
+/// Workspace profile returned to the frontend
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceProfile {
+    pub id: String,
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub selection: WorkspaceProfileSelection,
+    pub is_applied: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sort_index: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<String>,
+}
+
+/// Workspace profile content for database storage
+#[derive(Debug, Clone, Serialize)]
+pub struct WorkspaceProfileContent {
+    pub name: String,
+    pub description: Option<String>,
+    pub selection: WorkspaceProfileSelection,
+    pub is_applied: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sort_index: Option<i32>,
+    pub created_at: String,
+    pub updated_at: String,
+}
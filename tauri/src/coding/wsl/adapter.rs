@@ -22,6 +22,11 @@ pub fn config_from_db_value(value: Value, file_mappings: Vec<FileMapping>) -> WS
         // sync_mcp and sync_skills are always true (no UI to toggle them)
         sync_mcp: true,
         sync_skills: true,
+        auto_sync_on_change: value
+            .get("auto_sync_on_change")
+            .or_else(|| value.get("autoSyncOnChange"))
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false),
         file_mappings,
         last_sync_time: value
             .get("last_sync_time")
@@ -47,6 +52,7 @@ pub fn config_to_db_value(config: &WSLSyncConfig) -> Value {
     json!({
         "enabled": config.enabled,
         "distro": config.distro,
+        "auto_sync_on_change": config.auto_sync_on_change,
         "last_sync_time": config.last_sync_time,
         "last_sync_status": config.last_sync_status,
         "last_sync_error": config.last_sync_error,
@@ -1,12 +1,12 @@
 use super::types::{
-    FileMapping, SyncProgress, SyncResult, WSLDetectResult, WSLErrorResult, WSLStatusResult,
-    WSLSyncConfig,
+    FileMapping, SyncPreview, SyncProgress, SyncResult, WSLDetectResult, WSLErrorResult,
+    WSLStatusResult, WSLSyncConfig,
 };
 use super::{adapter, sync};
 use crate::coding::{oh_my_opencode, oh_my_opencode_slim, open_code};
 use crate::db::DbState;
 use chrono::Local;
-use tauri::Emitter;
+use tauri::{Emitter, Manager};
 
 // ============================================================================
 // WSL Detection Commands
@@ -272,6 +272,8 @@ pub(super) async fn do_full_sync(
                 synced_files: vec![],
                 skipped_files: vec![],
                 errors: vec![e],
+                conflicts: vec![],
+                hosts: vec![],
             };
         }
     };
@@ -389,6 +391,8 @@ fn sync_mappings_with_progress(
         synced_files,
         skipped_files,
         errors,
+        conflicts: vec![],
+        hosts: vec![],
     }
 }
 
@@ -420,6 +424,74 @@ pub async fn wsl_sync(
     Ok(result)
 }
 
+/// Incrementally sync the given file mappings (triggered by the local file watcher)
+///
+/// MCP and Skills are not included; they have their own change events.
+pub async fn sync_changed_mappings(
+    app: &tauri::AppHandle,
+    mapping_ids: &[String],
+) -> Result<SyncResult, String> {
+    let state = app.state::<DbState>();
+    let config = wsl_get_config(state.clone()).await?;
+
+    if !config.enabled {
+        return Err("WSL 同步未启用".to_string());
+    }
+
+    let distro = sync::get_effective_distro(&config.distro)?;
+    let mappings: Vec<_> = config
+        .file_mappings
+        .into_iter()
+        .filter(|m| mapping_ids.contains(&m.id))
+        .collect();
+    let mappings = resolve_dynamic_paths(mappings);
+
+    let result = sync_mappings_with_progress(&mappings, &distro, None, None, app);
+
+    update_sync_status(state.inner(), &result).await?;
+    let _ = app.emit("wsl-sync-completed", result.clone());
+
+    Ok(result)
+}
+
+/// Preview what `wsl_sync` would change without writing anything (dry-run)
+#[tauri::command]
+pub async fn wsl_sync_preview(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+    module: Option<String>,
+    skip_modules: Option<Vec<String>>,
+) -> Result<SyncPreview, String> {
+    let config = wsl_get_config(state.clone()).await?;
+    let distro = sync::get_effective_distro(&config.distro)?;
+
+    let file_mappings: Vec<_> = resolve_dynamic_paths(config.file_mappings.clone())
+        .into_iter()
+        .filter(|m| {
+            !skip_modules
+                .as_ref()
+                .is_some_and(|skip| skip.contains(&m.module))
+        })
+        .collect();
+    let mut preview = sync::preview_mappings(&file_mappings, &distro, module.as_deref());
+
+    // MCP / Skills follow the same enabled checks as sync_mcp_to_wsl / sync_skills_to_wsl
+    if config.enabled && config.sync_mcp {
+        match super::mcp_sync::preview_mcp_to_wsl(&state, &distro).await {
+            Ok(mcp) => preview.mcp = Some(mcp),
+            Err(e) => preview.errors.push(format!("MCP sync: {}", e)),
+        }
+    }
+    if config.enabled && config.sync_skills {
+        match super::skills_sync::preview_skills_to_wsl(&state, &distro, &app).await {
+            Ok(skills) => preview.skills = Some(skills),
+            Err(e) => preview.errors.push(format!("Skills sync: {}", e)),
+        }
+    }
+
+    Ok(preview)
+}
+
 /// Whether WSL automatic sync triggers are enabled.
 ///
 /// Automatic triggers include startup sync and event-driven sync from
@@ -613,7 +685,7 @@ async fn backfill_default_mappings(
 
 /// Dynamically resolve config file paths for opencode and oh-my-opencode
 /// This ensures we sync the actual config file format (.jsonc or .json) being used
-pub(super) fn resolve_dynamic_paths(mappings: Vec<FileMapping>) -> Vec<FileMapping> {
+pub fn resolve_dynamic_paths(mappings: Vec<FileMapping>) -> Vec<FileMapping> {
     mappings
         .into_iter()
         .map(|mut mapping| {
@@ -11,9 +11,10 @@ use tauri::{AppHandle, Emitter};
 use super::adapter;
 use super::commands::resolve_dynamic_paths;
 use super::sync::{read_wsl_file, sync_mappings, write_wsl_file};
-use super::types::{FileMapping, SyncProgress, WSLSyncConfig};
+use super::types::{FileMapping, McpChangePreview, SyncProgress, WSLSyncConfig};
 use crate::coding::mcp::command_normalize;
 use crate::coding::mcp::mcp_store;
+use crate::coding::sync_preview::{is_mcp_config_file, preview_claude_mcp, strip_cmd_c};
 use crate::DbState;
 
 /// Read WSL sync config directly from database (without tauri::State wrapper)
@@ -187,6 +188,8 @@ pub async fn sync_mcp_to_wsl(state: &DbState, app: AppHandle) -> Result<(), Stri
         synced_files: vec![],
         skipped_files: vec![],
         errors: all_errors,
+        conflicts: vec![],
+        hosts: vec![],
     };
     let _ = super::commands::update_sync_status(state, &sync_result).await;
 
@@ -236,6 +239,22 @@ fn sync_mcp_to_wsl_claude(
     Ok(())
 }
 
+/// Preview changes to WSL ~/.claude.json mcpServers without writing (dry-run)
+pub(super) async fn preview_mcp_to_wsl(
+    state: &DbState,
+    distro: &str,
+) -> Result<McpChangePreview, String> {
+    let servers = mcp_store::get_mcp_servers(state).await?;
+    let mcp_servers: serde_json::Map<String, Value> = servers
+        .iter()
+        .filter(|s| s.enabled_tools.contains(&"claude_code".to_string()))
+        .map(|s| (s.name.clone(), build_standard_server_config(s)))
+        .collect();
+
+    let existing_content = read_wsl_file(distro, "~/.claude.json")?;
+    preview_claude_mcp(&existing_content, &mcp_servers, "WSL")
+}
+
 /// Build standard JSON server config for Claude Code format
 /// Note: Database stores normalized config (no cmd /c), but we add a safeguard here
 fn build_standard_server_config(server: &crate::coding::mcp::types::McpServer) -> Value {
@@ -300,36 +319,11 @@ fn build_standard_server_config(server: &crate::coding::mcp::types::McpServer) -
     }
 }
 
-/// Check if a file mapping ID corresponds to a file that contains MCP server configurations.
-/// Only these files need cmd /c stripping; auth files, slim configs, etc. do not.
-fn is_mcp_config_file(mapping_id: &str) -> bool {
-    matches!(
-        mapping_id,
-        "opencode-main" | "opencode-oh-my" | "codex-config"
-    )
-}
-
 /// Strip cmd /c from WSL MCP config file after sync.
-/// Selects the correct parser based on file extension rather than module name,
-/// so that JSON files are not accidentally parsed as TOML.
 fn strip_cmd_c_from_wsl_mcp_file(distro: &str, wsl_path: &str, module: &str) -> Result<(), String> {
     let content = read_wsl_file(distro, wsl_path)?;
-    if content.trim().is_empty() {
+    let Some(processed) = strip_cmd_c(wsl_path, module, &content)? else {
         return Ok(());
-    }
-
-    let processed = match module {
-        "opencode" => command_normalize::process_opencode_json(&content, false)?,
-        "codex" => {
-            // Determine parser by file extension: only .toml files use TOML parser
-            if wsl_path.ends_with(".toml") {
-                command_normalize::process_codex_toml(&content, false)?
-            } else {
-                // JSON files in codex module (e.g. auth.json) should not be processed
-                return Ok(());
-            }
-        }
-        _ => return Ok(()),
     };
 
     // Only write back if content changed
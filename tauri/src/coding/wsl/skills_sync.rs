@@ -9,12 +9,13 @@ use tauri::{AppHandle, Emitter};
 
 use super::adapter;
 use super::sync::{
-    check_wsl_symlink_exists, create_wsl_symlink, list_wsl_dir, read_wsl_file_raw, remove_wsl_path,
-    sync_directory, write_wsl_file,
+    check_wsl_symlink_exists, create_wsl_symlink, exec_wsl_command, list_wsl_dir,
+    read_wsl_file_raw, remove_wsl_path, sync_directory, write_wsl_file,
 };
-use super::types::{SyncProgress, WSLSyncConfig};
+use super::types::{SkillsChangePreview, SyncProgress, WSLSyncConfig};
 use crate::coding::skills::central_repo::{resolve_central_repo_path, resolve_skill_central_path};
 use crate::coding::skills::skill_store;
+use crate::coding::sync_preview::{parse_skills_state, plan_skills, skills_state_command};
 use crate::coding::tools::builtin::BUILTIN_TOOLS;
 use crate::DbState;
 
@@ -233,6 +234,8 @@ pub async fn sync_skills_to_wsl(state: &DbState, app: AppHandle) -> Result<(), S
         synced_files: vec![],
         skipped_files: vec![],
         errors: vec![],
+        conflicts: vec![],
+        hosts: vec![],
     };
     let _ = super::commands::update_sync_status(state, &sync_result).await;
 
@@ -242,3 +245,35 @@ pub async fn sync_skills_to_wsl(state: &DbState, app: AppHandle) -> Result<(), S
 
     Ok(())
 }
+
+/// Preview skills sync changes in WSL without writing (dry-run)
+pub(super) async fn preview_skills_to_wsl(
+    state: &DbState,
+    distro: &str,
+    app: &AppHandle,
+) -> Result<SkillsChangePreview, String> {
+    let skills = skill_store::get_managed_skills(state).await?;
+    let central_dir = resolve_central_repo_path(app, state)
+        .await
+        .map_err(|e| format!("{}", e))?;
+    let unavailable: HashSet<String> = skills
+        .iter()
+        .filter(|s| !resolve_skill_central_path(&s.central_path, &central_dir).exists())
+        .map(|s| s.name.clone())
+        .collect();
+
+    let tool_dirs: Vec<(&str, String)> = get_all_skill_tool_keys()
+        .into_iter()
+        .filter_map(|key| get_wsl_tool_skills_dir(key).map(|dir| (key, dir)))
+        .collect();
+    let dirs: Vec<String> = tool_dirs.iter().map(|(_, dir)| dir.clone()).collect();
+
+    let output = exec_wsl_command(distro, &skills_state_command(WSL_CENTRAL_DIR, &dirs))?;
+    Ok(plan_skills(
+        &skills,
+        &unavailable,
+        &tool_dirs,
+        WSL_CENTRAL_DIR,
+        &parse_skills_state(&output),
+    ))
+}
@@ -1,4 +1,9 @@
-use super::types::{FileMapping, SyncResult, WSLDetectResult};
+use super::types::{FileChangePreview, FileMapping, SyncPreview, SyncResult, WSLDetectResult};
+use crate::coding::ssh::sync_state::content_hash;
+use crate::coding::sync_preview::{
+    parse_hash_output, pushed_text, remote_text, text_change, HASH_CMD, MAX_DIFF_BYTES,
+};
+use std::collections::BTreeMap;
 use std::path::Path;
 use std::process::Command;
 
@@ -499,9 +504,279 @@ pub fn sync_mappings(
         synced_files,
         skipped_files,
         errors,
+        conflicts: vec![],
+        hosts: vec![],
     }
 }
 
+// ============================================================================
+// Dry-run Preview (read-only, nothing is written)
+// ============================================================================
+
+/// A file taking part in the preview comparison
+struct PreviewEntry {
+    /// Expanded Windows source path
+    source_path: String,
+    /// WSL target path (may contain ~)
+    target_path: String,
+    source_hash: Option<String>,
+    target_hash: Option<String>,
+}
+
+fn local_file_hash(path: &Path) -> Option<String> {
+    std::fs::read(path).ok().map(|data| content_hash(&data))
+}
+
+fn wsl_file_hash(distro: &str, wsl_path: &str) -> Result<Option<String>, String> {
+    let target = wsl_path.replace("~", "$HOME");
+    let command = format!(
+        "{} if [ -f \"{}\" ]; then $H \"{}\"; fi",
+        HASH_CMD, target, target
+    );
+    let output = exec_wsl_command(distro, &command)?;
+    Ok(parse_hash_output(&output)
+        .into_iter()
+        .next()
+        .map(|(_, hash)| hash))
+}
+
+/// Hashes of files under a WSL directory, keyed by relative path
+fn wsl_dir_hashes(
+    distro: &str,
+    wsl_dir: &str,
+    recursive: bool,
+) -> Result<BTreeMap<String, String>, String> {
+    let target = wsl_dir.replace("~", "$HOME");
+    let depth = if recursive { "" } else { " -maxdepth 1" };
+    let command = format!(
+        "{} if [ -d \"{}\" ]; then cd \"{}\" && find .{} -type f -exec $H {{}} +; fi",
+        HASH_CMD, target, target, depth
+    );
+    let output = exec_wsl_command(distro, &command)?;
+
+    Ok(parse_hash_output(&output)
+        .into_iter()
+        .map(|(path, hash)| (path.trim_start_matches("./").to_string(), hash))
+        .collect())
+}
+
+/// Read a WSL text file for diffing; None if it is too large or not text
+fn read_wsl_text(distro: &str, wsl_path: &str) -> Option<String> {
+    let target = wsl_path.replace("~", "$HOME");
+    // Output starting with T means the file exists and is within the size limit
+    let command = format!(
+        "if [ -f \"{}\" ] && [ \"$(wc -c < \"{}\")\" -le {} ]; then printf T; cat \"{}\"; fi",
+        target, target, MAX_DIFF_BYTES, target
+    );
+    let output = create_wsl_command()
+        .args(["-d", distro, "--exec", "bash", "-c", &command])
+        .output()
+        .ok()?;
+    let content = String::from_utf8_lossy(&output.stdout);
+    remote_text(content.strip_prefix('T')?.to_string())
+}
+
+/// Collect every file a mapping would copy, with hashes on both sides
+fn collect_preview_entries(
+    mapping: &FileMapping,
+    distro: &str,
+) -> Result<Vec<PreviewEntry>, String> {
+    let windows_path = expand_env_vars(&mapping.windows_path)?;
+    let target_dir = mapping.wsl_path.trim_end_matches('/').to_string();
+
+    if mapping.is_directory {
+        if !Path::new(&windows_path).exists() {
+            return Ok(vec![]);
+        }
+
+        // relative path -> (source hash, target hash)
+        let mut files: BTreeMap<String, (Option<String>, Option<String>)> = BTreeMap::new();
+        for entry in walkdir::WalkDir::new(&windows_path)
+            .follow_links(true)
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_file())
+        {
+            let Ok(relative) = entry.path().strip_prefix(&windows_path) else {
+                continue;
+            };
+            let relative = relative.to_string_lossy().replace('\\', "/");
+            files.entry(relative).or_default().0 = local_file_hash(entry.path());
+        }
+        for (relative, hash) in wsl_dir_hashes(distro, &target_dir, true)? {
+            files.entry(relative).or_default().1 = Some(hash);
+        }
+
+        Ok(files
+            .into_iter()
+            .map(|(relative, (source_hash, target_hash))| PreviewEntry {
+                source_path: Path::new(&windows_path)
+                    .join(&relative)
+                    .to_string_lossy()
+                    .to_string(),
+                target_path: format!("{}/{}", target_dir, relative),
+                source_hash,
+                target_hash,
+            })
+            .collect())
+    } else if mapping.is_pattern {
+        let matches: Vec<_> = glob::glob(&windows_path)
+            .map_err(|e| format!("Invalid glob pattern: {}", e))?
+            .filter_map(|p| p.ok())
+            .filter(|p| p.is_file())
+            .collect();
+        if matches.is_empty() {
+            return Ok(vec![]);
+        }
+
+        // Pattern sync only copies matching files, existing target files are never removed
+        let target_hashes = wsl_dir_hashes(distro, &target_dir, false)?;
+        Ok(matches
+            .into_iter()
+            .map(|path| {
+                let name = path
+                    .file_name()
+                    .map(|n| n.to_string_lossy().to_string())
+                    .unwrap_or_default();
+                PreviewEntry {
+                    source_hash: local_file_hash(&path),
+                    target_hash: target_hashes.get(&name).cloned(),
+                    source_path: path.to_string_lossy().to_string(),
+                    target_path: format!("{}/{}", target_dir, name),
+                }
+            })
+            .collect())
+    } else {
+        if !Path::new(&windows_path).exists() {
+            return Ok(vec![]);
+        }
+        Ok(vec![PreviewEntry {
+            source_hash: local_file_hash(Path::new(&windows_path)),
+            target_hash: wsl_file_hash(distro, &mapping.wsl_path)?,
+            source_path: windows_path,
+            target_path: mapping.wsl_path.clone(),
+        }])
+    }
+}
+
+fn file_change(
+    mapping: &FileMapping,
+    action: &str,
+    source_path: &str,
+    target_path: &str,
+    diff: Option<String>,
+) -> FileChangePreview {
+    FileChangePreview {
+        mapping_id: mapping.id.clone(),
+        mapping_name: mapping.name.clone(),
+        action: action.to_string(),
+        direction: "push".to_string(),
+        source_path: source_path.to_string(),
+        target_path: target_path.to_string(),
+        diff,
+    }
+}
+
+/// Preview a single file mapping: per-file action and text diff, nothing is written
+///
+/// An empty list means the source does not exist and the mapping would be skipped.
+pub fn preview_file_mapping(
+    mapping: &FileMapping,
+    distro: &str,
+) -> Result<Vec<FileChangePreview>, String> {
+    let entries = collect_preview_entries(mapping, distro)?;
+    if entries.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let mut changes = vec![];
+    for entry in &entries {
+        let change = match (&entry.source_hash, &entry.target_hash) {
+            (Some(source), Some(target)) if source == target => continue,
+            // Directory sync replaces the whole target directory
+            (None, Some(_)) => file_change(
+                mapping,
+                "delete",
+                &entry.source_path,
+                &entry.target_path,
+                None,
+            ),
+            (None, None) => continue,
+            (Some(_), target_hash) => {
+                let source = std::fs::read(&entry.source_path).ok().and_then(|data| {
+                    pushed_text(&mapping.id, &mapping.module, &entry.target_path, &data)
+                });
+                let target = match target_hash {
+                    Some(_) => read_wsl_text(distro, &entry.target_path),
+                    None => Some(String::new()),
+                };
+                let Some(diff) = text_change(
+                    target.as_deref(),
+                    source.as_deref(),
+                    &entry.target_path,
+                    &entry.source_path,
+                ) else {
+                    continue;
+                };
+                let action = if target_hash.is_some() {
+                    "update"
+                } else {
+                    "create"
+                };
+                file_change(
+                    mapping,
+                    action,
+                    &entry.source_path,
+                    &entry.target_path,
+                    diff,
+                )
+            }
+        };
+        changes.push(change);
+    }
+
+    if changes.is_empty() {
+        changes.push(file_change(
+            mapping,
+            "unchanged",
+            &mapping.windows_path,
+            &mapping.wsl_path,
+            None,
+        ));
+    }
+
+    Ok(changes)
+}
+
+/// Preview all enabled file mappings (dry-run, nothing is written)
+pub fn preview_mappings(
+    mappings: &[FileMapping],
+    distro: &str,
+    module_filter: Option<&str>,
+) -> SyncPreview {
+    let mut preview = SyncPreview::default();
+
+    let filtered_mappings: Vec<_> = mappings
+        .iter()
+        .filter(|m| m.enabled)
+        .filter(|m| module_filter.is_none() || Some(m.module.as_str()) == module_filter)
+        .collect();
+
+    for mapping in filtered_mappings {
+        match preview_file_mapping(mapping, distro) {
+            Ok(changes) if changes.is_empty() => {
+                preview.skipped_files.push(mapping.name.clone());
+            }
+            Ok(changes) => preview.files.extend(changes),
+            Err(e) => {
+                preview.errors.push(format!("{}: {}", mapping.name, e));
+            }
+        }
+    }
+
+    preview
+}
+
 // ============================================================================
 // WSL File Operations
 // ============================================================================
@@ -713,6 +988,21 @@ pub fn remove_wsl_path(distro: &str, wsl_path: &str) -> Result<(), String> {
     }
 }
 
+/// Run a bash command in WSL and return its stdout
+pub fn exec_wsl_command(distro: &str, command: &str) -> Result<String, String> {
+    let output = create_wsl_command()
+        .args(["-d", distro, "--exec", "bash", "-c", command])
+        .output()
+        .map_err(|e| format!("Failed to execute WSL command: {}", e))?;
+
+    if output.status.success() {
+        Ok(decode_wsl_output(&output.stdout))
+    } else {
+        let stderr = decode_wsl_output(&output.stderr);
+        Err(format!("WSL command failed: {}", stderr.trim()))
+    }
+}
+
 /// List subdirectories in a WSL directory
 pub fn list_wsl_dir(distro: &str, wsl_path: &str) -> Result<Vec<String>, String> {
     let wsl_target = wsl_path.replace("~", "$HOME");
@@ -34,6 +34,9 @@ pub struct WSLSyncConfig {
     /// Sync Skills to WSL (default: true)
     #[serde(default = "default_true")]
     pub sync_skills: bool,
+    /// Sync a mapping automatically when its Windows-side file changes (default: false)
+    #[serde(default)]
+    pub auto_sync_on_change: bool,
     pub file_mappings: Vec<FileMapping>,
     pub last_sync_time: Option<String>,
     pub last_sync_status: String, // "success" | "error" | "never"
@@ -47,6 +50,7 @@ impl Default for WSLSyncConfig {
             distro: String::new(),
             sync_mcp: true,
             sync_skills: true,
+            auto_sync_on_change: false,
             file_mappings: vec![],
             last_sync_time: None,
             last_sync_status: "never".to_string(),
@@ -71,6 +75,82 @@ pub struct SyncResult {
     pub synced_files: Vec<String>,
     pub skipped_files: Vec<String>,
     pub errors: Vec<String>,
+    /// Files changed on both sides since the last sync (two-way sync), left untouched
+    #[serde(default)]
+    pub conflicts: Vec<SyncConflict>,
+    /// Per-host results when syncing to several SSH connections (empty for WSL)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub hosts: Vec<HostSyncResult>,
+}
+
+/// Result of syncing a single SSH target connection
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HostSyncResult {
+    pub connection_id: String,
+    pub connection_name: String,
+    pub result: SyncResult,
+}
+
+/// A file that changed both locally and remotely since the last sync
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncConflict {
+    pub mapping_id: String,
+    pub mapping_name: String,
+    /// Absolute local path
+    pub local_path: String,
+    pub remote_path: String,
+    pub local_exists: bool,
+    pub remote_exists: bool,
+}
+
+/// Dry-run result of a sync: what would change, nothing is written (API response)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncPreview {
+    pub files: Vec<FileChangePreview>,
+    /// Names of mappings whose source does not exist (would be skipped)
+    pub skipped_files: Vec<String>,
+    pub mcp: Option<McpChangePreview>,
+    pub skills: Option<SkillsChangePreview>,
+    pub errors: Vec<String>,
+}
+
+/// A single file (or symlink) that a sync would touch
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileChangePreview {
+    pub mapping_id: String,
+    pub mapping_name: String,
+    /// "create" | "update" | "unchanged" | "delete" | "delete_symlink" | "conflict"
+    pub action: String,
+    /// "push" (local → remote) | "pull" (remote → local) | "both" (conflict / unchanged two-way)
+    pub direction: String,
+    pub source_path: String,
+    pub target_path: String,
+    /// Unified diff of the target against the incoming content (text files only)
+    pub diff: Option<String>,
+}
+
+/// MCP servers that would be written to ~/.claude.json
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct McpChangePreview {
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    pub updated: Vec<String>,
+    pub diff: Option<String>,
+}
+
+/// Skill directories and tool symlinks that a skills sync would change
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SkillsChangePreview {
+    pub added: Vec<String>,
+    pub updated: Vec<String>,
+    pub removed: Vec<String>,
+    pub links: Vec<FileChangePreview>,
 }
 
 /// WSL detection result (API response)
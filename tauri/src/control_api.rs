@@ -0,0 +1,456 @@
+//! 本地 HTTP 控制接口
+//!
+//! 供编辑器插件、shell 提示符等在不打开窗口的情况下读取和切换当前 provider / 模型。
+//! 默认关闭；开启后监听 `127.0.0.1` 的随机端口，端口与 Bearer token 写入应用数据目录下的
+//! `control_api.json`（每次启动重新生成）。
+//!
+//! 写操作直接复用托盘菜单的 apply 函数，因此发出相同的 `config-changed` / `mcp-changed`
+//! 事件，随后刷新托盘菜单，界面与托盘保持一致。
+//!
+//! 所有请求需带 `Authorization: Bearer <token>`，请求体与响应均为 JSON：
+//! - `GET  /v1/claude-code/providers`、`POST /v1/claude-code/providers/apply` `{providerId}`
+//! - `GET  /v1/codex/providers`、`POST /v1/codex/providers/apply` `{providerId}`
+//! - `GET  /v1/opencode/models`、`POST /v1/opencode/models/apply` `{modelType, modelId}`
+//! - `GET  /v1/mcp/servers`、`POST /v1/mcp/servers/toggle` `{serverId, toolKey}`
+
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tauri::{AppHandle, Manager};
+use tokio::io::AsyncWriteExt;
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::{oneshot, Mutex};
+
+use crate::coding::claude_code::tray_support as claude_tray;
+use crate::coding::codex::tray_support as codex_tray;
+use crate::coding::mcp::tray_support as mcp_tray;
+use crate::coding::open_code::tray_support as opencode_tray;
+use crate::local_http;
+use crate::tray;
+
+/// 写入应用数据目录的连接信息文件
+const INFO_FILE_NAME: &str = "control_api.json";
+/// 请求头与请求体上限，超出直接断开
+const MAX_HEAD_BYTES: usize = 16 * 1024;
+const MAX_BODY_BYTES: usize = 64 * 1024;
+/// 单个请求的读取超时
+const READ_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// 当前运行的控制接口，注册到 Tauri State
+pub struct ControlApiState(pub Mutex<Option<RunningServer>>);
+
+pub struct RunningServer {
+    port: u16,
+    info_path: PathBuf,
+    shutdown: oneshot::Sender<()>,
+}
+
+/// 控制接口状态（返回给设置页）
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ControlApiStatus {
+    pub running: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub port: Option<u16>,
+    /// 保存端口与 token 的文件路径
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub info_path: Option<String>,
+}
+
+/// 按设置启动或停止控制接口
+pub async fn set_enabled(app: &AppHandle, enabled: bool) -> Result<(), String> {
+    let state = app.state::<ControlApiState>();
+    let mut current = state.0.lock().await;
+
+    if enabled && current.is_none() {
+        *current = Some(start(app).await?);
+    } else if !enabled {
+        if let Some(server) = current.take() {
+            let _ = server.shutdown.send(());
+            let _ = std::fs::remove_file(&server.info_path);
+            log::info!("Control API stopped");
+        }
+    }
+    Ok(())
+}
+
+/// 启动时按已保存的设置开启
+pub fn init(app: &AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let enabled = crate::settings::get_settings(app.state())
+            .await
+            .map(|s| s.control_api_enabled)
+            .unwrap_or(false);
+        if let Err(e) = set_enabled(&app, enabled).await {
+            log::warn!("Failed to start control API: {}", e);
+        }
+    });
+}
+
+/// Get control API status
+#[tauri::command]
+pub async fn get_control_api_status(
+    state: tauri::State<'_, ControlApiState>,
+) -> Result<ControlApiStatus, String> {
+    let current = state.0.lock().await;
+    Ok(match current.as_ref() {
+        Some(server) => ControlApiStatus {
+            running: true,
+            port: Some(server.port),
+            info_path: Some(server.info_path.to_string_lossy().to_string()),
+        },
+        None => ControlApiStatus {
+            running: false,
+            port: None,
+            info_path: None,
+        },
+    })
+}
+
+async fn start(app: &AppHandle) -> Result<RunningServer, String> {
+    let listener = TcpListener::bind(("127.0.0.1", 0))
+        .await
+        .map_err(|e| format!("Failed to bind control API: {}", e))?;
+    let port = listener
+        .local_addr()
+        .map_err(|e| format!("Failed to get control API address: {}", e))?
+        .port();
+
+    let token = format!(
+        "{}{}",
+        uuid::Uuid::new_v4().simple(),
+        uuid::Uuid::new_v4().simple()
+    );
+    let info_path = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data dir: {}", e))?
+        .join(INFO_FILE_NAME);
+    write_info_file(&info_path, port, &token)?;
+
+    let (shutdown, mut shutdown_rx) = oneshot::channel::<()>();
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::select! {
+                _ = &mut shutdown_rx => break,
+                accepted = listener.accept() => match accepted {
+                    Ok((stream, _)) => {
+                        let app = app.clone();
+                        let token = token.clone();
+                        tauri::async_runtime::spawn(async move {
+                            if let Err(e) = handle_connection(stream, &app, &token).await {
+                                log::debug!("Control API connection error: {}", e);
+                            }
+                        });
+                    }
+                    Err(e) => log::warn!("Control API accept failed: {}", e),
+                },
+            }
+        }
+    });
+
+    log::info!("Control API listening on 127.0.0.1:{}", port);
+    Ok(RunningServer {
+        port,
+        info_path,
+        shutdown,
+    })
+}
+
+/// 写入连接信息，仅当前用户可读
+fn write_info_file(path: &Path, port: u16, token: &str) -> Result<(), String> {
+    use std::io::Write;
+
+    let content = json!({
+        "baseUrl": format!("http://127.0.0.1:{}", port),
+        "port": port,
+        "token": token,
+        "pid": std::process::id(),
+    });
+
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options
+        .open(path)
+        .map_err(|e| format!("Failed to write {}: {}", INFO_FILE_NAME, e))?;
+    file.write_all(
+        serde_json::to_string_pretty(&content)
+            .unwrap_or_default()
+            .as_bytes(),
+    )
+    .map_err(|e| format!("Failed to write {}: {}", INFO_FILE_NAME, e))
+}
+
+// ============================================================================
+// HTTP
+// ============================================================================
+
+#[derive(Debug, PartialEq)]
+struct RequestHead {
+    method: String,
+    path: String,
+    content_length: usize,
+    authorization: Option<String>,
+}
+
+/// 解析请求行与请求头（不含结尾的空行）
+fn parse_request_head(head: &str) -> Result<RequestHead, String> {
+    let mut lines = head.split("\r\n");
+    let request_line = lines.next().unwrap_or_default();
+    let mut parts = request_line.split_whitespace();
+    let (Some(method), Some(target)) = (parts.next(), parts.next()) else {
+        return Err("Malformed request line".to_string());
+    };
+    // 忽略查询参数
+    let path = target.split('?').next().unwrap_or(target);
+
+    let mut content_length = 0;
+    let mut authorization = None;
+    for line in lines {
+        let Some((name, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.trim();
+        if name.eq_ignore_ascii_case("content-length") {
+            content_length = value
+                .parse()
+                .map_err(|_| "Invalid Content-Length".to_string())?;
+        } else if name.eq_ignore_ascii_case("authorization") {
+            authorization = Some(value.to_string());
+        }
+    }
+
+    Ok(RequestHead {
+        method: method.to_ascii_uppercase(),
+        path: path.trim_end_matches('/').to_string(),
+        content_length,
+        authorization,
+    })
+}
+
+fn is_authorized(authorization: Option<&str>, token: &str) -> bool {
+    local_http::token_matches(
+        authorization.and_then(|value| value.strip_prefix("Bearer ")),
+        token,
+    )
+}
+
+async fn handle_connection(
+    mut stream: TcpStream,
+    app: &AppHandle,
+    token: &str,
+) -> Result<(), String> {
+    let request = tokio::time::timeout(READ_TIMEOUT, read_request(&mut stream))
+        .await
+        .map_err(|_| "Request timed out".to_string())?;
+
+    let (status, body) = match request {
+        Ok((head, _)) if !is_authorized(head.authorization.as_deref(), token) => {
+            (401, json!({ "error": "Unauthorized" }))
+        }
+        Ok((head, body)) => dispatch(app, &head.method, &head.path, &body).await,
+        Err(e) => (400, json!({ "error": e })),
+    };
+
+    write_response(&mut stream, status, &body).await
+}
+
+async fn read_request(stream: &mut TcpStream) -> Result<(RequestHead, Vec<u8>), String> {
+    let (head, body) = local_http::read_head(stream, MAX_HEAD_BYTES).await?;
+    let head = parse_request_head(&head)?;
+    if head.content_length > MAX_BODY_BYTES {
+        return Err("Request body too large".to_string());
+    }
+
+    let body = local_http::read_body(stream, body, head.content_length).await?;
+    Ok((head, body))
+}
+
+async fn write_response(stream: &mut TcpStream, status: u16, body: &Value) -> Result<(), String> {
+    let reason = match status {
+        200 => "OK",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        404 => "Not Found",
+        _ => "Internal Server Error",
+    };
+    let body = body.to_string();
+    let response = format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        reason,
+        body.len(),
+        body
+    );
+    stream
+        .write_all(response.as_bytes())
+        .await
+        .map_err(|e| e.to_string())?;
+    stream.shutdown().await.map_err(|e| e.to_string())
+}
+
+// ============================================================================
+// Routes
+// ============================================================================
+
+#[derive(Debug, PartialEq)]
+enum Route {
+    ClaudeProviders,
+    ApplyClaudeProvider,
+    CodexProviders,
+    ApplyCodexProvider,
+    OpenCodeModels,
+    ApplyOpenCodeModel,
+    McpServers,
+    ToggleMcpTool,
+}
+
+fn match_route(method: &str, path: &str) -> Option<Route> {
+    let route = match (method, path) {
+        ("GET", "/v1/claude-code/providers") => Route::ClaudeProviders,
+        ("POST", "/v1/claude-code/providers/apply") => Route::ApplyClaudeProvider,
+        ("GET", "/v1/codex/providers") => Route::CodexProviders,
+        ("POST", "/v1/codex/providers/apply") => Route::ApplyCodexProvider,
+        ("GET", "/v1/opencode/models") => Route::OpenCodeModels,
+        ("POST", "/v1/opencode/models/apply") => Route::ApplyOpenCodeModel,
+        ("GET", "/v1/mcp/servers") => Route::McpServers,
+        ("POST", "/v1/mcp/servers/toggle") => Route::ToggleMcpTool,
+        _ => return None,
+    };
+    Some(route)
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ApplyProviderBody {
+    provider_id: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ApplyModelBody {
+    /// "main" | "small"
+    model_type: String,
+    /// "provider/model"
+    model_id: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ToggleMcpBody {
+    server_id: String,
+    tool_key: String,
+}
+
+fn parse_body<T: serde::de::DeserializeOwned>(body: &[u8]) -> Result<T, (u16, Value)> {
+    serde_json::from_slice(body)
+        .map_err(|e| (400, json!({ "error": format!("Invalid body: {}", e) })))
+}
+
+async fn dispatch(app: &AppHandle, method: &str, path: &str, body: &[u8]) -> (u16, Value) {
+    let Some(route) = match_route(method, path) else {
+        return (404, json!({ "error": "Not found" }));
+    };
+    match handle_route(app, route, body).await {
+        Ok(value) => (200, value),
+        Err(response) => response,
+    }
+}
+
+async fn handle_route(app: &AppHandle, route: Route, body: &[u8]) -> Result<Value, (u16, Value)> {
+    let is_write = matches!(
+        route,
+        Route::ApplyClaudeProvider
+            | Route::ApplyCodexProvider
+            | Route::ApplyOpenCodeModel
+            | Route::ToggleMcpTool
+    );
+
+    let result = match route {
+        Route::ClaudeProviders => claude_tray::get_claude_code_tray_data(app)
+            .await
+            .map(|data| json!(data)),
+        Route::CodexProviders => codex_tray::get_codex_tray_data(app)
+            .await
+            .map(|data| json!(data)),
+        Route::OpenCodeModels => opencode_tray::get_opencode_tray_model_data(app)
+            .await
+            .map(|(main, small)| json!({ "main": main, "small": small })),
+        Route::McpServers => mcp_tray::get_mcp_tray_data(app)
+            .await
+            .map(|data| json!(data)),
+        Route::ApplyClaudeProvider => {
+            let body: ApplyProviderBody = parse_body(body)?;
+            claude_tray::apply_claude_code_provider(app, &body.provider_id)
+                .await
+                .map(|_| json!({ "success": true }))
+        }
+        Route::ApplyCodexProvider => {
+            let body: ApplyProviderBody = parse_body(body)?;
+            codex_tray::apply_codex_provider(app, &body.provider_id)
+                .await
+                .map(|_| json!({ "success": true }))
+        }
+        Route::ApplyOpenCodeModel => {
+            let body: ApplyModelBody = parse_body(body)?;
+            opencode_tray::apply_opencode_model(app, &body.model_type, &body.model_id)
+                .await
+                .map(|_| json!({ "success": true }))
+        }
+        Route::ToggleMcpTool => {
+            let body: ToggleMcpBody = parse_body(body)?;
+            mcp_tray::apply_mcp_tool_toggle(app, &body.server_id, &body.tool_key)
+                .await
+                .map(|_| json!({ "success": true }))
+        }
+    };
+
+    // 与托盘点击后一致：刷新菜单勾选状态
+    if is_write && result.is_ok() {
+        let _ = tray::refresh_tray_menus(app).await;
+    }
+
+    result.map_err(|e| (500, json!({ "error": e })))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_request_head_and_route() {
+        let head = parse_request_head(
+            "POST /v1/codex/providers/apply/?x=1 HTTP/1.1\r\nHost: 127.0.0.1\r\ncontent-length: 23\r\nAuthorization: Bearer abc",
+        )
+        .unwrap();
+        assert_eq!(
+            head,
+            RequestHead {
+                method: "POST".to_string(),
+                path: "/v1/codex/providers/apply".to_string(),
+                content_length: 23,
+                authorization: Some("Bearer abc".to_string()),
+            }
+        );
+        assert_eq!(
+            match_route(&head.method, &head.path),
+            Some(Route::ApplyCodexProvider)
+        );
+        assert_eq!(match_route("GET", "/v1/codex/providers/apply"), None);
+
+        assert!(is_authorized(Some("Bearer abc"), "abc"));
+        assert!(!is_authorized(Some("Bearer abd"), "abc"));
+        assert!(!is_authorized(Some("abc"), "abc"));
+        assert!(!is_authorized(None, "abc"));
+        assert!(parse_request_head("garbage").is_err());
+    }
+}
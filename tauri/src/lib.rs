@@ -19,9 +19,14 @@ use std::sync::Mutex as StdMutex;
 
 // Module declarations
 pub mod auto_launch;
+pub mod cli;
 pub mod coding;
+pub mod control_api;
 pub mod db;
 pub mod http_client;
+pub mod llm_gateway;
+pub mod local_http;
+pub mod secret_vault;
 pub mod settings;
 pub mod single_instance;
 pub mod tray;
@@ -626,6 +631,10 @@ pub fn run() {
         }
     }
 
+    // macOS / Windows: 单实例由 tauri-plugin-single-instance 负责，文件锁仅供 CLI 检测
+    #[cfg(not(target_os = "linux"))]
+    single_instance::hold_lock_for_app();
+
     tauri::Builder::default()
         .plugin(tauri_plugin_single_instance::init(|app, _args, _cwd| {
             // When a second instance is launched, show and focus the existing window
@@ -726,6 +735,19 @@ pub fn run() {
             coding::preset_models::set_cache_dir(app_data_dir.clone());
             info!("预设模型缓存目录已初始化");
 
+            // Initialize SSH recorded host key and two-way sync state stores
+            coding::ssh::known_hosts::set_store_dir(app_data_dir.clone());
+            coding::ssh::sync_state::set_store_dir(app_data_dir.clone());
+
+            // Secret vault file (encrypted API keys and passwords referenced from the database)
+            secret_vault::set_store_dir(app_data_dir.clone());
+
+            // Snapshots of managed config files taken before each write
+            coding::config_history::set_store_dir(app_data_dir.clone());
+
+            // Skills central repo fallback for syncs without an app handle
+            coding::skills::central_repo::set_app_data_dir(app_data_dir.clone());
+
             // Initialize SurrealDB
             info!("正在初始化 SurrealDB...");
             tauri::async_runtime::block_on(async {
@@ -784,6 +806,15 @@ pub fn run() {
                 ));
                 app.manage(ssh_session);
                 info!("SSH 会话状态已注册到应用");
+
+                // 注册本地配置文件监听状态
+                app.manage(coding::sync_watcher::SyncWatcherState(
+                    tokio::sync::Mutex::new(None),
+                ));
+
+                // 注册本地控制接口状态
+                app.manage(control_api::ControlApiState(tokio::sync::Mutex::new(None)));
+                app.manage(llm_gateway::LlmGatewayState(tokio::sync::Mutex::new(None)));
             });
 
             // Create system tray
@@ -1112,16 +1143,21 @@ pub fn run() {
                         tauri::async_runtime::spawn(async move {
                             let db_state = app.state::<crate::DbState>();
                             let session_state = app.state::<coding::ssh::SshSessionState>();
-                            let mut session = session_state.0.lock().await;
-                            // SSH 未配置连接时跳过
-                            if session.conn().is_none() {
-                                return;
-                            }
-                            if session.ensure_connected().await.is_err() {
-                                return;
+                            {
+                                let mut session = session_state.0.lock().await;
+                                // SSH 未配置连接时跳过主连接
+                                if session.conn().is_some()
+                                    && session.ensure_connected().await.is_ok()
+                                {
+                                    let _ = coding::ssh::sync_mcp_to_ssh(
+                                        &db_state,
+                                        &session,
+                                        Some(&app),
+                                    )
+                                    .await;
+                                }
                             }
-                            let _ = coding::ssh::sync_mcp_to_ssh(&db_state, &session, app.clone())
-                                .await;
+                            coding::ssh::sync_other_targets(&db_state, &app, "mcp").await;
                         });
                     });
                     std::future::pending::<()>().await;
@@ -1136,17 +1172,21 @@ pub fn run() {
                         tauri::async_runtime::spawn(async move {
                             let db_state = app.state::<crate::DbState>();
                             let session_state = app.state::<coding::ssh::SshSessionState>();
-                            let mut session = session_state.0.lock().await;
-                            // SSH 未配置连接时跳过
-                            if session.conn().is_none() {
-                                return;
-                            }
-                            if session.ensure_connected().await.is_err() {
-                                return;
-                            }
-                            let _ =
-                                coding::ssh::sync_skills_to_ssh(&db_state, &session, app.clone())
+                            {
+                                let mut session = session_state.0.lock().await;
+                                // SSH 未配置连接时跳过主连接
+                                if session.conn().is_some()
+                                    && session.ensure_connected().await.is_ok()
+                                {
+                                    let _ = coding::ssh::sync_skills_to_ssh(
+                                        &db_state,
+                                        &session,
+                                        Some(&app),
+                                    )
                                     .await;
+                                }
+                            }
+                            coding::ssh::sync_other_targets(&db_state, &app, "skills").await;
                         });
                     });
                     std::future::pending::<()>().await;
@@ -1182,14 +1222,17 @@ pub fn run() {
                         let mut session = session_state.0.lock().await;
                         if let Err(e) = session.connect(conn).await {
                             log::warn!("SSH 启动主连接失败: {}", e);
-                            return;
+                            // 没有其它同步目标时无需继续
+                            if config.sync_targets().len() <= 1 {
+                                return;
+                            }
                         }
 
                         // 主连接建立后，执行首次同步
                         if session.try_acquire_sync_lock() {
                             let result = coding::ssh::do_full_sync(
                                 &db_state,
-                                &app_ssh_startup,
+                                Some(&app_ssh_startup),
                                 &session,
                                 &config,
                                 None,
@@ -1235,6 +1278,15 @@ pub fn run() {
                 });
             }
 
+            // Local config file watcher: auto sync changed SSH/WSL mappings
+            coding::sync_watcher::init(&app_handle);
+
+            // Localhost control API for scripts and editor plugins (opt-in)
+            control_api::init(&app_handle);
+
+            // Local LLM gateway for Claude Code / Codex (opt-in)
+            llm_gateway::init(&app_handle);
+
             // Git cache auto-cleanup task (checks every hour)
             {
                 let app_clone = app_handle.clone();
@@ -1331,6 +1383,32 @@ pub fn run() {
             // Start auto-backup scheduler
             settings::backup::auto_backup::start_auto_backup_scheduler(app_handle.clone());
 
+            // Provider health monitor (probes applied providers when enabled in settings)
+            coding::health_monitor::start_health_monitor(app_handle.clone());
+
+            // All API Hub balance monitor (refreshes imported account balances when enabled in settings)
+            coding::balance_monitor::start_balance_monitor(app_handle.clone());
+
+            // Move plaintext secrets still stored in the database into the secret vault
+            let app_handle_clone = app_handle.clone();
+            tauri::async_runtime::spawn(async move {
+                let db = app_handle_clone.state::<DbState>().db();
+                match secret_vault::seal_stored_secrets(&db).await {
+                    Ok(report) => info!(
+                        "密钥保险库迁移完成: {} 条记录已改为引用, 清理 {} 条无引用密钥",
+                        report.sealed_records, report.pruned_secrets
+                    ),
+                    Err(e) => warn!("密钥保险库迁移跳过: {}", e),
+                }
+            });
+
+            // Apply the config history retention policy from settings
+            let app_handle_clone = app_handle.clone();
+            tauri::async_runtime::spawn(async move {
+                let db = app_handle_clone.state::<DbState>().db();
+                coding::config_history::load_retention(&db).await;
+            });
+
             info!("setup() 完成，应用即将启动");
             Ok(())
         })
@@ -1414,6 +1492,12 @@ pub fn run() {
             settings::backup::restore_from_webdav,
             settings::backup::test_webdav_connection,
             settings::backup::delete_webdav_backup,
+            // Backup - S3
+            settings::backup::backup_to_s3,
+            settings::backup::list_s3_backups,
+            settings::backup::restore_from_s3,
+            settings::backup::test_s3_connection,
+            settings::backup::delete_s3_backup,
             // Claude Code
             coding::claude_code::list_claude_providers,
             coding::claude_code::create_claude_provider,
@@ -1425,6 +1509,8 @@ pub fn run() {
             coding::claude_code::reveal_claude_config_folder,
             coding::claude_code::read_claude_settings,
             coding::claude_code::apply_claude_config,
+            coding::claude_code::detect_claude_config_drift,
+            coding::claude_code::import_claude_config_drift,
             coding::claude_code::toggle_claude_code_provider_disabled,
             coding::claude_code::get_claude_common_config,
             coding::claude_code::save_claude_common_config,
@@ -1467,6 +1553,9 @@ pub fn run() {
             coding::open_code::list_opencode_favorite_providers,
             coding::open_code::upsert_opencode_favorite_provider,
             coding::open_code::delete_opencode_favorite_provider,
+            coding::open_code::detect_opencode_config_drift,
+            coding::open_code::import_opencode_config_drift,
+            coding::open_code::restore_opencode_config_drift,
             coding::open_code::list_opencode_all_api_hub_providers,
             coding::open_code::resolve_opencode_all_api_hub_providers,
             coding::open_code::list_opencode_prompt_configs,
@@ -1490,8 +1579,14 @@ pub fn run() {
             coding::codex::reorder_codex_providers,
             coding::codex::select_codex_provider,
             coding::codex::apply_codex_config,
+            coding::codex::detect_codex_config_drift,
+            coding::codex::import_codex_config_drift,
             coding::codex::toggle_codex_provider_disabled,
             coding::codex::read_codex_settings,
+            coding::codex::read_codex_profiles,
+            coding::codex::generate_codex_profiles,
+            coding::codex::save_codex_profiles,
+            coding::codex::set_codex_default_profile,
             coding::codex::get_codex_common_config,
             coding::codex::save_codex_common_config,
             coding::codex::save_codex_local_config,
@@ -1504,6 +1599,31 @@ pub fn run() {
             coding::codex::apply_codex_prompt_config,
             coding::codex::reorder_codex_prompt_configs,
             coding::codex::save_codex_local_prompt_config,
+            // Gemini CLI
+            coding::gemini_cli::get_gemini_cli_config_dir_path,
+            coding::gemini_cli::get_gemini_cli_config_file_path,
+            coding::gemini_cli::reveal_gemini_cli_config_folder,
+            coding::gemini_cli::list_gemini_cli_providers,
+            coding::gemini_cli::create_gemini_cli_provider,
+            coding::gemini_cli::update_gemini_cli_provider,
+            coding::gemini_cli::delete_gemini_cli_provider,
+            coding::gemini_cli::reorder_gemini_cli_providers,
+            coding::gemini_cli::select_gemini_cli_provider,
+            coding::gemini_cli::apply_gemini_cli_config,
+            coding::gemini_cli::toggle_gemini_cli_provider_disabled,
+            coding::gemini_cli::read_gemini_cli_settings,
+            coding::gemini_cli::get_gemini_cli_common_config,
+            coding::gemini_cli::save_gemini_cli_common_config,
+            coding::gemini_cli::save_gemini_cli_local_config,
+            coding::gemini_cli::list_gemini_cli_all_api_hub_providers,
+            coding::gemini_cli::resolve_gemini_cli_all_api_hub_providers,
+            coding::gemini_cli::list_gemini_cli_prompt_configs,
+            coding::gemini_cli::create_gemini_cli_prompt_config,
+            coding::gemini_cli::update_gemini_cli_prompt_config,
+            coding::gemini_cli::delete_gemini_cli_prompt_config,
+            coding::gemini_cli::apply_gemini_cli_prompt_config,
+            coding::gemini_cli::reorder_gemini_cli_prompt_configs,
+            coding::gemini_cli::save_gemini_cli_local_prompt_config,
             // OpenClaw
             coding::open_claw::get_openclaw_config_path,
             coding::open_claw::get_openclaw_config_path_info,
@@ -1522,6 +1642,43 @@ pub fn run() {
             coding::open_claw::resolve_openclaw_all_api_hub_providers,
             // Tray
             tray::refresh_tray_menu,
+            // Control API
+            control_api::get_control_api_status,
+            // LLM Gateway
+            llm_gateway::get_llm_gateway_status,
+            // Provider Health Monitor
+            coding::health_monitor::run_provider_health_check,
+            coding::health_monitor::list_provider_health_history,
+            // Provider Balance Monitor
+            coding::balance_monitor::refresh_provider_balances,
+            coding::balance_monitor::list_provider_balances,
+            // Secret Vault
+            secret_vault::get_secret_vault_status,
+            secret_vault::unlock_secret_vault,
+            secret_vault::migrate_secrets_to_vault,
+            // Config History
+            coding::config_history::list_config_history_files,
+            coding::config_history::get_config_history_status,
+            coding::config_history::list_config_history,
+            coding::config_history::get_config_snapshot,
+            coding::config_history::diff_config_snapshots,
+            coding::config_history::restore_config_snapshot,
+            // Workspace Profiles
+            coding::workspace_profile::list_workspace_profiles,
+            coding::workspace_profile::create_workspace_profile,
+            coding::workspace_profile::update_workspace_profile,
+            coding::workspace_profile::delete_workspace_profile,
+            coding::workspace_profile::reorder_workspace_profiles,
+            coding::workspace_profile::capture_workspace_selection,
+            coding::workspace_profile::apply_workspace_profile,
+            // Usage Analytics
+            coding::usage::get_usage_report,
+            coding::usage::rescan_usage_logs,
+            // Session History
+            coding::session_history::search_session_history,
+            coding::session_history::reindex_session_history,
+            coding::session_history::get_session_transcript,
+            coding::session_history::export_session_markdown,
             // Oh My OpenCode
             coding::oh_my_opencode::list_oh_my_opencode_configs,
             coding::oh_my_opencode::create_oh_my_opencode_config,
@@ -1559,6 +1716,7 @@ pub fn run() {
             coding::wsl::wsl_delete_file_mapping,
             coding::wsl::wsl_reset_file_mappings,
             coding::wsl::wsl_sync,
+            coding::wsl::wsl_sync_preview,
             coding::wsl::wsl_get_status,
             coding::wsl::wsl_test_path,
             coding::wsl::wsl_get_default_mappings,
@@ -1566,6 +1724,10 @@ pub fn run() {
             coding::wsl::wsl_open_folder,
             // SSH Sync
             coding::ssh::ssh_test_connection,
+            coding::ssh::ssh_get_host_key,
+            coding::ssh::ssh_forget_host_key,
+            coding::ssh::ssh_list_config_hosts,
+            coding::ssh::ssh_import_config_hosts,
             coding::ssh::ssh_get_config,
             coding::ssh::ssh_save_config,
             coding::ssh::ssh_list_connections,
@@ -1578,6 +1740,8 @@ pub fn run() {
             coding::ssh::ssh_delete_file_mapping,
             coding::ssh::ssh_reset_file_mappings,
             coding::ssh::ssh_sync,
+            coding::ssh::ssh_resolve_conflict,
+            coding::ssh::ssh_sync_preview,
             coding::ssh::ssh_get_status,
             coding::ssh::ssh_test_local_path,
             coding::ssh::ssh_get_default_mappings,
@@ -0,0 +1,887 @@
+//! Anthropic Messages ↔ OpenAI Chat Completions 协议转换
+//!
+//! Claude Code 只会调用 `/v1/messages`，当已应用的 provider 只提供 OpenAI 接口时，
+//! 网关把请求转换为 `/chat/completions`，再把响应（包括 SSE 流）转换回 Anthropic 格式。
+//! 支持文本、图片、工具调用（tool_use / tool_result）与流式输出；thinking 等
+//! OpenAI 没有对应字段的内容会被丢弃。
+
+use std::collections::HashSet;
+
+use serde_json::{json, Map, Value};
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+
+use super::proxy::{self, ProxyRequest, Upstream};
+
+const MESSAGES_PATH: &str = "/v1/messages";
+const COUNT_TOKENS_PATH: &str = "/v1/messages/count_tokens";
+const CHAT_COMPLETIONS_PATH: &str = "/chat/completions";
+
+/// 把 Anthropic 的 system（字符串或文本块数组）拼成一段文本
+fn system_text(system: &Value) -> Option<String> {
+    let text = match system {
+        Value::String(text) => text.clone(),
+        Value::Array(blocks) => blocks
+            .iter()
+            .filter_map(|b| b.get("text").and_then(|t| t.as_str()))
+            .collect::<Vec<_>>()
+            .join("\n\n"),
+        _ => return None,
+    };
+    (!text.is_empty()).then_some(text)
+}
+
+/// tool_result 的 content 可以是字符串或内容块数组，OpenAI 的 tool 消息只接受文本
+fn tool_result_text(content: Option<&Value>) -> String {
+    match content {
+        Some(Value::String(text)) => text.clone(),
+        Some(Value::Array(blocks)) => blocks
+            .iter()
+            .filter_map(|b| match b.get("type").and_then(|t| t.as_str()) {
+                Some("text") => b.get("text").and_then(|t| t.as_str()).map(String::from),
+                Some("image") => Some("[image]".to_string()),
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+            .join("\n"),
+        Some(other) if !other.is_null() => other.to_string(),
+        _ => String::new(),
+    }
+}
+
+fn image_part(block: &Value) -> Option<Value> {
+    let source = block.get("source")?;
+    let url = match source.get("type").and_then(|t| t.as_str()) {
+        Some("base64") => format!(
+            "data:{};base64,{}",
+            source
+                .get("media_type")
+                .and_then(|v| v.as_str())
+                .unwrap_or("image/png"),
+            source.get("data").and_then(|v| v.as_str())?
+        ),
+        Some("url") => source.get("url").and_then(|v| v.as_str())?.to_string(),
+        _ => return None,
+    };
+    Some(json!({ "type": "image_url", "image_url": { "url": url } }))
+}
+
+/// 转换一条 user 消息：tool_result 拆成独立的 tool 消息（必须紧跟在 assistant 的 tool_calls 之后），
+/// 其余内容合并为一条 user 消息
+fn convert_user_message(content: &Value, out: &mut Vec<Value>) {
+    let blocks = match content {
+        Value::Array(blocks) => blocks,
+        other => {
+            out.push(json!({ "role": "user", "content": other.as_str().unwrap_or_default() }));
+            return;
+        }
+    };
+
+    let mut parts = Vec::new();
+    for block in blocks {
+        match block.get("type").and_then(|t| t.as_str()) {
+            Some("text") => parts.push(json!({
+                "type": "text",
+                "text": block.get("text").and_then(|t| t.as_str()).unwrap_or_default(),
+            })),
+            Some("image") => parts.extend(image_part(block)),
+            Some("tool_result") => {
+                let mut text = tool_result_text(block.get("content"));
+                if block.get("is_error").and_then(|v| v.as_bool()) == Some(true) {
+                    text = format!("Error: {}", text);
+                }
+                out.push(json!({
+                    "role": "tool",
+                    "tool_call_id": block.get("tool_use_id").and_then(|v| v.as_str()).unwrap_or_default(),
+                    "content": text,
+                }));
+            }
+            _ => {}
+        }
+    }
+
+    if parts.is_empty() {
+        return;
+    }
+    // 纯文本时使用字符串，兼容不支持多段 content 的上游
+    let text_only = parts
+        .iter()
+        .all(|p| p.get("type").and_then(|t| t.as_str()) == Some("text"));
+    let content = if text_only {
+        Value::String(
+            parts
+                .iter()
+                .filter_map(|p| p.get("text").and_then(|t| t.as_str()))
+                .collect::<Vec<_>>()
+                .join("\n"),
+        )
+    } else {
+        Value::Array(parts)
+    };
+    out.push(json!({ "role": "user", "content": content }));
+}
+
+fn convert_assistant_message(content: &Value, out: &mut Vec<Value>) {
+    let blocks = match content {
+        Value::Array(blocks) => blocks,
+        other => {
+            out.push(json!({ "role": "assistant", "content": other.as_str().unwrap_or_default() }));
+            return;
+        }
+    };
+
+    let mut text = String::new();
+    let mut tool_calls = Vec::new();
+    for block in blocks {
+        match block.get("type").and_then(|t| t.as_str()) {
+            Some("text") => text.push_str(block.get("text").and_then(|t| t.as_str()).unwrap_or_default()),
+            Some("tool_use") => tool_calls.push(json!({
+                "id": block.get("id").and_then(|v| v.as_str()).unwrap_or_default(),
+                "type": "function",
+                "function": {
+                    "name": block.get("name").and_then(|v| v.as_str()).unwrap_or_default(),
+                    "arguments": block.get("input").cloned().unwrap_or_else(|| json!({})).to_string(),
+                },
+            })),
+            _ => {}
+        }
+    }
+
+    let mut message = Map::new();
+    message.insert("role".to_string(), json!("assistant"));
+    message.insert(
+        "content".to_string(),
+        if text.is_empty() {
+            Value::Null
+        } else {
+            json!(text)
+        },
+    );
+    if !tool_calls.is_empty() {
+        message.insert("tool_calls".to_string(), Value::Array(tool_calls));
+    }
+    out.push(Value::Object(message));
+}
+
+fn convert_tool_choice(choice: &Value) -> Option<Value> {
+    match choice.get("type").and_then(|t| t.as_str())? {
+        "auto" => Some(json!("auto")),
+        "any" => Some(json!("required")),
+        "none" => Some(json!("none")),
+        "tool" => Some(json!({
+            "type": "function",
+            "function": { "name": choice.get("name")?.as_str()? },
+        })),
+        _ => None,
+    }
+}
+
+/// Anthropic Messages 请求 → OpenAI Chat Completions 请求
+pub fn convert_request(body: &Value) -> Result<Value, String> {
+    let messages = body
+        .get("messages")
+        .and_then(|m| m.as_array())
+        .ok_or_else(|| "Request has no messages".to_string())?;
+
+    let mut out_messages = Vec::new();
+    if let Some(system) = body.get("system").and_then(system_text) {
+        out_messages.push(json!({ "role": "system", "content": system }));
+    }
+    for message in messages {
+        let content = message.get("content").unwrap_or(&Value::Null);
+        match message.get("role").and_then(|r| r.as_str()) {
+            Some("assistant") => convert_assistant_message(content, &mut out_messages),
+            _ => convert_user_message(content, &mut out_messages),
+        }
+    }
+
+    let mut request = Map::new();
+    request.insert(
+        "model".to_string(),
+        body.get("model").cloned().unwrap_or(Value::Null),
+    );
+    request.insert("messages".to_string(), Value::Array(out_messages));
+    for (from, to) in [
+        ("max_tokens", "max_tokens"),
+        ("temperature", "temperature"),
+        ("top_p", "top_p"),
+        ("stop_sequences", "stop"),
+    ] {
+        if let Some(value) = body.get(from).filter(|v| !v.is_null()) {
+            request.insert(to.to_string(), value.clone());
+        }
+    }
+
+    if let Some(tools) = body.get("tools").and_then(|t| t.as_array()) {
+        // 服务端工具（web_search 等）带 type 且没有 input_schema，OpenAI 无法执行，直接忽略
+        let functions: Vec<Value> = tools
+            .iter()
+            .filter(|tool| tool.get("input_schema").is_some())
+            .map(|tool| {
+                json!({
+                    "type": "function",
+                    "function": {
+                        "name": tool.get("name").cloned().unwrap_or(Value::Null),
+                        "description": tool.get("description").cloned().unwrap_or_else(|| json!("")),
+                        "parameters": tool.get("input_schema").cloned().unwrap_or_else(|| json!({})),
+                    },
+                })
+            })
+            .collect();
+        if !functions.is_empty() {
+            request.insert("tools".to_string(), Value::Array(functions));
+            if let Some(choice) = body.get("tool_choice").and_then(convert_tool_choice) {
+                request.insert("tool_choice".to_string(), choice);
+            }
+        }
+    }
+
+    if body.get("stream").and_then(|v| v.as_bool()) == Some(true) {
+        request.insert("stream".to_string(), json!(true));
+        request.insert(
+            "stream_options".to_string(),
+            json!({ "include_usage": true }),
+        );
+    }
+
+    Ok(Value::Object(request))
+}
+
+fn stop_reason(finish_reason: Option<&str>) -> &'static str {
+    match finish_reason {
+        Some("length") => "max_tokens",
+        Some("tool_calls") | Some("function_call") => "tool_use",
+        _ => "end_turn",
+    }
+}
+
+fn message_id(id: Option<&str>) -> String {
+    match id.filter(|id| !id.is_empty()) {
+        Some(id) if id.starts_with("msg_") => id.to_string(),
+        Some(id) => format!("msg_{}", id),
+        None => format!("msg_{}", uuid::Uuid::new_v4().simple()),
+    }
+}
+
+/// 工具参数应为 JSON 对象，上游返回非法 JSON 时保留原文以免丢失
+fn parse_tool_input(arguments: &str) -> Value {
+    if arguments.trim().is_empty() {
+        return json!({});
+    }
+    serde_json::from_str(arguments).unwrap_or_else(|_| json!({ "raw_arguments": arguments }))
+}
+
+fn usage_json(usage: Option<&Value>) -> Value {
+    let get = |key: &str| {
+        usage
+            .and_then(|u| u.get(key))
+            .and_then(|v| v.as_u64())
+            .unwrap_or(0)
+    };
+    json!({
+        "input_tokens": get("prompt_tokens"),
+        "output_tokens": get("completion_tokens"),
+    })
+}
+
+/// OpenAI Chat Completions 响应 → Anthropic Messages 响应
+pub fn convert_response(response: &Value, request_model: &str) -> Value {
+    let choice = response
+        .get("choices")
+        .and_then(|c| c.as_array())
+        .and_then(|c| c.first());
+    let message = choice.and_then(|c| c.get("message"));
+
+    let mut content = Vec::new();
+    if let Some(text) = message
+        .and_then(|m| m.get("content"))
+        .and_then(|c| c.as_str())
+        .filter(|t| !t.is_empty())
+    {
+        content.push(json!({ "type": "text", "text": text }));
+    }
+    if let Some(tool_calls) = message
+        .and_then(|m| m.get("tool_calls"))
+        .and_then(|t| t.as_array())
+    {
+        for call in tool_calls {
+            let function = call.get("function");
+            content.push(json!({
+                "type": "tool_use",
+                "id": call.get("id").and_then(|v| v.as_str()).unwrap_or_default(),
+                "name": function.and_then(|f| f.get("name")).and_then(|v| v.as_str()).unwrap_or_default(),
+                "input": parse_tool_input(
+                    function.and_then(|f| f.get("arguments")).and_then(|v| v.as_str()).unwrap_or_default(),
+                ),
+            }));
+        }
+    }
+
+    json!({
+        "id": message_id(response.get("id").and_then(|v| v.as_str())),
+        "type": "message",
+        "role": "assistant",
+        "model": response.get("model").and_then(|v| v.as_str()).unwrap_or(request_model),
+        "content": content,
+        "stop_reason": stop_reason(choice.and_then(|c| c.get("finish_reason")).and_then(|v| v.as_str())),
+        "stop_sequence": Value::Null,
+        "usage": usage_json(response.get("usage")),
+    })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum OpenBlock {
+    Text,
+    /// OpenAI tool_calls 中的 index
+    Tool(u64),
+}
+
+/// 把 OpenAI 的 SSE 流逐行转换为 Anthropic 的 SSE 事件
+pub struct StreamTranslator {
+    model: String,
+    /// 未处理完的字节；按行解码，避免多字节字符被分片截断
+    buffer: Vec<u8>,
+    started: bool,
+    finished: bool,
+    next_index: usize,
+    open_block: Option<OpenBlock>,
+    /// 已开始的工具调用，用于忽略重复的起始分片
+    tool_blocks: HashSet<u64>,
+    finish_reason: Option<String>,
+    usage: Option<Value>,
+}
+
+impl StreamTranslator {
+    pub fn new(model: &str) -> Self {
+        Self {
+            model: model.to_string(),
+            buffer: Vec::new(),
+            started: false,
+            finished: false,
+            next_index: 0,
+            open_block: None,
+            tool_blocks: HashSet::new(),
+            finish_reason: None,
+            usage: None,
+        }
+    }
+
+    /// 输入上游字节，返回可以立即写给客户端的 SSE 文本
+    pub fn feed(&mut self, bytes: &[u8]) -> String {
+        self.buffer.extend_from_slice(bytes);
+        let mut out = String::new();
+        while let Some(pos) = self.buffer.iter().position(|&b| b == b'\n') {
+            let line: Vec<u8> = self.buffer.drain(..=pos).collect();
+            self.handle_line(String::from_utf8_lossy(&line).trim(), &mut out);
+        }
+        out
+    }
+
+    /// 上游流结束（收到 `[DONE]` 或连接关闭）时补齐结束事件
+    pub fn finish(&mut self) -> String {
+        let mut out = String::new();
+        let rest = std::mem::take(&mut self.buffer);
+        self.handle_line(String::from_utf8_lossy(&rest).trim(), &mut out);
+        self.close(&mut out);
+        out
+    }
+
+    fn handle_line(&mut self, line: &str, out: &mut String) {
+        let Some(data) = line.strip_prefix("data:").map(str::trim) else {
+            return;
+        };
+        if data == "[DONE]" {
+            self.close(out);
+            return;
+        }
+        if let Ok(chunk) = serde_json::from_str::<Value>(data) {
+            self.handle_chunk(&chunk, out);
+        }
+    }
+
+    fn emit(out: &mut String, event: &str, data: Value) {
+        out.push_str(&format!("event: {}\ndata: {}\n\n", event, data));
+    }
+
+    fn start(&mut self, chunk: &Value, out: &mut String) {
+        if self.started {
+            return;
+        }
+        self.started = true;
+        if let Some(model) = chunk.get("model").and_then(|v| v.as_str()) {
+            self.model = model.to_string();
+        }
+        // OpenAI 通常在最后一个分片才返回 usage，此时未知的 input_tokens 不写入，
+        // 由 message_delta 中的 usage 补齐
+        let mut usage = json!({ "output_tokens": 0 });
+        if let Some(input) = self
+            .usage
+            .as_ref()
+            .and_then(|u| u.get("prompt_tokens"))
+            .and_then(|v| v.as_u64())
+        {
+            usage["input_tokens"] = json!(input);
+        }
+        Self::emit(
+            out,
+            "message_start",
+            json!({
+                "type": "message_start",
+                "message": {
+                    "id": message_id(chunk.get("id").and_then(|v| v.as_str())),
+                    "type": "message",
+                    "role": "assistant",
+                    "model": self.model,
+                    "content": [],
+                    "stop_reason": Value::Null,
+                    "stop_sequence": Value::Null,
+                    "usage": usage,
+                },
+            }),
+        );
+    }
+
+    fn close_block(&mut self, out: &mut String) {
+        if self.open_block.take().is_some() {
+            Self::emit(
+                out,
+                "content_block_stop",
+                json!({ "type": "content_block_stop", "index": self.next_index - 1 }),
+            );
+        }
+    }
+
+    fn open(&mut self, block: OpenBlock, content_block: Value, out: &mut String) {
+        self.close_block(out);
+        Self::emit(
+            out,
+            "content_block_start",
+            json!({
+                "type": "content_block_start",
+                "index": self.next_index,
+                "content_block": content_block,
+            }),
+        );
+        self.open_block = Some(block);
+        self.next_index += 1;
+    }
+
+    fn delta(&self, delta: Value, out: &mut String) {
+        Self::emit(
+            out,
+            "content_block_delta",
+            json!({ "type": "content_block_delta", "index": self.next_index - 1, "delta": delta }),
+        );
+    }
+
+    fn handle_chunk(&mut self, chunk: &Value, out: &mut String) {
+        if self.finished {
+            return;
+        }
+        if let Some(usage) = chunk.get("usage").filter(|u| u.is_object()) {
+            self.usage = Some(usage.clone());
+        }
+        self.start(chunk, out);
+
+        let Some(choice) = chunk
+            .get("choices")
+            .and_then(|c| c.as_array())
+            .and_then(|c| c.first())
+        else {
+            return;
+        };
+        let delta = choice.get("delta");
+
+        if let Some(text) = delta
+            .and_then(|d| d.get("content"))
+            .and_then(|c| c.as_str())
+            .filter(|t| !t.is_empty())
+        {
+            if self.open_block != Some(OpenBlock::Text) {
+                self.open(OpenBlock::Text, json!({ "type": "text", "text": "" }), out);
+            }
+            self.delta(json!({ "type": "text_delta", "text": text }), out);
+        }
+
+        if let Some(tool_calls) = delta
+            .and_then(|d| d.get("tool_calls"))
+            .and_then(|t| t.as_array())
+        {
+            for call in tool_calls {
+                let index = call.get("index").and_then(|v| v.as_u64()).unwrap_or(0);
+                let function = call.get("function");
+                if self.tool_blocks.insert(index) {
+                    self.open(
+                        OpenBlock::Tool(index),
+                        json!({
+                            "type": "tool_use",
+                            "id": call
+                                .get("id")
+                                .and_then(|v| v.as_str())
+                                .map(String::from)
+                                .unwrap_or_else(|| format!("toolu_{}", uuid::Uuid::new_v4().simple())),
+                            "name": function.and_then(|f| f.get("name")).and_then(|v| v.as_str()).unwrap_or_default(),
+                            "input": {},
+                        }),
+                        out,
+                    );
+                }
+                // 参数分片只能追加到当前打开的工具块
+                if self.open_block != Some(OpenBlock::Tool(index)) {
+                    continue;
+                }
+                if let Some(arguments) = function
+                    .and_then(|f| f.get("arguments"))
+                    .and_then(|v| v.as_str())
+                    .filter(|a| !a.is_empty())
+                {
+                    self.delta(
+                        json!({ "type": "input_json_delta", "partial_json": arguments }),
+                        out,
+                    );
+                }
+            }
+        }
+
+        if let Some(reason) = choice.get("finish_reason").and_then(|v| v.as_str()) {
+            self.finish_reason = Some(reason.to_string());
+        }
+    }
+
+    fn close(&mut self, out: &mut String) {
+        if self.finished {
+            return;
+        }
+        if !self.started {
+            self.start(&Value::Null, out);
+        }
+        self.finished = true;
+        self.close_block(out);
+        let usage = usage_json(self.usage.as_ref());
+        Self::emit(
+            out,
+            "message_delta",
+            json!({
+                "type": "message_delta",
+                "delta": {
+                    "stop_reason": stop_reason(self.finish_reason.as_deref()),
+                    "stop_sequence": Value::Null,
+                },
+                "usage": usage,
+            }),
+        );
+        Self::emit(out, "message_stop", json!({ "type": "message_stop" }));
+    }
+}
+
+/// 粗略估算 token 数（约 4 字符一个 token），OpenAI 接口没有对应的计数端点
+fn estimate_tokens(body: &Value) -> u64 {
+    let chars = ["system", "messages", "tools"]
+        .iter()
+        .filter_map(|key| body.get(*key))
+        .map(|v| v.to_string().chars().count() as u64)
+        .sum::<u64>();
+    chars.div_ceil(4).max(1)
+}
+
+/// 处理 Claude Code 发往 OpenAI 格式 provider 的请求
+pub async fn forward<S: AsyncWrite + Unpin>(
+    client: &reqwest::Client,
+    upstream: &Upstream,
+    request: &ProxyRequest,
+    suffix: &str,
+    stream: &mut S,
+) -> Result<(), String> {
+    if request.method != "POST" || (suffix != MESSAGES_PATH && suffix != COUNT_TOKENS_PATH) {
+        return proxy::write_error(
+            stream,
+            404,
+            &format!("{} is not supported for OpenAI-format providers", suffix),
+        )
+        .await;
+    }
+    let body: Value = match serde_json::from_slice(&request.body) {
+        Ok(body) => body,
+        Err(e) => {
+            return proxy::write_error(stream, 400, &format!("Invalid JSON body: {}", e)).await
+        }
+    };
+
+    if suffix == COUNT_TOKENS_PATH {
+        return proxy::write_json(
+            stream,
+            200,
+            &json!({ "input_tokens": estimate_tokens(&body) }),
+        )
+        .await;
+    }
+
+    let converted = match convert_request(&body) {
+        Ok(converted) => converted,
+        Err(e) => return proxy::write_error(stream, 400, &e).await,
+    };
+    let streaming = converted.get("stream").is_some();
+    let model = body
+        .get("model")
+        .and_then(|v| v.as_str())
+        .unwrap_or_default()
+        .to_string();
+
+    let url = upstream.url(CHAT_COMPLETIONS_PATH, None);
+    let mut builder = client.post(&url).json(&converted);
+    for (name, value) in &upstream.headers {
+        builder = builder.header(name.as_str(), value.as_str());
+    }
+    let mut response = match builder.send().await {
+        Ok(response) => response,
+        Err(e) => {
+            log::warn!("LLM gateway upstream request to {} failed: {}", url, e);
+            return proxy::write_error(stream, 502, &format!("Upstream request failed: {}", e))
+                .await;
+        }
+    };
+
+    let status = response.status();
+    if !status.is_success() {
+        let text = response.text().await.unwrap_or_default();
+        // 尽量取出 OpenAI 错误中的 message，Claude Code 会直接显示
+        let message = serde_json::from_str::<Value>(&text)
+            .ok()
+            .and_then(|v| {
+                v.pointer("/error/message")
+                    .and_then(|m| m.as_str())
+                    .map(String::from)
+            })
+            .unwrap_or(text);
+        return proxy::write_json(
+            stream,
+            status.as_u16(),
+            &json!({
+                "type": "error",
+                "error": { "type": "api_error", "message": message },
+            }),
+        )
+        .await;
+    }
+
+    if !streaming {
+        let value: Value = match response.json().await {
+            Ok(value) => value,
+            Err(e) => {
+                return proxy::write_error(
+                    stream,
+                    502,
+                    &format!("Invalid upstream response: {}", e),
+                )
+                .await
+            }
+        };
+        return proxy::write_json(stream, 200, &convert_response(&value, &model)).await;
+    }
+
+    stream
+        .write_all(
+            b"HTTP/1.1 200 OK\r\ncontent-type: text/event-stream\r\ncache-control: no-cache\r\nConnection: close\r\n\r\n",
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let mut translator = StreamTranslator::new(&model);
+    loop {
+        let events = match response.chunk().await {
+            Ok(Some(chunk)) => translator.feed(&chunk),
+            Ok(None) => break,
+            Err(e) => {
+                log::warn!("LLM gateway upstream stream from {} broke: {}", url, e);
+                break;
+            }
+        };
+        if !events.is_empty() {
+            stream
+                .write_all(events.as_bytes())
+                .await
+                .map_err(|e| e.to_string())?;
+            stream.flush().await.map_err(|e| e.to_string())?;
+        }
+    }
+    stream
+        .write_all(translator.finish().as_bytes())
+        .await
+        .map_err(|e| e.to_string())?;
+    stream.shutdown().await.map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn convert_request_maps_tools_images_and_results() {
+        let body = json!({
+            "model": "gpt-4.1",
+            "max_tokens": 1024,
+            "system": [{ "type": "text", "text": "be brief" }],
+            "stream": true,
+            "tools": [{ "name": "read", "description": "Read a file", "input_schema": { "type": "object" } }],
+            "tool_choice": { "type": "any" },
+            "messages": [
+                { "role": "user", "content": [
+                    { "type": "text", "text": "look" },
+                    { "type": "image", "source": { "type": "base64", "media_type": "image/png", "data": "AAA" } },
+                ] },
+                { "role": "assistant", "content": [
+                    { "type": "text", "text": "reading" },
+                    { "type": "tool_use", "id": "call_1", "name": "read", "input": { "path": "a" } },
+                ] },
+                { "role": "user", "content": [
+                    { "type": "tool_result", "tool_use_id": "call_1", "content": [{ "type": "text", "text": "hi" }] },
+                    { "type": "text", "text": "go on" },
+                ] },
+            ],
+        });
+        let converted = convert_request(&body).unwrap();
+        assert_eq!(converted["tool_choice"], json!("required"));
+        assert_eq!(converted["stream_options"]["include_usage"], json!(true));
+        assert_eq!(
+            converted["tools"][0]["function"]["parameters"]["type"],
+            json!("object")
+        );
+
+        let messages = converted["messages"].as_array().unwrap();
+        assert_eq!(
+            messages[0],
+            json!({ "role": "system", "content": "be brief" })
+        );
+        assert_eq!(
+            messages[1]["content"][1]["image_url"]["url"],
+            json!("data:image/png;base64,AAA")
+        );
+        assert_eq!(
+            messages[2]["tool_calls"][0]["function"]["arguments"],
+            json!("{\"path\":\"a\"}")
+        );
+        assert_eq!(
+            messages[3],
+            json!({ "role": "tool", "tool_call_id": "call_1", "content": "hi" })
+        );
+        assert_eq!(messages[4], json!({ "role": "user", "content": "go on" }));
+    }
+
+    #[test]
+    fn convert_response_maps_tool_calls() {
+        let response = json!({
+            "id": "chatcmpl-1",
+            "model": "gpt-4.1",
+            "choices": [{
+                "finish_reason": "tool_calls",
+                "message": { "content": null, "tool_calls": [
+                    { "id": "call_1", "type": "function", "function": { "name": "read", "arguments": "{\"path\":\"a\"}" } },
+                ] },
+            }],
+            "usage": { "prompt_tokens": 10, "completion_tokens": 5 },
+        });
+        let converted = convert_response(&response, "gpt-4.1");
+        assert_eq!(converted["id"], json!("msg_chatcmpl-1"));
+        assert_eq!(converted["stop_reason"], json!("tool_use"));
+        assert_eq!(converted["content"][0]["input"], json!({ "path": "a" }));
+        assert_eq!(
+            converted["usage"],
+            json!({ "input_tokens": 10, "output_tokens": 5 })
+        );
+    }
+
+    fn events(sse: &str) -> Vec<Value> {
+        sse.lines()
+            .filter_map(|l| l.strip_prefix("data: "))
+            .map(|d| serde_json::from_str(d).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn stream_translator_emits_anthropic_events() {
+        let mut translator = StreamTranslator::new("gpt-4.1");
+        let upstream = concat!(
+            "data: {\"id\":\"c1\",\"choices\":[{\"delta\":{\"content\":\"Hi\"}}]}\n\n",
+            "data: {\"id\":\"c1\",\"choices\":[{\"delta\":{\"tool_calls\":[{\"index\":0,\"id\":\"call_1\",\"function\":{\"name\":\"read\",\"arguments\":\"{\\\"pa\"}}]}}]}\n\n",
+            "data: {\"id\":\"c1\",\"choices\":[{\"delta\":{\"tool_calls\":[{\"index\":0,\"function\":{\"arguments\":\"th\\\":1}\"}}]},\"finish_reason\":\"tool_calls\"}]}\n\n",
+            "data: {\"id\":\"c1\",\"choices\":[],\"usage\":{\"prompt_tokens\":3,\"completion_tokens\":7}}\n\n",
+            "data: [DONE]\n\n",
+        );
+        // 分片边界落在行中间
+        let (first, second) = upstream.split_at(30);
+        let mut out = translator.feed(first.as_bytes());
+        out.push_str(&translator.feed(second.as_bytes()));
+        out.push_str(&translator.finish());
+
+        let types: Vec<String> = events(&out)
+            .iter()
+            .map(|e| e["type"].as_str().unwrap().to_string())
+            .collect();
+        assert_eq!(
+            types,
+            [
+                "message_start",
+                "content_block_start",
+                "content_block_delta",
+                "content_block_stop",
+                "content_block_start",
+                "content_block_delta",
+                "content_block_delta",
+                "content_block_stop",
+                "message_delta",
+                "message_stop",
+            ]
+        );
+        let all = events(&out);
+        assert_eq!(all[4]["index"], json!(1));
+        assert_eq!(all[4]["content_block"]["name"], json!("read"));
+        assert_eq!(all[6]["delta"]["partial_json"], json!("th\":1}"));
+        assert_eq!(all[8]["delta"]["stop_reason"], json!("tool_use"));
+        assert_eq!(all[8]["usage"]["output_tokens"], json!(7));
+    }
+
+    #[test]
+    fn stream_translator_closes_stream_without_done() {
+        let mut translator = StreamTranslator::new("m");
+        let mut out = translator.feed(
+            b"data: {\"choices\":[{\"delta\":{\"content\":\"a\"},\"finish_reason\":\"length\"}]}",
+        );
+        out.push_str(&translator.finish());
+        let all = events(&out);
+        assert_eq!(all.last().unwrap()["type"], json!("message_stop"));
+        assert_eq!(
+            all[all.len() - 2]["delta"]["stop_reason"],
+            json!("max_tokens")
+        );
+    }
+
+    #[test]
+    fn stream_translator_keeps_multibyte_char_split_across_chunks() {
+        let mut translator = StreamTranslator::new("m");
+        let upstream = "data: {\"choices\":[{\"delta\":{\"content\":\"你好\"}}]}\n\n".as_bytes();
+        // "你" 占 3 个字节，分片边界落在它的第 2 个字节之后
+        let split = upstream.iter().position(|&b| b == 0xE4).unwrap() + 2;
+        let mut out = translator.feed(&upstream[..split]);
+        out.push_str(&translator.feed(&upstream[split..]));
+        out.push_str(&translator.finish());
+
+        let all = events(&out);
+        assert_eq!(all[2]["delta"]["text"], json!("你好"));
+    }
+
+    #[test]
+    fn stream_translator_reports_input_tokens_from_upstream_usage() {
+        let mut translator = StreamTranslator::new("m");
+        let mut out = translator.feed(
+            b"data: {\"choices\":[{\"delta\":{\"content\":\"a\"}}],\"usage\":{\"prompt_tokens\":12,\"completion_tokens\":0}}\n\n",
+        );
+        out.push_str(&translator.finish());
+        let all = events(&out);
+        assert_eq!(all[0]["message"]["usage"]["input_tokens"], json!(12));
+
+        // 上游在开始时未返回 usage：message_start 不写入占位的 0
+        let mut translator = StreamTranslator::new("m");
+        let out = translator.feed(b"data: {\"choices\":[{\"delta\":{\"content\":\"a\"}}]}\n\n");
+        let all = events(&out);
+        assert!(all[0]["message"]["usage"].get("input_tokens").is_none());
+    }
+}
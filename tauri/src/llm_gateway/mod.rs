@@ -0,0 +1,436 @@
+//! 本地 LLM 网关
+//!
+//! 在 `127.0.0.1` 的固定端口上提供反向代理，Claude Code / Codex 只需指向网关一次：
+//! - `/claude/*` 转发到当前应用的 Claude Code provider（Anthropic Messages 接口）
+//! - `/codex/*` 转发到当前应用的 Codex provider（OpenAI Responses / Chat 接口）
+//!
+//! 网关前缀之后的路径原样拼接到 provider 的 base URL 后，并注入该 provider 的密钥；
+//! 每个请求都会重新读取数据库中已应用的 provider，因此切换后立即生效，无需重启会话。
+//! 标记为 OpenAI 格式（`apiFormat = "openai_chat"`）的 Claude Code provider 由
+//! `anthropic_openai` 把 Messages 请求转换为 Chat Completions。
+//!
+//! 开启后，应用 provider 时写入 settings.json / config.toml 的地址和密钥会替换为网关地址与
+//! 网关 token（见 `claude_endpoint` / `codex_endpoint`），模型等其他字段照常写入。
+
+mod anthropic_openai;
+mod proxy;
+mod upstream;
+
+pub use upstream::{route_codex_config, unroute_codex_config};
+
+use std::time::Duration;
+
+use serde::Serialize;
+use tauri::{AppHandle, Manager};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::{oneshot, Mutex};
+
+use crate::coding::claude_code;
+use crate::coding::codex;
+use crate::db::DbState;
+use crate::http_client;
+use crate::local_http;
+use crate::settings::AppSettings;
+
+const CLAUDE_PREFIX: &str = "/claude";
+const CODEX_PREFIX: &str = "/codex";
+/// 读取客户端请求的超时
+const READ_TIMEOUT: Duration = Duration::from_secs(60);
+/// 上游请求总超时，需要覆盖较长的流式输出
+const UPSTREAM_TIMEOUT_SECS: u64 = 3600;
+
+/// 当前运行的网关，注册到 Tauri State
+pub struct LlmGatewayState(pub Mutex<Option<RunningGateway>>);
+
+pub struct RunningGateway {
+    port: u16,
+    token: String,
+    shutdown: oneshot::Sender<()>,
+}
+
+/// 网关状态（返回给设置页）
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LlmGatewayStatus {
+    pub running: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub port: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub claude_base_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub codex_base_url: Option<String>,
+}
+
+/// 工具配置中使用的网关地址与 token
+#[derive(Debug, Clone, PartialEq)]
+pub struct GatewayEndpoint {
+    pub base_url: String,
+    pub token: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum GatewayRoute {
+    ClaudeCode,
+    Codex,
+}
+
+/// 生成网关 token
+pub fn generate_token() -> String {
+    format!(
+        "sk-aitb-{}{}",
+        uuid::Uuid::new_v4().simple(),
+        uuid::Uuid::new_v4().simple()
+    )
+}
+
+/// 设置中开启网关时，Claude Code 应使用的 ANTHROPIC_BASE_URL 与 token
+pub async fn claude_endpoint(
+    db: &surrealdb::Surreal<surrealdb::engine::local::Db>,
+) -> Option<GatewayEndpoint> {
+    endpoint(db, CLAUDE_PREFIX).await
+}
+
+/// 设置中开启网关时，Codex 应使用的 base_url 与 token
+pub async fn codex_endpoint(
+    db: &surrealdb::Surreal<surrealdb::engine::local::Db>,
+) -> Option<GatewayEndpoint> {
+    endpoint(db, CODEX_PREFIX).await
+}
+
+/// 按设置而不是运行状态判断，命令行工具应用 provider 时也能得到同样的结果
+async fn endpoint(
+    db: &surrealdb::Surreal<surrealdb::engine::local::Db>,
+    prefix: &str,
+) -> Option<GatewayEndpoint> {
+    let settings = crate::settings::load_settings(db).await.ok()?;
+    if !settings.llm_gateway_enabled || settings.llm_gateway_token.is_empty() {
+        return None;
+    }
+    Some(GatewayEndpoint {
+        base_url: format!("http://127.0.0.1:{}{}", settings.llm_gateway_port, prefix),
+        token: settings.llm_gateway_token,
+    })
+}
+
+/// 按设置启动、重启或停止网关；网关地址变化时重新写入 Claude Code / Codex 配置
+pub async fn apply_settings(app: &AppHandle, settings: &AppSettings) -> Result<(), String> {
+    let wanted = if settings.llm_gateway_enabled && !settings.llm_gateway_token.is_empty() {
+        let port = u16::try_from(settings.llm_gateway_port)
+            .map_err(|_| format!("Invalid gateway port: {}", settings.llm_gateway_port))?;
+        Some((port, settings.llm_gateway_token.clone()))
+    } else {
+        None
+    };
+
+    let state = app.state::<LlmGatewayState>();
+    let mut current = state.0.lock().await;
+    let running = current.as_ref().map(|g| (g.port, g.token.clone()));
+    if running == wanted {
+        return Ok(());
+    }
+
+    if let Some(gateway) = current.take() {
+        let _ = gateway.shutdown.send(());
+        log::info!("LLM gateway stopped");
+    }
+    let result = match wanted {
+        Some((port, token)) => start(app, port, token).await.map(|g| {
+            *current = Some(g);
+        }),
+        None => Ok(()),
+    };
+    drop(current);
+
+    reapply_tool_configs(app).await;
+    result
+}
+
+/// 启动时按已保存的设置开启（配置文件已指向网关，无需重写）
+pub fn init(app: &AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let Ok(settings) = crate::settings::get_settings(app.state()).await else {
+            return;
+        };
+        if !settings.llm_gateway_enabled || settings.llm_gateway_token.is_empty() {
+            return;
+        }
+        let Ok(port) = u16::try_from(settings.llm_gateway_port) else {
+            return;
+        };
+        let state = app.state::<LlmGatewayState>();
+        let mut current = state.0.lock().await;
+        match start(&app, port, settings.llm_gateway_token).await {
+            Ok(gateway) => *current = Some(gateway),
+            Err(e) => log::warn!("Failed to start LLM gateway: {}", e),
+        }
+    });
+}
+
+/// Get LLM gateway status
+#[tauri::command]
+pub async fn get_llm_gateway_status(
+    state: tauri::State<'_, LlmGatewayState>,
+) -> Result<LlmGatewayStatus, String> {
+    let current = state.0.lock().await;
+    Ok(match current.as_ref() {
+        Some(gateway) => LlmGatewayStatus {
+            running: true,
+            port: Some(gateway.port),
+            claude_base_url: Some(format!(
+                "http://127.0.0.1:{}{}",
+                gateway.port, CLAUDE_PREFIX
+            )),
+            codex_base_url: Some(format!("http://127.0.0.1:{}{}", gateway.port, CODEX_PREFIX)),
+        },
+        None => LlmGatewayStatus {
+            running: false,
+            port: None,
+            claude_base_url: None,
+            codex_base_url: None,
+        },
+    })
+}
+
+/// 重新写入已应用 provider 的配置文件，使其指向（或不再指向）网关
+async fn reapply_tool_configs(app: &AppHandle) {
+    let db = app.state::<DbState>().db();
+
+    if let Ok(Some(provider)) = claude_code::commands::load_applied_provider(&db).await {
+        if let Err(e) = claude_code::commands::apply_config_to_file_public(&db, &provider.id).await
+        {
+            log::warn!("Failed to rewrite Claude Code config for gateway: {}", e);
+        }
+    }
+    if let Ok(Some(provider)) = codex::commands::load_applied_provider(&db).await {
+        if let Err(e) = codex::commands::apply_config_to_file_public(&db, &provider.id).await {
+            log::warn!("Failed to rewrite Codex config for gateway: {}", e);
+        }
+    }
+}
+
+async fn start(app: &AppHandle, port: u16, token: String) -> Result<RunningGateway, String> {
+    let listener = TcpListener::bind(("127.0.0.1", port))
+        .await
+        .map_err(|e| format!("Failed to bind LLM gateway on port {}: {}", port, e))?;
+
+    let (shutdown, mut shutdown_rx) = oneshot::channel::<()>();
+    let app = app.clone();
+    let server_token = token.clone();
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::select! {
+                _ = &mut shutdown_rx => break,
+                accepted = listener.accept() => match accepted {
+                    Ok((stream, _)) => {
+                        let app = app.clone();
+                        let token = server_token.clone();
+                        tauri::async_runtime::spawn(async move {
+                            if let Err(e) = handle_connection(stream, &app, &token, port).await {
+                                log::debug!("LLM gateway connection error: {}", e);
+                            }
+                        });
+                    }
+                    Err(e) => log::warn!("LLM gateway accept failed: {}", e),
+                },
+            }
+        }
+    });
+
+    log::info!("LLM gateway listening on 127.0.0.1:{}", port);
+    Ok(RunningGateway {
+        port,
+        token,
+        shutdown,
+    })
+}
+
+fn match_route(path: &str) -> Option<(GatewayRoute, &str)> {
+    [
+        (CLAUDE_PREFIX, GatewayRoute::ClaudeCode),
+        (CODEX_PREFIX, GatewayRoute::Codex),
+    ]
+    .into_iter()
+    .find_map(|(prefix, route)| {
+        let rest = path.strip_prefix(prefix)?;
+        (rest.is_empty() || rest.starts_with('/')).then_some((route, rest))
+    })
+}
+
+/// 接受 `Authorization: Bearer <token>`（Claude Code 的 AUTH_TOKEN、Codex）或 `x-api-key`
+fn is_authorized(request: &proxy::ProxyRequest, token: &str) -> bool {
+    let provided = request
+        .header("authorization")
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .or_else(|| request.header("x-api-key"));
+    local_http::token_matches(provided, token)
+}
+
+/// provider 的地址指向网关自身时会无限转发
+fn points_to_gateway(base_url: &str, port: u16) -> bool {
+    let Ok(url) = reqwest::Url::parse(base_url) else {
+        return false;
+    };
+    let local = matches!(
+        url.host_str(),
+        Some("127.0.0.1") | Some("localhost") | Some("[::1]") | Some("::1")
+    );
+    local && url.port_or_known_default() == Some(port)
+}
+
+async fn handle_connection(
+    mut stream: TcpStream,
+    app: &AppHandle,
+    token: &str,
+    port: u16,
+) -> Result<(), String> {
+    let request = match tokio::time::timeout(READ_TIMEOUT, proxy::read_request(&mut stream)).await {
+        Ok(Ok(request)) => request,
+        Ok(Err(e)) => return proxy::write_error(&mut stream, 400, &e).await,
+        Err(_) => return Err("Request timed out".to_string()),
+    };
+
+    let Some((route, suffix)) = match_route(&request.path) else {
+        return proxy::write_error(&mut stream, 404, "Not found").await;
+    };
+    if !is_authorized(&request, token) {
+        return proxy::write_error(&mut stream, 401, "Invalid gateway token").await;
+    }
+
+    let db_state = app.state::<DbState>();
+    let db = db_state.db();
+    let upstream = match route {
+        GatewayRoute::ClaudeCode => upstream::claude_upstream(&db).await,
+        GatewayRoute::Codex => upstream::codex_upstream(&db).await,
+    };
+    let upstream = match upstream {
+        Ok(upstream) if points_to_gateway(&upstream.base_url, port) => {
+            return proxy::write_error(
+                &mut stream,
+                502,
+                "The applied provider points at the gateway itself",
+            )
+            .await;
+        }
+        Ok(upstream) => upstream,
+        Err(e) => return proxy::write_error(&mut stream, 503, &e).await,
+    };
+
+    let client = match http_client::client_with_timeout(&db_state, UPSTREAM_TIMEOUT_SECS).await {
+        Ok(client) => client,
+        Err(e) => return proxy::write_error(&mut stream, 500, &e).await,
+    };
+    match upstream.protocol {
+        proxy::UpstreamProtocol::Native => {
+            proxy::forward(&client, &upstream, &request, suffix, &mut stream).await
+        }
+        proxy::UpstreamProtocol::OpenAiChat => {
+            anthropic_openai::forward(&client, &upstream, &request, suffix, &mut stream).await
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    #[test]
+    fn match_route_strips_prefix() {
+        assert_eq!(
+            match_route("/claude/v1/messages"),
+            Some((GatewayRoute::ClaudeCode, "/v1/messages"))
+        );
+        assert_eq!(
+            match_route("/codex/responses"),
+            Some((GatewayRoute::Codex, "/responses"))
+        );
+        assert_eq!(match_route("/claudex/v1/messages"), None);
+        assert!(points_to_gateway("http://localhost:15721/claude", 15721));
+        assert!(!points_to_gateway("https://api.anthropic.com", 15721));
+    }
+
+    /// 本地 mock 上游：记录收到的请求头，分两段返回 SSE
+    async fn spawn_mock_upstream() -> (std::net::SocketAddr, oneshot::Receiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let (tx, rx) = oneshot::channel();
+
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let request = proxy::read_request(&mut socket).await.unwrap();
+            let summary = format!(
+                "{} {} auth={} client_key={} body={}",
+                request.method,
+                request.path,
+                request.header("authorization").unwrap_or_default(),
+                request.header("x-api-key").unwrap_or("none"),
+                String::from_utf8_lossy(&request.body)
+            );
+            let _ = tx.send(summary);
+
+            socket
+                .write_all(b"HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nTransfer-Encoding: chunked\r\n\r\n")
+                .await
+                .unwrap();
+            for event in [
+                "event: ping\ndata: {}\n\n",
+                "event: message_stop\ndata: {}\n\n",
+            ] {
+                let chunk = format!("{:x}\r\n{}\r\n", event.len(), event);
+                socket.write_all(chunk.as_bytes()).await.unwrap();
+                socket.flush().await.unwrap();
+            }
+            socket.write_all(b"0\r\n\r\n").await.unwrap();
+            let _ = socket.shutdown().await;
+        });
+
+        (addr, rx)
+    }
+
+    #[tokio::test]
+    async fn forward_injects_provider_key_and_streams_response() {
+        let (addr, received) = spawn_mock_upstream().await;
+        let upstream = proxy::Upstream {
+            base_url: format!("http://{}", addr),
+            headers: vec![(
+                "authorization".to_string(),
+                "Bearer provider-key".to_string(),
+            )],
+            protocol: proxy::UpstreamProtocol::Native,
+        };
+
+        let (mut client_side, mut gateway_side) = tokio::io::duplex(64 * 1024);
+        client_side
+            .write_all(b"POST /claude/v1/messages?beta=true HTTP/1.1\r\nHost: 127.0.0.1\r\nx-api-key: gateway-token\r\nanthropic-version: 2023-06-01\r\nContent-Length: 9\r\n\r\n{\"a\":\"b\"}")
+            .await
+            .unwrap();
+        let request = proxy::read_request(&mut gateway_side).await.unwrap();
+        let (_, suffix) = match_route(&request.path).unwrap();
+        assert!(is_authorized(&request, "gateway-token"));
+
+        proxy::forward(
+            &reqwest::Client::new(),
+            &upstream,
+            &request,
+            suffix,
+            &mut gateway_side,
+        )
+        .await
+        .unwrap();
+        drop(gateway_side);
+
+        let summary = received.await.unwrap();
+        assert_eq!(
+            summary,
+            "POST /v1/messages auth=Bearer provider-key client_key=none body={\"a\":\"b\"}"
+        );
+
+        let mut response = String::new();
+        client_side.read_to_string(&mut response).await.unwrap();
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(response.contains("content-type: text/event-stream\r\n"));
+        assert!(!response.to_ascii_lowercase().contains("transfer-encoding"));
+        assert!(response.ends_with("event: ping\ndata: {}\n\nevent: message_stop\ndata: {}\n\n"));
+    }
+}
@@ -0,0 +1,317 @@
+//! 网关的 HTTP 层：读取客户端请求，转发到上游并原样回传响应
+//!
+//! 每个连接只处理一个请求（`Connection: close`），响应体不做缓冲，上游返回的每个分块
+//! 立即写回客户端，因此 SSE 流式输出可以直接透传。
+
+use serde_json::json;
+use tokio::io::{AsyncRead, AsyncWrite, AsyncWriteExt};
+
+use crate::local_http;
+
+/// 请求头上限
+const MAX_HEAD_BYTES: usize = 64 * 1024;
+/// 请求体上限（长上下文和图片可能很大）
+const MAX_BODY_BYTES: usize = 64 * 1024 * 1024;
+
+/// 不转发给上游的请求头：逐跳头部、由 reqwest 重新生成的头部，以及客户端凭据
+const SKIPPED_REQUEST_HEADERS: [&str; 11] = [
+    "host",
+    "connection",
+    "keep-alive",
+    "proxy-connection",
+    "transfer-encoding",
+    "content-length",
+    "te",
+    "upgrade",
+    "expect",
+    "authorization",
+    "x-api-key",
+];
+
+/// 不回传给客户端的响应头，响应体以关闭连接结束
+const SKIPPED_RESPONSE_HEADERS: [&str; 4] = [
+    "connection",
+    "keep-alive",
+    "transfer-encoding",
+    "content-length",
+];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProxyRequest {
+    pub method: String,
+    pub path: String,
+    pub query: Option<String>,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+impl ProxyRequest {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// 上游接口格式
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum UpstreamProtocol {
+    /// 与客户端相同，原样透传
+    Native,
+    /// Claude Code 请求需转换为 OpenAI Chat Completions（见 `anthropic_openai`）
+    OpenAiChat,
+}
+
+/// 上游地址与需要注入的请求头
+#[derive(Debug, Clone, PartialEq)]
+pub struct Upstream {
+    pub base_url: String,
+    pub headers: Vec<(String, String)>,
+    pub protocol: UpstreamProtocol,
+}
+
+impl Upstream {
+    /// `suffix` 为去掉网关前缀后的路径，直接拼接到 provider 的 base URL 后
+    pub fn url(&self, suffix: &str, query: Option<&str>) -> String {
+        let mut url = format!("{}{}", self.base_url.trim_end_matches('/'), suffix);
+        if let Some(query) = query.filter(|q| !q.is_empty()) {
+            url.push('?');
+            url.push_str(query);
+        }
+        url
+    }
+}
+
+pub async fn read_request<S: AsyncRead + Unpin>(stream: &mut S) -> Result<ProxyRequest, String> {
+    let (head, mut body) = local_http::read_head(stream, MAX_HEAD_BYTES).await?;
+    let mut lines = head.split("\r\n");
+    let mut parts = lines.next().unwrap_or_default().split_whitespace();
+    let (Some(method), Some(target)) = (parts.next(), parts.next()) else {
+        return Err("Malformed request line".to_string());
+    };
+    let (path, query) = match target.split_once('?') {
+        Some((path, query)) => (path.to_string(), Some(query.to_string())),
+        None => (target.to_string(), None),
+    };
+    let headers: Vec<(String, String)> = lines
+        .filter_map(|line| line.split_once(':'))
+        .map(|(name, value)| (name.trim().to_string(), value.trim().to_string()))
+        .collect();
+
+    let mut request = ProxyRequest {
+        method: method.to_ascii_uppercase(),
+        path,
+        query,
+        headers,
+        body: Vec::new(),
+    };
+
+    let chunked = request
+        .header("transfer-encoding")
+        .is_some_and(|v| v.to_ascii_lowercase().contains("chunked"));
+    if chunked {
+        request.body = loop {
+            if let Some(decoded) = decode_chunked(&body)? {
+                break decoded;
+            }
+            if body.len() > MAX_BODY_BYTES {
+                return Err("Request body too large".to_string());
+            }
+            local_http::read_more(stream, &mut body).await?;
+        };
+    } else {
+        let content_length: usize = match request.header("content-length") {
+            Some(value) => value
+                .parse()
+                .map_err(|_| "Invalid Content-Length".to_string())?,
+            None => 0,
+        };
+        if content_length > MAX_BODY_BYTES {
+            return Err("Request body too large".to_string());
+        }
+        request.body = local_http::read_body(stream, body, content_length).await?;
+    }
+
+    Ok(request)
+}
+
+fn find_subslice(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack.windows(needle.len()).position(|w| w == needle)
+}
+
+/// 解码 chunked 请求体，数据不完整时返回 None
+fn decode_chunked(data: &[u8]) -> Result<Option<Vec<u8>>, String> {
+    let mut decoded = Vec::new();
+    let mut pos = 0;
+    loop {
+        let Some(line_len) = find_subslice(&data[pos..], b"\r\n") else {
+            return Ok(None);
+        };
+        let size_line = String::from_utf8_lossy(&data[pos..pos + line_len]);
+        let size_str = size_line.split(';').next().unwrap_or_default().trim();
+        let size =
+            usize::from_str_radix(size_str, 16).map_err(|_| "Invalid chunk size".to_string())?;
+        pos += line_len + 2;
+
+        if size == 0 {
+            // 跳过 trailer，直到空行
+            loop {
+                let Some(line_len) = find_subslice(&data[pos..], b"\r\n") else {
+                    return Ok(None);
+                };
+                pos += line_len + 2;
+                if line_len == 0 {
+                    return Ok(Some(decoded));
+                }
+            }
+        }
+
+        if data.len() < pos + size + 2 {
+            return Ok(None);
+        }
+        decoded.extend_from_slice(&data[pos..pos + size]);
+        pos += size + 2;
+    }
+}
+
+/// 转发请求并把上游响应流式写回客户端
+pub async fn forward<S: AsyncWrite + Unpin>(
+    client: &reqwest::Client,
+    upstream: &Upstream,
+    request: &ProxyRequest,
+    suffix: &str,
+    stream: &mut S,
+) -> Result<(), String> {
+    let method = reqwest::Method::from_bytes(request.method.as_bytes())
+        .map_err(|_| format!("Invalid method: {}", request.method))?;
+    let url = upstream.url(suffix, request.query.as_deref());
+
+    let mut builder = client.request(method, &url);
+    for (name, value) in &request.headers {
+        if !SKIPPED_REQUEST_HEADERS
+            .iter()
+            .any(|h| name.eq_ignore_ascii_case(h))
+        {
+            builder = builder.header(name.as_str(), value.as_str());
+        }
+    }
+    for (name, value) in &upstream.headers {
+        builder = builder.header(name.as_str(), value.as_str());
+    }
+    if !request.body.is_empty() {
+        builder = builder.body(request.body.clone());
+    }
+
+    let mut response = match builder.send().await {
+        Ok(response) => response,
+        Err(e) => {
+            log::warn!("LLM gateway upstream request to {} failed: {}", url, e);
+            return write_error(stream, 502, &format!("Upstream request failed: {}", e)).await;
+        }
+    };
+
+    let status = response.status();
+    let mut head = format!(
+        "HTTP/1.1 {} {}\r\n",
+        status.as_u16(),
+        status.canonical_reason().unwrap_or("")
+    );
+    for (name, value) in response.headers() {
+        if SKIPPED_RESPONSE_HEADERS
+            .iter()
+            .any(|h| name.as_str().eq_ignore_ascii_case(h))
+        {
+            continue;
+        }
+        if let Ok(value) = value.to_str() {
+            head.push_str(&format!("{}: {}\r\n", name.as_str(), value));
+        }
+    }
+    head.push_str("Connection: close\r\n\r\n");
+    stream
+        .write_all(head.as_bytes())
+        .await
+        .map_err(|e| e.to_string())?;
+
+    loop {
+        match response.chunk().await {
+            Ok(Some(chunk)) => {
+                stream.write_all(&chunk).await.map_err(|e| e.to_string())?;
+                stream.flush().await.map_err(|e| e.to_string())?;
+            }
+            Ok(None) => break,
+            Err(e) => {
+                // 响应头已发出，只能直接断开
+                log::warn!("LLM gateway upstream stream from {} broke: {}", url, e);
+                break;
+            }
+        }
+    }
+    stream.shutdown().await.map_err(|e| e.to_string())
+}
+
+/// 返回 Anthropic / OpenAI 客户端都能显示的 JSON 错误
+pub async fn write_error<S: AsyncWrite + Unpin>(
+    stream: &mut S,
+    status: u16,
+    message: &str,
+) -> Result<(), String> {
+    let body = json!({
+        "type": "error",
+        "error": { "type": "gateway_error", "message": message },
+    });
+    write_json(stream, status, &body).await
+}
+
+/// 写出完整的 JSON 响应并关闭连接
+pub async fn write_json<S: AsyncWrite + Unpin>(
+    stream: &mut S,
+    status: u16,
+    body: &serde_json::Value,
+) -> Result<(), String> {
+    let reason = reqwest::StatusCode::from_u16(status)
+        .ok()
+        .and_then(|s| s.canonical_reason())
+        .unwrap_or("");
+    let body = body.to_string();
+    let response = format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        reason,
+        body.len(),
+        body
+    );
+    stream
+        .write_all(response.as_bytes())
+        .await
+        .map_err(|e| e.to_string())?;
+    stream.shutdown().await.map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_chunked_waits_for_terminating_chunk() {
+        assert_eq!(decode_chunked(b"5\r\nhello\r\n").unwrap(), None);
+        assert_eq!(
+            decode_chunked(b"5\r\nhello\r\n6;ext=1\r\n world\r\n0\r\n\r\n").unwrap(),
+            Some(b"hello world".to_vec())
+        );
+        assert!(decode_chunked(b"zz\r\n").is_err());
+    }
+
+    #[tokio::test]
+    async fn read_request_parses_head_and_body() {
+        let raw = b"POST /claude/v1/messages?beta=true HTTP/1.1\r\nHost: x\r\nContent-Length: 2\r\nx-api-key: k\r\n\r\n{}";
+        let mut reader = &raw[..];
+        let request = read_request(&mut reader).await.unwrap();
+        assert_eq!(request.method, "POST");
+        assert_eq!(request.path, "/claude/v1/messages");
+        assert_eq!(request.query.as_deref(), Some("beta=true"));
+        assert_eq!(request.header("X-API-KEY"), Some("k"));
+        assert_eq!(request.body, b"{}");
+    }
+}
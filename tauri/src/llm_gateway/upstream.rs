@@ -0,0 +1,347 @@
+//! 按当前应用的 provider 解析上游地址与凭据，以及把工具配置改为指向网关
+
+use serde_json::Value;
+use toml_edit::{value, DocumentMut, Item, Table};
+
+use super::proxy::{Upstream, UpstreamProtocol};
+use crate::coding::claude_code;
+use crate::coding::codex;
+use crate::secret_vault;
+
+const DEFAULT_ANTHROPIC_BASE_URL: &str = "https://api.anthropic.com";
+const DEFAULT_OPENAI_BASE_URL: &str = "https://api.openai.com/v1";
+
+/// provider 未声明 model_provider 时，config.toml 中写入的网关 provider 名称
+const GATEWAY_MODEL_PROVIDER: &str = "ai_toolbox_gateway";
+/// 网关改写的 model_providers 条目字段
+const GATEWAY_MANAGED_FIELDS: [&str; 4] = [
+    "base_url",
+    "requires_openai_auth",
+    "env_key",
+    "experimental_bearer_token",
+];
+
+fn non_empty_str(value: Option<&Value>) -> Option<String> {
+    value
+        .and_then(|v| v.as_str())
+        .map(|s| s.trim())
+        .filter(|s| !s.is_empty())
+        .map(String::from)
+}
+
+/// 当前应用的 Claude Code provider
+pub async fn claude_upstream(
+    db: &surrealdb::Surreal<surrealdb::engine::local::Db>,
+) -> Result<Upstream, String> {
+    let provider = claude_code::commands::load_applied_provider(db)
+        .await?
+        .ok_or_else(|| "No Claude Code provider is applied".to_string())?;
+    claude_upstream_from_config(&secret_vault::resolve_json_text(&provider.settings_config)?)
+}
+
+fn claude_upstream_from_config(settings_config: &str) -> Result<Upstream, String> {
+    let config: Value = serde_json::from_str(settings_config)
+        .map_err(|e| format!("Failed to parse provider config: {}", e))?;
+    let env = config.get("env");
+
+    let token = non_empty_str(env.and_then(|e| e.get("ANTHROPIC_AUTH_TOKEN")));
+    let api_key = non_empty_str(env.and_then(|e| e.get("ANTHROPIC_API_KEY")));
+
+    // OpenAI 格式：base URL 为 OpenAI base（含 /v1），密钥统一用 Bearer
+    if claude_code::uses_openai_chat_format(&config) {
+        let base_url = non_empty_str(env.and_then(|e| e.get("ANTHROPIC_BASE_URL")))
+            .unwrap_or_else(|| DEFAULT_OPENAI_BASE_URL.to_string());
+        let headers = token
+            .or(api_key)
+            .map(|key| vec![("authorization".to_string(), format!("Bearer {}", key))])
+            .unwrap_or_default();
+        return Ok(Upstream {
+            base_url,
+            headers,
+            protocol: UpstreamProtocol::OpenAiChat,
+        });
+    }
+
+    let base_url = non_empty_str(env.and_then(|e| e.get("ANTHROPIC_BASE_URL")))
+        .unwrap_or_else(|| DEFAULT_ANTHROPIC_BASE_URL.to_string());
+
+    // 与写入 settings.json 时一致：AUTH_TOKEN 优先，旧版本只有 API_KEY
+    let mut headers = Vec::new();
+    if let Some(token) = token {
+        headers.push(("authorization".to_string(), format!("Bearer {}", token)));
+    } else if let Some(key) = api_key {
+        headers.push(("x-api-key".to_string(), key));
+    }
+
+    Ok(Upstream {
+        base_url,
+        headers,
+        protocol: UpstreamProtocol::Native,
+    })
+}
+
+/// 当前应用的 Codex provider
+pub async fn codex_upstream(
+    db: &surrealdb::Surreal<surrealdb::engine::local::Db>,
+) -> Result<Upstream, String> {
+    let provider = codex::commands::load_applied_provider(db)
+        .await?
+        .ok_or_else(|| "No Codex provider is applied".to_string())?;
+    codex_upstream_from_config(&secret_vault::resolve_json_text(&provider.settings_config)?)
+}
+
+fn codex_upstream_from_config(settings_config: &str) -> Result<Upstream, String> {
+    let config: Value = serde_json::from_str(settings_config)
+        .map_err(|e| format!("Failed to parse provider config: {}", e))?;
+    let config_toml = config.get("config").and_then(|v| v.as_str()).unwrap_or("");
+    let table: toml::Table = toml::from_str(config_toml)
+        .map_err(|e| format!("Failed to parse provider config.toml: {}", e))?;
+
+    let model_provider = table
+        .get("model_provider")
+        .and_then(|v| v.as_str())
+        .and_then(|key| table.get("model_providers")?.get(key));
+
+    let base_url = model_provider
+        .and_then(|p| p.get("base_url"))
+        .and_then(|v| v.as_str())
+        .unwrap_or(DEFAULT_OPENAI_BASE_URL)
+        .to_string();
+
+    let mut headers = Vec::new();
+    let api_key = non_empty_str(config.get("auth").and_then(|a| a.get("OPENAI_API_KEY")))
+        .or_else(|| {
+            model_provider
+                .and_then(|p| p.get("experimental_bearer_token"))
+                .and_then(|v| v.as_str())
+                .map(String::from)
+        })
+        .or_else(|| {
+            // env_key 指定的环境变量（Codex 自己读取，网关运行在同一用户下）
+            model_provider
+                .and_then(|p| p.get("env_key"))
+                .and_then(|v| v.as_str())
+                .and_then(|name| std::env::var(name).ok())
+        });
+    if let Some(key) = api_key {
+        headers.push(("authorization".to_string(), format!("Bearer {}", key)));
+    }
+    if let Some(extra) = model_provider
+        .and_then(|p| p.get("http_headers"))
+        .and_then(|v| v.as_table())
+    {
+        for (name, value) in extra {
+            if let Some(value) = value.as_str() {
+                headers.push((name.clone(), value.to_string()));
+            }
+        }
+    }
+
+    Ok(Upstream {
+        base_url,
+        headers,
+        protocol: UpstreamProtocol::Native,
+    })
+}
+
+/// 把 Codex config.toml 的当前 model_provider 指向网关，凭据改由 auth.json 中的网关 token 提供
+pub fn route_codex_config(config_toml: &str, base_url: &str) -> Result<String, String> {
+    let mut doc: DocumentMut = config_toml
+        .parse()
+        .map_err(|e| format!("Failed to parse config.toml: {}", e))?;
+
+    let current = doc
+        .get("model_provider")
+        .and_then(|v| v.as_str())
+        .map(String::from)
+        .filter(|key| {
+            doc.get("model_providers")
+                .and_then(|p| p.get(key))
+                .is_some_and(|p| p.is_table_like())
+        });
+
+    if doc.get("model_providers").is_none() {
+        let mut providers = Table::new();
+        providers.set_implicit(true);
+        doc["model_providers"] = Item::Table(providers);
+    }
+
+    let key = match current {
+        Some(key) => key,
+        None => {
+            // 内置 OpenAI provider：新增一个同样走 Responses 接口的自定义 provider
+            let mut table = Table::new();
+            table["name"] = value("AI Toolbox Gateway");
+            table["wire_api"] = value("responses");
+            doc["model_providers"][GATEWAY_MODEL_PROVIDER] = Item::Table(table);
+            doc["model_provider"] = value(GATEWAY_MODEL_PROVIDER);
+            GATEWAY_MODEL_PROVIDER.to_string()
+        }
+    };
+
+    let provider = &mut doc["model_providers"][&key];
+    provider["base_url"] = value(base_url);
+    provider["requires_openai_auth"] = value(true);
+    if let Some(table) = provider.as_table_like_mut() {
+        table.remove("env_key");
+        table.remove("experimental_bearer_token");
+    }
+
+    Ok(doc.to_string())
+}
+
+/// 撤销 `route_codex_config` 的改写：网关管理的字段恢复为 `original`（应用网关前的配置）中的值，
+/// 用于把外部修改导入 provider 时不写入网关地址
+pub fn unroute_codex_config(doc: &mut DocumentMut, original: &DocumentMut) {
+    let Some(key) = doc
+        .get("model_provider")
+        .and_then(|v| v.as_str())
+        .map(String::from)
+    else {
+        return;
+    };
+
+    if key == GATEWAY_MODEL_PROVIDER {
+        match original.get("model_provider") {
+            Some(item) => doc["model_provider"] = item.clone(),
+            None => {
+                doc.remove("model_provider");
+            }
+        }
+        let now_empty = match doc
+            .get_mut("model_providers")
+            .and_then(|p| p.as_table_like_mut())
+        {
+            Some(providers) => {
+                providers.remove(GATEWAY_MODEL_PROVIDER);
+                providers.is_empty()
+            }
+            None => false,
+        };
+        if now_empty && original.get("model_providers").is_none() {
+            doc.remove("model_providers");
+        }
+        return;
+    }
+
+    let original_entry = original
+        .get("model_providers")
+        .and_then(|p| p.get(&key))
+        .and_then(|e| e.as_table_like());
+    let Some(entry) = doc
+        .get_mut("model_providers")
+        .and_then(|p| p.get_mut(&key))
+        .and_then(|e| e.as_table_like_mut())
+    else {
+        return;
+    };
+    for field in GATEWAY_MANAGED_FIELDS {
+        match original_entry.and_then(|o| o.get(field)) {
+            Some(item) => {
+                entry.insert(field, item.clone());
+            }
+            None => {
+                entry.remove(field);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn claude_upstream_prefers_auth_token() {
+        let upstream = claude_upstream_from_config(
+            r#"{"env":{"ANTHROPIC_BASE_URL":"https://relay.example.com","ANTHROPIC_AUTH_TOKEN":"t","ANTHROPIC_API_KEY":"k"}}"#,
+        )
+        .unwrap();
+        assert_eq!(upstream.base_url, "https://relay.example.com");
+        assert_eq!(
+            upstream.headers,
+            vec![("authorization".to_string(), "Bearer t".to_string())]
+        );
+        assert_eq!(upstream.protocol, UpstreamProtocol::Native);
+    }
+
+    #[test]
+    fn claude_upstream_openai_chat_keeps_v1_base_url() {
+        let upstream = claude_upstream_from_config(
+            r#"{"apiFormat":"openai_chat","env":{"ANTHROPIC_BASE_URL":"https://relay.example.com/v1","ANTHROPIC_API_KEY":"k"}}"#,
+        )
+        .unwrap();
+        assert_eq!(upstream.protocol, UpstreamProtocol::OpenAiChat);
+        assert_eq!(
+            upstream.url("/chat/completions", None),
+            "https://relay.example.com/v1/chat/completions"
+        );
+        assert_eq!(
+            upstream.headers,
+            vec![("authorization".to_string(), "Bearer k".to_string())]
+        );
+    }
+
+    #[test]
+    fn route_codex_config_rewrites_current_provider() {
+        let routed = route_codex_config(
+            "model = \"gpt-5\"\nmodel_provider = \"relay\"\n\n[model_providers.relay]\nname = \"relay\"\nbase_url = \"https://relay.example.com/v1\"\nwire_api = \"chat\"\nenv_key = \"RELAY_KEY\"\n",
+            "http://127.0.0.1:15721/codex",
+        )
+        .unwrap();
+        let doc: DocumentMut = routed.parse().unwrap();
+        let provider = &doc["model_providers"]["relay"];
+        assert_eq!(
+            provider["base_url"].as_str(),
+            Some("http://127.0.0.1:15721/codex")
+        );
+        assert_eq!(provider["wire_api"].as_str(), Some("chat"));
+        assert_eq!(provider["requires_openai_auth"].as_bool(), Some(true));
+        assert!(provider.get("env_key").is_none());
+    }
+
+    #[test]
+    fn route_codex_config_adds_provider_for_builtin_openai() {
+        let routed =
+            route_codex_config("model = \"gpt-5\"\n", "http://127.0.0.1:15721/codex").unwrap();
+        let doc: DocumentMut = routed.parse().unwrap();
+        assert_eq!(doc["model_provider"].as_str(), Some(GATEWAY_MODEL_PROVIDER));
+        assert_eq!(
+            doc["model_providers"][GATEWAY_MODEL_PROVIDER]["base_url"].as_str(),
+            Some("http://127.0.0.1:15721/codex")
+        );
+    }
+
+    #[test]
+    fn unroute_codex_config_restores_upstream_fields() {
+        let original = "model = \"gpt-5\"\nmodel_provider = \"relay\"\n\n[model_providers.relay]\nname = \"relay\"\nbase_url = \"https://relay.example.com/v1\"\nenv_key = \"RELAY_KEY\"\n";
+        let original_doc: DocumentMut = original.parse().unwrap();
+        let routed = route_codex_config(original, "http://127.0.0.1:15721/codex").unwrap();
+
+        // 外部修改了模型，网关字段保持不变
+        let mut doc: DocumentMut = routed.replace("gpt-5", "gpt-5-codex").parse().unwrap();
+        unroute_codex_config(&mut doc, &original_doc);
+
+        let provider = &doc["model_providers"]["relay"];
+        assert_eq!(doc["model"].as_str(), Some("gpt-5-codex"));
+        assert_eq!(
+            provider["base_url"].as_str(),
+            Some("https://relay.example.com/v1")
+        );
+        assert_eq!(provider["env_key"].as_str(), Some("RELAY_KEY"));
+        assert!(provider.get("requires_openai_auth").is_none());
+    }
+
+    #[test]
+    fn unroute_codex_config_drops_gateway_provider_for_builtin_openai() {
+        let original_doc: DocumentMut = "model = \"gpt-5\"\n".parse().unwrap();
+        let routed =
+            route_codex_config("model = \"gpt-5\"\n", "http://127.0.0.1:15721/codex").unwrap();
+        let mut doc: DocumentMut = routed.parse().unwrap();
+        unroute_codex_config(&mut doc, &original_doc);
+
+        assert!(doc.get("model_provider").is_none());
+        assert!(doc.get("model_providers").is_none());
+        assert_eq!(doc["model"].as_str(), Some("gpt-5"));
+    }
+}
@@ -0,0 +1,90 @@
+//! 本地 HTTP 服务（控制接口 `control_api` 与 LLM 网关 `llm_gateway`）共用的请求读取与 token 校验
+//!
+//! 两个服务都只监听 `127.0.0.1`、每个连接处理一个请求，只需要读取请求头与定长请求体，
+//! 不引入完整的 HTTP 服务端实现。
+
+use tokio::io::{AsyncRead, AsyncReadExt};
+
+const READ_CHUNK_BYTES: usize = 8192;
+
+/// 从连接读取一段数据追加到 `buffer`，连接已关闭时返回错误
+pub async fn read_more<S: AsyncRead + Unpin>(
+    stream: &mut S,
+    buffer: &mut Vec<u8>,
+) -> Result<(), String> {
+    let mut chunk = [0u8; READ_CHUNK_BYTES];
+    let n = stream.read(&mut chunk).await.map_err(|e| e.to_string())?;
+    if n == 0 {
+        return Err("Connection closed".to_string());
+    }
+    buffer.extend_from_slice(&chunk[..n]);
+    Ok(())
+}
+
+/// 读取到请求头结束（空行）为止，返回请求头文本与已经读到的请求体部分
+pub async fn read_head<S: AsyncRead + Unpin>(
+    stream: &mut S,
+    max_head_bytes: usize,
+) -> Result<(String, Vec<u8>), String> {
+    let mut buffer = Vec::new();
+    let head_end = loop {
+        if let Some(pos) = buffer.windows(4).position(|w| w == b"\r\n\r\n") {
+            break pos;
+        }
+        if buffer.len() > max_head_bytes {
+            return Err("Request header too large".to_string());
+        }
+        read_more(stream, &mut buffer).await?;
+    };
+
+    let body = buffer.split_off(head_end + 4);
+    buffer.truncate(head_end);
+    Ok((String::from_utf8_lossy(&buffer).to_string(), body))
+}
+
+/// 在 `read_head` 返回的部分请求体后继续读取，直到满 `content_length` 字节
+pub async fn read_body<S: AsyncRead + Unpin>(
+    stream: &mut S,
+    mut body: Vec<u8>,
+    content_length: usize,
+) -> Result<Vec<u8>, String> {
+    while body.len() < content_length {
+        read_more(stream, &mut body).await?;
+    }
+    body.truncate(content_length);
+    Ok(body)
+}
+
+/// 校验客户端提供的 token；定长比较，避免按前缀逐字节猜测 token
+pub fn token_matches(provided: Option<&str>, token: &str) -> bool {
+    let Some(provided) = provided else {
+        return false;
+    };
+    provided.len() == token.len()
+        && provided
+            .bytes()
+            .zip(token.bytes())
+            .fold(0u8, |acc, (a, b)| acc | (a ^ b))
+            == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn read_head_and_body_split_at_blank_line() {
+        let mut reader: &[u8] = b"POST /v1 HTTP/1.1\r\nContent-Length: 5\r\n\r\nhello extra";
+        let (head, body) = read_head(&mut reader, 1024).await.unwrap();
+        assert_eq!(head, "POST /v1 HTTP/1.1\r\nContent-Length: 5");
+        assert_eq!(read_body(&mut reader, body, 5).await.unwrap(), b"hello");
+    }
+
+    #[test]
+    fn token_matches_requires_exact_token() {
+        assert!(token_matches(Some("abc"), "abc"));
+        assert!(!token_matches(Some("abd"), "abc"));
+        assert!(!token_matches(Some("ab"), "abc"));
+        assert!(!token_matches(None, "abc"));
+    }
+}
@@ -0,0 +1,67 @@
+use serde::Serialize;
+
+use super::master_key;
+use super::migration::{seal_stored_secrets, SealReport};
+use super::{read_store, write_store, KeySource, STORE_LOCK};
+use crate::db::DbState;
+
+/// 保险库状态
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SecretVaultStatus {
+    /// 是否已生成主密钥
+    pub initialized: bool,
+    /// 主密钥保存方式：keyring | passphrase
+    pub key_source: Option<String>,
+    /// 主密钥当前是否可用
+    pub unlocked: bool,
+    /// 保险库中的密钥条目数
+    pub secret_count: usize,
+}
+
+/// 获取保险库状态（不会初始化保险库）
+#[tauri::command]
+pub fn get_secret_vault_status() -> Result<SecretVaultStatus, String> {
+    let _guard = STORE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut store = read_store()?;
+    let initialized = store.key_source.is_some();
+    let key_source = store.key_source.map(|source| match source {
+        KeySource::Keyring => "keyring".to_string(),
+        KeySource::Passphrase => "passphrase".to_string(),
+    });
+    let unlocked = initialized && master_key::master_key(&mut store, None).is_ok();
+
+    Ok(SecretVaultStatus {
+        initialized,
+        key_source,
+        unlocked,
+        secret_count: store.secrets.len(),
+    })
+}
+
+/// 用口令解锁保险库（钥匙串不可用时也用于初始化），随后迁移遗留的明文密钥
+#[tauri::command]
+pub async fn unlock_secret_vault(
+    state: tauri::State<'_, DbState>,
+    passphrase: String,
+) -> Result<SealReport, String> {
+    {
+        let _guard = STORE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let mut store = read_store()?;
+        let initialized = store.key_source.is_some();
+        master_key::unlock(&mut store, &passphrase)?;
+        if !initialized {
+            write_store(&store)?;
+        }
+    }
+
+    seal_stored_secrets(&state.db()).await
+}
+
+/// 立即把数据库中的明文密钥迁入保险库
+#[tauri::command]
+pub async fn migrate_secrets_to_vault(
+    state: tauri::State<'_, DbState>,
+) -> Result<SealReport, String> {
+    seal_stored_secrets(&state.db()).await
+}
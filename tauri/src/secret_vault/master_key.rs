@@ -0,0 +1,284 @@
+//! 保险库主密钥：优先保存在系统钥匙串，不可用时由口令派生的密钥包裹后写入保险库文件
+
+use std::sync::Mutex;
+
+use aes_gcm::aead::rand_core::RngCore;
+use aes_gcm::aead::OsRng;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+
+use super::{fingerprint, KeySource, VaultFile, ERR_VAULT_LOCKED};
+use crate::settings::backup::crypto;
+
+const KEYRING_SERVICE: &str = "ai-toolbox";
+const KEYRING_USER: &str = "secret-vault-master-key";
+
+/// 无钥匙串环境下提供保险库口令的环境变量
+pub const PASSPHRASE_ENV: &str = "AI_TOOLBOX_VAULT_PASSPHRASE";
+
+/// 已解出的主密钥（进程内缓存）
+static MASTER_KEY: Mutex<Option<[u8; 32]>> = Mutex::new(None);
+
+pub(super) fn cached() -> Option<[u8; 32]> {
+    *MASTER_KEY.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+pub(super) fn clear_cache() {
+    *MASTER_KEY.lock().unwrap_or_else(|e| e.into_inner()) = None;
+}
+
+fn cache(key: [u8; 32]) {
+    *MASTER_KEY.lock().unwrap_or_else(|e| e.into_inner()) = Some(key);
+}
+
+fn key_check(key: &[u8; 32]) -> String {
+    fingerprint(key, KEYRING_USER)
+}
+
+fn decode_key(encoded: &str) -> Result<[u8; 32], String> {
+    BASE64
+        .decode(encoded.trim())
+        .ok()
+        .and_then(|bytes| <[u8; 32]>::try_from(bytes.as_slice()).ok())
+        .ok_or_else(|| "保险库主密钥格式无效".to_string())
+}
+
+fn env_passphrase() -> Option<String> {
+    std::env::var(PASSPHRASE_ENV).ok().filter(|p| !p.is_empty())
+}
+
+// ============================================================================
+// OS keyring
+// ============================================================================
+
+fn keyring_entry() -> Result<keyring::Entry, String> {
+    keyring::Entry::new(KEYRING_SERVICE, KEYRING_USER)
+        .map_err(|e| format!("系统钥匙串不可用: {}", e))
+}
+
+fn keyring_load() -> Result<Option<[u8; 32]>, String> {
+    match keyring_entry()?.get_password() {
+        Ok(encoded) => decode_key(&encoded).map(Some),
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(e) => Err(format!("读取系统钥匙串失败: {}", e)),
+    }
+}
+
+/// 写入钥匙串并读回校验（部分后端在无会话时会静默丢弃）
+fn keyring_store(key: &[u8; 32]) -> Result<(), String> {
+    let entry = keyring_entry()?;
+    entry
+        .set_password(&BASE64.encode(key))
+        .map_err(|e| format!("写入系统钥匙串失败: {}", e))?;
+    match keyring_load()? {
+        Some(stored) if stored == *key => Ok(()),
+        _ => Err("系统钥匙串未保存主密钥".to_string()),
+    }
+}
+
+// ============================================================================
+// Passphrase wrapping
+// ============================================================================
+
+/// 包裹主密钥，沿用加密备份的 Argon2id + AES-256-GCM 格式
+fn wrap_key(key: &[u8; 32], passphrase: &str) -> Result<String, String> {
+    crypto::encrypt_backup(key, passphrase).map(|data| BASE64.encode(data))
+}
+
+fn unwrap_key(wrapped: &str, passphrase: &str) -> Result<[u8; 32], String> {
+    let data = BASE64
+        .decode(wrapped)
+        .map_err(|e| format!("保险库主密钥数据损坏: {}", e))?;
+    let key = crypto::decrypt_backup(&data, passphrase)
+        .map_err(|_| format!("{}: 保险库口令错误", ERR_VAULT_LOCKED))?;
+    <[u8; 32]>::try_from(key.as_slice()).map_err(|_| "保险库主密钥格式无效".to_string())
+}
+
+// ============================================================================
+// Master key
+// ============================================================================
+
+/// 取得主密钥；保险库尚未初始化时生成新的主密钥并写入 `store`（由调用方落盘）。
+///
+/// `passphrase` 为用户在设置页输入的口令，未提供时尝试 `AI_TOOLBOX_VAULT_PASSPHRASE`。
+pub(super) fn master_key(
+    store: &mut VaultFile,
+    passphrase: Option<&str>,
+) -> Result<[u8; 32], String> {
+    if let Some(key) = cached() {
+        return Ok(key);
+    }
+
+    let key = match store.key_source {
+        Some(KeySource::Keyring) => keyring_load()
+            .map_err(|e| format!("{}: {}", ERR_VAULT_LOCKED, e))?
+            .ok_or_else(|| format!("{}: 系统钥匙串中找不到保险库主密钥", ERR_VAULT_LOCKED))?,
+        Some(KeySource::Passphrase) => {
+            let wrapped = store
+                .wrapped_key
+                .as_deref()
+                .ok_or("保险库缺少被包裹的主密钥")?;
+            let passphrase = passphrase
+                .map(String::from)
+                .or_else(env_passphrase)
+                .ok_or_else(|| format!("{}: 请输入保险库口令解锁", ERR_VAULT_LOCKED))?;
+            unwrap_key(wrapped, &passphrase)?
+        }
+        None => return initialize(store, passphrase),
+    };
+
+    if store
+        .key_check
+        .as_deref()
+        .is_some_and(|check| check != key_check(&key))
+    {
+        return Err(format!(
+            "{}: 主密钥与保险库不匹配（保险库可能来自其他设备的备份）",
+            ERR_VAULT_LOCKED
+        ));
+    }
+
+    cache(key);
+    Ok(key)
+}
+
+/// 初始化保险库：用户显式给出口令时使用口令模式，否则优先系统钥匙串
+fn initialize(store: &mut VaultFile, passphrase: Option<&str>) -> Result<[u8; 32], String> {
+    let mut key = [0u8; 32];
+    OsRng.fill_bytes(&mut key);
+
+    let passphrase = match passphrase {
+        Some(p) => Some(p.to_string()),
+        None => match keyring_store(&key) {
+            Ok(()) => None,
+            Err(e) => {
+                log::info!("系统钥匙串不可用，改用口令保护保险库: {}", e);
+                Some(env_passphrase().ok_or_else(|| {
+                    format!(
+                        "{}: 系统钥匙串不可用，请设置保险库口令（或 {} 环境变量）",
+                        ERR_VAULT_LOCKED, PASSPHRASE_ENV
+                    )
+                })?)
+            }
+        },
+    };
+
+    match passphrase {
+        Some(p) => {
+            store.key_source = Some(KeySource::Passphrase);
+            store.wrapped_key = Some(wrap_key(&key, &p)?);
+        }
+        None => {
+            store.key_source = Some(KeySource::Keyring);
+            store.wrapped_key = None;
+        }
+    }
+    store.key_check = Some(key_check(&key));
+
+    cache(key);
+    Ok(key)
+}
+
+/// 用口令解锁（或以口令模式初始化）保险库
+pub(super) fn unlock(store: &mut VaultFile, passphrase: &str) -> Result<[u8; 32], String> {
+    if passphrase.is_empty() {
+        return Err("保险库口令不能为空".to_string());
+    }
+    if store.key_source == Some(KeySource::Keyring) {
+        return Err("保险库使用系统钥匙串保存主密钥，无需口令".to_string());
+    }
+    master_key(store, Some(passphrase))
+}
+
+/// 备份时导出主密钥：钥匙串模式下主密钥不在保险库文件中，以备份口令包裹后随备份保存
+pub(super) fn export_wrapped(key: &[u8; 32], passphrase: &str) -> Result<String, String> {
+    wrap_key(key, passphrase)
+}
+
+/// 恢复备份后导入随备份导出的主密钥：写入本机钥匙串，
+/// 钥匙串不可用时改为口令模式，由备份口令包裹主密钥
+pub(super) fn import_wrapped(
+    store: &mut VaultFile,
+    wrapped: &str,
+    passphrase: &str,
+) -> Result<(), String> {
+    let key = unwrap_key(wrapped, passphrase)?;
+    if store
+        .key_check
+        .as_deref()
+        .is_some_and(|check| check != key_check(&key))
+    {
+        return Err("备份中的主密钥与保险库不匹配".to_string());
+    }
+    // 口令模式的保险库文件自带被包裹的主密钥，无需导入
+    if store.key_source == Some(KeySource::Passphrase) {
+        return Ok(());
+    }
+
+    match keyring_store(&key) {
+        Ok(()) => {
+            store.key_source = Some(KeySource::Keyring);
+            store.wrapped_key = None;
+        }
+        Err(e) => {
+            log::info!("系统钥匙串不可用，改用备份口令保护保险库: {}", e);
+            store.key_source = Some(KeySource::Passphrase);
+            store.wrapped_key = Some(wrapped.to_string());
+        }
+    }
+    store.key_check = Some(key_check(&key));
+
+    cache(key);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_passphrase_wrapped_key_round_trip() {
+        let key = [3u8; 32];
+        let wrapped = wrap_key(&key, "correct horse").unwrap();
+        assert_eq!(unwrap_key(&wrapped, "correct horse").unwrap(), key);
+
+        let err = unwrap_key(&wrapped, "wrong").unwrap_err();
+        assert!(err.starts_with(ERR_VAULT_LOCKED));
+    }
+
+    #[test]
+    fn test_keyring_vault_restores_with_empty_keyring() {
+        // 模拟恢复到钥匙串为空的新设备
+        keyring::set_default_credential_builder(keyring::mock::default_credential_builder());
+
+        let key = [5u8; 32];
+        let ciphertext = super::super::encrypt_secret(&key, "entry", "sk-secret").unwrap();
+        let original = VaultFile {
+            key_source: Some(KeySource::Keyring),
+            wrapped_key: None,
+            key_check: Some(key_check(&key)),
+            secrets: Default::default(),
+        };
+
+        // 备份：保险库文件与以备份口令包裹的主密钥
+        let backup_vault = serde_json::to_string(&original).unwrap();
+        let exported = export_wrapped(&key, "backup pass").unwrap();
+
+        // 恢复：钥匙串中没有主密钥，保险库无法打开
+        let mut restored: VaultFile = serde_json::from_str(&backup_vault).unwrap();
+        clear_cache();
+        assert!(master_key(&mut restored, None).is_err());
+
+        assert!(import_wrapped(&mut restored, &exported, "wrong").is_err());
+        import_wrapped(&mut restored, &exported, "backup pass").unwrap();
+        assert_eq!(restored.key_source, Some(KeySource::Passphrase));
+
+        clear_cache();
+        let recovered = master_key(&mut restored, Some("backup pass")).unwrap();
+        assert_eq!(
+            super::super::decrypt_secret(&recovered, "entry", &ciphertext).unwrap(),
+            "sk-secret"
+        );
+        clear_cache();
+    }
+}
@@ -0,0 +1,133 @@
+//! 把数据库中遗留的明文密钥迁入保险库，并清理不再被任何记录引用的条目
+
+use std::collections::HashSet;
+
+use chrono::Local;
+use serde::Serialize;
+use serde_json::Value;
+
+use super::{
+    collect_ref_ids, ensure_unlocked, prune_unreferenced, seal_json_fields, CLAUDE_SECRET_FIELDS,
+    CODEX_SECRET_FIELDS, GEMINI_CLI_SECRET_FIELDS,
+};
+use crate::coding::ssh::adapter as ssh_adapter;
+use crate::coding::{db_extract_id, db_record_id};
+use crate::settings;
+
+type Db = surrealdb::Surreal<surrealdb::engine::local::Db>;
+
+/// 迁移结果
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SealReport {
+    /// 本次改写为引用的记录数
+    pub sealed_records: usize,
+    /// 清理掉的无引用条目数
+    pub pruned_secrets: usize,
+}
+
+/// 扫描所有保存密钥的记录：明文改写为保险库引用，随后清理无引用的条目。
+///
+/// 新增保存密钥的位置时需要同步加到这里，否则其引用会被当作无引用条目清理。
+pub async fn seal_stored_secrets(db: &Db) -> Result<SealReport, String> {
+    // 保险库不可用时直接返回，避免在无法加密的情况下清理条目
+    ensure_unlocked()?;
+
+    let scan_started = Local::now();
+    let mut report = SealReport::default();
+    let mut referenced = HashSet::new();
+
+    for (table, fields) in [
+        ("claude_provider", CLAUDE_SECRET_FIELDS),
+        ("codex_provider", CODEX_SECRET_FIELDS),
+        ("gemini_cli_provider", GEMINI_CLI_SECRET_FIELDS),
+    ] {
+        let records: Vec<Value> = db
+            .query(format!(
+                "SELECT type::string(id) as id, settings_config FROM {}",
+                table
+            ))
+            .await
+            .map_err(|e| format!("Failed to query {}: {}", table, e))?
+            .take(0)
+            .map_err(|e| format!("Failed to parse {}: {}", table, e))?;
+
+        for record in records {
+            let id = db_extract_id(&record);
+            let Some(config) = record.get("settings_config").and_then(|v| v.as_str()) else {
+                continue;
+            };
+            let sealed = seal_json_fields(&format!("{}/{}", table, id), config, fields);
+            if sealed != config {
+                db.query(format!(
+                    "UPDATE {} SET settings_config = $settings_config",
+                    db_record_id(table, &id)
+                ))
+                .bind(("settings_config", sealed.clone()))
+                .await
+                .map_err(|e| format!("Failed to update {} {}: {}", table, id, e))?;
+                report.sealed_records += 1;
+            }
+            collect_ref_ids(&sealed, &mut referenced);
+        }
+    }
+
+    // SSH 连接：adapter 写入时会密封 password / private_key_content / passphrase（含跳板机）
+    let records: Vec<Value> = db
+        .query("SELECT *, type::string(id) as id FROM ssh_connection")
+        .await
+        .map_err(|e| format!("Failed to query SSH connections: {}", e))?
+        .take(0)
+        .map_err(|e| format!("Failed to parse SSH connections: {}", e))?;
+    for record in records {
+        let conn = ssh_adapter::connection_from_db_value(record.clone());
+        let data = ssh_adapter::connection_to_db_value(&conn);
+        let changed = [
+            "password",
+            "private_key_content",
+            "passphrase",
+            "jump_hosts",
+        ]
+        .iter()
+        .any(|field| data.get(field) != record.get(field));
+        if changed {
+            db.query(format!(
+                "UPDATE {} SET password = $password, private_key_content = $private_key_content, passphrase = $passphrase, jump_hosts = $jump_hosts",
+                db_record_id("ssh_connection", &conn.id)
+            ))
+            .bind(("password", data["password"].clone()))
+            .bind(("private_key_content", data["private_key_content"].clone()))
+            .bind(("passphrase", data["passphrase"].clone()))
+            .bind(("jump_hosts", data["jump_hosts"].clone()))
+            .await
+            .map_err(|e| format!("Failed to update SSH connection {}: {}", conn.id, e))?;
+            report.sealed_records += 1;
+        }
+        collect_ref_ids(&data.to_string(), &mut referenced);
+    }
+
+    // 备份凭据与备份口令
+    let app_settings = settings::commands::load_settings(db).await?;
+    let data = settings::adapter::to_db_value(&app_settings);
+    let webdav_password = data["webdav"]["password"].clone();
+    let s3_secret_key = data["s3"]["secret_key"].clone();
+    let backup_passphrase = data["backup_passphrase"].clone();
+    if webdav_password != app_settings.webdav.password.as_str()
+        || s3_secret_key != app_settings.s3.secret_key.as_str()
+        || backup_passphrase != app_settings.backup_passphrase.as_str()
+    {
+        db.query(
+            "UPDATE settings:`app` SET webdav.password = $webdav_password, s3.secret_key = $s3_secret_key, backup_passphrase = $backup_passphrase",
+        )
+        .bind(("webdav_password", webdav_password))
+        .bind(("s3_secret_key", s3_secret_key))
+        .bind(("backup_passphrase", backup_passphrase))
+        .await
+        .map_err(|e| format!("Failed to update settings: {}", e))?;
+        report.sealed_records += 1;
+    }
+    collect_ref_ids(&data.to_string(), &mut referenced);
+
+    report.pruned_secrets = prune_unreferenced(&referenced, scan_started)?;
+    Ok(report)
+}
@@ -0,0 +1,450 @@
+//! 密钥保险库
+//!
+//! Provider API Key、SSH 密码 / 私钥 / 私钥口令、WebDAV 密码、S3 Secret Key 与备份口令不再以明文写入数据库：
+//! 记录中只保存 `aitb-vault://<id>` 形式的引用，明文用主密钥（AES-256-GCM）加密后存放在
+//! 应用数据目录的 `secret_vault.json` 中，仅在写入 CLI 配置文件、建立 SSH 连接或上传备份时解出。
+//!
+//! 主密钥优先保存在系统钥匙串（macOS Keychain / Windows 凭据管理器 / Secret Service）；
+//! 没有钥匙串的环境（如无桌面的 Linux）改用口令派生的密钥包裹主密钥，
+//! 口令来自 `AI_TOOLBOX_VAULT_PASSPHRASE` 环境变量或设置页的解锁操作。
+//! 钥匙串中的主密钥不会随保险库文件备份，因此加密备份会附带以备份口令包裹的主密钥，
+//! 恢复到其他设备或新的系统账户时导入（见 [`export_key_for_backup`]）。
+//!
+//! 托管配置文件的历史快照同样包含密钥，用 [`encrypt_text`] 以主密钥整体加密后保存。
+//!
+//! 写入数据库的 adapter 负责调用 [`seal`] 把明文换成引用；保险库不可用时保留明文，
+//! 启动时的 [`seal_stored_secrets`] 会补做迁移并清理不再被引用的条目。
+
+mod commands;
+mod master_key;
+mod migration;
+
+pub use commands::*;
+pub use migration::seal_stored_secrets;
+
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{Mutex, OnceLock};
+
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng, Payload};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use chrono::{DateTime, Local};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::Sha256;
+
+/// 引用前缀，后接 32 位十六进制 id
+pub const SECRET_REF_PREFIX: &str = "aitb-vault://";
+
+/// 保险库已初始化但主密钥不可用（需要解锁）时的错误前缀，前端据此提示解锁
+pub const ERR_VAULT_LOCKED: &str = "SECRET_VAULT_LOCKED";
+
+/// Claude Code provider `settings_config` 中的密钥字段（JSON Pointer）
+pub const CLAUDE_SECRET_FIELDS: &[&str] = &["/env/ANTHROPIC_AUTH_TOKEN", "/env/ANTHROPIC_API_KEY"];
+
+/// Codex provider `settings_config` 中的密钥字段（auth.json 部分）
+pub const CODEX_SECRET_FIELDS: &[&str] = &[
+    "/auth/OPENAI_API_KEY",
+    "/auth/tokens/access_token",
+    "/auth/tokens/refresh_token",
+    "/auth/tokens/id_token",
+];
+
+/// Gemini CLI provider `settings_config` 中的密钥字段（.env 部分）
+pub const GEMINI_CLI_SECRET_FIELDS: &[&str] = &["/env/GEMINI_API_KEY", "/env/GOOGLE_API_KEY"];
+
+pub(crate) const STORE_FILE_NAME: &str = "secret_vault.json";
+const REF_ID_LEN: usize = 32;
+const NONCE_LEN: usize = 12;
+
+static STORE_DIR: OnceLock<PathBuf> = OnceLock::new();
+/// 保护保险库文件的读-改-写
+static STORE_LOCK: Mutex<()> = Mutex::new(());
+
+/// Set the directory for the vault file (call once at startup)
+pub fn set_store_dir(dir: PathBuf) {
+    let _ = STORE_DIR.set(dir);
+}
+
+/// 保险库文件路径（未初始化时为 None）
+pub fn store_file_path() -> Option<PathBuf> {
+    STORE_DIR.get().map(|dir| dir.join(STORE_FILE_NAME))
+}
+
+// ============================================================================
+// Vault file
+// ============================================================================
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum KeySource {
+    /// 主密钥保存在系统钥匙串
+    Keyring,
+    /// 主密钥由口令派生的密钥包裹后保存在保险库文件中
+    Passphrase,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SecretEntry {
+    /// 首次存入时的来源描述，仅用于排查
+    label: String,
+    /// HMAC-SHA256(主密钥, 明文)，相同明文复用同一条目
+    fingerprint: String,
+    /// base64(nonce | 密文)
+    ciphertext: String,
+    created_at: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct VaultFile {
+    #[serde(default)]
+    key_source: Option<KeySource>,
+    /// 口令模式下被包裹的主密钥（与加密备份相同的 Argon2id + AES-256-GCM 格式）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    wrapped_key: Option<String>,
+    /// 主密钥校验值，用于识别钥匙串中的密钥与保险库不匹配（例如恢复了其他机器的备份）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    key_check: Option<String>,
+    #[serde(default)]
+    secrets: BTreeMap<String, SecretEntry>,
+}
+
+fn read_store() -> Result<VaultFile, String> {
+    let path = store_file_path().ok_or("密钥保险库目录未初始化")?;
+    match fs::read_to_string(&path) {
+        Ok(content) => {
+            serde_json::from_str(&content).map_err(|e| format!("解析密钥保险库失败: {}", e))
+        }
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(VaultFile::default()),
+        Err(e) => Err(format!("读取密钥保险库失败: {}", e)),
+    }
+}
+
+/// Atomic write: write to .tmp then rename
+fn write_store(store: &VaultFile) -> Result<(), String> {
+    let path = store_file_path().ok_or("密钥保险库目录未初始化")?;
+    let content =
+        serde_json::to_string_pretty(store).map_err(|e| format!("序列化密钥保险库失败: {}", e))?;
+    let tmp_path = path.with_extension("json.tmp");
+    fs::write(&tmp_path, content).map_err(|e| format!("写入密钥保险库失败: {}", e))?;
+    fs::rename(&tmp_path, &path).map_err(|e| format!("写入密钥保险库失败: {}", e))
+}
+
+/// 读取保险库并取得主密钥；保险库尚未初始化时会生成主密钥并落盘
+fn open_store() -> Result<(VaultFile, [u8; 32]), String> {
+    let mut store = read_store()?;
+    let initialized = store.key_source.is_some();
+    let key = master_key::master_key(&mut store, None)?;
+    if !initialized {
+        write_store(&store)?;
+    }
+    Ok((store, key))
+}
+
+// ============================================================================
+// Crypto helpers
+// ============================================================================
+
+fn fingerprint(key: &[u8; 32], plaintext: &str) -> String {
+    let mut mac = <Hmac<Sha256> as Mac>::new_from_slice(key).expect("HMAC accepts any key length");
+    mac.update(plaintext.as_bytes());
+    hex::encode(mac.finalize().into_bytes())
+}
+
+/// 加密单个密钥，条目 id 作为附加数据，防止密文在条目间被调换
+fn encrypt_secret(key: &[u8; 32], id: &str, plaintext: &str) -> Result<String, String> {
+    let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key));
+    let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+    let ciphertext = cipher
+        .encrypt(
+            &nonce,
+            Payload {
+                msg: plaintext.as_bytes(),
+                aad: id.as_bytes(),
+            },
+        )
+        .map_err(|_| "加密密钥失败".to_string())?;
+
+    let mut output = nonce.to_vec();
+    output.extend_from_slice(&ciphertext);
+    Ok(BASE64.encode(output))
+}
+
+fn decrypt_secret(key: &[u8; 32], id: &str, encoded: &str) -> Result<String, String> {
+    let data = BASE64
+        .decode(encoded)
+        .map_err(|e| format!("密钥 {} 数据损坏: {}", id, e))?;
+    if data.len() < NONCE_LEN {
+        return Err(format!("密钥 {} 数据损坏", id));
+    }
+
+    let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key));
+    let plaintext = cipher
+        .decrypt(
+            Nonce::from_slice(&data[..NONCE_LEN]),
+            Payload {
+                msg: &data[NONCE_LEN..],
+                aad: id.as_bytes(),
+            },
+        )
+        .map_err(|_| format!("解密密钥 {} 失败：主密钥不匹配或数据已损坏", id))?;
+    String::from_utf8(plaintext).map_err(|_| format!("密钥 {} 不是有效的 UTF-8 文本", id))
+}
+
+// ============================================================================
+// References
+// ============================================================================
+
+/// 提取引用中的条目 id；不是引用时返回 None
+fn ref_id(value: &str) -> Option<&str> {
+    let id = value.trim().strip_prefix(SECRET_REF_PREFIX)?;
+    (id.len() == REF_ID_LEN && id.bytes().all(|b| b.is_ascii_hexdigit())).then_some(id)
+}
+
+/// 判断字段值是否为保险库引用
+pub fn is_secret_ref(value: &str) -> bool {
+    ref_id(value).is_some()
+}
+
+/// 收集文本中出现的所有引用 id
+fn collect_ref_ids(text: &str, ids: &mut HashSet<String>) {
+    for (pos, _) in text.match_indices(SECRET_REF_PREFIX) {
+        let start = pos + SECRET_REF_PREFIX.len();
+        if let Some(id) = text.get(start..start + REF_ID_LEN) {
+            if id.bytes().all(|b| b.is_ascii_hexdigit()) {
+                ids.insert(id.to_string());
+            }
+        }
+    }
+}
+
+/// 把明文存入保险库并返回引用；空值与已经是引用的值原样返回。
+///
+/// 保险库不可用（未解锁、钥匙串不可用等）时保留明文并记录警告，
+/// 下次启动的迁移会再次尝试。
+pub fn seal(label: &str, value: &str) -> String {
+    if value.is_empty() || is_secret_ref(value) || STORE_DIR.get().is_none() {
+        return value.to_string();
+    }
+
+    match try_seal(label, value) {
+        Ok(reference) => reference,
+        Err(e) => {
+            log::warn!("{} 未能存入密钥保险库，暂以明文保存: {}", label, e);
+            value.to_string()
+        }
+    }
+}
+
+fn try_seal(label: &str, value: &str) -> Result<String, String> {
+    let _guard = STORE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let (mut store, key) = open_store()?;
+
+    let fp = fingerprint(&key, value);
+    if let Some((id, _)) = store.secrets.iter().find(|(_, e)| e.fingerprint == fp) {
+        return Ok(format!("{}{}", SECRET_REF_PREFIX, id));
+    }
+
+    let id = crate::coding::db_new_id();
+    let entry = SecretEntry {
+        label: label.to_string(),
+        fingerprint: fp,
+        ciphertext: encrypt_secret(&key, &id, value)?,
+        created_at: Local::now().to_rfc3339(),
+    };
+    store.secrets.insert(id.clone(), entry);
+    write_store(&store)?;
+    Ok(format!("{}{}", SECRET_REF_PREFIX, id))
+}
+
+/// 解析单个字段：引用返回明文，其余值原样返回
+pub fn resolve(value: &str) -> Result<String, String> {
+    let Some(id) = ref_id(value) else {
+        return Ok(value.to_string());
+    };
+
+    let _guard = STORE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let (store, key) = open_store()?;
+    let entry = store
+        .secrets
+        .get(id)
+        .ok_or_else(|| format!("密钥保险库中找不到 {}{}", SECRET_REF_PREFIX, id))?;
+    decrypt_secret(&key, id, &entry.ciphertext)
+}
+
+/// 解析 JSON 中所有字符串形式的引用
+pub fn resolve_json(value: &mut Value) -> Result<(), String> {
+    match value {
+        Value::String(s) if is_secret_ref(s) => *s = resolve(s)?,
+        Value::Array(items) => {
+            for item in items {
+                resolve_json(item)?;
+            }
+        }
+        Value::Object(map) => {
+            for item in map.values_mut() {
+                resolve_json(item)?;
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// 解析 JSON 文本（如 provider 的 settings_config）中的引用；不含引用时原样返回
+pub fn resolve_json_text(text: &str) -> Result<String, String> {
+    if !text.contains(SECRET_REF_PREFIX) {
+        return Ok(text.to_string());
+    }
+    let mut value: Value = serde_json::from_str(text)
+        .map_err(|e| format!("Failed to parse provider config: {}", e))?;
+    resolve_json(&mut value)?;
+    serde_json::to_string(&value).map_err(|e| format!("Failed to serialize provider config: {}", e))
+}
+
+/// 把 JSON 文本中指定字段（JSON Pointer）的明文存入保险库；没有需要处理的字段时原样返回
+pub fn seal_json_fields(label: &str, text: &str, pointers: &[&str]) -> String {
+    let Ok(mut value) = serde_json::from_str::<Value>(text) else {
+        return text.to_string();
+    };
+
+    let mut changed = false;
+    for pointer in pointers {
+        if let Some(Value::String(field)) = value.pointer_mut(pointer) {
+            let sealed = seal(&format!("{}{}", label, pointer), field);
+            if sealed != *field {
+                *field = sealed;
+                changed = true;
+            }
+        }
+    }
+
+    if !changed {
+        return text.to_string();
+    }
+    serde_json::to_string(&value).unwrap_or_else(|_| text.to_string())
+}
+
+/// 用主密钥加密不经引用保存的文本（如配置历史快照），`context` 作为附加数据绑定密文所属位置
+pub fn encrypt_text(context: &str, plaintext: &str) -> Result<String, String> {
+    let _guard = STORE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let (_, key) = open_store()?;
+    encrypt_secret(&key, context, plaintext)
+}
+
+/// 解密 [`encrypt_text`] 的结果，`context` 需与加密时一致
+pub fn decrypt_text(context: &str, encoded: &str) -> Result<String, String> {
+    let _guard = STORE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let (_, key) = open_store()?;
+    decrypt_secret(&key, context, encoded)
+}
+
+/// 确认保险库可用（必要时初始化主密钥）
+fn ensure_unlocked() -> Result<(), String> {
+    let _guard = STORE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    open_store().map(|_| ())
+}
+
+/// 删除未被 `referenced` 引用的条目，返回删除数量。
+/// 扫描开始后新建的条目可能属于尚未写入数据库的记录，不在清理范围内。
+fn prune_unreferenced(
+    referenced: &HashSet<String>,
+    created_before: DateTime<Local>,
+) -> Result<usize, String> {
+    let _guard = STORE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut store = read_store()?;
+    let before = store.secrets.len();
+    store.secrets.retain(|id, entry| {
+        referenced.contains(id)
+            || !matches!(
+                DateTime::parse_from_rfc3339(&entry.created_at),
+                Ok(created) if created < created_before
+            )
+    });
+    let pruned = before - store.secrets.len();
+    if pruned > 0 {
+        write_store(&store)?;
+    }
+    Ok(pruned)
+}
+
+/// 恢复备份替换保险库文件后调用：丢弃缓存的主密钥，下次使用时重新校验
+pub fn reset_cached_key() {
+    master_key::clear_cache();
+}
+
+/// 备份时导出以备份口令包裹的主密钥；只有钥匙串模式需要导出（口令模式的保险库文件自带主密钥），
+/// 保险库未初始化或不是钥匙串模式时返回 None
+pub fn export_key_for_backup(passphrase: &str) -> Result<Option<String>, String> {
+    let _guard = STORE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut store = read_store()?;
+    if store.key_source != Some(KeySource::Keyring) {
+        return Ok(None);
+    }
+    let key = master_key::master_key(&mut store, None)?;
+    master_key::export_wrapped(&key, passphrase).map(Some)
+}
+
+/// 恢复备份后导入随备份导出的主密钥，使钥匙串模式的保险库在其他设备上也能打开
+pub fn import_key_from_backup(wrapped: &str, passphrase: &str) -> Result<(), String> {
+    let _guard = STORE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut store = read_store()?;
+    master_key::clear_cache();
+    master_key::import_wrapped(&mut store, wrapped, passphrase)?;
+    write_store(&store)
+}
+
+/// 钥匙串模式且已保存密钥时，备份必须设置口令才能随备份导出主密钥
+pub fn backup_requires_passphrase() -> bool {
+    let _guard = STORE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    read_store().is_ok_and(|store| {
+        store.key_source == Some(KeySource::Keyring) && !store.secrets.is_empty()
+    })
+}
+
+/// 恢复不带主密钥的备份后检查钥匙串中的主密钥能否打开恢复的保险库（口令模式的保险库自带主密钥，不检查）
+pub fn check_keyring_key() -> Result<(), String> {
+    let _guard = STORE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut store = read_store()?;
+    if store.key_source != Some(KeySource::Keyring) || store.secrets.is_empty() {
+        return Ok(());
+    }
+    master_key::clear_cache();
+    master_key::master_key(&mut store, None).map(|_| ())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_secret_round_trip_is_bound_to_id() {
+        let key = [7u8; 32];
+        let sealed = encrypt_secret(&key, "a", "sk-test").unwrap();
+        assert_eq!(decrypt_secret(&key, "a", &sealed).unwrap(), "sk-test");
+        assert!(decrypt_secret(&key, "b", &sealed).is_err());
+        assert!(decrypt_secret(&[8u8; 32], "a", &sealed).is_err());
+    }
+
+    #[test]
+    fn test_ref_detection_and_collection() {
+        let id = "0123456789abcdef0123456789abcdef";
+        let reference = format!("{}{}", SECRET_REF_PREFIX, id);
+        assert!(is_secret_ref(&reference));
+        assert!(!is_secret_ref("sk-plain"));
+        assert!(!is_secret_ref(&format!("{}short", SECRET_REF_PREFIX)));
+
+        let mut ids = HashSet::new();
+        collect_ref_ids(
+            &format!(r#"{{"env":{{"ANTHROPIC_AUTH_TOKEN":"{}"}}}}"#, reference),
+            &mut ids,
+        );
+        assert_eq!(ids.into_iter().collect::<Vec<_>>(), vec![id.to_string()]);
+    }
+}
@@ -7,6 +7,8 @@ use super::types::{AppSettings, S3Config, WebDAVConfig};
  */
 use serde_json::{json, Value};
 
+use crate::secret_vault;
+
 /// Convert database JSON Value to AppSettings with fault tolerance
 /// Missing fields will use default values, never panics
 pub fn from_db_value(value: Value) -> AppSettings {
@@ -30,20 +32,50 @@ pub fn from_db_value(value: Value) -> AppSettings {
         auto_backup_interval_days: get_u32(&value, "auto_backup_interval_days", 7),
         auto_backup_max_keep: get_u32(&value, "auto_backup_max_keep", 10),
         last_auto_backup_time: get_opt_str(&value, "last_auto_backup_time"),
+        backup_passphrase: get_str(&value, "backup_passphrase", ""),
         auto_check_update: get_bool(&value, "auto_check_update", true),
         visible_tabs: get_string_array(
             &value,
             "visible_tabs",
-            &["opencode", "claudecode", "codex", "openclaw", "ssh", "wsl"],
+            &[
+                "opencode",
+                "claudecode",
+                "codex",
+                "geminicli",
+                "openclaw",
+                "ssh",
+                "wsl",
+            ],
         ),
+        control_api_enabled: get_bool(&value, "control_api_enabled", false),
+        health_monitor_enabled: get_bool(&value, "health_monitor_enabled", false),
+        health_monitor_interval_minutes: get_u32(&value, "health_monitor_interval_minutes", 5),
+        health_monitor_failure_threshold: get_u32(&value, "health_monitor_failure_threshold", 3),
+        health_monitor_auto_failover: get_bool(&value, "health_monitor_auto_failover", false),
+        llm_gateway_enabled: get_bool(&value, "llm_gateway_enabled", false),
+        llm_gateway_port: get_u32(&value, "llm_gateway_port", 15721),
+        llm_gateway_token: get_str(&value, "llm_gateway_token", ""),
+        balance_monitor_enabled: get_bool(&value, "balance_monitor_enabled", false),
+        balance_monitor_interval_minutes: get_u32(&value, "balance_monitor_interval_minutes", 60),
+        balance_alert_threshold_usd: get_f64(&value, "balance_alert_threshold_usd", 5.0),
+        config_history_max_per_file: get_u32(&value, "config_history_max_per_file", 20),
+        config_history_max_age_days: get_u32(&value, "config_history_max_age_days", 30),
     }
 }
 
 /// Convert AppSettings to database JSON Value
+/// Backup credentials and the backup passphrase are moved into the secret vault and stored as references
 pub fn to_db_value(settings: &AppSettings) -> Value {
+    let mut settings = settings.clone();
+    settings.webdav.password =
+        secret_vault::seal("settings/webdav.password", &settings.webdav.password);
+    settings.s3.secret_key = secret_vault::seal("settings/s3.secret_key", &settings.s3.secret_key);
+    settings.backup_passphrase =
+        secret_vault::seal("settings/backup_passphrase", &settings.backup_passphrase);
+
     // Use serde to serialize the entire structure
     // This ensures all types are properly converted
-    serde_json::to_value(settings).unwrap_or_else(|e| {
+    serde_json::to_value(&settings).unwrap_or_else(|e| {
         eprintln!("Failed to serialize settings: {}", e);
         json!({})
     })
@@ -75,6 +107,10 @@ fn get_u32(value: &Value, key: &str, default: u32) -> u32 {
         .unwrap_or(default)
 }
 
+fn get_f64(value: &Value, key: &str, default: f64) -> f64 {
+    value.get(key).and_then(|v| v.as_f64()).unwrap_or(default)
+}
+
 fn get_string_array(value: &Value, key: &str, defaults: &[&str]) -> Vec<String> {
     value
         .get(key)
@@ -3,7 +3,9 @@ use log::{error, info, warn};
 use std::time::Duration;
 use tauri::{Emitter, Manager};
 
-use super::utils::{create_backup_zip, get_db_path};
+use super::crypto::encrypt_if_enabled;
+use super::s3::{delete_s3_backup_internal, list_s3_backups_internal, upload_s3_backup_internal};
+use super::utils::{create_backup_zip, get_db_path, get_skills_dir};
 use super::webdav::{delete_webdav_backup_internal, list_webdav_backups_internal};
 use crate::db::DbState;
 use crate::http_client;
@@ -88,6 +90,45 @@ async fn check_and_perform_backup(app_handle: &tauri::AppHandle) -> Result<(), S
 
             Ok(())
         }
+        "s3" => {
+            if settings.s3.bucket.is_empty() {
+                return Ok(());
+            }
+
+            info!("Auto-backup is due, performing S3 backup...");
+
+            match perform_s3_backup(app_handle, &db_state, &settings).await {
+                Ok(()) => {
+                    info!("Auto-backup (S3) completed successfully");
+
+                    let now = Utc::now().to_rfc3339();
+                    update_last_auto_backup_time(&db_state, &now).await?;
+                    let _ = app_handle.emit("auto-backup-completed", &now);
+
+                    if settings.auto_backup_max_keep > 0 {
+                        if let Err(e) = cleanup_old_s3_backups(
+                            &db_state,
+                            &settings.s3,
+                            settings.auto_backup_max_keep,
+                        )
+                        .await
+                        {
+                            warn!("Auto-backup S3 cleanup failed: {}", e);
+                        }
+                    }
+                }
+                Err(e) => {
+                    warn!("Auto-backup (S3) failed: {}", e);
+
+                    // Update last_auto_backup_time even on failure to prevent retry every 10 minutes
+                    let now = Utc::now().to_rfc3339();
+                    update_last_auto_backup_time(&db_state, &now).await?;
+                    let _ = app_handle.emit("auto-backup-failed", &e);
+                }
+            }
+
+            Ok(())
+        }
         "local" => {
             if settings.local_backup_path.is_empty() {
                 return Ok(());
@@ -171,7 +212,9 @@ async fn perform_webdav_backup(
     settings: &crate::settings::types::AppSettings,
 ) -> Result<(), String> {
     let db_path = get_db_path(app_handle)?;
-    let zip_data = create_backup_zip(app_handle, &db_path)?;
+    let passphrase = crate::secret_vault::resolve(&settings.backup_passphrase)?;
+    let zip_data = create_backup_zip(&db_path, &get_skills_dir(app_handle)?, Some(&passphrase))?;
+    let zip_data = encrypt_if_enabled(zip_data, Some(&passphrase))?;
 
     let timestamp = Local::now().format("%Y%m%d-%H%M%S");
     let host = settings.webdav.host_label.trim();
@@ -198,9 +241,10 @@ async fn perform_webdav_backup(
             e
         })?;
 
+    let password = crate::secret_vault::resolve(&settings.webdav.password)?;
     let response = client
         .put(&full_url)
-        .basic_auth(&settings.webdav.username, Some(&settings.webdav.password))
+        .basic_auth(&settings.webdav.username, Some(&password))
         .body(zip_data)
         .send()
         .await
@@ -216,13 +260,34 @@ async fn perform_webdav_backup(
     }
 }
 
+/// Perform an S3 backup
+async fn perform_s3_backup(
+    app_handle: &tauri::AppHandle,
+    db_state: &DbState,
+    settings: &crate::settings::types::AppSettings,
+) -> Result<(), String> {
+    let db_path = get_db_path(app_handle)?;
+    let passphrase = crate::secret_vault::resolve(&settings.backup_passphrase)?;
+    let zip_data = create_backup_zip(&db_path, &get_skills_dir(app_handle)?, Some(&passphrase))?;
+    let zip_data = encrypt_if_enabled(zip_data, Some(&passphrase))?;
+
+    let timestamp = Local::now().format("%Y%m%d-%H%M%S");
+    let backup_filename = format!("ai-toolbox-backup-{}.zip", timestamp);
+
+    let url = upload_s3_backup_internal(db_state, &settings.s3, &backup_filename, zip_data).await?;
+    info!("Auto-backup: uploaded to {}", url);
+    Ok(())
+}
+
 /// Perform a local backup
 async fn perform_local_backup(
     app_handle: &tauri::AppHandle,
     settings: &crate::settings::types::AppSettings,
 ) -> Result<(), String> {
     let db_path = get_db_path(app_handle)?;
-    let zip_data = create_backup_zip(app_handle, &db_path)?;
+    let passphrase = crate::secret_vault::resolve(&settings.backup_passphrase)?;
+    let zip_data = create_backup_zip(&db_path, &get_skills_dir(app_handle)?, Some(&passphrase))?;
+    let zip_data = encrypt_if_enabled(zip_data, Some(&passphrase))?;
 
     let backup_dir = std::path::Path::new(&settings.local_backup_path);
     if !backup_dir.exists() {
@@ -294,6 +359,33 @@ async fn cleanup_old_webdav_backups(
     Ok(())
 }
 
+/// Cleanup old S3 backups, keeping only the latest `max_keep` files
+async fn cleanup_old_s3_backups(
+    db_state: &DbState,
+    config: &crate::settings::types::S3Config,
+    max_keep: u32,
+) -> Result<(), String> {
+    let backups = list_s3_backups_internal(db_state, config).await?;
+
+    if backups.len() <= max_keep as usize {
+        return Ok(());
+    }
+
+    let to_delete = &backups[max_keep as usize..];
+    info!(
+        "Auto-backup cleanup: deleting {} old S3 backup(s)",
+        to_delete.len()
+    );
+
+    for backup in to_delete {
+        if let Err(e) = delete_s3_backup_internal(db_state, config, &backup.filename).await {
+            warn!("Failed to delete old backup {}: {}", backup.filename, e);
+        }
+    }
+
+    Ok(())
+}
+
 /// Cleanup old local backups, keeping only the latest `max_keep` files
 fn cleanup_old_local_backups(backup_path: &str, max_keep: u32) -> Result<(), String> {
     let backup_dir = std::path::Path::new(backup_path);
@@ -0,0 +1,237 @@
+//! Optional passphrase encryption for backup archives.
+//!
+//! Encrypted archives keep the `.zip` filename so listing/cleanup logic stays
+//! unchanged; they are detected by the magic header instead.
+//!
+//! Layout (all integers little-endian):
+//! `MAGIC (8) | m_cost (4) | t_cost (4) | p_cost (4) | salt (16) | nonce (12) | ciphertext + tag`
+//!
+//! The key is derived with Argon2id and the payload is sealed with AES-256-GCM.
+//! The whole header is bound as associated data, so tampering with the KDF
+//! parameters is detected the same way as a wrong passphrase.
+
+use aes_gcm::aead::rand_core::RngCore;
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng, Payload};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use argon2::{Algorithm, Argon2, Params, Version};
+
+const MAGIC: &[u8; 8] = b"AITBENC1";
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+const HEADER_LEN: usize = MAGIC.len() + 12 + SALT_LEN + NONCE_LEN;
+
+/// Argon2id defaults (OWASP recommendation: 19 MiB, 2 iterations, 1 lane)
+const DEFAULT_M_COST: u32 = 19 * 1024;
+const DEFAULT_T_COST: u32 = 2;
+const DEFAULT_P_COST: u32 = 1;
+
+/// Upper bounds accepted from a header, so a crafted file can't request gigabytes of memory
+/// or pin the CPU for hours
+const MAX_M_COST: u32 = 1024 * 1024;
+const MAX_T_COST: u32 = 16;
+const MAX_P_COST: u32 = 16;
+
+/// Error prefix returned when an encrypted archive is restored without a passphrase.
+/// The frontend matches on this prefix to prompt the user.
+pub const ERR_PASSPHRASE_REQUIRED: &str = "BACKUP_PASSPHRASE_REQUIRED";
+/// Error prefix returned when decryption fails (wrong passphrase or corrupted archive)
+pub const ERR_WRONG_PASSPHRASE: &str = "BACKUP_WRONG_PASSPHRASE";
+
+/// Check whether the data is an encrypted backup archive
+pub fn is_encrypted_backup(data: &[u8]) -> bool {
+    data.len() >= MAGIC.len() && &data[..MAGIC.len()] == MAGIC
+}
+
+fn derive_key(
+    passphrase: &str,
+    salt: &[u8],
+    m_cost: u32,
+    t_cost: u32,
+    p_cost: u32,
+) -> Result<[u8; 32], String> {
+    let params = Params::new(m_cost, t_cost, p_cost, Some(32))
+        .map_err(|e| format!("Invalid key derivation parameters: {}", e))?;
+    let argon2 = Argon2::new(Algorithm::Argon2id, Version::V0x13, params);
+
+    let mut key = [0u8; 32];
+    argon2
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+        .map_err(|e| format!("Failed to derive encryption key: {}", e))?;
+    Ok(key)
+}
+
+/// Encrypt backup data with the given passphrase
+pub fn encrypt_backup(data: &[u8], passphrase: &str) -> Result<Vec<u8>, String> {
+    if passphrase.is_empty() {
+        return Err("Backup passphrase must not be empty".to_string());
+    }
+
+    let mut salt = [0u8; SALT_LEN];
+    OsRng.fill_bytes(&mut salt);
+    let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+
+    let key = derive_key(
+        passphrase,
+        &salt,
+        DEFAULT_M_COST,
+        DEFAULT_T_COST,
+        DEFAULT_P_COST,
+    )?;
+
+    let mut header = Vec::with_capacity(HEADER_LEN);
+    header.extend_from_slice(MAGIC);
+    header.extend_from_slice(&DEFAULT_M_COST.to_le_bytes());
+    header.extend_from_slice(&DEFAULT_T_COST.to_le_bytes());
+    header.extend_from_slice(&DEFAULT_P_COST.to_le_bytes());
+    header.extend_from_slice(&salt);
+    header.extend_from_slice(&nonce);
+
+    let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&key));
+    let ciphertext = cipher
+        .encrypt(
+            &nonce,
+            Payload {
+                msg: data,
+                aad: &header,
+            },
+        )
+        .map_err(|_| "Failed to encrypt backup".to_string())?;
+
+    let mut output = header;
+    output.extend_from_slice(&ciphertext);
+    Ok(output)
+}
+
+/// Decrypt an encrypted backup archive
+pub fn decrypt_backup(data: &[u8], passphrase: &str) -> Result<Vec<u8>, String> {
+    if !is_encrypted_backup(data) || data.len() < HEADER_LEN {
+        return Err("Not an encrypted backup archive".to_string());
+    }
+
+    let read_u32 = |offset: usize| {
+        let mut buf = [0u8; 4];
+        buf.copy_from_slice(&data[offset..offset + 4]);
+        u32::from_le_bytes(buf)
+    };
+    let m_cost = read_u32(MAGIC.len());
+    let t_cost = read_u32(MAGIC.len() + 4);
+    let p_cost = read_u32(MAGIC.len() + 8);
+    if m_cost > MAX_M_COST || t_cost > MAX_T_COST || p_cost > MAX_P_COST {
+        return Err(format!(
+            "{}: Unsupported key derivation parameters in backup header",
+            ERR_WRONG_PASSPHRASE
+        ));
+    }
+
+    let salt_start = MAGIC.len() + 12;
+    let nonce_start = salt_start + SALT_LEN;
+    let salt = &data[salt_start..nonce_start];
+    let nonce = Nonce::from_slice(&data[nonce_start..HEADER_LEN]);
+
+    let key = derive_key(passphrase, salt, m_cost, t_cost, p_cost)?;
+    let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&key));
+    cipher
+        .decrypt(
+            nonce,
+            Payload {
+                msg: &data[HEADER_LEN..],
+                aad: &data[..HEADER_LEN],
+            },
+        )
+        .map_err(|_| {
+            format!(
+                "{}: Wrong passphrase or the backup file is corrupted",
+                ERR_WRONG_PASSPHRASE
+            )
+        })
+}
+
+/// Resolve a passphrase that may be a secret vault reference (the stored `backup_passphrase`)
+pub fn resolve_passphrase(passphrase: Option<String>) -> Result<Option<String>, String> {
+    passphrase
+        .map(|p| crate::secret_vault::resolve(&p))
+        .transpose()
+}
+
+/// Encrypt the archive when a passphrase is configured, otherwise return it unchanged
+pub fn encrypt_if_enabled(data: Vec<u8>, passphrase: Option<&str>) -> Result<Vec<u8>, String> {
+    match passphrase {
+        Some(p) if !p.is_empty() => encrypt_backup(&data, p),
+        _ => Ok(data),
+    }
+}
+
+/// Decrypt the archive if it is encrypted, otherwise return it unchanged
+pub fn decrypt_if_needed(data: Vec<u8>, passphrase: Option<&str>) -> Result<Vec<u8>, String> {
+    if !is_encrypted_backup(&data) {
+        return Ok(data);
+    }
+
+    match passphrase {
+        Some(p) if !p.is_empty() => decrypt_backup(&data, p),
+        _ => Err(format!(
+            "{}: This backup is encrypted, please enter the backup passphrase",
+            ERR_PASSPHRASE_REQUIRED
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_roundtrip() {
+        let data = b"PK\x03\x04 fake zip payload".to_vec();
+        let encrypted = encrypt_backup(&data, "correct horse").unwrap();
+
+        assert!(is_encrypted_backup(&encrypted));
+        assert_ne!(&encrypted[HEADER_LEN..], &data[..]);
+        assert_eq!(decrypt_backup(&encrypted, "correct horse").unwrap(), data);
+    }
+
+    #[test]
+    fn test_wrong_passphrase() {
+        let encrypted = encrypt_backup(b"secret", "right").unwrap();
+        let err = decrypt_backup(&encrypted, "wrong").unwrap_err();
+        assert!(err.starts_with(ERR_WRONG_PASSPHRASE));
+    }
+
+    #[test]
+    fn test_tampered_header_is_rejected() {
+        let mut encrypted = encrypt_backup(b"secret", "pass").unwrap();
+        // Flip a salt byte: header is authenticated, so this must fail
+        encrypted[MAGIC.len() + 12] ^= 0xff;
+        assert!(decrypt_backup(&encrypted, "pass").is_err());
+    }
+
+    #[test]
+    fn test_oversized_kdf_params_are_rejected() {
+        let encrypted = encrypt_backup(b"secret", "pass").unwrap();
+        for offset in [MAGIC.len(), MAGIC.len() + 4, MAGIC.len() + 8] {
+            let mut crafted = encrypted.clone();
+            crafted[offset..offset + 4].copy_from_slice(&u32::MAX.to_le_bytes());
+            let err = decrypt_backup(&crafted, "pass").unwrap_err();
+            assert!(err.contains("Unsupported key derivation parameters"));
+        }
+    }
+
+    #[test]
+    fn test_plain_archive_passthrough() {
+        let data = b"PK\x03\x04 plain".to_vec();
+        assert!(!is_encrypted_backup(&data));
+        assert_eq!(encrypt_if_enabled(data.clone(), Some("")).unwrap(), data);
+        assert_eq!(decrypt_if_needed(data.clone(), Some("pass")).unwrap(), data);
+    }
+
+    #[test]
+    fn test_passphrase_required() {
+        let encrypted = encrypt_if_enabled(b"secret".to_vec(), Some("pass")).unwrap();
+        let err = decrypt_if_needed(encrypted.clone(), None).unwrap_err();
+        assert!(err.starts_with(ERR_PASSPHRASE_REQUIRED));
+        assert_eq!(
+            decrypt_if_needed(encrypted, Some("pass")).unwrap(),
+            b"secret".to_vec()
+        );
+    }
+}
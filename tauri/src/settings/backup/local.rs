@@ -1,17 +1,18 @@
 use chrono::Local;
 use std::fs::{self, File};
-use std::io::{Read, Write};
+use std::io::{Cursor, Read, Write};
 use std::path::{Path, PathBuf};
 use tauri::Manager;
 use walkdir::WalkDir;
 use zip::write::SimpleFileOptions;
 use zip::{ZipArchive, ZipWriter};
 
+use super::crypto::{decrypt_if_needed, encrypt_backup, resolve_passphrase};
 use super::utils::{
     get_claude_prompt_path, get_codex_auth_path, get_codex_config_path, get_codex_prompt_path,
     get_db_path, get_models_cache_file, get_opencode_auth_path, get_opencode_config_path,
     get_opencode_prompt_path, get_opencode_restore_dir, get_preset_models_cache_file,
-    get_skills_dir,
+    get_secret_vault_file, get_skills_dir, VAULT_KEY_ENTRY,
 };
 
 /// Get the home directory
@@ -59,7 +60,10 @@ fn add_file_to_zip(
 pub async fn backup_database(
     app_handle: tauri::AppHandle,
     backup_path: String,
+    passphrase: Option<String>,
 ) -> Result<String, String> {
+    let passphrase = resolve_passphrase(passphrase)?;
+    super::utils::ensure_vault_key_exportable(passphrase.as_deref())?;
     let db_path = get_db_path(&app_handle)?;
 
     // Ensure database directory exists
@@ -234,6 +238,12 @@ pub async fn backup_database(
         )?;
     }
 
+    // Backup secret_vault.json (encrypted API keys / passwords) if exists
+    if let Some(secret_vault_path) = get_secret_vault_file() {
+        add_file_to_zip(&mut zip, &secret_vault_path, "secret_vault.json", options)?;
+    }
+    super::utils::add_vault_key_to_zip(&mut zip, options, passphrase.as_deref())?;
+
     // Backup skills directory if exists
     let skills_dir = get_skills_dir(&app_handle)?;
     if skills_dir.exists() {
@@ -271,6 +281,15 @@ pub async fn backup_database(
     zip.finish()
         .map_err(|e| format!("Failed to finish zip: {}", e))?;
 
+    // Encrypt the finished archive in place when a passphrase is provided
+    if let Some(passphrase) = passphrase.as_deref().filter(|p| !p.is_empty()) {
+        let zip_data = fs::read(&backup_file_path)
+            .map_err(|e| format!("Failed to read backup file: {}", e))?;
+        let encrypted = encrypt_backup(&zip_data, passphrase)?;
+        fs::write(&backup_file_path, encrypted)
+            .map_err(|e| format!("Failed to write encrypted backup: {}", e))?;
+    }
+
     Ok(backup_file_path.to_string_lossy().to_string())
 }
 
@@ -279,7 +298,9 @@ pub async fn backup_database(
 pub async fn restore_database(
     app_handle: tauri::AppHandle,
     zip_file_path: String,
+    passphrase: Option<String>,
 ) -> Result<(), String> {
+    let passphrase = resolve_passphrase(passphrase)?;
     let db_path = get_db_path(&app_handle)?;
     let zip_path = Path::new(&zip_file_path);
 
@@ -287,10 +308,11 @@ pub async fn restore_database(
         return Err("Backup file does not exist".to_string());
     }
 
-    // Open zip file
-    let file = File::open(zip_path).map_err(|e| format!("Failed to open backup file: {}", e))?;
-    let mut archive =
-        ZipArchive::new(file).map_err(|e| format!("Failed to read zip archive: {}", e))?;
+    // Read backup file, decrypting it first if it is encrypted
+    let data = fs::read(zip_path).map_err(|e| format!("Failed to open backup file: {}", e))?;
+    let data = decrypt_if_needed(data, passphrase.as_deref())?;
+    let mut archive = ZipArchive::new(Cursor::new(data))
+        .map_err(|e| format!("Failed to read zip archive: {}", e))?;
 
     // Check if this is a new format backup (with db/ prefix) or old format
     let is_new_format = (0..archive.len()).any(|i| {
@@ -312,6 +334,7 @@ pub async fn restore_database(
 
     // Get home directory for external configs
     let home_dir = get_home_dir()?;
+    let mut vault_key = None;
 
     // Extract zip contents
     for i in 0..archive.len() {
@@ -466,6 +489,20 @@ pub async fn restore_database(
                         format!("Failed to extract preset models cache file: {}", e)
                     })?;
                 }
+            } else if file_name == "secret_vault.json" {
+                // Restore secret_vault.json to app data directory
+                if let Some(vault_path) = crate::secret_vault::store_file_path() {
+                    let mut outfile = File::create(&vault_path)
+                        .map_err(|e| format!("Failed to create secret vault file: {}", e))?;
+                    std::io::copy(&mut file, &mut outfile)
+                        .map_err(|e| format!("Failed to extract secret vault file: {}", e))?;
+                    crate::secret_vault::reset_cached_key();
+                }
+            } else if file_name == VAULT_KEY_ENTRY {
+                let mut wrapped = String::new();
+                file.read_to_string(&mut wrapped)
+                    .map_err(|e| format!("Failed to read secret vault key: {}", e))?;
+                vault_key = Some(wrapped);
             } else if file_name.starts_with("skills/") {
                 // Restore skills directory
                 let relative_path = &file_name[7..]; // Remove "skills/" prefix
@@ -522,7 +559,7 @@ pub async fn restore_database(
     let resync_flag = app_data_dir.join(".resync_required");
     let _ = fs::write(&resync_flag, "1");
 
-    Ok(())
+    super::utils::import_vault_key(vault_key, passphrase.as_deref())
 }
 
 /// Get database directory path for frontend
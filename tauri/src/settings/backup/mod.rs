@@ -1,7 +1,10 @@
 pub mod auto_backup;
+pub mod crypto;
 pub mod local;
+pub mod s3;
 pub mod utils;
 pub mod webdav;
 
 pub use local::*;
+pub use s3::*;
 pub use webdav::*;
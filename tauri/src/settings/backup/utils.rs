@@ -1,3 +1,4 @@
+use log::{error, info};
 use std::fs::File;
 use std::io::{Read, Write};
 use std::path::{Path, PathBuf};
@@ -7,14 +8,18 @@ use zip::ZipWriter;
 
 use crate::coding::open_code::shell_env;
 
-/// Get database directory path
-pub fn get_db_path(app_handle: &tauri::AppHandle) -> Result<std::path::PathBuf, String> {
+/// Get app data directory path
+pub fn get_app_data_dir(app_handle: &tauri::AppHandle) -> Result<PathBuf, String> {
     use tauri::Manager;
-    let app_data_dir = app_handle
+    app_handle
         .path()
         .app_data_dir()
-        .map_err(|e| format!("Failed to get app data dir: {}", e))?;
-    Ok(app_data_dir.join("database"))
+        .map_err(|e| format!("Failed to get app data dir: {}", e))
+}
+
+/// Get database directory path
+pub fn get_db_path(app_handle: &tauri::AppHandle) -> Result<std::path::PathBuf, String> {
+    Ok(get_app_data_dir(app_handle)?.join("database"))
 }
 
 /// Get home directory
@@ -208,12 +213,7 @@ pub fn get_codex_prompt_path() -> Result<Option<PathBuf>, String> {
 
 /// Get skills directory path
 pub fn get_skills_dir(app_handle: &tauri::AppHandle) -> Result<PathBuf, String> {
-    use tauri::Manager;
-    let app_data_dir = app_handle
-        .path()
-        .app_data_dir()
-        .map_err(|e| format!("Failed to get app data dir: {}", e))?;
-    Ok(app_data_dir.join("skills"))
+    Ok(get_app_data_dir(app_handle)?.join("skills"))
 }
 
 /// Get models.dev.json cache file path if it exists
@@ -226,6 +226,11 @@ pub fn get_preset_models_cache_file() -> Option<PathBuf> {
     crate::coding::preset_models::get_preset_models_cache_path().filter(|p| p.exists())
 }
 
+/// Get secret_vault.json file path if it exists
+pub fn get_secret_vault_file() -> Option<PathBuf> {
+    crate::secret_vault::store_file_path().filter(|p| p.exists())
+}
+
 /// Add a file to zip archive with a specific path
 fn add_file_to_zip<W: Write + std::io::Seek>(
     zip: &mut ZipWriter<W>,
@@ -246,8 +251,69 @@ fn add_file_to_zip<W: Write + std::io::Seek>(
     Ok(())
 }
 
-/// Create a temporary backup zip file and return its contents as bytes
-pub fn create_backup_zip(app_handle: &tauri::AppHandle, db_path: &Path) -> Result<Vec<u8>, String> {
+/// Zip entry holding the secret vault master key wrapped by the backup passphrase
+pub const VAULT_KEY_ENTRY: &str = "secret_vault_key";
+
+/// In keyring mode the vault master key is not part of secret_vault.json and can only be
+/// carried by an encrypted backup, so refuse to back up stored secrets without a passphrase
+pub fn ensure_vault_key_exportable(passphrase: Option<&str>) -> Result<(), String> {
+    if passphrase.is_none_or(str::is_empty) && crate::secret_vault::backup_requires_passphrase() {
+        return Err("The secret vault master key is kept in the system keychain. Set a backup encryption passphrase, otherwise the stored API keys and passwords cannot be restored on another device or OS account".to_string());
+    }
+    Ok(())
+}
+
+/// Add the vault master key wrapped by the backup passphrase.
+/// In keyring mode the key is not part of secret_vault.json, so without it the secrets
+/// cannot be restored on another machine or OS account.
+pub fn add_vault_key_to_zip<W: Write + std::io::Seek>(
+    zip: &mut ZipWriter<W>,
+    options: SimpleFileOptions,
+    passphrase: Option<&str>,
+) -> Result<(), String> {
+    ensure_vault_key_exportable(passphrase)?;
+    let Some(passphrase) = passphrase.filter(|p| !p.is_empty()) else {
+        return Ok(());
+    };
+    let wrapped = crate::secret_vault::export_key_for_backup(passphrase)
+        .map_err(|e| format!("Failed to export secret vault key for backup: {}", e))?;
+    if let Some(wrapped) = wrapped {
+        zip.start_file(VAULT_KEY_ENTRY, options)
+            .map_err(|e| format!("Failed to start file in zip: {}", e))?;
+        zip.write_all(wrapped.as_bytes())
+            .map_err(|e| format!("Failed to write to zip: {}", e))?;
+    }
+    Ok(())
+}
+
+/// Import the vault master key carried by the backup (after secret_vault.json was restored).
+/// Backups without the key are checked against the local keychain instead. The rest of the
+/// restore has already been written when this fails, so the error tells the user which part is missing.
+pub fn import_vault_key(wrapped: Option<String>, passphrase: Option<&str>) -> Result<(), String> {
+    let result = match (wrapped, passphrase.filter(|p| !p.is_empty())) {
+        (Some(wrapped), Some(passphrase)) => {
+            crate::secret_vault::import_key_from_backup(wrapped.trim(), passphrase)
+        }
+        (Some(_), None) => {
+            Err("the backup carries a vault key but no passphrase was given".to_string())
+        }
+        (None, _) => crate::secret_vault::check_keyring_key(),
+    };
+    result.map_err(|e| {
+        format!(
+            "Backup restored, but the secret vault master key is unavailable, so stored API keys and passwords cannot be decrypted: {}",
+            e
+        )
+    })
+}
+
+/// Create a temporary backup zip file and return its contents as bytes.
+/// `passphrase` is the one the archive will be encrypted with, used to wrap the vault key.
+pub fn create_backup_zip(
+    db_path: &Path,
+    skills_dir: &Path,
+    passphrase: Option<&str>,
+) -> Result<Vec<u8>, String> {
     use std::io::Cursor;
 
     let mut buffer = Cursor::new(Vec::new());
@@ -403,17 +469,22 @@ pub fn create_backup_zip(app_handle: &tauri::AppHandle, db_path: &Path) -> Resul
             )?;
         }
 
+        // Backup secret_vault.json (encrypted API keys / passwords) if exists
+        if let Some(secret_vault_path) = get_secret_vault_file() {
+            add_file_to_zip(&mut zip, &secret_vault_path, "secret_vault.json", options)?;
+        }
+        add_vault_key_to_zip(&mut zip, options, passphrase)?;
+
         // Backup skills directory if exists
-        let skills_dir = get_skills_dir(app_handle)?;
         if skills_dir.exists() {
             zip.add_directory("skills/", options)
                 .map_err(|e| format!("Failed to add skills directory: {}", e))?;
 
-            for entry in WalkDir::new(&skills_dir) {
+            for entry in WalkDir::new(skills_dir) {
                 let entry = entry.map_err(|e| format!("Failed to read skills entry: {}", e))?;
                 let path = entry.path();
                 let relative_path = path
-                    .strip_prefix(&skills_dir)
+                    .strip_prefix(skills_dir)
                     .map_err(|e| format!("Failed to get relative path: {}", e))?;
 
                 if path.is_file() {
@@ -443,3 +514,273 @@ pub fn create_backup_zip(app_handle: &tauri::AppHandle, db_path: &Path) -> Resul
 
     Ok(buffer.into_inner())
 }
+
+/// Extract a backup archive (already loaded in memory) into the database directory
+/// and restore external configs, caches and skills to their original locations.
+///
+/// Shared by the remote restore commands (WebDAV / S3) and the CLI.
+/// `passphrase` is the one the archive was decrypted with, used to import the vault key.
+pub fn restore_from_zip_data(
+    app_data_dir: &Path,
+    zip_data: &[u8],
+    passphrase: Option<&str>,
+) -> Result<(), String> {
+    use std::fs;
+    use zip::ZipArchive;
+
+    let db_path = app_data_dir.join("database");
+
+    info!("Extracting backup archive...");
+
+    // Extract zip contents
+    let cursor = std::io::Cursor::new(zip_data);
+    let mut archive = ZipArchive::new(cursor).map_err(|e| {
+        error!("Failed to read zip archive: {}", e);
+        format!("Failed to read zip archive: {}", e)
+    })?;
+
+    // Check if this is a new format backup (with db/ prefix) or old format
+    let is_new_format = (0..archive.len()).any(|i| {
+        archive
+            .by_index(i)
+            .map(|f| f.name().starts_with("db/"))
+            .unwrap_or(false)
+    });
+
+    // Remove existing database directory
+    if db_path.exists() {
+        info!("Removing existing database directory");
+        fs::remove_dir_all(&db_path).map_err(|e| {
+            error!("Failed to remove existing database: {}", e);
+            format!("Failed to remove existing database: {}", e)
+        })?;
+    }
+
+    // Create database directory
+    fs::create_dir_all(&db_path).map_err(|e| {
+        error!("Failed to create database directory: {}", e);
+        format!("Failed to create database directory: {}", e)
+    })?;
+
+    // Get home directory for external configs
+    let home_dir = get_home_dir()?;
+    let mut vault_key = None;
+
+    for i in 0..archive.len() {
+        let mut file = archive
+            .by_index(i)
+            .map_err(|e| format!("Failed to read zip entry: {}", e))?;
+
+        let file_name = file.name().to_string();
+
+        // Skip backup marker file
+        if file_name == ".backup_marker" || file_name == "db/.backup_marker" {
+            continue;
+        }
+
+        // Handle files based on new or old format
+        if is_new_format {
+            if file_name.starts_with("db/") {
+                // Database files
+                let relative_path = &file_name[3..]; // Remove "db/" prefix
+                if relative_path.is_empty() {
+                    continue;
+                }
+
+                let outpath = db_path.join(relative_path);
+
+                if file_name.ends_with('/') {
+                    fs::create_dir_all(&outpath)
+                        .map_err(|e| format!("Failed to create directory: {}", e))?;
+                } else {
+                    if let Some(parent) = outpath.parent() {
+                        if !parent.exists() {
+                            fs::create_dir_all(parent)
+                                .map_err(|e| format!("Failed to create parent directory: {}", e))?;
+                        }
+                    }
+                    let mut outfile = std::fs::File::create(&outpath)
+                        .map_err(|e| format!("Failed to create file: {}", e))?;
+                    std::io::copy(&mut file, &mut outfile)
+                        .map_err(|e| format!("Failed to extract file: {}", e))?;
+                }
+            } else if file_name.starts_with("external-configs/opencode/") {
+                // OpenCode config - restore to appropriate directory based on env/shell/default
+                let relative_path = &file_name[26..]; // Remove "external-configs/opencode/" prefix
+                if relative_path.is_empty() || file_name.ends_with('/') {
+                    continue;
+                }
+
+                // auth.json should be restored to ~/.local/share/opencode/
+                // config files (opencode.json, opencode.jsonc) should go to config dir
+                if relative_path == "auth.json" {
+                    let auth_dir = home_dir.join(".local").join("share").join("opencode");
+                    if !auth_dir.exists() {
+                        fs::create_dir_all(&auth_dir).map_err(|e| {
+                            format!("Failed to create opencode auth directory: {}", e)
+                        })?;
+                    }
+                    let outpath = auth_dir.join("auth.json");
+                    let mut outfile = std::fs::File::create(&outpath)
+                        .map_err(|e| format!("Failed to create file: {}", e))?;
+                    std::io::copy(&mut file, &mut outfile)
+                        .map_err(|e| format!("Failed to extract file: {}", e))?;
+                } else {
+                    let opencode_dir = get_opencode_restore_dir()?;
+                    if !opencode_dir.exists() {
+                        fs::create_dir_all(&opencode_dir).map_err(|e| {
+                            format!("Failed to create opencode config directory: {}", e)
+                        })?;
+                    }
+
+                    let outpath = opencode_dir.join(relative_path);
+
+                    // Just copy the file - MCP cmd /c normalization will be handled
+                    // by mcp_sync_all during startup resync (triggered by .resync_required flag)
+                    let mut outfile = std::fs::File::create(&outpath)
+                        .map_err(|e| format!("Failed to create file: {}", e))?;
+                    std::io::copy(&mut file, &mut outfile)
+                        .map_err(|e| format!("Failed to extract file: {}", e))?;
+                }
+            } else if file_name.starts_with("external-configs/claude/") {
+                // Claude settings
+                let relative_path = &file_name[24..]; // Remove "external-configs/claude/" prefix
+                if relative_path.is_empty() || file_name.ends_with('/') {
+                    continue;
+                }
+
+                let claude_dir = home_dir.join(".claude");
+                if !claude_dir.exists() {
+                    fs::create_dir_all(&claude_dir)
+                        .map_err(|e| format!("Failed to create claude config directory: {}", e))?;
+                }
+
+                let outpath = claude_dir.join(relative_path);
+
+                // Note: Claude's MCP config is in ~/.claude.json, not ~/.claude/settings.json
+                // settings.json contains other settings without MCP, so just copy it directly
+                let mut outfile = std::fs::File::create(&outpath)
+                    .map_err(|e| format!("Failed to create file: {}", e))?;
+                std::io::copy(&mut file, &mut outfile)
+                    .map_err(|e| format!("Failed to extract file: {}", e))?;
+            } else if file_name.starts_with("external-configs/codex/") {
+                // Codex settings
+                let relative_path = &file_name[23..]; // Remove "external-configs/codex/" prefix
+                if relative_path.is_empty() || file_name.ends_with('/') {
+                    continue;
+                }
+
+                let codex_dir = home_dir.join(".codex");
+                if !codex_dir.exists() {
+                    fs::create_dir_all(&codex_dir)
+                        .map_err(|e| format!("Failed to create codex config directory: {}", e))?;
+                }
+
+                let outpath = codex_dir.join(relative_path);
+
+                // Just copy the file - MCP cmd /c normalization will be handled
+                // by mcp_sync_all during startup resync (triggered by .resync_required flag)
+                let mut outfile = std::fs::File::create(&outpath)
+                    .map_err(|e| format!("Failed to create file: {}", e))?;
+                std::io::copy(&mut file, &mut outfile)
+                    .map_err(|e| format!("Failed to extract file: {}", e))?;
+            } else if file_name == "models.dev.json" {
+                // Restore models.dev.json to app data directory
+                if let Some(cache_path) =
+                    crate::coding::open_code::free_models::get_models_cache_path()
+                {
+                    if let Some(parent) = cache_path.parent() {
+                        if !parent.exists() {
+                            fs::create_dir_all(parent)
+                                .map_err(|e| format!("Failed to create cache directory: {}", e))?;
+                        }
+                    }
+                    let mut outfile = std::fs::File::create(&cache_path)
+                        .map_err(|e| format!("Failed to create models cache file: {}", e))?;
+                    std::io::copy(&mut file, &mut outfile)
+                        .map_err(|e| format!("Failed to extract models cache file: {}", e))?;
+                }
+            } else if file_name == "preset_models.json" {
+                // Restore preset_models.json to app data directory
+                if let Some(cache_path) =
+                    crate::coding::preset_models::get_preset_models_cache_path()
+                {
+                    if let Some(parent) = cache_path.parent() {
+                        if !parent.exists() {
+                            fs::create_dir_all(parent)
+                                .map_err(|e| format!("Failed to create cache directory: {}", e))?;
+                        }
+                    }
+                    let mut outfile = std::fs::File::create(&cache_path)
+                        .map_err(|e| format!("Failed to create preset models cache file: {}", e))?;
+                    std::io::copy(&mut file, &mut outfile).map_err(|e| {
+                        format!("Failed to extract preset models cache file: {}", e)
+                    })?;
+                }
+            } else if file_name == "secret_vault.json" {
+                // Restore secret_vault.json to app data directory
+                let vault_path = app_data_dir.join(crate::secret_vault::STORE_FILE_NAME);
+                let mut outfile = std::fs::File::create(&vault_path)
+                    .map_err(|e| format!("Failed to create secret vault file: {}", e))?;
+                std::io::copy(&mut file, &mut outfile)
+                    .map_err(|e| format!("Failed to extract secret vault file: {}", e))?;
+                crate::secret_vault::reset_cached_key();
+            } else if file_name == VAULT_KEY_ENTRY {
+                let mut wrapped = String::new();
+                file.read_to_string(&mut wrapped)
+                    .map_err(|e| format!("Failed to read secret vault key: {}", e))?;
+                vault_key = Some(wrapped);
+            } else if file_name.starts_with("skills/") {
+                // Restore skills directory
+                let relative_path = &file_name[7..]; // Remove "skills/" prefix
+                if relative_path.is_empty() || file_name.ends_with('/') {
+                    continue;
+                }
+
+                let skills_dir = app_data_dir.join("skills");
+                if !skills_dir.exists() {
+                    fs::create_dir_all(&skills_dir)
+                        .map_err(|e| format!("Failed to create skills directory: {}", e))?;
+                }
+
+                let outpath = skills_dir.join(relative_path);
+                if let Some(parent) = outpath.parent() {
+                    if !parent.exists() {
+                        fs::create_dir_all(parent).map_err(|e| {
+                            format!("Failed to create skills parent directory: {}", e)
+                        })?;
+                    }
+                }
+                let mut outfile = std::fs::File::create(&outpath)
+                    .map_err(|e| format!("Failed to create skills file: {}", e))?;
+                std::io::copy(&mut file, &mut outfile)
+                    .map_err(|e| format!("Failed to extract skills file: {}", e))?;
+            }
+        } else {
+            // Old format: all files are database files
+            let outpath = db_path.join(&file_name);
+
+            if file_name.ends_with('/') {
+                fs::create_dir_all(&outpath)
+                    .map_err(|e| format!("Failed to create directory: {}", e))?;
+            } else {
+                if let Some(parent) = outpath.parent() {
+                    if !parent.exists() {
+                        fs::create_dir_all(parent)
+                            .map_err(|e| format!("Failed to create parent directory: {}", e))?;
+                    }
+                }
+                let mut outfile = std::fs::File::create(&outpath)
+                    .map_err(|e| format!("Failed to create file: {}", e))?;
+                std::io::copy(&mut file, &mut outfile)
+                    .map_err(|e| format!("Failed to extract file: {}", e))?;
+            }
+        }
+    }
+
+    // Create resync flag file to trigger skills and MCP resync on next startup
+    let resync_flag = app_data_dir.join(".resync_required");
+    let _ = fs::write(&resync_flag, "1");
+
+    import_vault_key(vault_key, passphrase)
+}
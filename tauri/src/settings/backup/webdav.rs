@@ -2,13 +2,14 @@ use chrono::Local;
 use log::{error, info};
 use serde::{Deserialize, Serialize};
 use std::fs;
-use std::path::PathBuf;
-use tauri::Manager;
-use zip::ZipArchive;
 
-use super::utils::{create_backup_zip, get_db_path, get_opencode_restore_dir, get_skills_dir};
+use super::crypto::{decrypt_if_needed, encrypt_if_enabled, resolve_passphrase};
+use super::utils::{
+    create_backup_zip, get_app_data_dir, get_db_path, get_skills_dir, restore_from_zip_data,
+};
 use crate::db::DbState;
 use crate::http_client;
+use crate::secret_vault;
 
 /// Backup file info structure
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -135,6 +136,8 @@ pub async fn test_webdav_connection(
         e
     })?;
 
+    let password = secret_vault::resolve(&password)?;
+
     let response = client
         .request(
             reqwest::Method::from_bytes(b"PROPFIND").unwrap(),
@@ -166,6 +169,7 @@ pub async fn test_webdav_connection(
 
 /// Backup database to WebDAV server
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 pub async fn backup_to_webdav(
     app_handle: tauri::AppHandle,
     state: tauri::State<'_, DbState>,
@@ -174,9 +178,11 @@ pub async fn backup_to_webdav(
     password: String,
     remote_path: String,
     host_label: String,
+    passphrase: Option<String>,
 ) -> Result<String, String> {
     info!("Starting WebDAV backup to: {}", url);
 
+    let passphrase = resolve_passphrase(passphrase)?;
     let db_path = get_db_path(&app_handle)?;
 
     // Ensure database directory exists
@@ -187,8 +193,13 @@ pub async fn backup_to_webdav(
         })?;
     }
 
-    // Create backup zip in memory
-    let zip_data = create_backup_zip(&app_handle, &db_path)?;
+    // Create backup zip in memory, encrypted when a passphrase is provided
+    let zip_data = create_backup_zip(
+        &db_path,
+        &get_skills_dir(&app_handle)?,
+        passphrase.as_deref(),
+    )?;
+    let zip_data = encrypt_if_enabled(zip_data, passphrase.as_deref())?;
 
     // Generate backup filename with timestamp and optional host label
     let timestamp = Local::now().format("%Y%m%d-%H%M%S");
@@ -218,6 +229,8 @@ pub async fn backup_to_webdav(
             e
         })?;
 
+    let password = secret_vault::resolve(&password)?;
+
     let response = client
         .put(&full_url)
         .basic_auth(&username, Some(&password))
@@ -274,12 +287,14 @@ pub(crate) async fn list_webdav_backups_internal(
   <d:allprop/>
 </d:propfind>"#;
 
+    let password = secret_vault::resolve(password)?;
+
     let response = client
         .request(
             reqwest::Method::from_bytes(b"PROPFIND").unwrap(),
             &folder_url,
         )
-        .basic_auth(username, Some(password))
+        .basic_auth(username, Some(&password))
         .header("Depth", "1")
         .header("Content-Type", "application/xml; charset=utf-8")
         .body(propfind_body)
@@ -392,9 +407,11 @@ pub(crate) async fn delete_webdav_backup_internal(
         e
     })?;
 
+    let password = secret_vault::resolve(password)?;
+
     let response = client
         .delete(&full_url)
-        .basic_auth(username, Some(password))
+        .basic_auth(username, Some(&password))
         .send()
         .await;
 
@@ -430,16 +447,9 @@ pub async fn delete_webdav_backup(
     delete_webdav_backup_internal(&state, &url, &username, &password, &remote_path, &filename).await
 }
 
-/// Get home directory
-fn get_home_dir() -> Result<PathBuf, String> {
-    std::env::var("USERPROFILE")
-        .or_else(|_| std::env::var("HOME"))
-        .map(PathBuf::from)
-        .map_err(|_| "Failed to get home directory".to_string())
-}
-
 /// Restore database from WebDAV server
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 pub async fn restore_from_webdav(
     app_handle: tauri::AppHandle,
     state: tauri::State<'_, DbState>,
@@ -448,11 +458,10 @@ pub async fn restore_from_webdav(
     password: String,
     remote_path: String,
     filename: String,
+    passphrase: Option<String>,
 ) -> Result<(), String> {
     info!("Starting WebDAV restore from: {}/{}", url, filename);
 
-    let db_path = get_db_path(&app_handle)?;
-
     // Build WebDAV URL
     let base_url = url.trim_end_matches('/');
     let remote = remote_path.trim_matches('/');
@@ -472,6 +481,8 @@ pub async fn restore_from_webdav(
             e
         })?;
 
+    let password = secret_vault::resolve(&password)?;
+
     let response = client
         .get(&full_url)
         .basic_auth(&username, Some(&password))
@@ -498,247 +509,13 @@ pub async fn restore_from_webdav(
         }
     };
 
-    info!("Extracting backup archive...");
-
-    // Extract zip contents
-    let cursor = std::io::Cursor::new(zip_data);
-    let mut archive = ZipArchive::new(cursor).map_err(|e| {
-        error!("Failed to read zip archive: {}", e);
-        format!("Failed to read zip archive: {}", e)
-    })?;
-
-    // Check if this is a new format backup (with db/ prefix) or old format
-    let is_new_format = (0..archive.len()).any(|i| {
-        archive
-            .by_index(i)
-            .map(|f| f.name().starts_with("db/"))
-            .unwrap_or(false)
-    });
-
-    // Remove existing database directory
-    if db_path.exists() {
-        info!("Removing existing database directory");
-        fs::remove_dir_all(&db_path).map_err(|e| {
-            error!("Failed to remove existing database: {}", e);
-            format!("Failed to remove existing database: {}", e)
-        })?;
-    }
-
-    // Create database directory
-    fs::create_dir_all(&db_path).map_err(|e| {
-        error!("Failed to create database directory: {}", e);
-        format!("Failed to create database directory: {}", e)
-    })?;
-
-    // Get home directory for external configs
-    let home_dir = get_home_dir()?;
-
-    for i in 0..archive.len() {
-        let mut file = archive
-            .by_index(i)
-            .map_err(|e| format!("Failed to read zip entry: {}", e))?;
-
-        let file_name = file.name().to_string();
-
-        // Skip backup marker file
-        if file_name == ".backup_marker" || file_name == "db/.backup_marker" {
-            continue;
-        }
-
-        // Handle files based on new or old format
-        if is_new_format {
-            if file_name.starts_with("db/") {
-                // Database files
-                let relative_path = &file_name[3..]; // Remove "db/" prefix
-                if relative_path.is_empty() {
-                    continue;
-                }
-
-                let outpath = db_path.join(relative_path);
-
-                if file_name.ends_with('/') {
-                    fs::create_dir_all(&outpath)
-                        .map_err(|e| format!("Failed to create directory: {}", e))?;
-                } else {
-                    if let Some(parent) = outpath.parent() {
-                        if !parent.exists() {
-                            fs::create_dir_all(parent)
-                                .map_err(|e| format!("Failed to create parent directory: {}", e))?;
-                        }
-                    }
-                    let mut outfile = std::fs::File::create(&outpath)
-                        .map_err(|e| format!("Failed to create file: {}", e))?;
-                    std::io::copy(&mut file, &mut outfile)
-                        .map_err(|e| format!("Failed to extract file: {}", e))?;
-                }
-            } else if file_name.starts_with("external-configs/opencode/") {
-                // OpenCode config - restore to appropriate directory based on env/shell/default
-                let relative_path = &file_name[26..]; // Remove "external-configs/opencode/" prefix
-                if relative_path.is_empty() || file_name.ends_with('/') {
-                    continue;
-                }
-
-                // auth.json should be restored to ~/.local/share/opencode/
-                // config files (opencode.json, opencode.jsonc) should go to config dir
-                if relative_path == "auth.json" {
-                    let auth_dir = home_dir.join(".local").join("share").join("opencode");
-                    if !auth_dir.exists() {
-                        fs::create_dir_all(&auth_dir).map_err(|e| {
-                            format!("Failed to create opencode auth directory: {}", e)
-                        })?;
-                    }
-                    let outpath = auth_dir.join("auth.json");
-                    let mut outfile = std::fs::File::create(&outpath)
-                        .map_err(|e| format!("Failed to create file: {}", e))?;
-                    std::io::copy(&mut file, &mut outfile)
-                        .map_err(|e| format!("Failed to extract file: {}", e))?;
-                } else {
-                    let opencode_dir = get_opencode_restore_dir()?;
-                    if !opencode_dir.exists() {
-                        fs::create_dir_all(&opencode_dir).map_err(|e| {
-                            format!("Failed to create opencode config directory: {}", e)
-                        })?;
-                    }
-
-                    let outpath = opencode_dir.join(relative_path);
-
-                    // Just copy the file - MCP cmd /c normalization will be handled
-                    // by mcp_sync_all during startup resync (triggered by .resync_required flag)
-                    let mut outfile = std::fs::File::create(&outpath)
-                        .map_err(|e| format!("Failed to create file: {}", e))?;
-                    std::io::copy(&mut file, &mut outfile)
-                        .map_err(|e| format!("Failed to extract file: {}", e))?;
-                }
-            } else if file_name.starts_with("external-configs/claude/") {
-                // Claude settings
-                let relative_path = &file_name[24..]; // Remove "external-configs/claude/" prefix
-                if relative_path.is_empty() || file_name.ends_with('/') {
-                    continue;
-                }
-
-                let claude_dir = home_dir.join(".claude");
-                if !claude_dir.exists() {
-                    fs::create_dir_all(&claude_dir)
-                        .map_err(|e| format!("Failed to create claude config directory: {}", e))?;
-                }
-
-                let outpath = claude_dir.join(relative_path);
-
-                // Note: Claude's MCP config is in ~/.claude.json, not ~/.claude/settings.json
-                // settings.json contains other settings without MCP, so just copy it directly
-                let mut outfile = std::fs::File::create(&outpath)
-                    .map_err(|e| format!("Failed to create file: {}", e))?;
-                std::io::copy(&mut file, &mut outfile)
-                    .map_err(|e| format!("Failed to extract file: {}", e))?;
-            } else if file_name.starts_with("external-configs/codex/") {
-                // Codex settings
-                let relative_path = &file_name[23..]; // Remove "external-configs/codex/" prefix
-                if relative_path.is_empty() || file_name.ends_with('/') {
-                    continue;
-                }
-
-                let codex_dir = home_dir.join(".codex");
-                if !codex_dir.exists() {
-                    fs::create_dir_all(&codex_dir)
-                        .map_err(|e| format!("Failed to create codex config directory: {}", e))?;
-                }
-
-                let outpath = codex_dir.join(relative_path);
-
-                // Just copy the file - MCP cmd /c normalization will be handled
-                // by mcp_sync_all during startup resync (triggered by .resync_required flag)
-                let mut outfile = std::fs::File::create(&outpath)
-                    .map_err(|e| format!("Failed to create file: {}", e))?;
-                std::io::copy(&mut file, &mut outfile)
-                    .map_err(|e| format!("Failed to extract file: {}", e))?;
-            } else if file_name == "models.dev.json" {
-                // Restore models.dev.json to app data directory
-                if let Some(cache_path) =
-                    crate::coding::open_code::free_models::get_models_cache_path()
-                {
-                    if let Some(parent) = cache_path.parent() {
-                        if !parent.exists() {
-                            fs::create_dir_all(parent)
-                                .map_err(|e| format!("Failed to create cache directory: {}", e))?;
-                        }
-                    }
-                    let mut outfile = std::fs::File::create(&cache_path)
-                        .map_err(|e| format!("Failed to create models cache file: {}", e))?;
-                    std::io::copy(&mut file, &mut outfile)
-                        .map_err(|e| format!("Failed to extract models cache file: {}", e))?;
-                }
-            } else if file_name == "preset_models.json" {
-                // Restore preset_models.json to app data directory
-                if let Some(cache_path) =
-                    crate::coding::preset_models::get_preset_models_cache_path()
-                {
-                    if let Some(parent) = cache_path.parent() {
-                        if !parent.exists() {
-                            fs::create_dir_all(parent)
-                                .map_err(|e| format!("Failed to create cache directory: {}", e))?;
-                        }
-                    }
-                    let mut outfile = std::fs::File::create(&cache_path)
-                        .map_err(|e| format!("Failed to create preset models cache file: {}", e))?;
-                    std::io::copy(&mut file, &mut outfile).map_err(|e| {
-                        format!("Failed to extract preset models cache file: {}", e)
-                    })?;
-                }
-            } else if file_name.starts_with("skills/") {
-                // Restore skills directory
-                let relative_path = &file_name[7..]; // Remove "skills/" prefix
-                if relative_path.is_empty() || file_name.ends_with('/') {
-                    continue;
-                }
-
-                let skills_dir = get_skills_dir(&app_handle)?;
-                if !skills_dir.exists() {
-                    fs::create_dir_all(&skills_dir)
-                        .map_err(|e| format!("Failed to create skills directory: {}", e))?;
-                }
-
-                let outpath = skills_dir.join(relative_path);
-                if let Some(parent) = outpath.parent() {
-                    if !parent.exists() {
-                        fs::create_dir_all(parent).map_err(|e| {
-                            format!("Failed to create skills parent directory: {}", e)
-                        })?;
-                    }
-                }
-                let mut outfile = std::fs::File::create(&outpath)
-                    .map_err(|e| format!("Failed to create skills file: {}", e))?;
-                std::io::copy(&mut file, &mut outfile)
-                    .map_err(|e| format!("Failed to extract skills file: {}", e))?;
-            }
-        } else {
-            // Old format: all files are database files
-            let outpath = db_path.join(&file_name);
-
-            if file_name.ends_with('/') {
-                fs::create_dir_all(&outpath)
-                    .map_err(|e| format!("Failed to create directory: {}", e))?;
-            } else {
-                if let Some(parent) = outpath.parent() {
-                    if !parent.exists() {
-                        fs::create_dir_all(parent)
-                            .map_err(|e| format!("Failed to create parent directory: {}", e))?;
-                    }
-                }
-                let mut outfile = std::fs::File::create(&outpath)
-                    .map_err(|e| format!("Failed to create file: {}", e))?;
-                std::io::copy(&mut file, &mut outfile)
-                    .map_err(|e| format!("Failed to extract file: {}", e))?;
-            }
-        }
-    }
-
-    // Create resync flag file to trigger skills and MCP resync on next startup
-    let app_data_dir = app_handle
-        .path()
-        .app_data_dir()
-        .map_err(|e| format!("Failed to get app data dir: {}", e))?;
-    let resync_flag = app_data_dir.join(".resync_required");
-    let _ = fs::write(&resync_flag, "1");
+    let passphrase = resolve_passphrase(passphrase)?;
+    let zip_data = decrypt_if_needed(zip_data.to_vec(), passphrase.as_deref())?;
+    restore_from_zip_data(
+        &get_app_data_dir(&app_handle)?,
+        &zip_data,
+        passphrase.as_deref(),
+    )?;
 
     info!("WebDAV restore completed successfully");
     Ok(())
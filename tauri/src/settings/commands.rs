@@ -1,14 +1,22 @@
 use super::adapter;
 use super::types::AppSettings;
 use crate::auto_launch;
+use crate::coding;
+use crate::control_api;
 use crate::db::DbState;
+use crate::llm_gateway;
 use crate::tray;
 
 /// Get settings from database using adapter layer for fault tolerance
 #[tauri::command]
 pub async fn get_settings(state: tauri::State<'_, DbState>) -> Result<AppSettings, String> {
-    let db = state.db();
+    load_settings(&state.db()).await
+}
 
+/// Read settings with a plain database handle (no Tauri state needed)
+pub async fn load_settings(
+    db: &surrealdb::Surreal<surrealdb::engine::local::Db>,
+) -> Result<AppSettings, String> {
     // Use type::string(id) to convert Thing ID to string
     let mut result = db
         .query("SELECT *, type::string(id) as id FROM settings:`app` LIMIT 1")
@@ -33,10 +41,15 @@ pub async fn get_settings(state: tauri::State<'_, DbState>) -> Result<AppSetting
 pub async fn save_settings(
     state: tauri::State<'_, DbState>,
     app: tauri::AppHandle,
-    settings: AppSettings,
+    mut settings: AppSettings,
 ) -> Result<(), String> {
     let db = state.db();
 
+    // Generate the gateway token the first time the gateway is enabled
+    if settings.llm_gateway_enabled && settings.llm_gateway_token.is_empty() {
+        settings.llm_gateway_token = llm_gateway::generate_token();
+    }
+
     // Convert to JSON using adapter
     let json = adapter::to_db_value(&settings);
 
@@ -48,6 +61,19 @@ pub async fn save_settings(
 
     drop(db);
 
+    coding::config_history::set_retention(
+        settings.config_history_max_per_file,
+        settings.config_history_max_age_days,
+    );
+
+    if let Err(err) = control_api::set_enabled(&app, settings.control_api_enabled).await {
+        log::warn!("Failed to update control API: {err}");
+    }
+
+    if let Err(err) = llm_gateway::apply_settings(&app, &settings).await {
+        log::warn!("Failed to update LLM gateway: {err}");
+    }
+
     if let Err(err) = tray::refresh_tray_menus(&app).await {
         log::warn!("Failed to refresh tray after saving settings: {err}");
     }
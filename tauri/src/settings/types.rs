@@ -55,10 +55,38 @@ pub struct AppSettings {
     pub auto_backup_max_keep: u32,
     /// Last auto backup time in ISO 8601 format
     pub last_auto_backup_time: Option<String>,
+    /// Passphrase used to encrypt backup archives, empty = no encryption (default: "")
+    pub backup_passphrase: String,
     /// Auto check for updates on startup (default: true)
     pub auto_check_update: bool,
     /// Visible tabs in the tab bar (default: all tabs shown)
     pub visible_tabs: Vec<String>,
+    /// Serve the localhost control API for scripts and editor plugins (default: false)
+    pub control_api_enabled: bool,
+    /// Periodically probe the applied providers (default: false)
+    pub health_monitor_enabled: bool,
+    /// Minutes between health checks (default: 5)
+    pub health_monitor_interval_minutes: u32,
+    /// Consecutive failed checks before alerting / failing over (default: 3)
+    pub health_monitor_failure_threshold: u32,
+    /// Switch to the next provider when the threshold is reached (default: false)
+    pub health_monitor_auto_failover: bool,
+    /// Route Claude Code / Codex through the local LLM gateway (default: false)
+    pub llm_gateway_enabled: bool,
+    /// Local LLM gateway port (default: 15721)
+    pub llm_gateway_port: u32,
+    /// Token the tools use to authenticate to the gateway, generated on first enable
+    pub llm_gateway_token: String,
+    /// Periodically refresh All API Hub account balances (default: false)
+    pub balance_monitor_enabled: bool,
+    /// Minutes between balance refreshes (default: 60)
+    pub balance_monitor_interval_minutes: u32,
+    /// Alert when the applied provider's account balance drops below this amount in USD (default: 5)
+    pub balance_alert_threshold_usd: f64,
+    /// Snapshots kept per managed config file, 0 = unlimited (default: 20)
+    pub config_history_max_per_file: u32,
+    /// Days config file snapshots are kept, 0 = forever (default: 30)
+    pub config_history_max_age_days: u32,
 }
 
 impl Default for AppSettings {
@@ -81,15 +109,30 @@ impl Default for AppSettings {
             auto_backup_interval_days: 7,
             auto_backup_max_keep: 10,
             last_auto_backup_time: None,
+            backup_passphrase: String::new(),
             auto_check_update: true,
             visible_tabs: vec![
                 "opencode".to_string(),
                 "claudecode".to_string(),
                 "codex".to_string(),
+                "geminicli".to_string(),
                 "openclaw".to_string(),
                 "ssh".to_string(),
                 "wsl".to_string(),
             ],
+            control_api_enabled: false,
+            health_monitor_enabled: false,
+            health_monitor_interval_minutes: 5,
+            health_monitor_failure_threshold: 3,
+            health_monitor_auto_failover: false,
+            llm_gateway_enabled: false,
+            llm_gateway_port: 15721,
+            llm_gateway_token: String::new(),
+            balance_monitor_enabled: false,
+            balance_monitor_interval_minutes: 60,
+            balance_alert_threshold_usd: 5.0,
+            config_history_max_per_file: 20,
+            config_history_max_age_days: 30,
         }
     }
 }
@@ -1,14 +1,16 @@
-//! Linux-specific single instance detection using file locks.
+//! Single instance detection using file locks.
 //!
-//! This module provides a fallback mechanism for single instance detection on Linux,
-//! where the D-Bus based detection from tauri-plugin-single-instance may not work
-//! reliably in all environments (different D-Bus sessions, SSH, etc.).
+//! On Linux this is a fallback for the desktop app, where the D-Bus based detection
+//! from tauri-plugin-single-instance may not work reliably in all environments
+//! (different D-Bus sessions, SSH, etc.). On macOS and Windows the plugin handles
+//! duplicate launches; the app still holds the lock so the CLI can tell it is running.
 //!
 //! ## Crash Recovery
 //!
-//! This implementation uses `flock()` which is **process-level locking**:
+//! This implementation uses `flock()` (Linux, macOS) and `LockFileEx` (Windows), which
+//! are **process-level locks**:
 //! - When a process crashes or exits (normally or abnormally), the OS automatically
-//!   releases all `flock` locks held by that process.
+//!   releases all locks held by that process.
 //! - Even if the lock file still exists on disk, a new process can successfully
 //!   acquire the lock because the previous lock holder is gone.
 //! - This means stale lock files from crashed processes are NOT a problem.
@@ -136,14 +138,62 @@ pub fn try_focus_existing_instance() -> bool {
     false
 }
 
-// Stub implementations for non-Linux platforms
+// macOS / Windows: std file locks (flock / LockFileEx)
 #[cfg(not(target_os = "linux"))]
-pub struct SingleInstanceLock;
+pub struct SingleInstanceLock {
+    _lock_file: std::fs::File,
+}
 
+/// Try to acquire a single instance lock.
+/// The lock file lives in the per-user temp dir (`$TMPDIR` / `%TEMP%`).
 #[cfg(not(target_os = "linux"))]
 pub fn try_acquire_lock() -> Result<SingleInstanceLock, String> {
-    // On non-Linux platforms, always succeed (rely on tauri-plugin-single-instance)
-    Ok(SingleInstanceLock)
+    use std::fs::TryLockError;
+
+    let lock_path = std::env::temp_dir().join("ai-toolbox.lock");
+    log::info!("尝试获取单实例锁: {:?}", lock_path);
+
+    let lock_file = std::fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(&lock_path)
+        .map_err(|e| format!("无法打开锁文件: {}", e))?;
+
+    match lock_file.try_lock() {
+        Ok(()) => Ok(SingleInstanceLock {
+            _lock_file: lock_file,
+        }),
+        Err(TryLockError::WouldBlock) => {
+            log::warn!("检测到另一个实例正在运行");
+            Err("另一个实例正在运行".to_string())
+        }
+        Err(TryLockError::Error(e)) => {
+            log::error!("获取文件锁失败: {}", e);
+            Err(format!("获取文件锁失败: {}", e))
+        }
+    }
+}
+
+/// Hold the lock for the lifetime of the desktop app so the CLI refuses to run alongside it.
+/// Duplicate launches are handled by tauri-plugin-single-instance, so failing to acquire
+/// the lock is not fatal; retry in the background because on restart the previous
+/// process may not have exited yet.
+#[cfg(not(target_os = "linux"))]
+pub fn hold_lock_for_app() {
+    static HELD: std::sync::OnceLock<SingleInstanceLock> = std::sync::OnceLock::new();
+
+    std::thread::spawn(|| {
+        for _ in 0..40 {
+            if let Ok(lock) = try_acquire_lock() {
+                let _ = HELD.set(lock);
+                return;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(250));
+        }
+        log::warn!("Failed to acquire single instance lock, the CLI will not detect this app");
+    });
 }
 
 #[cfg(not(target_os = "linux"))]
@@ -2,6 +2,8 @@
 //!
 //! Provides system tray icon and menu with flat structure:
 //! - Open Main Window
+//! - ─── Workspace Profiles ───
+//! - Profile options (with checkmarks for the last applied profile)
 //! - ─── OpenCode ────
 //! - 主模型 / 小模型 (with submenus for model selection)
 //! - ─── OpenCode 插件 ────
@@ -16,12 +18,14 @@
 
 use crate::coding::claude_code::tray_support as claude_tray;
 use crate::coding::codex::tray_support as codex_tray;
+use crate::coding::gemini_cli::tray_support as gemini_cli_tray;
 use crate::coding::mcp::tray_support as mcp_tray;
 use crate::coding::oh_my_opencode::tray_support as omo_tray;
 use crate::coding::oh_my_opencode_slim::tray_support as omo_slim_tray;
 use crate::coding::open_claw::tray_support as openclaw_tray;
 use crate::coding::open_code::tray_support as opencode_tray;
 use crate::coding::skills::tray_support as skills_tray;
+use crate::coding::workspace_profile::tray_support as workspace_tray;
 use std::sync::atomic::{AtomicBool, Ordering};
 use tauri::{
     menu::{CheckMenuItem, Menu, MenuItem, PredefinedMenuItem, Submenu},
@@ -80,6 +84,21 @@ pub fn create_tray<R: Runtime>(app: &AppHandle<R>) -> Result<(), Box<dyn std::er
                     let _ = window.show();
                     let _ = window.set_focus();
                 }
+            } else if event_id.starts_with("workspace_profile_") {
+                let profile_id = event_id
+                    .strip_prefix("workspace_profile_")
+                    .unwrap()
+                    .to_string();
+                let app_handle = app.clone();
+                tauri::async_runtime::spawn(async move {
+                    if let Err(e) =
+                        workspace_tray::apply_workspace_profile(&app_handle, &profile_id).await
+                    {
+                        eprintln!("Failed to apply workspace profile: {}", e);
+                    }
+                    // Refresh tray menu to update checkmarks
+                    let _ = refresh_tray_menus(&app_handle).await;
+                });
             } else if event_id.starts_with("omo_config_") {
                 let config_id = event_id.strip_prefix("omo_config_").unwrap().to_string();
                 let app_handle = app.clone();
@@ -205,6 +224,35 @@ pub fn create_tray<R: Runtime>(app: &AppHandle<R>) -> Result<(), Box<dyn std::er
                     }
                     let _ = refresh_tray_menus(&app_handle).await;
                 });
+            } else if event_id.starts_with("gemini_cli_provider_") {
+                let provider_id = event_id
+                    .strip_prefix("gemini_cli_provider_")
+                    .unwrap()
+                    .to_string();
+                let app_handle = app.clone();
+                tauri::async_runtime::spawn(async move {
+                    if let Err(e) =
+                        gemini_cli_tray::apply_gemini_cli_provider(&app_handle, &provider_id).await
+                    {
+                        eprintln!("Failed to apply Gemini CLI provider: {}", e);
+                    }
+                    let _ = refresh_tray_menus(&app_handle).await;
+                });
+            } else if event_id.starts_with("gemini_cli_prompt_") {
+                let config_id = event_id
+                    .strip_prefix("gemini_cli_prompt_")
+                    .unwrap()
+                    .to_string();
+                let app_handle = app.clone();
+                tauri::async_runtime::spawn(async move {
+                    if let Err(e) =
+                        gemini_cli_tray::apply_gemini_cli_prompt_config(&app_handle, &config_id)
+                            .await
+                    {
+                        eprintln!("Failed to apply Gemini CLI prompt config: {}", e);
+                    }
+                    let _ = refresh_tray_menus(&app_handle).await;
+                });
             } else if event_id.starts_with("openclaw_model_") {
                 let item_id = event_id
                     .strip_prefix("openclaw_model_")
@@ -292,6 +340,20 @@ pub fn create_tray<R: Runtime>(app: &AppHandle<R>) -> Result<(), Box<dyn std::er
     Ok(())
 }
 
+/// Show or clear a short alert on the tray icon.
+/// The title text is only rendered on macOS / Linux; the tooltip works everywhere.
+pub fn set_tray_alert<R: Runtime>(app: &AppHandle<R>, title: Option<&str>, tooltip: Option<&str>) {
+    let Some(tray) = app.try_state::<tauri::tray::TrayIcon>() else {
+        return;
+    };
+    if let Err(e) = tray.set_title(title) {
+        log::warn!("Failed to set tray title: {}", e);
+    }
+    if let Err(e) = tray.set_tooltip(tooltip) {
+        log::warn!("Failed to set tray tooltip: {}", e);
+    }
+}
+
 /// Refresh tray menus with deduplication (coalescing pattern)
 pub async fn refresh_tray_menus<R: Runtime>(app: &AppHandle<R>) -> Result<(), String> {
     // If already refreshing, mark pending and return
@@ -325,6 +387,7 @@ async fn refresh_tray_menus_inner<R: Runtime>(app: &AppHandle<R>) -> Result<(),
                 "opencode".to_string(),
                 "claudecode".to_string(),
                 "codex".to_string(),
+                "geminicli".to_string(),
                 "openclaw".to_string(),
             ]
         }
@@ -341,6 +404,8 @@ async fn refresh_tray_menus_inner<R: Runtime>(app: &AppHandle<R>) -> Result<(),
     let claude_enabled =
         is_tab_visible("claudecode") && claude_tray::is_enabled_for_tray(app).await;
     let codex_enabled = is_tab_visible("codex") && codex_tray::is_enabled_for_tray(app).await;
+    let gemini_cli_enabled =
+        is_tab_visible("geminicli") && gemini_cli_tray::is_enabled_for_tray(app).await;
     let openclaw_enabled =
         is_tab_visible("openclaw") && openclaw_tray::is_enabled_for_tray(app).await;
     let opencode_plugins_enabled =
@@ -431,6 +496,23 @@ async fn refresh_tray_menus_inner<R: Runtime>(app: &AppHandle<R>) -> Result<(),
             items: vec![],
         }
     };
+    let gemini_cli_data = if gemini_cli_enabled {
+        gemini_cli_tray::get_gemini_cli_tray_data(app).await?
+    } else {
+        gemini_cli_tray::TrayProviderData {
+            title: "──── Gemini CLI ────".to_string(),
+            items: vec![],
+        }
+    };
+    let gemini_cli_prompt_data = if gemini_cli_enabled {
+        gemini_cli_tray::get_gemini_cli_prompt_tray_data(app).await?
+    } else {
+        gemini_cli_tray::TrayPromptData {
+            title: "全局提示词".to_string(),
+            current_display: String::new(),
+            items: vec![],
+        }
+    };
     let openclaw_model_data = if openclaw_enabled {
         openclaw_tray::get_openclaw_tray_model_data(app).await?
     } else {
@@ -448,6 +530,7 @@ async fn refresh_tray_menus_inner<R: Runtime>(app: &AppHandle<R>) -> Result<(),
             items: vec![],
         }
     };
+    let workspace_data = workspace_tray::get_workspace_profile_tray_data(app).await?;
     let mcp_enabled = mcp_tray::is_mcp_enabled_for_tray(app).await;
     let mcp_data = if mcp_enabled {
         mcp_tray::get_mcp_tray_data(app).await?
@@ -669,6 +752,40 @@ async fn refresh_tray_menus_inner<R: Runtime>(app: &AppHandle<R>) -> Result<(),
         }
     }
 
+    // Workspace profile section (only if any profile exists)
+    let workspace_header = if workspace_data.items.is_empty() {
+        None
+    } else {
+        Some(
+            MenuItem::with_id(
+                app,
+                "workspace_header",
+                &workspace_data.title,
+                false,
+                None::<&str>,
+            )
+            .map_err(|e| e.to_string())?,
+        )
+    };
+
+    // Build workspace profile items
+    let mut workspace_items: Vec<Box<dyn tauri::menu::IsMenuItem<R>>> = Vec::new();
+    for item in workspace_data.items {
+        let item_id = format!("workspace_profile_{}", item.id);
+        let menu_item: Box<dyn tauri::menu::IsMenuItem<R>> = Box::new(
+            CheckMenuItem::with_id(
+                app,
+                &item_id,
+                &item.display_name,
+                true,
+                item.is_selected,
+                None::<&str>,
+            )
+            .map_err(|e| e.to_string())?,
+        );
+        workspace_items.push(menu_item);
+    }
+
     // Check if modules have items (must be done before consuming items in for loops)
     let claude_has_items = claude_enabled && !claude_data.items.is_empty();
     let codex_has_items = codex_enabled && !codex_data.items.is_empty();
@@ -676,6 +793,11 @@ async fn refresh_tray_menus_inner<R: Runtime>(app: &AppHandle<R>) -> Result<(),
     let codex_has_prompt_items = codex_enabled && !codex_prompt_data.items.is_empty();
     let claude_has_section = claude_enabled && (claude_has_items || claude_has_prompt_items);
     let codex_has_section = codex_enabled && (codex_has_items || codex_has_prompt_items);
+    let gemini_cli_has_items = gemini_cli_enabled && !gemini_cli_data.items.is_empty();
+    let gemini_cli_has_prompt_items =
+        gemini_cli_enabled && !gemini_cli_prompt_data.items.is_empty();
+    let gemini_cli_has_section =
+        gemini_cli_enabled && (gemini_cli_has_items || gemini_cli_has_prompt_items);
     let claude_prompt_submenu = if claude_has_prompt_items {
         Some(build_named_prompt_submenu(
             app,
@@ -694,6 +816,15 @@ async fn refresh_tray_menus_inner<R: Runtime>(app: &AppHandle<R>) -> Result<(),
     } else {
         None
     };
+    let gemini_cli_prompt_submenu = if gemini_cli_has_prompt_items {
+        Some(build_named_prompt_submenu(
+            app,
+            "gemini_cli",
+            &gemini_cli_prompt_data,
+        )?)
+    } else {
+        None
+    };
 
     // Claude Code section (only if enabled and has items)
     let claude_header = if claude_has_section {
@@ -760,6 +891,41 @@ async fn refresh_tray_menus_inner<R: Runtime>(app: &AppHandle<R>) -> Result<(),
         }
     }
 
+    let gemini_cli_header = if gemini_cli_has_section {
+        Some(
+            MenuItem::with_id(
+                app,
+                "gemini_cli_header",
+                &gemini_cli_data.title,
+                false,
+                None::<&str>,
+            )
+            .map_err(|e| e.to_string())?,
+        )
+    } else {
+        None
+    };
+
+    // Build Gemini CLI items (only if has items)
+    let mut gemini_cli_items: Vec<Box<dyn tauri::menu::IsMenuItem<R>>> = Vec::new();
+    if gemini_cli_has_items {
+        for item in gemini_cli_data.items {
+            let item_id = format!("gemini_cli_provider_{}", item.id);
+            let menu_item: Box<dyn tauri::menu::IsMenuItem<R>> = Box::new(
+                CheckMenuItem::with_id(
+                    app,
+                    &item_id,
+                    &item.display_name,
+                    !item.is_disabled,
+                    item.is_selected,
+                    None::<&str>,
+                )
+                .map_err(|e| e.to_string())?,
+            );
+            gemini_cli_items.push(menu_item);
+        }
+    }
+
     // OpenClaw section (only if enabled and has items)
     let openclaw_header = if openclaw_has_items {
         Some(
@@ -781,6 +947,13 @@ async fn refresh_tray_menus_inner<R: Runtime>(app: &AppHandle<R>) -> Result<(),
     all_items.push(&show_item);
     all_items.push(&separator1);
 
+    // Add workspace profile section if any
+    if let Some(ref header) = workspace_header {
+        all_items.push(header);
+    }
+    for item in &workspace_items {
+        all_items.push(item.as_ref());
+    }
     // Add OpenCode section if enabled
     if let Some(ref header) = opencode_model_header {
         all_items.push(header);
@@ -848,6 +1021,16 @@ async fn refresh_tray_menus_inner<R: Runtime>(app: &AppHandle<R>) -> Result<(),
     for item in &codex_items {
         all_items.push(item.as_ref());
     }
+    // Add Gemini CLI section if enabled
+    if let Some(ref header) = gemini_cli_header {
+        all_items.push(header);
+    }
+    if let Some(ref submenu) = gemini_cli_prompt_submenu {
+        all_items.push(submenu);
+    }
+    for item in &gemini_cli_items {
+        all_items.push(item.as_ref());
+    }
     // Add OpenClaw section if enabled
     if let Some(ref header) = openclaw_header {
         all_items.push(header);
@@ -1069,6 +1252,36 @@ impl NamedPromptTrayData for codex_tray::TrayPromptData {
     }
 }
 
+impl NamedPromptTrayItem for gemini_cli_tray::TrayPromptItem {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn display_name(&self) -> &str {
+        &self.display_name
+    }
+
+    fn is_selected(&self) -> bool {
+        self.is_selected
+    }
+}
+
+impl NamedPromptTrayData for gemini_cli_tray::TrayPromptData {
+    type Item = gemini_cli_tray::TrayPromptItem;
+
+    fn title(&self) -> &str {
+        &self.title
+    }
+
+    fn current_display(&self) -> &str {
+        &self.current_display
+    }
+
+    fn items(&self) -> &[Self::Item] {
+        &self.items
+    }
+}
+
 /// Build a skill submenu with tool checkmarks
 fn build_skill_submenu<R: Runtime>(
     app: &AppHandle<R>,
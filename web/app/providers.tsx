@@ -6,8 +6,9 @@ import { emit, listen } from '@tauri-apps/api/event';
 import { useAppStore, useSettingsStore } from '@/stores';
 import { useThemeStore } from '@/stores/themeStore';
 import { checkForUpdates, openExternalUrl, setWindowBackgroundColor, installUpdate, loadCachedPresetModels, fetchRemotePresetModels, GITHUB_REPO, type UpdateInfo } from '@/services';
-import { restartApp } from '@/services/settingsApi';
+import { restartApp, type ProviderBalanceAlert, type ProviderHealthAlert } from '@/services/settingsApi';
 import i18n from '@/i18n';
+import { ConfigValidationIssueList, type ConfigValidationReport } from '@/utils/configValidation';
 
 interface ProvidersProps {
   children: React.ReactNode;
@@ -208,6 +209,85 @@ const AppInitializer: React.FC<{ children: React.ReactNode }> = ({ children }) =
     };
   }, []);
 
+  // Listen for provider health alerts (consecutive failures / automatic failover)
+  React.useEffect(() => {
+    const unlisten = listen<ProviderHealthAlert>('provider-health-alert', (event) => {
+      const alert = event.payload;
+      const failedOver = !!alert.failoverProviderId;
+      notification.warning({
+        message: failedOver
+          ? i18n.t('settings.providerHealth.failoverTitle')
+          : i18n.t('settings.providerHealth.alertTitle'),
+        description: failedOver
+          ? i18n.t('settings.providerHealth.failoverMessage', {
+              from: alert.providerName,
+              to: alert.failoverProviderName,
+              count: alert.consecutiveFailures,
+            })
+          : i18n.t('settings.providerHealth.alertMessage', {
+              name: alert.providerName,
+              count: alert.consecutiveFailures,
+              error: alert.errorMessage || '',
+            }),
+        btn: failedOver ? (
+          <Button size="small" type="primary" onClick={() => window.location.reload()}>
+            {i18n.t('settings.providerHealth.reload')}
+          </Button>
+        ) : undefined,
+        duration: 0,
+      });
+    });
+
+    return () => {
+      unlisten.then((fn) => fn()).catch(console.error);
+    };
+  }, [notification]);
+
+  // Listen for low balance alerts of the applied All API Hub accounts
+  React.useEffect(() => {
+    const unlisten = listen<ProviderBalanceAlert>('provider-balance-alert', (event) => {
+      const alert = event.payload;
+      const description = i18n.t('settings.balanceMonitor.alertMessage', {
+        name: alert.accountName,
+        balance: alert.balanceUsd.toFixed(2),
+        threshold: alert.thresholdUsd.toFixed(2),
+        providers: alert.providers.map((p) => p.providerName).join(', '),
+      });
+      notification.warning({
+        message: i18n.t('settings.balanceMonitor.alertTitle'),
+        description:
+          alert.daysRemaining != null
+            ? `${description} ${i18n.t('settings.balanceMonitor.alertDaysRemaining', {
+                days: alert.daysRemaining.toFixed(1),
+              })}`
+            : description,
+        duration: 0,
+      });
+    });
+
+    return () => {
+      unlisten.then((fn) => fn()).catch(console.error);
+    };
+  }, [notification]);
+
+  // Listen for validation issues left in generated config files after a write
+  React.useEffect(() => {
+    const unlisten = listen<ConfigValidationReport>('config-validation-warning', (event) => {
+      const report = event.payload;
+      notification.warning({
+        message: i18n.t(
+          report.forced ? 'configValidation.forcedTitle' : 'configValidation.warningTitle',
+          { target: report.target },
+        ),
+        description: <ConfigValidationIssueList issues={report.issues} />,
+      });
+    });
+
+    return () => {
+      unlisten.then((fn) => fn()).catch(console.error);
+    };
+  }, [notification]);
+
   return (
     <>
       {children}
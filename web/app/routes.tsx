@@ -1,11 +1,13 @@
 import { createBrowserRouter, Navigate } from 'react-router-dom';
 import MainLayout from '@/components/layout/MainLayout';
 import { NotesPage } from '@/features/daily';
-import { OpenCodePage, ClaudeCodePage, CodexPage } from '@/features/coding';
+import { OpenCodePage, ClaudeCodePage, CodexPage, GeminiCliPage } from '@/features/coding';
 import { OpenClawPage } from '@/features/coding/openclaw';
 import { SettingsPage } from '@/features/settings';
 import { SkillsPage } from '@/features/coding/skills';
 import { McpPage } from '@/features/coding/mcp';
+import { UsagePage } from '@/features/coding/usage';
+import { SessionHistoryPage } from '@/features/coding/sessions';
 import { DEFAULT_PATH } from '@/constants';
 
 export const router = createBrowserRouter([
@@ -33,6 +35,10 @@ export const router = createBrowserRouter([
         path: 'coding/codex',
         element: <CodexPage />,
       },
+      {
+        path: 'coding/geminicli',
+        element: <GeminiCliPage />,
+      },
       {
         path: 'coding/openclaw',
         element: <OpenClawPage />,
@@ -49,6 +55,14 @@ export const router = createBrowserRouter([
         path: 'mcp',
         element: <McpPage />,
       },
+      {
+        path: 'usage',
+        element: <UsagePage />,
+      },
+      {
+        path: 'sessions',
+        element: <SessionHistoryPage />,
+      },
     ],
   },
 ]);
@@ -445,7 +445,8 @@ function ImportExternalProvidersModal<TConfig>({
                             <div className={styles.infoItem}>
                               <AppstoreOutlined className={styles.icon} />
                               <Text className={styles.infoText}>
-                                {profileLabel}: {item.sourceProfileName}
+                                {profileLabel}: {item.sourceBrowser ? `${item.sourceBrowser} / ` : ''}
+                                {item.sourceProfileName}
                               </Text>
                             </div>
                           )}
@@ -5,6 +5,7 @@ export interface ExternalProviderDisplayItem<TConfig> {
   accountLabel: string;
   siteName?: string;
   siteType?: string;
+  sourceBrowser?: string;
   sourceProfileName: string;
   sourceExtensionId: string;
   requiresBrowserOpen?: boolean;
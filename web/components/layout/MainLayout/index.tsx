@@ -15,12 +15,14 @@ import { SSHSyncModal } from '@/features/settings/components/SSHSyncModal';
 import { useSSHSync } from '@/features/settings/hooks/useSSHSync';
 import { SkillsButton } from '@/features/coding/skills';
 import { McpButton } from '@/features/coding/mcp';
+import { UsageButton } from '@/features/coding/usage';
+import { SessionsButton } from '@/features/coding/sessions';
 import styles from './styles.module.less';
 
 import OpencodeIcon from '@/assets/opencode.svg';
 import ClaudeIcon from '@/assets/claude.svg';
 import ChatgptIcon from '@/assets/chatgpt.svg';
-import { OpenClaw as OpenClawIcon } from '@lobehub/icons';
+import { OpenClaw as OpenClawIcon, Gemini as GeminiIcon } from '@lobehub/icons';
 
 const TAB_ICONS: Record<string, string> = {
   opencode: OpencodeIcon,
@@ -66,7 +68,9 @@ const MainLayout: React.FC = () => {
   const isSettingsPage = location.pathname.startsWith('/settings');
   const isSkillsPage = location.pathname.startsWith('/skills');
   const isMcpPage = location.pathname.startsWith('/mcp');
-  const isNonTabPage = isSettingsPage || isSkillsPage || isMcpPage;
+  const isUsagePage = location.pathname.startsWith('/usage');
+  const isSessionsPage = location.pathname.startsWith('/sessions');
+  const isNonTabPage = isSettingsPage || isSkillsPage || isMcpPage || isUsagePage || isSessionsPage;
 
   // Get coding module's subTabs, filtered and ordered by visibility settings
   const codingModule = MODULES.find((m) => m.key === 'coding');
@@ -169,6 +173,8 @@ const MainLayout: React.FC = () => {
                         ) : (
                           <OpenClawIcon.Color size={16} className={styles.tabIconColor} />
                         )
+                      ) : tab.key === 'geminicli' ? (
+                        <GeminiIcon.Color size={16} className={styles.tabIconColor} />
                       ) : TAB_ICONS[tab.key] ? (
                         <img src={TAB_ICONS[tab.key]} className={styles.tabIcon} alt="" />
                       ) : null}
@@ -194,6 +200,7 @@ const MainLayout: React.FC = () => {
                         ? 'error'
                         : 'idle'
                   }
+                  targetCount={sshStatus.targetCount}
                   onClick={() => window.dispatchEvent(new CustomEvent('open-ssh-settings'))}
                 />
                 <div className={styles.actionsDivider} />
@@ -227,6 +234,14 @@ const MainLayout: React.FC = () => {
             <McpButton />
             <div className={styles.actionsDivider} />
 
+            {/* Usage button */}
+            <UsageButton />
+            <div className={styles.actionsDivider} />
+
+            {/* Session history button */}
+            <SessionsButton />
+            <div className={styles.actionsDivider} />
+
             {/* Settings button */}
             <div
               className={`${styles.settingsBtn} ${isSettingsPage ? styles.active : ''}`}
@@ -25,6 +25,7 @@ export const MODULES: Module[] = [
       { key: 'opencode', labelKey: 'subModules.opencode', path: '/coding/opencode' },
       { key: 'claudecode', labelKey: 'subModules.claudecode', path: '/coding/claudecode' },
       { key: 'codex', labelKey: 'subModules.codex', path: '/coding/codex' },
+      { key: 'geminicli', labelKey: 'subModules.geminicli', path: '/coding/geminicli' },
       { key: 'openclaw', labelKey: 'subModules.openclaw', path: '/coding/openclaw' },
     ],
   },
@@ -19,6 +19,8 @@ export interface PresetModel {
   temperature?: boolean;
   variants?: Record<string, unknown>;
   options?: Record<string, unknown>;
+  /** USD per 1M tokens */
+  cost?: { input: number; output: number; cacheRead?: number; cacheWrite?: number };
 }
 
 /**
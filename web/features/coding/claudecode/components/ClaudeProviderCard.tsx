@@ -173,6 +173,9 @@ const ClaudeProviderCard: React.FC<ClaudeProviderCardProps> = ({
                   {settingsConfig.env.ANTHROPIC_BASE_URL}
                 </Text>
               )}
+              {settingsConfig.apiFormat === 'openai_chat' && (
+                <Tag color="blue">{t('claudecode.provider.apiFormatOpenAiTag')}</Tag>
+              )}
               {isApplied && (
                 <Tag color="green" icon={<CheckCircleOutlined />}>
                   {t('claudecode.provider.applied')}
@@ -95,6 +95,7 @@ const ClaudeProviderFormModal: React.FC<ClaudeProviderFormModalProps> = ({
         name: provider.name,
         baseUrl,
         apiKey: settingsConfig.env?.ANTHROPIC_AUTH_TOKEN || settingsConfig.env?.ANTHROPIC_API_KEY,
+        apiFormat: settingsConfig.apiFormat || 'anthropic',
         model: settingsConfig.model,
         haikuModel: settingsConfig.haikuModel,
         sonnetModel: settingsConfig.sonnetModel,
@@ -141,24 +142,26 @@ const ClaudeProviderFormModal: React.FC<ClaudeProviderFormModalProps> = ({
   const handleFetchModels = async () => {
     const baseUrl = form.getFieldValue('baseUrl');
     const apiKey = form.getFieldValue('apiKey');
+    const isOpenAiChat = form.getFieldValue('apiFormat') === 'openai_chat';
 
     if (!baseUrl) {
       message.warning(t('claudecode.fetchModels.baseUrlRequired'));
       return;
     }
 
-    // 构建 customUrl：在 baseUrl 后追加 /v1/models
+    // 构建 customUrl：Anthropic 格式在 baseUrl 后追加 /v1/models，OpenAI 格式的 baseUrl 已包含 /v1
     const base = baseUrl.replace(/\/$/, '');
-    const customUrl = `${base}/v1/models`;
+    const apiBase = isOpenAiChat ? base : `${base}/v1`;
+    const customUrl = `${apiBase}/models`;
 
     setLoadingModels(true);
     try {
       const response = await invoke<FetchModelsResponse>('fetch_provider_models', {
         request: {
-          baseUrl: `${base}/v1`,
+          baseUrl: apiBase,
           apiKey,
-          apiType: fetchApiType,
-          sdkType: '@ai-sdk/anthropic',
+          apiType: isOpenAiChat ? 'openai_compat' : fetchApiType,
+          sdkType: isOpenAiChat ? '@ai-sdk/openai-compatible' : '@ai-sdk/anthropic',
           customUrl,
         },
       });
@@ -209,7 +212,7 @@ const ClaudeProviderFormModal: React.FC<ClaudeProviderFormModalProps> = ({
       // 只验证当前模式需要的字段
       const fieldsToValidate = mode === 'import'
         ? ['sourceProvider', 'name', 'baseUrl', 'apiKey', 'model', 'haikuModel', 'sonnetModel', 'opusModel', 'reasoningModel', 'notes']
-        : ['name', 'baseUrl', 'apiKey', 'model', 'haikuModel', 'sonnetModel', 'opusModel', 'reasoningModel', 'notes'];
+        : ['name', 'baseUrl', 'apiKey', 'apiFormat', 'model', 'haikuModel', 'sonnetModel', 'opusModel', 'reasoningModel', 'notes'];
       
       const values = await form.validateFields(fieldsToValidate);
       
@@ -220,6 +223,7 @@ const ClaudeProviderFormModal: React.FC<ClaudeProviderFormModalProps> = ({
         category: 'custom',
         baseUrl: values.baseUrl,
         apiKey: values.apiKey,
+        apiFormat: mode === 'import' ? undefined : values.apiFormat,
         model: values.model,
         haikuModel: values.haikuModel,
         sonnetModel: values.sonnetModel,
@@ -313,6 +317,7 @@ const ClaudeProviderFormModal: React.FC<ClaudeProviderFormModalProps> = ({
       layout="horizontal"
       labelCol={labelCol}
       wrapperCol={wrapperCol}
+      initialValues={{ apiFormat: 'anthropic' }}
     >
       <Form.Item
         name="name"
@@ -354,6 +359,23 @@ const ClaudeProviderFormModal: React.FC<ClaudeProviderFormModalProps> = ({
         />
       </Form.Item>
 
+      <Form.Item
+        name="apiFormat"
+        label={t('claudecode.provider.apiFormat')}
+        extra={
+          <Form.Item noStyle shouldUpdate={(prev, cur) => prev.apiFormat !== cur.apiFormat}>
+            {({ getFieldValue }) =>
+              getFieldValue('apiFormat') === 'openai_chat' ? t('claudecode.provider.apiFormatOpenAiHint') : null
+            }
+          </Form.Item>
+        }
+      >
+        <Radio.Group>
+          <Radio value="anthropic">{t('claudecode.provider.apiFormatAnthropic')}</Radio>
+          <Radio value="openai_chat">{t('claudecode.provider.apiFormatOpenAi')}</Radio>
+        </Radio.Group>
+      </Form.Item>
+
       {/* 获取模型列表 */}
       <Form.Item wrapperCol={{ offset: labelCol.span, span: wrapperCol.span }}>
         <Space size="middle" style={{ width: '100%' }}>
@@ -37,6 +37,8 @@ import {
   selectClaudeProvider,
   applyClaudeConfig,
   readClaudeSettings,
+  detectClaudeConfigDrift,
+  importClaudeConfigDrift,
   toggleClaudeCodeProviderDisabled,
   reorderClaudeProviders,
 } from '@/services/claudeCodeApi';
@@ -52,6 +54,7 @@ import ClaudeCodeSettingsModal from '../components/ClaudeCodeSettingsModal';
 import JsonPreviewModal from '@/components/common/JsonPreviewModal';
 import AllApiHubIcon from '@/components/common/AllApiHubIcon';
 import { GlobalPromptSettings } from '@/features/coding/shared/prompt';
+import { ConfigDriftAlert } from '@/features/coding/shared/configDrift';
 import type { OpenCodeAllApiHubProvider } from '@/services/opencodeApi';
 
 const { Title, Text, Link } = Typography;
@@ -81,6 +84,7 @@ const ClaudeCodePage: React.FC = () => {
   const [providerListCollapsed, setProviderListCollapsed] = React.useState(false);
   const [allApiHubImportModalOpen, setAllApiHubImportModalOpen] = React.useState(false);
   const [allApiHubAvailable, setAllApiHubAvailable] = React.useState(false);
+  const [driftCheckKey, setDriftCheckKey] = React.useState(0);
 
   // 配置拖拽传感器
   const sensors = useSensors(
@@ -125,6 +129,7 @@ const ClaudeCodePage: React.FC = () => {
 
       const applied = providerList.find((p) => p.isApplied);
       setAppliedProviderId(applied?.id || '');
+      setDriftCheckKey((key) => key + 1);
     } catch (error) {
       console.error('Failed to load config:', error);
       message.error(t('common.error'));
@@ -313,18 +318,22 @@ const ClaudeCodePage: React.FC = () => {
   const handleImportFromAllApiHub = async (imported: OpenCodeAllApiHubProvider[]) => {
     try {
       for (const item of imported) {
+        // 非 Anthropic 协议的站点按 OpenAI Chat Completions 导入，保留 /v1，由本地网关转换协议
+        const isOpenAiChat = item.providerConfig.npm !== '@ai-sdk/anthropic';
+        const baseURL = item.providerConfig.options?.baseURL;
         const providerInput: ClaudeProviderInput = {
           name: item.name,
           category: 'custom',
           settingsConfig: JSON.stringify({
             env: {
-              ...(item.providerConfig.options?.baseURL && {
-                ANTHROPIC_BASE_URL: item.providerConfig.options.baseURL.replace(/\/v1$/, ''),
+              ...(baseURL && {
+                ANTHROPIC_BASE_URL: isOpenAiChat ? baseURL : baseURL.replace(/\/v1$/, ''),
               }),
               ...(item.providerConfig.options?.apiKey && {
                 ANTHROPIC_AUTH_TOKEN: item.providerConfig.options.apiKey,
               }),
             },
+            ...(isOpenAiChat && { apiFormat: 'openai_chat' }),
           }),
           sourceProviderId: item.providerId,
           notes: undefined,
@@ -357,6 +366,7 @@ const ClaudeCodePage: React.FC = () => {
       if (values.sonnetModel) settingsConfigObj.sonnetModel = values.sonnetModel;
       if (values.opusModel) settingsConfigObj.opusModel = values.opusModel;
       if (values.reasoningModel) settingsConfigObj.reasoningModel = values.reasoningModel;
+      if (values.apiFormat === 'openai_chat') settingsConfigObj.apiFormat = values.apiFormat;
 
       // Check if this is a temporary provider from local file
       const isLocalTemp = editingProvider?.id === "__local__";
@@ -419,6 +429,7 @@ const ClaudeCodePage: React.FC = () => {
       if (values.sonnetModel) settingsConfigObj.sonnetModel = values.sonnetModel;
       if (values.opusModel) settingsConfigObj.opusModel = values.opusModel;
       if (values.reasoningModel) settingsConfigObj.reasoningModel = values.reasoningModel;
+      if (values.apiFormat === 'openai_chat') settingsConfigObj.apiFormat = values.apiFormat;
 
       const providerData: ClaudeCodeProvider = {
         ...existingProvider,
@@ -522,6 +533,20 @@ const ClaudeCodePage: React.FC = () => {
         </div>
       </div>
 
+      {/* 配置文件被外部修改提示 */}
+      <ConfigDriftAlert
+        detect={detectClaudeConfigDrift}
+        refreshKey={driftCheckKey}
+        onImport={async () => {
+          await importClaudeConfigDrift();
+          await loadConfig();
+        }}
+        onReapply={async () => {
+          await applyClaudeConfig(appliedProviderId);
+          await loadConfig();
+        }}
+      />
+
       {/* Provider 列表 */}
       <Collapse
         style={{ marginBottom: 16 }}
@@ -0,0 +1,310 @@
+import React from 'react';
+import { Modal, Alert, Button, Input, Select, Space, Table, Typography, message } from 'antd';
+import { DeleteOutlined, PlusOutlined, ThunderboltOutlined } from '@ant-design/icons';
+import { useTranslation } from 'react-i18next';
+import type { CodexModelProviderEntry, CodexProfileEntry, CodexProfilesConfig } from '@/types/codex';
+import { readCodexProfiles, generateCodexProfiles, saveCodexProfiles } from '@/services/codexApi';
+
+const { Text } = Typography;
+
+// 与 config_validation 中的 CODEX_BUILTIN_PROVIDERS 保持一致
+const BUILTIN_MODEL_PROVIDERS = ['openai', 'oss', 'ollama', 'lmstudio'];
+const REASONING_EFFORTS = ['minimal', 'low', 'medium', 'high', 'xhigh'];
+const WIRE_APIS = ['responses', 'chat'];
+
+interface CodexProfilesModalProps {
+  open: boolean;
+  onCancel: () => void;
+  onSuccess: () => void;
+}
+
+const EMPTY_CONFIG: CodexProfilesConfig = { modelProviders: [], profiles: [] };
+
+const CodexProfilesModal: React.FC<CodexProfilesModalProps> = ({ open, onCancel, onSuccess }) => {
+  const { t } = useTranslation();
+  const [loading, setLoading] = React.useState(false);
+  const [saving, setSaving] = React.useState(false);
+  const [config, setConfig] = React.useState<CodexProfilesConfig>(EMPTY_CONFIG);
+
+  const runLoad = async (loader: () => Promise<CodexProfilesConfig>) => {
+    setLoading(true);
+    try {
+      setConfig(await loader());
+    } catch (error) {
+      console.error('Failed to load Codex profiles:', error);
+      const errorMsg = error instanceof Error ? error.message : String(error);
+      message.error(errorMsg || t('common.error'));
+    } finally {
+      setLoading(false);
+    }
+  };
+
+  React.useEffect(() => {
+    if (open) {
+      runLoad(readCodexProfiles);
+    }
+  }, [open]);
+
+  // 修改 ID 时同步更新引用它的 profile
+  const updateProvider = (index: number, patch: Partial<CodexModelProviderEntry>) => {
+    setConfig((prev) => {
+      const oldId = prev.modelProviders[index]?.id;
+      const renamed = patch.id !== undefined && oldId && patch.id !== oldId;
+      return {
+        ...prev,
+        modelProviders: prev.modelProviders.map((p, i) => (i === index ? { ...p, ...patch } : p)),
+        profiles: renamed
+          ? prev.profiles.map((p) => (p.modelProvider === oldId ? { ...p, modelProvider: patch.id } : p))
+          : prev.profiles,
+      };
+    });
+  };
+
+  // 修改名称时同步更新默认 profile
+  const updateProfile = (index: number, patch: Partial<CodexProfileEntry>) => {
+    setConfig((prev) => {
+      const oldName = prev.profiles[index]?.name;
+      const renamed = patch.name !== undefined && oldName && prev.defaultProfile === oldName;
+      return {
+        ...prev,
+        defaultProfile: renamed ? patch.name : prev.defaultProfile,
+        profiles: prev.profiles.map((p, i) => (i === index ? { ...p, ...patch } : p)),
+      };
+    });
+  };
+
+  const removeProvider = (index: number) => {
+    setConfig((prev) => ({
+      ...prev,
+      modelProviders: prev.modelProviders.filter((_, i) => i !== index),
+    }));
+  };
+
+  const removeProfile = (index: number) => {
+    setConfig((prev) => {
+      const removed = prev.profiles[index];
+      return {
+        ...prev,
+        defaultProfile: prev.defaultProfile === removed?.name ? undefined : prev.defaultProfile,
+        profiles: prev.profiles.filter((_, i) => i !== index),
+      };
+    });
+  };
+
+  const handleSave = async () => {
+    setSaving(true);
+    try {
+      await saveCodexProfiles(config);
+      message.success(t('common.success'));
+      onSuccess();
+    } catch (error) {
+      console.error('Failed to save Codex profiles:', error);
+      const errorMsg = error instanceof Error ? error.message : String(error);
+      message.error(errorMsg || t('common.error'));
+    } finally {
+      setSaving(false);
+    }
+  };
+
+  const modelProviderOptions = [
+    ...config.modelProviders.filter((p) => p.id).map((p) => ({ value: p.id, label: p.name ? `${p.id} (${p.name})` : p.id })),
+    ...BUILTIN_MODEL_PROVIDERS.map((id) => ({ value: id, label: id })),
+  ];
+
+  return (
+    <Modal
+      title={t('codex.profiles.title')}
+      open={open}
+      onCancel={onCancel}
+      onOk={handleSave}
+      confirmLoading={saving}
+      width={960}
+      okText={t('common.save')}
+      cancelText={t('common.cancel')}
+    >
+      <Alert
+        message={t('codex.profiles.hint')}
+        type="info"
+        showIcon
+        style={{ marginBottom: 16 }}
+      />
+
+      <Space style={{ marginBottom: 16 }} wrap>
+        <Text>{t('codex.profiles.defaultProfile')}:</Text>
+        <Select
+          allowClear
+          style={{ minWidth: 200 }}
+          placeholder={t('codex.profiles.defaultProfilePlaceholder')}
+          value={config.defaultProfile}
+          onChange={(value) => setConfig((prev) => ({ ...prev, defaultProfile: value || undefined }))}
+          options={config.profiles.filter((p) => p.name).map((p) => ({ value: p.name, label: p.name }))}
+        />
+        <Button
+          icon={<ThunderboltOutlined />}
+          loading={loading}
+          onClick={() => runLoad(generateCodexProfiles)}
+        >
+          {t('codex.profiles.generate')}
+        </Button>
+      </Space>
+
+      <Text strong>[profiles]</Text>
+      <Table<CodexProfileEntry>
+        size="small"
+        loading={loading}
+        pagination={false}
+        rowKey={(_, index) => `profile-${index}`}
+        dataSource={config.profiles}
+        style={{ margin: '8px 0 8px' }}
+        columns={[
+          {
+            title: t('codex.profiles.name'),
+            dataIndex: 'name',
+            width: 160,
+            render: (value: string, _, index) => (
+              <Input size="small" value={value} onChange={(e) => updateProfile(index, { name: e.target.value })} />
+            ),
+          },
+          {
+            title: 'model_provider',
+            dataIndex: 'modelProvider',
+            width: 200,
+            render: (value: string | undefined, _, index) => (
+              <Select
+                size="small"
+                allowClear
+                style={{ width: '100%' }}
+                value={value}
+                options={modelProviderOptions}
+                onChange={(next) => updateProfile(index, { modelProvider: next || undefined })}
+              />
+            ),
+          },
+          {
+            title: 'model',
+            dataIndex: 'model',
+            render: (value: string | undefined, _, index) => (
+              <Input size="small" value={value} onChange={(e) => updateProfile(index, { model: e.target.value })} />
+            ),
+          },
+          {
+            title: 'model_reasoning_effort',
+            dataIndex: 'modelReasoningEffort',
+            width: 180,
+            render: (value: string | undefined, _, index) => (
+              <Select
+                size="small"
+                allowClear
+                style={{ width: '100%' }}
+                value={value}
+                options={REASONING_EFFORTS.map((effort) => ({ value: effort, label: effort }))}
+                onChange={(next) => updateProfile(index, { modelReasoningEffort: next || undefined })}
+              />
+            ),
+          },
+          {
+            width: 40,
+            render: (_, __, index) => (
+              <Button type="text" size="small" danger icon={<DeleteOutlined />} onClick={() => removeProfile(index)} />
+            ),
+          },
+        ]}
+      />
+      <Button
+        type="dashed"
+        size="small"
+        icon={<PlusOutlined />}
+        style={{ marginBottom: 16 }}
+        onClick={() => setConfig((prev) => ({ ...prev, profiles: [...prev.profiles, { name: '' }] }))}
+      >
+        {t('codex.profiles.addProfile')}
+      </Button>
+
+      <div>
+        <Text strong>[model_providers]</Text>
+      </div>
+      <Table<CodexModelProviderEntry>
+        size="small"
+        loading={loading}
+        pagination={false}
+        rowKey={(_, index) => `provider-${index}`}
+        dataSource={config.modelProviders}
+        style={{ margin: '8px 0 8px' }}
+        columns={[
+          {
+            title: 'ID',
+            dataIndex: 'id',
+            width: 140,
+            render: (value: string, _, index) => (
+              <Input size="small" value={value} onChange={(e) => updateProvider(index, { id: e.target.value })} />
+            ),
+          },
+          {
+            title: 'name',
+            dataIndex: 'name',
+            width: 140,
+            render: (value: string | undefined, _, index) => (
+              <Input size="small" value={value} onChange={(e) => updateProvider(index, { name: e.target.value })} />
+            ),
+          },
+          {
+            title: 'base_url',
+            dataIndex: 'baseUrl',
+            render: (value: string | undefined, _, index) => (
+              <Input size="small" value={value} onChange={(e) => updateProvider(index, { baseUrl: e.target.value })} />
+            ),
+          },
+          {
+            title: 'wire_api',
+            dataIndex: 'wireApi',
+            width: 120,
+            render: (value: string | undefined, _, index) => (
+              <Select
+                size="small"
+                allowClear
+                style={{ width: '100%' }}
+                value={value}
+                options={WIRE_APIS.map((api) => ({ value: api, label: api }))}
+                onChange={(next) => updateProvider(index, { wireApi: next || undefined })}
+              />
+            ),
+          },
+          {
+            title: t('codex.profiles.apiKey'),
+            dataIndex: 'experimentalBearerToken',
+            width: 180,
+            render: (value: string | undefined, record, index) => (
+              <Input.Password
+                size="small"
+                value={value}
+                placeholder={record.envKey ? `$${record.envKey}` : record.requiresOpenaiAuth ? 'auth.json' : undefined}
+                onChange={(e) => updateProvider(index, { experimentalBearerToken: e.target.value })}
+              />
+            ),
+          },
+          {
+            width: 40,
+            render: (_, __, index) => (
+              <Button type="text" size="small" danger icon={<DeleteOutlined />} onClick={() => removeProvider(index)} />
+            ),
+          },
+        ]}
+      />
+      <Button
+        type="dashed"
+        size="small"
+        icon={<PlusOutlined />}
+        onClick={() =>
+          setConfig((prev) => ({
+            ...prev,
+            modelProviders: [...prev.modelProviders, { id: '', wireApi: 'responses' }],
+          }))
+        }
+      >
+        {t('codex.profiles.addModelProvider')}
+      </Button>
+    </Modal>
+  );
+};
+
+export default CodexProfilesModal;
@@ -69,6 +69,7 @@ const ImportFromAllApiHubModal: React.FC<Props> = ({
       accountLabel: provider.accountLabel,
       siteName: provider.siteName || undefined,
       siteType: provider.siteType || undefined,
+      sourceBrowser: provider.sourceBrowser,
       sourceProfileName: provider.sourceProfileName,
       sourceExtensionId: provider.sourceExtensionId,
       requiresBrowserOpen: provider.requiresBrowserOpen,
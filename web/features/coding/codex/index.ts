@@ -2,4 +2,5 @@ export { default as CodexPage } from './pages/CodexPage';
 export { default as CodexProviderCard } from './components/CodexProviderCard';
 export { default as CodexProviderFormModal } from './components/CodexProviderFormModal';
 export { default as CodexCommonConfigModal } from './components/CodexCommonConfigModal';
+export { default as CodexProfilesModal } from './components/CodexProfilesModal';
 export { default as ImportConflictDialog } from './components/ImportConflictDialog';
@@ -1,6 +1,6 @@
 import React from 'react';
 import { Typography, Button, Space, Empty, message, Modal, Spin, Collapse } from 'antd';
-import { PlusOutlined, FolderOpenOutlined, AppstoreOutlined, SyncOutlined, EyeOutlined, ExclamationCircleOutlined, LinkOutlined, DatabaseOutlined, ImportOutlined } from '@ant-design/icons';
+import { PlusOutlined, FolderOpenOutlined, AppstoreOutlined, SyncOutlined, EyeOutlined, ExclamationCircleOutlined, LinkOutlined, DatabaseOutlined, ImportOutlined, ProfileOutlined } from '@ant-design/icons';
 import { useTranslation } from 'react-i18next';
 import { openUrl, revealItemInDir } from '@tauri-apps/plugin-opener';
 import { invoke } from '@tauri-apps/api/core';
@@ -34,6 +34,8 @@ import {
   selectCodexProvider,
   applyCodexConfig,
   readCodexSettings,
+  detectCodexConfigDrift,
+  importCodexConfigDrift,
   createCodexProvider,
   updateCodexProvider,
   saveCodexLocalConfig,
@@ -46,11 +48,13 @@ import { refreshTrayMenu, hasAllApiHubExtension } from '@/services/appApi';
 import CodexProviderCard from '../components/CodexProviderCard';
 import CodexProviderFormModal from '../components/CodexProviderFormModal';
 import CodexCommonConfigModal from '../components/CodexCommonConfigModal';
+import CodexProfilesModal from '../components/CodexProfilesModal';
 import ImportConflictDialog from '../components/ImportConflictDialog';
 import ImportFromAllApiHubModal from '../components/ImportFromAllApiHubModal';
 import AllApiHubIcon from '@/components/common/AllApiHubIcon';
 import JsonPreviewModal from '@/components/common/JsonPreviewModal';
 import { GlobalPromptSettings } from '@/features/coding/shared/prompt';
+import { ConfigDriftAlert } from '@/features/coding/shared/configDrift';
 import type { OpenCodeAllApiHubProvider } from '@/services/opencodeApi';
 
 const { Title, Text, Link } = Typography;
@@ -68,6 +72,7 @@ const CodexPage: React.FC = () => {
   const [isCopyMode, setIsCopyMode] = React.useState(false);
   const [providerModalMode, setProviderModalMode] = React.useState<'manual' | 'import'>('manual');
   const [commonConfigModalOpen, setCommonConfigModalOpen] = React.useState(false);
+  const [profilesModalOpen, setProfilesModalOpen] = React.useState(false);
   const [conflictDialogOpen, setConflictDialogOpen] = React.useState(false);
   const [conflictInfo, setConflictInfo] = React.useState<ImportConflictInfo | null>(null);
   const [pendingFormValues, setPendingFormValues] = React.useState<CodexProviderFormValues | null>(null);
@@ -76,6 +81,7 @@ const CodexPage: React.FC = () => {
   const [providerListCollapsed, setProviderListCollapsed] = React.useState(false);
   const [allApiHubImportModalOpen, setAllApiHubImportModalOpen] = React.useState(false);
   const [allApiHubAvailable, setAllApiHubAvailable] = React.useState(false);
+  const [driftCheckKey, setDriftCheckKey] = React.useState(0);
 
   // 配置拖拽传感器
   const sensors = useSensors(
@@ -100,6 +106,7 @@ const CodexPage: React.FC = () => {
       setProviders(providerList);
       const applied = providerList.find((p) => p.isApplied);
       setAppliedProviderId(applied?.id || '');
+      setDriftCheckKey((key) => key + 1);
     } catch (error) {
       console.error('Failed to load config:', error);
       const errorMsg = error instanceof Error ? error.message : String(error);
@@ -548,6 +555,20 @@ settingsConfig = JSON.stringify(settingsConfigObj);
         </div>
       </div>
 
+      {/* Config files edited outside the app */}
+      <ConfigDriftAlert
+        detect={detectCodexConfigDrift}
+        refreshKey={driftCheckKey}
+        onImport={async () => {
+          await importCodexConfigDrift();
+          await loadConfig();
+        }}
+        onReapply={async () => {
+          await applyCodexConfig(appliedProviderId);
+          await loadConfig();
+        }}
+      />
+
       {/* Provider List */}
       <Collapse
         style={{ marginBottom: 16 }}
@@ -576,6 +597,18 @@ settingsConfig = JSON.stringify(settingsConfigObj);
                 >
                   {t('codex.commonConfigButton')}
                 </Button>
+                <Button
+                  type="link"
+                  size="small"
+                  style={{ fontSize: 12 }}
+                  icon={<ProfileOutlined />}
+                  onClick={(e) => {
+                    e.stopPropagation();
+                    setProfilesModalOpen(true);
+                  }}
+                >
+                  {t('codex.profilesButton')}
+                </Button>
                 <Button
                   type="link"
                   size="small"
@@ -694,6 +727,15 @@ settingsConfig = JSON.stringify(settingsConfigObj);
         isLocalProvider={providers.some((provider) => provider.id === '__local__')}
       />
 
+      <CodexProfilesModal
+        open={profilesModalOpen}
+        onCancel={() => setProfilesModalOpen(false)}
+        onSuccess={async () => {
+          setProfilesModalOpen(false);
+          await loadConfig();
+        }}
+      />
+
       <ImportConflictDialog
         open={conflictDialogOpen}
         conflictInfo={conflictInfo}
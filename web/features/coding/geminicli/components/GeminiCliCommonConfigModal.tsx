@@ -0,0 +1,117 @@
+import React from 'react';
+import { Modal, Alert, message } from 'antd';
+import { useTranslation } from 'react-i18next';
+import {
+  getGeminiCliCommonConfig,
+  saveGeminiCliCommonConfig,
+  saveGeminiCliLocalConfig,
+} from '@/services/geminiCliApi';
+import JsonEditor from '@/components/common/JsonEditor';
+
+interface GeminiCliCommonConfigModalProps {
+  open: boolean;
+  onCancel: () => void;
+  onSuccess: () => void;
+  isLocalProvider?: boolean;
+}
+
+const GeminiCliCommonConfigModal: React.FC<GeminiCliCommonConfigModalProps> = ({
+  open,
+  onCancel,
+  onSuccess,
+  isLocalProvider = false,
+}) => {
+  const { t } = useTranslation();
+  const [loading, setLoading] = React.useState(false);
+  const [configValue, setConfigValue] = React.useState<unknown>({});
+  const [isJsonValid, setIsJsonValid] = React.useState(true);
+
+  // Load existing config
+  React.useEffect(() => {
+    if (open) {
+      loadConfig();
+    }
+  }, [open]);
+
+  const loadConfig = async () => {
+    try {
+      const config = await getGeminiCliCommonConfig();
+      setConfigValue(config?.config ? JSON.parse(config.config) : {});
+      setIsJsonValid(true);
+    } catch (error) {
+      console.error('Failed to load common config:', error);
+      const errorMsg = error instanceof Error ? error.message : String(error);
+      message.error(errorMsg || t('common.error'));
+    }
+  };
+
+  const handleSave = async () => {
+    // 通用配置必须是 JSON 对象
+    if (!isJsonValid || typeof configValue !== 'object' || configValue === null || Array.isArray(configValue)) {
+      message.error(t('geminicli.commonConfig.invalidJson'));
+      return;
+    }
+
+    setLoading(true);
+    try {
+      const config = JSON.stringify(configValue, null, 2);
+      if (isLocalProvider) {
+        await saveGeminiCliLocalConfig({ commonConfig: config });
+      } else {
+        await saveGeminiCliCommonConfig(config);
+      }
+      message.success(t('common.success'));
+      onSuccess();
+      onCancel();
+    } catch (error) {
+      console.error('Failed to save common config:', error);
+      const errorMsg = error instanceof Error ? error.message : String(error);
+      message.error(errorMsg || t('common.error'));
+    } finally {
+      setLoading(false);
+    }
+  };
+
+  return (
+    <Modal
+      title={t('geminicli.commonConfig.title')}
+      open={open}
+      onCancel={onCancel}
+      onOk={handleSave}
+      confirmLoading={loading}
+      width={800}
+      okText={t('common.save')}
+      cancelText={t('common.cancel')}
+    >
+      {isLocalProvider && (
+        <Alert
+          message={t('geminicli.localConfigHint')}
+          type="warning"
+          showIcon
+          style={{ marginBottom: 16 }}
+        />
+      )}
+      <JsonEditor
+        value={configValue}
+        onChange={(value, isValid) => {
+          setIsJsonValid(isValid);
+          if (isValid) {
+            setConfigValue(value);
+          }
+        }}
+        height={400}
+        placeholder={t('geminicli.commonConfig.placeholder')}
+      />
+
+      <div style={{ marginTop: 12 }}>
+        <Alert
+          message={t('geminicli.commonConfig.description')}
+          type="info"
+          showIcon
+        />
+      </div>
+    </Modal>
+  );
+};
+
+export default GeminiCliCommonConfigModal;
@@ -0,0 +1,177 @@
+import React from 'react';
+import { Modal, Form, Input, Select, Button, Typography } from 'antd';
+import { EyeInvisibleOutlined, EyeOutlined } from '@ant-design/icons';
+import { useTranslation } from 'react-i18next';
+import { useAppStore } from '@/stores';
+import type {
+  GeminiCliProvider,
+  GeminiCliProviderFormValues,
+  GeminiCliSettingsConfig,
+} from '@/types/geminiCli';
+
+const { Text } = Typography;
+const { TextArea } = Input;
+
+interface GeminiCliProviderFormModalProps {
+  open: boolean;
+  provider?: GeminiCliProvider | null;
+  isCopy?: boolean;
+  onCancel: () => void;
+  onSubmit: (values: GeminiCliProviderFormValues) => Promise<void>;
+}
+
+const parseSettingsConfig = (settingsConfig?: string): GeminiCliSettingsConfig => {
+  if (!settingsConfig) return {};
+  try {
+    return JSON.parse(settingsConfig);
+  } catch {
+    return {};
+  }
+};
+
+const GeminiCliProviderFormModal: React.FC<GeminiCliProviderFormModalProps> = ({
+  open,
+  provider,
+  isCopy = false,
+  onCancel,
+  onSubmit,
+}) => {
+  const { t } = useTranslation();
+  const language = useAppStore((state) => state.language);
+  const [form] = Form.useForm<GeminiCliProviderFormValues>();
+  const [loading, setLoading] = React.useState(false);
+  const [showApiKey, setShowApiKey] = React.useState(false);
+  const authType = Form.useWatch('authType', form) || 'gemini-api-key';
+
+  const isEdit = !!provider && !isCopy;
+  const labelCol = { span: language === 'zh-CN' ? 4 : 6 };
+  const wrapperCol = { span: 20 };
+
+  React.useEffect(() => {
+    if (!open) return;
+
+    if (provider) {
+      const config = parseSettingsConfig(provider.settingsConfig);
+      const env = config.env || {};
+      form.setFieldsValue({
+        name: provider.name,
+        authType: config.authType || 'gemini-api-key',
+        apiKey: env.GEMINI_API_KEY || env.GOOGLE_API_KEY || '',
+        baseUrl: env.GOOGLE_GEMINI_BASE_URL || '',
+        model: config.model || '',
+        project: env.GOOGLE_CLOUD_PROJECT || '',
+        location: env.GOOGLE_CLOUD_LOCATION || '',
+        notes: provider.notes,
+      });
+    } else {
+      form.resetFields();
+      form.setFieldsValue({ authType: 'gemini-api-key' });
+    }
+  }, [open, provider]);
+
+  const handleSubmit = async () => {
+    try {
+      const values = await form.validateFields();
+      setLoading(true);
+      await onSubmit(values);
+      form.resetFields();
+    } catch (error) {
+      console.error('Form validation failed:', error);
+    } finally {
+      setLoading(false);
+    }
+  };
+
+  return (
+    <Modal
+      title={isEdit ? t('geminicli.provider.editProvider') : t('geminicli.provider.addProvider')}
+      open={open}
+      onCancel={onCancel}
+      onOk={handleSubmit}
+      confirmLoading={loading}
+      width={800}
+      okText={t('common.save')}
+      cancelText={t('common.cancel')}
+    >
+      <Form form={form} layout="horizontal" labelCol={labelCol} wrapperCol={wrapperCol}>
+        <Form.Item
+          name="name"
+          label={t('geminicli.provider.name')}
+          rules={[{ required: true, message: t('common.error') }]}
+        >
+          <Input placeholder={t('geminicli.provider.namePlaceholder')} />
+        </Form.Item>
+
+        <Form.Item name="authType" label={t('geminicli.provider.authType')}>
+          <Select
+            options={[
+              { value: 'gemini-api-key', label: t('geminicli.provider.authTypeApiKey') },
+              { value: 'vertex-ai', label: t('geminicli.provider.authTypeVertex') },
+            ]}
+          />
+        </Form.Item>
+
+        <Form.Item
+          name="apiKey"
+          label={t('geminicli.provider.apiKey')}
+          rules={[{ required: authType === 'gemini-api-key', message: t('common.error') }]}
+          help={
+            <Text type="secondary" style={{ fontSize: 12 }}>
+              {authType === 'vertex-ai'
+                ? t('geminicli.provider.apiKeyVertexHelp')
+                : t('geminicli.provider.apiKeyHelp')}
+            </Text>
+          }
+        >
+          <Input
+            type={showApiKey ? 'text' : 'password'}
+            placeholder={t('geminicli.provider.apiKeyPlaceholder')}
+            addonAfter={
+              <Button
+                type="text"
+                size="small"
+                icon={showApiKey ? <EyeInvisibleOutlined /> : <EyeOutlined />}
+                onClick={() => setShowApiKey(!showApiKey)}
+              >
+                {showApiKey ? t('geminicli.provider.hideApiKey') : t('geminicli.provider.showApiKey')}
+              </Button>
+            }
+          />
+        </Form.Item>
+
+        {authType === 'vertex-ai' && (
+          <>
+            <Form.Item name="project" label={t('geminicli.provider.project')}>
+              <Input placeholder="GOOGLE_CLOUD_PROJECT" />
+            </Form.Item>
+            <Form.Item name="location" label={t('geminicli.provider.location')}>
+              <Input placeholder="us-central1" />
+            </Form.Item>
+          </>
+        )}
+
+        <Form.Item
+          name="baseUrl"
+          label={t('geminicli.provider.baseUrl')}
+          help={<Text type="secondary" style={{ fontSize: 12 }}>{t('geminicli.provider.baseUrlHelp')}</Text>}
+        >
+          <Input placeholder="https://your-api-endpoint.com" />
+        </Form.Item>
+
+        <Form.Item
+          name="model"
+          label={t('geminicli.provider.modelName')}
+          help={<Text type="secondary" style={{ fontSize: 12 }}>{t('geminicli.provider.modelNameHelp')}</Text>}
+        >
+          <Input placeholder={t('geminicli.provider.modelNamePlaceholder')} />
+        </Form.Item>
+
+        <Form.Item name="notes" label={t('geminicli.provider.notes')}>
+          <TextArea rows={2} placeholder={t('geminicli.provider.notesPlaceholder')} />
+        </Form.Item>
+      </Form>
+    </Modal>
+  );
+};
+
+export default GeminiCliProviderFormModal;
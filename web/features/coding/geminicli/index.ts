@@ -0,0 +1,4 @@
+export { default as GeminiCliPage } from './pages/GeminiCliPage';
+export { default as GeminiCliProviderCard } from './components/GeminiCliProviderCard';
+export { default as GeminiCliProviderFormModal } from './components/GeminiCliProviderFormModal';
+export { default as GeminiCliCommonConfigModal } from './components/GeminiCliCommonConfigModal';
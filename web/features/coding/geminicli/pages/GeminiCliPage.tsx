@@ -0,0 +1,578 @@
+import React from 'react';
+import { Typography, Button, Space, Empty, message, Modal, Spin, Collapse } from 'antd';
+import { PlusOutlined, FolderOpenOutlined, AppstoreOutlined, SyncOutlined, EyeOutlined, ExclamationCircleOutlined, LinkOutlined, DatabaseOutlined } from '@ant-design/icons';
+import { useTranslation } from 'react-i18next';
+import { openUrl, revealItemInDir } from '@tauri-apps/plugin-opener';
+import { invoke } from '@tauri-apps/api/core';
+import {
+  DndContext,
+  closestCenter,
+  KeyboardSensor,
+  PointerSensor,
+  useSensor,
+  useSensors,
+  type DragEndEvent,
+} from '@dnd-kit/core';
+import {
+  SortableContext,
+  sortableKeyboardCoordinates,
+  verticalListSortingStrategy,
+  arrayMove,
+} from '@dnd-kit/sortable';
+import { restrictToVerticalAxis } from '@dnd-kit/modifiers';
+import type {
+  GeminiCliProvider,
+  GeminiCliProviderFormValues,
+  GeminiCliProviderInput,
+  GeminiCliSettingsConfig,
+} from '@/types/geminiCli';
+import {
+  getGeminiCliConfigFilePath,
+  listGeminiCliProviders,
+  selectGeminiCliProvider,
+  applyGeminiCliConfig,
+  readGeminiCliSettings,
+  createGeminiCliProvider,
+  updateGeminiCliProvider,
+  saveGeminiCliLocalConfig,
+  deleteGeminiCliProvider,
+  toggleGeminiCliProviderDisabled,
+  reorderGeminiCliProviders,
+} from '@/services/geminiCliApi';
+import { geminiCliPromptApi } from '@/services/geminiCliPromptApi';
+import { refreshTrayMenu, hasAllApiHubExtension } from '@/services/appApi';
+import GeminiCliProviderCard from '../components/GeminiCliProviderCard';
+import GeminiCliProviderFormModal from '../components/GeminiCliProviderFormModal';
+import GeminiCliCommonConfigModal from '../components/GeminiCliCommonConfigModal';
+import ImportFromAllApiHubModal from '../components/ImportFromAllApiHubModal';
+import AllApiHubIcon from '@/components/common/AllApiHubIcon';
+import JsonPreviewModal from '@/components/common/JsonPreviewModal';
+import { GlobalPromptSettings } from '@/features/coding/shared/prompt';
+import type { OpenCodeAllApiHubProvider } from '@/services/opencodeApi';
+
+const { Title, Text, Link } = Typography;
+
+/**
+ * 将表单值转换为 settingsConfig JSON 字符串
+ * Vertex AI 使用 GOOGLE_API_KEY（express 模式），其余使用 GEMINI_API_KEY
+ */
+const buildSettingsConfig = (values: GeminiCliProviderFormValues): string => {
+  const isVertex = values.authType === 'vertex-ai';
+  const env: NonNullable<GeminiCliSettingsConfig['env']> = {};
+
+  if (values.apiKey) {
+    if (isVertex) {
+      env.GOOGLE_API_KEY = values.apiKey;
+    } else {
+      env.GEMINI_API_KEY = values.apiKey;
+    }
+  }
+  if (values.baseUrl) {
+    env.GOOGLE_GEMINI_BASE_URL = values.baseUrl.trim();
+  }
+  if (isVertex && values.project) {
+    env.GOOGLE_CLOUD_PROJECT = values.project.trim();
+  }
+  if (isVertex && values.location) {
+    env.GOOGLE_CLOUD_LOCATION = values.location.trim();
+  }
+
+  const config: GeminiCliSettingsConfig = {
+    authType: values.authType,
+    env,
+  };
+  if (values.model) {
+    config.model = values.model.trim();
+  }
+
+  return JSON.stringify(config);
+};
+
+// Gemini SDK 会自行拼接 /v1beta，导入时去掉 All API Hub 补上的版本后缀
+const stripApiVersion = (url: string): string => url.replace(/\/+$/, '').replace(/\/(v1|v1beta)$/, '');
+
+const GeminiCliPage: React.FC = () => {
+  const { t } = useTranslation();
+  const [loading, setLoading] = React.useState(false);
+  const [configPath, setConfigPath] = React.useState<string>('');
+  const [providers, setProviders] = React.useState<GeminiCliProvider[]>([]);
+  const [appliedProviderId, setAppliedProviderId] = React.useState<string>('');
+
+  // Modal states
+  const [providerModalOpen, setProviderModalOpen] = React.useState(false);
+  const [editingProvider, setEditingProvider] = React.useState<GeminiCliProvider | null>(null);
+  const [isCopyMode, setIsCopyMode] = React.useState(false);
+  const [commonConfigModalOpen, setCommonConfigModalOpen] = React.useState(false);
+  const [previewModalOpen, setPreviewModalOpen] = React.useState(false);
+  const [previewData, setPreviewDataLocal] = React.useState<unknown>(null);
+  const [providerListCollapsed, setProviderListCollapsed] = React.useState(false);
+  const [allApiHubImportModalOpen, setAllApiHubImportModalOpen] = React.useState(false);
+  const [allApiHubAvailable, setAllApiHubAvailable] = React.useState(false);
+
+  // 配置拖拽传感器
+  const sensors = useSensors(
+    useSensor(PointerSensor, {
+      activationConstraint: {
+        distance: 8, // 防止点击误触
+      },
+    }),
+    useSensor(KeyboardSensor, {
+      coordinateGetter: sortableKeyboardCoordinates,
+    })
+  );
+
+  const loadConfig = async () => {
+    setLoading(true);
+    try {
+      const [path, providerList] = await Promise.all([
+        getGeminiCliConfigFilePath(),
+        listGeminiCliProviders(),
+      ]);
+      setConfigPath(path);
+      setProviders(providerList);
+      const applied = providerList.find((p) => p.isApplied);
+      setAppliedProviderId(applied?.id || '');
+    } catch (error) {
+      console.error('Failed to load config:', error);
+      const errorMsg = error instanceof Error ? error.message : String(error);
+      message.error(errorMsg || t('common.error'));
+    } finally {
+      setLoading(false);
+    }
+  };
+
+  React.useEffect(() => {
+    loadConfig();
+  }, []);
+
+  React.useEffect(() => {
+    const checkAllApiHubAvailability = async () => {
+      try {
+        const available = await hasAllApiHubExtension();
+        setAllApiHubAvailable(available);
+      } catch {
+        setAllApiHubAvailable(false);
+      }
+    };
+
+    checkAllApiHubAvailability();
+  }, []);
+
+  const handleOpenFolder = async () => {
+    if (!configPath) return;
+
+    try {
+      await revealItemInDir(configPath);
+    } catch {
+      try {
+        const parentDir = configPath.replace(/[\\/][^\\/]+$/, '');
+        await invoke('open_folder', { path: parentDir });
+      } catch (error) {
+        console.error('Failed to open folder:', error);
+        const errorMsg = error instanceof Error ? error.message : String(error);
+        message.error(errorMsg || t('common.error'));
+      }
+    }
+  };
+
+  const handleRefreshPage = () => {
+    window.location.reload();
+  };
+
+  const handleSelectProvider = async (provider: GeminiCliProvider) => {
+    try {
+      await selectGeminiCliProvider(provider.id);
+      await applyGeminiCliConfig(provider.id);
+      message.success(t('geminicli.apply.success'));
+      await loadConfig();
+      await refreshTrayMenu();
+    } catch (error) {
+      console.error('Failed to select provider:', error);
+      const errorMsg = error instanceof Error ? error.message : String(error);
+      message.error(errorMsg || t('common.error'));
+    }
+  };
+
+  const handleToggleDisabled = async (provider: GeminiCliProvider, isDisabled: boolean) => {
+    try {
+      await toggleGeminiCliProviderDisabled(provider.id, isDisabled);
+      message.success(isDisabled ? t('geminicli.providerDisabled') : t('geminicli.providerEnabled'));
+      await loadConfig();
+      await refreshTrayMenu();
+    } catch (error) {
+      console.error('Failed to toggle provider disabled status:', error);
+      const errorMsg = error instanceof Error ? error.message : String(error);
+      message.error(errorMsg || t('common.error'));
+    }
+  };
+
+  // 拖拽排序处理
+  const handleDragEnd = async (event: DragEndEvent) => {
+    const { active, over } = event;
+    if (!over || active.id === over.id) return;
+
+    const oldIndex = providers.findIndex((p) => p.id === active.id);
+    const newIndex = providers.findIndex((p) => p.id === over.id);
+    const oldProviders = [...providers];
+    const newProviders = arrayMove(providers, oldIndex, newIndex);
+    setProviders(newProviders);
+
+    try {
+      await reorderGeminiCliProviders(newProviders.map((p) => p.id));
+      await refreshTrayMenu();
+    } catch (error) {
+      console.error('Failed to reorder providers:', error);
+      setProviders(oldProviders);
+      message.error(t('common.error'));
+    }
+  };
+
+  const handleAddProvider = () => {
+    setEditingProvider(null);
+    setIsCopyMode(false);
+    setProviderModalOpen(true);
+  };
+
+  const handleEditProvider = (provider: GeminiCliProvider) => {
+    setEditingProvider(provider);
+    setIsCopyMode(false);
+    setProviderModalOpen(true);
+  };
+
+  const handleCopyProvider = (provider: GeminiCliProvider) => {
+    setEditingProvider({
+      ...provider,
+      id: `${provider.id}_copy`,
+      name: `${provider.name}_copy`,
+      isApplied: false,
+    });
+    setIsCopyMode(true);
+    setProviderModalOpen(true);
+  };
+
+  const handleDeleteProvider = (provider: GeminiCliProvider) => {
+    Modal.confirm({
+      title: t('geminicli.provider.confirmDelete', { name: provider.name }),
+      icon: <ExclamationCircleOutlined />,
+      onOk: async () => {
+        try {
+          await deleteGeminiCliProvider(provider.id);
+          message.success(t('common.success'));
+          await loadConfig();
+          await refreshTrayMenu();
+        } catch (error) {
+          console.error('Failed to delete provider:', error);
+          const errorMsg = error instanceof Error ? error.message : String(error);
+          message.error(errorMsg || t('common.error'));
+        }
+      },
+    });
+  };
+
+  const handleImportFromAllApiHub = async (imported: OpenCodeAllApiHubProvider[]) => {
+    try {
+      for (const item of imported) {
+        const baseUrl = item.providerConfig.options?.baseURL || '';
+        const apiKey = item.providerConfig.options?.apiKey || '';
+
+        const providerInput: GeminiCliProviderInput = {
+          name: item.name,
+          category: 'custom',
+          settingsConfig: buildSettingsConfig({
+            name: item.name,
+            authType: 'gemini-api-key',
+            apiKey,
+            baseUrl: baseUrl ? stripApiVersion(baseUrl) : undefined,
+          }),
+          sourceProviderId: item.providerId,
+          notes: undefined,
+        };
+
+        await createGeminiCliProvider(providerInput);
+      }
+
+      message.success(t('common.allApiHub.importSuccess', { count: imported.length }));
+      setAllApiHubImportModalOpen(false);
+      await loadConfig();
+      await refreshTrayMenu();
+    } catch (error) {
+      console.error('Failed to import from All API Hub:', error);
+      const errorMsg = error instanceof Error ? error.message : String(error);
+      message.error(errorMsg || t('common.error'));
+    }
+  };
+
+  const handleProviderSubmit = async (values: GeminiCliProviderFormValues) => {
+    try {
+      const settingsConfig = buildSettingsConfig(values);
+
+      // Check if this is a temporary provider from local files
+      const isLocalTemp = editingProvider?.id === '__local__';
+
+      const providerInput: GeminiCliProviderInput = {
+        name: values.name,
+        category: editingProvider?.category || 'custom',
+        settingsConfig,
+        sourceProviderId: editingProvider?.sourceProviderId,
+        notes: values.notes,
+      };
+
+      if (isLocalTemp) {
+        await saveGeminiCliLocalConfig({ provider: providerInput });
+      } else if (editingProvider && !isCopyMode) {
+        await updateGeminiCliProvider({
+          ...editingProvider,
+          name: values.name,
+          settingsConfig,
+          notes: values.notes,
+        });
+      } else {
+        // 让服务端生成 ID
+        await createGeminiCliProvider(providerInput);
+      }
+
+      message.success(t('common.success'));
+      setProviderModalOpen(false);
+      setIsCopyMode(false);
+      await loadConfig();
+      await refreshTrayMenu();
+    } catch (error) {
+      console.error('Failed to save provider:', error);
+      const errorMsg = error instanceof Error ? error.message : String(error);
+      message.error(errorMsg || t('common.error'));
+      throw error;
+    }
+  };
+
+  const handlePreviewCurrentConfig = async () => {
+    try {
+      const settings = await readGeminiCliSettings();
+      setPreviewDataLocal(settings);
+      setPreviewModalOpen(true);
+    } catch (error) {
+      console.error('Failed to preview config:', error);
+      const errorMsg = error instanceof Error ? error.message : String(error);
+      message.error(errorMsg || t('common.error'));
+    }
+  };
+
+  return (
+    <div>
+      {/* Page Header */}
+      <div style={{ marginBottom: 16 }}>
+        <div style={{ display: 'flex', justifyContent: 'space-between', alignItems: 'flex-start' }}>
+          <div>
+            <div style={{ marginBottom: 8 }}>
+              <Title level={4} style={{ margin: 0, display: 'inline-block', marginRight: 8 }}>
+                {t('geminicli.title')}
+              </Title>
+              <Link
+                type="secondary"
+                style={{ fontSize: 12 }}
+                onClick={(e) => {
+                  e.stopPropagation();
+                  openUrl('https://github.com/google-gemini/gemini-cli/blob/main/docs/get-started/configuration.md');
+                }}
+              >
+                <LinkOutlined /> {t('geminicli.viewDocs')}
+              </Link>
+              {appliedProviderId && (
+                <Link
+                  type="secondary"
+                  style={{ fontSize: 12, marginLeft: 16 }}
+                  onClick={(e) => {
+                    e.stopPropagation();
+                    handlePreviewCurrentConfig();
+                  }}
+                >
+                  <EyeOutlined /> {t('common.previewConfig')}
+                </Link>
+              )}
+            </div>
+            <Space size="small">
+              <Text type="secondary" style={{ fontSize: 12 }}>
+                {t('geminicli.configPath')}:
+              </Text>
+              <Text code style={{ fontSize: 12 }}>
+                {configPath || '~/.gemini/settings.json'}
+              </Text>
+              <Button
+                type="text"
+                size="small"
+                icon={<FolderOpenOutlined />}
+                onClick={handleOpenFolder}
+                style={{ padding: 0, fontSize: 12 }}
+              >
+                {t('geminicli.openFolder')}
+              </Button>
+              <Button
+                type="text"
+                size="small"
+                icon={<SyncOutlined />}
+                onClick={handleRefreshPage}
+                style={{ padding: 0, fontSize: 12 }}
+              >
+                {t('geminicli.refreshConfig')}
+              </Button>
+            </Space>
+          </div>
+        </div>
+      </div>
+
+      {/* Provider List */}
+      <Collapse
+        style={{ marginBottom: 16 }}
+        activeKey={providerListCollapsed ? [] : ['providers']}
+        onChange={(keys) => setProviderListCollapsed(!keys.includes('providers'))}
+        items={[
+          {
+            key: 'providers',
+            label: (
+              <Text strong>
+                <DatabaseOutlined style={{ marginRight: 8 }} />
+                {t('geminicli.provider.title')}
+              </Text>
+            ),
+            extra: (
+              <Space size={4}>
+                <Button
+                  type="link"
+                  size="small"
+                  style={{ fontSize: 12 }}
+                  icon={<AppstoreOutlined />}
+                  onClick={(e) => {
+                    e.stopPropagation();
+                    setCommonConfigModalOpen(true);
+                  }}
+                >
+                  {t('geminicli.commonConfigButton')}
+                </Button>
+                <Button
+                  type="link"
+                  size="small"
+                  style={{ fontSize: 12 }}
+                  icon={<PlusOutlined />}
+                  onClick={(e) => {
+                    e.stopPropagation();
+                    handleAddProvider();
+                  }}
+                >
+                  {t('geminicli.addProvider')}
+                </Button>
+              </Space>
+            ),
+            children: (
+              <Spin spinning={loading}>
+                <div
+                  style={{
+                    fontSize: 12,
+                    color: 'var(--color-text-secondary)',
+                    borderLeft: '2px solid var(--color-border)',
+                    paddingLeft: 8,
+                    marginBottom: 12,
+                  }}
+                >
+                  <div>{t('geminicli.pageHint')}</div>
+                  <div>{t('geminicli.pageWarning')}</div>
+                </div>
+
+                {providers.length === 0 ? (
+                  <Empty description={t('geminicli.emptyText')} style={{ marginTop: 40 }} />
+                ) : (
+                  <DndContext
+                    sensors={sensors}
+                    collisionDetection={closestCenter}
+                    onDragEnd={handleDragEnd}
+                    modifiers={[restrictToVerticalAxis]}
+                  >
+                    <SortableContext
+                      items={providers.map((p) => p.id)}
+                      strategy={verticalListSortingStrategy}
+                    >
+                      <div>
+                        {providers.map((provider) => (
+                          <GeminiCliProviderCard
+                            key={provider.id}
+                            provider={provider}
+                            isApplied={provider.id === appliedProviderId}
+                            onEdit={handleEditProvider}
+                            onDelete={handleDeleteProvider}
+                            onCopy={handleCopyProvider}
+                            onSelect={handleSelectProvider}
+                            onToggleDisabled={handleToggleDisabled}
+                          />
+                        ))}
+                      </div>
+                    </SortableContext>
+                  </DndContext>
+                )}
+
+                {allApiHubAvailable && (
+                  <div style={{ marginTop: 12 }}>
+                    <Button
+                      type="dashed"
+                      icon={<AllApiHubIcon />}
+                      onClick={() => setAllApiHubImportModalOpen(true)}
+                    >
+                      {t('common.allApiHub.importFromAllApiHub')}
+                    </Button>
+                  </div>
+                )}
+              </Spin>
+            ),
+          },
+        ]}
+      />
+
+      <GlobalPromptSettings
+        translationKeyPrefix="geminicli.prompt"
+        service={geminiCliPromptApi}
+        collapseKey="geminicli-prompt"
+        onUpdated={loadConfig}
+      />
+
+      {/* Modals */}
+      {providerModalOpen && (
+        <GeminiCliProviderFormModal
+          open={providerModalOpen}
+          provider={editingProvider}
+          isCopy={isCopyMode}
+          onCancel={() => {
+            setProviderModalOpen(false);
+            setEditingProvider(null);
+            setIsCopyMode(false);
+          }}
+          onSubmit={handleProviderSubmit}
+        />
+      )}
+
+      <GeminiCliCommonConfigModal
+        open={commonConfigModalOpen}
+        onCancel={() => setCommonConfigModalOpen(false)}
+        onSuccess={() => {
+          setCommonConfigModalOpen(false);
+        }}
+        isLocalProvider={providers.some((provider) => provider.id === '__local__')}
+      />
+
+      {allApiHubAvailable && (
+        <ImportFromAllApiHubModal
+          open={allApiHubImportModalOpen}
+          existingProviderIds={providers.map((provider) => provider.sourceProviderId || provider.id)}
+          onCancel={() => setAllApiHubImportModalOpen(false)}
+          onImport={handleImportFromAllApiHub}
+        />
+      )}
+
+      {/* Preview Modal */}
+      <JsonPreviewModal
+        open={previewModalOpen}
+        onClose={() => setPreviewModalOpen(false)}
+        title={t('geminicli.preview.currentConfigTitle')}
+        data={previewData}
+      />
+    </div>
+  );
+};
+
+export default GeminiCliPage;
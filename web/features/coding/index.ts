@@ -1,5 +1,6 @@
 export * from './opencode';
 export * from './claudecode';
 export * from './codex';
+export * from './geminicli';
 export * from './openclaw';
 export * from './skills';
@@ -22,7 +22,7 @@ import {
   verticalListSortingStrategy,
 } from '@dnd-kit/sortable';
 import { restrictToVerticalAxis } from '@dnd-kit/modifiers';
-import { readOpenCodeConfigWithResult, saveOpenCodeConfig, getOpenCodeConfigPathInfo, getOpenCodeUnifiedModels, getOpenCodeAuthProviders, getOpenCodeAuthConfigPath, listFavoriteProviders, upsertFavoriteProvider, buildModelVariantsMap, getOpenCodeFreeModels, type ConfigPathInfo, type UnifiedModelOption, type GetAuthProvidersResponse, type OpenCodeFavoriteProvider, type OpenCodeDiagnosticsConfig } from '@/services/opencodeApi';
+import { readOpenCodeConfigWithResult, saveOpenCodeConfig, getOpenCodeConfigPathInfo, getOpenCodeUnifiedModels, getOpenCodeAuthProviders, getOpenCodeAuthConfigPath, listFavoriteProviders, upsertFavoriteProvider, detectOpenCodeConfigDrift, importOpenCodeConfigDrift, restoreOpenCodeConfigDrift, buildModelVariantsMap, getOpenCodeFreeModels, type ConfigPathInfo, type UnifiedModelOption, type GetAuthProvidersResponse, type OpenCodeFavoriteProvider, type OpenCodeDiagnosticsConfig } from '@/services/opencodeApi';
 import { listOhMyOpenCodeConfigs, applyOhMyOpenCodeConfig } from '@/services/ohMyOpenCodeApi';
 import { listOhMyOpenCodeSlimConfigs } from '@/services/ohMyOpenCodeSlimApi';
 import { refreshTrayMenu, fetchRemotePresetModels, hasAllApiHubExtension } from '@/services/appApi';
@@ -41,6 +41,7 @@ import type { FetchedModel } from '@/components/common/FetchModelsModal/types';
 import PluginSettings from '../components/PluginSettings';
 import ConfigPathModal from '../components/ConfigPathModal';
 import ConfigParseErrorAlert from '../components/ConfigParseErrorAlert';
+import { ConfigDriftAlert } from '@/features/coding/shared/configDrift';
 import OhMyOpenCodeConfigSelector from '../components/OhMyOpenCodeConfigSelector';
 import OhMyOpenCodeSlimConfigSelector from '../components/OhMyOpenCodeSlimConfigSelector';
 import OhMyOpenCodeSettings from '../components/OhMyOpenCodeSettings';
@@ -156,6 +157,8 @@ const OpenCodePage: React.FC = () => {
     })
   );
 
+  const [driftCheckKey, setDriftCheckKey] = React.useState(0);
+
   const loadConfig = React.useCallback(async (showSuccessMessage = false) => {
     setLoading(true);
     setParseError(null); // Reset parse error state
@@ -213,6 +216,7 @@ const OpenCodePage: React.FC = () => {
       message.error(errorMessage);
     } finally {
       setLoading(false);
+      setDriftCheckKey((key) => key + 1);
     }
   }, [t]);
 
@@ -1224,6 +1228,20 @@ const OpenCodePage: React.FC = () => {
           </div>
         </div>
 
+          {/* Providers edited outside the app */}
+          <ConfigDriftAlert
+            detect={detectOpenCodeConfigDrift}
+            refreshKey={driftCheckKey}
+            onImport={async () => {
+              await importOpenCodeConfigDrift();
+              await loadConfig();
+            }}
+            onReapply={async () => {
+              await restoreOpenCodeConfigDrift();
+              await loadConfig();
+            }}
+          />
+
           <div className={styles.modelCard}>
         <Title level={5} className={styles.modelCardTitle}>
           <RobotOutlined style={{ marginRight: 8 }} />
@@ -0,0 +1,28 @@
+import React from 'react';
+import { Tooltip } from 'antd';
+import { HistoryOutlined } from '@ant-design/icons';
+import { useTranslation } from 'react-i18next';
+import { useNavigate, useLocation } from 'react-router-dom';
+import styles from './SessionsButton.module.less';
+
+export const SessionsButton: React.FC = () => {
+  const { t } = useTranslation();
+  const navigate = useNavigate();
+  const location = useLocation();
+
+  const isActive = location.pathname.startsWith('/sessions');
+
+  return (
+    <Tooltip title={t('sessions.tooltip')}>
+      <div
+        className={`${styles.sessionsButton} ${isActive ? styles.active : ''}`}
+        onClick={() => navigate('/sessions')}
+      >
+        <HistoryOutlined className={styles.icon} />
+        <span className={styles.text}>{t('sessions.button')}</span>
+      </div>
+    </Tooltip>
+  );
+};
+
+export default SessionsButton;
@@ -0,0 +1,192 @@
+import React from 'react';
+import { Drawer, Button, Tag, Typography, Collapse, Empty, Spin, Space, message } from 'antd';
+import { ExportOutlined, EditOutlined, ToolOutlined } from '@ant-design/icons';
+import { useTranslation } from 'react-i18next';
+import { exportSessionMarkdown, getSessionTranscript } from '../services/sessionHistoryApi';
+import type { SessionSummary, SessionTranscript, TranscriptMessage, TranscriptPart } from '../types';
+import styles from './TranscriptDrawer.module.less';
+
+const { Text, Paragraph } = Typography;
+
+interface TranscriptDrawerProps {
+  session: SessionSummary | null;
+  onClose: () => void;
+}
+
+const formatInput = (input: unknown) => (typeof input === 'string' ? input : JSON.stringify(input, null, 2));
+
+const editDiff = (part: Extract<TranscriptPart, { type: 'fileEdit' }>) => {
+  if (part.diff) return part.diff;
+  const lines = (text: string | undefined, prefix: string) =>
+    (text || '').split('\n').filter((line, idx, all) => line || idx < all.length - 1).map((line) => `${prefix}${line}`);
+  return [...lines(part.oldText, '-'), ...lines(part.newText, '+')].join('\n');
+};
+
+const PartView: React.FC<{ part: TranscriptPart }> = ({ part }) => {
+  const { t } = useTranslation();
+
+  switch (part.type) {
+    case 'text':
+      return <Paragraph className={styles.text}>{part.text}</Paragraph>;
+    case 'thinking':
+      return (
+        <Collapse
+          size="small"
+          className={styles.block}
+          items={[{ key: 'thinking', label: t('sessions.thinking'), children: <pre className={styles.code}>{part.text}</pre> }]}
+        />
+      );
+    case 'toolCall':
+      return (
+        <Collapse
+          size="small"
+          className={styles.block}
+          items={[
+            {
+              key: 'call',
+              label: (
+                <Space size={4}>
+                  <ToolOutlined />
+                  <Text code>{part.name}</Text>
+                </Space>
+              ),
+              children: <pre className={styles.code}>{formatInput(part.input)}</pre>,
+            },
+          ]}
+        />
+      );
+    case 'toolResult':
+      return (
+        <Collapse
+          size="small"
+          className={styles.block}
+          items={[
+            {
+              key: 'result',
+              label: part.isError ? <Text type="danger">{t('sessions.toolError')}</Text> : t('sessions.toolResult'),
+              children: <pre className={styles.code}>{part.output}</pre>,
+            },
+          ]}
+        />
+      );
+    case 'fileEdit':
+      return (
+        <Collapse
+          size="small"
+          className={styles.block}
+          items={[
+            {
+              key: 'edit',
+              label: (
+                <Space size={4}>
+                  <EditOutlined />
+                  <Text code>{part.path}</Text>
+                  <Text type="secondary">{part.tool}</Text>
+                </Space>
+              ),
+              children: <pre className={styles.code}>{editDiff(part)}</pre>,
+            },
+          ]}
+        />
+      );
+    default:
+      return null;
+  }
+};
+
+const MessageView: React.FC<{ message: TranscriptMessage }> = ({ message: msg }) => {
+  const { t } = useTranslation();
+  const roleColor = msg.role === 'user' ? 'blue' : msg.role === 'assistant' ? 'green' : 'default';
+
+  return (
+    <div className={styles.message}>
+      <div className={styles.messageHeader}>
+        <Tag color={roleColor}>{t(`sessions.role.${msg.role}`)}</Tag>
+        {msg.model && <Text type="secondary">{msg.model}</Text>}
+        {msg.timestamp && <Text type="secondary">{new Date(msg.timestamp).toLocaleString()}</Text>}
+      </div>
+      {msg.parts.map((part, idx) => (
+        <PartView key={idx} part={part} />
+      ))}
+    </div>
+  );
+};
+
+const TranscriptDrawer: React.FC<TranscriptDrawerProps> = ({ session, onClose }) => {
+  const { t } = useTranslation();
+  const [transcript, setTranscript] = React.useState<SessionTranscript | null>(null);
+  const [loading, setLoading] = React.useState(false);
+  const [exporting, setExporting] = React.useState(false);
+
+  React.useEffect(() => {
+    if (!session) {
+      setTranscript(null);
+      return;
+    }
+    let cancelled = false;
+    setLoading(true);
+    getSessionTranscript(session.id)
+      .then((result) => {
+        if (!cancelled) setTranscript(result);
+      })
+      .catch((error) => {
+        console.error('Failed to load session transcript:', error);
+        if (!cancelled) {
+          setTranscript(null);
+          message.error(String(error));
+        }
+      })
+      .finally(() => {
+        if (!cancelled) setLoading(false);
+      });
+    return () => {
+      cancelled = true;
+    };
+  }, [session]);
+
+  const handleExport = async () => {
+    if (!session) return;
+    setExporting(true);
+    try {
+      const path = await exportSessionMarkdown(session);
+      if (path) {
+        message.success(t('sessions.exportSuccess', { path }));
+      }
+    } catch (error) {
+      console.error('Failed to export session:', error);
+      message.error(String(error));
+    } finally {
+      setExporting(false);
+    }
+  };
+
+  return (
+    <Drawer
+      open={!!session}
+      onClose={onClose}
+      width={820}
+      title={session?.title || session?.sessionId}
+      extra={
+        <Button icon={<ExportOutlined />} loading={exporting} onClick={handleExport} disabled={!transcript}>
+          {t('sessions.exportMarkdown')}
+        </Button>
+      }
+    >
+      {session && (
+        <div className={styles.meta}>
+          <Text type="secondary">{session.project}</Text>
+          <Text type="secondary" copyable={{ text: session.sessionId }}>
+            {session.sessionId}
+          </Text>
+        </div>
+      )}
+      <Spin spinning={loading}>
+        {transcript && transcript.messages.length > 0
+          ? transcript.messages.map((msg, idx) => <MessageView key={idx} message={msg} />)
+          : !loading && <Empty description={t('sessions.emptyTranscript')} />}
+      </Spin>
+    </Drawer>
+  );
+};
+
+export default TranscriptDrawer;
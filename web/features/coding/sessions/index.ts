@@ -0,0 +1,5 @@
+// Session history module exports
+export * from './types';
+export * from './services/sessionHistoryApi';
+export { default as SessionHistoryPage } from './pages/SessionHistoryPage';
+export { default as SessionsButton } from './components/SessionsButton';
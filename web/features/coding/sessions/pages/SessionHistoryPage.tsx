@@ -0,0 +1,222 @@
+import React from 'react';
+import { Typography, Button, Input, Select, Segmented, Table, Tag, message } from 'antd';
+import { ReloadOutlined } from '@ant-design/icons';
+import { useTranslation } from 'react-i18next';
+import { reindexSessionHistory, searchSessionHistory } from '../services/sessionHistoryApi';
+import TranscriptDrawer from '../components/TranscriptDrawer';
+import type { SessionSource, SessionSummary } from '../types';
+import styles from './SessionHistoryPage.module.less';
+
+const { Title, Text } = Typography;
+
+const SOURCE_LABELS: Record<SessionSource, string> = {
+  claudecode: 'Claude Code',
+  codex: 'Codex',
+  opencode: 'OpenCode',
+};
+
+type RangeKey = '7d' | '30d' | '90d' | 'all';
+
+const RANGE_DAYS: Record<RangeKey, number | null> = {
+  '7d': 7,
+  '30d': 30,
+  '90d': 90,
+  all: null,
+};
+
+// 本地日期 YYYY-MM-DD（与后端按本地时区过滤一致）
+const formatDate = (date: Date) => {
+  const pad = (n: number) => String(n).padStart(2, '0');
+  return `${date.getFullYear()}-${pad(date.getMonth() + 1)}-${pad(date.getDate())}`;
+};
+
+/** Render a snippet whose matched term is wrapped in `**` */
+const renderSnippet = (snippet: string) =>
+  snippet.split('**').map((segment, idx) => (idx % 2 === 1 ? <strong key={idx}>{segment}</strong> : segment));
+
+const SessionHistoryPage: React.FC = () => {
+  const { t } = useTranslation();
+  const [text, setText] = React.useState('');
+  const [project, setProject] = React.useState('');
+  const [model, setModel] = React.useState('');
+  const [sources, setSources] = React.useState<SessionSource[]>([]);
+  const [range, setRange] = React.useState<RangeKey>('all');
+  const [sessions, setSessions] = React.useState<SessionSummary[]>([]);
+  const [loading, setLoading] = React.useState(false);
+  const [reindexing, setReindexing] = React.useState(false);
+  const [selected, setSelected] = React.useState<SessionSummary | null>(null);
+
+  // 文本框在提交时才触发搜索，其它筛选项变化立即生效
+  const [submitted, setSubmitted] = React.useState({ text: '', project: '', model: '' });
+
+  const loadSessions = React.useCallback(async () => {
+    setLoading(true);
+    try {
+      const days = RANGE_DAYS[range];
+      const start = new Date();
+      if (days) start.setDate(start.getDate() - days + 1);
+      setSessions(
+        await searchSessionHistory({
+          text: submitted.text || undefined,
+          project: submitted.project || undefined,
+          model: submitted.model || undefined,
+          sources,
+          startDate: days ? formatDate(start) : undefined,
+        })
+      );
+    } catch (error) {
+      console.error('Failed to search session history:', error);
+      message.error(String(error));
+    } finally {
+      setLoading(false);
+    }
+  }, [submitted, sources, range]);
+
+  React.useEffect(() => {
+    loadSessions();
+  }, [loadSessions]);
+
+  const handleSearch = (searchText: string = text) => {
+    setSubmitted({ text: searchText.trim(), project: project.trim(), model: model.trim() });
+  };
+
+  const handleReindex = async () => {
+    setReindexing(true);
+    try {
+      const result = await reindexSessionHistory();
+      message.success(t('sessions.reindexSuccess', { count: result.sessionsUpdated }));
+      await loadSessions();
+    } catch (error) {
+      console.error('Failed to reindex session history:', error);
+      message.error(String(error));
+    } finally {
+      setReindexing(false);
+    }
+  };
+
+  const columns = [
+    {
+      title: t('sessions.columns.title'),
+      key: 'title',
+      render: (_: unknown, session: SessionSummary) => (
+        <div className={styles.title} onClick={() => setSelected(session)}>
+          <Typography.Link>{session.title || session.sessionId}</Typography.Link>
+          {session.snippet && <span className={styles.snippet}>{renderSnippet(session.snippet)}</span>}
+        </div>
+      ),
+    },
+    {
+      title: t('sessions.columns.source'),
+      dataIndex: 'source',
+      key: 'source',
+      width: 110,
+      render: (source: SessionSource) => <Tag>{SOURCE_LABELS[source] || source}</Tag>,
+    },
+    {
+      title: t('sessions.columns.project'),
+      dataIndex: 'project',
+      key: 'project',
+      width: 220,
+      ellipsis: true,
+    },
+    {
+      title: t('sessions.columns.models'),
+      dataIndex: 'models',
+      key: 'models',
+      width: 160,
+      ellipsis: true,
+      render: (models: string[]) => models.join(', '),
+    },
+    {
+      title: t('sessions.columns.messages'),
+      dataIndex: 'messageCount',
+      key: 'messageCount',
+      width: 80,
+    },
+    {
+      title: t('sessions.columns.updatedAt'),
+      dataIndex: 'updatedAt',
+      key: 'updatedAt',
+      width: 170,
+      render: (value: string) => (value ? new Date(value).toLocaleString() : '-'),
+    },
+  ];
+
+  return (
+    <div className={styles.sessionsPage}>
+      <div className={styles.pageHeader}>
+        <Title level={4} style={{ margin: 0 }}>
+          {t('sessions.title')}
+        </Title>
+        <Button icon={<ReloadOutlined />} loading={reindexing} onClick={handleReindex}>
+          {t('sessions.reindex')}
+        </Button>
+      </div>
+
+      <Text type="secondary" style={{ fontSize: 12, marginBottom: 16, marginTop: -16 }}>
+        {t('sessions.pageHint')}
+      </Text>
+
+      <div className={styles.toolbar}>
+        <Input.Search
+          allowClear
+          value={text}
+          onChange={(e) => setText(e.target.value)}
+          onSearch={(value) => handleSearch(value)}
+          placeholder={t('sessions.searchPlaceholder')}
+          style={{ width: 280 }}
+        />
+        <Input
+          allowClear
+          value={project}
+          onChange={(e) => setProject(e.target.value)}
+          onPressEnter={() => handleSearch()}
+          placeholder={t('sessions.projectPlaceholder')}
+          style={{ width: 200 }}
+        />
+        <Input
+          allowClear
+          value={model}
+          onChange={(e) => setModel(e.target.value)}
+          onPressEnter={() => handleSearch()}
+          placeholder={t('sessions.modelPlaceholder')}
+          style={{ width: 160 }}
+        />
+        <Select
+          mode="multiple"
+          allowClear
+          value={sources}
+          onChange={setSources}
+          placeholder={t('sessions.allSources')}
+          style={{ minWidth: 200 }}
+          options={(Object.keys(SOURCE_LABELS) as SessionSource[]).map((key) => ({
+            label: SOURCE_LABELS[key],
+            value: key,
+          }))}
+        />
+        <Segmented
+          value={range}
+          onChange={(value) => setRange(value as RangeKey)}
+          options={(Object.keys(RANGE_DAYS) as RangeKey[]).map((key) => ({
+            label: t(`sessions.range.${key}`),
+            value: key,
+          }))}
+        />
+      </div>
+
+      <Table
+        rowKey="id"
+        size="small"
+        loading={loading}
+        columns={columns}
+        dataSource={sessions}
+        pagination={{ pageSize: 20, size: 'small', hideOnSinglePage: true }}
+        locale={{ emptyText: t('sessions.empty') }}
+      />
+
+      <TranscriptDrawer session={selected} onClose={() => setSelected(null)} />
+    </div>
+  );
+};
+
+export default SessionHistoryPage;
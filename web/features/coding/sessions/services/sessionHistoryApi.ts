@@ -0,0 +1,33 @@
+import { invoke } from '@tauri-apps/api/core';
+import { save } from '@tauri-apps/plugin-dialog';
+import type { SessionIndexResult, SessionSearchQuery, SessionSummary, SessionTranscript } from '../types';
+
+/** Index new or changed session logs, then search */
+export const searchSessionHistory = async (query: SessionSearchQuery): Promise<SessionSummary[]> => {
+  return invoke<SessionSummary[]>('search_session_history', { query });
+};
+
+export const reindexSessionHistory = async (): Promise<SessionIndexResult> => {
+  return invoke<SessionIndexResult>('reindex_session_history');
+};
+
+/** Normalized transcript, parsed again from the original log */
+export const getSessionTranscript = async (id: string): Promise<SessionTranscript> => {
+  return invoke<SessionTranscript>('get_session_transcript', { id });
+};
+
+/**
+ * Ask for a target file and export the session as Markdown
+ * @returns The written path, or null if cancelled
+ */
+export const exportSessionMarkdown = async (session: SessionSummary): Promise<string | null> => {
+  const safeName = (session.title || session.sessionId).replace(/[\\/:*?"<>|\s]+/g, '-').slice(0, 60);
+  const outputPath = await save({
+    defaultPath: `${safeName || 'session'}.md`,
+    filters: [{ name: 'Markdown', extensions: ['md'] }],
+  });
+  if (!outputPath) {
+    return null;
+  }
+  return invoke<string>('export_session_markdown', { id: session.id, outputPath });
+};
@@ -0,0 +1,68 @@
+/**
+ * Session history types (mirror of the Rust `coding::session_history` module)
+ */
+
+export type SessionSource = 'claudecode' | 'codex' | 'opencode';
+
+export interface SessionSummary {
+  /** Index record ID */
+  id: string;
+  source: SessionSource;
+  /** The tool's own session ID */
+  sessionId: string;
+  /** Log file (message directory for OpenCode) */
+  path: string;
+  title: string;
+  project: string;
+  models: string[];
+  startedAt: string;
+  updatedAt: string;
+  messageCount: number;
+  /** Full-text hit with matched term wrapped in `**` */
+  snippet?: string;
+}
+
+export interface SessionSearchQuery {
+  text?: string;
+  project?: string;
+  model?: string;
+  /** Empty means all sources */
+  sources: SessionSource[];
+  /** Inclusive, YYYY-MM-DD */
+  startDate?: string;
+  /** Inclusive, YYYY-MM-DD */
+  endDate?: string;
+  limit?: number;
+}
+
+export type TranscriptPart =
+  | { type: 'text'; text: string }
+  | { type: 'thinking'; text: string }
+  | { type: 'toolCall'; callId?: string; name: string; input: unknown }
+  | { type: 'toolResult'; callId?: string; output: string; isError: boolean }
+  | {
+      type: 'fileEdit';
+      path: string;
+      tool: string;
+      oldText?: string;
+      newText?: string;
+      diff?: string;
+    };
+
+export interface TranscriptMessage {
+  role: 'user' | 'assistant' | 'tool';
+  timestamp?: string;
+  model?: string;
+  parts: TranscriptPart[];
+}
+
+export interface SessionTranscript {
+  summary: SessionSummary;
+  messages: TranscriptMessage[];
+}
+
+export interface SessionIndexResult {
+  sessionsTotal: number;
+  sessionsUpdated: number;
+  sessionsRemoved: number;
+}
@@ -0,0 +1,144 @@
+/**
+ * Config Drift Alert
+ *
+ * Warns when a managed config file was edited outside the app and offers to
+ * import the changes into the app or re-apply the app's config over them
+ */
+
+import React from 'react';
+import { Alert, Button, Modal, Space, Table, Typography, message } from 'antd';
+import { useTranslation } from 'react-i18next';
+import type { ConfigDrift, DriftEntry } from '@/types/configDrift';
+
+const { Text } = Typography;
+
+interface ConfigDriftAlertProps {
+  /** Runs the drift detection, called on mount, when refreshKey changes and on window focus */
+  detect: () => Promise<ConfigDrift | null>;
+  /** Fold the file changes back into the app */
+  onImport: () => Promise<void>;
+  /** Overwrite the file with the app's config */
+  onReapply: () => Promise<void>;
+  refreshKey?: number;
+}
+
+const formatValue = (value: unknown) => {
+  if (value === undefined) return null;
+  return typeof value === 'string' ? value : JSON.stringify(value);
+};
+
+const ConfigDriftAlert: React.FC<ConfigDriftAlertProps> = ({ detect, onImport, onReapply, refreshKey }) => {
+  const { t } = useTranslation();
+  const [drift, setDrift] = React.useState<ConfigDrift | null>(null);
+  const [detailOpen, setDetailOpen] = React.useState(false);
+  const [busy, setBusy] = React.useState<'import' | 'reapply' | null>(null);
+
+  const runDetect = React.useCallback(async () => {
+    try {
+      setDrift(await detect());
+    } catch (error) {
+      // 文件无法解析等情况由页面自身提示，这里只记录
+      console.error('Failed to detect config drift:', error);
+      setDrift(null);
+    }
+  }, [detect]);
+
+  React.useEffect(() => {
+    runDetect();
+  }, [runDetect, refreshKey]);
+
+  React.useEffect(() => {
+    const handleFocus = () => runDetect();
+    window.addEventListener('focus', handleFocus);
+    return () => window.removeEventListener('focus', handleFocus);
+  }, [runDetect]);
+
+  const handleAction = async (action: 'import' | 'reapply') => {
+    setBusy(action);
+    try {
+      await (action === 'import' ? onImport() : onReapply());
+      message.success(t(action === 'import' ? 'common.configDrift.importSuccess' : 'common.configDrift.reapplySuccess'));
+      setDetailOpen(false);
+      await runDetect();
+    } catch (error) {
+      console.error('Failed to resolve config drift:', error);
+      message.error(String(error));
+    } finally {
+      setBusy(null);
+    }
+  };
+
+  if (!drift?.drifted) {
+    return null;
+  }
+
+  const renderValue = (value: unknown) => {
+    const text = formatValue(value);
+    return text === null ? (
+      <Text type="secondary" italic>
+        {t('common.configDrift.missing')}
+      </Text>
+    ) : (
+      <Text code style={{ fontSize: 12, wordBreak: 'break-all' }}>
+        {text}
+      </Text>
+    );
+  };
+
+  const actions = (
+    <Space>
+      <Button size="small" onClick={() => setDetailOpen(true)}>
+        {t('common.configDrift.viewChanges')}
+      </Button>
+      <Button size="small" loading={busy === 'import'} disabled={!!busy} onClick={() => handleAction('import')}>
+        {t('common.configDrift.import')}
+      </Button>
+      <Button size="small" danger loading={busy === 'reapply'} disabled={!!busy} onClick={() => handleAction('reapply')}>
+        {t('common.configDrift.reapply')}
+      </Button>
+    </Space>
+  );
+
+  return (
+    <>
+      <Alert
+        type="warning"
+        showIcon
+        style={{ marginBottom: 16 }}
+        message={t('common.configDrift.title', { count: drift.entries.length })}
+        description={
+          <Text type="secondary" style={{ fontSize: 12 }}>
+            {drift.providerName
+              ? t('common.configDrift.descriptionWithProvider', { path: drift.configPath, name: drift.providerName })
+              : t('common.configDrift.description', { path: drift.configPath })}
+          </Text>
+        }
+        action={actions}
+      />
+
+      <Modal
+        title={t('common.configDrift.detailTitle')}
+        open={detailOpen}
+        onCancel={() => setDetailOpen(false)}
+        footer={actions}
+        width={760}
+        destroyOnClose
+      >
+        <Table<DriftEntry>
+          size="small"
+          rowKey="key"
+          pagination={false}
+          dataSource={drift.entries}
+          scroll={{ y: 400 }}
+          columns={[
+            { title: t('common.configDrift.key'), dataIndex: 'key', width: 220, render: (key: string) => <Text strong>{key}</Text> },
+            { title: t('common.configDrift.expected'), dataIndex: 'expected', render: renderValue },
+            { title: t('common.configDrift.actual'), dataIndex: 'actual', render: renderValue },
+          ]}
+        />
+      </Modal>
+    </>
+  );
+};
+
+export default ConfigDriftAlert;
@@ -0,0 +1 @@
+export { default as ConfigDriftAlert } from './ConfigDriftAlert';
@@ -0,0 +1,28 @@
+import React from 'react';
+import { Tooltip } from 'antd';
+import { BarChartOutlined } from '@ant-design/icons';
+import { useTranslation } from 'react-i18next';
+import { useNavigate, useLocation } from 'react-router-dom';
+import styles from './UsageButton.module.less';
+
+export const UsageButton: React.FC = () => {
+  const { t } = useTranslation();
+  const navigate = useNavigate();
+  const location = useLocation();
+
+  const isActive = location.pathname.startsWith('/usage');
+
+  return (
+    <Tooltip title={t('usage.tooltip')}>
+      <div
+        className={`${styles.usageButton} ${isActive ? styles.active : ''}`}
+        onClick={() => navigate('/usage')}
+      >
+        <BarChartOutlined className={styles.icon} />
+        <span className={styles.text}>{t('usage.button')}</span>
+      </div>
+    </Tooltip>
+  );
+};
+
+export default UsageButton;
@@ -0,0 +1,5 @@
+// Usage analytics module exports
+export * from './types';
+export * from './services/usageApi';
+export { default as UsagePage } from './pages/UsagePage';
+export { default as UsageButton } from './components/UsageButton';
@@ -0,0 +1,242 @@
+import React from 'react';
+import { Typography, Button, Select, Segmented, Table, Statistic, Card, Alert, Space, message } from 'antd';
+import { ReloadOutlined } from '@ant-design/icons';
+import { useTranslation } from 'react-i18next';
+import { getUsageReport, rescanUsageLogs } from '../services/usageApi';
+import type { UsageGroupBy, UsageReport, UsageReportRow, UsageSource } from '../types';
+import styles from './UsagePage.module.less';
+
+const { Title, Text } = Typography;
+
+const SOURCE_LABELS: Record<UsageSource, string> = {
+  claudecode: 'Claude Code',
+  codex: 'Codex',
+  opencode: 'OpenCode',
+};
+
+type RangeKey = '7d' | '30d' | '90d' | 'all';
+
+const RANGE_DAYS: Record<RangeKey, number | null> = {
+  '7d': 7,
+  '30d': 30,
+  '90d': 90,
+  all: null,
+};
+
+// 本地日期 YYYY-MM-DD（与后端按本地时区分桶一致）
+const formatDate = (date: Date) => {
+  const pad = (n: number) => String(n).padStart(2, '0');
+  return `${date.getFullYear()}-${pad(date.getMonth() + 1)}-${pad(date.getDate())}`;
+};
+
+const formatTokens = (value: number) => {
+  if (value >= 1_000_000) return `${(value / 1_000_000).toFixed(2)}M`;
+  if (value >= 1_000) return `${(value / 1_000).toFixed(1)}K`;
+  return String(value);
+};
+
+const formatCost = (value: number) => `$${value.toFixed(value >= 100 ? 0 : 2)}`;
+
+const UsagePage: React.FC = () => {
+  const { t } = useTranslation();
+  const [range, setRange] = React.useState<RangeKey>('30d');
+  const [sources, setSources] = React.useState<UsageSource[]>([]);
+  const [groupBy, setGroupBy] = React.useState<UsageGroupBy>('day');
+  const [report, setReport] = React.useState<UsageReport | null>(null);
+  const [loading, setLoading] = React.useState(false);
+  const [rescanning, setRescanning] = React.useState(false);
+
+  const loadReport = React.useCallback(async () => {
+    setLoading(true);
+    try {
+      const days = RANGE_DAYS[range];
+      const start = new Date();
+      if (days) start.setDate(start.getDate() - days + 1);
+      setReport(
+        await getUsageReport({
+          startDate: days ? formatDate(start) : undefined,
+          endDate: undefined,
+          sources,
+          groupBy,
+        })
+      );
+    } catch (error) {
+      console.error('Failed to load usage report:', error);
+      message.error(String(error));
+    } finally {
+      setLoading(false);
+    }
+  }, [range, sources, groupBy]);
+
+  React.useEffect(() => {
+    loadReport();
+  }, [loadReport]);
+
+  const handleRescan = async () => {
+    setRescanning(true);
+    try {
+      const result = await rescanUsageLogs();
+      message.success(t('usage.rescanSuccess', { count: result.filesUpdated }));
+      await loadReport();
+    } catch (error) {
+      console.error('Failed to rescan usage logs:', error);
+      message.error(String(error));
+    } finally {
+      setRescanning(false);
+    }
+  };
+
+  const renderKey = (row: UsageReportRow) => {
+    if (groupBy === 'source') return SOURCE_LABELS[row.key as UsageSource] || row.key;
+    if (groupBy === 'provider') return row.label || row.key || t('usage.unknownProvider');
+    return row.key || '-';
+  };
+
+  const columns = [
+    {
+      title: t(`usage.groupBy.${groupBy}`),
+      key: 'key',
+      ellipsis: true,
+      render: (_: unknown, row: UsageReportRow) => renderKey(row),
+    },
+    {
+      title: t('usage.input'),
+      dataIndex: 'inputTokens',
+      key: 'inputTokens',
+      width: 100,
+      render: formatTokens,
+    },
+    {
+      title: t('usage.output'),
+      dataIndex: 'outputTokens',
+      key: 'outputTokens',
+      width: 100,
+      render: formatTokens,
+    },
+    {
+      title: t('usage.cacheRead'),
+      dataIndex: 'cacheReadTokens',
+      key: 'cacheReadTokens',
+      width: 100,
+      render: formatTokens,
+    },
+    {
+      title: t('usage.cacheWrite'),
+      dataIndex: 'cacheWriteTokens',
+      key: 'cacheWriteTokens',
+      width: 100,
+      render: formatTokens,
+    },
+    {
+      title: t('usage.requests'),
+      dataIndex: 'requests',
+      key: 'requests',
+      width: 90,
+    },
+    {
+      title: t('usage.cost'),
+      key: 'costUsd',
+      width: 100,
+      render: (_: unknown, row: UsageReportRow) => (
+        <span>
+          {formatCost(row.costUsd)}
+          {row.unpricedTokens > 0 && <Text type="warning"> *</Text>}
+        </span>
+      ),
+    },
+  ];
+
+  const total = report?.total;
+
+  return (
+    <div className={styles.usagePage}>
+      <div className={styles.pageHeader}>
+        <Title level={4} style={{ margin: 0 }}>
+          {t('usage.title')}
+        </Title>
+        <Button icon={<ReloadOutlined />} loading={rescanning} onClick={handleRescan}>
+          {t('usage.rescan')}
+        </Button>
+      </div>
+
+      <Text type="secondary" style={{ fontSize: 12, marginBottom: 16, marginTop: -16 }}>
+        {t('usage.pageHint')}
+      </Text>
+
+      <div className={styles.toolbar}>
+        <Space wrap>
+          <Segmented
+            value={range}
+            onChange={(value) => setRange(value as RangeKey)}
+            options={(Object.keys(RANGE_DAYS) as RangeKey[]).map((key) => ({
+              label: t(`usage.range.${key}`),
+              value: key,
+            }))}
+          />
+          <Select
+            mode="multiple"
+            allowClear
+            value={sources}
+            onChange={setSources}
+            placeholder={t('usage.allSources')}
+            style={{ minWidth: 220 }}
+            options={(Object.keys(SOURCE_LABELS) as UsageSource[]).map((key) => ({
+              label: SOURCE_LABELS[key],
+              value: key,
+            }))}
+          />
+        </Space>
+        <Segmented
+          value={groupBy}
+          onChange={(value) => setGroupBy(value as UsageGroupBy)}
+          options={(['day', 'model', 'project', 'provider', 'source'] as UsageGroupBy[]).map((key) => ({
+            label: t(`usage.groupBy.${key}`),
+            value: key,
+          }))}
+        />
+      </div>
+
+      <div className={styles.summary}>
+        <Card size="small">
+          <Statistic title={t('usage.totalCost')} value={total ? formatCost(total.costUsd) : '-'} />
+        </Card>
+        <Card size="small">
+          <Statistic title={t('usage.input')} value={total ? formatTokens(total.inputTokens) : '-'} />
+        </Card>
+        <Card size="small">
+          <Statistic title={t('usage.output')} value={total ? formatTokens(total.outputTokens) : '-'} />
+        </Card>
+        <Card size="small">
+          <Statistic
+            title={t('usage.cache')}
+            value={total ? formatTokens(total.cacheReadTokens + total.cacheWriteTokens) : '-'}
+          />
+        </Card>
+        <Card size="small">
+          <Statistic title={t('usage.requests')} value={total?.requests ?? '-'} />
+        </Card>
+      </div>
+
+      {report && report.unpricedModels.length > 0 && (
+        <Alert
+          type="warning"
+          showIcon
+          style={{ marginBottom: 16 }}
+          message={t('usage.unpricedModels', { models: report.unpricedModels.join(', ') })}
+        />
+      )}
+
+      <Table
+        rowKey="key"
+        size="small"
+        loading={loading}
+        columns={columns}
+        dataSource={report?.rows || []}
+        pagination={{ pageSize: 20, size: 'small', hideOnSinglePage: true }}
+        locale={{ emptyText: t('usage.empty') }}
+      />
+    </div>
+  );
+};
+
+export default UsagePage;
@@ -0,0 +1,12 @@
+import { invoke } from '@tauri-apps/api/core';
+import type { UsageQuery, UsageReport, UsageScanResult } from '../types';
+
+/** Scan new session logs and aggregate usage for the query */
+export const getUsageReport = async (query: UsageQuery): Promise<UsageReport> => {
+  return invoke<UsageReport>('get_usage_report', { query });
+};
+
+/** Drop the usage cache and parse all session logs again */
+export const rescanUsageLogs = async (): Promise<UsageScanResult> => {
+  return invoke<UsageScanResult>('rescan_usage_logs');
+};
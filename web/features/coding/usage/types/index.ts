@@ -0,0 +1,42 @@
+/**
+ * Usage analytics types (mirror of the Rust `coding::usage` module)
+ */
+
+export type UsageSource = 'claudecode' | 'codex' | 'opencode';
+
+export type UsageGroupBy = 'day' | 'model' | 'project' | 'provider' | 'source';
+
+export interface UsageQuery {
+  /** Inclusive, YYYY-MM-DD */
+  startDate?: string;
+  /** Inclusive, YYYY-MM-DD */
+  endDate?: string;
+  /** Empty means all sources */
+  sources: UsageSource[];
+  groupBy: UsageGroupBy;
+}
+
+export interface UsageReportRow {
+  /** Group key; empty string when the provider is unknown */
+  key: string;
+  label?: string;
+  inputTokens: number;
+  outputTokens: number;
+  cacheReadTokens: number;
+  cacheWriteTokens: number;
+  requests: number;
+  /** USD, priced models only */
+  costUsd: number;
+  unpricedTokens: number;
+}
+
+export interface UsageReport {
+  rows: UsageReportRow[];
+  total: UsageReportRow;
+  unpricedModels: string[];
+}
+
+export interface UsageScanResult {
+  filesTotal: number;
+  filesUpdated: number;
+}
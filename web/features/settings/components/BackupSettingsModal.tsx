@@ -17,7 +17,7 @@ const BackupSettingsModal: React.FC<BackupSettingsModalProps> = ({
 }) => {
   const { t } = useTranslation();
   const [form] = Form.useForm();
-  const { backupType, localBackupPath, webdav, setBackupSettings, autoBackupEnabled, autoBackupIntervalDays, autoBackupMaxKeep, setAutoBackupSettings } = useSettingsStore();
+  const { backupType, localBackupPath, webdav, setBackupSettings, autoBackupEnabled, autoBackupIntervalDays, autoBackupMaxKeep, setAutoBackupSettings, backupPassphrase, setBackupPassphrase } = useSettingsStore();
 
   const [currentBackupType, setCurrentBackupType] = React.useState<'local' | 'webdav'>(backupType);
   const [currentLocalPath, setCurrentLocalPath] = React.useState(localBackupPath);
@@ -25,6 +25,7 @@ const BackupSettingsModal: React.FC<BackupSettingsModalProps> = ({
   const [currentAutoBackupEnabled, setCurrentAutoBackupEnabled] = React.useState(autoBackupEnabled);
   const [currentIntervalDays, setCurrentIntervalDays] = React.useState(autoBackupIntervalDays);
   const [currentMaxKeep, setCurrentMaxKeep] = React.useState(autoBackupMaxKeep);
+  const [currentPassphrase, setCurrentPassphrase] = React.useState(backupPassphrase);
 
   React.useEffect(() => {
     if (isOpen) {
@@ -33,12 +34,13 @@ const BackupSettingsModal: React.FC<BackupSettingsModalProps> = ({
       setCurrentAutoBackupEnabled(autoBackupEnabled);
       setCurrentIntervalDays(autoBackupIntervalDays);
       setCurrentMaxKeep(autoBackupMaxKeep);
+      setCurrentPassphrase(backupPassphrase);
       form.setFieldsValue({
         backupType,
         webdav,
       });
     }
-  }, [isOpen, backupType, localBackupPath, webdav, autoBackupEnabled, autoBackupIntervalDays, autoBackupMaxKeep, form]);
+  }, [isOpen, backupType, localBackupPath, webdav, autoBackupEnabled, autoBackupIntervalDays, autoBackupMaxKeep, backupPassphrase, form]);
 
   const handleSelectFolder = async () => {
     try {
@@ -68,6 +70,9 @@ const BackupSettingsModal: React.FC<BackupSettingsModalProps> = ({
         intervalDays: currentIntervalDays,
         maxKeep: currentMaxKeep,
       });
+      if (currentPassphrase !== backupPassphrase) {
+        await setBackupPassphrase(currentPassphrase);
+      }
       onClose();
     } catch {
       // Validation failed
@@ -178,6 +183,20 @@ const BackupSettingsModal: React.FC<BackupSettingsModalProps> = ({
           </>
         )}
 
+        <Form.Item label={t('settings.backupSettings.passphrase')}>
+          <Input.Password
+            value={currentPassphrase}
+            onChange={(e) => setCurrentPassphrase(e.target.value)}
+            placeholder={t('settings.backupSettings.passphrasePlaceholder')}
+            visibilityToggle
+          />
+          {currentPassphrase && (
+            <div style={{ marginTop: 4 }}>
+              <span style={{ fontSize: 12, color: '#faad14' }}>{t('settings.backupSettings.passphraseHint')}</span>
+            </div>
+          )}
+        </Form.Item>
+
         <Divider />
 
         <Form.Item label={t('settings.autoBackup.title')}>
@@ -0,0 +1,219 @@
+import React from 'react';
+import { Modal, Form, InputNumber, Switch, Divider, Table, Tag, Button, Typography, Space, Tooltip, message } from 'antd';
+import { SyncOutlined } from '@ant-design/icons';
+import { useTranslation } from 'react-i18next';
+import { useSettingsStore } from '@/stores';
+import {
+  listProviderBalances,
+  refreshProviderBalances,
+  type AccountBalanceSummary,
+} from '@/services/settingsApi';
+
+const { Text } = Typography;
+
+const MODULE_LABELS: Record<string, string> = {
+  claudecode: 'Claude Code',
+  codex: 'Codex',
+  geminicli: 'Gemini CLI',
+  opencode: 'OpenCode',
+};
+
+interface BalanceMonitorModalProps {
+  open: boolean;
+  onClose: () => void;
+}
+
+const BalanceMonitorModal: React.FC<BalanceMonitorModalProps> = ({ open, onClose }) => {
+  const { t } = useTranslation();
+  const {
+    balanceMonitorEnabled,
+    balanceMonitorIntervalMinutes,
+    balanceAlertThresholdUsd,
+    setBalanceMonitorSettings,
+  } = useSettingsStore();
+
+  const [currentEnabled, setCurrentEnabled] = React.useState(balanceMonitorEnabled);
+  const [currentInterval, setCurrentInterval] = React.useState(balanceMonitorIntervalMinutes);
+  const [currentThreshold, setCurrentThreshold] = React.useState(balanceAlertThresholdUsd);
+  const [balances, setBalances] = React.useState<AccountBalanceSummary[]>([]);
+  const [refreshing, setRefreshing] = React.useState(false);
+
+  const loadBalances = React.useCallback(async () => {
+    try {
+      setBalances(await listProviderBalances());
+    } catch (error) {
+      console.error('Failed to load provider balances:', error);
+    }
+  }, []);
+
+  React.useEffect(() => {
+    if (open) {
+      setCurrentEnabled(balanceMonitorEnabled);
+      setCurrentInterval(balanceMonitorIntervalMinutes);
+      setCurrentThreshold(balanceAlertThresholdUsd);
+      loadBalances();
+    }
+  }, [open, balanceMonitorEnabled, balanceMonitorIntervalMinutes, balanceAlertThresholdUsd, loadBalances]);
+
+  const handleSave = async () => {
+    try {
+      await setBalanceMonitorSettings({
+        enabled: currentEnabled,
+        intervalMinutes: currentInterval,
+        thresholdUsd: currentThreshold,
+      });
+      onClose();
+    } catch (error) {
+      console.error('Failed to save balance monitor settings:', error);
+      message.error(String(error));
+    }
+  };
+
+  const handleRefresh = async () => {
+    setRefreshing(true);
+    try {
+      const results = await refreshProviderBalances();
+      if (results.length === 0) {
+        message.info(t('settings.balanceMonitor.nothingToRefresh'));
+      }
+      setBalances(results);
+    } catch (error) {
+      console.error('Provider balance refresh failed:', error);
+      message.error(String(error));
+    } finally {
+      setRefreshing(false);
+    }
+  };
+
+  const columns = [
+    {
+      title: t('settings.balanceMonitor.account'),
+      key: 'account',
+      ellipsis: true,
+      render: (_: unknown, record: AccountBalanceSummary) => (
+        <Space orientation="vertical" size={0}>
+          <Text ellipsis={{ tooltip: record.siteUrl }}>{record.accountName}</Text>
+          <Space size={4} wrap>
+            {record.providers.map((provider) => (
+              <Tag
+                key={`${provider.module}-${provider.providerId}`}
+                color={provider.isApplied ? 'blue' : undefined}
+                style={{ fontSize: 11, marginInlineEnd: 0 }}
+              >
+                {MODULE_LABELS[provider.module] || provider.module}
+              </Tag>
+            ))}
+          </Space>
+        </Space>
+      ),
+    },
+    {
+      title: t('settings.balanceMonitor.balance'),
+      key: 'balance',
+      width: 130,
+      render: (_: unknown, record: AccountBalanceSummary) => {
+        if (record.balanceUsd == null) {
+          return (
+            <Text type="secondary" ellipsis={{ tooltip: record.error }} style={{ fontSize: 12 }}>
+              {record.error || '-'}
+            </Text>
+          );
+        }
+        const value = (
+          <Text type={record.isLow ? 'danger' : undefined}>
+            ${record.balanceUsd.toFixed(2)}
+            {!record.fromSite && ' *'}
+          </Text>
+        );
+        return record.fromSite ? (
+          value
+        ) : (
+          <Tooltip title={t('settings.balanceMonitor.cached', { error: record.error || '' })}>{value}</Tooltip>
+        );
+      },
+    },
+    {
+      title: t('settings.balanceMonitor.burnRate'),
+      dataIndex: 'burnRateUsdPerDay',
+      key: 'burnRate',
+      width: 110,
+      render: (value?: number) => (value != null ? `$${value.toFixed(2)}` : '-'),
+    },
+    {
+      title: t('settings.balanceMonitor.daysRemaining'),
+      dataIndex: 'daysRemaining',
+      key: 'daysRemaining',
+      width: 100,
+      render: (value?: number) => (value != null ? value.toFixed(1) : '-'),
+    },
+    {
+      title: t('settings.balanceMonitor.checkedAt'),
+      dataIndex: 'checkedAt',
+      key: 'checkedAt',
+      width: 150,
+      render: (value?: string) => (value ? new Date(value).toLocaleString() : '-'),
+    },
+  ];
+
+  return (
+    <Modal
+      title={t('settings.balanceMonitor.title')}
+      open={open}
+      onOk={handleSave}
+      onCancel={onClose}
+      width={760}
+      okText={t('common.save')}
+      cancelText={t('common.cancel')}
+    >
+      <Form layout="horizontal" size="small" labelCol={{ span: 6 }} wrapperCol={{ span: 18 }}>
+        <Form.Item label={t('settings.balanceMonitor.enable')}>
+          <Switch checked={currentEnabled} onChange={setCurrentEnabled} />
+        </Form.Item>
+        <Form.Item label={t('settings.balanceMonitor.interval')}>
+          <InputNumber
+            value={currentInterval}
+            onChange={(v) => setCurrentInterval(v && v >= 1 ? Math.floor(v) : 1)}
+            min={1}
+            precision={0}
+            style={{ width: 80 }}
+            addonAfter={t('settings.balanceMonitor.minutes')}
+          />
+        </Form.Item>
+        <Form.Item label={t('settings.balanceMonitor.threshold')}>
+          <InputNumber
+            value={currentThreshold}
+            onChange={(v) => setCurrentThreshold(v && v > 0 ? v : 0)}
+            min={0}
+            precision={2}
+            style={{ width: 100 }}
+            addonBefore="$"
+          />
+          <div style={{ marginTop: 4 }}>
+            <Text type="secondary" style={{ fontSize: 12 }}>
+              {t('settings.balanceMonitor.thresholdHint')}
+            </Text>
+          </div>
+        </Form.Item>
+      </Form>
+
+      <Divider />
+
+      <div style={{ display: 'flex', justifyContent: 'space-between', alignItems: 'center', marginBottom: 8 }}>
+        <Text strong>{t('settings.balanceMonitor.accounts')}</Text>
+        <Button size="small" icon={<SyncOutlined />} loading={refreshing} onClick={handleRefresh}>
+          {t('settings.balanceMonitor.refreshNow')}
+        </Button>
+      </div>
+      <Table
+        rowKey="accountId"
+        columns={columns}
+        dataSource={balances}
+        size="small"
+        pagination={{ pageSize: 8, size: 'small', hideOnSinglePage: true }}
+        locale={{ emptyText: t('settings.balanceMonitor.noAccounts') }}
+      />
+    </Modal>
+  );
+};
+
+export default BalanceMonitorModal;
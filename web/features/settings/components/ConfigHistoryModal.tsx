@@ -0,0 +1,333 @@
+import React from 'react';
+import {
+  Alert,
+  Modal,
+  Form,
+  InputNumber,
+  Divider,
+  Select,
+  Table,
+  Tag,
+  Button,
+  Popconfirm,
+  Typography,
+  Space,
+  Empty,
+  message,
+  theme,
+} from 'antd';
+import { DiffOutlined, RollbackOutlined, SwapOutlined } from '@ant-design/icons';
+import { useTranslation } from 'react-i18next';
+import { useSettingsStore } from '@/stores';
+import {
+  diffConfigSnapshots,
+  getConfigHistoryStatus,
+  listConfigHistory,
+  listConfigHistoryFiles,
+  restoreConfigSnapshot,
+  type ConfigHistoryFile,
+  type ConfigHistoryStatus,
+  type ConfigSnapshot,
+} from '@/services/settingsApi';
+
+const { Text } = Typography;
+
+const MODULE_LABELS: Record<string, string> = {
+  claudecode: 'Claude Code',
+  codex: 'Codex',
+  opencode: 'OpenCode',
+  openclaw: 'OpenClaw',
+  mcp: 'MCP',
+};
+
+const formatSize = (size: number) => (size < 1024 ? `${size} B` : `${(size / 1024).toFixed(1)} KB`);
+
+interface ConfigHistoryModalProps {
+  open: boolean;
+  onClose: () => void;
+}
+
+const ConfigHistoryModal: React.FC<ConfigHistoryModalProps> = ({ open, onClose }) => {
+  const { t } = useTranslation();
+  const { token } = theme.useToken();
+  const { configHistoryMaxPerFile, configHistoryMaxAgeDays, setConfigHistoryRetention } = useSettingsStore();
+
+  const [maxPerFile, setMaxPerFile] = React.useState(configHistoryMaxPerFile);
+  const [maxAgeDays, setMaxAgeDays] = React.useState(configHistoryMaxAgeDays);
+  const [files, setFiles] = React.useState<ConfigHistoryFile[]>([]);
+  const [selectedPath, setSelectedPath] = React.useState<string>();
+  const [snapshots, setSnapshots] = React.useState<ConfigSnapshot[]>([]);
+  const [selectedIds, setSelectedIds] = React.useState<React.Key[]>([]);
+  const [diff, setDiff] = React.useState<{ title: string; text: string } | null>(null);
+  const [loading, setLoading] = React.useState(false);
+  const [status, setStatus] = React.useState<ConfigHistoryStatus>();
+
+  const loadFiles = React.useCallback(async () => {
+    try {
+      const [result, nextStatus] = await Promise.all([listConfigHistoryFiles(), getConfigHistoryStatus()]);
+      setFiles(result);
+      setStatus(nextStatus);
+      setSelectedPath((current) =>
+        current && result.some((file) => file.path === current) ? current : result[0]?.path,
+      );
+    } catch (error) {
+      console.error('Failed to load config history files:', error);
+      message.error(String(error));
+    }
+  }, []);
+
+  const loadSnapshots = React.useCallback(async (path: string) => {
+    setLoading(true);
+    try {
+      setSnapshots(await listConfigHistory(path));
+    } catch (error) {
+      console.error('Failed to load config history:', error);
+      message.error(String(error));
+    } finally {
+      setLoading(false);
+    }
+  }, []);
+
+  React.useEffect(() => {
+    if (open) {
+      setMaxPerFile(configHistoryMaxPerFile);
+      setMaxAgeDays(configHistoryMaxAgeDays);
+      setDiff(null);
+      loadFiles();
+    }
+  }, [open, configHistoryMaxPerFile, configHistoryMaxAgeDays, loadFiles]);
+
+  React.useEffect(() => {
+    setSelectedIds([]);
+    setDiff(null);
+    if (open && selectedPath) {
+      loadSnapshots(selectedPath);
+    } else {
+      setSnapshots([]);
+    }
+  }, [open, selectedPath, loadSnapshots]);
+
+  const handleSave = async () => {
+    try {
+      await setConfigHistoryRetention({ maxPerFile, maxAgeDays });
+      onClose();
+    } catch (error) {
+      console.error('Failed to save config history settings:', error);
+      message.error(String(error));
+    }
+  };
+
+  const formatTime = (value: string) => new Date(value).toLocaleString();
+
+  const showDiff = async (from: ConfigSnapshot, to?: ConfigSnapshot) => {
+    if (!selectedPath) return;
+    try {
+      const text = await diffConfigSnapshots(selectedPath, from.id, to?.id);
+      setDiff({
+        title: `${formatTime(from.createdAt)} → ${to ? formatTime(to.createdAt) : t('settings.configHistory.current')}`,
+        text,
+      });
+    } catch (error) {
+      console.error('Failed to diff config snapshots:', error);
+      message.error(String(error));
+    }
+  };
+
+  const handleCompareSelected = () => {
+    const [older, newer] = snapshots
+      .filter((snapshot) => selectedIds.includes(snapshot.id))
+      .sort((a, b) => a.id.localeCompare(b.id));
+    if (older && newer) {
+      showDiff(older, newer);
+    }
+  };
+
+  const handleRestore = async (snapshot: ConfigSnapshot) => {
+    if (!selectedPath) return;
+    try {
+      await restoreConfigSnapshot(selectedPath, snapshot.id);
+      message.success(t('settings.configHistory.restored', { time: formatTime(snapshot.createdAt) }));
+      await loadFiles();
+      await loadSnapshots(selectedPath);
+      setDiff(null);
+    } catch (error) {
+      console.error('Failed to restore config snapshot:', error);
+      message.error(String(error));
+    }
+  };
+
+  const columns = [
+    {
+      title: t('settings.configHistory.time'),
+      dataIndex: 'createdAt',
+      key: 'createdAt',
+      width: 170,
+      render: (value: string) => formatTime(value),
+    },
+    {
+      title: t('settings.configHistory.action'),
+      key: 'action',
+      render: (_: unknown, record: ConfigSnapshot) => (
+        <Space size={4}>
+          <Tag style={{ marginInlineEnd: 0 }}>{MODULE_LABELS[record.module] || record.module}</Tag>
+          <Text>{t(`settings.configHistory.actions.${record.action}`, { defaultValue: record.action })}</Text>
+        </Space>
+      ),
+    },
+    {
+      title: t('settings.configHistory.size'),
+      key: 'size',
+      width: 90,
+      render: (_: unknown, record: ConfigSnapshot) =>
+        record.existed ? formatSize(record.size) : <Text type="secondary">{t('settings.configHistory.missing')}</Text>,
+    },
+    {
+      title: '',
+      key: 'operations',
+      width: 190,
+      render: (_: unknown, record: ConfigSnapshot) => (
+        <Space size={4}>
+          <Button size="small" type="text" icon={<DiffOutlined />} onClick={() => showDiff(record)}>
+            {t('settings.configHistory.diffWithCurrent')}
+          </Button>
+          <Popconfirm
+            title={t('settings.configHistory.restoreConfirm')}
+            onConfirm={() => handleRestore(record)}
+            okText={t('common.confirm')}
+            cancelText={t('common.cancel')}
+          >
+            <Button size="small" type="text" icon={<RollbackOutlined />}>
+              {t('settings.configHistory.restore')}
+            </Button>
+          </Popconfirm>
+        </Space>
+      ),
+    },
+  ];
+
+  return (
+    <Modal
+      title={t('settings.configHistory.title')}
+      open={open}
+      onOk={handleSave}
+      onCancel={onClose}
+      width={860}
+      okText={t('common.save')}
+      cancelText={t('common.cancel')}
+    >
+      <Form layout="inline" size="small">
+        <Form.Item label={t('settings.configHistory.maxPerFile')}>
+          <InputNumber
+            value={maxPerFile}
+            onChange={(v) => setMaxPerFile(v && v > 0 ? Math.floor(v) : 0)}
+            min={0}
+            precision={0}
+            style={{ width: 80 }}
+          />
+        </Form.Item>
+        <Form.Item label={t('settings.configHistory.maxAgeDays')}>
+          <InputNumber
+            value={maxAgeDays}
+            onChange={(v) => setMaxAgeDays(v && v > 0 ? Math.floor(v) : 0)}
+            min={0}
+            precision={0}
+            style={{ width: 80 }}
+            addonAfter={t('settings.configHistory.days')}
+          />
+        </Form.Item>
+      </Form>
+      <Text type="secondary" style={{ fontSize: 12 }}>
+        {t('settings.configHistory.retentionHint')}
+      </Text>
+
+      <Divider />
+
+      {status && status.skippedSnapshots > 0 && (
+        <Alert
+          type="warning"
+          showIcon
+          style={{ marginBottom: 12 }}
+          message={t('settings.configHistory.snapshotsSkipped', { count: status.skippedSnapshots })}
+          description={status.lastError}
+        />
+      )}
+
+      {files.length === 0 ? (
+        <Empty description={t('settings.configHistory.noHistory')} />
+      ) : (
+        <>
+          <div style={{ display: 'flex', gap: 8, alignItems: 'center', marginBottom: 8 }}>
+            <Select
+              value={selectedPath}
+              onChange={setSelectedPath}
+              style={{ flex: 1, minWidth: 0 }}
+              options={files.map((file) => ({
+                value: file.path,
+                label: (
+                  <Space size={6}>
+                    <Tag style={{ marginInlineEnd: 0 }}>{MODULE_LABELS[file.module] || file.module}</Tag>
+                    <Text type={file.exists ? undefined : 'secondary'}>{file.path}</Text>
+                    <Text type="secondary">({file.snapshotCount})</Text>
+                  </Space>
+                ),
+              }))}
+            />
+            <Button
+              size="small"
+              icon={<SwapOutlined />}
+              disabled={selectedIds.length !== 2}
+              onClick={handleCompareSelected}
+            >
+              {t('settings.configHistory.compareSelected')}
+            </Button>
+          </div>
+          <Table
+            rowKey="id"
+            columns={columns}
+            dataSource={snapshots}
+            loading={loading}
+            size="small"
+            rowSelection={{
+              selectedRowKeys: selectedIds,
+              onChange: (keys) => setSelectedIds(keys.slice(-2)),
+            }}
+            pagination={{ pageSize: 8, size: 'small', hideOnSinglePage: true }}
+          />
+        </>
+      )}
+
+      {diff && (
+        <div style={{ marginTop: 12 }}>
+          <Text strong>{diff.title}</Text>
+          <pre
+            style={{
+              margin: '8px 0 0',
+              maxHeight: 320,
+              overflow: 'auto',
+              fontSize: 12,
+              padding: 8,
+              background: token.colorFillQuaternary,
+              borderRadius: 4,
+            }}
+          >
+            {diff.text
+              ? diff.text.split('\n').map((line, idx) => {
+                  let color: string | undefined;
+                  if (line.startsWith('+') && !line.startsWith('+++')) color = token.colorSuccess;
+                  else if (line.startsWith('-') && !line.startsWith('---')) color = token.colorError;
+                  else if (line.startsWith('@@')) color = token.colorInfo;
+                  return (
+                    <div key={idx} style={{ color }}>
+                      {line || ' '}
+                    </div>
+                  );
+                })
+              : t('settings.configHistory.identical')}
+          </pre>
+        </div>
+      )}
+    </Modal>
+  );
+};
+
+export default ConfigHistoryModal;
@@ -0,0 +1,191 @@
+import React from 'react';
+import { Modal, Form, InputNumber, Switch, Divider, Table, Tag, Button, Typography, message } from 'antd';
+import { SyncOutlined } from '@ant-design/icons';
+import { useTranslation } from 'react-i18next';
+import { useSettingsStore } from '@/stores';
+import {
+  listProviderHealthHistory,
+  runProviderHealthCheck,
+  type ProviderHealthCheck,
+} from '@/services/settingsApi';
+
+const { Text } = Typography;
+
+const MODULE_LABELS: Record<string, string> = {
+  claudecode: 'Claude Code',
+  codex: 'Codex',
+  opencode: 'OpenCode',
+};
+
+interface ProviderHealthModalProps {
+  open: boolean;
+  onClose: () => void;
+}
+
+const ProviderHealthModal: React.FC<ProviderHealthModalProps> = ({ open, onClose }) => {
+  const { t } = useTranslation();
+  const {
+    healthMonitorEnabled,
+    healthMonitorIntervalMinutes,
+    healthMonitorFailureThreshold,
+    healthMonitorAutoFailover,
+    setHealthMonitorSettings,
+  } = useSettingsStore();
+
+  const [currentEnabled, setCurrentEnabled] = React.useState(healthMonitorEnabled);
+  const [currentInterval, setCurrentInterval] = React.useState(healthMonitorIntervalMinutes);
+  const [currentThreshold, setCurrentThreshold] = React.useState(healthMonitorFailureThreshold);
+  const [currentAutoFailover, setCurrentAutoFailover] = React.useState(healthMonitorAutoFailover);
+  const [history, setHistory] = React.useState<ProviderHealthCheck[]>([]);
+  const [checking, setChecking] = React.useState(false);
+
+  const loadHistory = React.useCallback(async () => {
+    try {
+      setHistory(await listProviderHealthHistory(undefined, 50));
+    } catch (error) {
+      console.error('Failed to load provider health history:', error);
+    }
+  }, []);
+
+  React.useEffect(() => {
+    if (open) {
+      setCurrentEnabled(healthMonitorEnabled);
+      setCurrentInterval(healthMonitorIntervalMinutes);
+      setCurrentThreshold(healthMonitorFailureThreshold);
+      setCurrentAutoFailover(healthMonitorAutoFailover);
+      loadHistory();
+    }
+  }, [open, healthMonitorEnabled, healthMonitorIntervalMinutes, healthMonitorFailureThreshold, healthMonitorAutoFailover, loadHistory]);
+
+  const handleSave = async () => {
+    try {
+      await setHealthMonitorSettings({
+        enabled: currentEnabled,
+        intervalMinutes: currentInterval,
+        failureThreshold: currentThreshold,
+        autoFailover: currentAutoFailover,
+      });
+      onClose();
+    } catch (error) {
+      console.error('Failed to save provider health settings:', error);
+      message.error(String(error));
+    }
+  };
+
+  const handleCheckNow = async () => {
+    setChecking(true);
+    try {
+      const results = await runProviderHealthCheck();
+      if (results.length === 0) {
+        message.info(t('settings.providerHealth.nothingToCheck'));
+      }
+      await loadHistory();
+    } catch (error) {
+      console.error('Provider health check failed:', error);
+      message.error(String(error));
+    } finally {
+      setChecking(false);
+    }
+  };
+
+  const columns = [
+    {
+      title: t('settings.providerHealth.time'),
+      dataIndex: 'checkedAt',
+      key: 'checkedAt',
+      width: 150,
+      render: (value: string) => new Date(value).toLocaleString(),
+    },
+    {
+      title: t('settings.providerHealth.module'),
+      dataIndex: 'module',
+      key: 'module',
+      width: 100,
+      render: (value: string) => MODULE_LABELS[value] || value,
+    },
+    {
+      title: t('settings.providerHealth.provider'),
+      dataIndex: 'providerName',
+      key: 'providerName',
+      ellipsis: true,
+    },
+    {
+      title: t('settings.providerHealth.result'),
+      key: 'result',
+      width: 160,
+      render: (_: unknown, record: ProviderHealthCheck) =>
+        record.status === 'success' ? (
+          <Tag color="success">{record.latencyMs != null ? `${record.latencyMs} ms` : 'OK'}</Tag>
+        ) : (
+          <Text type="danger" ellipsis={{ tooltip: record.errorMessage }} style={{ fontSize: 12 }}>
+            {record.errorMessage || t('settings.providerHealth.failed')}
+          </Text>
+        ),
+    },
+  ];
+
+  return (
+    <Modal
+      title={t('settings.providerHealth.title')}
+      open={open}
+      onOk={handleSave}
+      onCancel={onClose}
+      width={720}
+      okText={t('common.save')}
+      cancelText={t('common.cancel')}
+    >
+      <Form layout="horizontal" size="small" labelCol={{ span: 6 }} wrapperCol={{ span: 18 }}>
+        <Form.Item label={t('settings.providerHealth.enable')}>
+          <Switch checked={currentEnabled} onChange={setCurrentEnabled} />
+        </Form.Item>
+        <Form.Item label={t('settings.providerHealth.interval')}>
+          <InputNumber
+            value={currentInterval}
+            onChange={(v) => setCurrentInterval(v && v >= 1 ? Math.floor(v) : 1)}
+            min={1}
+            precision={0}
+            style={{ width: 80 }}
+            addonAfter={t('settings.providerHealth.minutes')}
+          />
+        </Form.Item>
+        <Form.Item label={t('settings.providerHealth.threshold')}>
+          <InputNumber
+            value={currentThreshold}
+            onChange={(v) => setCurrentThreshold(v && v >= 1 ? Math.floor(v) : 1)}
+            min={1}
+            precision={0}
+            style={{ width: 80 }}
+            addonAfter={t('settings.providerHealth.times')}
+          />
+        </Form.Item>
+        <Form.Item label={t('settings.providerHealth.autoFailover')}>
+          <Switch checked={currentAutoFailover} onChange={setCurrentAutoFailover} />
+          <div style={{ marginTop: 4 }}>
+            <Text type="secondary" style={{ fontSize: 12 }}>
+              {t('settings.providerHealth.autoFailoverHint')}
+            </Text>
+          </div>
+        </Form.Item>
+      </Form>
+
+      <Divider />
+
+      <div style={{ display: 'flex', justifyContent: 'space-between', alignItems: 'center', marginBottom: 8 }}>
+        <Text strong>{t('settings.providerHealth.history')}</Text>
+        <Button size="small" icon={<SyncOutlined />} loading={checking} onClick={handleCheckNow}>
+          {t('settings.providerHealth.checkNow')}
+        </Button>
+      </div>
+      <Table
+        rowKey="id"
+        columns={columns}
+        dataSource={history}
+        size="small"
+        pagination={{ pageSize: 8, size: 'small' }}
+        locale={{ emptyText: t('settings.providerHealth.noHistory') }}
+      />
+    </Modal>
+  );
+};
+
+export default ProviderHealthModal;
@@ -0,0 +1,121 @@
+/**
+ * SSH Config Import Modal
+ *
+ * Lists host aliases from ~/.ssh/config and creates connection presets from them
+ */
+
+import React, { useEffect, useState } from 'react';
+import { Modal, List, Checkbox, Empty, Spin, Tag, Typography, message } from 'antd';
+import { useTranslation } from 'react-i18next';
+import { sshImportConfigHosts, sshListConfigHosts } from '@/services/sshSyncApi';
+import type { SSHConfigHost, SSHConnection } from '@/types/sshsync';
+
+const { Text } = Typography;
+
+interface SSHConfigImportModalProps {
+  open: boolean;
+  onClose: () => void;
+  onImported: (connections: SSHConnection[]) => void;
+}
+
+export const SSHConfigImportModal: React.FC<SSHConfigImportModalProps> = ({
+  open,
+  onClose,
+  onImported,
+}) => {
+  const { t } = useTranslation();
+  const [hosts, setHosts] = useState<SSHConfigHost[]>([]);
+  const [selected, setSelected] = useState<string[]>([]);
+  const [loading, setLoading] = useState(false);
+  const [importing, setImporting] = useState(false);
+
+  useEffect(() => {
+    if (!open) return;
+
+    setLoading(true);
+    setSelected([]);
+    sshListConfigHosts()
+      .then(setHosts)
+      .catch((error) => {
+        console.error('Failed to read ssh config:', error);
+        message.error(String(error));
+        setHosts([]);
+      })
+      .finally(() => setLoading(false));
+  }, [open]);
+
+  const toggle = (alias: string, checked: boolean) => {
+    setSelected((prev) => (checked ? [...prev, alias] : prev.filter((a) => a !== alias)));
+  };
+
+  const handleOk = async () => {
+    if (selected.length === 0) {
+      onClose();
+      return;
+    }
+
+    setImporting(true);
+    try {
+      const created = await sshImportConfigHosts(selected);
+      message.success(t('settings.ssh.importConfigSuccess', { count: created.length }));
+      onImported(created);
+      onClose();
+    } catch (error) {
+      console.error('Failed to import ssh config hosts:', error);
+      message.error(String(error));
+    } finally {
+      setImporting(false);
+    }
+  };
+
+  return (
+    <Modal
+      title={t('settings.ssh.importConfig')}
+      open={open}
+      onOk={handleOk}
+      onCancel={onClose}
+      okText={t('settings.ssh.importSelected')}
+      cancelText={t('common.cancel')}
+      okButtonProps={{ disabled: selected.length === 0 }}
+      confirmLoading={importing}
+      destroyOnClose
+    >
+      <Text type="secondary" style={{ fontSize: 12, display: 'block', marginBottom: 12 }}>
+        {t('settings.ssh.importConfigHint')}
+      </Text>
+      <Spin spinning={loading}>
+        {hosts.length === 0 && !loading ? (
+          <Empty description={t('settings.ssh.noConfigHosts')} />
+        ) : (
+          <List
+            size="small"
+            dataSource={hosts}
+            renderItem={(host) => (
+              <List.Item>
+                <Checkbox
+                  checked={selected.includes(host.alias)}
+                  disabled={host.imported}
+                  onChange={(e) => toggle(host.alias, e.target.checked)}
+                >
+                  <Text strong>{host.alias}</Text>{' '}
+                  <Text type="secondary">
+                    {host.user}@{host.hostName}:{host.port}
+                  </Text>
+                </Checkbox>
+                <span>
+                  {host.proxyJump.length > 0 && (
+                    <Tag>{t('settings.ssh.viaJumpHosts', { count: host.proxyJump.length })}</Tag>
+                  )}
+                  <Tag color={host.identityFile ? 'blue' : 'purple'}>
+                    {host.identityFile ? t('settings.ssh.authKey') : t('settings.ssh.authAgent')}
+                  </Tag>
+                  {host.imported && <Tag>{t('settings.ssh.alreadyImported')}</Tag>}
+                </span>
+              </List.Item>
+            )}
+          />
+        )}
+      </Spin>
+    </Modal>
+  );
+};
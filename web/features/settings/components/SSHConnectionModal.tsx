@@ -5,13 +5,47 @@
  */
 
 import React, { useEffect } from 'react';
-import { Modal, Form, Input, InputNumber, Radio, Space } from 'antd';
+import { Modal, Form, Input, InputNumber, Radio, Select, Space, Button, Typography } from 'antd';
+import { PlusOutlined, MinusCircleOutlined } from '@ant-design/icons';
 import { useTranslation } from 'react-i18next';
-import type { SSHConnection } from '@/types/sshsync';
+import type { SSHConnection, SSHJumpHost } from '@/types/sshsync';
 
 // Check if the value looks like PEM private key content (not a file path)
 const isPrivateKeyContent = (value: string) => value.trim().startsWith('-----BEGIN');
 
+interface JumpHostFormValue {
+  host: string;
+  port?: number;
+  username?: string;
+  authMethod: string;
+  credential?: string;
+}
+
+// Jump hosts keep a single "credential" field in the form: key path/content or password
+const jumpHostToForm = (jump: SSHJumpHost): JumpHostFormValue => ({
+  host: jump.host,
+  port: jump.port || 22,
+  username: jump.username,
+  authMethod: jump.authMethod || 'agent',
+  credential:
+    jump.authMethod === 'password' ? jump.password : jump.privateKeyContent || jump.privateKeyPath,
+});
+
+const jumpHostFromForm = (value: JumpHostFormValue): SSHJumpHost => {
+  const credential = value.credential || '';
+  const isContent = value.authMethod === 'key' && isPrivateKeyContent(credential);
+  return {
+    host: value.host.trim(),
+    port: value.port || 22,
+    username: value.username?.trim() || '',
+    authMethod: value.authMethod,
+    password: value.authMethod === 'password' ? credential : '',
+    privateKeyPath: value.authMethod === 'key' && !isContent ? credential : '',
+    privateKeyContent: isContent ? credential : '',
+    passphrase: '',
+  };
+};
+
 interface SSHConnectionModalProps {
   open: boolean;
   onClose: () => void;
@@ -41,12 +75,14 @@ export const SSHConnectionModal: React.FC<SSHConnectionModalProps> = ({
         password: connection.password,
         privateKeyPath: connection.privateKeyContent || connection.privateKeyPath,
         passphrase: connection.passphrase,
+        jumpHosts: (connection.jumpHosts || []).map(jumpHostToForm),
       });
     } else if (open) {
       form.resetFields();
       form.setFieldsValue({
         port: 22,
         authMethod: 'key',
+        jumpHosts: [],
       });
     }
   }, [open, connection, form]);
@@ -69,6 +105,7 @@ export const SSHConnectionModal: React.FC<SSHConnectionModalProps> = ({
         privateKeyContent: isContent ? keyInput : '',
         passphrase: values.authMethod === 'key' ? values.passphrase || '' : '',
         sortOrder: connection?.sortOrder || 0,
+        jumpHosts: (values.jumpHosts || []).map(jumpHostFromForm),
       });
       onClose();
     } catch {
@@ -122,10 +159,17 @@ export const SSHConnectionModal: React.FC<SSHConnectionModalProps> = ({
             <Space direction="horizontal">
               <Radio value="key">{t('settings.ssh.authKey')}</Radio>
               <Radio value="password">{t('settings.ssh.authPassword')}</Radio>
+              <Radio value="agent">{t('settings.ssh.authAgent')}</Radio>
             </Space>
           </Radio.Group>
         </Form.Item>
 
+        {authMethod === 'agent' && (
+          <Form.Item wrapperCol={{ offset: 6, span: 18 }}>
+            <Typography.Text type="secondary">{t('settings.ssh.agentHint')}</Typography.Text>
+          </Form.Item>
+        )}
+
         {authMethod === 'key' && (
           <>
             <Form.Item name="privateKeyPath" label={t('settings.ssh.privateKey')}>
@@ -149,6 +193,66 @@ export const SSHConnectionModal: React.FC<SSHConnectionModalProps> = ({
             <Input.Password />
           </Form.Item>
         )}
+
+        <Form.Item label={t('settings.ssh.jumpHosts')} tooltip={t('settings.ssh.jumpHostsHint')}>
+          <Form.List name="jumpHosts">
+            {(fields, { add, remove }) => (
+              <>
+                {fields.map((field) => (
+                  <Space key={field.key} align="baseline" wrap style={{ marginBottom: 8 }}>
+                    <Form.Item
+                      name={[field.name, 'host']}
+                      noStyle
+                      rules={[{ required: true, message: t('settings.ssh.hostRequired') }]}
+                    >
+                      <Input placeholder={t('settings.ssh.host')} style={{ width: 140 }} />
+                    </Form.Item>
+                    <Form.Item name={[field.name, 'port']} noStyle>
+                      <InputNumber min={1} max={65535} placeholder="22" style={{ width: 80 }} />
+                    </Form.Item>
+                    <Form.Item name={[field.name, 'username']} noStyle>
+                      <Input placeholder={t('settings.ssh.jumpUsernamePlaceholder')} style={{ width: 120 }} />
+                    </Form.Item>
+                    <Form.Item name={[field.name, 'authMethod']} noStyle initialValue="agent">
+                      <Select
+                        style={{ width: 110 }}
+                        options={[
+                          { value: 'agent', label: t('settings.ssh.authAgent') },
+                          { value: 'key', label: t('settings.ssh.authKey') },
+                          { value: 'password', label: t('settings.ssh.authPassword') },
+                        ]}
+                      />
+                    </Form.Item>
+                    <Form.Item noStyle dependencies={[['jumpHosts', field.name, 'authMethod']]}>
+                      {({ getFieldValue }) => {
+                        const jumpAuth = getFieldValue(['jumpHosts', field.name, 'authMethod']);
+                        if (jumpAuth === 'key') {
+                          return (
+                            <Form.Item name={[field.name, 'credential']} noStyle>
+                              <Input placeholder={t('settings.ssh.privateKeyPlaceholder')} style={{ width: 200 }} />
+                            </Form.Item>
+                          );
+                        }
+                        if (jumpAuth === 'password') {
+                          return (
+                            <Form.Item name={[field.name, 'credential']} noStyle>
+                              <Input.Password placeholder={t('settings.ssh.password')} style={{ width: 200 }} />
+                            </Form.Item>
+                          );
+                        }
+                        return null;
+                      }}
+                    </Form.Item>
+                    <MinusCircleOutlined onClick={() => remove(field.name)} />
+                  </Space>
+                ))}
+                <Button type="dashed" size="small" icon={<PlusOutlined />} onClick={() => add({ authMethod: 'agent', port: 22 })}>
+                  {t('settings.ssh.addJumpHost')}
+                </Button>
+              </>
+            )}
+          </Form.List>
+        </Form.Item>
       </Form>
     </Modal>
   );
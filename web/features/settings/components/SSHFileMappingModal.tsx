@@ -5,7 +5,7 @@
  */
 
 import React, { useEffect } from 'react';
-import { Modal, Form, Input, Select, Switch, Space, Typography, Divider } from 'antd';
+import { Modal, Form, Input, Select, Switch, Space, Typography, Divider, Radio } from 'antd';
 import { useTranslation } from 'react-i18next';
 import { sshAddFileMapping, sshUpdateFileMapping } from '@/services/sshSyncApi';
 import type { SSHFileMapping } from '@/types/sshsync';
@@ -27,7 +27,7 @@ export const SSHFileMappingModal: React.FC<SSHFileMappingModalProps> = ({ open,
   useEffect(() => {
     if (open) {
       if (mapping && mapping.id) {
-        form.setFieldsValue(mapping);
+        form.setFieldsValue({ ...mapping, direction: mapping.direction || 'push' });
       } else {
         form.resetFields();
         form.setFieldsValue({
@@ -35,6 +35,7 @@ export const SSHFileMappingModal: React.FC<SSHFileMappingModalProps> = ({ open,
           enabled: true,
           isPattern: false,
           isDirectory: false,
+          direction: 'push',
         });
       }
     }
@@ -114,6 +115,18 @@ export const SSHFileMappingModal: React.FC<SSHFileMappingModalProps> = ({ open,
           <Input placeholder="~/.config/opencode/config.json" />
         </Form.Item>
 
+        <Form.Item
+          name="direction"
+          label={t('settings.ssh.syncDirection')}
+          extra={t('settings.ssh.syncDirectionHint')}
+        >
+          <Radio.Group>
+            <Radio.Button value="push">{t('settings.ssh.directionPush')}</Radio.Button>
+            <Radio.Button value="pull">{t('settings.ssh.directionPull')}</Radio.Button>
+            <Radio.Button value="both">{t('settings.ssh.directionBoth')}</Radio.Button>
+          </Radio.Group>
+        </Form.Item>
+
         <Divider />
 
         <Form.Item
@@ -11,12 +11,15 @@ import './SSHStatusIndicator.css';
 interface SSHStatusIndicatorProps {
   enabled: boolean;
   status: 'idle' | 'success' | 'error';
+  /** Number of connections a sync goes to (shown when more than one) */
+  targetCount?: number;
   onClick: () => void;
 }
 
 export const SSHStatusIndicator: React.FC<SSHStatusIndicatorProps> = ({
   enabled,
   status,
+  targetCount = 1,
   onClick,
 }) => {
   const { t } = useTranslation();
@@ -38,6 +41,11 @@ export const SSHStatusIndicator: React.FC<SSHStatusIndicatorProps> = ({
     >
       <span className={`ssh-status-dot ssh-status-dot-${color}`} />
       <span className="ssh-status-label">SSH</span>
+      {enabled && targetCount > 1 && (
+        <span className="ssh-status-label" title={t('settings.ssh.targetCount', { count: targetCount })}>
+          ×{targetCount}
+        </span>
+      )}
     </div>
   );
 };
@@ -6,12 +6,14 @@
 
 import React, { useState, useEffect } from 'react';
 import { Modal, Switch, Select, Button, List, Space, Typography, Alert, Spin, Tag, Modal as AntdModal, Tabs, Tooltip, Progress, theme } from 'antd';
-import { CheckCircleOutlined, CloseCircleOutlined, ReloadOutlined, DeleteOutlined, EditOutlined, PlusOutlined, ClearOutlined, ApiOutlined } from '@ant-design/icons';
+import { CheckCircleOutlined, CloseCircleOutlined, ReloadOutlined, DeleteOutlined, EditOutlined, PlusOutlined, ClearOutlined, ApiOutlined, ImportOutlined, DiffOutlined } from '@ant-design/icons';
 import { useTranslation } from 'react-i18next';
 import { useSSHSync } from '@/features/settings/hooks/useSSHSync';
 import { useSettingsStore } from '@/stores';
 import { SSHConnectionModal } from './SSHConnectionModal';
+import { SSHConfigImportModal } from './SSHConfigImportModal';
 import { SSHFileMappingModal } from './SSHFileMappingModal';
+import { SyncPreviewModal } from './SyncPreviewModal';
 import {
   sshDeleteFileMapping,
   sshResetFileMappings,
@@ -20,8 +22,10 @@ import {
   sshUpdateConnection,
   sshDeleteConnection,
   sshSetActiveConnection,
+  sshForgetHostKey,
+  sshSyncPreview,
 } from '@/services/sshSyncApi';
-import type { SSHConnection, SSHFileMapping, SSHConnectionResult } from '@/types/sshsync';
+import type { SSHConnection, SSHFileMapping, SSHConnectionResult, SyncConflict } from '@/types/sshsync';
 
 const { Text } = Typography;
 
@@ -59,7 +63,7 @@ interface SSHSyncModalProps {
 export const SSHSyncModal: React.FC<SSHSyncModalProps> = ({ open, onClose }) => {
   const { t } = useTranslation();
   const { token } = theme.useToken();
-  const { config, status, loading, syncing, syncWarning, syncProgress, saveConfig, sync, dismissSyncWarning } = useSSHSync();
+  const { config, status, loading, syncing, syncWarning, syncProgress, autoSyncMappings, conflicts, saveConfig, sync, dismissSyncWarning, resolveConflict } = useSSHSync();
   const { visibleTabs } = useSettingsStore();
 
   // Filter module keys by visibleTabs
@@ -67,7 +71,10 @@ export const SSHSyncModal: React.FC<SSHSyncModalProps> = ({ open, onClose }) =>
 
   const [enabled, setEnabled] = useState(false);
   const [activeConnectionId, setActiveConnectionId] = useState('');
+  const [targetConnectionIds, setTargetConnectionIds] = useState<string[]>([]);
   const [connectionModalOpen, setConnectionModalOpen] = useState(false);
+  const [importModalOpen, setImportModalOpen] = useState(false);
+  const [previewOpen, setPreviewOpen] = useState(false);
   const [editingConnection, setEditingConnection] = useState<SSHConnection | null>(null);
   const [editingMapping, setEditingMapping] = useState<SSHFileMapping | null>(null);
   const [mappingModalOpen, setMappingModalOpen] = useState(false);
@@ -80,6 +87,7 @@ export const SSHSyncModal: React.FC<SSHSyncModalProps> = ({ open, onClose }) =>
     if (config) {
       setEnabled(config.enabled);
       setActiveConnectionId(config.activeConnectionId);
+      setTargetConnectionIds(config.targetConnectionIds || []);
     }
   }, [config]);
 
@@ -97,6 +105,19 @@ export const SSHSyncModal: React.FC<SSHSyncModalProps> = ({ open, onClose }) =>
     }
   };
 
+  // Handle auto sync on file change switch
+  const handleAutoSyncChange = async (checked: boolean) => {
+    if (!config) return;
+    try {
+      await saveConfig({
+        ...config,
+        autoSyncOnChange: checked,
+      });
+    } catch (error) {
+      console.error('Failed to save auto sync state:', error);
+    }
+  };
+
   // Handle active connection change
   const handleActiveConnectionChange = async (value: string) => {
     setActiveConnectionId(value);
@@ -108,6 +129,20 @@ export const SSHSyncModal: React.FC<SSHSyncModalProps> = ({ open, onClose }) =>
     }
   };
 
+  // Handle other sync targets change
+  const handleTargetConnectionsChange = async (ids: string[]) => {
+    if (!config) return;
+    setTargetConnectionIds(ids);
+    try {
+      await saveConfig({
+        ...config,
+        targetConnectionIds: ids,
+      });
+    } catch (error) {
+      console.error('Failed to save sync targets:', error);
+    }
+  };
+
   // Test connection
   const handleTestConnection = async (connId?: string) => {
     const targetId = connId || activeConnectionId;
@@ -129,6 +164,26 @@ export const SSHSyncModal: React.FC<SSHSyncModalProps> = ({ open, onClose }) =>
     }
   };
 
+  // Forget the recorded host key after the user confirms the server key was replaced
+  const handleForgetHostKey = () => {
+    if (!activeConnectionId) return;
+    AntdModal.confirm({
+      title: t('settings.ssh.forgetHostKey'),
+      content: t('settings.ssh.forgetHostKeyConfirm'),
+      okText: t('common.confirm'),
+      cancelText: t('common.cancel'),
+      okButtonProps: { danger: true },
+      onOk: async () => {
+        try {
+          await sshForgetHostKey(activeConnectionId);
+          await handleTestConnection(activeConnectionId);
+        } catch (error) {
+          console.error('Failed to forget host key:', error);
+        }
+      },
+    });
+  };
+
   // Auto test connection when modal opens or active connection changes
   useEffect(() => {
     if (open && enabled && activeConnectionId && config?.connections.length) {
@@ -150,6 +205,7 @@ export const SSHSyncModal: React.FC<SSHSyncModalProps> = ({ open, onClose }) =>
       privateKeyContent: '',
       passphrase: '',
       sortOrder: (config?.connections.length || 0),
+      jumpHosts: [],
     });
     setConnectionModalOpen(true);
   };
@@ -204,6 +260,18 @@ export const SSHSyncModal: React.FC<SSHSyncModalProps> = ({ open, onClose }) =>
     }
   };
 
+  const handleConfigImported = async (connections: SSHConnection[]) => {
+    // Auto-select the first imported connection if none is active
+    if (!activeConnectionId && connections.length > 0) {
+      setActiveConnectionId(connections[0].id);
+      try {
+        await sshSetActiveConnection(connections[0].id);
+      } catch (error) {
+        console.error('Failed to set active connection:', error);
+      }
+    }
+  };
+
   // File mapping management
   const handleEditMapping = (mapping: SSHFileMapping) => {
     setEditingMapping(mapping);
@@ -220,6 +288,7 @@ export const SSHSyncModal: React.FC<SSHSyncModalProps> = ({ open, onClose }) =>
       enabled: true,
       isPattern: false,
       isDirectory: false,
+      direction: 'push',
     };
     setEditingMapping(newMapping);
     setMappingModalOpen(true);
@@ -267,6 +336,14 @@ export const SSHSyncModal: React.FC<SSHSyncModalProps> = ({ open, onClose }) =>
     }
   };
 
+  const handleResolveConflict = async (conflict: SyncConflict, keep: 'local' | 'remote') => {
+    try {
+      await resolveConflict(conflict, keep);
+    } catch (error) {
+      console.error('Failed to resolve conflict:', error);
+    }
+  };
+
   const formatSyncTime = (time?: string) => {
     if (!time) return t('settings.ssh.never');
     return new Date(time).toLocaleString();
@@ -342,12 +419,14 @@ export const SSHSyncModal: React.FC<SSHSyncModalProps> = ({ open, onClose }) =>
                   <Space>
                     <Text>{item.name}</Text>
                     <Tag color={MODULE_COLORS[item.module] || 'default'}>{MODULE_NAMES[item.module] || item.module}</Tag>
+                    {item.direction === 'pull' && <Tag>{t('settings.ssh.directionPull')}</Tag>}
+                    {item.direction === 'both' && <Tag color="cyan">{t('settings.ssh.directionBoth')}</Tag>}
                     {!item.enabled && <Tag>{t('settings.ssh.disabled')}</Tag>}
                   </Space>
                 }
                 description={
                   <Text type="secondary" style={{ fontSize: 12 }}>
-                    {item.localPath} → {item.remotePath}
+                    {item.localPath} {item.direction === 'both' ? '⇄' : item.direction === 'pull' ? '←' : '→'} {item.remotePath}
                   </Text>
                 }
               />
@@ -377,8 +456,21 @@ export const SSHSyncModal: React.FC<SSHSyncModalProps> = ({ open, onClose }) =>
               onChange={handleEnabledChange}
             />
           </div>
+          {!config?.autoSyncOnChange && (
+            <Text type="secondary" style={{ fontSize: 12, marginBottom: 16, display: 'block' }}>
+              {t('settings.ssh.enableSyncHint')}
+            </Text>
+          )}
+          <div style={{ display: 'flex', justifyContent: 'space-between', alignItems: 'center', marginBottom: 4 }}>
+            <Text>{t('settings.ssh.autoSyncOnChange')}</Text>
+            <Switch
+              checked={config?.autoSyncOnChange ?? false}
+              onChange={handleAutoSyncChange}
+              disabled={!enabled}
+            />
+          </div>
           <Text type="secondary" style={{ fontSize: 12, marginBottom: 16, display: 'block' }}>
-            {t('settings.ssh.enableSyncHint')}
+            {t('settings.ssh.autoSyncOnChangeHint')}
           </Text>
 
           {/* B. Connection management */}
@@ -428,6 +520,14 @@ export const SSHSyncModal: React.FC<SSHSyncModalProps> = ({ open, onClose }) =>
                   size="small"
                 />
               </Tooltip>
+              <Tooltip title={t('settings.ssh.importConfig')}>
+                <Button
+                  icon={<ImportOutlined />}
+                  onClick={() => setImportModalOpen(true)}
+                  disabled={!enabled}
+                  size="small"
+                />
+              </Tooltip>
               <Tooltip title={t('settings.ssh.testConnection')}>
                 <Button
                   icon={<ApiOutlined />}
@@ -440,21 +540,59 @@ export const SSHSyncModal: React.FC<SSHSyncModalProps> = ({ open, onClose }) =>
             </Space>
           </div>
 
+          {(config?.connections.length ?? 0) > 1 && (
+            <div style={{ marginBottom: 16 }}>
+              <Space style={{ width: '100%' }}>
+                <Text>{t('settings.ssh.syncTargets')}:</Text>
+                <Select
+                  mode="multiple"
+                  value={targetConnectionIds.filter((id) => id !== activeConnectionId)}
+                  onChange={handleTargetConnectionsChange}
+                  disabled={!enabled || !activeConnectionId}
+                  style={{ minWidth: 300 }}
+                  placeholder={t('settings.ssh.syncTargetsPlaceholder')}
+                  options={config?.connections
+                    .filter((c) => c.id !== activeConnectionId)
+                    .map((c) => ({ value: c.id, label: c.name }))}
+                />
+              </Space>
+              <Text type="secondary" style={{ fontSize: 12, display: 'block', marginTop: 4 }}>
+                {t('settings.ssh.syncTargetsHint')}
+              </Text>
+            </div>
+          )}
+
           {/* C. Connection status */}
           {activeConnection && (
             <div style={{ marginBottom: 16, padding: 8, background: 'var(--color-bg-elevated)', borderRadius: 4 }}>
               <Space wrap>
                 <Text type="secondary">{activeConnection.host}:{activeConnection.port}</Text>
                 <Tag>{activeConnection.username}@</Tag>
-                <Tag color={activeConnection.authMethod === 'key' ? 'blue' : 'green'}>
-                  {activeConnection.authMethod === 'key' ? t('settings.ssh.authKey') : t('settings.ssh.authPassword')}
+                <Tag color={activeConnection.authMethod === 'key' ? 'blue' : activeConnection.authMethod === 'agent' ? 'purple' : 'green'}>
+                  {activeConnection.authMethod === 'key'
+                    ? t('settings.ssh.authKey')
+                    : activeConnection.authMethod === 'agent'
+                      ? t('settings.ssh.authAgent')
+                      : t('settings.ssh.authPassword')}
                 </Tag>
+                {activeConnection.jumpHosts?.length > 0 && (
+                  <Tooltip title={activeConnection.jumpHosts.map((j) => `${j.host}:${j.port || 22}`).join(' → ')}>
+                    <Tag>{t('settings.ssh.viaJumpHosts', { count: activeConnection.jumpHosts.length })}</Tag>
+                  </Tooltip>
+                )}
                 {testing && <Spin size="small" />}
                 {!testing && testResult && (
                   testResult.connected
                     ? <Tag color="success">{t('settings.ssh.connected')}</Tag>
                     : <Tag color="error">{t('settings.ssh.connectionFailed')}</Tag>
                 )}
+                {!testing && testResult?.hostKeyError && (
+                  <Tag color="warning">
+                    {testResult.hostKeyError.kind === 'revoked'
+                      ? t('settings.ssh.hostKeyRevoked')
+                      : t('settings.ssh.hostKeyChanged')}
+                  </Tag>
+                )}
               </Space>
               {testResult?.serverInfo && (
                 <div style={{ marginTop: 4 }}>
@@ -466,6 +604,13 @@ export const SSHSyncModal: React.FC<SSHSyncModalProps> = ({ open, onClose }) =>
                   <Text type="danger" style={{ fontSize: 12 }}>{testResult.error}</Text>
                 </div>
               )}
+              {testResult?.hostKeyError?.kind === 'changed' && testResult.hostKeyError.source === 'app' && (
+                <div style={{ marginTop: 4 }}>
+                  <Button size="small" danger onClick={handleForgetHostKey}>
+                    {t('settings.ssh.forgetHostKey')}
+                  </Button>
+                </div>
+              )}
             </div>
           )}
 
@@ -524,16 +669,49 @@ export const SSHSyncModal: React.FC<SSHSyncModalProps> = ({ open, onClose }) =>
                 <Text>{formatSyncTime(status?.lastSyncTime)}</Text>
                 {getStatusIcon()}
               </Space>
-              <Button
-                type="primary"
-                icon={<ReloadOutlined />}
-                onClick={handleSyncNow}
-                disabled={!enabled || syncing || !activeConnectionId}
-                loading={syncing}
-              >
-                {t('settings.ssh.syncNow')}
-              </Button>
+              <Space>
+                <Button
+                  icon={<DiffOutlined />}
+                  onClick={() => setPreviewOpen(true)}
+                  disabled={!enabled || syncing || !activeConnectionId}
+                >
+                  {t('settings.syncPreview.button')}
+                </Button>
+                <Button
+                  type="primary"
+                  icon={<ReloadOutlined />}
+                  onClick={handleSyncNow}
+                  disabled={!enabled || syncing || !activeConnectionId}
+                  loading={syncing}
+                >
+                  {t('settings.ssh.syncNow')}
+                </Button>
+              </Space>
             </div>
+            {status && status.hostStatus.length > 1 && (
+              <div style={{ marginTop: 12 }}>
+                <Text type="secondary" style={{ fontSize: 12 }}>{t('settings.ssh.hostStatus')}:</Text>
+                {status.hostStatus.map((host) => (
+                  <div key={host.connectionId} style={{ display: 'flex', alignItems: 'center', gap: 8, marginTop: 4 }}>
+                    {host.lastSyncStatus === 'success'
+                      ? <CheckCircleOutlined style={{ color: token.colorSuccess }} />
+                      : <CloseCircleOutlined style={{ color: token.colorError }} />}
+                    <Text>{host.connectionName}</Text>
+                    <Text type="secondary" style={{ fontSize: 12 }}>{formatSyncTime(host.lastSyncTime)}</Text>
+                    {host.lastSyncError && (
+                      <Text type="danger" style={{ fontSize: 12, maxWidth: 360 }} ellipsis={{ tooltip: host.lastSyncError }}>
+                        {host.lastSyncError}
+                      </Text>
+                    )}
+                  </div>
+                ))}
+              </div>
+            )}
+            {autoSyncMappings && (
+              <Text type="secondary" style={{ fontSize: 12, display: 'block', marginTop: 12 }}>
+                {t('settings.ssh.autoSyncTriggered', { names: autoSyncMappings.join(', ') })}
+              </Text>
+            )}
             {syncing && syncProgress && (
               <div style={{ marginTop: 12 }}>
                 <div style={{ marginBottom: 4 }}>
@@ -564,6 +742,41 @@ export const SSHSyncModal: React.FC<SSHSyncModalProps> = ({ open, onClose }) =>
                 style={{ marginTop: 12 }}
               />
             )}
+            {conflicts.length > 0 && (
+              <Alert
+                type="warning"
+                showIcon
+                style={{ marginTop: 12 }}
+                message={t('settings.ssh.conflictsTitle', { count: conflicts.length })}
+                description={
+                  <List
+                    size="small"
+                    dataSource={conflicts}
+                    renderItem={(conflict) => (
+                      <List.Item
+                        actions={[
+                          <Button size="small" onClick={() => handleResolveConflict(conflict, 'local')}>
+                            {t('settings.ssh.keepLocal')}
+                          </Button>,
+                          <Button size="small" onClick={() => handleResolveConflict(conflict, 'remote')}>
+                            {t('settings.ssh.keepRemote')}
+                          </Button>,
+                        ]}
+                      >
+                        <List.Item.Meta
+                          title={conflict.mappingName}
+                          description={
+                            <Text type="secondary" style={{ fontSize: 12 }}>
+                              {conflict.localPath} ⇄ {conflict.remotePath}
+                            </Text>
+                          }
+                        />
+                      </List.Item>
+                    )}
+                  />
+                }
+              />
+            )}
           </div>
         </Spin>
       </Modal>
@@ -579,6 +792,21 @@ export const SSHSyncModal: React.FC<SSHSyncModalProps> = ({ open, onClose }) =>
         connection={editingConnection}
       />
 
+      <SSHConfigImportModal
+        open={importModalOpen}
+        onClose={() => setImportModalOpen(false)}
+        onImported={handleConfigImported}
+      />
+
+      {/* Sync Preview Modal */}
+      <SyncPreviewModal
+        open={previewOpen}
+        onClose={() => setPreviewOpen(false)}
+        loadPreview={() => sshSyncPreview()}
+        onConfirm={handleSyncNow}
+        confirmText={t('settings.ssh.syncNow')}
+      />
+
       {/* File Mapping Modal */}
       <SSHFileMappingModal
         open={mappingModalOpen}
@@ -0,0 +1,166 @@
+import React from 'react';
+import { Modal, Descriptions, Input, Button, Tag, Typography, Space, Alert, message } from 'antd';
+import { UnlockOutlined, SafetyOutlined } from '@ant-design/icons';
+import { useTranslation } from 'react-i18next';
+import {
+  getSecretVaultStatus,
+  migrateSecretsToVault,
+  unlockSecretVault,
+  type SecretVaultSealReport,
+  type SecretVaultStatus,
+} from '@/services/settingsApi';
+
+const { Text } = Typography;
+
+interface SecretVaultModalProps {
+  open: boolean;
+  onClose: () => void;
+}
+
+const SecretVaultModal: React.FC<SecretVaultModalProps> = ({ open, onClose }) => {
+  const { t } = useTranslation();
+  const [status, setStatus] = React.useState<SecretVaultStatus | null>(null);
+  const [passphrase, setPassphrase] = React.useState('');
+  const [unlocking, setUnlocking] = React.useState(false);
+  const [migrating, setMigrating] = React.useState(false);
+
+  const loadStatus = React.useCallback(async () => {
+    try {
+      setStatus(await getSecretVaultStatus());
+    } catch (error) {
+      console.error('Failed to load secret vault status:', error);
+      message.error(String(error));
+    }
+  }, []);
+
+  React.useEffect(() => {
+    if (open) {
+      setPassphrase('');
+      loadStatus();
+    }
+  }, [open, loadStatus]);
+
+  const showReport = (report: SecretVaultSealReport) => {
+    message.success(
+      t('settings.secretVault.migrated', {
+        sealed: report.sealedRecords,
+        pruned: report.prunedSecrets,
+      }),
+    );
+  };
+
+  const handleUnlock = async () => {
+    if (!passphrase) {
+      message.warning(t('settings.secretVault.passphraseRequired'));
+      return;
+    }
+    setUnlocking(true);
+    try {
+      showReport(await unlockSecretVault(passphrase));
+      setPassphrase('');
+    } catch (error) {
+      console.error('Failed to unlock secret vault:', error);
+      message.error(String(error));
+    } finally {
+      setUnlocking(false);
+      loadStatus();
+    }
+  };
+
+  const handleMigrate = async () => {
+    setMigrating(true);
+    try {
+      showReport(await migrateSecretsToVault());
+    } catch (error) {
+      console.error('Failed to migrate secrets to vault:', error);
+      message.error(String(error));
+    } finally {
+      setMigrating(false);
+      loadStatus();
+    }
+  };
+
+  const needsPassphrase = status != null && !status.unlocked && status.keySource !== 'keyring';
+
+  return (
+    <Modal
+      title={t('settings.secretVault.title')}
+      open={open}
+      onCancel={onClose}
+      footer={<Button onClick={onClose}>{t('common.close')}</Button>}
+      width={560}
+    >
+      <Space orientation="vertical" size={16} style={{ width: '100%' }}>
+        <Text type="secondary" style={{ fontSize: 12 }}>
+          {t('settings.secretVault.description')}
+        </Text>
+
+        <Descriptions column={1} size="small" bordered>
+          <Descriptions.Item label={t('settings.secretVault.state')}>
+            {status == null ? (
+              '-'
+            ) : !status.initialized ? (
+              <Tag>{t('settings.secretVault.notInitialized')}</Tag>
+            ) : status.unlocked ? (
+              <Tag color="green">{t('settings.secretVault.unlocked')}</Tag>
+            ) : (
+              <Tag color="orange">{t('settings.secretVault.locked')}</Tag>
+            )}
+          </Descriptions.Item>
+          <Descriptions.Item label={t('settings.secretVault.keySource')}>
+            {status?.keySource === 'keyring'
+              ? t('settings.secretVault.keySourceKeyring')
+              : status?.keySource === 'passphrase'
+                ? t('settings.secretVault.keySourcePassphrase')
+                : '-'}
+          </Descriptions.Item>
+          <Descriptions.Item label={t('settings.secretVault.secretCount')}>
+            {status?.secretCount ?? '-'}
+          </Descriptions.Item>
+        </Descriptions>
+
+        {needsPassphrase && (
+          <>
+            <Alert
+              type="warning"
+              showIcon
+              message={
+                status?.initialized
+                  ? t('settings.secretVault.lockedHint')
+                  : t('settings.secretVault.setupHint')
+              }
+            />
+            <Space.Compact style={{ width: '100%' }}>
+              <Input.Password
+                value={passphrase}
+                onChange={(e) => setPassphrase(e.target.value)}
+                onPressEnter={handleUnlock}
+                placeholder={t('settings.secretVault.passphrasePlaceholder')}
+              />
+              <Button type="primary" icon={<UnlockOutlined />} loading={unlocking} onClick={handleUnlock}>
+                {status?.initialized ? t('settings.secretVault.unlock') : t('settings.secretVault.setup')}
+              </Button>
+            </Space.Compact>
+          </>
+        )}
+
+        <div style={{ display: 'flex', justifyContent: 'space-between', alignItems: 'center' }}>
+          <Text type="secondary" style={{ fontSize: 12 }}>
+            {t('settings.secretVault.migrateHint')}
+          </Text>
+          <Button
+            size="small"
+            icon={<SafetyOutlined />}
+            loading={migrating}
+            disabled={needsPassphrase}
+            onClick={handleMigrate}
+          >
+            {t('settings.secretVault.migrateNow')}
+          </Button>
+        </div>
+      </Space>
+    </Modal>
+  );
+};
+
+export default SecretVaultModal;
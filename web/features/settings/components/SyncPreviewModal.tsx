@@ -0,0 +1,241 @@
+/**
+ * Sync Preview Modal
+ *
+ * Shows what an SSH / WSL sync would change (dry-run) before anything is written
+ */
+
+import React, { useEffect, useState } from 'react';
+import { Modal, List, Tag, Collapse, Alert, Spin, Empty, Space, Typography, theme } from 'antd';
+import { useTranslation } from 'react-i18next';
+import type { FileChangePreview, SyncPreview } from '@/types/wslsync';
+
+const { Text } = Typography;
+
+const ACTION_COLORS: Record<string, string> = {
+  create: 'green',
+  update: 'blue',
+  unchanged: 'default',
+  delete: 'red',
+  delete_symlink: 'orange',
+  conflict: 'volcano',
+};
+
+interface SyncPreviewModalProps {
+  open: boolean;
+  onClose: () => void;
+  /** Loads the dry-run result, called every time the modal opens */
+  loadPreview: () => Promise<SyncPreview>;
+  /** Run the real sync after reviewing the changes */
+  onConfirm: () => void;
+  confirmText: string;
+}
+
+export const SyncPreviewModal: React.FC<SyncPreviewModalProps> = ({
+  open,
+  onClose,
+  loadPreview,
+  onConfirm,
+  confirmText,
+}) => {
+  const { t } = useTranslation();
+  const { token } = theme.useToken();
+  const [preview, setPreview] = useState<SyncPreview | null>(null);
+  const [loading, setLoading] = useState(false);
+  const [loadError, setLoadError] = useState<string | null>(null);
+
+  useEffect(() => {
+    if (!open) return;
+
+    setLoading(true);
+    setPreview(null);
+    setLoadError(null);
+    loadPreview()
+      .then(setPreview)
+      .catch((error) => {
+        console.error('Failed to load sync preview:', error);
+        setLoadError(String(error));
+      })
+      .finally(() => setLoading(false));
+    // eslint-disable-next-line react-hooks/exhaustive-deps
+  }, [open]);
+
+  const renderDiff = (diff: string) => (
+    <pre
+      style={{
+        margin: 0,
+        maxHeight: 320,
+        overflow: 'auto',
+        fontSize: 12,
+        padding: 8,
+        background: token.colorFillQuaternary,
+        borderRadius: 4,
+      }}
+    >
+      {diff.split('\n').map((line, idx) => {
+        let color: string | undefined;
+        if (line.startsWith('+') && !line.startsWith('+++')) color = token.colorSuccess;
+        else if (line.startsWith('-') && !line.startsWith('---')) color = token.colorError;
+        else if (line.startsWith('@@')) color = token.colorInfo;
+        return (
+          <div key={idx} style={{ color }}>
+            {line || ' '}
+          </div>
+        );
+      })}
+    </pre>
+  );
+
+  const renderChange = (change: FileChangePreview) => {
+    const arrow = change.direction === 'pull' ? '←' : change.direction === 'both' ? '⇄' : '→';
+    const showsDiff = change.action === 'create' || change.action === 'update';
+
+    return (
+      <List.Item style={{ display: 'block' }}>
+        <Space size={4} wrap>
+          <Tag color={ACTION_COLORS[change.action] ?? 'default'}>
+            {t(`settings.syncPreview.action.${change.action}`)}
+          </Tag>
+          <Text strong>{change.mappingName}</Text>
+        </Space>
+        <div>
+          <Text type="secondary" style={{ fontSize: 12 }}>
+            {change.sourcePath} {arrow} {change.targetPath}
+          </Text>
+        </div>
+        {showsDiff &&
+          (change.diff ? (
+            <Collapse
+              size="small"
+              ghost
+              items={[{ key: 'diff', label: t('settings.syncPreview.showDiff'), children: renderDiff(change.diff) }]}
+            />
+          ) : (
+            <Text type="secondary" style={{ fontSize: 12 }}>
+              {t('settings.syncPreview.noDiff')}
+            </Text>
+          ))}
+      </List.Item>
+    );
+  };
+
+  const renderNames = (label: string, color: string, names: string[]) =>
+    names.length > 0 && (
+      <div style={{ marginBottom: 4 }}>
+        <Text type="secondary">{label}: </Text>
+        {names.map((name) => (
+          <Tag key={name} color={color}>
+            {name}
+          </Tag>
+        ))}
+      </div>
+    );
+
+  const changedFiles = preview?.files.filter((f) => f.action !== 'unchanged') ?? [];
+  const unchangedFiles = preview?.files.filter((f) => f.action === 'unchanged') ?? [];
+  const mcp = preview?.mcp;
+  const skills = preview?.skills;
+  const mcpChanged = !!mcp && mcp.added.length + mcp.removed.length + mcp.updated.length > 0;
+  const skillsChanged =
+    !!skills && skills.added.length + skills.updated.length + skills.removed.length + skills.links.length > 0;
+
+  return (
+    <Modal
+      title={t('settings.syncPreview.title')}
+      open={open}
+      onOk={() => {
+        onClose();
+        onConfirm();
+      }}
+      onCancel={onClose}
+      okText={confirmText}
+      cancelText={t('common.cancel')}
+      okButtonProps={{ disabled: loading || !!loadError }}
+      width={760}
+      destroyOnClose
+    >
+      <Text type="secondary" style={{ fontSize: 12, display: 'block', marginBottom: 12 }}>
+        {t('settings.syncPreview.hint')}
+      </Text>
+      <Spin spinning={loading}>
+        {loadError && <Alert type="error" showIcon message={loadError} style={{ marginBottom: 12 }} />}
+        {preview && preview.errors.length > 0 && (
+          <Alert
+            type="warning"
+            showIcon
+            style={{ marginBottom: 12 }}
+            message={preview.errors.map((e) => (
+              <div key={e}>{e}</div>
+            ))}
+          />
+        )}
+
+        {preview && (
+          <>
+            <Text strong>{t('settings.syncPreview.files')}</Text>
+            {changedFiles.length === 0 ? (
+              <Empty image={Empty.PRESENTED_IMAGE_SIMPLE} description={t('settings.syncPreview.noChanges')} />
+            ) : (
+              <List size="small" dataSource={changedFiles} renderItem={renderChange} />
+            )}
+            {unchangedFiles.length > 0 && (
+              <div style={{ marginBottom: 8 }}>
+                <Tag>{t('settings.syncPreview.action.unchanged')}</Tag>
+                <Text type="secondary" style={{ fontSize: 12 }}>
+                  {unchangedFiles.map((f) => f.mappingName).join(', ')}
+                </Text>
+              </div>
+            )}
+            {preview.skippedFiles.length > 0 && (
+              <Text type="secondary" style={{ fontSize: 12, display: 'block', marginBottom: 8 }}>
+                {t('settings.syncPreview.skipped', { names: preview.skippedFiles.join(', ') })}
+              </Text>
+            )}
+
+            {mcp && (
+              <div style={{ marginTop: 16 }}>
+                <Text strong>{t('settings.syncPreview.mcpTitle')}</Text>
+                {mcpChanged ? (
+                  <div style={{ marginTop: 8 }}>
+                    {renderNames(t('settings.syncPreview.added'), 'green', mcp.added)}
+                    {renderNames(t('settings.syncPreview.updated'), 'blue', mcp.updated)}
+                    {renderNames(t('settings.syncPreview.removed'), 'red', mcp.removed)}
+                    {mcp.diff && (
+                      <Collapse
+                        size="small"
+                        ghost
+                        items={[{ key: 'diff', label: t('settings.syncPreview.showDiff'), children: renderDiff(mcp.diff) }]}
+                      />
+                    )}
+                  </div>
+                ) : (
+                  <Empty image={Empty.PRESENTED_IMAGE_SIMPLE} description={t('settings.syncPreview.noChanges')} />
+                )}
+              </div>
+            )}
+
+            {skills && (
+              <div style={{ marginTop: 16 }}>
+                <Text strong>{t('settings.syncPreview.skillsTitle')}</Text>
+                {skillsChanged ? (
+                  <div style={{ marginTop: 8 }}>
+                    {renderNames(t('settings.syncPreview.added'), 'green', skills.added)}
+                    {renderNames(t('settings.syncPreview.updated'), 'blue', skills.updated)}
+                    {renderNames(t('settings.syncPreview.removed'), 'red', skills.removed)}
+                    {skills.links.length > 0 && (
+                      <>
+                        <Text type="secondary">{t('settings.syncPreview.symlinks')}:</Text>
+                        <List size="small" dataSource={skills.links} renderItem={renderChange} />
+                      </>
+                    )}
+                  </div>
+                ) : (
+                  <Empty image={Empty.PRESENTED_IMAGE_SIMPLE} description={t('settings.syncPreview.noChanges')} />
+                )}
+              </div>
+            )}
+          </>
+        )}
+      </Spin>
+    </Modal>
+  );
+};
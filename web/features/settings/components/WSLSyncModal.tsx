@@ -6,12 +6,13 @@
 
 import React, { useState, useEffect, useCallback } from 'react';
 import { Modal, Form, Switch, Select, Button, List, Space, Typography, Alert, Spin, Tag, Modal as AntdModal, Tabs, Tooltip, Progress } from 'antd';
-import { CheckCircleOutlined, CloseCircleOutlined, ReloadOutlined, DeleteOutlined, EditOutlined, PlusOutlined, ClearOutlined, CodeOutlined, FolderOpenOutlined } from '@ant-design/icons';
+import { CheckCircleOutlined, CloseCircleOutlined, ReloadOutlined, DeleteOutlined, EditOutlined, PlusOutlined, ClearOutlined, CodeOutlined, FolderOpenOutlined, DiffOutlined } from '@ant-design/icons';
 import { useTranslation } from 'react-i18next';
 import { useWSLSync } from '@/features/settings/hooks/useWSLSync';
 import { useSettingsStore } from '@/stores';
 import { FileMappingModal } from './FileMappingModal';
-import { wslDeleteFileMapping, wslResetFileMappings, wslOpenTerminal, wslOpenFolder, wslGetDistroState } from '@/services/wslSyncApi';
+import { SyncPreviewModal } from './SyncPreviewModal';
+import { wslDeleteFileMapping, wslResetFileMappings, wslOpenTerminal, wslOpenFolder, wslGetDistroState, wslSyncPreview } from '@/services/wslSyncApi';
 import type { FileMapping } from '@/types/wslsync';
 
 const { Text } = Typography;
@@ -49,7 +50,7 @@ interface WSLSyncModalProps {
 
 export const WSLSyncModal: React.FC<WSLSyncModalProps> = ({ open, onClose }) => {
   const { t } = useTranslation();
-  const { config, status, loading, syncing, syncWarning, syncProgress, saveConfig, sync, detect, checkDistro, dismissSyncWarning } = useWSLSync();
+  const { config, status, loading, syncing, syncWarning, syncProgress, autoSyncMappings, saveConfig, sync, detect, checkDistro, dismissSyncWarning } = useWSLSync();
   const { visibleTabs } = useSettingsStore();
 
   // Filter module keys by visibleTabs
@@ -63,6 +64,7 @@ export const WSLSyncModal: React.FC<WSLSyncModalProps> = ({ open, onClose }) =>
   const [distroState, setDistroState] = useState<'Running' | 'Stopped' | 'Unknown'>('Unknown');
   const [editingMapping, setEditingMapping] = useState<FileMapping | null>(null);
   const [mappingModalOpen, setMappingModalOpen] = useState(false);
+  const [previewOpen, setPreviewOpen] = useState(false);
   const [activeModuleTab, setActiveModuleTab] = useState<string>(visibleModuleKeys[0] || 'all');
 
   // Initialize form when config loads
@@ -136,6 +138,20 @@ export const WSLSyncModal: React.FC<WSLSyncModalProps> = ({ open, onClose }) =>
     }
   };
 
+  // Handle auto sync on file change switch - save immediately
+  const handleAutoSyncChange = async (checked: boolean) => {
+    if (!config) return;
+    try {
+      await saveConfig({
+        ...config,
+        autoSyncOnChange: checked,
+        distro,
+      });
+    } catch (error) {
+      console.error('Failed to save auto sync state:', error);
+    }
+  };
+
   // Handle distro change - save immediately
   const handleDistroChange = async (value: string) => {
     if (!config) return;
@@ -359,6 +375,19 @@ export const WSLSyncModal: React.FC<WSLSyncModalProps> = ({ open, onClose }) =>
               {t('settings.wsl.enableSyncSubtitle')}
             </Text>
 
+            {/* Auto sync on file change */}
+            <div style={{ display: 'flex', justifyContent: 'space-between', alignItems: 'center', marginBottom: 4 }}>
+              <Text>{t('settings.wsl.autoSyncOnChange')}</Text>
+              <Switch
+                checked={config?.autoSyncOnChange ?? false}
+                onChange={handleAutoSyncChange}
+                disabled={!enabled}
+              />
+            </div>
+            <Text type="secondary" style={{ fontSize: 12, marginBottom: 16, display: 'block' }}>
+              {t('settings.wsl.autoSyncOnChangeHint')}
+            </Text>
+
             {/* WSL Distro - left-right layout */}
             <div style={{ display: 'flex', justifyContent: 'space-between', alignItems: 'center', marginBottom: 16 }}>
               <Text>{t('settings.wsl.distro')}</Text>
@@ -482,16 +511,26 @@ export const WSLSyncModal: React.FC<WSLSyncModalProps> = ({ open, onClose }) =>
                   <Text>{formatSyncTime(status?.lastSyncTime)}</Text>
                   {getStatusIcon()}
                 </Space>
-                <Button
-                  type="primary"
-                  icon={<ReloadOutlined />}
-                  onClick={handleSyncNow}
-                  disabled={syncing}
-                  loading={syncing}
-                >
-                  {t('settings.wsl.syncNow')}
-                </Button>
+                <Space>
+                  <Button icon={<DiffOutlined />} onClick={() => setPreviewOpen(true)} disabled={syncing}>
+                    {t('settings.syncPreview.button')}
+                  </Button>
+                  <Button
+                    type="primary"
+                    icon={<ReloadOutlined />}
+                    onClick={handleSyncNow}
+                    disabled={syncing}
+                    loading={syncing}
+                  >
+                    {t('settings.wsl.syncNow')}
+                  </Button>
+                </Space>
               </div>
+              {autoSyncMappings && (
+                <Text type="secondary" style={{ fontSize: 12, display: 'block', marginTop: 12 }}>
+                  {t('settings.wsl.autoSyncTriggered', { names: autoSyncMappings.join(', ') })}
+                </Text>
+              )}
               {/* Sync Progress */}
               {syncing && syncProgress && (
                 <div style={{ marginTop: 12 }}>
@@ -533,6 +572,14 @@ export const WSLSyncModal: React.FC<WSLSyncModalProps> = ({ open, onClose }) =>
         onClose={handleMappingModalClose}
         mapping={editingMapping}
       />
+
+      <SyncPreviewModal
+        open={previewOpen}
+        onClose={() => setPreviewOpen(false)}
+        loadPreview={() => wslSyncPreview()}
+        onConfirm={handleSyncNow}
+        confirmText={t('settings.wsl.syncNow')}
+      />
     </>
   );
 };
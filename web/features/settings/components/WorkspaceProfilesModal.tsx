@@ -0,0 +1,356 @@
+import React from 'react';
+import {
+  Modal,
+  Form,
+  Input,
+  Checkbox,
+  Table,
+  Tag,
+  Button,
+  Popconfirm,
+  Typography,
+  Space,
+  Empty,
+  message,
+} from 'antd';
+import { ArrowDownOutlined, ArrowUpOutlined, PlusOutlined, ReloadOutlined } from '@ant-design/icons';
+import { useTranslation } from 'react-i18next';
+import {
+  applyWorkspaceProfile,
+  captureWorkspaceSelection,
+  createWorkspaceProfile,
+  deleteWorkspaceProfile,
+  listWorkspaceProfiles,
+  reorderWorkspaceProfiles,
+  updateWorkspaceProfile,
+  type WorkspaceProfile,
+  type WorkspaceProfileSelection,
+} from '@/services/workspaceProfileApi';
+
+const { Text } = Typography;
+
+type Scope = 'claudecode' | 'codex' | 'geminicli' | 'opencode' | 'openclaw' | 'ohMyOpenCode' | 'mcp' | 'skills';
+
+/** Selection fields covered by each scope */
+const SCOPE_FIELDS: Record<Scope, (keyof WorkspaceProfileSelection)[]> = {
+  claudecode: ['claudeProviderId', 'claudePromptId'],
+  codex: ['codexProviderId', 'codexPromptId'],
+  geminicli: ['geminiCliProviderId', 'geminiCliPromptId'],
+  opencode: ['opencodeModel', 'opencodeSmallModel', 'opencodePromptId'],
+  openclaw: ['openclawModel'],
+  ohMyOpenCode: ['ohMyOpenCodeConfigId', 'ohMyOpenCodeSlimConfigId'],
+  mcp: ['mcpServers'],
+  skills: ['skills'],
+};
+
+const SCOPES = Object.keys(SCOPE_FIELDS) as Scope[];
+
+const SCOPE_LABELS: Record<Scope, string> = {
+  claudecode: 'Claude Code',
+  codex: 'Codex',
+  geminicli: 'Gemini CLI',
+  opencode: 'OpenCode',
+  openclaw: 'OpenClaw',
+  ohMyOpenCode: 'Oh My OpenCode',
+  mcp: 'MCP',
+  skills: 'Skills',
+};
+
+const scopesOf = (selection: WorkspaceProfileSelection) =>
+  SCOPES.filter((scope) => SCOPE_FIELDS[scope].some((field) => selection[field] != null));
+
+const pickScopes = (selection: WorkspaceProfileSelection, scopes: Scope[]): WorkspaceProfileSelection => {
+  const picked: WorkspaceProfileSelection = {};
+  for (const scope of scopes) {
+    for (const field of SCOPE_FIELDS[scope]) {
+      if (selection[field] != null) {
+        Object.assign(picked, { [field]: selection[field] });
+      }
+    }
+  }
+  return picked;
+};
+
+interface EditorState {
+  /** Undefined when creating a new profile */
+  profile?: WorkspaceProfile;
+  selection: WorkspaceProfileSelection;
+}
+
+interface EditorValues {
+  name: string;
+  description?: string;
+  scopes: Scope[];
+}
+
+interface WorkspaceProfilesModalProps {
+  open: boolean;
+  onClose: () => void;
+}
+
+const WorkspaceProfilesModal: React.FC<WorkspaceProfilesModalProps> = ({ open, onClose }) => {
+  const { t } = useTranslation();
+  const [form] = Form.useForm<EditorValues>();
+  const [profiles, setProfiles] = React.useState<WorkspaceProfile[]>([]);
+  const [loading, setLoading] = React.useState(false);
+  const [applyingId, setApplyingId] = React.useState<string>();
+  const [editor, setEditor] = React.useState<EditorState | null>(null);
+
+  const loadProfiles = React.useCallback(async () => {
+    setLoading(true);
+    try {
+      setProfiles(await listWorkspaceProfiles());
+    } catch (error) {
+      console.error('Failed to load workspace profiles:', error);
+      message.error(String(error));
+    } finally {
+      setLoading(false);
+    }
+  }, []);
+
+  React.useEffect(() => {
+    if (open) {
+      loadProfiles();
+    }
+  }, [open, loadProfiles]);
+
+  const openEditor = async (profile?: WorkspaceProfile) => {
+    try {
+      const selection = profile ? profile.selection : await captureWorkspaceSelection();
+      form.setFieldsValue({
+        name: profile?.name ?? '',
+        description: profile?.description,
+        scopes: scopesOf(selection),
+      });
+      setEditor({ profile, selection });
+    } catch (error) {
+      console.error('Failed to capture workspace selection:', error);
+      message.error(String(error));
+    }
+  };
+
+  const handleRecapture = async () => {
+    if (!editor) return;
+    try {
+      const selection = await captureWorkspaceSelection();
+      setEditor({ ...editor, selection });
+      message.success(t('settings.workspaceProfiles.recaptured'));
+    } catch (error) {
+      console.error('Failed to capture workspace selection:', error);
+      message.error(String(error));
+    }
+  };
+
+  const handleSaveProfile = async () => {
+    if (!editor) return;
+    const values = await form.validateFields();
+    const input = {
+      name: values.name.trim(),
+      description: values.description?.trim() || undefined,
+      selection: pickScopes(editor.selection, values.scopes),
+    };
+    try {
+      if (editor.profile) {
+        await updateWorkspaceProfile(editor.profile.id, input);
+      } else {
+        await createWorkspaceProfile(input);
+      }
+      setEditor(null);
+      await loadProfiles();
+    } catch (error) {
+      console.error('Failed to save workspace profile:', error);
+      message.error(String(error));
+    }
+  };
+
+  const handleApply = async (profile: WorkspaceProfile) => {
+    setApplyingId(profile.id);
+    try {
+      await applyWorkspaceProfile(profile.id);
+      message.success(t('settings.workspaceProfiles.applied', { name: profile.name }));
+      await loadProfiles();
+    } catch (error) {
+      console.error('Failed to apply workspace profile:', error);
+      message.error(t('settings.workspaceProfiles.applyFailed', { error: String(error) }));
+    } finally {
+      setApplyingId(undefined);
+    }
+  };
+
+  const handleDelete = async (profile: WorkspaceProfile) => {
+    try {
+      await deleteWorkspaceProfile(profile.id);
+      await loadProfiles();
+    } catch (error) {
+      console.error('Failed to delete workspace profile:', error);
+      message.error(String(error));
+    }
+  };
+
+  const handleMove = async (index: number, offset: number) => {
+    const next = [...profiles];
+    const [moved] = next.splice(index, 1);
+    next.splice(index + offset, 0, moved);
+    setProfiles(next);
+    try {
+      await reorderWorkspaceProfiles(next.map((profile) => profile.id));
+    } catch (error) {
+      console.error('Failed to reorder workspace profiles:', error);
+      message.error(String(error));
+      loadProfiles();
+    }
+  };
+
+  const columns = [
+    {
+      title: t('settings.workspaceProfiles.name'),
+      key: 'name',
+      render: (_: unknown, record: WorkspaceProfile) => (
+        <Space orientation="vertical" size={0}>
+          <Space size={4}>
+            <Text strong>{record.name}</Text>
+            {record.isApplied && <Tag color="blue">{t('settings.workspaceProfiles.current')}</Tag>}
+          </Space>
+          {record.description && (
+            <Text type="secondary" style={{ fontSize: 12 }}>
+              {record.description}
+            </Text>
+          )}
+        </Space>
+      ),
+    },
+    {
+      title: t('settings.workspaceProfiles.scopes'),
+      key: 'scopes',
+      render: (_: unknown, record: WorkspaceProfile) => (
+        <Space size={4} wrap>
+          {scopesOf(record.selection).map((scope) => (
+            <Tag key={scope} style={{ fontSize: 11, marginInlineEnd: 0 }}>
+              {SCOPE_LABELS[scope]}
+            </Tag>
+          ))}
+        </Space>
+      ),
+    },
+    {
+      title: '',
+      key: 'actions',
+      width: 260,
+      render: (_: unknown, record: WorkspaceProfile, index: number) => (
+        <Space size={4}>
+          <Button
+            type="text"
+            size="small"
+            icon={<ArrowUpOutlined />}
+            disabled={index === 0}
+            onClick={() => handleMove(index, -1)}
+          />
+          <Button
+            type="text"
+            size="small"
+            icon={<ArrowDownOutlined />}
+            disabled={index === profiles.length - 1}
+            onClick={() => handleMove(index, 1)}
+          />
+          <Button
+            type="primary"
+            size="small"
+            loading={applyingId === record.id}
+            disabled={applyingId != null && applyingId !== record.id}
+            onClick={() => handleApply(record)}
+          >
+            {t('settings.workspaceProfiles.apply')}
+          </Button>
+          <Button size="small" onClick={() => openEditor(record)}>
+            {t('common.edit')}
+          </Button>
+          <Popconfirm
+            title={t('settings.workspaceProfiles.deleteConfirm', { name: record.name })}
+            onConfirm={() => handleDelete(record)}
+          >
+            <Button size="small" danger>
+              {t('common.delete')}
+            </Button>
+          </Popconfirm>
+        </Space>
+      ),
+    },
+  ];
+
+  return (
+    <Modal
+      title={t('settings.workspaceProfiles.title')}
+      open={open}
+      onCancel={onClose}
+      footer={null}
+      width={760}
+    >
+      <Space orientation="vertical" style={{ width: '100%' }}>
+        <Text type="secondary" style={{ fontSize: 12 }}>
+          {t('settings.workspaceProfiles.description')}
+        </Text>
+        <div style={{ textAlign: 'right' }}>
+          <Button type="primary" icon={<PlusOutlined />} onClick={() => openEditor()}>
+            {t('settings.workspaceProfiles.createFromCurrent')}
+          </Button>
+        </div>
+        <Table
+          rowKey="id"
+          size="small"
+          loading={loading}
+          dataSource={profiles}
+          columns={columns}
+          pagination={false}
+          locale={{ emptyText: <Empty description={t('settings.workspaceProfiles.empty')} /> }}
+        />
+      </Space>
+
+      <Modal
+        title={
+          editor?.profile
+            ? t('settings.workspaceProfiles.editTitle')
+            : t('settings.workspaceProfiles.createTitle')
+        }
+        open={editor != null}
+        onOk={handleSaveProfile}
+        onCancel={() => setEditor(null)}
+        okText={t('common.save')}
+        cancelText={t('common.cancel')}
+        forceRender
+      >
+        <Form form={form} layout="vertical">
+          <Form.Item
+            name="name"
+            label={t('settings.workspaceProfiles.name')}
+            rules={[{ required: true, whitespace: true, message: t('settings.workspaceProfiles.nameRequired') }]}
+          >
+            <Input placeholder={t('settings.workspaceProfiles.namePlaceholder')} />
+          </Form.Item>
+          <Form.Item name="description" label={t('settings.workspaceProfiles.descriptionLabel')}>
+            <Input />
+          </Form.Item>
+          <Form.Item
+            name="scopes"
+            label={t('settings.workspaceProfiles.scopes')}
+            extra={t('settings.workspaceProfiles.scopesHint')}
+          >
+            <Checkbox.Group
+              options={SCOPES.map((scope) => ({
+                label: SCOPE_LABELS[scope],
+                value: scope,
+                disabled: editor ? !scopesOf(editor.selection).includes(scope) : true,
+              }))}
+            />
+          </Form.Item>
+          {editor?.profile && (
+            <Button size="small" icon={<ReloadOutlined />} onClick={handleRecapture}>
+              {t('settings.workspaceProfiles.recapture')}
+            </Button>
+          )}
+        </Form>
+      </Modal>
+    </Modal>
+  );
+};
+
+export default WorkspaceProfilesModal;
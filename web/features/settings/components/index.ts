@@ -1,3 +1,8 @@
 export { default as BackupSettingsModal } from './BackupSettingsModal';
+export { default as BalanceMonitorModal } from './BalanceMonitorModal';
+export { default as ConfigHistoryModal } from './ConfigHistoryModal';
+export { default as ProviderHealthModal } from './ProviderHealthModal';
 export { default as S3SettingsModal } from './S3SettingsModal';
+export { default as SecretVaultModal } from './SecretVaultModal';
 export { default as WebDAVRestoreModal } from './WebDAVRestoreModal';
+export { default as WorkspaceProfilesModal } from './WorkspaceProfilesModal';
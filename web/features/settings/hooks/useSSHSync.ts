@@ -6,13 +6,14 @@
 
 import { useState, useEffect, useCallback, useRef } from 'react';
 import { listen } from '@tauri-apps/api/event';
-import type { SSHSyncConfig, SSHStatusResult, SyncResult, SSHFileMapping, SyncProgress } from '@/types/sshsync';
+import type { SSHSyncConfig, SSHStatusResult, SyncResult, SSHFileMapping, SyncProgress, SyncWatcherEvent, SyncConflict } from '@/types/sshsync';
 import {
   sshGetConfig,
   sshSaveConfig,
   sshSync,
   sshGetStatus,
   sshGetDefaultMappings,
+  sshResolveConflict,
 } from '@/services/sshSyncApi';
 import { useSettingsStore } from '@/stores';
 
@@ -32,6 +33,8 @@ export function useSSHSync() {
   const [syncing, setSyncing] = useState(false);
   const [syncWarning, setSyncWarning] = useState<string | null>(null);
   const [syncProgress, setSyncProgress] = useState<SyncProgress | null>(null);
+  const [autoSyncMappings, setAutoSyncMappings] = useState<string[] | null>(null);
+  const [conflicts, setConflicts] = useState<SyncConflict[]>([]);
 
   const skipNextReload = useRef(false);
 
@@ -111,6 +114,7 @@ export function useSSHSync() {
         .filter(Boolean);
       const skipModules = ALL_CODING_MODULES.filter((m) => !visibleModules.includes(m));
       const result = await sshSync(module, skipModules.length > 0 ? skipModules : undefined);
+      setConflicts(result.conflicts || []);
       await loadStatus();
       return result;
     } catch (error) {
@@ -147,9 +151,11 @@ export function useSSHSync() {
       loadStatus();
     });
 
-    const unlistenSync = listen<SyncResult>('ssh-sync-completed', () => {
+    const unlistenSync = listen<SyncResult>('ssh-sync-completed', (event) => {
       loadStatus();
       setSyncProgress(null);
+      setAutoSyncMappings(null);
+      setConflicts(event.payload?.conflicts || []);
     });
 
     const unlistenWarning = listen<string>('ssh-sync-warning', (event) => {
@@ -160,14 +166,31 @@ export function useSSHSync() {
       setSyncProgress(event.payload);
     });
 
+    const unlistenWatcher = listen<SyncWatcherEvent>('sync-watcher-triggered', (event) => {
+      if (event.payload.target === 'ssh') {
+        setAutoSyncMappings(event.payload.mappings);
+      }
+    });
+
     return () => {
       unlistenConfig.then(fn => fn());
       unlistenSync.then(fn => fn());
       unlistenWarning.then(fn => fn());
       unlistenProgress.then(fn => fn());
+      unlistenWatcher.then(fn => fn());
     };
   }, [loadConfig, loadStatus]);
 
+  /**
+   * Resolve a two-way sync conflict by keeping one side
+   */
+  const resolveConflict = useCallback(async (conflict: SyncConflict, keep: 'local' | 'remote') => {
+    await sshResolveConflict(conflict, keep);
+    setConflicts((prev) => prev.filter(
+      (c) => c.localPath !== conflict.localPath || c.remotePath !== conflict.remotePath,
+    ));
+  }, []);
+
   /**
    * Dismiss sync warning
    */
@@ -182,11 +205,14 @@ export function useSSHSync() {
     syncing,
     syncWarning,
     syncProgress,
+    autoSyncMappings,
+    conflicts,
     loadConfig,
     loadStatus,
     saveConfig,
     sync,
     getDefaultMappings,
     dismissSyncWarning,
+    resolveConflict,
   };
 }
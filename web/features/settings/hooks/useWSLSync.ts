@@ -6,7 +6,7 @@
 
 import { useState, useEffect, useCallback, useRef } from 'react';
 import { listen } from '@tauri-apps/api/event';
-import type { WSLSyncConfig, WSLStatusResult, SyncResult, FileMapping, WSLDetectResult, SyncProgress } from '@/types/wslsync';
+import type { WSLSyncConfig, WSLStatusResult, SyncResult, FileMapping, WSLDetectResult, SyncProgress, SyncWatcherEvent } from '@/types/wslsync';
 import {
   wslGetConfig,
   wslSaveConfig,
@@ -34,6 +34,7 @@ export function useWSLSync() {
   const [syncing, setSyncing] = useState(false);
   const [syncWarning, setSyncWarning] = useState<string | null>(null);
   const [syncProgress, setSyncProgress] = useState<SyncProgress | null>(null);
+  const [autoSyncMappings, setAutoSyncMappings] = useState<string[] | null>(null);
 
   // Flag to prevent reload after we just saved defaults
   const skipNextReload = useRef(false);
@@ -175,6 +176,7 @@ export function useWSLSync() {
         distro: 'Ubuntu',
         syncMcp: true,
         syncSkills: true,
+        autoSyncOnChange: false,
         fileMappings: defaultMappings,
         lastSyncStatus: 'never',
       };
@@ -204,6 +206,7 @@ export function useWSLSync() {
     const unlistenSync = listen<SyncResult>('wsl-sync-completed', () => {
       loadStatus();
       setSyncProgress(null); // Clear progress when sync completes
+      setAutoSyncMappings(null);
     });
 
     const unlistenWarning = listen<string>('wsl-sync-warning', (event) => {
@@ -214,11 +217,18 @@ export function useWSLSync() {
       setSyncProgress(event.payload);
     });
 
+    const unlistenWatcher = listen<SyncWatcherEvent>('sync-watcher-triggered', (event) => {
+      if (event.payload.target === 'wsl') {
+        setAutoSyncMappings(event.payload.mappings);
+      }
+    });
+
     return () => {
       unlistenConfig.then(fn => fn());
       unlistenSync.then(fn => fn());
       unlistenWarning.then(fn => fn());
       unlistenProgress.then(fn => fn());
+      unlistenWatcher.then(fn => fn());
     };
   }, [loadConfig, loadStatus]);
 
@@ -236,6 +246,7 @@ export function useWSLSync() {
     syncing,
     syncWarning,
     syncProgress,
+    autoSyncMappings,
     loadConfig,
     loadStatus,
     saveConfig,
@@ -1,5 +1,5 @@
 import React from 'react';
-import { Typography, Button, Select, Space, message, Modal, Table, Switch, Progress, Input, Row, Col, Card, Divider } from 'antd';
+import { Typography, Button, Select, Space, message, Modal, Table, Switch, Progress, Input, InputNumber, Row, Col, Card, Divider } from 'antd';
 import {
   EditOutlined,
   CloudUploadOutlined,
@@ -16,7 +16,14 @@ import {
   BulbOutlined,
   EyeOutlined,
   HolderOutlined,
-  DragOutlined
+  DragOutlined,
+  LinkOutlined,
+  HeartOutlined,
+  WalletOutlined,
+  SwapOutlined,
+  LockOutlined,
+  HistoryOutlined,
+  ApartmentOutlined
 } from '@ant-design/icons';
 import { useTranslation } from 'react-i18next';
 import {
@@ -39,7 +46,15 @@ import { useAppStore, useSettingsStore } from '@/stores';
 import { useThemeStore, type ThemeMode } from '@/stores/themeStore';
 import { languages, type Language } from '@/i18n';
 import i18n from '@/i18n';
-import { BackupSettingsModal, WebDAVRestoreModal } from '../components';
+import {
+  BackupSettingsModal,
+  WebDAVRestoreModal,
+  ProviderHealthModal,
+  BalanceMonitorModal,
+  ConfigHistoryModal,
+  SecretVaultModal,
+  WorkspaceProfilesModal,
+} from '../components';
 import { platform } from '@tauri-apps/plugin-os';
 import {
   backupDatabase,
@@ -47,6 +62,7 @@ import {
   selectBackupFile,
   backupToWebDAV,
   restoreFromWebDAV,
+  isBackupPassphraseError,
   openAppDataDir,
   getAppVersion,
   checkForUpdates,
@@ -57,7 +73,13 @@ import {
   type UpdateInfo,
   GITHUB_REPO,
 } from '@/services';
-import { restartApp } from '@/services/settingsApi';
+import {
+  restartApp,
+  getControlApiStatus,
+  getLlmGatewayStatus,
+  type ControlApiStatus,
+  type LlmGatewayStatus,
+} from '@/services/settingsApi';
 import { listen } from '@tauri-apps/api/event';
 import styles from './GeneralSettingsPage.module.less';
 
@@ -127,15 +149,35 @@ const GeneralSettingsPage: React.FC = () => {
     autoBackupEnabled,
     autoBackupIntervalDays,
     lastAutoBackupTime,
+    backupPassphrase,
     autoCheckUpdate,
     setAutoCheckUpdate,
     visibleTabs,
     setVisibleTabs,
+    controlApiEnabled,
+    setControlApiEnabled,
+    healthMonitorEnabled,
+    healthMonitorIntervalMinutes,
+    healthMonitorFailureThreshold,
+    healthMonitorAutoFailover,
+    balanceMonitorEnabled,
+    balanceMonitorIntervalMinutes,
+    balanceAlertThresholdUsd,
+    configHistoryMaxPerFile,
+    configHistoryMaxAgeDays,
+    llmGatewayEnabled,
+    llmGatewayPort,
+    setLlmGatewaySettings,
   } = useSettingsStore();
 
   const isWindows = React.useMemo(() => platform() === 'windows', []);
 
   const [backupModalOpen, setBackupModalOpen] = React.useState(false);
+  const [providerHealthModalOpen, setProviderHealthModalOpen] = React.useState(false);
+  const [balanceMonitorModalOpen, setBalanceMonitorModalOpen] = React.useState(false);
+  const [secretVaultModalOpen, setSecretVaultModalOpen] = React.useState(false);
+  const [configHistoryModalOpen, setConfigHistoryModalOpen] = React.useState(false);
+  const [workspaceProfilesModalOpen, setWorkspaceProfilesModalOpen] = React.useState(false);
   const [webdavRestoreModalOpen, setWebdavRestoreModalOpen] = React.useState(false);
   const [backupLoading, setBackupLoading] = React.useState(false);
   const [restoreLoading, setRestoreLoading] = React.useState(false);
@@ -144,6 +186,55 @@ const GeneralSettingsPage: React.FC = () => {
   const [proxyInput, setProxyInput] = React.useState(proxyUrl);
   const [proxyTesting, setProxyTesting] = React.useState(false);
 
+  // Control API status (port and token file of the running server)
+  const [controlApiStatus, setControlApiStatus] = React.useState<ControlApiStatus | null>(null);
+
+  React.useEffect(() => {
+    getControlApiStatus().then(setControlApiStatus).catch(console.error);
+  }, [controlApiEnabled]);
+
+  const handleControlApiToggle = async (checked: boolean) => {
+    try {
+      await setControlApiEnabled(checked);
+      setControlApiStatus(await getControlApiStatus());
+    } catch (error) {
+      console.error('Failed to toggle control API:', error);
+      message.error(String(error));
+    }
+  };
+
+  // Local LLM gateway
+  const [llmGatewayStatus, setLlmGatewayStatus] = React.useState<LlmGatewayStatus | null>(null);
+  const [llmGatewayPortInput, setLlmGatewayPortInput] = React.useState<number>(llmGatewayPort);
+
+  React.useEffect(() => {
+    setLlmGatewayPortInput(llmGatewayPort);
+  }, [llmGatewayPort]);
+
+  React.useEffect(() => {
+    getLlmGatewayStatus().then(setLlmGatewayStatus).catch(console.error);
+  }, [llmGatewayEnabled, llmGatewayPort]);
+
+  const handleLlmGatewayChange = async (enabled: boolean, port: number) => {
+    try {
+      await setLlmGatewaySettings({ enabled, port });
+      const status = await getLlmGatewayStatus();
+      setLlmGatewayStatus(status);
+      if (enabled && !status.running) {
+        message.error(t('settings.llmGateway.startFailed', { port }));
+      }
+    } catch (error) {
+      console.error('Failed to update LLM gateway:', error);
+      message.error(String(error));
+    }
+  };
+
+  const handleLlmGatewayPortSave = () => {
+    if (llmGatewayPortInput !== llmGatewayPort) {
+      handleLlmGatewayChange(llmGatewayEnabled, llmGatewayPortInput);
+    }
+  };
+
   // Version and update states
   const [appVersion, setAppVersion] = React.useState<string>('');
   const [checkingUpdate, setCheckingUpdate] = React.useState(false);
@@ -346,7 +437,8 @@ const GeneralSettingsPage: React.FC = () => {
           webdav.username,
           webdav.password,
           webdav.remotePath,
-          webdav.hostLabel
+          webdav.hostLabel,
+          backupPassphrase
         );
         const now = new Date().toISOString();
         await setLastBackupTime(now);
@@ -358,7 +450,7 @@ const GeneralSettingsPage: React.FC = () => {
           message.warning(t('settings.backupSettings.noPathConfigured'));
           return;
         }
-        const filePath = await backupDatabase(localBackupPath);
+        const filePath = await backupDatabase(localBackupPath, backupPassphrase);
         const now = new Date().toISOString();
         await setLastBackupTime(now);
         message.success(t('settings.backupSettings.backupSuccess'));
@@ -409,7 +501,7 @@ const GeneralSettingsPage: React.FC = () => {
           cancelText: t('common.cancel'),
           onOk: async () => {
             try {
-              await restoreDatabase(zipFilePath);
+              await restoreDatabase(zipFilePath, backupPassphrase);
               // 恢复成功后弹出重启对话框
               Modal.info({
                 title: t('settings.backupSettings.restoreSuccess'),
@@ -421,7 +513,11 @@ const GeneralSettingsPage: React.FC = () => {
               });
             } catch (error) {
               console.error('Restore failed:', error);
-              message.error(t('settings.backupSettings.restoreFailed'));
+              if (isBackupPassphraseError(error)) {
+                message.error(t('settings.backupSettings.passphraseError'));
+              } else {
+                message.error(t('settings.backupSettings.restoreFailed'));
+              }
             }
           },
         });
@@ -448,7 +544,8 @@ const GeneralSettingsPage: React.FC = () => {
             webdav.username,
             webdav.password,
             webdav.remotePath,
-            filename
+            filename,
+            backupPassphrase
           );
           // 恢复成功后弹出重启对话框
           Modal.info({
@@ -462,6 +559,11 @@ const GeneralSettingsPage: React.FC = () => {
         } catch (error) {
           console.error('Restore failed:', error);
 
+          if (isBackupPassphraseError(error)) {
+            message.error(t('settings.backupSettings.passphraseError'));
+            return;
+          }
+
           // Parse error if it's JSON
           let errorMessage = t('settings.backupSettings.restoreFailed');
           try {
@@ -557,7 +659,7 @@ const GeneralSettingsPage: React.FC = () => {
     </div>
   );
 
-  const CODING_TABS = ['opencode', 'claudecode', 'codex', 'openclaw'] as const;
+  const CODING_TABS = ['opencode', 'claudecode', 'codex', 'geminicli', 'openclaw'] as const;
   const OTHER_TABS = ['ssh', ...(isWindows ? ['wsl'] : [])] as string[];
 
   const [reorderMode, setReorderMode] = React.useState(false);
@@ -831,6 +933,194 @@ const GeneralSettingsPage: React.FC = () => {
 
             <Divider />
 
+            {/* Control API Settings */}
+            <SectionTitle icon={<LinkOutlined style={{ color: '#2f54eb' }} />} title={t('settings.cards.controlApi')} />
+            <div style={{ display: 'flex', flexDirection: 'column', gap: 12, marginBottom: 16 }}>
+              <div style={{ display: 'flex', justifyContent: 'space-between', alignItems: 'center' }}>
+                <Text>{t('settings.controlApi.enable')}</Text>
+                <Switch checked={controlApiEnabled} onChange={handleControlApiToggle} />
+              </div>
+              {controlApiEnabled && controlApiStatus?.running ? (
+                <Text type="secondary" style={{ fontSize: 12 }}>
+                  {t('settings.controlApi.running', { port: controlApiStatus.port, path: controlApiStatus.infoPath })}
+                </Text>
+              ) : (
+                <Text type="secondary" style={{ fontSize: 12 }}>
+                  {t('settings.controlApi.hint')}
+                </Text>
+              )}
+            </div>
+
+            <Divider />
+
+            {/* Local LLM Gateway */}
+            <SectionTitle icon={<SwapOutlined style={{ color: '#13c2c2' }} />} title={t('settings.cards.llmGateway')} />
+            <div style={{ display: 'flex', flexDirection: 'column', gap: 12, marginBottom: 16 }}>
+              <div style={{ display: 'flex', justifyContent: 'space-between', alignItems: 'center' }}>
+                <Text>{t('settings.llmGateway.enable')}</Text>
+                <Space>
+                  <InputNumber
+                    size="small"
+                    value={llmGatewayPortInput}
+                    onChange={(v) => setLlmGatewayPortInput(v && v >= 1 && v <= 65535 ? Math.floor(v) : llmGatewayPort)}
+                    onBlur={handleLlmGatewayPortSave}
+                    onPressEnter={handleLlmGatewayPortSave}
+                    min={1}
+                    max={65535}
+                    precision={0}
+                    style={{ width: 130 }}
+                    addonBefore={t('settings.llmGateway.port')}
+                  />
+                  <Switch
+                    checked={llmGatewayEnabled}
+                    onChange={(checked) => handleLlmGatewayChange(checked, llmGatewayPortInput)}
+                  />
+                </Space>
+              </div>
+              {llmGatewayEnabled && llmGatewayStatus?.running ? (
+                <Text type="secondary" style={{ fontSize: 12 }}>
+                  {t('settings.llmGateway.running', {
+                    claude: llmGatewayStatus.claudeBaseUrl,
+                    codex: llmGatewayStatus.codexBaseUrl,
+                  })}
+                </Text>
+              ) : (
+                <Text type="secondary" style={{ fontSize: 12 }}>
+                  {t('settings.llmGateway.hint')}
+                </Text>
+              )}
+            </div>
+
+            <Divider />
+
+            {/* Provider Health Monitor */}
+            <SectionTitle
+              icon={<HeartOutlined style={{ color: '#eb2f96' }} />}
+              title={t('settings.cards.providerHealth')}
+              extra={
+                <Button
+                  type="text"
+                  icon={<EditOutlined />}
+                  size="small"
+                  onClick={() => setProviderHealthModalOpen(true)}
+                >
+                  {t('common.edit')}
+                </Button>
+              }
+            />
+            <div style={{ marginBottom: 16 }}>
+              <Text type="secondary" style={{ fontSize: 12 }}>
+                {healthMonitorEnabled
+                  ? t('settings.providerHealth.statusEnabled', {
+                      minutes: healthMonitorIntervalMinutes,
+                      threshold: healthMonitorFailureThreshold,
+                    }) + (healthMonitorAutoFailover ? ` | ${t('settings.providerHealth.autoFailover')}` : '')
+                  : t('settings.providerHealth.hint')}
+              </Text>
+            </div>
+
+            <Divider />
+
+            {/* All API Hub Balance Monitor */}
+            <SectionTitle
+              icon={<WalletOutlined style={{ color: '#faad14' }} />}
+              title={t('settings.cards.balanceMonitor')}
+              extra={
+                <Button
+                  type="text"
+                  icon={<EditOutlined />}
+                  size="small"
+                  onClick={() => setBalanceMonitorModalOpen(true)}
+                >
+                  {t('common.edit')}
+                </Button>
+              }
+            />
+            <div style={{ marginBottom: 16 }}>
+              <Text type="secondary" style={{ fontSize: 12 }}>
+                {balanceMonitorEnabled
+                  ? t('settings.balanceMonitor.statusEnabled', {
+                      minutes: balanceMonitorIntervalMinutes,
+                      threshold: balanceAlertThresholdUsd,
+                    })
+                  : t('settings.balanceMonitor.hint')}
+              </Text>
+            </div>
+
+            <Divider />
+
+            {/* Secret Vault */}
+            <SectionTitle
+              icon={<LockOutlined style={{ color: '#2f54eb' }} />}
+              title={t('settings.cards.secretVault')}
+              extra={
+                <Button
+                  type="text"
+                  icon={<EditOutlined />}
+                  size="small"
+                  onClick={() => setSecretVaultModalOpen(true)}
+                >
+                  {t('common.edit')}
+                </Button>
+              }
+            />
+            <div style={{ marginBottom: 16 }}>
+              <Text type="secondary" style={{ fontSize: 12 }}>
+                {t('settings.secretVault.hint')}
+              </Text>
+            </div>
+
+            <Divider />
+
+            {/* Workspace Profiles */}
+            <SectionTitle
+              icon={<ApartmentOutlined style={{ color: '#13c2c2' }} />}
+              title={t('settings.cards.workspaceProfiles')}
+              extra={
+                <Button
+                  type="text"
+                  icon={<EditOutlined />}
+                  size="small"
+                  onClick={() => setWorkspaceProfilesModalOpen(true)}
+                >
+                  {t('common.edit')}
+                </Button>
+              }
+            />
+            <div style={{ marginBottom: 16 }}>
+              <Text type="secondary" style={{ fontSize: 12 }}>
+                {t('settings.workspaceProfiles.hint')}
+              </Text>
+            </div>
+
+            <Divider />
+
+            {/* Config Write History */}
+            <SectionTitle
+              icon={<HistoryOutlined style={{ color: '#722ed1' }} />}
+              title={t('settings.cards.configHistory')}
+              extra={
+                <Button
+                  type="text"
+                  icon={<EditOutlined />}
+                  size="small"
+                  onClick={() => setConfigHistoryModalOpen(true)}
+                >
+                  {t('common.edit')}
+                </Button>
+              }
+            />
+            <div style={{ marginBottom: 16 }}>
+              <Text type="secondary" style={{ fontSize: 12 }}>
+                {t('settings.configHistory.hint', {
+                  count: configHistoryMaxPerFile,
+                  days: configHistoryMaxAgeDays,
+                })}
+              </Text>
+            </div>
+
+            <Divider />
+
             {/* Backup Settings */}
             <SectionTitle 
               icon={<CloudSyncOutlined style={{ color: '#52c41a' }} />} 
@@ -884,6 +1174,14 @@ const GeneralSettingsPage: React.FC = () => {
 
       {/* Modals */}
       <BackupSettingsModal open={backupModalOpen} onClose={() => setBackupModalOpen(false)} />
+      <ProviderHealthModal open={providerHealthModalOpen} onClose={() => setProviderHealthModalOpen(false)} />
+      <BalanceMonitorModal open={balanceMonitorModalOpen} onClose={() => setBalanceMonitorModalOpen(false)} />
+      <SecretVaultModal open={secretVaultModalOpen} onClose={() => setSecretVaultModalOpen(false)} />
+      <ConfigHistoryModal open={configHistoryModalOpen} onClose={() => setConfigHistoryModalOpen(false)} />
+      <WorkspaceProfilesModal
+        open={workspaceProfilesModalOpen}
+        onClose={() => setWorkspaceProfilesModalOpen(false)}
+      />
       <WebDAVRestoreModal
         open={webdavRestoreModalOpen}
         onClose={() => setWebdavRestoreModalOpen(false)}
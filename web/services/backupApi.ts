@@ -6,6 +6,7 @@
 
 import { invoke } from '@tauri-apps/api/core';
 import { open } from '@tauri-apps/plugin-dialog';
+import type { S3Config } from './settingsApi';
 
 /**
  * Backup file info from WebDAV server
@@ -18,23 +19,25 @@ export interface BackupFileInfo {
 /**
  * Backup database to a local zip file
  * @param backupPath - The directory to save the backup file
+ * @param passphrase - Optional passphrase, the archive is encrypted when non-empty
  * @returns The full path of the created backup file
  */
-export const backupDatabase = async (backupPath: string): Promise<string> => {
+export const backupDatabase = async (backupPath: string, passphrase?: string): Promise<string> => {
   if (!backupPath) {
     throw new Error('Backup path is not configured');
   }
 
-  const result = await invoke<string>('backup_database', { backupPath });
+  const result = await invoke<string>('backup_database', { backupPath, passphrase });
   return result;
 };
 
 /**
  * Restore database from a local zip file
  * @param zipFilePath - The path to the backup zip file
+ * @param passphrase - Passphrase for encrypted backups
  */
-export const restoreDatabase = async (zipFilePath: string): Promise<void> => {
-  await invoke('restore_database', { zipFilePath });
+export const restoreDatabase = async (zipFilePath: string, passphrase?: string): Promise<void> => {
+  await invoke('restore_database', { zipFilePath, passphrase });
 };
 
 /**
@@ -72,7 +75,8 @@ export const backupToWebDAV = async (
   username: string,
   password: string,
   remotePath: string,
-  hostLabel: string
+  hostLabel: string,
+  passphrase?: string
 ): Promise<string> => {
   const result = await invoke<string>('backup_to_webdav', {
     url,
@@ -80,6 +84,7 @@ export const backupToWebDAV = async (
     password,
     remotePath,
     hostLabel,
+    passphrase,
   });
   return result;
 };
@@ -110,7 +115,8 @@ export const restoreFromWebDAV = async (
   username: string,
   password: string,
   remotePath: string,
-  filename: string
+  filename: string,
+  passphrase?: string
 ): Promise<void> => {
   await invoke('restore_from_webdav', {
     url,
@@ -118,6 +124,7 @@ export const restoreFromWebDAV = async (
     password,
     remotePath,
     filename,
+    passphrase,
   });
 };
 
@@ -156,3 +163,61 @@ export const deleteWebDAVBackup = async (
     filename,
   });
 };
+
+/**
+ * Test S3 connection
+ */
+export const testS3Connection = async (config: S3Config): Promise<void> => {
+  await invoke('test_s3_connection', { config });
+};
+
+/**
+ * Backup database to S3-compatible storage
+ * @returns The URL of the uploaded object
+ */
+export const backupToS3 = async (config: S3Config, passphrase?: string): Promise<string> => {
+  const result = await invoke<string>('backup_to_s3', { config, passphrase });
+  return result;
+};
+
+/**
+ * List backup files from S3-compatible storage
+ */
+export const listS3Backups = async (config: S3Config): Promise<BackupFileInfo[]> => {
+  const result = await invoke<BackupFileInfo[]>('list_s3_backups', { config });
+  return result;
+};
+
+/**
+ * Restore database from S3-compatible storage
+ */
+export const restoreFromS3 = async (
+  config: S3Config,
+  filename: string,
+  passphrase?: string
+): Promise<void> => {
+  await invoke('restore_from_s3', { config, filename, passphrase });
+};
+
+/**
+ * Delete a backup file from S3-compatible storage
+ */
+export const deleteS3Backup = async (config: S3Config, filename: string): Promise<void> => {
+  await invoke('delete_s3_backup', { config, filename });
+};
+
+/**
+ * Error prefixes returned by restore commands for encrypted backups
+ */
+export const BACKUP_PASSPHRASE_REQUIRED = 'BACKUP_PASSPHRASE_REQUIRED';
+export const BACKUP_WRONG_PASSPHRASE = 'BACKUP_WRONG_PASSPHRASE';
+
+/**
+ * Check whether a restore error means the backup needs a (different) passphrase
+ */
+export const isBackupPassphraseError = (error: unknown): boolean => {
+  const message = String(error);
+  return (
+    message.includes(BACKUP_PASSPHRASE_REQUIRED) || message.includes(BACKUP_WRONG_PASSPHRASE)
+  );
+};
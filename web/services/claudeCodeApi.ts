@@ -5,6 +5,7 @@
  */
 
 import { invoke } from '@tauri-apps/api/core';
+import { withConfigValidation } from '@/utils/configValidation';
 import type {
   ClaudeCodeProvider,
   ClaudeCommonConfig,
@@ -12,6 +13,7 @@ import type {
   ClaudeSettings,
   ClaudePluginStatus,
 } from '@/types/claudecode';
+import type { ConfigDrift } from '@/types/configDrift';
 import type { OpenCodeAllApiHubProvider, OpenCodeAllApiHubProvidersResult } from '@/services/opencodeApi';
 
 /**
@@ -80,7 +82,7 @@ export const selectClaudeProvider = async (id: string): Promise<void> => {
  * Apply Claude Code configuration (write to settings.json)
  */
 export const applyClaudeConfig = async (providerId: string): Promise<void> => {
-  await invoke('apply_claude_config', { providerId });
+  await withConfigValidation((force) => invoke('apply_claude_config', { providerId, force }));
 };
 
 /**
@@ -90,6 +92,21 @@ export const readClaudeSettings = async (): Promise<ClaudeSettings> => {
   return await invoke<ClaudeSettings>('read_claude_settings');
 };
 
+/**
+ * Compare settings.json with the applied provider and common config
+ * Returns null when no provider is applied
+ */
+export const detectClaudeConfigDrift = async (): Promise<ConfigDrift | null> => {
+  return await invoke<ConfigDrift | null>('detect_claude_config_drift');
+};
+
+/**
+ * Fold external edits of settings.json back into the applied provider and common config
+ */
+export const importClaudeConfigDrift = async (): Promise<void> => {
+  await invoke('import_claude_config_drift');
+};
+
 /**
  * Get common configuration
  */
@@ -101,7 +118,7 @@ export const getClaudeCommonConfig = async (): Promise<ClaudeCommonConfig | null
  * Save common configuration
  */
 export const saveClaudeCommonConfig = async (config: string): Promise<void> => {
-  await invoke('save_claude_common_config', { config });
+  await withConfigValidation((force) => invoke('save_claude_common_config', { config, force }));
 };
 
 /**
@@ -5,12 +5,15 @@
  */
 
 import { invoke } from '@tauri-apps/api/core';
+import { withConfigValidation } from '@/utils/configValidation';
 import type {
   CodexProvider,
   CodexCommonConfig,
   CodexLocalConfigInput,
   CodexSettings,
+  CodexProfilesConfig,
 } from '@/types/codex';
+import type { ConfigDrift } from '@/types/configDrift';
 import type { OpenCodeAllApiHubProvider, OpenCodeAllApiHubProvidersResult } from '@/services/opencodeApi';
 
 /**
@@ -77,7 +80,7 @@ export const selectCodexProvider = async (id: string): Promise<void> => {
  * Apply Codex configuration
  */
 export const applyCodexConfig = async (providerId: string): Promise<void> => {
-  await invoke('apply_codex_config', { providerId });
+  await withConfigValidation((force) => invoke('apply_codex_config', { providerId, force }));
 };
 
 export async function toggleCodexProviderDisabled(
@@ -94,6 +97,49 @@ export const readCodexSettings = async (): Promise<CodexSettings> => {
   return await invoke<CodexSettings>('read_codex_settings');
 };
 
+/**
+ * Read model_providers / profiles from config.toml
+ */
+export const readCodexProfiles = async (): Promise<CodexProfilesConfig> => {
+  return await invoke<CodexProfilesConfig>('read_codex_profiles');
+};
+
+/**
+ * Generate model_providers / profiles from stored providers (merged with config.toml, not written)
+ */
+export const generateCodexProfiles = async (): Promise<CodexProfilesConfig> => {
+  return await invoke<CodexProfilesConfig>('generate_codex_profiles');
+};
+
+/**
+ * Write all model_providers / profiles and the default profile into config.toml
+ */
+export const saveCodexProfiles = async (config: CodexProfilesConfig): Promise<void> => {
+  await withConfigValidation((force) => invoke('save_codex_profiles', { config, force }));
+};
+
+/**
+ * Set the default profile (top-level `profile` key), pass null to clear
+ */
+export const setCodexDefaultProfile = async (profile: string | null): Promise<void> => {
+  await invoke('set_codex_default_profile', { profile });
+};
+
+/**
+ * Compare auth.json / config.toml with the applied provider and common config
+ * Returns null when no provider is applied
+ */
+export const detectCodexConfigDrift = async (): Promise<ConfigDrift | null> => {
+  return await invoke<ConfigDrift | null>('detect_codex_config_drift');
+};
+
+/**
+ * Fold external edits of auth.json / config.toml back into the applied provider and common config
+ */
+export const importCodexConfigDrift = async (): Promise<void> => {
+  await invoke('import_codex_config_drift');
+};
+
 /**
  * Get common configuration
  */
@@ -105,7 +151,7 @@ export const getCodexCommonConfig = async (): Promise<CodexCommonConfig | null>
  * Save common configuration
  */
 export const saveCodexCommonConfig = async (config: string): Promise<void> => {
-  await invoke('save_codex_common_config', { config });
+  await withConfigValidation((force) => invoke('save_codex_common_config', { config, force }));
 };
 
 /**
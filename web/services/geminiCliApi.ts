@@ -0,0 +1,137 @@
+/**
+ * Gemini CLI API Service
+ *
+ * Handles all Gemini CLI configuration related communication with the Tauri backend.
+ */
+
+import { invoke } from '@tauri-apps/api/core';
+import type {
+  GeminiCliProvider,
+  GeminiCliCommonConfig,
+  GeminiCliLocalConfigInput,
+  GeminiCliSettings,
+} from '@/types/geminiCli';
+import type { OpenCodeAllApiHubProvider, OpenCodeAllApiHubProvidersResult } from '@/services/opencodeApi';
+
+/**
+ * Get Gemini CLI config directory path
+ */
+export const getGeminiCliConfigPath = async (): Promise<string> => {
+  return await invoke<string>('get_gemini_cli_config_dir_path');
+};
+
+/**
+ * Get Gemini CLI settings.json file path
+ */
+export const getGeminiCliConfigFilePath = async (): Promise<string> => {
+  return await invoke<string>('get_gemini_cli_config_file_path');
+};
+
+/**
+ * Reveal Gemini CLI config folder in file explorer
+ */
+export const revealGeminiCliConfigFolder = async (): Promise<void> => {
+  await invoke('reveal_gemini_cli_config_folder');
+};
+
+/**
+ * List all Gemini CLI providers
+ */
+export const listGeminiCliProviders = async (): Promise<GeminiCliProvider[]> => {
+  return await invoke<GeminiCliProvider[]>('list_gemini_cli_providers');
+};
+
+/**
+ * Create a new Gemini CLI provider
+ */
+export const createGeminiCliProvider = async (
+  provider: Omit<GeminiCliProvider, 'id' | 'createdAt' | 'updatedAt'>
+): Promise<GeminiCliProvider> => {
+  return await invoke<GeminiCliProvider>('create_gemini_cli_provider', { provider });
+};
+
+/**
+ * Update an existing Gemini CLI provider
+ */
+export const updateGeminiCliProvider = async (
+  provider: GeminiCliProvider
+): Promise<GeminiCliProvider> => {
+  return await invoke<GeminiCliProvider>('update_gemini_cli_provider', { provider });
+};
+
+/**
+ * Delete a Gemini CLI provider
+ */
+export const deleteGeminiCliProvider = async (id: string): Promise<void> => {
+  await invoke('delete_gemini_cli_provider', { id });
+};
+
+/**
+ * Select a Gemini CLI provider
+ */
+export const selectGeminiCliProvider = async (id: string): Promise<void> => {
+  await invoke('select_gemini_cli_provider', { id });
+};
+
+/**
+ * Apply Gemini CLI configuration (writes settings.json and .env)
+ */
+export const applyGeminiCliConfig = async (providerId: string): Promise<void> => {
+  await invoke('apply_gemini_cli_config', { providerId });
+};
+
+export async function toggleGeminiCliProviderDisabled(
+  providerId: string,
+  isDisabled: boolean
+): Promise<void> {
+  await invoke('toggle_gemini_cli_provider_disabled', { providerId, isDisabled });
+}
+
+/**
+ * Read Gemini CLI settings from files
+ */
+export const readGeminiCliSettings = async (): Promise<GeminiCliSettings> => {
+  return await invoke<GeminiCliSettings>('read_gemini_cli_settings');
+};
+
+/**
+ * Get common configuration
+ */
+export const getGeminiCliCommonConfig = async (): Promise<GeminiCliCommonConfig | null> => {
+  return await invoke<GeminiCliCommonConfig | null>('get_gemini_cli_common_config');
+};
+
+/**
+ * Save common configuration
+ */
+export const saveGeminiCliCommonConfig = async (config: string): Promise<void> => {
+  await invoke('save_gemini_cli_common_config', { config });
+};
+
+/**
+ * Reorder Gemini CLI providers
+ */
+export const reorderGeminiCliProviders = async (ids: string[]): Promise<void> => {
+  await invoke('reorder_gemini_cli_providers', { ids });
+};
+
+/**
+ * Save local config (provider and/or common) into database
+ */
+export const saveGeminiCliLocalConfig = async (
+  input: GeminiCliLocalConfigInput
+): Promise<void> => {
+  await invoke('save_gemini_cli_local_config', { input });
+};
+
+export const listGeminiCliAllApiHubProviders = async (): Promise<OpenCodeAllApiHubProvidersResult> => {
+  return await invoke<OpenCodeAllApiHubProvidersResult>('list_gemini_cli_all_api_hub_providers');
+};
+
+export const resolveGeminiCliAllApiHubProviders = async (
+  providerIds: string[]
+): Promise<OpenCodeAllApiHubProvider[]> => {
+  return await invoke<OpenCodeAllApiHubProvider[]>('resolve_gemini_cli_all_api_hub_providers', {
+    request: { providerIds },
+  });
+};
@@ -12,5 +12,6 @@ export * from './globalPromptApi';
 export * from './openCodePromptApi';
 export * from './claudeCodePromptApi';
 export * from './codexPromptApi';
+export * from './geminiCliPromptApi';
 export * from './appApi';
 export * from './ohMyOpenCodeApi';
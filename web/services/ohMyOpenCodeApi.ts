@@ -1,4 +1,5 @@
 import { invoke } from '@tauri-apps/api/core';
+import { withConfigValidation } from '@/utils/configValidation';
 import type { OhMyOpenCodeConfig, OhMyOpenCodeGlobalConfig } from '@/types/ohMyOpenCode';
 import { OH_MY_OPENCODE_AGENTS, OH_MY_OPENCODE_CATEGORIES } from '@/types/ohMyOpenCode';
 
@@ -42,7 +43,7 @@ export const deleteOhMyOpenCodeConfig = async (id: string): Promise<void> => {
  * Apply a configuration to the oh-my-opencode.json file
  */
 export const applyOhMyOpenCodeConfig = async (configId: string): Promise<void> => {
-    await invoke('apply_oh_my_opencode_config', { configId });
+    await withConfigValidation((force) => invoke('apply_oh_my_opencode_config', { configId, force }));
 };
 
 /**
@@ -97,7 +98,9 @@ export const getOhMyOpenCodeGlobalConfig = async (): Promise<OhMyOpenCodeGlobalC
 export const saveOhMyOpenCodeGlobalConfig = async (
     config: OhMyOpenCodeGlobalConfigInput
 ): Promise<OhMyOpenCodeGlobalConfig> => {
-    return await invoke<OhMyOpenCodeGlobalConfig>('save_oh_my_opencode_global_config', { input: config });
+    return await withConfigValidation((force) =>
+        invoke<OhMyOpenCodeGlobalConfig>('save_oh_my_opencode_global_config', { input: config, force })
+    );
 };
 
 /**
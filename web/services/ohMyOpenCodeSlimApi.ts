@@ -3,6 +3,7 @@
  */
 
 import { invoke } from '@tauri-apps/api/core';
+import { withConfigValidation } from '@/utils/configValidation';
 import type {
   OhMyOpenCodeSlimConfig,
   OhMyOpenCodeSlimConfigInput,
@@ -47,7 +48,7 @@ export const deleteOhMyOpenCodeSlimConfig = async (id: string): Promise<void> =>
  * Apply a configuration to the oh-my-opencode-slim.json file
  */
 export const applyOhMyOpenCodeSlimConfig = async (configId: string): Promise<void> => {
-  await invoke('apply_oh_my_opencode_slim_config', { configId });
+  await withConfigValidation((force) => invoke('apply_oh_my_opencode_slim_config', { configId, force }));
 };
 
 /**
@@ -77,7 +78,9 @@ export const getOhMyOpenCodeSlimGlobalConfig = async (): Promise<OhMyOpenCodeSli
 export const saveOhMyOpenCodeSlimGlobalConfig = async (
   input: OhMyOpenCodeSlimGlobalConfigInput
 ): Promise<OhMyOpenCodeSlimGlobalConfig> => {
-  return await invoke<OhMyOpenCodeSlimGlobalConfig>('save_oh_my_opencode_slim_global_config', { input });
+  return await withConfigValidation((force) =>
+    invoke<OhMyOpenCodeSlimGlobalConfig>('save_oh_my_opencode_slim_global_config', { input, force })
+  );
 };
 
 /**
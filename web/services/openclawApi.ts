@@ -5,6 +5,7 @@
  */
 
 import { invoke } from '@tauri-apps/api/core';
+import { withConfigValidation } from '@/utils/configValidation';
 import type {
   OpenClawConfig,
   OpenClawConfigPathInfo,
@@ -52,7 +53,7 @@ export const readOpenClawConfig = async (): Promise<OpenClawConfig | null> => {
  * Save OpenClaw configuration file (full replacement)
  */
 export const saveOpenClawConfig = async (config: OpenClawConfig): Promise<void> => {
-  await invoke('save_openclaw_config', { config });
+  await withConfigValidation((force) => invoke('save_openclaw_config', { config, force }));
 };
 
 /**
@@ -88,7 +89,7 @@ export const getOpenClawAgentsDefaults = async (): Promise<OpenClawAgentsDefault
  * Set agents.defaults section in config (read-modify-write)
  */
 export const setOpenClawAgentsDefaults = async (defaults: OpenClawAgentsDefaults): Promise<void> => {
-  await invoke('set_openclaw_agents_defaults', { defaults });
+  await withConfigValidation((force) => invoke('set_openclaw_agents_defaults', { defaults, force }));
 };
 
 /**
@@ -102,7 +103,7 @@ export const getOpenClawEnv = async (): Promise<OpenClawEnvConfig | null> => {
  * Set env section in config (read-modify-write)
  */
 export const setOpenClawEnv = async (env: OpenClawEnvConfig): Promise<void> => {
-  await invoke('set_openclaw_env', { env });
+  await withConfigValidation((force) => invoke('set_openclaw_env', { env, force }));
 };
 
 /**
@@ -116,10 +117,12 @@ export const getOpenClawTools = async (): Promise<OpenClawToolsConfig | null> =>
  * Set tools section in config (read-modify-write)
  */
 export const setOpenClawTools = async (tools: OpenClawToolsConfig): Promise<void> => {
-  await invoke('set_openclaw_tools', { tools });
+  await withConfigValidation((force) => invoke('set_openclaw_tools', { tools, force }));
 };
 
 export interface AllApiHubProfileInfo {
+  /** Browser the extension was found in, e.g. "Chrome", "Edge", "Firefox" */
+  browser: string;
   profileName: string;
   extensionId: string;
   path: string;
@@ -139,6 +142,7 @@ export interface OpenClawAllApiHubProvider {
   siteName?: string;
   siteType?: string;
   accountLabel: string;
+  sourceBrowser: string;
   sourceProfileName: string;
   sourceExtensionId: string;
   config: OpenClawProviderConfig;
@@ -5,7 +5,9 @@
  */
 
 import { invoke } from '@tauri-apps/api/core';
+import { withConfigValidation } from '@/utils/configValidation';
 import type { OpenCodeConfig, OpenCodeProvider } from '@/types/opencode';
+import type { ConfigDrift } from '@/types/configDrift';
 
 /**
  * Configuration path information
@@ -78,7 +80,7 @@ export const readOpenCodeConfig = async (): Promise<OpenCodeConfig | null> => {
  * Save OpenCode configuration file
  */
 export const saveOpenCodeConfig = async (config: OpenCodeConfig): Promise<void> => {
-  await invoke('save_opencode_config', { config });
+  await withConfigValidation((force) => invoke('save_opencode_config', { config, force }));
 };
 
 /**
@@ -360,7 +362,31 @@ export const deleteFavoriteProvider = async (providerId: string): Promise<void>
   await invoke('delete_opencode_favorite_provider', { providerId });
 };
 
+/**
+ * Compare providers in the config file with the saved favorite providers
+ * Returns null when the config file can't be read or nothing was saved yet
+ */
+export const detectOpenCodeConfigDrift = async (): Promise<ConfigDrift | null> => {
+  return await invoke<ConfigDrift | null>('detect_opencode_config_drift');
+};
+
+/**
+ * Save externally edited providers from the config file as favorite providers
+ */
+export const importOpenCodeConfigDrift = async (): Promise<void> => {
+  await invoke('import_opencode_config_drift');
+};
+
+/**
+ * Overwrite externally edited providers in the config file with the favorite providers
+ */
+export const restoreOpenCodeConfigDrift = async (): Promise<void> => {
+  await invoke('restore_opencode_config_drift');
+};
+
 export interface AllApiHubProfileInfo {
+  /** Browser the extension was found in, e.g. "Chrome", "Edge", "Firefox" */
+  browser: string;
   profileName: string;
   extensionId: string;
   path: string;
@@ -380,6 +406,7 @@ export interface OpenCodeAllApiHubProvider {
   siteName?: string;
   siteType?: string;
   accountLabel: string;
+  sourceBrowser: string;
   sourceProfileName: string;
   sourceExtensionId: string;
   providerConfig: OpenCodeProvider;
@@ -44,8 +44,24 @@ export interface AppSettings {
   auto_backup_interval_days: number;
   auto_backup_max_keep: number;
   last_auto_backup_time: string | null;
+  backup_passphrase: string;
   auto_check_update: boolean;
   visible_tabs: string[];
+  control_api_enabled: boolean;
+  health_monitor_enabled: boolean;
+  health_monitor_interval_minutes: number;
+  health_monitor_failure_threshold: number;
+  health_monitor_auto_failover: boolean;
+  llm_gateway_enabled: boolean;
+  llm_gateway_port: number;
+  llm_gateway_token: string;
+  balance_monitor_enabled: boolean;
+  balance_monitor_interval_minutes: number;
+  balance_alert_threshold_usd: number;
+  /** Snapshots kept per managed config file, 0 = unlimited */
+  config_history_max_per_file: number;
+  /** Days config file snapshots are kept, 0 = forever */
+  config_history_max_age_days: number;
 }
 
 // Default settings
@@ -82,8 +98,22 @@ export const defaultSettings: AppSettings = {
   auto_backup_interval_days: 7,
   auto_backup_max_keep: 10,
   last_auto_backup_time: null,
+  backup_passphrase: '',
   auto_check_update: true,
-  visible_tabs: ['opencode', 'claudecode', 'codex', 'openclaw', 'ssh', 'wsl'],
+  visible_tabs: ['opencode', 'claudecode', 'codex', 'geminicli', 'openclaw', 'ssh', 'wsl'],
+  control_api_enabled: false,
+  health_monitor_enabled: false,
+  health_monitor_interval_minutes: 5,
+  health_monitor_failure_threshold: 3,
+  health_monitor_auto_failover: false,
+  llm_gateway_enabled: false,
+  llm_gateway_port: 15721,
+  llm_gateway_token: '',
+  balance_monitor_enabled: false,
+  balance_monitor_interval_minutes: 60,
+  balance_alert_threshold_usd: 5,
+  config_history_max_per_file: 20,
+  config_history_max_age_days: 30,
 };
 
 /**
@@ -157,3 +187,243 @@ export const restartApp = async (): Promise<void> => {
 export const testProxyConnection = async (proxyUrl: string): Promise<void> => {
   await invoke('test_proxy_connection', { proxyUrl });
 };
+
+export interface ControlApiStatus {
+  running: boolean;
+  port?: number;
+  /** File holding the port and bearer token */
+  infoPath?: string;
+}
+
+/**
+ * Get localhost control API status
+ */
+export const getControlApiStatus = async (): Promise<ControlApiStatus> => {
+  return await invoke<ControlApiStatus>('get_control_api_status');
+};
+
+export interface LlmGatewayStatus {
+  running: boolean;
+  port?: number;
+  /** ANTHROPIC_BASE_URL written for Claude Code */
+  claudeBaseUrl?: string;
+  /** base_url written for Codex */
+  codexBaseUrl?: string;
+}
+
+/**
+ * Get local LLM gateway status
+ */
+export const getLlmGatewayStatus = async (): Promise<LlmGatewayStatus> => {
+  return await invoke<LlmGatewayStatus>('get_llm_gateway_status');
+};
+
+export interface ProviderHealthCheck {
+  id: string;
+  /** "claudecode" | "codex" | "opencode" */
+  module: string;
+  providerId: string;
+  providerName: string;
+  status: 'success' | 'error';
+  latencyMs?: number;
+  errorMessage?: string;
+  checkedAt: string;
+}
+
+/** Payload of the `provider-health-alert` event */
+export interface ProviderHealthAlert {
+  module: string;
+  providerId: string;
+  providerName: string;
+  consecutiveFailures: number;
+  errorMessage?: string;
+  failoverProviderId?: string;
+  failoverProviderName?: string;
+}
+
+/**
+ * Probe the applied providers of all modules now
+ */
+export const runProviderHealthCheck = async (): Promise<ProviderHealthCheck[]> => {
+  return await invoke<ProviderHealthCheck[]>('run_provider_health_check');
+};
+
+/**
+ * List recent provider health checks (newest first)
+ */
+export const listProviderHealthHistory = async (
+  module?: string,
+  limit?: number,
+): Promise<ProviderHealthCheck[]> => {
+  return await invoke<ProviderHealthCheck[]>('list_provider_health_history', { module, limit });
+};
+
+/** A provider imported from an All API Hub account */
+export interface BalanceLinkedProvider {
+  /** "claudecode" | "codex" | "geminicli" | "opencode" */
+  module: string;
+  providerId: string;
+  providerName: string;
+  isApplied: boolean;
+}
+
+export interface AccountBalanceSummary {
+  accountId: string;
+  accountName: string;
+  siteUrl?: string;
+  balanceUsd?: number;
+  balanceCny?: number;
+  /** false when the last site query failed and the balance comes from the extension cache */
+  fromSite: boolean;
+  error?: string;
+  checkedAt?: string;
+  burnRateUsdPerDay?: number;
+  daysRemaining?: number;
+  isLow: boolean;
+  providers: BalanceLinkedProvider[];
+}
+
+/** Payload of the `provider-balance-alert` event */
+export interface ProviderBalanceAlert {
+  accountId: string;
+  accountName: string;
+  balanceUsd: number;
+  thresholdUsd: number;
+  daysRemaining?: number;
+  providers: BalanceLinkedProvider[];
+}
+
+/**
+ * Query the sites of all imported All API Hub accounts for their balances now
+ */
+export const refreshProviderBalances = async (): Promise<AccountBalanceSummary[]> => {
+  return await invoke<AccountBalanceSummary[]>('refresh_provider_balances');
+};
+
+/**
+ * List the last recorded balances of imported All API Hub accounts
+ */
+export const listProviderBalances = async (): Promise<AccountBalanceSummary[]> => {
+  return await invoke<AccountBalanceSummary[]>('list_provider_balances');
+};
+
+// ============================================================================
+// Secret Vault
+// ============================================================================
+
+export interface SecretVaultStatus {
+  /** Whether a master key has been generated */
+  initialized: boolean;
+  /** "keyring" | "passphrase" */
+  keySource?: string;
+  /** Whether the master key is currently available */
+  unlocked: boolean;
+  secretCount: number;
+}
+
+export interface SecretVaultSealReport {
+  /** Records whose plaintext secrets were replaced with vault references */
+  sealedRecords: number;
+  /** Vault entries removed because nothing references them anymore */
+  prunedSecrets: number;
+}
+
+/**
+ * Get the secret vault status without initializing it
+ */
+export const getSecretVaultStatus = async (): Promise<SecretVaultStatus> => {
+  return await invoke<SecretVaultStatus>('get_secret_vault_status');
+};
+
+/**
+ * Unlock (or initialize) the vault with a passphrase, then migrate remaining plaintext secrets
+ */
+export const unlockSecretVault = async (passphrase: string): Promise<SecretVaultSealReport> => {
+  return await invoke<SecretVaultSealReport>('unlock_secret_vault', { passphrase });
+};
+
+/**
+ * Move plaintext API keys and passwords stored in the database into the vault now
+ */
+export const migrateSecretsToVault = async (): Promise<SecretVaultSealReport> => {
+  return await invoke<SecretVaultSealReport>('migrate_secrets_to_vault');
+};
+
+// ============================================================================
+// Config History
+// ============================================================================
+
+/** A managed config file with write history */
+export interface ConfigHistoryFile {
+  path: string;
+  /** Module of the most recent write, e.g. "claudecode" | "codex" | "opencode" | "openclaw" | "mcp" */
+  module: string;
+  snapshotCount: number;
+  latestAt: string;
+  /** Whether the file currently exists */
+  exists: boolean;
+}
+
+/** Contents of a managed config file taken right before a write */
+export interface ConfigSnapshot {
+  id: string;
+  path: string;
+  module: string;
+  /** Action that triggered the write, e.g. "apply_provider" | "mcp_sync" | "prompt" | "restore" */
+  action: string;
+  createdAt: string;
+  /** false when the file did not exist before the write; restoring it deletes the file */
+  existed: boolean;
+  size: number;
+  /** Only returned by getConfigSnapshot */
+  content?: string;
+}
+
+/** Snapshot recording state; snapshots are skipped while the secret vault is unavailable */
+export interface ConfigHistoryStatus {
+  /** Snapshots skipped since the last one that was recorded */
+  skippedSnapshots: number;
+  lastError?: string;
+}
+
+/**
+ * Get the snapshot recording state
+ */
+export const getConfigHistoryStatus = async (): Promise<ConfigHistoryStatus> => {
+  return await invoke<ConfigHistoryStatus>('get_config_history_status');
+};
+
+/**
+ * List managed config files that have write history
+ */
+export const listConfigHistoryFiles = async (): Promise<ConfigHistoryFile[]> => {
+  return await invoke<ConfigHistoryFile[]>('list_config_history_files');
+};
+
+/**
+ * List the snapshots of a file, newest first (without contents)
+ */
+export const listConfigHistory = async (path: string): Promise<ConfigSnapshot[]> => {
+  return await invoke<ConfigSnapshot[]>('list_config_history', { path });
+};
+
+/**
+ * Get a snapshot including its contents
+ */
+export const getConfigSnapshot = async (path: string, snapshotId: string): Promise<ConfigSnapshot> => {
+  return await invoke<ConfigSnapshot>('get_config_snapshot', { path, snapshotId });
+};
+
+/**
+ * Unified diff between two snapshots; compares with the current file when toId is omitted
+ */
+export const diffConfigSnapshots = async (path: string, fromId: string, toId?: string): Promise<string> => {
+  return await invoke<string>('diff_config_snapshots', { path, fromId, toId: toId ?? null });
+};
+
+/**
+ * Restore a file to the contents of a snapshot (the current contents are snapshotted first)
+ */
+export const restoreConfigSnapshot = async (path: string, snapshotId: string): Promise<void> => {
+  await invoke('restore_config_snapshot', { path, snapshotId });
+};
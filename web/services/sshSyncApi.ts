@@ -6,11 +6,15 @@
 
 import { invoke } from '@tauri-apps/api/core';
 import type {
+  SSHConfigHost,
   SSHConnection,
   SSHConnectionResult,
   SSHFileMapping,
+  SSHKnownHost,
   SSHStatusResult,
   SSHSyncConfig,
+  SyncConflict,
+  SyncPreview,
   SyncResult,
 } from '@/types/sshsync';
 
@@ -70,6 +74,34 @@ export const sshTestConnection = async (connection: SSHConnection): Promise<SSHC
   return await invoke<SSHConnectionResult>('ssh_test_connection', { connection });
 };
 
+/**
+ * List host aliases from ~/.ssh/config that can be imported
+ */
+export const sshListConfigHosts = async (): Promise<SSHConfigHost[]> => {
+  return await invoke<SSHConfigHost[]>('ssh_list_config_hosts');
+};
+
+/**
+ * Create connection presets from ~/.ssh/config host aliases
+ */
+export const sshImportConfigHosts = async (aliases: string[]): Promise<SSHConnection[]> => {
+  return await invoke<SSHConnection[]>('ssh_import_config_hosts', { aliases });
+};
+
+/**
+ * Get the host key recorded for a connection
+ */
+export const sshGetHostKey = async (connectionId: string): Promise<SSHKnownHost | null> => {
+  return await invoke<SSHKnownHost | null>('ssh_get_host_key', { connectionId });
+};
+
+/**
+ * Forget the recorded host key of a connection
+ */
+export const sshForgetHostKey = async (connectionId: string): Promise<void> => {
+  await invoke('ssh_forget_host_key', { connectionId });
+};
+
 /**
  * Add a new SSH file mapping
  */
@@ -105,6 +137,23 @@ export const sshSync = async (module?: string, skipModules?: string[]): Promise<
   return await invoke<SyncResult>('ssh_sync', { module, skipModules });
 };
 
+/**
+ * Preview what a sync would change on the SSH remote (dry-run, nothing is written)
+ */
+export const sshSyncPreview = async (module?: string, skipModules?: string[]): Promise<SyncPreview> => {
+  return await invoke<SyncPreview>('ssh_sync_preview', { module, skipModules });
+};
+
+/**
+ * Resolve a two-way sync conflict by keeping one side
+ */
+export const sshResolveConflict = async (
+  conflict: SyncConflict,
+  keep: 'local' | 'remote',
+): Promise<void> => {
+  await invoke('ssh_resolve_conflict', { conflict, keep });
+};
+
 /**
  * Get SSH sync status
  */
@@ -0,0 +1,102 @@
+import { invoke } from '@tauri-apps/api/core';
+import { withConfigValidation } from '@/utils/configValidation';
+
+// ============================================================================
+// Workspace Profile API
+// ============================================================================
+
+/**
+ * Selection captured by a workspace profile.
+ * Omitted fields are left untouched when the profile is applied.
+ */
+export interface WorkspaceProfileSelection {
+  claudeProviderId?: string;
+  codexProviderId?: string;
+  geminiCliProviderId?: string;
+  claudePromptId?: string;
+  codexPromptId?: string;
+  geminiCliPromptId?: string;
+  opencodePromptId?: string;
+  /** OpenCode main model, "provider/model" */
+  opencodeModel?: string;
+  /** OpenCode small model, "provider/model" */
+  opencodeSmallModel?: string;
+  /** OpenClaw agents.defaults.model.primary */
+  openclawModel?: string;
+  ohMyOpenCodeConfigId?: string;
+  ohMyOpenCodeSlimConfigId?: string;
+  /** MCP server id -> enabled tool keys (only listed servers are changed) */
+  mcpServers?: Record<string, string[]>;
+  /** Skill id -> synced tool keys (only listed skills are changed) */
+  skills?: Record<string, string[]>;
+}
+
+export interface WorkspaceProfile {
+  id: string;
+  name: string;
+  description?: string;
+  selection: WorkspaceProfileSelection;
+  /** Whether this profile was the last one applied */
+  isApplied: boolean;
+  sortIndex?: number;
+  createdAt?: string;
+  updatedAt?: string;
+}
+
+export interface WorkspaceProfileInput {
+  name: string;
+  description?: string;
+  selection: WorkspaceProfileSelection;
+}
+
+/**
+ * List all workspace profiles
+ */
+export const listWorkspaceProfiles = async (): Promise<WorkspaceProfile[]> => {
+  return await invoke<WorkspaceProfile[]>('list_workspace_profiles');
+};
+
+/**
+ * Create a workspace profile
+ */
+export const createWorkspaceProfile = async (input: WorkspaceProfileInput): Promise<WorkspaceProfile> => {
+  return await invoke<WorkspaceProfile>('create_workspace_profile', { input });
+};
+
+/**
+ * Update a workspace profile (does not apply it)
+ */
+export const updateWorkspaceProfile = async (
+  id: string,
+  input: WorkspaceProfileInput,
+): Promise<WorkspaceProfile> => {
+  return await invoke<WorkspaceProfile>('update_workspace_profile', { id, input });
+};
+
+/**
+ * Delete a workspace profile
+ */
+export const deleteWorkspaceProfile = async (id: string): Promise<void> => {
+  await invoke('delete_workspace_profile', { id });
+};
+
+/**
+ * Reorder workspace profiles
+ */
+export const reorderWorkspaceProfiles = async (ids: string[]): Promise<void> => {
+  await invoke('reorder_workspace_profiles', { ids });
+};
+
+/**
+ * Read the current selection of every tool
+ */
+export const captureWorkspaceSelection = async (): Promise<WorkspaceProfileSelection> => {
+  return await invoke<WorkspaceProfileSelection>('capture_workspace_selection');
+};
+
+/**
+ * Apply a workspace profile to all tools; written files are rolled back if any step fails
+ */
+export const applyWorkspaceProfile = async (profileId: string): Promise<void> => {
+  await withConfigValidation((force) => invoke('apply_workspace_profile', { profileId, force }));
+};
@@ -11,6 +11,7 @@ import type {
   WSLSyncConfig,
   WSLStatusResult,
   FileMapping,
+  SyncPreview,
   SyncResult,
 } from '@/types/wslsync';
 
@@ -55,6 +55,7 @@ interface SettingsState {
   autoBackupIntervalDays: number;
   autoBackupMaxKeep: number;
   lastAutoBackupTime: string | null;
+  backupPassphrase: string;
 
   // Update settings
   autoCheckUpdate: boolean;
@@ -62,6 +63,28 @@ interface SettingsState {
   // Tab visibility settings
   visibleTabs: string[];
 
+  // Localhost control API
+  controlApiEnabled: boolean;
+
+  // Provider health monitor
+  healthMonitorEnabled: boolean;
+  healthMonitorIntervalMinutes: number;
+  healthMonitorFailureThreshold: number;
+  healthMonitorAutoFailover: boolean;
+
+  // Local LLM gateway
+  llmGatewayEnabled: boolean;
+  llmGatewayPort: number;
+
+  // All API Hub balance monitor
+  balanceMonitorEnabled: boolean;
+  balanceMonitorIntervalMinutes: number;
+  balanceAlertThresholdUsd: number;
+
+  // Config file write history retention
+  configHistoryMaxPerFile: number;
+  configHistoryMaxAgeDays: number;
+
   // Actions
   initSettings: () => Promise<void>;
   setBackupSettings: (config: {
@@ -81,8 +104,23 @@ interface SettingsState {
     maxKeep: number;
   }) => Promise<void>;
   setLastAutoBackupTime: (time: string) => void;
+  setBackupPassphrase: (passphrase: string) => Promise<void>;
   setAutoCheckUpdate: (enabled: boolean) => Promise<void>;
   setVisibleTabs: (tabs: string[]) => Promise<void>;
+  setControlApiEnabled: (enabled: boolean) => Promise<void>;
+  setHealthMonitorSettings: (config: {
+    enabled: boolean;
+    intervalMinutes: number;
+    failureThreshold: number;
+    autoFailover: boolean;
+  }) => Promise<void>;
+  setLlmGatewaySettings: (config: { enabled: boolean; port: number }) => Promise<void>;
+  setBalanceMonitorSettings: (config: {
+    enabled: boolean;
+    intervalMinutes: number;
+    thresholdUsd: number;
+  }) => Promise<void>;
+  setConfigHistoryRetention: (config: { maxPerFile: number; maxAgeDays: number }) => Promise<void>;
 }
 
 // Convert backend snake_case to frontend camelCase
@@ -160,8 +198,21 @@ export const useSettingsStore = create<SettingsState>()((set, get) => ({
   autoBackupIntervalDays: 7,
   autoBackupMaxKeep: 10,
   lastAutoBackupTime: null,
+  backupPassphrase: '',
   autoCheckUpdate: true,
-  visibleTabs: ['opencode', 'claudecode', 'codex', 'openclaw', 'ssh', 'wsl'],
+  visibleTabs: ['opencode', 'claudecode', 'codex', 'geminicli', 'openclaw', 'ssh', 'wsl'],
+  controlApiEnabled: false,
+  healthMonitorEnabled: false,
+  healthMonitorIntervalMinutes: 5,
+  healthMonitorFailureThreshold: 3,
+  healthMonitorAutoFailover: false,
+  llmGatewayEnabled: false,
+  llmGatewayPort: 15721,
+  balanceMonitorEnabled: false,
+  balanceMonitorIntervalMinutes: 60,
+  balanceAlertThresholdUsd: 5,
+  configHistoryMaxPerFile: 20,
+  configHistoryMaxAgeDays: 30,
 
   initSettings: async () => {
     if (get().isInitialized) return;
@@ -183,8 +234,21 @@ export const useSettingsStore = create<SettingsState>()((set, get) => ({
         autoBackupIntervalDays: settings.auto_backup_interval_days ?? 7,
         autoBackupMaxKeep: settings.auto_backup_max_keep ?? 10,
         lastAutoBackupTime: settings.last_auto_backup_time ?? null,
+        backupPassphrase: settings.backup_passphrase ?? '',
         autoCheckUpdate: settings.auto_check_update ?? true,
-        visibleTabs: settings.visible_tabs ?? ['opencode', 'claudecode', 'codex', 'openclaw', 'ssh', 'wsl'],
+        visibleTabs: settings.visible_tabs ?? ['opencode', 'claudecode', 'codex', 'geminicli', 'openclaw', 'ssh', 'wsl'],
+        controlApiEnabled: settings.control_api_enabled ?? false,
+        healthMonitorEnabled: settings.health_monitor_enabled ?? false,
+        healthMonitorIntervalMinutes: settings.health_monitor_interval_minutes ?? 5,
+        healthMonitorFailureThreshold: settings.health_monitor_failure_threshold ?? 3,
+        healthMonitorAutoFailover: settings.health_monitor_auto_failover ?? false,
+        llmGatewayEnabled: settings.llm_gateway_enabled ?? false,
+        llmGatewayPort: settings.llm_gateway_port ?? 15721,
+        balanceMonitorEnabled: settings.balance_monitor_enabled ?? false,
+        balanceMonitorIntervalMinutes: settings.balance_monitor_interval_minutes ?? 60,
+        balanceAlertThresholdUsd: settings.balance_alert_threshold_usd ?? 5,
+        configHistoryMaxPerFile: settings.config_history_max_per_file ?? 20,
+        configHistoryMaxAgeDays: settings.config_history_max_age_days ?? 30,
         isInitialized: true,
       });
     } catch (error) {
@@ -317,6 +381,17 @@ export const useSettingsStore = create<SettingsState>()((set, get) => ({
     set({ lastAutoBackupTime: time });
   },
 
+  setBackupPassphrase: async (passphrase) => {
+    set({ backupPassphrase: passphrase });
+
+    const currentSettings = await getSettings();
+    const newSettings: AppSettings = {
+      ...currentSettings,
+      backup_passphrase: passphrase,
+    };
+    await saveSettings(newSettings);
+  },
+
   setAutoCheckUpdate: async (enabled) => {
     set({ autoCheckUpdate: enabled });
 
@@ -340,4 +415,82 @@ export const useSettingsStore = create<SettingsState>()((set, get) => ({
     };
     await saveSettings(newSettings);
   },
+
+  setControlApiEnabled: async (enabled) => {
+    set({ controlApiEnabled: enabled });
+
+    // Update database (the backend starts or stops the server on save)
+    const currentSettings = await getSettings();
+    const newSettings: AppSettings = {
+      ...currentSettings,
+      control_api_enabled: enabled,
+    };
+    await saveSettings(newSettings);
+  },
+
+  setHealthMonitorSettings: async (config) => {
+    set({
+      healthMonitorEnabled: config.enabled,
+      healthMonitorIntervalMinutes: config.intervalMinutes,
+      healthMonitorFailureThreshold: config.failureThreshold,
+      healthMonitorAutoFailover: config.autoFailover,
+    });
+
+    const currentSettings = await getSettings();
+    const newSettings: AppSettings = {
+      ...currentSettings,
+      health_monitor_enabled: config.enabled,
+      health_monitor_interval_minutes: config.intervalMinutes,
+      health_monitor_failure_threshold: config.failureThreshold,
+      health_monitor_auto_failover: config.autoFailover,
+    };
+    await saveSettings(newSettings);
+  },
+
+  setLlmGatewaySettings: async (config) => {
+    set({ llmGatewayEnabled: config.enabled, llmGatewayPort: config.port });
+
+    // Update database (the backend generates the token, restarts the gateway
+    // and rewrites the Claude Code / Codex configs on save)
+    const currentSettings = await getSettings();
+    const newSettings: AppSettings = {
+      ...currentSettings,
+      llm_gateway_enabled: config.enabled,
+      llm_gateway_port: config.port,
+    };
+    await saveSettings(newSettings);
+  },
+
+  setBalanceMonitorSettings: async (config) => {
+    set({
+      balanceMonitorEnabled: config.enabled,
+      balanceMonitorIntervalMinutes: config.intervalMinutes,
+      balanceAlertThresholdUsd: config.thresholdUsd,
+    });
+
+    const currentSettings = await getSettings();
+    const newSettings: AppSettings = {
+      ...currentSettings,
+      balance_monitor_enabled: config.enabled,
+      balance_monitor_interval_minutes: config.intervalMinutes,
+      balance_alert_threshold_usd: config.thresholdUsd,
+    };
+    await saveSettings(newSettings);
+  },
+
+  setConfigHistoryRetention: async (config) => {
+    set({
+      configHistoryMaxPerFile: config.maxPerFile,
+      configHistoryMaxAgeDays: config.maxAgeDays,
+    });
+
+    // The backend prunes existing snapshots with the new policy on save
+    const currentSettings = await getSettings();
+    const newSettings: AppSettings = {
+      ...currentSettings,
+      config_history_max_per_file: config.maxPerFile,
+      config_history_max_age_days: config.maxAgeDays,
+    };
+    await saveSettings(newSettings);
+  },
 }));
@@ -6,6 +6,12 @@
 
 export type ClaudeProviderCategory = 'official' | 'third_party' | 'custom';
 
+/**
+ * Upstream API format. 'openai_chat' providers are reached through the local
+ * LLM gateway, which translates Anthropic Messages to OpenAI Chat Completions.
+ */
+export type ClaudeApiFormat = 'anthropic' | 'openai_chat';
+
 /**
  * Claude Code Provider settings configuration
  * Maps to the settings.json env section
@@ -17,6 +23,8 @@ export interface ClaudeSettingsConfig {
     ANTHROPIC_BASE_URL?: string;
     ANTHROPIC_REASONING_MODEL?: string;
   };
+  // 未设置时为 anthropic
+  apiFormat?: ClaudeApiFormat;
   // Model configurations
   model?: string;
   haikuModel?: string;
@@ -83,6 +91,7 @@ export interface ClaudeProviderFormValues {
   category: ClaudeProviderCategory;
   baseUrl?: string;
   apiKey?: string;
+  apiFormat?: ClaudeApiFormat;
   model?: string;
   haikuModel?: string;
   sonnetModel?: string;
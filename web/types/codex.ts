@@ -107,3 +107,39 @@ export interface ImportConflictInfo {
   newProviderName: string;
   sourceProviderId: string;
 }
+
+/**
+ * `[model_providers.<id>]` entry in config.toml
+ */
+export interface CodexModelProviderEntry {
+  id: string;
+  name?: string;
+  baseUrl?: string;
+  wireApi?: string;
+  envKey?: string;
+  experimentalBearerToken?: string;
+  requiresOpenaiAuth?: boolean;
+  queryParams?: Record<string, string>;
+  httpHeaders?: Record<string, string>;
+}
+
+/**
+ * `[profiles.<name>]` entry in config.toml, selected via `codex --profile <name>`
+ */
+export interface CodexProfileEntry {
+  name: string;
+  modelProvider?: string;
+  model?: string;
+  modelReasoningEffort?: string;
+  approvalPolicy?: string;
+  sandboxMode?: string;
+}
+
+/**
+ * Structured view of model_providers / profiles in config.toml
+ */
+export interface CodexProfilesConfig {
+  defaultProfile?: string;
+  modelProviders: CodexModelProviderEntry[];
+  profiles: CodexProfileEntry[];
+}
@@ -0,0 +1,29 @@
+/**
+ * Config Drift Types
+ *
+ * Differences between a managed config file and what the app last applied
+ */
+
+/**
+ * A single key that differs between the expected and the live config
+ */
+export interface DriftEntry {
+  /** Dot separated key path, e.g. "env.ANTHROPIC_MODEL" */
+  key: string;
+  /** Value the applied provider would produce (missing = key only exists in the file) */
+  expected?: unknown;
+  /** Value currently in the file (missing = key was removed from the file) */
+  actual?: unknown;
+}
+
+/**
+ * Drift detection result for one module
+ */
+export interface ConfigDrift {
+  module: string; // "claudecode" | "codex" | "opencode"
+  providerId?: string;
+  providerName?: string;
+  configPath: string;
+  drifted: boolean;
+  entries: DriftEntry[];
+}
@@ -0,0 +1,99 @@
+/**
+ * Gemini CLI Configuration Types
+ *
+ * Type definitions for Gemini CLI configuration management.
+ */
+
+export type GeminiCliProviderCategory = 'official' | 'third_party' | 'custom';
+
+export type GeminiCliAuthType = 'gemini-api-key' | 'vertex-ai';
+
+/**
+ * Gemini CLI Provider settings configuration
+ * env 写入 ~/.gemini/.env，authType / model 写入 ~/.gemini/settings.json
+ */
+export interface GeminiCliSettingsConfig {
+  authType?: GeminiCliAuthType;
+  env?: {
+    GEMINI_API_KEY?: string;
+    GOOGLE_API_KEY?: string;
+    GOOGLE_GEMINI_BASE_URL?: string;
+    GOOGLE_CLOUD_PROJECT?: string;
+    GOOGLE_CLOUD_LOCATION?: string;
+  };
+  model?: string;
+}
+
+/**
+ * Gemini CLI Provider stored in database
+ */
+export interface GeminiCliProvider {
+  id: string;
+  name: string;
+  category: GeminiCliProviderCategory;
+  settingsConfig: string; // JSON string of GeminiCliSettingsConfig
+  sourceProviderId?: string;
+  websiteUrl?: string;
+  notes?: string;
+  icon?: string;
+  iconColor?: string;
+  sortIndex?: number;
+  isApplied?: boolean;
+  isDisabled?: boolean;
+  createdAt: string;
+  updatedAt: string;
+}
+
+/**
+ * Common configuration for all providers
+ */
+export interface GeminiCliCommonConfig {
+  config: string; // JSON fragment of settings.json, `env` goes to .env
+  updatedAt?: string;
+}
+
+/**
+ * Gemini CLI settings from files
+ */
+export interface GeminiCliSettings {
+  settings?: Record<string, unknown>;
+  env?: Record<string, string>;
+}
+
+/**
+ * Form values for creating/editing a provider
+ */
+export interface GeminiCliProviderFormValues {
+  name: string;
+  authType: GeminiCliAuthType;
+  apiKey?: string;
+  baseUrl?: string;
+  model?: string;
+  project?: string;
+  location?: string;
+  notes?: string;
+}
+
+/**
+ * Provider input for saving local config
+ */
+export interface GeminiCliProviderInput {
+  name: string;
+  category: GeminiCliProviderCategory;
+  settingsConfig: string;
+  sourceProviderId?: string;
+  websiteUrl?: string;
+  notes?: string;
+  icon?: string;
+  iconColor?: string;
+  sortIndex?: number;
+  isDisabled?: boolean;
+}
+
+/**
+ * Local config save input
+ */
+export interface GeminiCliLocalConfigInput {
+  provider?: GeminiCliProviderInput;
+  commonConfig?: string;
+}
@@ -11,12 +11,40 @@ export interface SSHConnection {
   host: string;
   port: number;
   username: string;
-  authMethod: string; // "key" | "password"
+  authMethod: string; // "key" | "password" | "agent"
   password: string;
   privateKeyPath: string;
   privateKeyContent: string;
   passphrase: string;
   sortOrder: number;
+  jumpHosts: SSHJumpHost[];
+}
+
+/**
+ * Jump host (bastion) traversed before the target, like OpenSSH ProxyJump
+ */
+export interface SSHJumpHost {
+  host: string;
+  port: number;
+  username: string; // empty = same as the target connection
+  authMethod: string; // "key" | "password" | "agent"
+  password: string;
+  privateKeyPath: string;
+  privateKeyContent: string;
+  passphrase: string;
+}
+
+/**
+ * Host alias from ~/.ssh/config, resolved like `ssh -G`
+ */
+export interface SSHConfigHost {
+  alias: string;
+  hostName: string;
+  port: number;
+  user: string;
+  identityFile: string; // empty = ssh-agent
+  proxyJump: string[];
+  imported: boolean;
 }
 
 /**
@@ -31,6 +59,7 @@ export interface SSHFileMapping {
   enabled: boolean;
   isPattern: boolean;
   isDirectory: boolean;
+  direction: string; // "push" | "pull" | "both"
 }
 
 /**
@@ -39,11 +68,25 @@ export interface SSHFileMapping {
 export interface SSHSyncConfig {
   enabled: boolean;
   activeConnectionId: string;
+  targetConnectionIds: string[]; // other connections that receive the same sync
+  autoSyncOnChange: boolean; // sync a mapping automatically when its local file changes
   fileMappings: SSHFileMapping[];
   connections: SSHConnection[];
   lastSyncTime?: string;
   lastSyncStatus: string; // "success" | "error" | "never"
   lastSyncError?: string;
+  hostStatus: SSHHostSyncStatus[];
+}
+
+/**
+ * Last sync status of a single target connection
+ */
+export interface SSHHostSyncStatus {
+  connectionId: string;
+  connectionName: string;
+  lastSyncTime?: string;
+  lastSyncStatus: string; // "success" | "error"
+  lastSyncError?: string;
 }
 
 /**
@@ -53,6 +96,34 @@ export interface SSHConnectionResult {
   connected: boolean;
   error?: string;
   serverInfo?: string;
+  hostKeyError?: SSHHostKeyError;
+}
+
+/**
+ * Host key recorded for a connection on first connect
+ */
+export interface SSHKnownHost {
+  connectionId: string;
+  host: string;
+  port: number;
+  keyType: string;
+  fingerprint: string;
+  publicKey: string;
+  firstSeen: string;
+}
+
+/**
+ * Host key verification failure
+ */
+export interface SSHHostKeyError {
+  kind: string; // "changed" | "revoked"
+  host: string;
+  port: number;
+  keyType: string;
+  fingerprint: string;
+  expectedFingerprint?: string;
+  source: string; // "app" | "known_hosts"
+  line?: number;
 }
 
 /**
@@ -64,6 +135,8 @@ export interface SSHStatusResult {
   lastSyncTime?: string;
   lastSyncStatus: string;
   lastSyncError?: string;
+  targetCount: number; // active + other targets
+  hostStatus: SSHHostSyncStatus[];
 }
 
 /**
@@ -74,8 +147,36 @@ export interface SyncResult {
   syncedFiles: string[];
   skippedFiles: string[];
   errors: string[];
+  conflicts: SyncConflict[];
+  hosts?: HostSyncResult[]; // per-connection results when syncing several targets
 }
 
+/**
+ * Result of syncing a single target connection
+ */
+export interface HostSyncResult {
+  connectionId: string;
+  connectionName: string;
+  result: SyncResult;
+}
+
+/**
+ * File changed both locally and remotely since the last two-way sync
+ */
+export interface SyncConflict {
+  mappingId: string;
+  mappingName: string;
+  localPath: string;
+  remotePath: string;
+  localExists: boolean;
+  remoteExists: boolean;
+}
+
+/**
+ * Dry-run sync preview (reuse from WSL)
+ */
+export type { FileChangePreview, McpChangePreview, SkillsChangePreview, SyncPreview, SyncWatcherEvent } from './wslsync';
+
 /**
  * Sync progress event payload (reuse from WSL)
  */
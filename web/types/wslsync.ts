@@ -26,6 +26,8 @@ export interface WSLSyncConfig {
   syncMcp: boolean;
   /** Sync Skills to WSL (default: true) */
   syncSkills: boolean;
+  /** Sync a mapping automatically when its Windows-side file changes (default: false) */
+  autoSyncOnChange: boolean;
   fileMappings: FileMapping[];
   lastSyncTime?: string;
   lastSyncStatus: string; // "success" | "error" | "never"
@@ -42,6 +44,50 @@ export interface SyncResult {
   errors: string[];
 }
 
+/**
+ * A single file (or symlink) that a sync would touch
+ */
+export interface FileChangePreview {
+  mappingId: string;
+  mappingName: string;
+  action: string; // "create" | "update" | "unchanged" | "delete" | "delete_symlink" | "conflict"
+  direction: string; // "push" | "pull" | "both"
+  sourcePath: string;
+  targetPath: string;
+  diff?: string;
+}
+
+/**
+ * MCP servers that would be written to ~/.claude.json
+ */
+export interface McpChangePreview {
+  added: string[];
+  removed: string[];
+  updated: string[];
+  diff?: string;
+}
+
+/**
+ * Skill directories and tool symlinks that a skills sync would change
+ */
+export interface SkillsChangePreview {
+  added: string[];
+  updated: string[];
+  removed: string[];
+  links: FileChangePreview[];
+}
+
+/**
+ * Dry-run result of a sync: what would change, nothing is written
+ */
+export interface SyncPreview {
+  files: FileChangePreview[];
+  skippedFiles: string[];
+  mcp?: McpChangePreview;
+  skills?: SkillsChangePreview;
+  errors: string[];
+}
+
 /**
  * WSL detection result
  */
@@ -84,3 +130,11 @@ export interface SyncProgress {
   /** Overall progress message */
   message: string;
 }
+
+/**
+ * Local file watcher triggered an incremental sync ("sync-watcher-triggered" event)
+ */
+export interface SyncWatcherEvent {
+  target: string; // "ssh" | "wsl"
+  mappings: string[]; // names of the changed mappings
+}
@@ -0,0 +1,101 @@
+/**
+ * 生成的工具配置校验结果处理
+ *
+ * 后端在写入 settings.json / config.toml / opencode.json 等文件前会按内置 schema 校验，
+ * 有错误时返回 `CONFIG_VALIDATION_FAILED:<report json>`；写入后仍有警告时发送
+ * `config-validation-warning` 事件。
+ */
+
+import React from 'react';
+import { Modal, Typography } from 'antd';
+import i18n from '@/i18n';
+
+export const CONFIG_VALIDATION_FAILED = 'CONFIG_VALIDATION_FAILED';
+
+export interface ConfigValidationIssue {
+  path: string;
+  message: string;
+  severity: 'error' | 'warning';
+}
+
+export interface ConfigValidationReport {
+  /** 目标文件名，如 settings.json */
+  target: string;
+  /** 是否由用户强制写入 */
+  forced: boolean;
+  issues: ConfigValidationIssue[];
+}
+
+/** 最多列出的问题条数 */
+const MAX_LISTED_ISSUES = 8;
+
+/**
+ * 从命令错误中解析校验报告，不是校验错误时返回 null
+ */
+export const parseConfigValidationError = (error: unknown): ConfigValidationReport | null => {
+  const message = String(error);
+  const index = message.indexOf(`${CONFIG_VALIDATION_FAILED}:`);
+  if (index < 0) return null;
+  try {
+    return JSON.parse(message.slice(index + CONFIG_VALIDATION_FAILED.length + 1)) as ConfigValidationReport;
+  } catch {
+    return null;
+  }
+};
+
+/**
+ * 校验问题列表，供确认框和通知使用
+ */
+export const ConfigValidationIssueList: React.FC<{ issues: ConfigValidationIssue[] }> = ({ issues }) => (
+  <ul style={{ margin: 0, paddingInlineStart: 18 }}>
+    {issues.slice(0, MAX_LISTED_ISSUES).map((issue, idx) => (
+      <li key={idx}>
+        <Typography.Text code>{issue.path || '/'}</Typography.Text>{' '}
+        <Typography.Text type={issue.severity === 'error' ? 'danger' : 'warning'}>{issue.message}</Typography.Text>
+      </li>
+    ))}
+    {issues.length > MAX_LISTED_ISSUES && (
+      <li>{i18n.t('configValidation.more', { count: issues.length - MAX_LISTED_ISSUES })}</li>
+    )}
+  </ul>
+);
+
+/**
+ * 执行写配置的命令；后端校验出错误时列出问题，用户确认后以 force=true 重试
+ */
+export const withConfigValidation = async <T,>(run: (force: boolean) => Promise<T>): Promise<T> => {
+  try {
+    return await run(false);
+  } catch (error) {
+    const report = parseConfigValidationError(error);
+    if (!report) throw error;
+
+    const confirmed = await new Promise<boolean>((resolve) => {
+      Modal.confirm({
+        title: i18n.t('configValidation.errorTitle', { target: report.target }),
+        content: (
+          <>
+            <Typography.Paragraph type="secondary">
+              {i18n.t('configValidation.errorDescription')}
+            </Typography.Paragraph>
+            <ConfigValidationIssueList issues={report.issues} />
+          </>
+        ),
+        width: 560,
+        okText: i18n.t('configValidation.writeAnyway'),
+        okButtonProps: { danger: true },
+        cancelText: i18n.t('common.cancel'),
+        onOk: () => resolve(true),
+        onCancel: () => resolve(false),
+      });
+    });
+
+    if (!confirmed) {
+      const errors = report.issues.filter((issue) => issue.severity === 'error');
+      throw new Error(
+        i18n.t('configValidation.notWritten', { target: report.target, count: errors.length }),
+      );
+    }
+    return await run(true);
+  }
+};